@@ -0,0 +1,129 @@
+use std::io;
+use std::path::PathBuf;
+
+use clap::ArgMatches;
+use roc_build::program::DEFAULT_ROC_FILENAME;
+use roc_docs::api_diff::{diff, Api, ApiChange, Change};
+use roc_docs::try_load_module_for_docs;
+
+use crate::{NEW_ROC_FILE, OLD_ROC_FILE};
+
+/// Implements `roc diff-api`: compare what two versions of a package expose, and report which
+/// changes would break code that uses the old version. Exits with 1 if anything is breaking,
+/// so this can be used in CI before tagging a release, and with 2 if either version can't be
+/// loaded, so that CI can tell a package which doesn't compile apart from a breaking change.
+pub fn diff_api(matches: &ArgMatches) -> io::Result<i32> {
+    let old_path = root_file(PathBuf::from(matches.value_of_os(OLD_ROC_FILE).unwrap()));
+    let new_path = root_file(PathBuf::from(matches.value_of_os(NEW_ROC_FILE).unwrap()));
+
+    for path in [&old_path, &new_path] {
+        if !path.exists() {
+            eprintln!(
+                "\nThis file was not found: {}\n\nYou can run `roc help diff-api` for more information on how to provide a .roc file.\n",
+                path.display()
+            );
+
+            return Ok(LOAD_FAILED);
+        }
+    }
+
+    let old_api = match try_load_module_for_docs(old_path) {
+        Ok(loaded) => Api::from_loaded(&loaded),
+        Err(report) => return load_failed(&report),
+    };
+    let new_api = match try_load_module_for_docs(new_path) {
+        Ok(loaded) => Api::from_loaded(&loaded),
+        Err(report) => return load_failed(&report),
+    };
+    let changes = diff(&old_api, &new_api);
+
+    if changes.is_empty() {
+        println!("\nThe exposed API did not change.\n");
+
+        return Ok(0);
+    }
+
+    let (breaking, rest): (Vec<&ApiChange>, Vec<&ApiChange>) =
+        changes.iter().partition(|change| change.is_breaking());
+    let (additive, notes): (Vec<&ApiChange>, Vec<&ApiChange>) =
+        rest.into_iter().partition(|change| change.is_additive());
+
+    if !breaking.is_empty() {
+        println!("\n\x1B[31mBreaking changes:\x1B[39m\n");
+
+        for change in breaking.iter() {
+            print_change(change);
+        }
+    }
+
+    if !additive.is_empty() {
+        println!("\n\x1B[32mAdditive changes:\x1B[39m\n");
+
+        for change in additive.iter() {
+            print_change(change);
+        }
+    }
+
+    if !notes.is_empty() {
+        println!("\n\x1B[33mCould not compare (missing type annotations):\x1B[39m\n");
+
+        for change in notes.iter() {
+            print_change(change);
+        }
+    }
+
+    println!(
+        "\n{} breaking, {} additive, {} not compared\n",
+        breaking.len(),
+        additive.len(),
+        notes.len()
+    );
+
+    Ok(if breaking.is_empty() { 0 } else { 1 })
+}
+
+/// The exit code for when a version couldn't be loaded. This isn't 1, which means that something
+/// is breaking.
+const LOAD_FAILED: i32 = 2;
+
+fn load_failed(report: &str) -> io::Result<i32> {
+    eprintln!("{}", report);
+
+    Ok(LOAD_FAILED)
+}
+
+/// A package directory stands for its main.roc
+fn root_file(path: PathBuf) -> PathBuf {
+    if path.is_dir() {
+        path.join(DEFAULT_ROC_FILENAME)
+    } else {
+        path
+    }
+}
+
+fn print_change(api_change: &ApiChange) {
+    let name = api_change.qualified_name();
+
+    match &api_change.change {
+        Change::ModuleAdded => println!("    + module {}", name),
+        Change::ModuleRemoved => println!("    - module {}", name),
+        Change::Added(description) => {
+            println!("    + {}", qualify(&api_change.module_name, description))
+        }
+        Change::Removed(description) => {
+            println!("    - {}", qualify(&api_change.module_name, description))
+        }
+        Change::Changed { old, new } => {
+            println!("    ~ {}", name);
+            println!("        was: {}", old);
+            println!("        now: {}", new);
+        }
+        Change::Unannotated(description) => {
+            println!("    ? {}", qualify(&api_change.module_name, description))
+        }
+    }
+}
+
+fn qualify(module_name: &str, description: &str) -> String {
+    format!("{}.{}", module_name, description)
+}
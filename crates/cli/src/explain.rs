@@ -0,0 +1,38 @@
+use std::io;
+
+use clap::ArgMatches;
+use roc_reporting::report::ErrorCode;
+
+use crate::ERROR_CODE;
+
+/// Implements `roc explain`: print the longer explanation for an error code from a report
+/// header, or list every code when none is given.
+pub fn explain(matches: &ArgMatches) -> io::Result<i32> {
+    let code = match matches.value_of(ERROR_CODE) {
+        Some(code) => code,
+        None => {
+            for code in ErrorCode::ALL {
+                println!("{}  {}", code, code.name());
+            }
+
+            return Ok(0);
+        }
+    };
+
+    match ErrorCode::lookup(code) {
+        Some(code) => {
+            println!("{} {}\n", code, code.name());
+            print!("{}", code.explanation());
+
+            Ok(0)
+        }
+        None => {
+            eprintln!(
+                "\nThere is no error code {:?}.\n\nRun `roc explain` without arguments to list every code.\n",
+                code
+            );
+
+            Ok(1)
+        }
+    }
+}
@@ -0,0 +1,935 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use bumpalo::collections::Vec;
+use bumpalo::Bump;
+use clap::ArgMatches;
+use roc_build::program::handle_loading_problem;
+use roc_error_macros::user_error;
+use roc_fmt::Buf;
+use roc_load::{ExecutionMode, LoadConfig, Threading};
+use roc_module::symbol::Interns;
+use roc_packaging::cache::{self, RocCacheDir};
+use roc_parse::ast::{
+    AssignedField, Collection, CommentOrNewline, Defs, Expr, ExtractSpaces, Header, Module,
+    Pattern, Spaced, TypeDef, ValueDef, WhenBranch,
+};
+use roc_parse::header::{ExposedName, ImportsEntry, KeywordItem};
+use roc_problem::can::Problem;
+use roc_region::all::{LineInfo, Loc, Region};
+use roc_reporting::report::{ErrorCode, RenderTarget, DEFAULT_PALETTE};
+use roc_reporting::warnings::SourceAllows;
+use roc_target::TargetInfo;
+use similar::TextDiff;
+
+use crate::format::{fmt_all, parse_all};
+use crate::{FLAG_DRY_RUN, FLAG_MAX_THREADS, ROC_FILE};
+
+/// Implements `roc fix`: remove unused imports and defs, prefix unused arguments with `_`, and
+/// remove names a module exposes but doesn't define. The fixes are made to the parsed modules,
+/// which are then formatted, so the files stay formatted. With --dry-run, print a diff instead.
+pub fn fix(matches: &ArgMatches) -> io::Result<i32> {
+    let path = PathBuf::from(matches.value_of_os(ROC_FILE).unwrap());
+    let dry_run = matches.is_present(FLAG_DRY_RUN);
+
+    if !path.exists() {
+        eprintln!(
+            "\nThis file was not found: {}\n\nYou can run `roc help fix` for more information on how to provide a .roc file.\n",
+            path.display()
+        );
+
+        return Ok(1);
+    }
+
+    let threading = match matches
+        .value_of(FLAG_MAX_THREADS)
+        .and_then(|s| s.parse::<usize>().ok())
+    {
+        None => Threading::AllAvailable,
+        Some(0) => user_error!("cannot build with at most 0 threads"),
+        Some(1) => Threading::Single,
+        Some(n) => Threading::AtMost(n),
+    };
+
+    fix_file(
+        &path,
+        dry_run,
+        threading,
+        RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
+    )
+}
+
+/// Fix the module at `path` and the other modules of the project it imports
+fn fix_file(
+    path: &Path,
+    dry_run: bool,
+    threading: Threading,
+    roc_cache_dir: RocCacheDir<'_>,
+) -> io::Result<i32> {
+    let arena = Bump::new();
+    let load_config = LoadConfig {
+        // only used for generating errors; we don't do code generation
+        target_info: TargetInfo::default_x86_64(),
+        render: RenderTarget::ColorTerminal,
+        palette: DEFAULT_PALETTE,
+        threading,
+        exec_mode: ExecutionMode::Check,
+    };
+    let loaded = match roc_load::load_and_typecheck(
+        &arena,
+        path.to_path_buf(),
+        roc_cache_dir,
+        load_config,
+    ) {
+        Ok(loaded) => loaded,
+        Err(problem) => return handle_loading_problem(problem, RenderTarget::ColorTerminal),
+    };
+
+    // Only touch the project's own modules, not the packages it downloaded.
+    let project_dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    }
+    .canonicalize()?;
+
+    let mut modules: std::vec::Vec<_> = loaded
+        .sources
+        .iter()
+        .filter(|(_, (module_path, _))| match module_path.canonicalize() {
+            Ok(module_path) => module_path.starts_with(&project_dir),
+            Err(_) => false,
+        })
+        .collect();
+
+    modules.sort_by(|(_, (a, _)), (_, (b, _))| a.cmp(b));
+
+    let mut total_fixed = 0;
+    let mut files_fixed = 0;
+
+    for (module_id, (module_path, src)) in modules {
+        let problems = match loaded.can_problems.get(module_id) {
+            Some(problems) => problems.as_slice(),
+            None => continue,
+        };
+        let fixes = Fixes::new(problems, src, &loaded.interns);
+
+        if fixes.is_empty() {
+            continue;
+        }
+
+        let arena = Bump::new();
+        let (fixed_src, fixed) = match fix_module(&arena, src, &fixes) {
+            Some(result) => result,
+            None => continue,
+        };
+
+        if fixed == 0 || fixed_src == src.as_ref() {
+            continue;
+        }
+
+        total_fixed += fixed;
+        files_fixed += 1;
+
+        if dry_run {
+            let old_name = format!("a/{}", module_path.display());
+            let new_name = format!("b/{}", module_path.display());
+
+            print!(
+                "{}",
+                TextDiff::from_lines(src.as_ref(), fixed_src.as_str())
+                    .unified_diff()
+                    .header(&old_name, &new_name)
+            );
+        } else {
+            fs::write(module_path, fixed_src)?;
+
+            println!(
+                "Fixed {} {} in {}",
+                fixed,
+                problems_str(fixed),
+                module_path.display()
+            );
+        }
+    }
+
+    if total_fixed == 0 {
+        println!("\nThere was nothing to fix.\n");
+
+        Ok(0)
+    } else if dry_run {
+        println!(
+            "\n{} {} would be fixed in {} {}.\n",
+            total_fixed,
+            problems_str(total_fixed),
+            files_fixed,
+            files_str(files_fixed)
+        );
+
+        // Like `roc format --check`, so CI can require that there's nothing left to fix
+        Ok(1)
+    } else {
+        println!(
+            "\nFixed {} {} in {} {}. Fixes can leave more things unused, so you may want to run `roc fix` again.\n",
+            total_fixed,
+            problems_str(total_fixed),
+            files_fixed,
+            files_str(files_fixed)
+        );
+
+        Ok(0)
+    }
+}
+
+fn problems_str(count: usize) -> &'static str {
+    if count == 1 {
+        "problem"
+    } else {
+        "problems"
+    }
+}
+
+fn files_str(count: usize) -> &'static str {
+    if count == 1 {
+        "file"
+    } else {
+        "files"
+    }
+}
+
+/// The problems in one module that `roc fix` knows how to fix
+#[derive(Debug, Default)]
+struct Fixes {
+    /// Regions of whole `imports` entries
+    unused_modules: std::vec::Vec<Region>,
+    /// Regions of the values and types exposed by `imports` entries
+    unused_imports: std::vec::Vec<Region>,
+    /// Regions and names of defs, both for values and types
+    unused_defs: std::vec::Vec<(Region, String)>,
+    unused_arguments: std::vec::Vec<Region>,
+    exposed_but_not_defined: std::vec::Vec<String>,
+}
+
+impl Fixes {
+    fn new(problems: &[Problem], src: &str, interns: &Interns) -> Self {
+        let lines = LineInfo::new(src);
+        let allows = SourceAllows::new(src);
+        let mut fixes = Fixes::default();
+
+        for problem in problems {
+            // Warnings silenced with a `# roc:allow` comment are there on purpose.
+            if let Some(region) = problem.region() {
+                let line = lines.convert_region(region).start.line;
+
+                if allows.allows(line, ErrorCode::of_can_problem(problem)) {
+                    continue;
+                }
+            }
+
+            match problem {
+                Problem::UnusedModuleImport(_, region) => fixes.unused_modules.push(*region),
+                Problem::UnusedImport(_, region) => fixes.unused_imports.push(*region),
+                Problem::UnusedDef(symbol, region) => fixes
+                    .unused_defs
+                    .push((*region, symbol.as_str(interns).to_string())),
+                Problem::UnusedArgument(_, _, _, region) => fixes.unused_arguments.push(*region),
+                Problem::ExposedButNotDefined(symbol) => fixes
+                    .exposed_but_not_defined
+                    .push(symbol.as_str(interns).to_string()),
+                _ => {}
+            }
+        }
+
+        fixes
+    }
+
+    fn is_empty(&self) -> bool {
+        self.unused_modules.is_empty()
+            && self.unused_imports.is_empty()
+            && self.unused_defs.is_empty()
+            && self.unused_arguments.is_empty()
+            && self.exposed_but_not_defined.is_empty()
+    }
+}
+
+/// The fixed and formatted source, and how many problems were fixed.
+/// `None` if the module doesn't parse, which the compiler will already have reported.
+fn fix_module(arena: &Bump, src: &str, fixes: &Fixes) -> Option<(String, usize)> {
+    let src = arena.alloc_str(src);
+    let ast = parse_all(arena, src).ok()?;
+    let mut fixer = Fixer {
+        arena,
+        fixes,
+        fixed: 0,
+    };
+
+    let ast = arena.alloc(roc_fmt::Ast {
+        module: Module {
+            comments: ast.module.comments,
+            header: fixer.header(&ast.module.header),
+        },
+        defs: fixer.defs(&ast.defs),
+    });
+
+    let mut buf = Buf::new_in(arena);
+    fmt_all(&mut buf, ast);
+
+    Some((buf.as_str().to_string(), fixer.fixed))
+}
+
+/// Rebuilds a module's AST without the problems in `fixes`, counting each one it fixes
+struct Fixer<'a, 'f> {
+    arena: &'a Bump,
+    fixes: &'f Fixes,
+    fixed: usize,
+}
+
+impl<'a, 'f> Fixer<'a, 'f> {
+    fn header(&mut self, header: &Header<'a>) -> Header<'a> {
+        match header {
+            Header::Interface(header) => {
+                let mut header = header.clone();
+
+                header.exposes = self.exposes(&header.exposes);
+                header.imports = self.imports(&header.imports);
+
+                Header::Interface(header)
+            }
+            Header::Hosted(header) => {
+                let mut header = header.clone();
+
+                header.exposes = self.exposes(&header.exposes);
+                header.imports = self.imports(&header.imports);
+
+                Header::Hosted(header)
+            }
+            Header::App(header) => {
+                let mut header = header.clone();
+
+                header.imports = header.imports.map(|imports| self.imports(&imports));
+
+                Header::App(header)
+            }
+            Header::Platform(header) => {
+                let mut header = header.clone();
+
+                header.imports = self.imports(&header.imports);
+
+                Header::Platform(header)
+            }
+            Header::Package(_) => header.clone(),
+        }
+    }
+
+    fn exposes<K: Clone>(
+        &mut self,
+        exposes: &KeywordItem<'a, K, Collection<'a, Loc<Spaced<'a, ExposedName<'a>>>>>,
+    ) -> KeywordItem<'a, K, Collection<'a, Loc<Spaced<'a, ExposedName<'a>>>>> {
+        let not_defined = &self.fixes.exposed_but_not_defined;
+        let mut kept = Vec::with_capacity_in(exposes.item.len(), self.arena);
+
+        for loc_name in exposes.item.iter() {
+            let name: &str = loc_name.value.extract_spaces().item.into();
+
+            if not_defined.iter().any(|not_defined| not_defined == name) {
+                self.fixed += 1;
+            } else {
+                kept.push(*loc_name);
+            }
+        }
+
+        KeywordItem {
+            keyword: exposes.keyword.clone(),
+            item: exposes.item.replace_items(kept.into_bump_slice()),
+        }
+    }
+
+    fn imports<K: Clone>(
+        &mut self,
+        imports: &KeywordItem<'a, K, Collection<'a, Loc<Spaced<'a, ImportsEntry<'a>>>>>,
+    ) -> KeywordItem<'a, K, Collection<'a, Loc<Spaced<'a, ImportsEntry<'a>>>>> {
+        let mut kept = Vec::with_capacity_in(imports.item.len(), self.arena);
+
+        for loc_entry in imports.item.iter() {
+            if self.fixes.unused_modules.contains(&loc_entry.region) {
+                self.fixed += 1;
+
+                continue;
+            }
+
+            let entry = map_spaced(self.arena, &loc_entry.value, |entry| match entry {
+                ImportsEntry::Module(name, exposed) => {
+                    ImportsEntry::Module(name, self.imported_names(exposed))
+                }
+                ImportsEntry::Package(shorthand, name, exposed) => {
+                    ImportsEntry::Package(shorthand, name, self.imported_names(exposed))
+                }
+                ImportsEntry::IngestedFile(_, _) => entry,
+            });
+
+            kept.push(Loc::at(loc_entry.region, entry));
+        }
+
+        KeywordItem {
+            keyword: imports.keyword.clone(),
+            item: imports.item.replace_items(kept.into_bump_slice()),
+        }
+    }
+
+    fn imported_names(
+        &mut self,
+        names: Collection<'a, Loc<Spaced<'a, ExposedName<'a>>>>,
+    ) -> Collection<'a, Loc<Spaced<'a, ExposedName<'a>>>> {
+        let mut kept = Vec::with_capacity_in(names.len(), self.arena);
+
+        for loc_name in names.iter() {
+            let unused = self.fixes.unused_imports.iter();
+
+            if unused
+                .clone()
+                .any(|region| loc_name.region.contains(region))
+            {
+                self.fixed += 1;
+            } else {
+                kept.push(*loc_name);
+            }
+        }
+
+        names.replace_items(kept.into_bump_slice())
+    }
+
+    fn is_unused_def(&self, region: Region, name: Option<&str>) -> bool {
+        match name {
+            Some(name) => self
+                .fixes
+                .unused_defs
+                .iter()
+                .any(|(unused, unused_name)| region.contains(unused) && unused_name == name),
+            None => false,
+        }
+    }
+
+    fn defs(&mut self, defs: &Defs<'a>) -> Defs<'a> {
+        let mut fixed = Defs::default();
+        // The spaces before the first of a run of removed defs, which the next kept def
+        // takes the newlines of, so that removing a def doesn't leave extra blank lines
+        let mut removed_before: Option<&[CommentOrNewline<'a>]> = None;
+
+        for (index, def) in defs.defs().enumerate() {
+            let region = defs.regions[index];
+            let own_before = &defs.spaces[defs.space_before[index].indices()];
+            let spaces_after = &defs.spaces[defs.space_after[index].indices()];
+            let spaces_before = match removed_before {
+                Some(before) => Vec::from_iter_in(
+                    before
+                        .iter()
+                        .filter(|space| space.is_newline())
+                        .chain(own_before.iter().skip_while(|space| space.is_newline()))
+                        .copied(),
+                    self.arena,
+                )
+                .into_bump_slice(),
+                None => own_before,
+            };
+
+            match def {
+                Ok(type_def) => {
+                    let name = match type_def {
+                        TypeDef::Alias { header, .. }
+                        | TypeDef::Opaque { header, .. }
+                        | TypeDef::Ability { header, .. } => header.name.value,
+                    };
+
+                    if self.is_unused_def(region, Some(name)) {
+                        self.fixed += 1;
+                        removed_before.get_or_insert(own_before);
+                    } else {
+                        fixed.push_type_def(*type_def, region, spaces_before, spaces_after);
+                        removed_before = None;
+                    }
+                }
+                Err(value_def) => {
+                    let name = match value_def {
+                        ValueDef::Annotation(pattern, _) => identifier(&pattern.value),
+                        ValueDef::Body(pattern, _)
+                        | ValueDef::AnnotatedBody {
+                            body_pattern: pattern,
+                            ..
+                        } => identifier(&pattern.value),
+                        ValueDef::Dbg { .. }
+                        | ValueDef::Expect { .. }
+                        | ValueDef::ExpectFx { .. } => None,
+                    };
+
+                    if self.is_unused_def(region, name) {
+                        self.fixed += 1;
+                        removed_before.get_or_insert(own_before);
+                    } else {
+                        let value_def = self.value_def(value_def);
+
+                        fixed.push_value_def(value_def, region, spaces_before, spaces_after);
+                        removed_before = None;
+                    }
+                }
+            }
+        }
+
+        fixed
+    }
+
+    fn value_def(&mut self, value_def: &ValueDef<'a>) -> ValueDef<'a> {
+        match *value_def {
+            ValueDef::Annotation(_, _) => *value_def,
+            ValueDef::Body(pattern, body) => ValueDef::Body(pattern, self.loc_expr(body)),
+            ValueDef::AnnotatedBody {
+                ann_pattern,
+                ann_type,
+                comment,
+                body_pattern,
+                body_expr,
+            } => ValueDef::AnnotatedBody {
+                ann_pattern,
+                ann_type,
+                comment,
+                body_pattern,
+                body_expr: self.loc_expr(body_expr),
+            },
+            ValueDef::Dbg {
+                condition,
+                preceding_comment,
+            } => ValueDef::Dbg {
+                condition: self.loc_expr(condition),
+                preceding_comment,
+            },
+            ValueDef::Expect {
+                condition,
+                preceding_comment,
+            } => ValueDef::Expect {
+                condition: self.loc_expr(condition),
+                preceding_comment,
+            },
+            ValueDef::ExpectFx {
+                condition,
+                preceding_comment,
+            } => ValueDef::ExpectFx {
+                condition: self.loc_expr(condition),
+                preceding_comment,
+            },
+        }
+    }
+
+    fn loc_expr(&mut self, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc<Expr<'a>> {
+        let expr = self.expr(&loc_expr.value);
+
+        self.arena.alloc(Loc::at(loc_expr.region, expr))
+    }
+
+    fn expr(&mut self, expr: &'a Expr<'a>) -> Expr<'a> {
+        let arena = self.arena;
+
+        match *expr {
+            Expr::RecordAccess(record, field) => {
+                Expr::RecordAccess(arena.alloc(self.expr(record)), field)
+            }
+            Expr::TupleAccess(tuple, index) => {
+                Expr::TupleAccess(arena.alloc(self.expr(tuple)), index)
+            }
+            Expr::List(items) => Expr::List(self.exprs(items)),
+            Expr::Tuple(items) => Expr::Tuple(self.exprs(items)),
+            Expr::RecordUpdate { update, fields } => Expr::RecordUpdate {
+                update: self.loc_expr(update),
+                fields: self.fields(fields),
+            },
+            Expr::Record(fields) => Expr::Record(self.fields(fields)),
+            Expr::Closure(args, body) => Expr::Closure(self.arguments(args), self.loc_expr(body)),
+            Expr::Defs(defs, body) => {
+                let defs = self.defs(defs);
+                let body = self.loc_expr(body);
+
+                if defs.is_empty() {
+                    // Without any defs, the blank line before the body would dangle.
+                    match body.value {
+                        Expr::SpaceBefore(inner, _) => *inner,
+                        other => other,
+                    }
+                } else {
+                    Expr::Defs(arena.alloc(defs), body)
+                }
+            }
+            Expr::Backpassing(args, call, body) => Expr::Backpassing(
+                self.arguments(args),
+                self.loc_expr(call),
+                self.loc_expr(body),
+            ),
+            Expr::Expect(condition, continuation) => {
+                Expr::Expect(self.loc_expr(condition), self.loc_expr(continuation))
+            }
+            Expr::Dbg(condition, continuation) => {
+                Expr::Dbg(self.loc_expr(condition), self.loc_expr(continuation))
+            }
+            Expr::Apply(function, args, called_via) => {
+                let mut fixed_args = Vec::with_capacity_in(args.len(), arena);
+
+                for arg in args.iter() {
+                    fixed_args.push(self.loc_expr(arg));
+                }
+
+                Expr::Apply(
+                    self.loc_expr(function),
+                    fixed_args.into_bump_slice(),
+                    called_via,
+                )
+            }
+            Expr::BinOps(lefts, right) => {
+                let mut fixed_lefts = Vec::with_capacity_in(lefts.len(), arena);
+
+                for (left, op) in lefts.iter() {
+                    fixed_lefts.push((Loc::at(left.region, self.expr(&left.value)), *op));
+                }
+
+                Expr::BinOps(fixed_lefts.into_bump_slice(), self.loc_expr(right))
+            }
+            Expr::UnaryOp(operand, op) => Expr::UnaryOp(self.loc_expr(operand), op),
+            Expr::If(branches, final_else) => {
+                let mut fixed_branches = Vec::with_capacity_in(branches.len(), arena);
+
+                for (condition, then) in branches.iter() {
+                    fixed_branches.push((
+                        Loc::at(condition.region, self.expr(&condition.value)),
+                        Loc::at(then.region, self.expr(&then.value)),
+                    ));
+                }
+
+                Expr::If(fixed_branches.into_bump_slice(), self.loc_expr(final_else))
+            }
+            Expr::When(condition, branches) => {
+                let mut fixed_branches = Vec::with_capacity_in(branches.len(), arena);
+
+                for branch in branches.iter() {
+                    let guard = branch
+                        .guard
+                        .as_ref()
+                        .map(|guard| Loc::at(guard.region, self.expr(&guard.value)));
+
+                    fixed_branches.push(&*arena.alloc(WhenBranch {
+                        patterns: branch.patterns,
+                        value: Loc::at(branch.value.region, self.expr(&branch.value.value)),
+                        guard,
+                    }));
+                }
+
+                Expr::When(self.loc_expr(condition), fixed_branches.into_bump_slice())
+            }
+            Expr::SpaceBefore(inner, spaces) => {
+                Expr::SpaceBefore(arena.alloc(self.expr(inner)), spaces)
+            }
+            Expr::SpaceAfter(inner, spaces) => {
+                Expr::SpaceAfter(arena.alloc(self.expr(inner)), spaces)
+            }
+            Expr::ParensAround(inner) => Expr::ParensAround(arena.alloc(self.expr(inner))),
+            // Nothing to fix in these, including string interpolations, which can't define anything
+            Expr::Float(_)
+            | Expr::Num(_)
+            | Expr::NonBase10Int { .. }
+            | Expr::Str(_)
+            | Expr::SingleQuote(_)
+            | Expr::AccessorFunction(_)
+            | Expr::IngestedFile(_, _)
+            | Expr::Var { .. }
+            | Expr::Underscore(_)
+            | Expr::Crash
+            | Expr::Tag(_)
+            | Expr::OpaqueRef(_)
+            | Expr::MalformedIdent(_, _)
+            | Expr::MalformedClosure
+            | Expr::PrecedenceConflict(_) => *expr,
+        }
+    }
+
+    fn exprs(
+        &mut self,
+        items: Collection<'a, &'a Loc<Expr<'a>>>,
+    ) -> Collection<'a, &'a Loc<Expr<'a>>> {
+        let mut fixed = Vec::with_capacity_in(items.len(), self.arena);
+
+        for item in items.iter() {
+            fixed.push(self.loc_expr(item));
+        }
+
+        items.replace_items(fixed.into_bump_slice())
+    }
+
+    fn fields(
+        &mut self,
+        fields: Collection<'a, Loc<AssignedField<'a, Expr<'a>>>>,
+    ) -> Collection<'a, Loc<AssignedField<'a, Expr<'a>>>> {
+        let mut fixed = Vec::with_capacity_in(fields.len(), self.arena);
+
+        for field in fields.iter() {
+            fixed.push(Loc::at(field.region, self.field(&field.value)));
+        }
+
+        fields.replace_items(fixed.into_bump_slice())
+    }
+
+    fn field(&mut self, field: &'a AssignedField<'a, Expr<'a>>) -> AssignedField<'a, Expr<'a>> {
+        match *field {
+            AssignedField::RequiredValue(label, spaces, value) => {
+                AssignedField::RequiredValue(label, spaces, self.loc_expr(value))
+            }
+            AssignedField::OptionalValue(label, spaces, value) => {
+                AssignedField::OptionalValue(label, spaces, self.loc_expr(value))
+            }
+            AssignedField::SpaceBefore(inner, spaces) => {
+                AssignedField::SpaceBefore(self.arena.alloc(self.field(inner)), spaces)
+            }
+            AssignedField::SpaceAfter(inner, spaces) => {
+                AssignedField::SpaceAfter(self.arena.alloc(self.field(inner)), spaces)
+            }
+            AssignedField::LabelOnly(_) | AssignedField::Malformed(_) => *field,
+        }
+    }
+
+    fn arguments(&mut self, args: &'a [Loc<Pattern<'a>>]) -> &'a [Loc<Pattern<'a>>] {
+        let mut fixed = Vec::with_capacity_in(args.len(), self.arena);
+
+        for arg in args.iter() {
+            fixed.push(self.argument(arg));
+        }
+
+        fixed.into_bump_slice()
+    }
+
+    /// Prefix the unused names in an argument pattern with `_`
+    fn argument(&mut self, arg: &'a Loc<Pattern<'a>>) -> Loc<Pattern<'a>> {
+        let arena = self.arena;
+        let pattern = match arg.value {
+            Pattern::Identifier(name) if self.fixes.unused_arguments.contains(&arg.region) => {
+                self.fixed += 1;
+
+                Pattern::Underscore(name)
+            }
+            Pattern::SpaceBefore(inner, spaces) => {
+                let inner = self.argument(arena.alloc(Loc::at(arg.region, *inner)));
+
+                Pattern::SpaceBefore(arena.alloc(inner.value), spaces)
+            }
+            Pattern::SpaceAfter(inner, spaces) => {
+                let inner = self.argument(arena.alloc(Loc::at(arg.region, *inner)));
+
+                Pattern::SpaceAfter(arena.alloc(inner.value), spaces)
+            }
+            Pattern::Apply(tag, args) => Pattern::Apply(tag, self.arguments(args)),
+            Pattern::Tuple(items) => {
+                Pattern::Tuple(items.replace_items(self.arguments(items.items)))
+            }
+            Pattern::List(items) => Pattern::List(items.replace_items(self.arguments(items.items))),
+            Pattern::As(inner, as_name) => {
+                let inner = self.argument(inner);
+
+                Pattern::As(arena.alloc(inner), as_name)
+            }
+            // Renaming a field in a record destructure would change which field it refers to.
+            _ => arg.value,
+        };
+
+        Loc::at(arg.region, pattern)
+    }
+}
+
+fn identifier<'a>(pattern: &Pattern<'a>) -> Option<&'a str> {
+    match pattern {
+        Pattern::Identifier(name) => Some(name),
+        Pattern::SpaceBefore(inner, _) | Pattern::SpaceAfter(inner, _) => identifier(inner),
+        _ => None,
+    }
+}
+
+fn map_spaced<'a, T: Copy>(
+    arena: &'a Bump,
+    spaced: &Spaced<'a, T>,
+    f: impl FnOnce(T) -> T,
+) -> Spaced<'a, T> {
+    match spaced {
+        Spaced::Item(item) => Spaced::Item(f(*item)),
+        Spaced::SpaceBefore(inner, spaces) => {
+            Spaced::SpaceBefore(arena.alloc(map_spaced(arena, inner, f)), spaces)
+        }
+        Spaced::SpaceAfter(inner, spaces) => {
+            Spaced::SpaceAfter(arena.alloc(map_spaced(arena, inner, f)), spaces)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    /// Write the module to a temp dir as Test.roc and run `roc fix` on it,
+    /// returning the exit code and what the file contains afterwards
+    fn run_fix(src: &str, dry_run: bool) -> (i32, String) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Test.roc");
+
+        fs::write(&path, src).unwrap();
+
+        let exit_code =
+            fix_file(&path, dry_run, Threading::Single, RocCacheDir::Disallowed).unwrap();
+
+        (exit_code, fs::read_to_string(&path).unwrap())
+    }
+
+    fn fixed(src: &str) -> String {
+        let (exit_code, fixed_src) = run_fix(src, false);
+
+        assert_eq!(exit_code, 0);
+
+        fixed_src
+    }
+
+    /// How many problems (warnings included) checking the module finds
+    fn total_problems(src: &str) -> usize {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Test.roc");
+
+        fs::write(&path, src).unwrap();
+
+        let arena = Bump::new();
+        let load_config = LoadConfig {
+            target_info: TargetInfo::default_x86_64(),
+            render: RenderTarget::Generic,
+            palette: DEFAULT_PALETTE,
+            threading: Threading::Single,
+            exec_mode: ExecutionMode::Check,
+        };
+        let loaded =
+            roc_load::load_and_typecheck(&arena, path, RocCacheDir::Disallowed, load_config)
+                .unwrap_or_else(|_| panic!("could not load:\n{}", src));
+
+        loaded.total_problems()
+    }
+
+    #[test]
+    fn removes_unused_defs() {
+        let src = indoc!(
+            r#"
+            interface Test
+                exposes [main]
+                imports []
+
+            main =
+                used = 1
+                unused = 2
+
+                used
+
+            unusedTopLevel = 3
+
+            Unused : Str
+            "#
+        );
+
+        assert_eq!(
+            fixed(src),
+            indoc!(
+                r#"
+                interface Test
+                    exposes [main]
+                    imports []
+
+                main =
+                    used = 1
+
+                    used
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn prefixes_unused_arguments() {
+        let src = indoc!(
+            r#"
+            interface Test
+                exposes [main]
+                imports []
+
+            main = \used, unused, (Pair first second) -> used + first
+            "#
+        );
+
+        assert_eq!(
+            fixed(src),
+            indoc!(
+                r#"
+                interface Test
+                    exposes [main]
+                    imports []
+
+                main = \used, _unused, Pair first _second -> used + first
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn removes_exposed_but_not_defined() {
+        let src = indoc!(
+            r#"
+            interface Test
+                exposes [main, missing, AlsoMissing]
+                imports []
+
+            main = 1
+            "#
+        );
+
+        assert_eq!(
+            fixed(src),
+            indoc!(
+                r#"
+                interface Test
+                    exposes [main]
+                    imports []
+
+                main = 1
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn writes_a_module_that_still_checks() {
+        let src = indoc!(
+            r#"
+            interface Test
+                exposes [main, missing]
+                imports []
+
+            main : I64, I64 -> I64
+            main = \x, y ->
+                double = \n -> n * 2
+                unused = 5
+
+                double x
+
+            helper = \a -> a
+            "#
+        );
+
+        assert!(total_problems(src) > 0);
+
+        // A dry run doesn't touch the file, and fails because there's something to fix
+        assert_eq!(run_fix(src, true), (1, src.to_string()));
+
+        let (exit_code, fixed_src) = run_fix(src, false);
+
+        assert_eq!(exit_code, 0);
+        assert_ne!(fixed_src, src);
+        assert_eq!(total_problems(&fixed_src), 0, "{}", fixed_src);
+
+        // There's nothing left to fix, so running it again leaves the file as it is
+        assert_eq!(run_fix(&fixed_src, false), (0, fixed_src.clone()));
+    }
+}
@@ -16,6 +16,7 @@ use roc_load::{ExpectMetadata, Threading};
 use roc_mono::ir::OptLevel;
 use roc_packaging::cache::RocCacheDir;
 use roc_packaging::tarball::Compression;
+use roc_reporting::warnings::{WarningConfig, WarningLevel};
 use std::env;
 use std::ffi::{CString, OsStr};
 use std::io;
@@ -35,6 +36,23 @@ use tempfile::TempDir;
 mod format;
 pub use format::format;
 
+mod package;
+pub use package::package;
+
+mod test_report;
+
+mod diff_api;
+pub use diff_api::diff_api;
+
+mod vendor;
+pub use vendor::vendor;
+
+mod explain;
+pub use explain::explain;
+
+mod fix;
+pub use fix::fix;
+
 pub const CMD_BUILD: &str = "build";
 pub const CMD_RUN: &str = "run";
 pub const CMD_DEV: &str = "dev";
@@ -47,6 +65,12 @@ pub const CMD_FORMAT: &str = "format";
 pub const CMD_TEST: &str = "test";
 pub const CMD_GLUE: &str = "glue";
 pub const CMD_GEN_STUB_LIB: &str = "gen-stub-lib";
+pub const CMD_LSP: &str = "lsp";
+pub const CMD_PACKAGE: &str = "package";
+pub const CMD_VENDOR: &str = "vendor";
+pub const CMD_DIFF_API: &str = "diff-api";
+pub const CMD_EXPLAIN: &str = "explain";
+pub const CMD_FIX: &str = "fix";
 
 pub const FLAG_DEBUG: &str = "debug";
 pub const FLAG_BUNDLE: &str = "bundle";
@@ -62,11 +86,30 @@ pub const FLAG_LINKER: &str = "linker";
 pub const FLAG_PREBUILT: &str = "prebuilt-platform";
 pub const FLAG_CHECK: &str = "check";
 pub const FLAG_WASM_STACK_SIZE_KB: &str = "wasm-stack-size-kb";
+pub const FLAG_FILTER: &str = "filter";
+pub const FLAG_LIST: &str = "list";
+pub const FLAG_FORMAT: &str = "format";
+pub const FLAG_PACKAGE_VERSION: &str = "package-version";
+pub const FLAG_LICENSE: &str = "license";
+pub const FLAG_SIGN: &str = "sign";
+pub const FLAG_GEN_KEY: &str = "gen-key";
+pub const FLAG_OUTPUT: &str = "output";
+pub const FLAG_PACKAGES_URL: &str = "packages-url";
+pub const FLAG_JSON: &str = "json";
+pub const FLAG_DOC: &str = "doc";
+pub const FLAG_DENY_WARNINGS: &str = "deny-warnings";
+pub const FLAG_ALLOW: &str = "allow";
+pub const FLAG_WARN: &str = "warn";
+pub const FLAG_DENY: &str = "deny";
+pub const FLAG_DRY_RUN: &str = "dry-run";
 pub const ROC_FILE: &str = "ROC_FILE";
 pub const ROC_DIR: &str = "ROC_DIR";
+pub const OLD_ROC_FILE: &str = "OLD_ROC_FILE";
+pub const NEW_ROC_FILE: &str = "NEW_ROC_FILE";
 pub const GLUE_DIR: &str = "GLUE_DIR";
 pub const GLUE_SPEC: &str = "GLUE_SPEC";
 pub const DIRECTORY_OR_FILES: &str = "DIRECTORY_OR_FILES";
+pub const ERROR_CODE: &str = "ERROR_CODE";
 pub const ARGS_FOR_APP: &str = "ARGS_FOR_APP";
 
 const VERSION: &str = include_str!("../../../version.txt");
@@ -123,6 +166,35 @@ pub fn build_app<'a>() -> Command<'a> {
         .validator(|s| s.parse::<u32>())
         .required(false);
 
+    let flag_deny_warnings = Arg::new(FLAG_DENY_WARNINGS)
+        .long(FLAG_DENY_WARNINGS)
+        .help("Report every warning as an error, so the exit code is 1 if there are any\n(Same as `--deny warnings`.)")
+        .required(false);
+
+    let flag_allow = Arg::new(FLAG_ALLOW)
+        .long(FLAG_ALLOW)
+        .short('A')
+        .help("Don't report this warning, given as a code or name (e.g. `-A unused-def`), or `warnings` for all of them\n(Overrides roc-warnings.txt next to the .roc file. A `# roc:allow unused-def` comment allows a warning on just one line.)")
+        .takes_value(true)
+        .multiple_occurrences(true)
+        .required(false);
+
+    let flag_warn = Arg::new(FLAG_WARN)
+        .long(FLAG_WARN)
+        .short('W')
+        .help("Report this warning as a warning, given as a code or name (e.g. `-W unused-import`), or `warnings` for all of them")
+        .takes_value(true)
+        .multiple_occurrences(true)
+        .required(false);
+
+    let flag_deny = Arg::new(FLAG_DENY)
+        .long(FLAG_DENY)
+        .short('D')
+        .help("Report this warning as an error, given as a code or name (e.g. `-D unused-import`), or `warnings` for all of them")
+        .takes_value(true)
+        .multiple_occurrences(true)
+        .required(false);
+
     let roc_file_to_run = Arg::new(ROC_FILE)
         .help("The .roc file of an app to run")
         .allow_invalid_utf8(true)
@@ -150,6 +222,10 @@ pub fn build_app<'a>() -> Command<'a> {
             .arg(flag_time.clone())
             .arg(flag_linker.clone())
             .arg(flag_prebuilt.clone())
+            .arg(flag_deny_warnings.clone())
+            .arg(flag_allow.clone())
+            .arg(flag_warn.clone())
+            .arg(flag_deny.clone())
             .arg(flag_wasm_stack_size_kb.clone())
             .arg(
                 Arg::new(FLAG_TARGET)
@@ -181,6 +257,14 @@ pub fn build_app<'a>() -> Command<'a> {
                     .help("Do not link\n(Instead, just output the `.o` file.)")
                     .required(false),
             )
+            .arg(
+                Arg::new(FLAG_FORMAT)
+                    .long(FLAG_FORMAT)
+                    .help("Set the output format for problems\n(`json` writes one JSON object per problem, `sarif` writes a SARIF 2.1.0 log, and neither writes anything else to stdout.)")
+                    .possible_values(["text", "json", "sarif"])
+                    .default_value("text")
+                    .required(false),
+            )
             .arg(
                 Arg::new(ROC_FILE)
                     .help("The .roc file to build")
@@ -199,6 +283,37 @@ pub fn build_app<'a>() -> Command<'a> {
             .arg(flag_time.clone())
             .arg(flag_linker.clone())
             .arg(flag_prebuilt.clone())
+            .arg(flag_deny_warnings.clone())
+            .arg(flag_allow.clone())
+            .arg(flag_warn.clone())
+            .arg(flag_deny.clone())
+            .arg(
+                Arg::new(FLAG_FILTER)
+                    .long(FLAG_FILTER)
+                    .help("Only run expects in modules whose name contains this pattern, or the expect at this location\n(e.g. `--filter Json` or `--filter src/Json.roc:42`)")
+                    .takes_value(true)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_LIST)
+                    .long(FLAG_LIST)
+                    .help("List the expects that would run, without running them")
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_DOC)
+                    .long(FLAG_DOC)
+                    .help("Run the examples in the package's doc comments instead of its expects\n(An example is a Roc code block with an `expect` in it.)")
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_FORMAT)
+                    .long(FLAG_FORMAT)
+                    .help("Set the output format\n(`json`, `junit` and `sarif` write only the results to stdout, so compiler warnings are not shown. If there are errors, `json` and `sarif` write those instead, in the same format as `roc check`.)")
+                    .possible_values(["text", "json", "junit", "sarif"])
+                    .default_value("text")
+                    .required(false),
+            )
             .arg(
                 Arg::new(ROC_FILE)
                     .help("The .roc file for the main module")
@@ -210,6 +325,15 @@ pub fn build_app<'a>() -> Command<'a> {
         )
         .subcommand(Command::new(CMD_REPL)
             .about("Launch the interactive Read Eval Print Loop (REPL)")
+            .arg(
+                Arg::new(ROC_FILE)
+                    .help("The .roc file of an app to start the REPL in, with its imports and packages in scope")
+                    .allow_invalid_utf8(true)
+                    .required(false)
+            )
+        )
+        .subcommand(Command::new(CMD_LSP)
+            .about("Start a language server that speaks the Language Server Protocol over stdio")
         )
         .subcommand(Command::new(CMD_RUN)
             .about("Run a .roc file even if it has build errors")
@@ -221,6 +345,10 @@ pub fn build_app<'a>() -> Command<'a> {
             .arg(flag_time.clone())
             .arg(flag_linker.clone())
             .arg(flag_prebuilt.clone())
+            .arg(flag_deny_warnings.clone())
+            .arg(flag_allow.clone())
+            .arg(flag_warn.clone())
+            .arg(flag_deny.clone())
             .arg(roc_file_to_run.clone())
             .arg(args_for_app.clone())
         )
@@ -234,6 +362,10 @@ pub fn build_app<'a>() -> Command<'a> {
             .arg(flag_time.clone())
             .arg(flag_linker.clone())
             .arg(flag_prebuilt.clone())
+            .arg(flag_deny_warnings.clone())
+            .arg(flag_allow.clone())
+            .arg(flag_warn.clone())
+            .arg(flag_deny.clone())
             .arg(roc_file_to_run.clone())
             .arg(args_for_app.clone())
         )
@@ -258,6 +390,18 @@ pub fn build_app<'a>() -> Command<'a> {
             .about("Check the code for problems, but don’t build or run it")
             .arg(flag_time.clone())
             .arg(flag_max_threads.clone())
+            .arg(flag_deny_warnings.clone())
+            .arg(flag_allow.clone())
+            .arg(flag_warn.clone())
+            .arg(flag_deny.clone())
+            .arg(
+                Arg::new(FLAG_FORMAT)
+                    .long(FLAG_FORMAT)
+                    .help("Set the output format\n(`json` writes one JSON object per problem, `sarif` writes a SARIF 2.1.0 log, and neither writes anything else to stdout.)")
+                    .possible_values(["text", "json", "sarif"])
+                    .default_value("text")
+                    .required(false),
+            )
             .arg(
                 Arg::new(ROC_FILE)
                     .help("The .roc file of an app to check")
@@ -266,9 +410,155 @@ pub fn build_app<'a>() -> Command<'a> {
                     .default_value(DEFAULT_ROC_FILENAME),
             )
             )
+        .subcommand(Command::new(CMD_FIX)
+            .about("Fix unused imports, defs, and arguments, and exposed names that aren't defined, in a .roc file and the modules it imports")
+            .arg(flag_max_threads.clone())
+            .arg(
+                Arg::new(FLAG_DRY_RUN)
+                    .long(FLAG_DRY_RUN)
+                    .help("Print a diff of the fixes instead of making them\n(Exits with 1 if there is anything to fix.)")
+                    .required(false),
+            )
+            .arg(
+                Arg::new(ROC_FILE)
+                    .help("The .roc file of an app or package to fix")
+                    .allow_invalid_utf8(true)
+                    .required(false)
+                    .default_value(DEFAULT_ROC_FILENAME),
+            )
+        )
+        .subcommand(Command::new(CMD_PACKAGE)
+            .about("Typecheck a package and bundle it, along with a manifest, so others can add it as a HTTPS dependency")
+            .arg(flag_max_threads.clone())
+            .arg(
+                Arg::new(FLAG_PACKAGE_VERSION)
+                    .long(FLAG_PACKAGE_VERSION)
+                    .help("The version to record in the package's manifest (e.g. `1.2.3`)")
+                    .takes_value(true)
+                    .required_unless_present(FLAG_GEN_KEY),
+            )
+            .arg(
+                Arg::new(FLAG_LICENSE)
+                    .long(FLAG_LICENSE)
+                    .help("The SPDX identifier of the package's license, to record in its manifest (e.g. `UPL-1.0`)")
+                    .takes_value(true)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_BUNDLE)
+                    .long(FLAG_BUNDLE)
+                    .help("The kind of archive to create")
+                    .possible_values([".tar", ".tar.gz", ".tar.br"])
+                    .default_value(".tar.br")
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_SIGN)
+                    .long(FLAG_SIGN)
+                    .help("Sign the archive with the secret key in this file, and write the signature next to the archive")
+                    .takes_value(true)
+                    .allow_invalid_utf8(true)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_GEN_KEY)
+                    .long(FLAG_GEN_KEY)
+                    .help("Generate a new signing key, write it to this file, and print its public key\n(Nothing gets packaged.)")
+                    .takes_value(true)
+                    .allow_invalid_utf8(true)
+                    .conflicts_with_all(&[FLAG_SIGN, FLAG_PACKAGE_VERSION])
+                    .required(false),
+            )
+            .arg(
+                Arg::new(ROC_FILE)
+                    .help("The package's main .roc file")
+                    .allow_invalid_utf8(true)
+                    .required(false)
+                    .default_value(DEFAULT_ROC_FILENAME),
+            )
+        )
+        .subcommand(Command::new(CMD_VENDOR)
+            .about("Download every package a .roc file depends on (including their dependencies) into a local directory, so builds can work offline")
+            .arg(
+                Arg::new(FLAG_OUTPUT)
+                    .long(FLAG_OUTPUT)
+                    .help("The directory to download the packages into")
+                    .takes_value(true)
+                    .allow_invalid_utf8(true)
+                    .default_value("vendor")
+                    .required(false),
+            )
+            .arg(
+                Arg::new(ROC_FILE)
+                    .help("The .roc file whose packages should be vendored")
+                    .allow_invalid_utf8(true)
+                    .required(false)
+                    .default_value(DEFAULT_ROC_FILENAME),
+            )
+        )
+        .subcommand(Command::new(CMD_DIFF_API)
+            .about("Compare the exposed API of two versions of a package, and exit with 1 if any change is breaking (or 2 if either version can't be loaded)")
+            .arg(
+                Arg::new(OLD_ROC_FILE)
+                    .help("The old version's main .roc file (or the directory it's in)")
+                    .allow_invalid_utf8(true)
+                    .required(true),
+            )
+            .arg(
+                Arg::new(NEW_ROC_FILE)
+                    .help("The new version's main .roc file (or the directory it's in)")
+                    .allow_invalid_utf8(true)
+                    .required(true),
+            )
+        )
+        .subcommand(Command::new(CMD_EXPLAIN)
+            .about("Explain an error code shown in a report header, like `roc explain R0202`")
+            .arg(
+                Arg::new(ERROR_CODE)
+                    .help("The code (e.g. `R0202`) or its name (e.g. `unused-import`)\n(Lists every code when omitted.)")
+                    .required(false),
+            )
+        )
         .subcommand(
             Command::new(CMD_DOCS)
                 .about("Generate documentation for a Roc package")
+                .arg(
+                    Arg::new(FLAG_OUTPUT)
+                        .long(FLAG_OUTPUT)
+                        .help("The directory to write the docs into\n(Anything already in this directory will be deleted!)")
+                        .takes_value(true)
+                        .allow_invalid_utf8(true)
+                        .default_value(roc_docs::BUILD_DIR)
+                        .required(false),
+                )
+                .arg(
+                    Arg::new(FLAG_PACKAGE_VERSION)
+                        .long(FLAG_PACKAGE_VERSION)
+                        .help("The version to show in the docs\n(Defaults to the version in the package's roc-package.json, if it has one.)")
+                        .takes_value(true)
+                        .required(false),
+                )
+                .arg(
+                    Arg::new(FLAG_PACKAGES_URL)
+                        .long(FLAG_PACKAGES_URL)
+                        .help("The URL where other packages' docs are hosted, for linking to types from this package's dependencies")
+                        .takes_value(true)
+                        .default_value("/")
+                        .required(false),
+                )
+                .arg(
+                    Arg::new(FLAG_CHECK)
+                        .long(FLAG_CHECK)
+                        .help("Typecheck the examples in the doc comments first, and don't generate docs if they have errors\n(An example is a Roc code block with an `expect` in it.)")
+                        .required(false),
+                )
+                .arg(flag_max_threads.clone())
+                .arg(
+                    Arg::new(FLAG_JSON)
+                        .long(FLAG_JSON)
+                        .help("Also write a JSON description of every exposed module to docs.json in the output directory")
+                        .required(false),
+                )
                 .arg(Arg::new(ROC_FILE)
                     .multiple_values(true)
                     .help("The package's main .roc file")
@@ -327,6 +617,10 @@ pub fn build_app<'a>() -> Command<'a> {
         .arg(flag_time)
         .arg(flag_linker)
         .arg(flag_prebuilt)
+        .arg(flag_deny_warnings)
+        .arg(flag_allow)
+        .arg(flag_warn)
+        .arg(flag_deny)
         .arg(roc_file_to_run.required(false))
         .arg(args_for_app);
 
@@ -359,21 +653,80 @@ pub enum FormatMode {
     CheckOnly,
 }
 
-#[cfg(windows)]
-pub fn test(_matches: &ArgMatches, _triple: Triple) -> io::Result<i32> {
-    todo!("running tests does not work on windows right now")
+/// Which warnings to allow or deny when checking `roc_file_path`: those in the roc-warnings.txt
+/// next to it, then `--deny-warnings`, then `--allow`, `--warn`, and `--deny` in the order given.
+pub fn warning_config(matches: &ArgMatches, roc_file_path: &Path) -> WarningConfig {
+    let mut config = WarningConfig::default();
+    let dir = match roc_file_path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let mut result = config.read_project_file(dir);
+
+    if matches.is_present(FLAG_DENY_WARNINGS) {
+        result = result.and_then(|()| config.set(WarningLevel::Deny, "warnings"));
+    }
+
+    let mut flags = Vec::new();
+
+    for (flag, level) in [
+        (FLAG_ALLOW, WarningLevel::Allow),
+        (FLAG_WARN, WarningLevel::Warn),
+        (FLAG_DENY, WarningLevel::Deny),
+    ] {
+        if let (Some(indices), Some(values)) = (matches.indices_of(flag), matches.values_of(flag)) {
+            flags.extend(
+                indices
+                    .zip(values)
+                    .map(|(index, value)| (index, level, value)),
+            );
+        }
+    }
+
+    // Later flags win, like in roc-warnings.txt
+    flags.sort_by_key(|(index, _, _)| *index);
+
+    for (_, level, warning) in flags {
+        result = result.and_then(|()| config.set(level, warning));
+    }
+
+    if let Err(msg) = result {
+        eprintln!("\n{}\n", msg);
+
+        process::exit(1);
+    }
+
+    config
 }
 
-#[cfg(not(windows))]
 pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
     use roc_build::program::report_problems_monomorphized;
     use roc_load::{ExecutionMode, LoadConfig, LoadMonomorphizedError};
+    use roc_module::symbol::Symbol;
     use roc_packaging::cache;
+    use roc_reporting::report::RenderTarget;
     use roc_target::TargetInfo;
+    use std::collections::HashMap;
+    use test_report::{ExpectInfo, TestOutputFormat};
 
     let start_time = Instant::now();
     let arena = Bump::new();
     let filename = matches.value_of_os(ROC_FILE).unwrap();
+    let format = TestOutputFormat::from_flag(matches.value_of(FLAG_FORMAT));
+    // How compiler problems are shown
+    let render = match format {
+        TestOutputFormat::Text => RenderTarget::ColorTerminal,
+        TestOutputFormat::Json => RenderTarget::Json,
+        TestOutputFormat::Sarif => RenderTarget::Sarif,
+        TestOutputFormat::Junit => RenderTarget::Generic,
+    };
+    // How failed expects are shown; machine-readable formats include them as plain text
+    let expect_render = match format {
+        TestOutputFormat::Text => RenderTarget::ColorTerminal,
+        TestOutputFormat::Json | TestOutputFormat::Junit | TestOutputFormat::Sarif => {
+            RenderTarget::Generic
+        }
+    };
     let opt_level = match (
         matches.is_present(FLAG_OPTIMIZE),
         matches.is_present(FLAG_OPT_SIZE),
@@ -416,6 +769,30 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
         process::exit(1);
     }
 
+    // With --doc, we test a copy of the package which has its doc examples added as expects.
+    let doc_test_package = if matches.is_present(FLAG_DOC) {
+        let loaded = roc_docs::load_module_for_docs(path.to_path_buf());
+        let doc_tests = roc_docs::doc_tests::doc_tests(&loaded);
+
+        if doc_tests.is_empty() {
+            if format == TestOutputFormat::Text {
+                println!("No doc examples were found.");
+            }
+
+            // This is an error for the same reason that having no expects is; see below.
+            return Ok(2);
+        }
+
+        Some(roc_docs::doc_tests::DocTestPackage::new(path, doc_tests)?)
+    } else {
+        None
+    };
+    let warnings = warning_config(matches, path);
+    let path = match &doc_test_package {
+        Some(package) => package.root_file.as_path(),
+        None => path,
+    };
+
     let arena = &arena;
     let target = &triple;
     let opt_level = opt_level;
@@ -424,8 +801,7 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
     // Step 1: compile the app and generate the .o file
     let load_config = LoadConfig {
         target_info,
-        // TODO: expose this from CLI?
-        render: roc_reporting::report::RenderTarget::ColorTerminal,
+        render,
         palette: roc_reporting::report::DEFAULT_PALETTE,
         threading,
         exec_mode: ExecutionMode::Test,
@@ -440,13 +816,78 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
     let mut loaded = match load_result {
         Ok(loaded) => loaded,
         Err(LoadMonomorphizedError::LoadingProblem(problem)) => {
-            return handle_loading_problem(problem);
+            return handle_loading_problem(problem, render);
         }
         Err(LoadMonomorphizedError::ErrorModule(module)) => {
-            return handle_error_module(module, start_time.elapsed(), filename, false);
+            return handle_error_module(
+                module,
+                start_time.elapsed(),
+                filename,
+                false,
+                &warnings,
+                render,
+            );
         }
     };
-    let problems = report_problems_monomorphized(&mut loaded);
+    // Warnings go to stdout, so they are left out of machine-readable output.
+    let problems = match format {
+        TestOutputFormat::Text => Some(report_problems_monomorphized(
+            &mut loaded,
+            render,
+            &warnings,
+        )),
+        TestOutputFormat::Json | TestOutputFormat::Junit | TestOutputFormat::Sarif => None,
+    };
+
+    let mut infos = test_report::locate_expects(&loaded);
+
+    if let Some(package) = &doc_test_package {
+        infos = test_report::locate_doc_tests(package, infos);
+
+        // The package's own expects got copied along with it, but we only want the examples.
+        let expects = &mut loaded.toplevel_expects;
+
+        expects.pure = std::mem::take(&mut expects.pure)
+            .into_iter()
+            .filter(|(symbol, _)| infos.contains_key(symbol))
+            .collect();
+        expects.fx = std::mem::take(&mut expects.fx)
+            .into_iter()
+            .filter(|(symbol, _)| infos.contains_key(symbol))
+            .collect();
+    }
+
+    if let Some(pattern) = matches.value_of(FLAG_FILTER) {
+        // An expect we couldn't locate can't be selected by a location or module either
+        let keep = |symbol: &Symbol| match infos.get(symbol) {
+            Some(info) => info.matches_filter(pattern),
+            None => false,
+        };
+        let expects = &mut loaded.toplevel_expects;
+
+        expects.pure = std::mem::take(&mut expects.pure)
+            .into_iter()
+            .filter(|(symbol, _)| keep(symbol))
+            .collect();
+        expects.fx = std::mem::take(&mut expects.fx)
+            .into_iter()
+            .filter(|(symbol, _)| keep(symbol))
+            .collect();
+    }
+
+    if matches.is_present(FLAG_LIST) {
+        let expects = &loaded.toplevel_expects;
+        let listed: HashMap<Symbol, ExpectInfo> = infos
+            .into_iter()
+            .filter(|(symbol, _)| {
+                expects.pure.contains_key(symbol) || expects.fx.contains_key(symbol)
+            })
+            .collect();
+
+        test_report::list_expects(&mut io::stdout(), &listed, format)?;
+
+        return Ok(0);
+    }
 
     let mut expectations = std::mem::take(&mut loaded.expectations);
 
@@ -462,11 +903,15 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
     .unwrap();
 
     // Print warnings before running tests.
-    {
-        debug_assert_eq!(
-            problems.errors, 0,
-            "if there were errors, we would have already exited."
-        );
+    if let Some(problems) = problems {
+        // Any errors here are warnings that were denied; real errors would have exited already.
+        if problems.errors > 0 {
+            problems.print_to_stdout(start_time.elapsed());
+            println!(".");
+
+            return Ok(problems.exit_code());
+        }
+
         if problems.warnings > 0 {
             problems.print_to_stdout(start_time.elapsed());
             println!(".\n\nRunning tests…\n\n\x1B[36m{}\x1B[39m", "─".repeat(80));
@@ -477,25 +922,46 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
     let arena = &bumpalo::Bump::new();
     let interns = arena.alloc(interns);
 
-    let mut writer = std::io::stdout();
+    // Failures are only printed as they happen in text mode; otherwise they end up in the report.
+    let mut writer: Box<dyn io::Write> = match format {
+        TestOutputFormat::Text => Box::new(io::stdout()),
+        TestOutputFormat::Json | TestOutputFormat::Junit | TestOutputFormat::Sarif => {
+            Box::new(io::sink())
+        }
+    };
 
-    let (failed, passed) = roc_repl_expect::run::run_toplevel_expects(
+    let results = roc_repl_expect::run::run_toplevel_expects(
         &mut writer,
-        roc_reporting::report::RenderTarget::ColorTerminal,
+        expect_render,
         arena,
         interns,
         &layout_interner.into_global(),
         &lib,
         &mut expectations,
         expects,
+        roc_repl_expect::run::ExpectRunner::default(),
     )
     .unwrap();
 
     let total_time = start_time.elapsed();
+    let (failed, passed) = roc_repl_expect::run::count_failed_and_passed(&results);
+
+    match format {
+        TestOutputFormat::Text => {}
+        TestOutputFormat::Json => {
+            test_report::write_json(&mut io::stdout(), &results, &infos, total_time)?
+        }
+        TestOutputFormat::Junit => {
+            test_report::write_junit(&mut io::stdout(), &results, &infos, total_time)?
+        }
+        TestOutputFormat::Sarif => test_report::write_sarif(&mut io::stdout(), &results, &infos)?,
+    }
 
     if failed == 0 && passed == 0 {
-        // TODO print this in a more nicely formatted way!
-        println!("No expectations were found.");
+        if format == TestOutputFormat::Text {
+            // TODO print this in a more nicely formatted way!
+            println!("No expectations were found.");
+        }
 
         // If no tests ran, treat that as an error. This is perhaps
         // briefly annoying at the very beginning of a project when
@@ -504,21 +970,71 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
         // running tests altogether!
         Ok(2)
     } else {
-        let failed_color = if failed == 0 {
-            32 // green
-        } else {
-            31 // red
-        };
+        if format == TestOutputFormat::Text {
+            let failed_color = if failed == 0 {
+                32 // green
+            } else {
+                31 // red
+            };
 
-        println!(
-            "\n\x1B[{failed_color}m{failed}\x1B[39m failed and \x1B[32m{passed}\x1B[39m passed in {} ms.\n",
-            total_time.as_millis(),
-        );
+            println!(
+                "\n\x1B[{failed_color}m{failed}\x1B[39m failed and \x1B[32m{passed}\x1B[39m passed in {} ms.\n",
+                total_time.as_millis(),
+            );
+        }
 
         Ok((failed > 0) as i32)
     }
 }
 
+/// Implements `roc docs --check`: typecheck the examples in a package's doc comments.
+/// (This doesn't run them; that's what `roc test --doc` is for.)
+pub fn check_doc_examples(root_file: &Path, threading: Threading) -> io::Result<i32> {
+    use roc_build::program::check_file;
+    use roc_docs::doc_tests::{doc_tests, DocTestPackage};
+    use roc_load::LoadingProblem;
+    use roc_packaging::cache;
+    use roc_reporting::report::RenderTarget;
+
+    let loaded = roc_docs::load_module_for_docs(root_file.to_path_buf());
+    let doc_tests = doc_tests(&loaded);
+
+    if doc_tests.is_empty() {
+        return Ok(0);
+    }
+
+    let package = DocTestPackage::new(root_file, doc_tests)?;
+    let arena = Bump::new();
+
+    match check_file(
+        &arena,
+        package.root_file.clone(),
+        false,
+        RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
+        threading,
+        RenderTarget::ColorTerminal,
+        &WarningConfig::default(),
+    ) {
+        Ok((problems, _)) if problems.errors > 0 => {
+            println!(
+                "\nThe doc examples have problems! The files above are copies of the package's modules, with each example added at the end as an `expect`.\n"
+            );
+
+            Ok(1)
+        }
+        // Warnings are about the package itself, not its docs, so they're `roc check`'s business.
+        Ok(_) => Ok(0),
+        Err(LoadingProblem::FormattedReport(report)) => {
+            print!("{}", report);
+
+            Ok(1)
+        }
+        Err(other) => {
+            panic!("check_file failed with error:\n{:?}", other);
+        }
+    }
+}
+
 pub fn build(
     matches: &ArgMatches,
     config: BuildConfig,
@@ -527,6 +1043,7 @@ pub fn build(
     link_type: LinkType,
 ) -> io::Result<i32> {
     use roc_build::program::build_file;
+    use roc_reporting::report::RenderTarget;
     use BuildConfig::*;
 
     let filename = matches.value_of_os(ROC_FILE).unwrap();
@@ -595,6 +1112,7 @@ pub fn build(
 
         path.to_path_buf()
     };
+    let warnings = warning_config(matches, &path_buf);
 
     // the process will end after this function,
     // so we don't want to spend time freeing these values
@@ -687,7 +1205,18 @@ pub fn build(
         emit_debug_info,
     };
 
-    let load_config = standard_load_config(&triple, build_ordering, threading);
+    // Only `roc build` has a --format flag; when running, the program's output goes to stdout too.
+    let render = match config {
+        BuildOnly => match matches.value_of(FLAG_FORMAT) {
+            Some("json") => RenderTarget::Json,
+            Some("sarif") => RenderTarget::Sarif,
+            _ => RenderTarget::ColorTerminal,
+        },
+        BuildAndRun | BuildAndRunIfNoErrors => RenderTarget::ColorTerminal,
+    };
+
+    let mut load_config = standard_load_config(&triple, build_ordering, threading);
+    load_config.render = render;
 
     let res_binary_path = build_file(
         &arena,
@@ -701,6 +1230,7 @@ pub fn build(
         wasm_dev_stack_bytes,
         roc_cache_dir,
         load_config,
+        &warnings,
     );
 
     match res_binary_path {
@@ -711,6 +1241,10 @@ pub fn build(
             expect_metadata,
         }) => {
             match config {
+                BuildOnly if render.is_machine_readable() => {
+                    // The problems were already reported in the requested format.
+                    Ok(problems.exit_code())
+                }
                 BuildOnly => {
                     // If possible, report the generated executable name relative to the current dir.
                     let generated_filename = binary_path
@@ -767,10 +1301,16 @@ pub fn build(
                         // Return a nonzero exit code due to fatal problem
                         return Ok(problems.exit_code());
                     }
-                    debug_assert_eq!(
-                        problems.errors, 0,
-                        "if there are non-fatal errors, they should have been returned as an error variant"
-                    );
+                    // Other errors stop the build before this, so these are warnings that were denied
+                    if problems.errors > 0 {
+                        problems.print_to_stdout(total_time);
+                        println!(
+                            ".\n\nCannot run program because warnings are denied…\n\n\x1B[36m{}\x1B[39m",
+                            "─".repeat(80)
+                        );
+
+                        return Ok(problems.exit_code());
+                    }
 
                     if problems.warnings > 0 {
                         problems.print_to_stdout(total_time);
@@ -791,9 +1331,9 @@ pub fn build(
             }
         }
         Err(BuildFileError::ErrorModule { module, total_time }) => {
-            handle_error_module(module, total_time, filename, true)
+            handle_error_module(module, total_time, filename, true, &warnings, render)
         }
-        Err(BuildFileError::LoadingProblem(problem)) => handle_loading_problem(problem),
+        Err(BuildFileError::LoadingProblem(problem)) => handle_loading_problem(problem, render),
     }
 }
 
@@ -815,17 +1355,17 @@ fn roc_run<'a, I: IntoIterator<Item = &'a OsStr>>(
                 .unwrap_or(path);
 
             #[cfg(target_family = "unix")]
-            {
+            let exit_code = {
                 use std::os::unix::ffi::OsStrExt;
 
                 run_wasm(
                     generated_filename,
                     args.into_iter().map(|os_str| os_str.as_bytes()),
-                );
-            }
+                )
+            };
 
             #[cfg(not(target_family = "unix"))]
-            {
+            let exit_code = {
                 run_wasm(
                     generated_filename,
                     args.into_iter().map(|os_str| {
@@ -833,10 +1373,10 @@ fn roc_run<'a, I: IntoIterator<Item = &'a OsStr>>(
                             "Roc does not currently support passing non-UTF8 arguments to Wasm.",
                         )
                     }),
-                );
-            }
+                )
+            };
 
-            Ok(0)
+            Ok(exit_code)
         }
         _ => roc_run_native(arena, opt_level, args, binary_bytes, expect_metadata),
     }
@@ -1204,9 +1744,9 @@ fn roc_run_native<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(
 }
 
 #[cfg(feature = "run-wasm32")]
-fn run_wasm<I: Iterator<Item = S>, S: AsRef<[u8]>>(wasm_path: &std::path::Path, args: I) {
+fn run_wasm<I: Iterator<Item = S>, S: AsRef<[u8]>>(wasm_path: &std::path::Path, args: I) -> i32 {
     use bumpalo::collections::Vec;
-    use roc_wasm_interp::{DefaultImportDispatcher, Instance};
+    use roc_wasm_interp::{DefaultImportDispatcher, Instance, Value};
 
     let bytes = std::fs::read(wasm_path).unwrap();
     let arena = Bump::new();
@@ -1217,21 +1757,32 @@ fn run_wasm<I: Iterator<Item = S>, S: AsRef<[u8]>>(wasm_path: &std::path::Path,
         arg_copy.extend_from_slice(arg.as_ref());
         argv.push(arg_copy.into_bump_slice());
     }
-    let import_dispatcher = DefaultImportDispatcher::new(&argv);
+    let mut import_dispatcher = DefaultImportDispatcher::new(&argv);
+
+    // Give the app the same view of the world that a native executable would get
+    import_dispatcher.wasi.env = std::env::vars_os()
+        .filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)))
+        .collect();
+    if let Ok(current_dir) = std::env::current_dir() {
+        import_dispatcher.wasi.preopen_dir(".", current_dir);
+    }
 
     let mut instance = Instance::from_bytes(&arena, &bytes, import_dispatcher, false).unwrap();
 
-    instance
-        .call_export("_start", [])
-        .unwrap()
-        .unwrap()
-        .expect_i32()
-        .unwrap();
+    match instance.call_export("_start", []) {
+        Ok(Some(Value::I32(exit_code))) => exit_code,
+        Ok(_) => 0,
+        Err(trap) => {
+            eprintln!("\nThe program crashed: {}", trap);
+            1
+        }
+    }
 }
 
 #[cfg(not(feature = "run-wasm32"))]
-fn run_wasm<I: Iterator<Item = S>, S: AsRef<[u8]>>(_wasm_path: &std::path::Path, _args: I) {
+fn run_wasm<I: Iterator<Item = S>, S: AsRef<[u8]>>(_wasm_path: &std::path::Path, _args: I) -> i32 {
     println!("Running wasm files is not supported on this target.");
+    0
 }
 
 #[derive(Debug, Copy, Clone, EnumIter, IntoStaticStr, PartialEq, Eq, Default)]
@@ -2,15 +2,19 @@
 use roc_build::link::LinkType;
 use roc_build::program::check_file;
 use roc_cli::{
-    build_app, format, test, BuildConfig, FormatMode, Target, CMD_BUILD, CMD_CHECK, CMD_DEV,
-    CMD_DOCS, CMD_EDIT, CMD_FORMAT, CMD_GEN_STUB_LIB, CMD_GLUE, CMD_REPL, CMD_RUN, CMD_TEST,
-    CMD_VERSION, DIRECTORY_OR_FILES, FLAG_CHECK, FLAG_LIB, FLAG_NO_LINK, FLAG_TARGET, FLAG_TIME,
-    GLUE_DIR, GLUE_SPEC, ROC_FILE,
+    build_app, check_doc_examples, diff_api, explain, fix, format, package, test, vendor,
+    warning_config, BuildConfig, FormatMode, Target, CMD_BUILD, CMD_CHECK, CMD_DEV, CMD_DIFF_API,
+    CMD_DOCS, CMD_EDIT, CMD_EXPLAIN, CMD_FIX, CMD_FORMAT, CMD_GEN_STUB_LIB, CMD_GLUE, CMD_LSP,
+    CMD_PACKAGE, CMD_REPL, CMD_RUN, CMD_TEST, CMD_VENDOR, CMD_VERSION, DIRECTORY_OR_FILES,
+    FLAG_CHECK, FLAG_FORMAT, FLAG_JSON, FLAG_LIB, FLAG_NO_LINK, FLAG_OUTPUT, FLAG_PACKAGES_URL,
+    FLAG_PACKAGE_VERSION, FLAG_TARGET, FLAG_TIME, GLUE_DIR, GLUE_SPEC, ROC_FILE,
 };
-use roc_docs::generate_docs_html;
+use roc_docs::{generate_docs_html, DocsOptions};
 use roc_error_macros::user_error;
 use roc_load::{LoadingProblem, Threading};
 use roc_packaging::cache::{self, RocCacheDir};
+use roc_reporting::cli::print_fatal_report;
+use roc_reporting::report::RenderTarget;
 use std::fs::{self, FileType};
 use std::io;
 use std::path::{Path, PathBuf};
@@ -143,6 +147,12 @@ fn main() -> io::Result<()> {
                 Some(1) => Threading::Single,
                 Some(n) => Threading::AtMost(n),
             };
+            let render = match matches.value_of(FLAG_FORMAT) {
+                Some("json") => RenderTarget::Json,
+                Some("sarif") => RenderTarget::Sarif,
+                _ => RenderTarget::ColorTerminal,
+            };
+            let warnings = warning_config(matches, &roc_file_path);
 
             match check_file(
                 &arena,
@@ -150,7 +160,10 @@ fn main() -> io::Result<()> {
                 emit_timings,
                 RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
                 threading,
+                render,
+                &warnings,
             ) {
+                Ok((problems, _)) if render.is_machine_readable() => Ok(problems.exit_code()),
                 Ok((problems, total_time)) => {
                     println!(
                         "\x1B[{}m{}\x1B[39m {} and \x1B[{}m{}\x1B[39m {} found in {} ms.",
@@ -183,7 +196,7 @@ fn main() -> io::Result<()> {
                 }
 
                 Err(LoadingProblem::FormattedReport(report)) => {
-                    print!("{}", report);
+                    print_fatal_report(&report, render);
 
                     Ok(1)
                 }
@@ -192,7 +205,10 @@ fn main() -> io::Result<()> {
                 }
             }
         }
-        Some((CMD_REPL, _)) => Ok(roc_repl_cli::main()),
+        Some((CMD_REPL, matches)) => Ok(roc_repl_cli::main(
+            matches.value_of_os(ROC_FILE).map(Path::new),
+        )),
+        Some((CMD_LSP, _)) => roc_lang_srv::run(),
         Some((CMD_EDIT, matches)) => {
             match matches
                 .values_of_os(DIRECTORY_OR_FILES)
@@ -209,12 +225,43 @@ fn main() -> io::Result<()> {
             // Exit 0 if the editor exited normally
             Ok(0)
         }
+        Some((CMD_PACKAGE, matches)) => package(matches),
+        Some((CMD_VENDOR, matches)) => vendor(matches),
+        Some((CMD_DIFF_API, matches)) => diff_api(matches),
+        Some((CMD_EXPLAIN, matches)) => explain(matches),
+        Some((CMD_FIX, matches)) => fix(matches),
         Some((CMD_DOCS, matches)) => {
             let root_filename = matches.value_of_os(ROC_FILE).unwrap();
 
-            generate_docs_html(PathBuf::from(root_filename));
+            let exit_code = if matches.is_present(FLAG_CHECK) {
+                let threading = match matches
+                    .value_of(roc_cli::FLAG_MAX_THREADS)
+                    .and_then(|s| s.parse::<usize>().ok())
+                {
+                    None => Threading::AllAvailable,
+                    Some(0) => user_error!("cannot build with at most 0 threads"),
+                    Some(1) => Threading::Single,
+                    Some(n) => Threading::AtMost(n),
+                };
+
+                check_doc_examples(Path::new(root_filename), threading)?
+            } else {
+                0
+            };
 
-            Ok(0)
+            // Don't publish docs whose examples are broken
+            if exit_code == 0 {
+                let options = DocsOptions {
+                    output_dir: PathBuf::from(matches.value_of_os(FLAG_OUTPUT).unwrap()),
+                    version: matches.value_of(FLAG_PACKAGE_VERSION).map(str::to_string),
+                    packages_url: matches.value_of(FLAG_PACKAGES_URL).unwrap().to_string(),
+                    emit_json: matches.is_present(FLAG_JSON),
+                };
+
+                generate_docs_html(PathBuf::from(root_filename), &options);
+            }
+
+            Ok(exit_code)
         }
         Some((CMD_FORMAT, matches)) => {
             let maybe_values = matches.values_of_os(DIRECTORY_OR_FILES);
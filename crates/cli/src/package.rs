@@ -0,0 +1,200 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use bumpalo::Bump;
+use clap::ArgMatches;
+use roc_build::program::check_file;
+use roc_error_macros::user_error;
+use roc_load::{LoadingProblem, Threading};
+use roc_packaging::cache::{self, RocCacheDir};
+use roc_packaging::manifest::{self, Manifest};
+use roc_packaging::signature::{self, SigningKey, SIGNATURE_EXT};
+use roc_packaging::tarball::{self, Compression};
+use roc_reporting::report::RenderTarget;
+use roc_reporting::warnings::WarningConfig;
+
+use crate::{
+    FLAG_BUNDLE, FLAG_GEN_KEY, FLAG_LICENSE, FLAG_MAX_THREADS, FLAG_PACKAGE_VERSION, FLAG_SIGN,
+    ROC_FILE,
+};
+
+/// Implements `roc package`: typecheck a package, then bundle it into a tarball along with a
+/// manifest describing it, and optionally sign the tarball's hash.
+pub fn package(matches: &ArgMatches) -> io::Result<i32> {
+    if let Some(key_path) = matches.value_of_os(FLAG_GEN_KEY) {
+        return gen_key(Path::new(key_path));
+    }
+
+    let start_time = Instant::now();
+    let path = PathBuf::from(matches.value_of_os(ROC_FILE).unwrap());
+
+    if !path.exists() {
+        eprintln!(
+            "\nThis file was not found: {}\n\nYou can run `roc help package` for more information on how to provide a .roc file.\n",
+            path.display()
+        );
+
+        return Ok(1);
+    }
+
+    // Read the manifest first; this is quick, and catches things like local dependencies
+    // before spending time on typechecking.
+    let version = matches.value_of(FLAG_PACKAGE_VERSION).unwrap().to_string();
+    let license = matches.value_of(FLAG_LICENSE).map(str::to_string);
+    let manifest = match Manifest::from_package_header(&path, version, license) {
+        Ok(manifest) => manifest,
+        Err(problem) => {
+            eprintln!("\n{}\n", describe_manifest_problem(&path, problem));
+
+            return Ok(1);
+        }
+    };
+
+    // Read the key up front too, so a typo in its path doesn't waste a whole typecheck.
+    let signing_key = match matches.value_of_os(FLAG_SIGN) {
+        Some(key_path) => match SigningKey::read(Path::new(key_path)) {
+            Ok(key) => Some(key),
+            Err(problem) => {
+                eprintln!(
+                    "\nI could not read the signing key in {}: {:?}\n\nYou can create a new key with `roc package --{FLAG_GEN_KEY} <KEY_FILE>`.\n",
+                    Path::new(key_path).display(),
+                    problem
+                );
+
+                return Ok(1);
+            }
+        },
+        None => None,
+    };
+
+    let threading = match matches
+        .value_of(FLAG_MAX_THREADS)
+        .and_then(|s| s.parse::<usize>().ok())
+    {
+        None => Threading::AllAvailable,
+        Some(0) => user_error!("cannot build with at most 0 threads"),
+        Some(1) => Threading::Single,
+        Some(n) => Threading::AtMost(n),
+    };
+
+    let arena = Bump::new();
+
+    // Make sure that everything the package exposes actually loads and typechecks.
+    match check_file(
+        &arena,
+        path.clone(),
+        false,
+        RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
+        threading,
+        RenderTarget::ColorTerminal,
+        &WarningConfig::default(),
+    ) {
+        Ok((problems, _)) if problems.errors > 0 => {
+            eprintln!(
+                "\nThe package has {} {}, so I did not bundle it.\n",
+                problems.errors,
+                if problems.errors == 1 {
+                    "error"
+                } else {
+                    "errors"
+                }
+            );
+
+            return Ok(1);
+        }
+        Ok(_) => {}
+        Err(LoadingProblem::FormattedReport(report)) => {
+            print!("{}", report);
+
+            return Ok(1);
+        }
+        Err(other) => {
+            eprintln!(
+                "\nI could not load the modules that {} exposes: {:?}\n",
+                path.display(),
+                other
+            );
+
+            return Ok(1);
+        }
+    }
+
+    let compression = Compression::try_from(matches.value_of(FLAG_BUNDLE).unwrap()).unwrap();
+    let filename = tarball::build_package(&path, compression, &manifest)?;
+    let created_path = path.with_file_name(&filename);
+
+    println!(
+        "\nPackaged \x1B[33m{} {}\x1B[39m into the following archive in {} ms:\n\n\t\x1B[33m{}\x1B[39m\n",
+        manifest.name,
+        manifest.version,
+        start_time.elapsed().as_millis(),
+        created_path.display()
+    );
+
+    if let Some(key) = signing_key {
+        // The content hash is the filename without its .tar, .tar.gz or .tar.br extension.
+        let content_hash = filename.split('.').next().unwrap();
+        let sig_path = path.with_file_name(format!("{filename}{SIGNATURE_EXT}"));
+
+        std::fs::write(&sig_path, key.sign(content_hash))?;
+
+        println!(
+            "Signed it with the key \x1B[32m{}\x1B[39m, and wrote the signature to:\n\n\t\x1B[33m{}\x1B[39m\n\nUpload the signature next to the archive. To only accept packages signed by this key, set {}={}\n",
+            key.public_key(),
+            sig_path.display(),
+            signature::TRUSTED_KEYS_ENV_VAR,
+            key.public_key(),
+        );
+    }
+
+    println!(
+        "To use this package, upload it to some URL and then add it as a dependency with:\n\n\t\x1B[32m\"https://your-url-goes-here/{filename}\"\x1B[39m\n"
+    );
+
+    Ok(0)
+}
+
+fn gen_key(key_path: &Path) -> io::Result<i32> {
+    let key = SigningKey::generate();
+
+    match key.write(key_path) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+            eprintln!(
+                "\n{} already exists, so I did not create a new key there.\n",
+                key_path.display()
+            );
+
+            return Ok(1);
+        }
+        Err(err) => return Err(err),
+    }
+
+    println!(
+        "\nWrote a new signing key to \x1B[33m{}\x1B[39m - keep this file private!\n\nIts public key, which people can use to verify your packages, is:\n\n\t\x1B[32m{}\x1B[39m\n",
+        key_path.display(),
+        key.public_key()
+    );
+
+    Ok(0)
+}
+
+fn describe_manifest_problem(path: &Path, problem: manifest::Problem) -> String {
+    use manifest::Problem::*;
+
+    match problem {
+        IoErr(err) => format!("I could not read {}: {}", path.display(), err),
+        NotAPackage => format!(
+            "{} is not a package. Only modules with a `package` header can be packaged; to distribute a platform, use `roc build --bundle` instead.",
+            path.display()
+        ),
+        LocalDependency { shorthand, path } => format!(
+            "The dependency `{shorthand}` refers to the local path \"{path}\". Published packages can only depend on other packages by URL."
+        ),
+        InvalidDependencyUrl { shorthand, problem } => {
+            format!("The URL of the dependency `{shorthand}` is invalid: {:?}", problem)
+        }
+        InvalidJson(err) => format!("The package manifest is invalid: {}", err),
+    }
+}
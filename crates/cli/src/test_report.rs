@@ -0,0 +1,367 @@
+//! Machine-readable output for `roc test`, and the names used to filter and list expects.
+use roc_docs::doc_tests::DocTestPackage;
+use roc_load::MonomorphizedModule;
+use roc_module::symbol::{ModuleId, Symbol};
+use roc_region::all::{LineColumn, LineColumnRegion, LineInfo};
+use roc_repl_expect::run::{count_failed_and_passed, ExpectResult};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::{PathBuf, MAIN_SEPARATOR};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestOutputFormat {
+    Text,
+    Json,
+    Junit,
+    Sarif,
+}
+
+impl TestOutputFormat {
+    pub fn from_flag(flag: Option<&str>) -> Self {
+        match flag {
+            Some("json") => TestOutputFormat::Json,
+            Some("junit") => TestOutputFormat::Junit,
+            Some("sarif") => TestOutputFormat::Sarif,
+            _ => TestOutputFormat::Text,
+        }
+    }
+}
+
+/// Where a top-level expect lives, in terms a user can type back into `--filter`.
+#[derive(Clone)]
+pub struct ExpectInfo {
+    /// `path/to/File.roc:line`, with a 1-based line number
+    /// (or `path/to/File.roc#item` for an example in the docs of `item`)
+    pub name: String,
+    pub module: String,
+    pub file: PathBuf,
+    pub region: LineColumnRegion,
+}
+
+impl ExpectInfo {
+    /// A pattern with a `:` in it selects an expect by location (e.g. `src/Json.roc:42`),
+    /// anything else selects all expects in modules whose name contains the pattern.
+    pub fn matches_filter(&self, pattern: &str) -> bool {
+        if pattern.contains(':') {
+            self.name == pattern
+                || self.name.ends_with(&format!("/{}", pattern))
+                || self
+                    .name
+                    .ends_with(&format!("{}{}", MAIN_SEPARATOR, pattern))
+        } else {
+            self.module.contains(pattern)
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "name": self.name,
+            "module": self.module,
+            "file": self.file.to_string_lossy(),
+            "region": {
+                "start": { "line": self.region.start.line + 1, "column": self.region.start.column + 1 },
+                "end": { "line": self.region.end.line + 1, "column": self.region.end.column + 1 },
+            },
+        })
+    }
+}
+
+pub fn locate_expects(loaded: &MonomorphizedModule) -> HashMap<Symbol, ExpectInfo> {
+    let expects = &loaded.toplevel_expects;
+    let mut line_infos: HashMap<ModuleId, LineInfo> = HashMap::new();
+    let mut infos = HashMap::with_capacity(expects.pure.len() + expects.fx.len());
+
+    for (symbol, region) in expects.pure.iter().chain(expects.fx.iter()) {
+        let module_id = symbol.module_id();
+        let (file, src) = &loaded.sources[&module_id];
+        let line_info = line_infos
+            .entry(module_id)
+            .or_insert_with(|| LineInfo::new(src));
+        let region = line_info.convert_region(*region);
+
+        let info = ExpectInfo {
+            name: format!("{}:{}", file.display(), region.start.line + 1),
+            module: symbol.module_string(&loaded.interns).to_string(),
+            file: file.clone(),
+            region,
+        };
+
+        infos.insert(*symbol, info);
+    }
+
+    infos
+}
+
+/// Keep only the expects which came from doc examples, and describe them in terms of the
+/// doc comments they came from rather than the copy of the package we actually tested.
+pub fn locate_doc_tests(
+    package: &DocTestPackage,
+    infos: HashMap<Symbol, ExpectInfo>,
+) -> HashMap<Symbol, ExpectInfo> {
+    infos
+        .into_iter()
+        .filter_map(|(symbol, info)| {
+            let test = package.example_at(&info.file, info.region.start.line)?;
+            let region = match test.line {
+                Some(line) => {
+                    // From the opening fence to the closing one
+                    let start = LineColumn {
+                        line: line - 1,
+                        column: 0,
+                    };
+                    let end = LineColumn {
+                        line: start.line + test.code.lines().count() as u32 + 1,
+                        column: 0,
+                    };
+
+                    LineColumnRegion::new(start, end)
+                }
+                None => LineColumnRegion::zero(),
+            };
+            let info = ExpectInfo {
+                name: test.name(),
+                module: info.module,
+                file: test.path.clone(),
+                region,
+            };
+
+            Some((symbol, info))
+        })
+        .collect()
+}
+
+/// Prints the expects in source order, either as a JSON array or one name per line.
+pub fn list_expects<W: Write>(
+    writer: &mut W,
+    infos: &HashMap<Symbol, ExpectInfo>,
+    format: TestOutputFormat,
+) -> io::Result<()> {
+    let mut sorted: Vec<&ExpectInfo> = infos.values().collect();
+    sorted.sort_by(|a, b| (&a.file, a.region.start).cmp(&(&b.file, b.region.start)));
+
+    match format {
+        TestOutputFormat::Json => {
+            let list: Vec<Value> = sorted.iter().map(|info| info.to_json()).collect();
+
+            serde_json::to_writer_pretty(&mut *writer, &list)?;
+            writeln!(writer)
+        }
+        TestOutputFormat::Text | TestOutputFormat::Junit | TestOutputFormat::Sarif => {
+            for info in sorted {
+                writeln!(writer, "{}", info.name)?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+pub fn write_json<W: Write>(
+    writer: &mut W,
+    results: &[ExpectResult],
+    infos: &HashMap<Symbol, ExpectInfo>,
+    total_time: Duration,
+) -> io::Result<()> {
+    let (failed, passed) = count_failed_and_passed(results);
+
+    let expects: Vec<Value> = results
+        .iter()
+        .map(|result| {
+            let mut value = match infos.get(&result.expect.symbol) {
+                Some(info) => info.to_json(),
+                None => json!({ "name": unknown_name(result) }),
+            };
+
+            value["passed"] = json!(result.passed);
+            value["duration_ms"] = json!(millis(result.duration));
+            value["failure"] = if result.passed {
+                Value::Null
+            } else {
+                json!(result.failure.trim())
+            };
+
+            value
+        })
+        .collect();
+
+    let report = json!({
+        "passed": passed,
+        "failed": failed,
+        "duration_ms": millis(total_time),
+        "expects": expects,
+    });
+
+    serde_json::to_writer_pretty(&mut *writer, &report)?;
+    writeln!(writer)
+}
+
+/// Writes one `<testsuite>` per module, in the order the expects were run.
+pub fn write_junit<W: Write>(
+    writer: &mut W,
+    results: &[ExpectResult],
+    infos: &HashMap<Symbol, ExpectInfo>,
+    total_time: Duration,
+) -> io::Result<()> {
+    let (failed, _) = count_failed_and_passed(results);
+
+    let mut suites: Vec<(&str, Vec<&ExpectResult>)> = Vec::new();
+    for result in results {
+        let module = match infos.get(&result.expect.symbol) {
+            Some(info) => info.module.as_str(),
+            None => "",
+        };
+
+        match suites.iter_mut().find(|(name, _)| *name == module) {
+            Some((_, suite)) => suite.push(result),
+            None => suites.push((module, vec![result])),
+        }
+    }
+
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<testsuites name="roc test" tests="{}" failures="{}" time="{}">"#,
+        results.len(),
+        failed,
+        seconds(total_time)
+    )?;
+
+    for (module, suite) in suites {
+        let suite_failures = suite.iter().filter(|result| !result.passed).count();
+        let suite_time = suite.iter().map(|result| result.duration).sum();
+
+        writeln!(
+            writer,
+            r#"  <testsuite name="{}" tests="{}" failures="{}" time="{}">"#,
+            escape_xml(module),
+            suite.len(),
+            suite_failures,
+            seconds(suite_time)
+        )?;
+
+        for result in suite {
+            let attributes = match infos.get(&result.expect.symbol) {
+                Some(info) => format!(
+                    r#"name="{}" classname="{}" file="{}" line="{}" time="{}""#,
+                    escape_xml(&info.name),
+                    escape_xml(module),
+                    escape_xml(&info.file.to_string_lossy()),
+                    info.region.start.line + 1,
+                    seconds(result.duration)
+                ),
+                None => format!(
+                    r#"name="{}" time="{}""#,
+                    escape_xml(&unknown_name(result)),
+                    seconds(result.duration)
+                ),
+            };
+
+            if result.passed {
+                writeln!(writer, "    <testcase {}/>", attributes)?;
+            } else {
+                writeln!(writer, "    <testcase {}>", attributes)?;
+                writeln!(
+                    writer,
+                    r#"      <failure message="expect failed">{}</failure>"#,
+                    escape_xml(result.failure.trim())
+                )?;
+                writeln!(writer, "    </testcase>")?;
+            }
+        }
+
+        writeln!(writer, "  </testsuite>")?;
+    }
+
+    writeln!(writer, "</testsuites>")
+}
+
+/// Writes a SARIF 2.1.0 log with a result for each expect that failed, so they can be shown
+/// alongside the problems `roc check --format sarif` finds.
+pub fn write_sarif<W: Write>(
+    writer: &mut W,
+    results: &[ExpectResult],
+    infos: &HashMap<Symbol, ExpectInfo>,
+) -> io::Result<()> {
+    let failures = results
+        .iter()
+        .filter(|result| !result.passed)
+        .map(|result| {
+            let (name, locations) = match infos.get(&result.expect.symbol) {
+                Some(info) => {
+                    let mut physical_location = json!({
+                        "artifactLocation": {
+                            "uri": info.file.to_string_lossy().replace('\\', "/"),
+                        },
+                    });
+
+                    // Doc examples we couldn't find a line for have no region
+                    if info.region != LineColumnRegion::zero() {
+                        physical_location["region"] = json!({
+                            "startLine": info.region.start.line + 1,
+                            "startColumn": info.region.start.column + 1,
+                            "endLine": info.region.end.line + 1,
+                            "endColumn": info.region.end.column + 1,
+                        });
+                    }
+
+                    (
+                        info.name.clone(),
+                        json!([{ "physicalLocation": physical_location }]),
+                    )
+                }
+                None => (unknown_name(result), json!([])),
+            };
+
+            json!({
+                "ruleId": "EXPECT FAILED",
+                "level": "error",
+                "message": { "text": result.failure.trim() },
+                "locations": locations,
+                "properties": { "name": name },
+            })
+        })
+        .collect();
+
+    writeln!(
+        writer,
+        "{}",
+        roc_reporting::cli::sarif_log_of_values(failures)
+    )
+}
+
+/// The name of an expect that `locate_expects` didn't find, which shouldn't happen,
+/// but isn't worth losing its result over.
+fn unknown_name(result: &ExpectResult) -> String {
+    format!("{:?}", result.expect.symbol)
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn seconds(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64())
+}
+
+/// Escapes text for use in XML attributes and elements. Control characters other than
+/// tabs and newlines are not allowed in XML 1.0, so they are dropped.
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(ch),
+            _ if ch.is_control() => {}
+            _ => escaped.push(ch),
+        }
+    }
+
+    escaped
+}
@@ -0,0 +1,90 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use clap::ArgMatches;
+use roc_packaging::mirror::{self, MIRRORS_ENV_VAR, MIRRORS_FILENAME};
+use roc_packaging::signature;
+use roc_packaging::vendor::vendor as vendor_packages;
+
+use crate::{FLAG_OUTPUT, ROC_FILE};
+
+/// Implements `roc vendor`: fetch every package the given module depends on (transitively)
+/// into a project-local directory, and set that directory up as a mirror so that future
+/// builds in this directory don't need internet access.
+pub fn vendor(matches: &ArgMatches) -> io::Result<i32> {
+    let path = PathBuf::from(matches.value_of_os(ROC_FILE).unwrap());
+    let vendor_dir = Path::new(matches.value_of_os(FLAG_OUTPUT).unwrap());
+
+    if !path.exists() {
+        eprintln!(
+            "\nThis file was not found: {}\n\nYou can run `roc help vendor` for more information on how to provide a .roc file.\n",
+            path.display()
+        );
+
+        return Ok(1);
+    }
+
+    let result = mirror::mirrors().and_then(|mirrors| {
+        vendor_packages(&path, vendor_dir, &mirrors, &signature::trusted_keys())
+    });
+
+    let vendored = match result {
+        Ok(vendored) => vendored,
+        Err(problem) => {
+            eprintln!(
+                "\nI could not vendor the packages that {} depends on: {:?}\n",
+                path.display(),
+                problem
+            );
+
+            return Ok(1);
+        }
+    };
+
+    if vendored.is_empty() {
+        println!(
+            "\n{} does not depend on any URL packages, so there is nothing to vendor.\n",
+            path.display()
+        );
+
+        return Ok(0);
+    }
+
+    println!(
+        "\nVendored these packages into \x1B[33m{}\x1B[39m:\n",
+        vendor_dir.display()
+    );
+
+    for package in vendored.iter() {
+        let note = if package.fetched {
+            ""
+        } else {
+            " (already vendored)"
+        };
+
+        println!("\t\x1B[36m{}\x1B[39m{note}", package.url);
+    }
+
+    // Set the vendor dir up as a mirror, unless there's already a mirrors file we'd clobber.
+    let entry = format!("* = {}", vendor_dir.display());
+    let mirrors_path = Path::new(MIRRORS_FILENAME);
+
+    if mirrors_path.exists() {
+        println!(
+            "\nTo build using these packages, make sure {MIRRORS_FILENAME} contains this line:\n\n\t{entry}\n"
+        );
+    } else {
+        fs::write(
+            mirrors_path,
+            format!("# Written by `roc vendor`, so that packages come from the vendored directory.\n{entry}\n"),
+        )?;
+
+        println!(
+            "\nWrote {MIRRORS_FILENAME}, so that `roc` commands run in this directory will use the vendored packages. (Elsewhere, set {MIRRORS_ENV_VAR}=\"* = /path/to/{}\")\n",
+            vendor_dir.display()
+        );
+    }
+
+    Ok(0)
+}
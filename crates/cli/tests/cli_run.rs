@@ -15,9 +15,13 @@ mod cli_run {
     };
     use const_format::concatcp;
     use indoc::indoc;
-    use roc_cli::{CMD_BUILD, CMD_CHECK, CMD_DEV, CMD_FORMAT, CMD_RUN, CMD_TEST};
+    use roc_cli::{
+        CMD_BUILD, CMD_CHECK, CMD_DEV, CMD_DIFF_API, CMD_DOCS, CMD_FIX, CMD_FORMAT, CMD_RUN,
+        CMD_TEST,
+    };
     use roc_test_utils::assert_multiline_str_eq;
     use serial_test::serial;
+    use std::fs;
     use std::iter;
     use std::path::Path;
 
@@ -638,6 +642,287 @@ mod cli_run {
             UseValgrind::Yes,
             TestCliCommands::Test,
         );
+
+        // the dev backend reports failures and dbg through the same shared memory;
+        // the lookup is a tag union with a string in it, so it can't just be copied over
+        check_output_with_stdin(
+            &file_path_from_root(
+                "crates/cli_testing_examples/expects",
+                "expects-dev-backend.roc",
+            ),
+            &[],
+            "expects-dev-backend",
+            &["--dev"],
+            &[],
+            &[],
+            indoc!(
+                r#"
+                This expectation failed:
+
+                17│      expect age person == 43
+                                ^^^^^^^^^^^^^^^^
+
+                When it failed, these variables had these values:
+
+                person : Person
+                person = Named "Roc" 42
+
+                [<ignored for tests> 18:9] Named "Roc" 42
+                Program finished!
+                "#
+            ),
+            UseValgrind::No,
+            TestCliCommands::Dev,
+        );
+    }
+
+    fn run_roc_test_report(flags: &[&str]) -> Out {
+        let file = fixture_file("test-report", "Arith.roc");
+
+        run_roc(
+            [CMD_TEST, file.to_str().unwrap()].iter().chain(flags),
+            &[],
+            &[],
+        )
+    }
+
+    /// The expect names `roc test --list` printed, without the directory of the fixture
+    fn listed_expects(out: &Out) -> Vec<String> {
+        let dir = fixtures_dir("test-report");
+
+        out.stdout
+            .lines()
+            .map(|line| {
+                let name = line.strip_prefix(dir.to_str().unwrap()).unwrap_or(line);
+
+                name.trim_start_matches(['/', '\\']).to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_list() {
+        let out = run_roc_test_report(&["--list"]);
+
+        assert!(out.status.success(), "{}", out.stderr);
+        assert_eq!(
+            listed_expects(&out),
+            ["Arith.roc:9", "Arith.roc:11", "Arith.roc:13", "Text.roc:7"]
+        );
+    }
+
+    #[test]
+    fn test_filter() {
+        // by module name
+        let out = run_roc_test_report(&["--list", "--filter", "Text"]);
+
+        assert!(out.status.success(), "{}", out.stderr);
+        assert_eq!(listed_expects(&out), ["Text.roc:7"]);
+
+        // by location
+        let out = run_roc_test_report(&["--list", "--filter", "Arith.roc:11"]);
+
+        assert!(out.status.success(), "{}", out.stderr);
+        assert_eq!(listed_expects(&out), ["Arith.roc:11"]);
+
+        // only the selected expects run, so this passes even though Arith.roc:11 fails
+        let out = run_roc_test_report(&["--filter", "Text"]);
+
+        assert!(out.status.success(), "{}", out.stdout);
+        assert!(
+            out.stdout.contains("0 failed and 1 passed"),
+            "{}",
+            out.stdout
+        );
+    }
+
+    #[test]
+    fn test_format_json() {
+        let out = run_roc_test_report(&["--format", "json"]);
+
+        assert_eq!(out.status.code(), Some(1), "{}", out.stderr);
+
+        let report: serde_json::Value = serde_json::from_str(&out.stdout).unwrap();
+
+        assert_eq!(report["passed"], 3);
+        assert_eq!(report["failed"], 1);
+
+        let expects = report["expects"].as_array().unwrap();
+        let failed: Vec<&serde_json::Value> = expects
+            .iter()
+            .filter(|expect| expect["passed"] == false)
+            .collect();
+
+        assert_eq!(expects.len(), 4);
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0]["module"], "Arith");
+        assert_eq!(failed[0]["region"]["start"]["line"], 11);
+        assert!(failed[0]["name"]
+            .as_str()
+            .unwrap()
+            .ends_with("Arith.roc:11"));
+        assert!(failed[0]["failure"]
+            .as_str()
+            .unwrap()
+            .contains("expect double 3 == 7"));
+    }
+
+    #[test]
+    fn test_format_sarif() {
+        let out = run_roc_test_report(&["--format", "sarif"]);
+
+        assert_eq!(out.status.code(), Some(1), "{}", out.stderr);
+
+        let log: serde_json::Value = serde_json::from_str(&out.stdout).unwrap();
+
+        assert_eq!(log["version"], "2.1.0");
+
+        let results = log["runs"][0]["results"].as_array().unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["ruleId"], "EXPECT FAILED");
+        assert_eq!(results[0]["level"], "error");
+
+        let location = &results[0]["locations"][0]["physicalLocation"];
+
+        assert!(location["artifactLocation"]["uri"]
+            .as_str()
+            .unwrap()
+            .ends_with("Arith.roc"));
+        assert_eq!(location["region"]["startLine"], 11);
+        assert!(results[0]["message"]["text"]
+            .as_str()
+            .unwrap()
+            .contains("expect double 3 == 7"));
+    }
+
+    #[test]
+    fn build_format_json() {
+        let file = known_bad_file("TypeError.roc");
+        let out = run_roc(
+            [CMD_BUILD, file.to_str().unwrap(), "--format", "json"],
+            &[],
+            &[],
+        );
+
+        assert_eq!(out.status.code(), Some(1), "{}", out.stderr);
+
+        // Nothing but the problems goes to stdout, one JSON object per line
+        let problems: Vec<serde_json::Value> = out
+            .stdout
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(problems.len(), 1, "{}", out.stdout);
+        assert_eq!(problems[0]["severity"], "error");
+        assert_eq!(problems[0]["title"], "TYPE MISMATCH");
+        assert!(problems[0]["file"]
+            .as_str()
+            .unwrap()
+            .ends_with("TypeError.roc"));
+    }
+
+    #[test]
+    fn test_format_junit() {
+        let out = run_roc_test_report(&["--format", "junit"]);
+
+        assert_eq!(out.status.code(), Some(1), "{}", out.stderr);
+
+        let junit = &out.stdout;
+
+        assert!(junit.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+        assert!(junit.contains(r#"<testsuites name="roc test" tests="4" failures="1""#));
+        assert!(junit.contains(r#"<testsuite name="Arith" tests="3" failures="1""#));
+        assert!(junit.contains(r#"<testsuite name="Text" tests="1" failures="0""#));
+        assert_eq!(junit.matches("<testcase ").count(), 4);
+        assert_eq!(
+            junit
+                .matches(r#"<failure message="expect failed">"#)
+                .count(),
+            1
+        );
+        assert!(junit.trim_end().ends_with("</testsuites>"));
+    }
+
+    #[test]
+    fn test_doc_examples() {
+        let file = fixture_file("doc-tests/good", "main.roc");
+        let run_test_doc = |flags: &[&str]| {
+            run_roc(
+                [CMD_TEST, file.to_str().unwrap(), "--doc"]
+                    .iter()
+                    .chain(flags),
+                &[],
+                &[],
+            )
+        };
+
+        // Only the examples are tests, not the package's own expect or the illustration.
+        let out = run_test_doc(&["--list"]);
+        let names: Vec<&str> = out.stdout.lines().collect();
+
+        assert!(out.status.success(), "{}", out.stderr);
+        assert_eq!(names.len(), 2, "{}", out.stdout);
+        assert!(names[0].ends_with("Arith.roc#double"), "{}", out.stdout);
+        assert!(names[1].ends_with("Arith.roc#triple"), "{}", out.stdout);
+
+        let out = run_test_doc(&[]);
+
+        assert_eq!(out.status.code(), Some(1), "{}", out.stderr);
+        assert!(
+            out.stdout.contains("1 failed and 1 passed"),
+            "{}",
+            out.stdout
+        );
+        assert!(
+            strip_colors(&out.stdout).contains("triple 2 == 7"),
+            "{}",
+            out.stdout
+        );
+    }
+
+    #[test]
+    fn docs_check() {
+        let output_dir = tempfile::tempdir().unwrap();
+        let output_flag = format!("--{}={}", roc_cli::FLAG_OUTPUT, output_dir.path().display());
+        let run_docs_check = |dir_name: &str| {
+            let file = fixture_file(dir_name, "main.roc");
+
+            run_roc(
+                [
+                    CMD_DOCS,
+                    file.to_str().unwrap(),
+                    CHECK_FLAG,
+                    output_flag.as_str(),
+                ],
+                &[],
+                &[],
+            )
+        };
+
+        // The examples typecheck (even though one of them fails), so the docs get generated.
+        let out = run_docs_check("doc-tests/good");
+
+        assert!(out.status.success(), "{}{}", out.stdout, out.stderr);
+        assert!(output_dir.path().join("Arith").join("index.html").exists());
+
+        fs::remove_dir_all(output_dir.path()).unwrap();
+
+        let out = run_docs_check("doc-tests/broken");
+
+        assert_eq!(out.status.code(), Some(1), "{}", out.stderr);
+        assert!(
+            out.stdout.contains("The doc examples have problems!"),
+            "{}",
+            out.stdout
+        );
+        assert!(
+            strip_colors(&out.stdout).contains("TYPE MISMATCH"),
+            "{}",
+            out.stdout
+        );
+        assert!(!output_dir.path().exists());
     }
 
     #[test]
@@ -1395,7 +1680,7 @@ mod cli_run {
             &[],
             indoc!(
                 r#"
-                ── TYPE MISMATCH ─────────────────────────────── tests/known_bad/TypeError.roc ─
+                ── TYPE MISMATCH [R0301] ─────────────────────── tests/known_bad/TypeError.roc ─
 
                 Something is off with the body of the main definition:
 
@@ -1431,7 +1716,7 @@ mod cli_run {
             &[],
             indoc!(
                 r#"
-                ── MISSING DEFINITION ────────────────── tests/known_bad/ExposedNotDefined.roc ─
+                ── MISSING DEFINITION [R0203] ────────── tests/known_bad/ExposedNotDefined.roc ─
 
                 bar is listed as exposed, but it isn't defined in this module.
 
@@ -1452,7 +1737,7 @@ mod cli_run {
             &[],
             indoc!(
                 r#"
-                ── UNUSED IMPORT ──────────────────────────── tests/known_bad/UnusedImport.roc ─
+                ── UNUSED IMPORT [R0202] ──────────────────── tests/known_bad/UnusedImport.roc ─
 
                 Nothing from Symbol is used in this module.
 
@@ -1468,6 +1753,44 @@ mod cli_run {
         );
     }
 
+    #[test]
+    fn fix_dry_run_unused_import() {
+        let path = known_bad_file("UnusedImport.roc");
+        let out = run_roc([CMD_FIX, path.to_str().unwrap(), "--dry-run"], &[], &[]);
+
+        assert!(!out.status.success());
+        assert!(out
+            .stdout
+            .contains("-    imports [Symbol.{ Ident }]\n+    imports []\n"));
+    }
+
+    #[test]
+    fn diff_api_load_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let broken = dir.path().join("Symbol.roc");
+
+        // A version that doesn't parse
+        fs::write(
+            &broken,
+            "interface Symbol\n    exposes [Ident]\n    imports []\n\nIdent :\n",
+        )
+        .unwrap();
+
+        let old = known_bad_file("Symbol.roc");
+        let out = run_roc(
+            [
+                CMD_DIFF_API,
+                old.to_str().unwrap(),
+                broken.to_str().unwrap(),
+            ],
+            &[],
+            &[],
+        );
+
+        // Not 1, which would mean that the API changed in a breaking way
+        assert_eq!(out.status.code(), Some(2), "{}", out.stderr);
+    }
+
     #[test]
     fn unknown_generates_with() {
         check_compile_error(
@@ -1475,7 +1798,7 @@ mod cli_run {
             &[],
             indoc!(
                 r#"
-                ── UNKNOWN GENERATES FUNCTION ─────── tests/known_bad/UnknownGeneratesWith.roc ─
+                ── UNKNOWN GENERATES FUNCTION [R0204] ─ .../known_bad/UnknownGeneratesWith.roc ─
 
                 I don't know how to generate the foobar function.
 
@@ -0,0 +1,11 @@
+interface Arith
+    exposes [double]
+    imports []
+
+## Doubles a number. This example doesn't typecheck, so `roc docs --check` rejects it.
+##
+## ```
+## expect double "two" == 4
+## ```
+double : I64 -> I64
+double = \x -> x * 2
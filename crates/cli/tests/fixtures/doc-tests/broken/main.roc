@@ -0,0 +1,3 @@
+package "doc-tests-broken"
+    exposes [Arith]
+    packages {}
@@ -0,0 +1,28 @@
+interface Arith
+    exposes [double, triple]
+    imports []
+
+## Doubles a number.
+##
+## ```
+## expect double 2 == 4
+## ```
+double : I64 -> I64
+double = \x -> x * 2
+
+## Triples a number. This example is wrong, so `roc test --doc` reports it.
+##
+## ```
+## expect triple 2 == 7
+## ```
+##
+## Code blocks without an `expect` are only there for illustration.
+##
+## ```
+## triple 5
+## ```
+triple : I64 -> I64
+triple = \x -> x * 3
+
+# This isn't an example, so `roc test --doc` leaves it out.
+expect double 0 == 0
@@ -0,0 +1,3 @@
+package "doc-tests"
+    exposes [Arith]
+    packages {}
@@ -0,0 +1,13 @@
+interface Arith
+    exposes [double, describe]
+    imports [Text]
+
+double = \x -> x * 2
+
+describe = \x -> Text.shout (Num.toStr (double x))
+
+expect double 2 == 4
+
+expect double 3 == 7
+
+expect describe 1 == "2!"
@@ -0,0 +1,7 @@
+interface Text
+    exposes [shout]
+    imports []
+
+shout = \str -> Str.concat str "!"
+
+expect shout "hi" == "hi!"
@@ -0,0 +1,20 @@
+app "expects-dev-backend"
+    packages { pf: "zig-platform/main.roc" }
+    imports []
+    provides [main] to pf
+
+Person : [Anonymous, Named Str U8]
+
+age : Person -> U8
+age = \person ->
+    when person is
+        Anonymous -> 0
+        Named _ years -> years
+
+main =
+    person : Person
+    person = Named "Roc" 42
+    expect age person == 43
+    dbg person
+
+    "Program finished!\n"
@@ -526,7 +526,7 @@ fn stmt_spec<'a>(
     use Stmt::*;
 
     match stmt {
-        Let(symbol, expr, expr_layout, mut continuation) => {
+        Let(symbol, expr, expr_layout, mut continuation, _) => {
             let value_id = expr_spec(builder, interner, env, block, *expr_layout, expr)?;
             env.symbols.insert(*symbol, value_id);
 
@@ -534,7 +534,7 @@ fn stmt_spec<'a>(
 
             loop {
                 match continuation {
-                    Let(symbol, expr, expr_layout, c) => {
+                    Let(symbol, expr, expr_layout, c, _) => {
                         let value_id =
                             expr_spec(builder, interner, env, block, *expr_layout, expr)?;
                         env.symbols.insert(*symbol, value_id);
@@ -568,6 +568,7 @@ fn stmt_spec<'a>(
             branches,
             default_branch,
             ret_layout: _lies,
+            region: _,
         } => {
             let mut cases = Vec::with_capacity(branches.len() + 1);
 
@@ -587,7 +588,7 @@ fn stmt_spec<'a>(
         Dbg { remainder, .. } => stmt_spec(builder, interner, env, block, layout, remainder),
         Expect { remainder, .. } => stmt_spec(builder, interner, env, block, layout, remainder),
         ExpectFx { remainder, .. } => stmt_spec(builder, interner, env, block, layout, remainder),
-        Ret(symbol) => Ok(env.symbols[symbol]),
+        Ret(symbol, _) => Ok(env.symbols[symbol]),
         Refcounting(modify_rc, continuation) => {
             apply_refcount_operation(builder, env, block, modify_rc)?;
 
@@ -598,6 +599,7 @@ fn stmt_spec<'a>(
             parameters,
             body,
             remainder,
+            region: _,
         } => {
             let mut type_ids = Vec::new();
 
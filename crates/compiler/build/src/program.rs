@@ -5,6 +5,7 @@ use bumpalo::Bump;
 use inkwell::memory_buffer::MemoryBuffer;
 use roc_error_macros::internal_error;
 use roc_gen_llvm::llvm::build::{module_from_builtins, LlvmBackendMode};
+use roc_gen_llvm::llvm::debug_info::DebugSources;
 use roc_gen_llvm::llvm::externs::add_default_roc_externs;
 use roc_load::{
     EntryPoint, ExecutionMode, ExpectMetadata, LoadConfig, LoadMonomorphizedError, LoadedModule,
@@ -13,8 +14,9 @@ use roc_load::{
 use roc_mono::ir::{OptLevel, SingleEntryPoint};
 use roc_packaging::cache::RocCacheDir;
 use roc_reporting::{
-    cli::{report_problems, Problems},
+    cli::{print_fatal_report, report_problems, Problems},
     report::{RenderTarget, DEFAULT_PALETTE},
+    warnings::WarningConfig,
 };
 use roc_target::TargetInfo;
 use std::ffi::OsStr;
@@ -36,23 +38,35 @@ pub struct CodeGenTiming {
     pub code_gen: Duration,
 }
 
-pub fn report_problems_monomorphized(loaded: &mut MonomorphizedModule) -> Problems {
+pub fn report_problems_monomorphized(
+    loaded: &mut MonomorphizedModule,
+    render: RenderTarget,
+    warnings: &WarningConfig,
+) -> Problems {
     report_problems(
         loaded.total_problems(),
         &loaded.sources,
         &loaded.interns,
         &mut loaded.can_problems,
         &mut loaded.type_problems,
+        render,
+        warnings,
     )
 }
 
-pub fn report_problems_typechecked(loaded: &mut LoadedModule) -> Problems {
+pub fn report_problems_typechecked(
+    loaded: &mut LoadedModule,
+    render: RenderTarget,
+    warnings: &WarningConfig,
+) -> Problems {
     report_problems(
         loaded.total_problems(),
         &loaded.sources,
         &loaded.interns,
         &mut loaded.can_problems,
         &mut loaded.type_problems,
+        render,
+        warnings,
     )
 }
 
@@ -187,7 +201,8 @@ fn gen_from_mono_module_llvm<'a>(
     }
 
     let builder = context.create_builder();
-    let (dibuilder, compile_unit) = roc_gen_llvm::llvm::build::Env::new_debug_info(module);
+    let (dibuilder, compile_unit) =
+        roc_gen_llvm::llvm::build::Env::new_debug_info_for_file(module, roc_file_path);
     let (mpm, _fpm) = roc_gen_llvm::llvm::build::construct_optimization_passes(module, opt_level);
 
     // Compile and add all the Procs before adding main
@@ -208,6 +223,11 @@ fn gen_from_mono_module_llvm<'a>(
             .keys()
             .copied()
             .collect(),
+        debug_sources: if emit_debug_info {
+            DebugSources::new(&dibuilder, &loaded.sources)
+        } else {
+            DebugSources::default()
+        },
     };
 
     // does not add any externs for this mode (we have a host) but cleans up some functions around
@@ -240,8 +260,9 @@ fn gen_from_mono_module_llvm<'a>(
 
     env.dibuilder.finalize();
 
-    // we don't use the debug info, and it causes weird errors.
-    module.strip_debug_info();
+    if !emit_debug_info {
+        module.strip_debug_info();
+    }
 
     // Uncomment this to see the module's optimized LLVM instruction output:
     // env.module.print_to_stderr();
@@ -263,8 +284,6 @@ fn gen_from_mono_module_llvm<'a>(
     // Uncomment this to see the module's optimized LLVM instruction output:
     // env.module.print_to_stderr();
 
-    // annotate the LLVM IR output with debug info
-    // so errors are reported with the line number of the LLVM source
     let memory_buffer = if cfg!(feature = "sanitizers") && std::env::var("ROC_SANITIZERS").is_ok() {
         let dir = tempfile::tempdir().unwrap();
         let dir = dir.into_path();
@@ -354,65 +373,6 @@ fn gen_from_mono_module_llvm<'a>(
 
         assert!(bc_to_object.status.success(), "{:#?}", bc_to_object);
 
-        MemoryBuffer::create_from_file(&app_o_file).expect("memory buffer creation works")
-    } else if emit_debug_info {
-        module.strip_debug_info();
-
-        let mut app_ll_dbg_file = PathBuf::from(roc_file_path);
-        app_ll_dbg_file.set_extension("dbg.ll");
-
-        let mut app_o_file = PathBuf::from(roc_file_path);
-        app_o_file.set_extension("o");
-
-        use std::process::Command;
-
-        // write the ll code to a file, so we can modify it
-        module.print_to_file(&app_ll_file).unwrap();
-
-        // run the debugir https://github.com/vaivaswatha/debugir tool
-        match Command::new("debugir")
-            .args(["-instnamer", app_ll_file.to_str().unwrap()])
-            .output()
-        {
-            Ok(_) => {}
-            Err(error) => {
-                use std::io::ErrorKind;
-                match error.kind() {
-                    ErrorKind::NotFound => internal_error!(
-                        r"I could not find the `debugir` tool on the PATH, install it from https://github.com/vaivaswatha/debugir"
-                    ),
-                    _ => internal_error!("{:?}", error),
-                }
-            }
-        }
-
-        use target_lexicon::Architecture;
-        match target.architecture {
-            Architecture::X86_64
-            | Architecture::X86_32(_)
-            | Architecture::Aarch64(_)
-            | Architecture::Wasm32 => {
-                // write the .o file. Note that this builds the .o for the local machine,
-                // and ignores the `target_machine` entirely.
-                //
-                // different systems name this executable differently, so we shotgun for
-                // the most common ones and then give up.
-                let ll_to_object = Command::new("llc")
-                    .args([
-                        "-relocation-model=pic",
-                        "-filetype=obj",
-                        app_ll_dbg_file.to_str().unwrap(),
-                        "-o",
-                        app_o_file.to_str().unwrap(),
-                    ])
-                    .output()
-                    .unwrap();
-
-                assert!(ll_to_object.stderr.is_empty(), "{:#?}", ll_to_object);
-            }
-            _ => unreachable!(),
-        }
-
         MemoryBuffer::create_from_file(&app_o_file).expect("memory buffer creation works")
     } else {
         // Emit the .o file
@@ -569,7 +529,6 @@ fn gen_from_mono_module_dev_assembly<'a>(
     let code_gen_start = Instant::now();
 
     let lazy_literals = true;
-    let generate_allocators = false; // provided by the platform
 
     let MonomorphizedModule {
         module_id,
@@ -585,7 +544,7 @@ fn gen_from_mono_module_dev_assembly<'a>(
         module_id,
         exposed_to_host: exposed_to_host.top_level_values.keys().copied().collect(),
         lazy_literals,
-        generate_allocators,
+        mode: roc_gen_dev::AssemblyBackendMode::Binary, // primitives are provided by the platform
     };
 
     let module_object =
@@ -663,10 +622,17 @@ pub fn handle_error_module(
     total_time: std::time::Duration,
     filename: &OsStr,
     print_run_anyway_hint: bool,
+    warnings: &WarningConfig,
+    render: RenderTarget,
 ) -> std::io::Result<i32> {
     debug_assert!(module.total_problems() > 0);
 
-    let problems = report_problems_typechecked(&mut module);
+    let problems = report_problems_typechecked(&mut module, render, warnings);
+
+    // Other programs only get the problems themselves
+    if render.is_machine_readable() {
+        return Ok(problems.exit_code());
+    }
 
     problems.print_to_stdout(total_time);
 
@@ -685,10 +651,13 @@ pub fn handle_error_module(
     Ok(problems.exit_code())
 }
 
-pub fn handle_loading_problem(problem: LoadingProblem) -> std::io::Result<i32> {
+pub fn handle_loading_problem(
+    problem: LoadingProblem,
+    render: RenderTarget,
+) -> std::io::Result<i32> {
     match problem {
         LoadingProblem::FormattedReport(report) => {
-            print!("{}", report);
+            print_fatal_report(&report, render);
             Ok(1)
         }
         _ => {
@@ -734,8 +703,10 @@ pub fn build_file<'a>(
     wasm_dev_stack_bytes: Option<u32>,
     roc_cache_dir: RocCacheDir<'_>,
     load_config: LoadConfig,
+    warnings: &WarningConfig,
 ) -> Result<BuiltFile<'a>, BuildFileError<'a>> {
     let compilation_start = Instant::now();
+    let render = load_config.render;
 
     // Step 1: compile the app and generate the .o file
     let loaded =
@@ -754,6 +725,59 @@ pub fn build_file<'a>(
         wasm_dev_stack_bytes,
         loaded,
         compilation_start,
+        render,
+        warnings,
+    )
+}
+
+/// Like `build_file`, but for an app whose source is in memory. Its imports are resolved as if it
+/// were the file `app_module_path`, and the executable is written to `output_dir`.
+#[allow(clippy::too_many_arguments)]
+pub fn build_str<'a>(
+    arena: &'a Bump,
+    target: &Triple,
+    app_module_path: PathBuf,
+    app_module_source: &'a str,
+    output_dir: &Path,
+    code_gen_options: CodeGenOptions,
+    link_type: LinkType,
+    linking_strategy: LinkingStrategy,
+    prebuilt_requested: bool,
+    roc_cache_dir: RocCacheDir<'_>,
+    load_config: LoadConfig,
+    warnings: &WarningConfig,
+) -> Result<BuiltFile<'a>, BuildFileError<'a>> {
+    let compilation_start = Instant::now();
+    let render = load_config.render;
+    let src_dir = app_module_path.parent().unwrap().to_path_buf();
+    let file_name = app_module_path.file_name().unwrap();
+
+    // Step 1: compile the app and generate the .o file
+    let loaded = roc_load::load_and_monomorphize_from_str(
+        arena,
+        app_module_path.clone(),
+        app_module_source,
+        src_dir,
+        roc_cache_dir,
+        load_config,
+    )
+    .map_err(|e| BuildFileError::from_mono_error(e, compilation_start))?;
+
+    // The executable goes beside the app module, so pretend that's in the output dir.
+    build_loaded_file(
+        arena,
+        target,
+        output_dir.join(file_name),
+        code_gen_options,
+        false,
+        link_type,
+        linking_strategy,
+        prebuilt_requested,
+        None,
+        loaded,
+        compilation_start,
+        render,
+        warnings,
     )
 }
 
@@ -770,6 +794,8 @@ fn build_loaded_file<'a>(
     wasm_dev_stack_bytes: Option<u32>,
     loaded: roc_load::MonomorphizedModule<'a>,
     compilation_start: Instant,
+    render: RenderTarget,
+    warnings: &WarningConfig,
 ) -> Result<BuiltFile<'a>, BuildFileError<'a>> {
     let operating_system = roc_target::OperatingSystem::from(target.operating_system);
 
@@ -871,7 +897,7 @@ fn build_loaded_file<'a>(
     // This only needs to be mutable for report_problems. This can't be done
     // inside a nested scope without causing a borrow error!
     let mut loaded = loaded;
-    let problems = report_problems_monomorphized(&mut loaded);
+    let problems = report_problems_monomorphized(&mut loaded, render, warnings);
     let loaded = loaded;
 
     enum HostRebuildTiming {
@@ -1161,6 +1187,8 @@ pub fn check_file<'a>(
     emit_timings: bool,
     roc_cache_dir: RocCacheDir<'_>,
     threading: Threading,
+    render: RenderTarget,
+    warnings: &WarningConfig,
 ) -> Result<(Problems, Duration), LoadingProblem<'a>> {
     let compilation_start = Instant::now();
 
@@ -1172,8 +1200,7 @@ pub fn check_file<'a>(
 
     let load_config = LoadConfig {
         target_info,
-        // TODO: expose this from CLI?
-        render: RenderTarget::ColorTerminal,
+        render,
         palette: DEFAULT_PALETTE,
         threading,
         exec_mode: ExecutionMode::Check,
@@ -1224,7 +1251,10 @@ pub fn check_file<'a>(
         println!("Finished checking in {} ms\n", compilation_end.as_millis(),);
     }
 
-    Ok((report_problems_typechecked(&mut loaded), compilation_end))
+    Ok((
+        report_problems_typechecked(&mut loaded, render, warnings),
+        compilation_end,
+    ))
 }
 
 pub fn build_str_test<'a>(
@@ -1251,6 +1281,7 @@ pub fn build_str_test<'a>(
     let threading = Threading::AtMost(2);
 
     let load_config = standard_load_config(&triple, build_ordering, threading);
+    let render = load_config.render;
 
     let compilation_start = std::time::Instant::now();
 
@@ -1277,5 +1308,7 @@ pub fn build_str_test<'a>(
         wasm_dev_stack_bytes,
         loaded,
         compilation_start,
+        render,
+        &WarningConfig::default(),
     )
 }
@@ -173,6 +173,9 @@ comptime {
 // Utils
 comptime {
     exportUtilsFn(utils.test_panic, "test_panic");
+    exportUtilsFn(utils.test_panic_jmp_buf_ptr, "test_panic_jmp_buf");
+    exportUtilsFn(utils.test_panic_tag_plus_one, "test_panic_tag");
+    exportUtilsFn(utils.test_panic_msg_ptr, "test_panic_msg");
     exportUtilsFn(utils.increfC, "incref");
     exportUtilsFn(utils.decrefC, "decref");
     exportUtilsFn(utils.decrefCheckNullC, "decref_check_null");
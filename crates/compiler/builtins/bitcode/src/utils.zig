@@ -105,17 +105,39 @@ pub fn memcpy(dst: [*]u8, src: [*]u8, size: usize) void {
     roc_memcpy(dst, src, size);
 }
 
+// The test implementation of roc_panic, used by the dev backend. The proc called by the test
+// sets up the jump buffer, and a crash jumps back to it. The test then reads the crash message
+// and tag (plus one, so zero means there was no crash).
+var test_panic_jmp_buf: [64]c_int align(16) = undefined;
+var test_panic_msg: [3]usize = .{ 0, 0, 0 };
+var test_panic_tag: u32 = 0;
+
+extern fn longjmp([*c]c_int, c_int) noreturn;
+
 // indirection because otherwise zig creates an alias to the panic function which our LLVM code
 // does not know how to deal with
-pub fn test_panic(c_ptr: *anyopaque, alignment: u32) callconv(.C) void {
-    _ = c_ptr;
-    _ = alignment;
-    // const cstr = @ptrCast([*:0]u8, c_ptr);
+pub fn test_panic(c_ptr: *anyopaque, tag: u32) callconv(.C) void {
+    if (@import("builtin").target.cpu.arch == .wasm32) {
+        unreachable;
+    } else {
+        // the message is a RocStr; its bytes outlive the crash, but the struct itself may not
+        test_panic_msg = @ptrCast(*[3]usize, @alignCast(@alignOf(usize), c_ptr)).*;
+        test_panic_tag = tag + 1;
+        longjmp(&test_panic_jmp_buf, 1);
+    }
+}
+
+pub fn test_panic_jmp_buf_ptr() callconv(.C) [*c]c_int {
+    test_panic_tag = 0;
+    return &test_panic_jmp_buf;
+}
 
-    // const stderr = std.io.getStdErr().writer();
-    // stderr.print("Roc panicked: {s}!\n", .{cstr}) catch unreachable;
+pub fn test_panic_tag_plus_one() callconv(.C) u32 {
+    return test_panic_tag;
+}
 
-    // std.c.exit(1);
+pub fn test_panic_msg_ptr() callconv(.C) *anyopaque {
+    return @ptrCast(*anyopaque, &test_panic_msg);
 }
 
 pub const Inc = fn (?[*]u8) callconv(.C) void;
@@ -375,6 +375,9 @@ pub const DEC_MUL_OR_PANIC: &str = "roc_builtins.dec.mul_or_panic";
 pub const DEC_MUL_SATURATED: &str = "roc_builtins.dec.mul_saturated";
 
 pub const UTILS_TEST_PANIC: &str = "roc_builtins.utils.test_panic";
+pub const UTILS_TEST_PANIC_JMP_BUF: &str = "roc_builtins.utils.test_panic_jmp_buf";
+pub const UTILS_TEST_PANIC_TAG: &str = "roc_builtins.utils.test_panic_tag";
+pub const UTILS_TEST_PANIC_MSG: &str = "roc_builtins.utils.test_panic_msg";
 pub const UTILS_ALLOCATE_WITH_REFCOUNT: &str = "roc_builtins.utils.allocate_with_refcount";
 pub const UTILS_INCREF: &str = "roc_builtins.utils.incref";
 pub const UTILS_DECREF: &str = "roc_builtins.utils.decref";
@@ -485,6 +485,89 @@ impl IAbilitiesStore<Resolved> {
     pub fn deserialize(bytes: &[u8]) -> (Self, usize) {
         serialize::deserialize(bytes)
     }
+
+    /// Replace every symbol in the store with `f(symbol)`, e.g. to move the symbols of a
+    /// deserialized store over to the module ids of the current compilation.
+    pub fn remap_symbols(&mut self, f: &mut impl FnMut(Symbol) -> Symbol) {
+        let Self {
+            members_of_ability,
+            specialization_to_root,
+            ability_members,
+            declared_implementations,
+            specializations,
+            next_specialization_id: _,
+            resolved_specializations,
+        } = self;
+
+        *members_of_ability = std::mem::take(members_of_ability)
+            .into_iter()
+            .map(|(ability, members)| {
+                let ability = f(ability);
+                let members = members.into_iter().map(&mut *f).collect();
+
+                (ability, members)
+            })
+            .collect();
+
+        *specialization_to_root = std::mem::take(specialization_to_root)
+            .into_iter()
+            .map(|(specialization, impl_key)| (f(specialization), impl_key.remap_symbols(f)))
+            .collect();
+
+        *ability_members = std::mem::take(ability_members)
+            .into_iter()
+            .map(|(member, mut data)| {
+                data.parent_ability = f(data.parent_ability);
+
+                (f(member), data)
+            })
+            .collect();
+
+        *declared_implementations = std::mem::take(declared_implementations)
+            .into_iter()
+            .map(|(impl_key, member_impl)| {
+                let member_impl = match member_impl {
+                    MemberImpl::Impl(symbol) => MemberImpl::Impl(f(symbol)),
+                    MemberImpl::Error => MemberImpl::Error,
+                };
+
+                (impl_key.remap_symbols(f), member_impl)
+            })
+            .collect();
+
+        *specializations = std::mem::take(specializations)
+            .into_iter()
+            .map(|(symbol, mut info)| {
+                info.symbol = f(info.symbol);
+
+                (f(symbol), info)
+            })
+            .collect();
+
+        for symbol in resolved_specializations.values_mut() {
+            *symbol = f(*symbol);
+        }
+    }
+}
+
+impl ImplKey {
+    /// Replace both symbols of the key with `f(symbol)`
+    pub fn remap_symbols(self, f: &mut impl FnMut(Symbol) -> Symbol) -> Self {
+        Self {
+            opaque: f(self.opaque),
+            ability_member: f(self.ability_member),
+        }
+    }
+}
+
+impl ResolvedImpl {
+    /// Replace the symbol of the implementation, if there is one, with `f(symbol)`
+    pub fn remap_symbols(&mut self, f: &mut impl FnMut(Symbol) -> Symbol) {
+        match self {
+            ResolvedImpl::Impl(info) => info.symbol = f(info.symbol),
+            ResolvedImpl::Error => {}
+        }
+    }
 }
 
 pub use serialize::deserialize_solved_implementations;
@@ -19,6 +19,7 @@ use roc_parse::header::HeaderType;
 use roc_parse::pattern::PatternType;
 use roc_problem::can::{Problem, RuntimeError};
 use roc_region::all::{Loc, Region};
+use roc_serialize::bytes::next_multiple_of;
 use roc_types::subs::{ExposedTypesStorageSubs, Subs, VarStore, Variable};
 use roc_types::types::{AbilitySet, Alias, AliasKind, AliasVar, Type};
 
@@ -1254,6 +1255,10 @@ pub struct TypeState {
     pub solved_implementations: ResolvedImplementations,
 }
 
+/// Subs, abilities and solved implementations are each serialized with offsets relative to their
+/// own start, so every one of them has to start at this alignment for their slices to be aligned.
+const TYPE_STATE_PART_ALIGNMENT: usize = 16;
+
 impl TypeState {
     pub fn serialize(&self, writer: &mut impl std::io::Write) -> std::io::Result<usize> {
         let Self {
@@ -1263,25 +1268,27 @@ impl TypeState {
             solved_implementations,
         } = self;
 
-        let written_subs = subs.serialize(exposed_vars_by_symbol, writer)?;
-        let written_ab = abilities.serialize(writer)?;
+        let written = subs.serialize(exposed_vars_by_symbol, writer)?;
+        let written = write_part_padding(writer, written)?;
+        let written = written + abilities.serialize(writer)?;
+        let written = write_part_padding(writer, written)?;
         let written_solved_impls =
             crate::abilities::serialize_solved_implementations(solved_implementations, writer)?;
 
-        Ok(written_subs + written_ab + written_solved_impls)
+        Ok(written + written_solved_impls)
     }
 
     pub fn deserialize(bytes: &[u8]) -> (Self, usize) {
         let ((subs, exposed_vars_by_symbol), len_subs) = Subs::deserialize(bytes);
-        let bytes = &bytes[len_subs..];
+        let offset = next_multiple_of(len_subs, TYPE_STATE_PART_ALIGNMENT);
 
-        let (abilities, len_abilities) = AbilitiesStore::deserialize(bytes);
-        let bytes = &bytes[len_abilities..];
+        let (abilities, len_abilities) = AbilitiesStore::deserialize(&bytes[offset..]);
+        let offset = next_multiple_of(offset + len_abilities, TYPE_STATE_PART_ALIGNMENT);
 
         let (solved_implementations, len_solved_impls) =
-            crate::abilities::deserialize_solved_implementations(bytes);
+            crate::abilities::deserialize_solved_implementations(&bytes[offset..]);
 
-        let total_offset = len_subs + len_abilities + len_solved_impls;
+        let total_offset = offset + len_solved_impls;
 
         (
             Self {
@@ -1293,4 +1300,40 @@ impl TypeState {
             total_offset,
         )
     }
+
+    /// Replace every symbol in the type state with `f(symbol)`, e.g. to move the symbols of a
+    /// deserialized type state over to the module ids of the current compilation.
+    pub fn remap_symbols(&mut self, f: &mut impl FnMut(Symbol) -> Symbol) {
+        let Self {
+            subs,
+            exposed_vars_by_symbol,
+            abilities,
+            solved_implementations,
+        } = self;
+
+        subs.remap_symbols(f);
+
+        for (symbol, _) in exposed_vars_by_symbol.iter_mut() {
+            *symbol = f(*symbol);
+        }
+
+        abilities.remap_symbols(f);
+
+        *solved_implementations = std::mem::take(solved_implementations)
+            .into_iter()
+            .map(|(impl_key, mut resolved_impl)| {
+                resolved_impl.remap_symbols(f);
+
+                (impl_key.remap_symbols(f), resolved_impl)
+            })
+            .collect();
+    }
+}
+
+fn write_part_padding(writer: &mut impl std::io::Write, written: usize) -> std::io::Result<usize> {
+    let aligned = next_multiple_of(written, TYPE_STATE_PART_ALIGNMENT);
+
+    writer.write_all(&[0; TYPE_STATE_PART_ALIGNMENT][..aligned - written])?;
+
+    Ok(aligned)
 }
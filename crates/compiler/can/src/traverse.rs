@@ -1,7 +1,7 @@
 //! Traversals over the can ast.
 
 use roc_module::{ident::Lowercase, symbol::Symbol};
-use roc_region::all::{Loc, Region};
+use roc_region::all::{Loc, Position, Region};
 use roc_types::{subs::Variable, types::MemberImpl};
 
 use crate::{
@@ -664,6 +664,86 @@ pub fn find_type_at(region: Region, decls: &Declarations) -> Option<Variable> {
     visitor.typ
 }
 
+/// Attempts to find the type of the smallest expression or pattern whose region contains `pos`.
+pub fn find_closest_type_at(pos: Position, decls: &Declarations) -> Option<(Region, Variable)> {
+    let mut visitor = Finder { pos, found: None };
+    visitor.visit_decls(decls);
+    return visitor.found;
+
+    struct Finder {
+        pos: Position,
+        found: Option<(Region, Variable)>,
+    }
+
+    impl Visitor for Finder {
+        fn should_visit(&mut self, region: Region) -> bool {
+            region.contains_pos(self.pos)
+        }
+
+        fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+            if region.contains_pos(self.pos) {
+                // Anything found while walking the children is more specific than this.
+                self.found = Some((region, var));
+
+                walk_expr(self, expr, var);
+            }
+        }
+
+        fn visit_pattern(&mut self, pattern: &Pattern, region: Region, opt_var: Option<Variable>) {
+            if region.contains_pos(self.pos) {
+                if let Some(var) = opt_var {
+                    self.found = Some((region, var));
+                }
+
+                walk_pattern(self, pattern);
+            }
+        }
+    }
+}
+
+/// Attempts to find the symbol referenced or introduced by the smallest variable or identifier
+/// pattern whose region contains `pos`.
+pub fn find_closest_symbol_at(pos: Position, decls: &Declarations) -> Option<Loc<Symbol>> {
+    let mut visitor = Finder { pos, found: None };
+    visitor.visit_decls(decls);
+    return visitor.found;
+
+    struct Finder {
+        pos: Position,
+        found: Option<Loc<Symbol>>,
+    }
+
+    impl Visitor for Finder {
+        fn should_visit(&mut self, region: Region) -> bool {
+            region.contains_pos(self.pos)
+        }
+
+        fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+            if region.contains_pos(self.pos) {
+                match expr {
+                    Expr::Var(symbol, _) | Expr::AbilityMember(symbol, _, _) => {
+                        self.found = Some(Loc::at(region, *symbol));
+                    }
+                    _ => walk_expr(self, expr, var),
+                }
+            }
+        }
+
+        fn visit_pattern(&mut self, pattern: &Pattern, region: Region, _opt_var: Option<Variable>) {
+            if region.contains_pos(self.pos) {
+                match pattern {
+                    Pattern::Identifier(symbol)
+                    | Pattern::Shadowed(_, _, symbol)
+                    | Pattern::AbilityMemberSpecialization { ident: symbol, .. } => {
+                        self.found = Some(Loc::at(region, *symbol));
+                    }
+                    _ => walk_pattern(self, pattern),
+                }
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum FoundSymbol {
     /// Specialization(T, foo1) is the specialization of foo for T.
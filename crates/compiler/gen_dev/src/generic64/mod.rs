@@ -1,6 +1,6 @@
 use crate::{
-    single_register_floats, single_register_int_builtins, single_register_integers, Backend, Env,
-    Relocation,
+    single_register_floats, single_register_int_builtins, single_register_integers,
+    AssemblyBackendMode, Backend, Env, Relocation,
 };
 use bumpalo::collections::{CollectIn, Vec};
 use roc_builtins::bitcode::{self, FloatWidth, IntWidth};
@@ -9,14 +9,16 @@ use roc_error_macros::internal_error;
 use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_mono::code_gen_help::{CallerProc, CodeGenHelp, HelperOp};
 use roc_mono::ir::{
-    BranchInfo, HigherOrderLowLevel, JoinPointId, ListLiteralElement, Literal, Param, ProcLayout,
-    SelfRecursive, Stmt,
+    BranchInfo, CrashTag, HigherOrderLowLevel, JoinPointId, ListLiteralElement, Literal,
+    LookupType, Param, ProcLayout, SelfRecursive, Stmt,
 };
 use roc_mono::layout::{
     Builtin, InLayout, Layout, LayoutInterner, STLayoutInterner, TagIdIntType, UnionLayout,
 };
 use roc_mono::low_level::HigherOrder;
+use roc_region::all::Region;
 use roc_target::TargetInfo;
+use roc_types::subs::Variable;
 use std::marker::PhantomData;
 
 pub(crate) mod aarch64;
@@ -2522,6 +2524,138 @@ impl<
         });
     }
 
+    fn build_expect(
+        &mut self,
+        condition: Symbol,
+        region: Region,
+        lookups: &'a [Symbol],
+        variables: &'a [LookupType],
+    ) {
+        if let AssemblyBackendMode::Test = self.env.mode {
+            // there is no parent process to report failures to
+            return;
+        }
+
+        // The failure block makes calls, so nothing can be kept in registers across it.
+        self.storage_manager.free_all_to_stack(&mut self.buf);
+        let cond_reg = self
+            .storage_manager
+            .load_to_general_reg(&mut self.buf, &condition);
+        self.storage_manager.free_all_to_stack(&mut self.buf);
+
+        // Skip the failure block if the condition holds.
+        // Since we don't know the offset yet, set it to 0 and overwrite later.
+        let jne_location = self.buf.len();
+        let start_offset = ASM::jne_reg64_imm64_imm32(&mut self.buf, cond_reg, 0, 0);
+
+        self.with_isolated_storage(|backend| {
+            let shared_memory =
+                backend.clone_to_shared_memory(condition, region, lookups, variables);
+            backend.build_notify_parent(bitcode::NOTIFY_PARENT_EXPECT, shared_memory);
+        });
+
+        self.update_jne_imm32_offset(jne_location, start_offset, cond_reg, 0);
+    }
+
+    fn build_dbg(&mut self, symbol: Symbol, variable: Variable) {
+        if let AssemblyBackendMode::Test = self.env.mode {
+            return;
+        }
+
+        // same as the llvm backend, the symbol is used as the region of a dbg
+        let region = unsafe { std::mem::transmute::<Symbol, Region>(symbol) };
+
+        // The calls below would clobber anything kept in registers.
+        self.storage_manager.free_all_to_stack(&mut self.buf);
+        self.with_isolated_storage(|backend| {
+            let shared_memory =
+                backend.clone_to_shared_memory(symbol, region, &[symbol], &[variable]);
+            backend.build_notify_parent(bitcode::NOTIFY_PARENT_DBG, shared_memory);
+        });
+    }
+
+    fn build_crash(&mut self, msg: Symbol, tag: CrashTag) {
+        // roc_panic takes the message by reference
+        self.storage_manager
+            .ensure_symbol_on_stack(&mut self.buf, &msg);
+        let (msg_offset, _) = self.storage_manager.stack_offset_and_size(&msg);
+
+        let msg_ptr = self.debug_symbol("msg_ptr");
+        let msg_ptr_reg = self
+            .storage_manager
+            .claim_general_reg(&mut self.buf, &msg_ptr);
+        ASM::add_reg64_reg64_imm32(&mut self.buf, msg_ptr_reg, CC::BASE_PTR_REG, msg_offset);
+
+        let tag_id = self.debug_symbol("tag_id");
+        let tag_literal = Literal::Int((tag as u32 as i128).to_ne_bytes());
+        self.load_literal(&tag_id, &Layout::U32, &tag_literal);
+
+        let ignored = self.debug_symbol("ignored");
+        self.build_fn_call(
+            &ignored,
+            "roc_panic".to_string(),
+            &[msg_ptr, tag_id],
+            &[Layout::U64, Layout::U32],
+            &Layout::UNIT,
+        );
+
+        self.free_symbol(&msg_ptr);
+        self.free_symbol(&tag_id);
+        self.free_symbol(&ignored);
+    }
+
+    fn build_test_setjmp(&mut self) {
+        let jmp_buf = self.debug_symbol("jmp_buf");
+        self.build_fn_call(
+            &jmp_buf,
+            bitcode::UTILS_TEST_PANIC_JMP_BUF.to_string(),
+            &[],
+            &[],
+            &Layout::U64,
+        );
+
+        // setjmp returns a second time, with a non-zero value, when roc_panic jumps back
+        let jumped = self.debug_symbol("jumped");
+        self.build_fn_call(
+            &jumped,
+            bitcode::UTILS_SETJMP.to_string(),
+            &[jmp_buf],
+            &[Layout::U64],
+            &Layout::U32,
+        );
+        let jumped_reg = self
+            .storage_manager
+            .load_to_general_reg(&mut self.buf, &jumped);
+
+        // Since we don't know the offsets yet, set them to 0 and overwrite later.
+        let jne_location = self.buf.len();
+        let start_offset = ASM::jne_reg64_imm64_imm32(&mut self.buf, jumped_reg, 0, 0);
+        let skip_location = self.buf.len();
+        let skip_start_offset = ASM::jmp_imm32(&mut self.buf, 0x1234_5678);
+        self.update_jne_imm32_offset(jne_location, start_offset, jumped_reg, 0);
+
+        // The proc crashed, so return without a value.
+        let inst_loc = self.buf.len() as u64;
+        let offset = ASM::jmp_imm32(&mut self.buf, 0x1234_5678) as u64;
+        self.relocs.push(Relocation::JmpToReturn {
+            inst_loc,
+            inst_size: self.buf.len() as u64 - inst_loc,
+            offset,
+        });
+
+        let mut tmp = bumpalo::vec![in self.env.arena];
+        let continue_offset = self.buf.len() as u64;
+        self.update_jmp_imm32_offset(
+            &mut tmp,
+            skip_location as u64,
+            skip_start_offset as u64,
+            continue_offset,
+        );
+
+        self.free_symbol(&jmp_buf);
+        self.free_symbol(&jumped);
+    }
+
     fn build_int_bitwise_and(
         &mut self,
         dst: &Symbol,
@@ -3045,6 +3179,641 @@ impl<
 
         self.load_literal(&symbol, &u64_layout, &width_literal);
     }
+
+    /// Overwrites the jne at `jne_location` so that it jumps to the current end of the buffer.
+    fn update_jne_imm32_offset(
+        &mut self,
+        jne_location: usize,
+        start_offset: usize,
+        reg: GeneralReg,
+        imm: u64,
+    ) {
+        let mut tmp = bumpalo::vec![in self.env.arena];
+        let jne_offset = self.buf.len() - start_offset;
+        ASM::jne_reg64_imm64_imm32(&mut tmp, reg, imm, jne_offset as i32);
+        for (i, byte) in tmp.iter().enumerate() {
+            self.buf[jne_location + i] = *byte;
+        }
+    }
+
+    /// Generates code with `build` and then goes back to the storage from before it, as is done for
+    /// the branches of a switch. Values that must survive the generated code have to be on the stack.
+    fn with_isolated_storage<F: FnOnce(&mut Self)>(&mut self, build: F) {
+        let base_storage = self.storage_manager.clone();
+        let base_literal_map = self.literal_map.clone();
+
+        build(self);
+
+        let branch_storage = std::mem::replace(&mut self.storage_manager, base_storage);
+        self.storage_manager.merge_branch(&branch_storage);
+        self.literal_map = base_literal_map;
+    }
+
+    /// Like `with_tmp_general_reg`, but with three registers. They are not safe across calls.
+    fn with_tmp_general_regs<F: FnOnce(&mut Vec<'a, u8>, GeneralReg, GeneralReg, GeneralReg)>(
+        &mut self,
+        callback: F,
+    ) {
+        self.storage_manager
+            .with_tmp_general_reg(&mut self.buf, |storage_manager, buf, reg1| {
+                storage_manager.with_tmp_general_reg(buf, |storage_manager, buf, reg2| {
+                    storage_manager
+                        .with_tmp_general_reg(buf, |_, buf, reg3| callback(buf, reg1, reg2, reg3))
+                })
+            });
+    }
+
+    /// Claims 8 bytes of stack, to keep a value of the generated code around across calls.
+    /// Returns the base offset of the slot.
+    fn claim_stack_slot(&mut self, name: &str) -> i32 {
+        let symbol = self.debug_symbol(name);
+        self.storage_manager.claim_stack_area(&symbol, 8)
+    }
+
+    /// Loads the value of a stack slot plus a constant into a new symbol.
+    fn load_stack_slot(&mut self, name: &str, (slot, delta): (i32, i32)) -> Symbol {
+        let symbol = self.debug_symbol(name);
+        let reg = self
+            .storage_manager
+            .claim_general_reg(&mut self.buf, &symbol);
+        ASM::mov_reg64_base32(&mut self.buf, reg, slot);
+        if delta != 0 {
+            ASM::add_reg64_reg64_imm32(&mut self.buf, reg, reg, delta);
+        }
+        symbol
+    }
+
+    /// Loads the address `shared_memory + offset` into `dst`, where both live in stack slots.
+    fn load_shared_memory_address(
+        buf: &mut Vec<'a, u8>,
+        dst: GeneralReg,
+        tmp: GeneralReg,
+        shared_memory_slot: i32,
+        offset_slot: i32,
+    ) {
+        ASM::mov_reg64_base32(buf, dst, shared_memory_slot);
+        ASM::mov_reg64_base32(buf, tmp, offset_slot);
+        ASM::add_reg64_reg64_reg64(buf, dst, dst, tmp);
+    }
+
+    fn build_notify_parent(&mut self, notify_function: &str, shared_memory: Symbol) {
+        let ignored = self.debug_symbol("ignored");
+        self.build_fn_call(
+            &ignored,
+            notify_function.to_string(),
+            &[shared_memory],
+            &[Layout::U64],
+            &Layout::UNIT,
+        );
+        self.free_symbol(&ignored);
+    }
+
+    /// Writes an expect frame with the values of the lookups to the shared memory, in the same
+    /// format as the llvm backend (see `roc_gen_llvm::llvm::expect::clone_to_shared_memory`).
+    /// Returns the symbol that holds the pointer to the shared memory.
+    fn clone_to_shared_memory(
+        &mut self,
+        condition: Symbol,
+        region: Region,
+        lookups: &[Symbol],
+        variables: &[LookupType],
+    ) -> Symbol {
+        const HEADER_SIZE: i32 = 3 * 4;
+        const LOOKUP_SIZE: i32 = 8 + 4;
+
+        let shared_memory = self.debug_symbol("shared_memory");
+        self.build_fn_call(
+            &shared_memory,
+            bitcode::UTILS_EXPECT_FAILED_START_SHARED_FILE.to_string(),
+            &[],
+            &[],
+            &Layout::U64,
+        );
+        self.storage_manager
+            .ensure_symbol_on_stack(&mut self.buf, &shared_memory);
+        let (shared_memory_slot, _) = self.storage_manager.stack_offset_and_size(&shared_memory);
+
+        let frame_slot = self.claim_stack_slot("frame_start");
+        let offset_slot = self.claim_stack_slot("offset");
+        let extra_offset_slot = self.claim_stack_slot("extra_offset");
+        let source_slot = self.claim_stack_slot("source");
+
+        // The frame starts at the next free byte, with the module and region of the expect.
+        let module_id: u32 = unsafe { std::mem::transmute(condition.module_id()) };
+        let header = [region.start().offset, region.end().offset, module_id];
+        let lookups_size = LOOKUP_SIZE * lookups.len() as i32;
+        self.with_tmp_general_regs(|buf, ptr_reg, offset_reg, tmp_reg| {
+            ASM::mov_reg64_base32(buf, ptr_reg, shared_memory_slot);
+            ASM::mov_reg64_mem64_offset32(buf, offset_reg, ptr_reg, 8);
+            ASM::mov_base32_reg64(buf, frame_slot, offset_reg);
+            ASM::add_reg64_reg64_reg64(buf, ptr_reg, ptr_reg, offset_reg);
+            for (i, value) in header.iter().enumerate() {
+                ASM::mov_reg64_imm64(buf, tmp_reg, *value as i64);
+                ASM::mov_mem32_offset32_reg32(buf, ptr_reg, 4 * i as i32, tmp_reg);
+            }
+            ASM::add_reg64_reg64_imm32(buf, offset_reg, offset_reg, HEADER_SIZE + lookups_size);
+            ASM::mov_base32_reg64(buf, offset_slot, offset_reg);
+        });
+
+        for (i, (lookup, variable)) in lookups.iter().zip(variables).enumerate() {
+            let layout = self.layout_map[lookup];
+            let stack_size = self.layout_interner.stack_size(layout);
+            let entry = HEADER_SIZE + LOOKUP_SIZE * i as i32;
+
+            // Point the lookup table at the value, and store its specialized variable.
+            // Any data the value points to goes right after it.
+            self.with_tmp_general_regs(|buf, ptr_reg, tmp_reg, _| {
+                Self::load_shared_memory_address(
+                    buf,
+                    ptr_reg,
+                    tmp_reg,
+                    shared_memory_slot,
+                    frame_slot,
+                );
+                ASM::mov_reg64_base32(buf, tmp_reg, offset_slot);
+                ASM::mov_mem64_offset32_reg64(buf, ptr_reg, entry, tmp_reg);
+                ASM::add_reg64_reg64_imm32(buf, tmp_reg, tmp_reg, stack_size as i32);
+                ASM::mov_base32_reg64(buf, extra_offset_slot, tmp_reg);
+                ASM::mov_reg64_imm64(buf, tmp_reg, variable.index() as i64);
+                ASM::mov_mem32_offset32_reg32(buf, ptr_reg, entry + 8, tmp_reg);
+            });
+
+            if stack_size > 0 {
+                self.storage_manager
+                    .ensure_symbol_on_stack(&mut self.buf, lookup);
+                let (value_offset, _) = self.storage_manager.stack_offset_and_size(lookup);
+                self.with_tmp_general_regs(|buf, tmp_reg, _, _| {
+                    ASM::add_reg64_reg64_imm32(buf, tmp_reg, CC::BASE_PTR_REG, value_offset);
+                    ASM::mov_base32_reg64(buf, source_slot, tmp_reg);
+                });
+
+                self.clone_value_to_shared_memory(
+                    layout,
+                    shared_memory_slot,
+                    (source_slot, 0),
+                    (offset_slot, 0),
+                    extra_offset_slot,
+                );
+            }
+
+            // the next value goes after all the data of this one
+            self.with_tmp_general_regs(|buf, tmp_reg, _, _| {
+                ASM::mov_reg64_base32(buf, tmp_reg, extra_offset_slot);
+                ASM::mov_base32_reg64(buf, offset_slot, tmp_reg);
+            });
+        }
+
+        // Update the number of frames, and the next free byte.
+        self.with_tmp_general_regs(|buf, ptr_reg, tmp_reg, _| {
+            ASM::mov_reg64_base32(buf, ptr_reg, shared_memory_slot);
+            ASM::mov_reg64_mem64_offset32(buf, tmp_reg, ptr_reg, 0);
+            ASM::add_reg64_reg64_imm32(buf, tmp_reg, tmp_reg, 1);
+            ASM::mov_mem64_offset32_reg64(buf, ptr_reg, 0, tmp_reg);
+            ASM::mov_reg64_base32(buf, tmp_reg, offset_slot);
+            ASM::mov_mem64_offset32_reg64(buf, ptr_reg, 8, tmp_reg);
+        });
+
+        shared_memory
+    }
+
+    /// Clones the value at the address in `source` to the offset in `destination` of the shared memory.
+    /// Both are given as a stack slot holding the address or offset, and a constant to add to it.
+    /// The data the value points to is cloned to the offset in `extra_offset_slot`, which is
+    /// moved past the cloned data.
+    fn clone_value_to_shared_memory(
+        &mut self,
+        layout: InLayout<'a>,
+        shared_memory_slot: i32,
+        source: (i32, i32),
+        destination: (i32, i32),
+        extra_offset_slot: i32,
+    ) {
+        let stack_size = self.layout_interner.stack_size(layout);
+
+        match self.layout_interner.get(layout) {
+            // Since we will never actually display functions (and hence lambda sets)
+            // we just write nothing to the buffer
+            Layout::LambdaSet(_) => {}
+            _ if stack_size == 0 => {}
+            _ if self.layout_interner.safe_to_memcpy(layout) => {
+                self.with_tmp_general_regs(|buf, src_reg, dst_reg, tmp_reg| {
+                    ASM::mov_reg64_base32(buf, src_reg, source.0);
+                    Self::load_shared_memory_address(
+                        buf,
+                        dst_reg,
+                        tmp_reg,
+                        shared_memory_slot,
+                        destination.0,
+                    );
+
+                    // a crude memcpy
+                    let size = stack_size as i32;
+                    let mut copied = 0;
+                    while size - copied >= 8 {
+                        ASM::mov_reg64_mem64_offset32(buf, tmp_reg, src_reg, source.1 + copied);
+                        ASM::mov_mem64_offset32_reg64(
+                            buf,
+                            dst_reg,
+                            destination.1 + copied,
+                            tmp_reg,
+                        );
+                        copied += 8;
+                    }
+                    if size - copied >= 4 {
+                        ASM::mov_reg32_mem32_offset32(buf, tmp_reg, src_reg, source.1 + copied);
+                        ASM::mov_mem32_offset32_reg32(
+                            buf,
+                            dst_reg,
+                            destination.1 + copied,
+                            tmp_reg,
+                        );
+                        copied += 4;
+                    }
+                    if size - copied >= 2 {
+                        ASM::mov_reg16_mem16_offset32(buf, tmp_reg, src_reg, source.1 + copied);
+                        ASM::mov_mem16_offset32_reg16(
+                            buf,
+                            dst_reg,
+                            destination.1 + copied,
+                            tmp_reg,
+                        );
+                        copied += 2;
+                    }
+                    if size - copied >= 1 {
+                        ASM::mov_reg8_mem8_offset32(buf, tmp_reg, src_reg, source.1 + copied);
+                        ASM::mov_mem8_offset32_reg8(buf, dst_reg, destination.1 + copied, tmp_reg);
+                    }
+                });
+            }
+            Layout::Builtin(Builtin::Str) => {
+                // strCloneTo takes the string by value
+                let string = self.debug_symbol("string");
+                let string_offset = self.storage_manager.claim_stack_area(&string, 24);
+                self.with_tmp_general_regs(|buf, src_reg, tmp_reg, _| {
+                    ASM::mov_reg64_base32(buf, src_reg, source.0);
+                    for i in (0..24).step_by(8) {
+                        ASM::mov_reg64_mem64_offset32(buf, tmp_reg, src_reg, source.1 + i);
+                        ASM::mov_base32_reg64(buf, string_offset + i, tmp_reg);
+                    }
+                });
+
+                let shared_memory = self.load_stack_slot("shared_memory", (shared_memory_slot, 0));
+                let offset = self.load_stack_slot("offset", destination);
+                let extra_offset = self.load_stack_slot("extra_offset", (extra_offset_slot, 0));
+                let new_extra_offset = self.debug_symbol("new_extra_offset");
+                self.build_fn_call(
+                    &new_extra_offset,
+                    bitcode::STR_CLONE_TO.to_string(),
+                    &[string, shared_memory, offset, extra_offset],
+                    &[Layout::STR, Layout::U64, Layout::U64, Layout::U64],
+                    &Layout::U64,
+                );
+
+                let new_extra_offset_reg = self
+                    .storage_manager
+                    .load_to_general_reg(&mut self.buf, &new_extra_offset);
+                ASM::mov_base32_reg64(&mut self.buf, extra_offset_slot, new_extra_offset_reg);
+
+                for symbol in [
+                    string,
+                    shared_memory,
+                    offset,
+                    extra_offset,
+                    new_extra_offset,
+                ] {
+                    self.free_symbol(&symbol);
+                }
+            }
+            Layout::Builtin(Builtin::List(element_layout)) => {
+                self.clone_list_to_shared_memory(
+                    element_layout,
+                    shared_memory_slot,
+                    source,
+                    destination,
+                    extra_offset_slot,
+                );
+            }
+            Layout::Struct { field_layouts, .. } => {
+                let mut field_offset = 0;
+                for field_layout in field_layouts {
+                    self.clone_value_to_shared_memory(
+                        *field_layout,
+                        shared_memory_slot,
+                        (source.0, source.1 + field_offset),
+                        (destination.0, destination.1 + field_offset),
+                        extra_offset_slot,
+                    );
+                    field_offset += self.layout_interner.stack_size(*field_layout) as i32;
+                }
+            }
+            Layout::Boxed(inner_layout) => {
+                // Write the offset of the inner value in place of the pointer,
+                // and clone the inner value to that offset.
+                let inner_stack_size = self.layout_interner.stack_size(inner_layout) as i32;
+                let inner_source_slot = self.claim_stack_slot("inner_source");
+                let inner_destination_slot = self.claim_stack_slot("inner_destination");
+                self.with_tmp_general_regs(|buf, dst_reg, tmp_reg, extra_reg| {
+                    Self::load_shared_memory_address(
+                        buf,
+                        dst_reg,
+                        tmp_reg,
+                        shared_memory_slot,
+                        destination.0,
+                    );
+                    ASM::mov_reg64_base32(buf, extra_reg, extra_offset_slot);
+                    ASM::mov_mem64_offset32_reg64(buf, dst_reg, destination.1, extra_reg);
+                    ASM::mov_base32_reg64(buf, inner_destination_slot, extra_reg);
+                    ASM::add_reg64_reg64_imm32(buf, extra_reg, extra_reg, inner_stack_size);
+                    ASM::mov_base32_reg64(buf, extra_offset_slot, extra_reg);
+
+                    ASM::mov_reg64_base32(buf, tmp_reg, source.0);
+                    ASM::mov_reg64_mem64_offset32(buf, tmp_reg, tmp_reg, source.1);
+                    ASM::mov_base32_reg64(buf, inner_source_slot, tmp_reg);
+                });
+
+                self.clone_value_to_shared_memory(
+                    inner_layout,
+                    shared_memory_slot,
+                    (inner_source_slot, 0),
+                    (inner_destination_slot, 0),
+                    extra_offset_slot,
+                );
+            }
+            Layout::Union(UnionLayout::NonRecursive(tags)) => {
+                self.clone_union_to_shared_memory(
+                    tags,
+                    shared_memory_slot,
+                    source,
+                    destination,
+                    extra_offset_slot,
+                );
+            }
+            Layout::Union(_) | Layout::RecursivePointer(_) => {
+                // The dev backend cannot construct recursive tag unions yet (see `create_union`),
+                // so there are no values of these layouts to clone.
+                todo!(
+                    "cloning recursive tag unions to shared memory, {:?}",
+                    self.layout_interner.dbg(layout)
+                )
+            }
+            Layout::Builtin(_) => {
+                internal_error!("numbers and booleans are safe to memcpy")
+            }
+        }
+    }
+
+    /// Clones a non-recursive tag union: the payload of the active tag goes at the start,
+    /// followed by the tag id, just like the value itself is laid out.
+    fn clone_union_to_shared_memory(
+        &mut self,
+        tags: &'a [&'a [InLayout<'a>]],
+        shared_memory_slot: i32,
+        source: (i32, i32),
+        destination: (i32, i32),
+        extra_offset_slot: i32,
+    ) {
+        let union_layout = UnionLayout::NonRecursive(tags);
+        let (data_size, data_alignment) = union_layout
+            .data_size_and_alignment(self.layout_interner, self.storage_manager.target_info());
+        let id_offset = (data_size - data_alignment) as i32;
+        let id_size = union_layout.discriminant().stack_size();
+
+        // The branches make calls, so the tag id has to be kept on the stack.
+        self.storage_manager.free_all_to_stack(&mut self.buf);
+        let tag_id_slot = self.claim_stack_slot("tag_id");
+        self.with_tmp_general_regs(|buf, src_reg, tmp_reg, _| {
+            ASM::mov_reg64_base32(buf, src_reg, source.0);
+            ASM::mov_reg64_imm64(buf, tmp_reg, 0);
+            match id_size {
+                0 => {}
+                1 => ASM::mov_reg8_mem8_offset32(buf, tmp_reg, src_reg, source.1 + id_offset),
+                2 => ASM::mov_reg16_mem16_offset32(buf, tmp_reg, src_reg, source.1 + id_offset),
+                _ => internal_error!("tag ids are at most 2 bytes"),
+            }
+            ASM::mov_base32_reg64(buf, tag_id_slot, tmp_reg);
+        });
+
+        let mut end_jumps = bumpalo::vec![in self.env.arena];
+        for (tag_id, field_layouts) in tags.iter().enumerate() {
+            let tag_id = tag_id as u64;
+
+            // Skip this tag if it is not the active one.
+            // Since we don't know the offset yet, set it to 0 and overwrite later.
+            let mut tag_jne = None;
+            self.storage_manager
+                .with_tmp_general_reg(&mut self.buf, |_, buf, tag_id_reg| {
+                    ASM::mov_reg64_base32(buf, tag_id_reg, tag_id_slot);
+                    let jne_location = buf.len();
+                    let start_offset = ASM::jne_reg64_imm64_imm32(buf, tag_id_reg, tag_id, 0);
+                    tag_jne = Some((jne_location, start_offset, tag_id_reg));
+                });
+            let (jne_location, start_offset, tag_id_reg) = tag_jne.unwrap();
+
+            self.with_isolated_storage(|backend| {
+                let mut field_offset = 0;
+                for field_layout in field_layouts.iter() {
+                    backend.clone_value_to_shared_memory(
+                        *field_layout,
+                        shared_memory_slot,
+                        (source.0, source.1 + field_offset),
+                        (destination.0, destination.1 + field_offset),
+                        extra_offset_slot,
+                    );
+                    field_offset += backend.layout_interner.stack_size(*field_layout) as i32;
+                }
+
+                backend.with_tmp_general_regs(|buf, dst_reg, tmp_reg, _| {
+                    Self::load_shared_memory_address(
+                        buf,
+                        dst_reg,
+                        tmp_reg,
+                        shared_memory_slot,
+                        destination.0,
+                    );
+                    ASM::mov_reg64_imm64(buf, tmp_reg, tag_id as i64);
+                    let offset = destination.1 + id_offset;
+                    match id_size {
+                        0 => {}
+                        1 => ASM::mov_mem8_offset32_reg8(buf, dst_reg, offset, tmp_reg),
+                        _ => ASM::mov_mem16_offset32_reg16(buf, dst_reg, offset, tmp_reg),
+                    }
+                });
+
+                // Jump past the other tags.
+                // Since we don't know the offset yet, set it to 0 and overwrite later.
+                let jmp_location = backend.buf.len();
+                let jmp_offset = ASM::jmp_imm32(&mut backend.buf, 0x1234_5678);
+                end_jumps.push((jmp_location, jmp_offset));
+            });
+
+            self.update_jne_imm32_offset(jne_location, start_offset, tag_id_reg, tag_id);
+        }
+
+        let mut tmp = bumpalo::vec![in self.env.arena];
+        let end_offset = self.buf.len();
+        for (jmp_location, start_offset) in end_jumps {
+            self.update_jmp_imm32_offset(
+                &mut tmp,
+                jmp_location as u64,
+                start_offset as u64,
+                end_offset as u64,
+            );
+        }
+    }
+
+    fn clone_list_to_shared_memory(
+        &mut self,
+        element_layout: InLayout<'a>,
+        shared_memory_slot: i32,
+        source: (i32, i32),
+        destination: (i32, i32),
+        extra_offset_slot: i32,
+    ) {
+        let element_size = self.layout_interner.stack_size(element_layout) as i32;
+        let elements_slot = self.claim_stack_slot("elements");
+        let length_slot = self.claim_stack_slot("length");
+        let element_destination_slot = self.claim_stack_slot("element_destination");
+
+        // We only copy the elements we actually have (and skip extra capacity).
+        // The elements go to the extra offset, and any data they point to after them.
+        self.with_tmp_general_regs(|buf, src_reg, dst_reg, tmp_reg| {
+            ASM::mov_reg64_base32(buf, src_reg, source.0);
+            ASM::mov_reg64_mem64_offset32(buf, tmp_reg, src_reg, source.1);
+            ASM::mov_base32_reg64(buf, elements_slot, tmp_reg);
+            ASM::mov_reg64_mem64_offset32(buf, tmp_reg, src_reg, source.1 + 8);
+            ASM::mov_base32_reg64(buf, length_slot, tmp_reg);
+
+            Self::load_shared_memory_address(
+                buf,
+                dst_reg,
+                tmp_reg,
+                shared_memory_slot,
+                destination.0,
+            );
+            ASM::mov_reg64_base32(buf, tmp_reg, extra_offset_slot);
+            ASM::mov_base32_reg64(buf, element_destination_slot, tmp_reg);
+            ASM::mov_mem64_offset32_reg64(buf, dst_reg, destination.1, tmp_reg);
+            ASM::mov_reg64_base32(buf, tmp_reg, length_slot);
+            ASM::mov_mem64_offset32_reg64(buf, dst_reg, destination.1 + 8, tmp_reg);
+            ASM::mov_mem64_offset32_reg64(buf, dst_reg, destination.1 + 16, tmp_reg);
+
+            ASM::mov_reg64_imm64(buf, dst_reg, element_size as i64);
+            ASM::imul_reg64_reg64_reg64(buf, tmp_reg, tmp_reg, dst_reg);
+            ASM::mov_reg64_base32(buf, src_reg, extra_offset_slot);
+            ASM::add_reg64_reg64_reg64(buf, src_reg, src_reg, tmp_reg);
+            ASM::mov_base32_reg64(buf, extra_offset_slot, src_reg);
+        });
+
+        if element_size == 0 {
+            // nothing to copy
+        } else if self.layout_interner.safe_to_memcpy(element_layout) {
+            let destination = self.debug_symbol("destination");
+            let destination_reg = self
+                .storage_manager
+                .claim_general_reg(&mut self.buf, &destination);
+            let elements = self.load_stack_slot("elements", (elements_slot, 0));
+            let size = self.debug_symbol("size");
+            let size_reg = self.storage_manager.claim_general_reg(&mut self.buf, &size);
+
+            self.storage_manager
+                .with_tmp_general_reg(&mut self.buf, |_, buf, tmp_reg| {
+                    Self::load_shared_memory_address(
+                        buf,
+                        destination_reg,
+                        tmp_reg,
+                        shared_memory_slot,
+                        element_destination_slot,
+                    );
+                    ASM::mov_reg64_base32(buf, size_reg, length_slot);
+                    ASM::mov_reg64_imm64(buf, tmp_reg, element_size as i64);
+                    ASM::imul_reg64_reg64_reg64(buf, size_reg, size_reg, tmp_reg);
+                });
+
+            let ignored = self.debug_symbol("ignored");
+            self.build_fn_call(
+                &ignored,
+                "roc_memcpy".to_string(),
+                &[destination, elements, size],
+                &[Layout::U64, Layout::U64, Layout::U64],
+                &Layout::UNIT,
+            );
+
+            for symbol in [destination, elements, size, ignored] {
+                self.free_symbol(&symbol);
+            }
+        } else {
+            self.build_counted_loop(length_slot, |backend| {
+                backend.clone_value_to_shared_memory(
+                    element_layout,
+                    shared_memory_slot,
+                    (elements_slot, 0),
+                    (element_destination_slot, 0),
+                    extra_offset_slot,
+                );
+
+                backend.with_tmp_general_regs(|buf, tmp_reg, _, _| {
+                    for slot in [elements_slot, element_destination_slot] {
+                        ASM::mov_reg64_base32(buf, tmp_reg, slot);
+                        ASM::add_reg64_reg64_imm32(buf, tmp_reg, tmp_reg, element_size);
+                        ASM::mov_base32_reg64(buf, slot, tmp_reg);
+                    }
+                });
+            });
+        }
+    }
+
+    /// Generates a loop that runs the code from `build_body` as many times as the count in
+    /// `counter_slot`, which is counted down to zero.
+    fn build_counted_loop<F: FnOnce(&mut Self)>(&mut self, counter_slot: i32, build_body: F) {
+        // Registers are not kept track of when jumping back to the start of the loop.
+        self.storage_manager.free_all_to_stack(&mut self.buf);
+
+        let loop_start = self.buf.len();
+        let mut counter_jne = None;
+        self.storage_manager
+            .with_tmp_general_reg(&mut self.buf, |_, buf, counter_reg| {
+                ASM::mov_reg64_base32(buf, counter_reg, counter_slot);
+                let jne_location = buf.len();
+                let start_offset = ASM::jne_reg64_imm64_imm32(buf, counter_reg, 0, 0);
+                counter_jne = Some((jne_location, start_offset, counter_reg));
+            });
+        let (jne_location, jne_start_offset, counter_reg) = counter_jne.unwrap();
+
+        // The counter is zero, so leave the loop.
+        // Since we don't know the offset yet, set it to 0 and overwrite later.
+        let exit_location = self.buf.len();
+        let exit_start_offset = ASM::jmp_imm32(&mut self.buf, 0x1234_5678);
+
+        self.update_jne_imm32_offset(jne_location, jne_start_offset, counter_reg, 0);
+
+        self.with_isolated_storage(|backend| {
+            build_body(backend);
+
+            backend.with_tmp_general_regs(|buf, tmp_reg, _, _| {
+                ASM::mov_reg64_base32(buf, tmp_reg, counter_slot);
+                ASM::sub_reg64_reg64_imm32(buf, tmp_reg, tmp_reg, 1);
+                ASM::mov_base32_reg64(buf, counter_slot, tmp_reg);
+            });
+        });
+
+        let mut tmp = bumpalo::vec![in self.env.arena];
+        let back_location = self.buf.len();
+        let back_start_offset = ASM::jmp_imm32(&mut self.buf, 0x1234_5678);
+        self.update_jmp_imm32_offset(
+            &mut tmp,
+            back_location as u64,
+            back_start_offset as u64,
+            loop_start as u64,
+        );
+
+        let loop_end = self.buf.len();
+        self.update_jmp_imm32_offset(
+            &mut tmp,
+            exit_location as u64,
+            exit_start_offset as u64,
+            loop_end as u64,
+        );
+    }
 }
 
 #[macro_export]
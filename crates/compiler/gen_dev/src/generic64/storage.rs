@@ -168,6 +168,10 @@ impl<
         CC: CallConv<GeneralReg, FloatReg, ASM>,
     > StorageManager<'a, 'r, GeneralReg, FloatReg, ASM, CC>
 {
+    pub fn target_info(&self) -> TargetInfo {
+        self.target_info
+    }
+
     pub fn reset(&mut self) {
         self.symbol_storage_map.clear();
         self.allocation_map.clear();
@@ -1139,6 +1143,17 @@ impl<
         self.fn_call_stack_size = max(self.fn_call_stack_size, tmp_size);
     }
 
+    /// Keeps the parts of `branch`, a copy of this storage manager that code was generated with,
+    /// that outlive that code: the stack it used and the callee saved registers it clobbered.
+    pub fn merge_branch(&mut self, branch: &Self) {
+        self.update_stack_size(branch.stack_size);
+        self.update_fn_call_stack_size(branch.fn_call_stack_size);
+        self.general_used_callee_saved_regs
+            .extend(&branch.general_used_callee_saved_regs);
+        self.float_used_callee_saved_regs
+            .extend(&branch.float_used_callee_saved_regs);
+    }
+
     /// Setups a join point.
     /// To do this, each of the join pionts params are given a storage location.
     /// Then those locations are stored.
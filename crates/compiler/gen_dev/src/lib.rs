@@ -14,26 +14,47 @@ use roc_module::low_level::{LowLevel, LowLevelWrapperType};
 use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_mono::code_gen_help::{CallerProc, CodeGenHelp};
 use roc_mono::ir::{
-    BranchInfo, CallType, Expr, HigherOrderLowLevel, JoinPointId, ListLiteralElement, Literal,
-    Param, Proc, ProcLayout, SelfRecursive, Stmt,
+    BranchInfo, CallType, CrashTag, Expr, HigherOrderLowLevel, JoinPointId, ListLiteralElement,
+    Literal, LookupType, Param, Proc, ProcLayout, SelfRecursive, Stmt,
 };
 use roc_mono::layout::{
     Builtin, InLayout, Layout, LayoutIds, LayoutInterner, STLayoutInterner, TagIdIntType,
     UnionLayout,
 };
 use roc_mono::list_element_layout;
+use roc_region::all::Region;
+use roc_types::subs::Variable;
 
 mod generic64;
 mod object_builder;
 pub use object_builder::build_module;
 mod run_roc;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssemblyBackendMode {
+    /// Assumes primitives (roc_alloc, roc_panic, etc) are provided by the host.
+    /// Failed expects and dbg are reported to the parent process through shared memory.
+    Binary,
+    /// Provides a testing implementation of primitives (roc_alloc, roc_panic, etc).
+    /// Failed expects and dbg are not reported.
+    Test,
+}
+
+impl AssemblyBackendMode {
+    fn generate_allocators(self) -> bool {
+        match self {
+            AssemblyBackendMode::Binary => false,
+            AssemblyBackendMode::Test => true,
+        }
+    }
+}
+
 pub struct Env<'a> {
     pub arena: &'a Bump,
     pub module_id: ModuleId,
     pub exposed_to_host: MutSet<Symbol>,
     pub lazy_literals: bool,
-    pub generate_allocators: bool,
+    pub mode: AssemblyBackendMode,
 }
 
 // These relocations likely will need a length.
@@ -179,6 +200,11 @@ trait Backend<'a> {
         }
         self.scan_ast(&proc.body);
         self.create_free_map();
+        if self.env().mode == AssemblyBackendMode::Test
+            && self.env().exposed_to_host.contains(&proc.name.name())
+        {
+            self.build_test_setjmp();
+        }
         self.build_stmt(&proc.body, &proc.ret_layout);
         let mut helper_proc_names = bumpalo::vec![in self.env().arena];
         helper_proc_names.reserve(self.helper_proc_symbols().len());
@@ -196,13 +222,13 @@ trait Backend<'a> {
     /// build_stmt builds a statement and outputs at the end of the buffer.
     fn build_stmt(&mut self, stmt: &Stmt<'a>, ret_layout: &InLayout<'a>) {
         match stmt {
-            Stmt::Let(sym, expr, layout, following) => {
+            Stmt::Let(sym, expr, layout, following, _) => {
                 self.build_expr(sym, expr, layout);
                 self.set_layout_map(*sym, layout);
                 self.free_symbols(stmt);
                 self.build_stmt(following, ret_layout);
             }
-            Stmt::Ret(sym) => {
+            Stmt::Ret(sym, _) => {
                 self.load_literal_symbols(&[*sym]);
                 self.return_symbol(sym, ret_layout);
                 self.free_symbols(stmt);
@@ -240,6 +266,7 @@ trait Backend<'a> {
                 branches,
                 default_branch,
                 ret_layout,
+                region: _,
             } => {
                 self.load_literal_symbols(&[*cond_symbol]);
                 self.build_switch(
@@ -256,6 +283,7 @@ trait Backend<'a> {
                 parameters,
                 body,
                 remainder,
+                region: _,
             } => {
                 for param in parameters.iter() {
                     self.set_layout_map(param.symbol, &param.layout);
@@ -279,9 +307,63 @@ trait Backend<'a> {
                 self.build_jump(id, args, arg_layouts.into_bump_slice(), ret_layout);
                 self.free_symbols(stmt);
             }
-            x => todo!("the statement, {:?}", x),
+            Stmt::Expect {
+                condition,
+                region,
+                lookups,
+                variables,
+                remainder,
+            }
+            | Stmt::ExpectFx {
+                condition,
+                region,
+                lookups,
+                variables,
+                remainder,
+            } => {
+                self.load_literal_symbols(&[*condition]);
+                self.load_literal_symbols(lookups);
+                self.build_expect(*condition, *region, lookups, variables);
+                self.free_symbols(stmt);
+                self.build_stmt(remainder, ret_layout);
+            }
+            Stmt::Dbg {
+                symbol,
+                variable,
+                remainder,
+            } => {
+                self.load_literal_symbols(&[*symbol]);
+                self.build_dbg(*symbol, *variable);
+                self.free_symbols(stmt);
+                self.build_stmt(remainder, ret_layout);
+            }
+            Stmt::Crash(msg, tag) => {
+                self.load_literal_symbols(&[*msg]);
+                self.build_crash(*msg, *tag);
+                self.free_symbols(stmt);
+            }
         }
     }
+
+    /// build_expect checks `condition` and, if it is false, reports the failure
+    /// together with the values of `lookups` to the parent process.
+    fn build_expect(
+        &mut self,
+        condition: Symbol,
+        region: Region,
+        lookups: &'a [Symbol],
+        variables: &'a [LookupType],
+    );
+
+    /// build_dbg reports the value of `symbol` to the parent process.
+    fn build_dbg(&mut self, symbol: Symbol, variable: Variable);
+
+    /// build_crash calls `roc_panic` with the message in `msg`.
+    fn build_crash(&mut self, msg: Symbol, tag: CrashTag);
+
+    /// build_test_setjmp sets up the jump buffer of the test implementation of `roc_panic`,
+    /// so that a crash returns from the current proc. The test then checks for the crash.
+    fn build_test_setjmp(&mut self);
     // build_switch generates a instructions for a switch statement.
     fn build_switch(
         &mut self,
@@ -1658,7 +1740,7 @@ trait Backend<'a> {
         // Join map keeps track of join point parameters so that we can keep them around while they still might be jumped to.
         let mut join_map: MutMap<JoinPointId, &'a [Param<'a>]> = MutMap::default();
         match stmt {
-            Stmt::Let(sym, expr, _, following) => {
+            Stmt::Let(sym, expr, _, following, _) => {
                 self.set_last_seen(*sym, stmt);
                 match expr {
                     Expr::Literal(_) => {}
@@ -1727,7 +1809,7 @@ trait Backend<'a> {
                 }
                 self.scan_ast(default_branch.1);
             }
-            Stmt::Ret(sym) => {
+            Stmt::Ret(sym, _) => {
                 self.set_last_seen(*sym, stmt);
             }
             Stmt::Refcounting(modify, following) => {
@@ -1763,11 +1845,34 @@ trait Backend<'a> {
                 }
             }
 
-            Stmt::Dbg { .. } => todo!("dbg not implemented in the dev backend"),
-            Stmt::Expect { .. } => todo!("expect is not implemented in the dev backend"),
-            Stmt::ExpectFx { .. } => todo!("expect-fx is not implemented in the dev backend"),
+            Stmt::Dbg {
+                symbol, remainder, ..
+            } => {
+                self.set_last_seen(*symbol, stmt);
+                self.scan_ast(remainder);
+            }
+            Stmt::Expect {
+                condition,
+                lookups,
+                remainder,
+                ..
+            }
+            | Stmt::ExpectFx {
+                condition,
+                lookups,
+                remainder,
+                ..
+            } => {
+                self.set_last_seen(*condition, stmt);
+                for sym in *lookups {
+                    self.set_last_seen(*sym, stmt);
+                }
+                self.scan_ast(remainder);
+            }
 
-            Stmt::Crash(..) => todo!("crash is not implemented in the dev backend"),
+            Stmt::Crash(msg, _) => {
+                self.set_last_seen(*msg, stmt);
+            }
         }
     }
 
@@ -3,6 +3,10 @@ use crate::llvm::build_list::{self, allocate_list, empty_polymorphic_list};
 use crate::llvm::convert::{
     argument_type_from_layout, basic_type_from_builtin, basic_type_from_layout, zig_str_type,
 };
+use crate::llvm::debug_info::{
+    declare_proc_args, init_proc_debug_location, new_proc_subprogram, set_region_debug_location,
+    DebugSources,
+};
 use crate::llvm::expect::{clone_to_shared_memory, SharedMemoryPointer};
 use crate::llvm::refcounting::{
     build_reset, decrement_refcount_layout, increment_refcount_layout, PointerToRefcount,
@@ -253,6 +257,8 @@ pub struct Env<'a, 'ctx, 'env> {
     pub target_info: TargetInfo,
     pub mode: LlvmBackendMode,
     pub exposed_to_host: MutSet<Symbol>,
+    /// Where Roc procs are defined, for debug info. Left empty when debug info isn't wanted.
+    pub debug_sources: DebugSources<'ctx>,
 }
 
 impl<'a, 'ctx, 'env> Env<'a, 'ctx, 'env> {
@@ -436,12 +442,43 @@ impl<'a, 'ctx, 'env> Env<'a, 'ctx, 'env> {
     }
 
     pub fn new_debug_info(module: &Module<'ctx>) -> (DebugInfoBuilder<'ctx>, DICompileUnit<'ctx>) {
+        Self::new_debug_info_for_file(module, Path::new("roc_app"))
+    }
+
+    /// Debug info whose compile unit is the given Roc source file
+    pub fn new_debug_info_for_file(
+        module: &Module<'ctx>,
+        roc_file_path: &Path,
+    ) -> (DebugInfoBuilder<'ctx>, DICompileUnit<'ctx>) {
+        // LLVM drops debug info from modules that don't say which version it is in
+        if module.get_flag("Debug Info Version").is_none() {
+            let version = module
+                .get_context()
+                .i32_type()
+                .const_int(inkwell::debug_info::debug_metadata_version() as u64, false);
+
+            module.add_basic_value_flag(
+                "Debug Info Version",
+                inkwell::module::FlagBehavior::Warning,
+                version,
+            );
+        }
+
+        let filename = roc_file_path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy();
+        let directory = match roc_file_path.parent() {
+            Some(parent) if parent != Path::new("") => parent.to_string_lossy(),
+            _ => ".".into(),
+        };
+
         module.create_debug_info_builder(
             true,
             /* language */ inkwell::debug_info::DWARFSourceLanguage::C,
-            /* filename */ "roc_app",
-            /* directory */ ".",
-            /* producer */ "my llvm compiler frontend",
+            /* filename */ &filename,
+            /* directory */ &directory,
+            /* producer */ "roc",
             /* is_optimized */ false,
             /* compiler command line flags */ "",
             /* runtime_ver */ 0,
@@ -2493,25 +2530,27 @@ pub fn build_exp_stmt<'a, 'ctx, 'env>(
     use roc_mono::ir::Stmt::*;
 
     match stmt {
-        Let(first_symbol, first_expr, first_layout, mut cont) => {
+        Let(first_symbol, first_expr, first_layout, mut cont, first_region) => {
             let mut queue = Vec::new_in(env.arena);
 
-            queue.push((first_symbol, first_expr, first_layout));
+            queue.push((first_symbol, first_expr, first_layout, first_region));
 
-            while let Let(symbol, expr, layout, new_cont) = cont {
-                queue.push((symbol, expr, layout));
+            while let Let(symbol, expr, layout, new_cont, region) = cont {
+                queue.push((symbol, expr, layout, region));
 
                 cont = new_cont;
             }
 
             let mut stack = Vec::with_capacity_in(queue.len(), env.arena);
 
-            for (symbol, expr, layout) in queue {
+            for (symbol, expr, layout, region) in queue {
                 debug_assert!(!matches!(
                     layout_interner.get(*layout),
                     Layout::RecursivePointer(_)
                 ));
 
+                set_region_debug_location(env, parent, symbol.module_id(), *region);
+
                 let val = build_exp_expr(
                     env,
                     layout_interner,
@@ -2550,7 +2589,9 @@ pub fn build_exp_stmt<'a, 'ctx, 'env>(
 
             result
         }
-        Ret(symbol) => {
+        Ret(symbol, region) => {
+            set_region_debug_location(env, parent, symbol.module_id(), *region);
+
             let (value, layout) = load_symbol_and_layout(scope, symbol);
 
             match RocReturn::from_layout(env, layout_interner, layout) {
@@ -2643,7 +2684,10 @@ pub fn build_exp_stmt<'a, 'ctx, 'env>(
             ret_layout,
             cond_layout,
             cond_symbol,
+            region,
         } => {
+            set_region_debug_location(env, parent, cond_symbol.module_id(), *region);
+
             let ret_type = basic_type_from_layout(env, layout_interner, *ret_layout);
 
             let switch_args = SwitchArgsIr {
@@ -2669,7 +2713,10 @@ pub fn build_exp_stmt<'a, 'ctx, 'env>(
             parameters,
             remainder,
             body: continuation,
+            region,
         } => {
+            set_region_debug_location(env, parent, id.0.module_id(), *region);
+
             let builder = env.builder;
             let context = env.context;
 
@@ -2905,6 +2952,8 @@ pub fn build_exp_stmt<'a, 'ctx, 'env>(
             variables,
             remainder,
         } => {
+            set_region_debug_location(env, parent, cond_symbol.module_id(), *region);
+
             let bd = env.builder;
             let context = env.context;
 
@@ -2977,6 +3026,8 @@ pub fn build_exp_stmt<'a, 'ctx, 'env>(
             variables,
             remainder,
         } => {
+            set_region_debug_location(env, parent, cond_symbol.module_id(), *region);
+
             let bd = env.builder;
             let context = env.context;
 
@@ -4990,7 +5041,7 @@ fn build_proc_header<'a, 'ctx, 'env>(
         Linkage::Internal,
     );
 
-    let subprogram = env.new_subprogram(&fn_name);
+    let subprogram = new_proc_subprogram(env, layout_interner, proc, &fn_name);
     fn_val.set_subprogram(subprogram);
 
     if env.exposed_to_host.contains(&symbol) {
@@ -5335,7 +5386,7 @@ fn build_proc<'a, 'ctx, 'env>(
 
     builder.position_at_end(entry);
 
-    debug_info_init!(env, fn_val);
+    init_proc_debug_location(env, proc, fn_val);
 
     // Add args to scope
     for (arg_val, (layout, arg_symbol)) in fn_val.get_param_iter().zip(args) {
@@ -5359,6 +5410,8 @@ fn build_proc<'a, 'ctx, 'env>(
             builder.build_return(Some(&body));
         }
     }
+
+    declare_proc_args(env, layout_interner, proc, fn_val);
 }
 
 pub fn verify_fn(fn_val: FunctionValue<'_>) {
@@ -0,0 +1,494 @@
+//! DWARF debug info that points back into Roc source code.
+//!
+//! Procs know where their body is, and mono statements carry the region of the source code
+//! they were generated from. Each statement moves the debug location to its region, so a
+//! debugger can step through a proc line by line. Statements generated by the compiler itself
+//! have no region and keep the location of the code before them.
+//!
+//! When no sources are available (e.g. in tests and the REPL), Roc procs get the same
+//! placeholder debug info as the compiler-generated helpers.
+
+use crate::llvm::build::Env;
+use crate::llvm::convert::basic_type_from_layout;
+use inkwell::debug_info::{
+    AsDIScope, DIFile, DIFlags, DIFlagsConstants, DIScope, DISubprogram, DIType, DebugInfoBuilder,
+};
+use inkwell::values::{BasicValue, FunctionValue};
+use inkwell::AddressSpace;
+use roc_builtins::bitcode::{FloatWidth, IntWidth};
+use roc_collections::all::MutMap;
+use roc_module::symbol::{ModuleId, Symbol};
+use roc_mono::ir::Proc;
+use roc_mono::layout::{
+    round_up_to_alignment, Builtin, InLayout, Layout, LayoutInterner, STLayoutInterner,
+};
+use roc_region::all::{LineColumn, LineInfo, Region};
+use std::path::PathBuf;
+
+// DWARF base type encodings, see section 7.8 of the DWARF 5 spec
+const DW_ATE_BOOLEAN: u32 = 0x02;
+const DW_ATE_FLOAT: u32 = 0x04;
+const DW_ATE_SIGNED: u32 = 0x05;
+const DW_ATE_UNSIGNED: u32 = 0x08;
+
+// DWARF expression operation that loads a value through the pointer on the stack
+const DW_OP_DEREF: i64 = 0x06;
+
+struct SourceFile<'ctx> {
+    file: DIFile<'ctx>,
+    line_info: LineInfo,
+}
+
+/// The source files of the Roc modules being compiled
+#[derive(Default)]
+pub struct DebugSources<'ctx> {
+    files: MutMap<ModuleId, SourceFile<'ctx>>,
+}
+
+impl<'ctx> DebugSources<'ctx> {
+    pub fn new(
+        dibuilder: &DebugInfoBuilder<'ctx>,
+        sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+    ) -> Self {
+        let files = sources
+            .iter()
+            .map(|(module_id, (path, src))| {
+                // debuggers look up relative paths from wherever they are started
+                let path = path.canonicalize().unwrap_or_else(|_| path.clone());
+                let filename = path.file_name().unwrap_or_default().to_string_lossy();
+                let directory = path
+                    .parent()
+                    .map(|dir| dir.to_string_lossy())
+                    .unwrap_or_default();
+
+                let source_file = SourceFile {
+                    file: dibuilder.create_file(&filename, &directory),
+                    line_info: LineInfo::new(src),
+                };
+
+                (*module_id, source_file)
+            })
+            .collect();
+
+        Self { files }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// The file and 1-based line and column of a region in a module.
+    /// Compiler-generated code has no location.
+    fn location(&self, module_id: ModuleId, region: Region) -> Option<(DIFile<'ctx>, LineColumn)> {
+        if region == Region::zero() {
+            return None;
+        }
+
+        let source_file = self.files.get(&module_id)?;
+        let LineColumn { line, column } = source_file.line_info.convert_pos(region.start());
+
+        let line_column = LineColumn {
+            line: line + 1,
+            column: column + 1,
+        };
+
+        Some((source_file.file, line_column))
+    }
+}
+
+/// Debug info for a Roc proc: its Roc name (e.g. `Num.add`), where it is defined and the
+/// types of its arguments. The name of the LLVM function becomes the linkage name, so
+/// specializations of the same proc can be told apart.
+pub(crate) fn new_proc_subprogram<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    layout_interner: &STLayoutInterner<'a>,
+    proc: &Proc<'a>,
+    linkage_name: &str,
+) -> DISubprogram<'ctx> {
+    if env.debug_sources.is_empty() {
+        return env.new_subprogram(linkage_name);
+    }
+
+    let dibuilder = env.dibuilder;
+    let symbol = proc.name.name();
+
+    let (file, line) = match env.debug_sources.location(symbol.module_id(), proc.region) {
+        Some((file, line_column)) => (file, line_column.line),
+        None => (env.compile_unit.get_file(), 0),
+    };
+
+    let return_type = ditype_from_layout(env, layout_interner, proc.ret_layout);
+    let parameter_types: Vec<_> = proc
+        .args
+        .iter()
+        .map(|(layout, _)| ditype_from_layout(env, layout_interner, *layout))
+        .collect();
+
+    let subroutine_type = dibuilder.create_subroutine_type(
+        file,
+        Some(return_type),
+        &parameter_types,
+        DIFlags::PUBLIC,
+    );
+
+    dibuilder.create_function(
+        /* scope */ file.as_debug_info_scope(),
+        /* func name */ &roc_name(env, symbol),
+        /* linkage_name */ Some(linkage_name),
+        /* file */ file,
+        /* line_no */ line,
+        /* DIType */ subroutine_type,
+        /* is_local_to_unit */ true,
+        /* is_definition */ true,
+        /* scope_line */ line,
+        /* flags */ DIFlags::PUBLIC,
+        /* is_optimized */ false,
+    )
+}
+
+/// Point the builder at the start of a Roc proc's definition
+pub(crate) fn init_proc_debug_location<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    proc: &Proc<'a>,
+    fn_val: FunctionValue<'ctx>,
+) {
+    if env.debug_sources.is_empty() {
+        crate::debug_info_init!(env, fn_val);
+        return;
+    }
+
+    let module_id = proc.name.name().module_id();
+
+    if !set_region_debug_location(env, fn_val, module_id, proc.region) {
+        let scope = fn_val.get_subprogram().expect("subprogram");
+        set_debug_location(env, scope.as_debug_info_scope(), 0, 0);
+    }
+}
+
+/// Point the builder at a region of Roc code in the current function.
+/// Returns false if the region has no known location.
+pub(crate) fn set_region_debug_location<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    fn_val: FunctionValue<'ctx>,
+    module_id: ModuleId,
+    region: Region,
+) -> bool {
+    let (subprogram, line_column) = match (
+        fn_val.get_subprogram(),
+        env.debug_sources.location(module_id, region),
+    ) {
+        (Some(subprogram), Some((_, line_column))) => (subprogram, line_column),
+        _ => return false,
+    };
+
+    set_debug_location(
+        env,
+        subprogram.as_debug_info_scope(),
+        line_column.line,
+        line_column.column,
+    );
+
+    true
+}
+
+fn set_debug_location<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    scope: DIScope<'ctx>,
+    line: u32,
+    column: u32,
+) {
+    let loc = env
+        .dibuilder
+        .create_debug_location(env.context, line, column, scope, None);
+
+    env.builder.set_current_debug_location(loc);
+}
+
+/// Describe the arguments of a Roc proc, so debuggers can show their values.
+/// Must be called after the body is built: the descriptions go before its first instruction.
+pub(crate) fn declare_proc_args<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    layout_interner: &mut STLayoutInterner<'a>,
+    proc: &Proc<'a>,
+    fn_val: FunctionValue<'ctx>,
+) {
+    if env.debug_sources.is_empty() {
+        return;
+    }
+
+    let dibuilder = env.dibuilder;
+
+    let subprogram = match fn_val.get_subprogram() {
+        Some(subprogram) => subprogram,
+        None => return,
+    };
+
+    let first_instruction = match fn_val
+        .get_first_basic_block()
+        .and_then(|entry| entry.get_first_instruction())
+    {
+        Some(instruction) => instruction,
+        None => return,
+    };
+
+    let scope = subprogram.as_debug_info_scope();
+    let module_id = proc.name.name().module_id();
+
+    let (file, line_column) = env
+        .debug_sources
+        .location(module_id, proc.region)
+        .unwrap_or((
+            env.compile_unit.get_file(),
+            LineColumn { line: 0, column: 0 },
+        ));
+
+    let loc = dibuilder.create_debug_location(
+        env.context,
+        line_column.line,
+        line_column.column,
+        scope,
+        None,
+    );
+
+    let params = fn_val.get_param_iter();
+
+    for (index, (arg_val, (layout, arg_symbol))) in params.zip(proc.args).enumerate() {
+        let ditype = ditype_from_layout(env, layout_interner, *layout);
+
+        let variable = dibuilder.create_parameter_variable(
+            scope,
+            arg_symbol.as_str(&env.interns),
+            index as u32 + 1,
+            file,
+            line_column.line,
+            ditype,
+            /* always_preserve */ true,
+            DIFlags::ZERO,
+        );
+
+        // large values are passed by reference
+        let passed_by_reference = arg_val.is_pointer_value()
+            && !basic_type_from_layout(env, layout_interner, *layout).is_pointer_type();
+
+        let expression = if passed_by_reference {
+            dibuilder.create_expression(vec![DW_OP_DEREF])
+        } else {
+            dibuilder.create_expression(vec![])
+        };
+
+        dibuilder.insert_dbg_value_before(
+            arg_val.as_basic_value_enum(),
+            variable,
+            Some(expression),
+            loc,
+            first_instruction,
+        );
+    }
+}
+
+fn roc_name(env: &Env, symbol: Symbol) -> String {
+    let module_name = env.interns.module_ids.get_name(symbol.module_id()).unwrap();
+
+    format!("{}.{}", module_name, symbol.as_str(&env.interns))
+}
+
+/// A description of the values of a layout. Tag unions and closures are opaque.
+fn ditype_from_layout<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    layout_interner: &STLayoutInterner<'a>,
+    layout: InLayout<'a>,
+) -> DIType<'ctx> {
+    let (size, alignment) = layout_interner.stack_size_and_alignment(layout);
+
+    match layout_interner.get(layout) {
+        Layout::Builtin(builtin) => match builtin {
+            Builtin::Int(int_width) => int_ditype(env, int_width),
+            Builtin::Float(float_width) => {
+                let name = match float_width {
+                    FloatWidth::F32 => "F32",
+                    FloatWidth::F64 => "F64",
+                };
+
+                basic_ditype(env, name, float_width.stack_size() * 8, DW_ATE_FLOAT)
+            }
+            Builtin::Bool => basic_ditype(env, "Bool", 8, DW_ATE_BOOLEAN),
+            Builtin::Decimal => basic_ditype(env, "Dec", 128, DW_ATE_SIGNED),
+            Builtin::Str => {
+                let bytes = pointer_ditype(env, "bytes", int_ditype(env, IntWidth::U8));
+
+                list_like_ditype(env, "Str", bytes)
+            }
+            Builtin::List(element_layout) => {
+                let element = ditype_from_layout(env, layout_interner, element_layout);
+                let elements = pointer_ditype(env, "elements", element);
+
+                list_like_ditype(env, "List", elements)
+            }
+        },
+        Layout::Struct { field_layouts, .. } => {
+            let fields: Vec<_> = field_layouts
+                .iter()
+                .enumerate()
+                .map(|(index, field_layout)| {
+                    let ditype = ditype_from_layout(env, layout_interner, *field_layout);
+                    let (size, alignment) = layout_interner.stack_size_and_alignment(*field_layout);
+
+                    (index.to_string(), ditype, size, alignment)
+                })
+                .collect();
+
+            struct_ditype(env, "Struct", &fields, size)
+        }
+        Layout::Boxed(inner_layout) => {
+            let inner = ditype_from_layout(env, layout_interner, inner_layout);
+
+            pointer_ditype(env, "Box", inner)
+        }
+        Layout::Union(_) => opaque_ditype(env, "Union", size, alignment),
+        Layout::LambdaSet(_) => opaque_ditype(env, "LambdaSet", size, alignment),
+        Layout::RecursivePointer(_) => opaque_ditype(env, "RecursivePointer", size, alignment),
+    }
+}
+
+fn int_ditype<'a, 'ctx, 'env>(env: &Env<'a, 'ctx, 'env>, int_width: IntWidth) -> DIType<'ctx> {
+    let encoding = if int_width.is_signed() {
+        DW_ATE_SIGNED
+    } else {
+        DW_ATE_UNSIGNED
+    };
+
+    basic_ditype(
+        env,
+        &int_width.type_name().to_uppercase(),
+        int_width.stack_size() * 8,
+        encoding,
+    )
+}
+
+fn basic_ditype<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    name: &str,
+    size_in_bits: u32,
+    encoding: u32,
+) -> DIType<'ctx> {
+    env.dibuilder
+        .create_basic_type(name, size_in_bits as u64, encoding, DIFlags::PUBLIC)
+        .unwrap()
+        .as_type()
+}
+
+fn pointer_ditype<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    name: &str,
+    pointee: DIType<'ctx>,
+) -> DIType<'ctx> {
+    let ptr_bits = env.target_info.ptr_width() as u32 * 8;
+
+    env.dibuilder
+        .create_pointer_type(
+            name,
+            pointee,
+            ptr_bits as u64,
+            ptr_bits,
+            AddressSpace::default(),
+        )
+        .as_type()
+}
+
+/// Str and List are a pointer, a length and a capacity
+fn list_like_ditype<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    name: &str,
+    pointer: DIType<'ctx>,
+) -> DIType<'ctx> {
+    let ptr_bytes = env.target_info.ptr_width() as u32;
+    let nat = basic_ditype(env, "Nat", ptr_bytes * 8, DW_ATE_UNSIGNED);
+
+    let fields = [
+        (String::from("pointer"), pointer, ptr_bytes, ptr_bytes),
+        (String::from("length"), nat, ptr_bytes, ptr_bytes),
+        (String::from("capacity"), nat, ptr_bytes, ptr_bytes),
+    ];
+
+    struct_ditype(env, name, &fields, 3 * ptr_bytes)
+}
+
+/// A struct from its fields' names, types, sizes and alignments, laid out like LLVM does
+fn struct_ditype<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    name: &str,
+    fields: &[(String, DIType<'ctx>, u32, u32)],
+    size: u32,
+) -> DIType<'ctx> {
+    let dibuilder = env.dibuilder;
+    let file = env.compile_unit.get_file();
+    let scope = file.as_debug_info_scope();
+
+    let mut offset = 0;
+    let mut struct_alignment = 1;
+    let mut members = Vec::with_capacity(fields.len());
+
+    for (field_name, ditype, field_size, field_alignment) in fields {
+        let field_alignment = (*field_alignment).max(1);
+        offset = round_up_to_alignment(offset, field_alignment);
+        struct_alignment = struct_alignment.max(field_alignment);
+
+        let member = dibuilder.create_member_type(
+            scope,
+            field_name,
+            file,
+            0,
+            *field_size as u64 * 8,
+            field_alignment * 8,
+            offset as u64 * 8,
+            DIFlags::PUBLIC,
+            *ditype,
+        );
+
+        members.push(member.as_type());
+        offset += field_size;
+    }
+
+    dibuilder
+        .create_struct_type(
+            scope,
+            name,
+            file,
+            0,
+            size as u64 * 8,
+            struct_alignment * 8,
+            DIFlags::PUBLIC,
+            None,
+            &members,
+            0,
+            None,
+            "",
+        )
+        .as_type()
+}
+
+fn opaque_ditype<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    name: &str,
+    size: u32,
+    alignment: u32,
+) -> DIType<'ctx> {
+    let file = env.compile_unit.get_file();
+
+    env.dibuilder
+        .create_struct_type(
+            file.as_debug_info_scope(),
+            name,
+            file,
+            0,
+            size as u64 * 8,
+            alignment * 8,
+            DIFlags::PUBLIC,
+            None,
+            &[],
+            0,
+            None,
+            "",
+        )
+        .as_type()
+}
@@ -5,17 +5,19 @@ use roc_builtins::bitcode::{FloatWidth, IntWidth};
 use roc_collections::all::MutMap;
 use roc_error_macros::internal_error;
 use roc_module::low_level::{LowLevel, LowLevelWrapperType};
-use roc_module::symbol::{Interns, Symbol};
+use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_mono::code_gen_help::{CodeGenHelp, HelperOp, REFCOUNT_MAX};
 use roc_mono::ir::{
-    BranchInfo, CallType, CrashTag, Expr, JoinPointId, ListLiteralElement, Literal, ModifyRc,
-    Param, Proc, ProcLayout, Stmt,
+    BranchInfo, CallType, CrashTag, Expr, JoinPointId, ListLiteralElement, Literal, LookupType,
+    ModifyRc, Param, Proc, ProcLayout, Stmt,
 };
 use roc_mono::layout::{
     Builtin, InLayout, Layout, LayoutIds, LayoutInterner, STLayoutInterner, TagIdIntType,
     UnionLayout,
 };
+use roc_region::all::Region;
 use roc_std::RocDec;
+use roc_types::subs::Variable;
 
 use roc_wasm_module::linking::{DataSymbol, WasmObjectSymbol};
 use roc_wasm_module::sections::{
@@ -35,6 +37,14 @@ use crate::{
     TARGET_INFO,
 };
 
+/// Hosts that want to report failed expects define this. It's called with a pointer to an
+/// expect frame (see `write_expect_frame`).
+const ROC_EXPECT_FAILED: &str = "roc_expect_failed";
+
+/// Hosts that want to print `dbg` values define this. It's called with a pointer to a frame
+/// in the same format as for `roc_expect_failed`, looking up just the one value.
+const ROC_DBG: &str = "roc_dbg";
+
 #[derive(Clone, Copy, Debug)]
 pub enum ProcSource {
     Roc,
@@ -700,9 +710,9 @@ impl<'a, 'r> WasmBackend<'a, 'r> {
 
     fn stmt(&mut self, stmt: &Stmt<'a>) {
         match stmt {
-            Stmt::Let(_, _, _, _) => self.stmt_let(stmt),
+            Stmt::Let(_, _, _, _, _) => self.stmt_let(stmt),
 
-            Stmt::Ret(sym) => self.stmt_ret(*sym),
+            Stmt::Ret(sym, _) => self.stmt_ret(*sym),
 
             Stmt::Switch {
                 cond_symbol,
@@ -710,6 +720,7 @@ impl<'a, 'r> WasmBackend<'a, 'r> {
                 branches,
                 default_branch,
                 ret_layout: _,
+                region: _,
             } => self.stmt_switch(*cond_symbol, *cond_layout, branches, default_branch),
 
             Stmt::Join {
@@ -717,15 +728,39 @@ impl<'a, 'r> WasmBackend<'a, 'r> {
                 parameters,
                 body,
                 remainder,
+                region: _,
             } => self.stmt_join(*id, parameters, body, remainder),
 
             Stmt::Jump(id, arguments) => self.stmt_jump(*id, arguments),
 
             Stmt::Refcounting(modify, following) => self.stmt_refcounting(modify, following),
 
-            Stmt::Dbg { .. } => todo!("dbg is not implemented in the wasm backend"),
-            Stmt::Expect { .. } => todo!("expect is not implemented in the wasm backend"),
-            Stmt::ExpectFx { .. } => todo!("expect-fx is not implemented in the wasm backend"),
+            Stmt::Dbg {
+                symbol,
+                variable,
+                remainder,
+            } => {
+                self.stmt_dbg(*symbol, *variable);
+                self.stmt(remainder);
+            }
+
+            Stmt::Expect {
+                condition,
+                region,
+                lookups,
+                variables,
+                remainder,
+            }
+            | Stmt::ExpectFx {
+                condition,
+                region,
+                lookups,
+                variables,
+                remainder,
+            } => {
+                self.stmt_expect(*condition, *region, lookups, variables);
+                self.stmt(remainder);
+            }
 
             Stmt::Crash(sym, tag) => self.stmt_crash(*sym, *tag),
         }
@@ -752,13 +787,13 @@ impl<'a, 'r> WasmBackend<'a, 'r> {
 
     fn stmt_let(&mut self, stmt: &Stmt<'a>) {
         let mut current_stmt = stmt;
-        while let Stmt::Let(sym, expr, layout, following) = current_stmt {
+        while let Stmt::Let(sym, expr, layout, following, _) = current_stmt {
             if DEBUG_SETTINGS.let_stmt_ir {
                 print!("\nlet {:?} = {}", sym, expr.to_pretty(200, true));
             }
 
             let kind = match following {
-                Stmt::Ret(ret_sym) if *sym == *ret_sym => StoredVarKind::ReturnValue,
+                Stmt::Ret(ret_sym, _) if *sym == *ret_sym => StoredVarKind::ReturnValue,
                 _ => StoredVarKind::Variable,
             };
 
@@ -1031,6 +1066,124 @@ impl<'a, 'r> WasmBackend<'a, 'r> {
         self.code_builder.unreachable_();
     }
 
+    fn stmt_expect(
+        &mut self,
+        condition: Symbol,
+        region: Region,
+        lookups: &[Symbol],
+        variables: &[LookupType],
+    ) {
+        // If the host has no way to report a failure, skip the expect, like optimized builds do.
+        if !self.host_has_fn(ROC_EXPECT_FAILED) {
+            return;
+        }
+
+        // Skip the report if the condition holds
+        self.start_block();
+        self.storage
+            .load_symbols(&mut self.code_builder, &[condition]);
+        self.code_builder.br_if(0);
+
+        let (frame_ptr, frame_offset) =
+            self.write_expect_frame(condition, region, lookups, variables);
+        self.code_builder.get_local(frame_ptr);
+        self.code_builder.i32_const(frame_offset as i32);
+        self.code_builder.i32_add();
+        self.call_host_fn_after_loading_args(ROC_EXPECT_FAILED, 1, false);
+
+        self.end_block();
+    }
+
+    fn stmt_dbg(&mut self, symbol: Symbol, variable: Variable) {
+        if !self.host_has_fn(ROC_DBG) {
+            return;
+        }
+
+        // same as the other backends, the symbol is used as the region of a dbg
+        let region = unsafe { std::mem::transmute::<Symbol, Region>(symbol) };
+
+        let (frame_ptr, frame_offset) =
+            self.write_expect_frame(symbol, region, &[symbol], &[variable]);
+        self.code_builder.get_local(frame_ptr);
+        self.code_builder.i32_const(frame_offset as i32);
+        self.code_builder.i32_add();
+        self.call_host_fn_after_loading_args(ROC_DBG, 1, false);
+    }
+
+    /// Write a frame describing an expect (or dbg) and the values it looks up to stack memory,
+    /// for the host to report. Returns where the frame starts.
+    ///
+    /// The frame has the same format as the other backends write to shared memory (see
+    /// `roc_gen_llvm::llvm::expect::clone_to_shared_memory`): the region and module of the expect,
+    /// then the offset and variable of each value. But the host can read all of our memory, so
+    /// nothing is deep-copied. Each value is copied into the frame as it is, and its "offset" is
+    /// its address. So the host can decode the frame by treating all of memory as the buffer.
+    fn write_expect_frame(
+        &mut self,
+        condition: Symbol,
+        region: Region,
+        lookups: &[Symbol],
+        variables: &[LookupType],
+    ) -> (LocalId, u32) {
+        const HEADER_SIZE: u32 = 3 * 4;
+        const LOOKUP_SIZE: u32 = 8 + 4;
+
+        let mut frame_size = HEADER_SIZE + LOOKUP_SIZE * lookups.len() as u32;
+        let mut value_offsets = Vec::with_capacity_in(lookups.len(), self.env.arena);
+
+        for lookup in lookups {
+            let layout = self.storage.symbol_layouts[lookup];
+            let (size, alignment) = self.layout_interner.stack_size_and_alignment(layout);
+
+            frame_size = round_up_to_alignment!(frame_size, alignment.max(1));
+            value_offsets.push(frame_size);
+            frame_size += size;
+        }
+
+        let (frame_ptr, frame_offset) = self.storage.allocate_anonymous_stack_memory(frame_size, 8);
+
+        let module_id = unsafe { std::mem::transmute::<ModuleId, u32>(condition.module_id()) };
+        let header = [region.start().offset, region.end().offset, module_id];
+
+        for (i, value) in header.into_iter().enumerate() {
+            self.code_builder.get_local(frame_ptr);
+            self.code_builder.i32_const(value as i32);
+            self.code_builder
+                .i32_store(Align::Bytes4, frame_offset + 4 * i as u32);
+        }
+
+        for (i, ((lookup, variable), value_offset)) in lookups
+            .iter()
+            .zip(variables.iter())
+            .zip(value_offsets)
+            .enumerate()
+        {
+            let entry = frame_offset + HEADER_SIZE + LOOKUP_SIZE * i as u32;
+
+            // The "offset" of the value is its address
+            self.code_builder.get_local(frame_ptr);
+            self.code_builder.get_local(frame_ptr);
+            self.code_builder
+                .i32_const((frame_offset + value_offset) as i32);
+            self.code_builder.i32_add();
+            self.code_builder.i64_extend_u_i32();
+            self.code_builder.i64_store(Align::Bytes4, entry);
+
+            self.code_builder.get_local(frame_ptr);
+            self.code_builder.i32_const(variable.index() as i32);
+            self.code_builder.i32_store(Align::Bytes4, entry + 8);
+
+            self.storage.copy_value_to_memory(
+                &mut self.code_builder,
+                frame_ptr,
+                frame_offset + value_offset,
+                *lookup,
+            );
+        }
+
+        (frame_ptr, frame_offset)
+    }
+
     /**********************************************************
 
             EXPRESSIONS
@@ -1370,6 +1523,11 @@ impl<'a, 'r> WasmBackend<'a, 'r> {
         low_level_call.generate(self);
     }
 
+    /// Whether the host (or the Zig builtins linked into it) defines a function with this name
+    fn host_has_fn(&self, name: &str) -> bool {
+        self.host_lookup.iter().any(|(fn_name, _)| *fn_name == name)
+    }
+
     /// Generate a call instruction to a host function or Zig builtin.
     pub fn call_host_fn_after_loading_args(
         &mut self,
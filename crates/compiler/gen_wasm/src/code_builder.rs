@@ -620,11 +620,6 @@ impl<'a> CodeBuilder<'a> {
         // where the branch was not taken. So we only pop 1 value, the condition.
         self.inst_imm32(BRIF, 1, false, levels);
     }
-    #[allow(dead_code)]
-    fn br_table() {
-        todo!("br instruction");
-    }
-
     instruction_no_args!(return_, RETURN, 0, false);
 
     pub fn call(&mut self, function_index: u32, n_args: usize, has_return_val: bool) {
@@ -10,6 +10,7 @@ use roc_mono::layout::{Builtin, FieldOrderHash, InLayout, Layout, LayoutInterner
 use roc_mono::low_level::HigherOrder;
 
 use crate::backend::{ProcLookupData, ProcSource, WasmBackend};
+use crate::code_builder::CodeBuilder;
 use crate::layout::{CallConv, StackMemoryFormat, WasmLayout};
 use crate::storage::{AddressValue, StackMemoryLocation, StoredValue};
 use crate::{PTR_TYPE, TARGET_INFO};
@@ -1080,6 +1081,14 @@ impl<'a> LowLevelCall<'a> {
                     x => internal_error!("NumMulChecked is not defined for {:?}", x),
                 }
             }
+            NumGt | NumGte | NumLt | NumLte | NumCompare
+                if matches!(
+                    CodeGenNumType::for_symbol(backend, self.arguments[0]),
+                    I128 | Decimal
+                ) =>
+            {
+                self.compare_num128(backend)
+            }
             NumGt => {
                 self.load_args(backend);
                 match CodeGenNumType::for_symbol(backend, self.arguments[0]) {
@@ -1246,6 +1255,14 @@ impl<'a> LowLevelCall<'a> {
                             backend.code_builder.i64_div_u()
                         }
                     }
+                    I128 => {
+                        // from compiler_rt
+                        if is_signed {
+                            self.load_args_and_call_zig(backend, "__divti3")
+                        } else {
+                            self.load_args_and_call_zig(backend, "__udivti3")
+                        }
+                    }
                     x => todo!("{:?} for {:?}", self.lowlevel, x),
                 }
             }
@@ -1261,6 +1278,14 @@ impl<'a> LowLevelCall<'a> {
                 match CodeGenNumType::for_symbol(backend, self.arguments[0]) {
                     I32 => backend.code_builder.i32_rem_s(),
                     I64 => backend.code_builder.i64_rem_s(),
+                    I128 => {
+                        // from compiler_rt
+                        if symbol_is_signed_int(backend, self.arguments[0]) {
+                            self.load_args_and_call_zig(backend, "__modti3")
+                        } else {
+                            self.load_args_and_call_zig(backend, "__umodti3")
+                        }
+                    }
                     _ => todo!("{:?} for {:?}", self.lowlevel, self.ret_layout),
                 }
             }
@@ -1360,6 +1385,8 @@ impl<'a> LowLevelCall<'a> {
                         code_builder.get_local(tmp);
                     }
 
+                    I128 => self.num128_is_multiple_of(backend, is_signed),
+
                     _ => panic_ret_type(),
                 }
             }
@@ -1367,9 +1394,14 @@ impl<'a> LowLevelCall<'a> {
                 const PANIC_MSG: &str =
                     "integer absolute overflowed because its argument is the minimum value";
 
+                let num_type = CodeGenNumType::from(self.ret_layout);
+                if matches!(num_type, I128 | Decimal) {
+                    return self.abs_num128(backend, PANIC_MSG);
+                }
+
                 self.load_args(backend);
 
-                match CodeGenNumType::from(self.ret_layout) {
+                match num_type {
                     I32 => {
                         if !layout_is_signed_int(self.ret_layout) {
                             return;
@@ -1431,8 +1463,13 @@ impl<'a> LowLevelCall<'a> {
                 const PANIC_MSG: &str =
                     "integer negation overflowed because its argument is the minimum value";
 
+                let num_type = CodeGenNumType::from(self.ret_layout);
+                if matches!(num_type, I128 | Decimal) {
+                    return self.neg_num128(backend, PANIC_MSG);
+                }
+
                 self.load_args(backend);
-                match CodeGenNumType::from(self.ret_layout) {
+                match num_type {
                     I32 => {
                         backend.code_builder.i32_const(i32::MIN);
                         backend.code_builder.i32_eq();
@@ -1493,18 +1530,65 @@ impl<'a> LowLevelCall<'a> {
             NumToFrac => {
                 self.load_args(backend);
                 let ret_type = CodeGenNumType::from(self.ret_layout);
+                let arg_layout = backend.storage.symbol_layouts[&self.arguments[0]];
+                let arg_signed = match backend.layout_interner.get(arg_layout) {
+                    Layout::Builtin(Builtin::Int(w)) => w.is_signed(),
+                    _ => true,
+                };
                 let arg_type = CodeGenNumType::for_symbol(backend, self.arguments[0]);
                 match (ret_type, arg_type) {
-                    (F32, I32) => backend.code_builder.f32_convert_s_i32(),
-                    (F32, I64) => backend.code_builder.f32_convert_s_i64(),
+                    (F32, I32) => {
+                        if arg_signed {
+                            backend.code_builder.f32_convert_s_i32()
+                        } else {
+                            backend.code_builder.f32_convert_u_i32()
+                        }
+                    }
+                    (F32, I64) => {
+                        if arg_signed {
+                            backend.code_builder.f32_convert_s_i64()
+                        } else {
+                            backend.code_builder.f32_convert_u_i64()
+                        }
+                    }
                     (F32, F32) => {}
                     (F32, F64) => backend.code_builder.f32_demote_f64(),
 
-                    (F64, I32) => backend.code_builder.f64_convert_s_i32(),
-                    (F64, I64) => backend.code_builder.f64_convert_s_i64(),
+                    (F64, I32) => {
+                        if arg_signed {
+                            backend.code_builder.f64_convert_s_i32()
+                        } else {
+                            backend.code_builder.f64_convert_u_i32()
+                        }
+                    }
+                    (F64, I64) => {
+                        if arg_signed {
+                            backend.code_builder.f64_convert_s_i64()
+                        } else {
+                            backend.code_builder.f64_convert_u_i64()
+                        }
+                    }
                     (F64, F32) => backend.code_builder.f64_promote_f32(),
                     (F64, F64) => {}
 
+                    // from compiler_rt
+                    (F32, I128) => {
+                        let name = if arg_signed {
+                            "__floattisf"
+                        } else {
+                            "__floatuntisf"
+                        };
+                        backend.call_host_fn_after_loading_args(name, 2, true)
+                    }
+                    (F64, I128) => {
+                        let name = if arg_signed {
+                            "__floattidf"
+                        } else {
+                            "__floatuntidf"
+                        };
+                        backend.call_host_fn_after_loading_args(name, 2, true)
+                    }
+
                     _ => todo!("{:?}: {:?} -> {:?}", self.lowlevel, arg_type, ret_type),
                 }
             }
@@ -1556,7 +1640,7 @@ impl<'a> LowLevelCall<'a> {
                 let width = match ret_type {
                     CodeGenNumType::I32 => IntWidth::I32,
                     CodeGenNumType::I64 => IntWidth::I64,
-                    CodeGenNumType::I128 => todo!("{:?} for I128", self.lowlevel),
+                    CodeGenNumType::I128 => IntWidth::I128,
                     _ => internal_error!("Invalid return type for round: {:?}", ret_type),
                 };
 
@@ -1591,7 +1675,9 @@ impl<'a> LowLevelCall<'a> {
                     (I32, F64) => backend.code_builder.i32_trunc_s_f64(),
                     (I64, F32) => backend.code_builder.i64_trunc_s_f32(),
                     (I64, F64) => backend.code_builder.i64_trunc_s_f64(),
-                    (I128, _) => todo!("{:?} for I128", self.lowlevel),
+                    // The return address was loaded before the argument, so we can call compiler_rt
+                    (I128, F32) => backend.call_host_fn_after_loading_args("__fixsfti", 2, false),
+                    (I128, F64) => backend.call_host_fn_after_loading_args("__fixdfti", 2, false),
                     _ => panic_ret_type(),
                 }
             }
@@ -1607,7 +1693,7 @@ impl<'a> LowLevelCall<'a> {
                 let width = match ret_type {
                     CodeGenNumType::I32 => IntWidth::I32,
                     CodeGenNumType::I64 => IntWidth::I64,
-                    CodeGenNumType::I128 => todo!("{:?} for I128", self.lowlevel),
+                    CodeGenNumType::I128 => IntWidth::I128,
                     _ => internal_error!("Invalid return type for pow: {:?}", ret_type),
                 };
 
@@ -1638,46 +1724,60 @@ impl<'a> LowLevelCall<'a> {
             NumBytesToU32 => self.load_args_and_call_zig(backend, bitcode::NUM_BYTES_TO_U32),
             NumBytesToU64 => self.load_args_and_call_zig(backend, bitcode::NUM_BYTES_TO_U64),
             NumBytesToU128 => self.load_args_and_call_zig(backend, bitcode::NUM_BYTES_TO_U128),
-            NumBitwiseAnd => {
-                self.load_args(backend);
-                match CodeGenNumType::from(self.ret_layout) {
-                    I32 => backend.code_builder.i32_and(),
-                    I64 => backend.code_builder.i64_and(),
-                    I128 => todo!("{:?} for I128", self.lowlevel),
-                    _ => panic_ret_type(),
+            NumBitwiseAnd => match CodeGenNumType::from(self.ret_layout) {
+                I32 => {
+                    self.load_args(backend);
+                    backend.code_builder.i32_and()
                 }
-            }
-            NumBitwiseXor => {
-                self.load_args(backend);
-                match CodeGenNumType::from(self.ret_layout) {
-                    I32 => backend.code_builder.i32_xor(),
-                    I64 => backend.code_builder.i64_xor(),
-                    I128 => todo!("{:?} for I128", self.lowlevel),
-                    _ => panic_ret_type(),
+                I64 => {
+                    self.load_args(backend);
+                    backend.code_builder.i64_and()
                 }
-            }
-            NumBitwiseOr => {
-                self.load_args(backend);
-                match CodeGenNumType::from(self.ret_layout) {
-                    I32 => backend.code_builder.i32_or(),
-                    I64 => backend.code_builder.i64_or(),
-                    I128 => todo!("{:?} for I128", self.lowlevel),
-                    _ => panic_ret_type(),
+                I128 => self.bitwise_num128(backend, CodeBuilder::i64_and),
+                _ => panic_ret_type(),
+            },
+            NumBitwiseXor => match CodeGenNumType::from(self.ret_layout) {
+                I32 => {
+                    self.load_args(backend);
+                    backend.code_builder.i32_xor()
                 }
-            }
+                I64 => {
+                    self.load_args(backend);
+                    backend.code_builder.i64_xor()
+                }
+                I128 => self.bitwise_num128(backend, CodeBuilder::i64_xor),
+                _ => panic_ret_type(),
+            },
+            NumBitwiseOr => match CodeGenNumType::from(self.ret_layout) {
+                I32 => {
+                    self.load_args(backend);
+                    backend.code_builder.i32_or()
+                }
+                I64 => {
+                    self.load_args(backend);
+                    backend.code_builder.i64_or()
+                }
+                I128 => self.bitwise_num128(backend, CodeBuilder::i64_or),
+                _ => panic_ret_type(),
+            },
             NumShiftLeftBy => {
                 let num = self.arguments[0];
                 let bits = self.arguments[1];
-                backend
-                    .storage
-                    .load_symbols(&mut backend.code_builder, &[num, bits]);
                 match CodeGenNumType::from(self.ret_layout) {
-                    I32 => backend.code_builder.i32_shl(),
+                    I32 => {
+                        backend
+                            .storage
+                            .load_symbols(&mut backend.code_builder, &[num, bits]);
+                        backend.code_builder.i32_shl();
+                    }
                     I64 => {
+                        backend
+                            .storage
+                            .load_symbols(&mut backend.code_builder, &[num, bits]);
                         backend.code_builder.i64_extend_u_i32();
                         backend.code_builder.i64_shl();
                     }
-                    I128 => todo!("{:?} for I128", self.lowlevel),
+                    I128 => self.load_args_and_call_zig(backend, "__ashlti3"), // from compiler_rt
                     _ => panic_ret_type(),
                 }
             }
@@ -1726,7 +1826,7 @@ impl<'a> LowLevelCall<'a> {
                         backend.code_builder.i64_extend_u_i32();
                         backend.code_builder.i64_shr_s();
                     }
-                    I128 => todo!("{:?} for I128", self.lowlevel),
+                    I128 => self.load_args_and_call_zig(backend, "__ashrti3"), // from compiler_rt
                     _ => panic_ret_type(),
                 }
             }
@@ -1799,8 +1899,13 @@ impl<'a> LowLevelCall<'a> {
                         self.wrap_small_int(backend, ret_width);
                     }
                     (I32, I128) => {
-                        self.load_args(backend);
-                        backend.code_builder.i32_load(Align::Bytes4, 0);
+                        let (frame_ptr, offset) = num128_local_and_offset(
+                            backend,
+                            backend.storage.get(&self.arguments[0]),
+                        );
+                        backend.code_builder.get_local(frame_ptr);
+                        backend.code_builder.i32_load(Align::Bytes4, offset);
+                        self.wrap_small_int(backend, ret_width);
                     }
                     (I64, I32) => {
                         self.load_args(backend);
@@ -1814,31 +1919,54 @@ impl<'a> LowLevelCall<'a> {
                         self.load_args(backend);
                     }
                     (I64, I128) => {
-                        let (frame_ptr, offset) = match backend.storage.get(&self.arguments[0]) {
-                            StoredValue::StackMemory { location, .. } => {
-                                location.local_and_offset(backend.storage.stack_frame_pointer)
-                            }
-                            _ => internal_error!("I128 should be in stack memory"),
-                        };
+                        let (frame_ptr, offset) = num128_local_and_offset(
+                            backend,
+                            backend.storage.get(&self.arguments[0]),
+                        );
                         backend.code_builder.get_local(frame_ptr);
                         backend.code_builder.i64_load(Align::Bytes8, offset);
                     }
-                    (I128, I64) => {
-                        // Symbols are loaded as if for a call, so the i128 "return address" and i64 value are on the value stack
-                        self.load_args(backend);
-                        backend.code_builder.i64_store(Align::Bytes8, 0);
-
-                        // Zero the most significant 64 bits
-                        let (frame_ptr, offset) = match &self.ret_storage {
-                            StoredValue::StackMemory { location, .. } => {
-                                location.local_and_offset(backend.storage.stack_frame_pointer)
+                    (I128, I32 | I64) => {
+                        let (frame_ptr, offset) =
+                            num128_local_and_offset(backend, &self.ret_storage);
+                        let load_as_i64 = |backend: &mut WasmBackend<'a, '_>| {
+                            backend
+                                .storage
+                                .load_symbols(&mut backend.code_builder, self.arguments);
+                            if arg_type == I32 {
+                                if arg_width.is_signed() {
+                                    backend.code_builder.i64_extend_s_i32()
+                                } else {
+                                    backend.code_builder.i64_extend_u_i32()
+                                }
                             }
-                            _ => internal_error!("I128 should be in stack memory"),
                         };
+
+                        // Least significant 64 bits
                         backend.code_builder.get_local(frame_ptr);
-                        backend.code_builder.i64_const(0);
+                        load_as_i64(backend);
+                        backend.code_builder.i64_store(Align::Bytes8, offset);
+
+                        // Most significant 64 bits are copies of the sign bit, or zero
+                        backend.code_builder.get_local(frame_ptr);
+                        if arg_width.is_signed() {
+                            load_as_i64(backend);
+                            backend.code_builder.i64_const(63);
+                            backend.code_builder.i64_shr_s();
+                        } else {
+                            backend.code_builder.i64_const(0);
+                        }
                         backend.code_builder.i64_store(Align::Bytes8, offset + 8);
                     }
+                    (I128, I128) => {
+                        // Same bits, possibly with a different sign interpretation
+                        let arg = num128_local_and_offset(
+                            backend,
+                            backend.storage.get(&self.arguments[0]),
+                        );
+                        let ret = num128_local_and_offset(backend, &self.ret_storage);
+                        copy_num128(backend, arg, ret);
+                    }
 
                     _ => todo!("{:?}: {:?} -> {:?}", self.lowlevel, arg_type, ret_type),
                 }
@@ -1887,6 +2015,23 @@ impl<'a> LowLevelCall<'a> {
                             backend.code_builder.f64_convert_u_i64()
                         }
                     }
+                    // from compiler_rt
+                    (F32, I128) => {
+                        let name = if arg_signed {
+                            "__floattisf"
+                        } else {
+                            "__floatuntisf"
+                        };
+                        backend.call_host_fn_after_loading_args(name, 2, true)
+                    }
+                    (F64, I128) => {
+                        let name = if arg_signed {
+                            "__floattidf"
+                        } else {
+                            "__floatuntidf"
+                        };
+                        backend.call_host_fn_after_loading_args(name, 2, true)
+                    }
                     _ => todo!("{:?}: {:?} -> {:?}", self.lowlevel, arg_type, ret_type),
                 }
             }
@@ -2137,6 +2282,220 @@ impl<'a> LowLevelCall<'a> {
         backend.code_builder.i32_and();
     }
 
+    /// Ordering comparisons for 128-bit numbers
+    /// Takes care of loading the arguments
+    fn compare_num128(&self, backend: &mut WasmBackend<'a, '_>) {
+        let layout = backend.storage.symbol_layouts[&self.arguments[0]];
+
+        // Dec is a fixed-point number stored in an i128, so it's ordered like a signed integer
+        let is_signed = layout_is_signed_int(layout) || layout == Layout::DEC;
+
+        let args = [
+            num128_local_and_offset(backend, backend.storage.get(&self.arguments[0])),
+            num128_local_and_offset(backend, backend.storage.get(&self.arguments[1])),
+        ];
+
+        match self.lowlevel {
+            LowLevel::NumGt => Self::compare_num128_halves(
+                backend,
+                args,
+                is_signed,
+                CodeBuilder::i64_gt_s,
+                CodeBuilder::i64_gt_u,
+            ),
+            LowLevel::NumGte => Self::compare_num128_halves(
+                backend,
+                args,
+                is_signed,
+                CodeBuilder::i64_ge_s,
+                CodeBuilder::i64_ge_u,
+            ),
+            LowLevel::NumLt => Self::compare_num128_halves(
+                backend,
+                args,
+                is_signed,
+                CodeBuilder::i64_lt_s,
+                CodeBuilder::i64_lt_u,
+            ),
+            LowLevel::NumLte => Self::compare_num128_halves(
+                backend,
+                args,
+                is_signed,
+                CodeBuilder::i64_le_s,
+                CodeBuilder::i64_le_u,
+            ),
+            LowLevel::NumCompare => {
+                // (x != y) as u8 + (x < y) as u8, as for the other number types
+                Self::compare_num128_halves(
+                    backend,
+                    args,
+                    is_signed,
+                    CodeBuilder::i64_ne,
+                    CodeBuilder::i64_ne,
+                );
+                Self::compare_num128_halves(
+                    backend,
+                    args,
+                    is_signed,
+                    CodeBuilder::i64_lt_s,
+                    CodeBuilder::i64_lt_u,
+                );
+                backend.code_builder.i32_add();
+            }
+            _ => internal_error!("{:?} is not a comparison", self.lowlevel),
+        }
+    }
+
+    /// Compare two 128-bit numbers one 64-bit half at a time.
+    /// The most significant halves decide the result, unless they're equal.
+    /// In that case it's decided by the least significant halves, which are always unsigned.
+    fn compare_num128_halves(
+        backend: &mut WasmBackend<'a, '_>,
+        args: [(LocalId, u32); 2],
+        is_signed: bool,
+        signed_op: fn(&mut CodeBuilder<'a>),
+        unsigned_op: fn(&mut CodeBuilder<'a>),
+    ) {
+        let [(local0, offset0), (local1, offset1)] = args;
+        let code_builder = &mut backend.code_builder;
+
+        // Result if the most significant halves are different
+        code_builder.get_local(local0);
+        code_builder.i64_load(Align::Bytes8, offset0 + 8);
+        code_builder.get_local(local1);
+        code_builder.i64_load(Align::Bytes8, offset1 + 8);
+        if is_signed {
+            signed_op(code_builder);
+        } else {
+            unsigned_op(code_builder);
+        }
+
+        // Result if they're equal
+        code_builder.get_local(local0);
+        code_builder.i64_load(Align::Bytes8, offset0);
+        code_builder.get_local(local1);
+        code_builder.i64_load(Align::Bytes8, offset1);
+        unsigned_op(code_builder);
+
+        // Choose a result
+        code_builder.get_local(local0);
+        code_builder.i64_load(Align::Bytes8, offset0 + 8);
+        code_builder.get_local(local1);
+        code_builder.i64_load(Align::Bytes8, offset1 + 8);
+        code_builder.i64_ne();
+        code_builder.select();
+    }
+
+    /// Bitwise operations on 128-bit integers, one 64-bit half at a time
+    fn bitwise_num128(&self, backend: &mut WasmBackend<'a, '_>, op: fn(&mut CodeBuilder<'a>)) {
+        let (local0, offset0) =
+            num128_local_and_offset(backend, backend.storage.get(&self.arguments[0]));
+        let (local1, offset1) =
+            num128_local_and_offset(backend, backend.storage.get(&self.arguments[1]));
+        let (ret_local, ret_offset) = num128_local_and_offset(backend, &self.ret_storage);
+
+        let code_builder = &mut backend.code_builder;
+        for half in [0, 8] {
+            code_builder.get_local(ret_local);
+            code_builder.get_local(local0);
+            code_builder.i64_load(Align::Bytes8, offset0 + half);
+            code_builder.get_local(local1);
+            code_builder.i64_load(Align::Bytes8, offset1 + half);
+            op(code_builder);
+            code_builder.i64_store(Align::Bytes8, ret_offset + half);
+        }
+    }
+
+    fn neg_num128(&self, backend: &mut WasmBackend<'a, '_>, panic_msg: &'a str) {
+        let arg = num128_local_and_offset(backend, backend.storage.get(&self.arguments[0]));
+        let ret = num128_local_and_offset(backend, &self.ret_storage);
+
+        panic_if_num128_min(backend, arg, panic_msg);
+        store_neg_num128(backend, arg, ret);
+    }
+
+    fn abs_num128(&self, backend: &mut WasmBackend<'a, '_>, panic_msg: &'a str) {
+        let arg = num128_local_and_offset(backend, backend.storage.get(&self.arguments[0]));
+        let ret = num128_local_and_offset(backend, &self.ret_storage);
+
+        if !layout_is_signed_int(self.ret_layout) && self.ret_layout != Layout::DEC {
+            copy_num128(backend, arg, ret);
+            return;
+        }
+
+        panic_if_num128_min(backend, arg, panic_msg);
+
+        // x < 0
+        let (arg_local, arg_offset) = arg;
+        backend.code_builder.get_local(arg_local);
+        backend.code_builder.i64_load(Align::Bytes8, arg_offset + 8);
+        backend.code_builder.i64_const(0);
+        backend.code_builder.i64_lt_s();
+
+        // (x < 0) ? -x : x
+        backend.code_builder.if_();
+        store_neg_num128(backend, arg, ret);
+        backend.code_builder.else_();
+        copy_num128(backend, arg, ret);
+        backend.code_builder.end();
+    }
+
+    /// Same construct as NumIsMultipleOf for smaller integers, but the remainder comes from compiler_rt
+    fn num128_is_multiple_of(&self, backend: &mut WasmBackend<'a, '_>, is_signed: bool) {
+        let lhs = num128_local_and_offset(backend, backend.storage.get(&self.arguments[0]));
+        let rhs = num128_local_and_offset(backend, backend.storage.get(&self.arguments[1]));
+        let tmp = backend.storage.create_anonymous_local(ValueType::I32);
+        let rem = backend.storage.allocate_anonymous_stack_memory(16, 16);
+
+        load_num128_halves(&mut backend.code_builder, rhs);
+        backend.code_builder.i64_or();
+        backend.code_builder.i64_const(0);
+        backend.code_builder.i64_ne(); // rhs != 0
+
+        if is_signed {
+            load_num128_halves(&mut backend.code_builder, rhs);
+            backend.code_builder.i64_and();
+            backend.code_builder.i64_const(-1);
+            backend.code_builder.i64_ne(); // rhs != -1
+            backend.code_builder.i32_and(); // rhs != 0 && rhs != -1
+        }
+        backend.code_builder.if_();
+        {
+            let (rem_local, rem_offset) = rem;
+            backend.code_builder.get_local(rem_local);
+            backend.code_builder.i32_const(rem_offset as i32);
+            backend.code_builder.i32_add();
+            load_num128_halves(&mut backend.code_builder, lhs);
+            load_num128_halves(&mut backend.code_builder, rhs);
+            if is_signed {
+                backend.call_host_fn_after_loading_args("__modti3", 5, false);
+            } else {
+                backend.call_host_fn_after_loading_args("__umodti3", 5, false);
+            }
+
+            load_num128_halves(&mut backend.code_builder, rem);
+            backend.code_builder.i64_or();
+            backend.code_builder.i64_eqz(); // rem == 0
+            backend.code_builder.set_local(tmp);
+        }
+        backend.code_builder.else_();
+        {
+            load_num128_halves(&mut backend.code_builder, lhs);
+            backend.code_builder.i64_or();
+            backend.code_builder.i64_eqz(); // lhs == 0
+            if is_signed {
+                load_num128_halves(&mut backend.code_builder, rhs);
+                backend.code_builder.i64_and();
+                backend.code_builder.i64_const(-1);
+                backend.code_builder.i64_eq(); // rhs == -1
+                backend.code_builder.i32_or(); // (lhs == 0) || (rhs == -1)
+            }
+            backend.code_builder.set_local(tmp);
+        }
+        backend.code_builder.end();
+        backend.code_builder.get_local(tmp);
+    }
+
     fn num_to_str(&self, backend: &mut WasmBackend<'a, '_>) {
         let arg_layout = backend.storage.symbol_layouts[&self.arguments[0]];
         match backend.layout_interner.get(arg_layout) {
@@ -2161,6 +2520,89 @@ impl<'a> LowLevelCall<'a> {
     }
 }
 
+/// Where a 128-bit number (I128, U128 or Dec) lives in stack memory
+fn num128_local_and_offset(backend: &WasmBackend<'_, '_>, stored: &StoredValue) -> (LocalId, u32) {
+    match stored {
+        StoredValue::StackMemory { location, .. } => {
+            location.local_and_offset(backend.storage.stack_frame_pointer)
+        }
+        _ => internal_error!("128-bit numbers should be in stack memory"),
+    }
+}
+
+/// Load both 64-bit halves of a 128-bit number, least significant first.
+/// This is also how Zig and compiler_rt expect 128-bit arguments to be passed.
+fn load_num128_halves(code_builder: &mut CodeBuilder, (local, offset): (LocalId, u32)) {
+    code_builder.get_local(local);
+    code_builder.i64_load(Align::Bytes8, offset);
+    code_builder.get_local(local);
+    code_builder.i64_load(Align::Bytes8, offset + 8);
+}
+
+fn copy_num128(backend: &mut WasmBackend<'_, '_>, from: (LocalId, u32), to: (LocalId, u32)) {
+    let (from_local, from_offset) = from;
+    let (to_local, to_offset) = to;
+    for half in [0, 8] {
+        backend.code_builder.get_local(to_local);
+        backend.code_builder.get_local(from_local);
+        backend
+            .code_builder
+            .i64_load(Align::Bytes8, from_offset + half);
+        backend
+            .code_builder
+            .i64_store(Align::Bytes8, to_offset + half);
+    }
+}
+
+/// Store 0 - x, one 64-bit half at a time.
+/// The most significant half is written first, in case `from` and `to` are the same memory.
+fn store_neg_num128(backend: &mut WasmBackend<'_, '_>, from: (LocalId, u32), to: (LocalId, u32)) {
+    let (from_local, from_offset) = from;
+    let (to_local, to_offset) = to;
+    let code_builder = &mut backend.code_builder;
+
+    // 0 - high - borrow, where we borrow from the high half if the low half is nonzero
+    code_builder.get_local(to_local);
+    code_builder.i64_const(0);
+    code_builder.get_local(from_local);
+    code_builder.i64_load(Align::Bytes8, from_offset + 8);
+    code_builder.i64_sub();
+    code_builder.get_local(from_local);
+    code_builder.i64_load(Align::Bytes8, from_offset);
+    code_builder.i64_const(0);
+    code_builder.i64_ne();
+    code_builder.i64_extend_u_i32();
+    code_builder.i64_sub();
+    code_builder.i64_store(Align::Bytes8, to_offset + 8);
+
+    // 0 - low
+    code_builder.get_local(to_local);
+    code_builder.i64_const(0);
+    code_builder.get_local(from_local);
+    code_builder.i64_load(Align::Bytes8, from_offset);
+    code_builder.i64_sub();
+    code_builder.i64_store(Align::Bytes8, to_offset);
+}
+
+/// The minimum signed 128-bit number has no positive counterpart, so negating it overflows
+fn panic_if_num128_min<'a>(
+    backend: &mut WasmBackend<'a, '_>,
+    (local, offset): (LocalId, u32),
+    panic_msg: &'a str,
+) {
+    backend.code_builder.get_local(local);
+    backend.code_builder.i64_load(Align::Bytes8, offset + 8);
+    backend.code_builder.i64_const(i64::MIN);
+    backend.code_builder.i64_eq();
+    backend.code_builder.get_local(local);
+    backend.code_builder.i64_load(Align::Bytes8, offset);
+    backend.code_builder.i64_eqz();
+    backend.code_builder.i32_and();
+    backend.code_builder.if_();
+    backend.stmt_internal_error(panic_msg);
+    backend.code_builder.end();
+}
+
 /// Helper for NumIsFinite op, and also part of Eq/NotEq
 fn num_is_finite(backend: &mut WasmBackend<'_, '_>, argument: Symbol) {
     use StoredValue::*;
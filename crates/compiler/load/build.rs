@@ -106,6 +106,8 @@ fn write_types_for_module_real(module_id: ModuleId, filename: &str, output_path:
         &module.interns,
         &mut module.can_problems,
         &mut module.type_problems,
+        roc_reporting::report::RenderTarget::ColorTerminal,
+        &roc_reporting::warnings::WarningConfig::default(),
     );
 
     if problems.errors + problems.warnings > 0 {
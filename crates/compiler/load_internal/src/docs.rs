@@ -7,6 +7,7 @@ use roc_module::symbol::{IdentIds, ModuleId, ModuleIds, Symbol};
 use roc_parse::ast::AssignedField;
 use roc_parse::ast::{self, ExtractSpaces, TypeHeader};
 use roc_parse::ast::{CommentOrNewline, TypeDef, ValueDef};
+use serde::Serialize;
 
 // Documentation generation requirements
 
@@ -18,22 +19,23 @@ pub struct ModuleDocumentation {
     pub exposed_symbols: VecSet<Symbol>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum DocEntry {
     DocDef(DocDef),
     DetachedDoc(String),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DocDef {
     pub name: String,
+    #[serde(skip)]
     pub symbol: Symbol,
     pub type_vars: Vec<String>,
     pub type_annotation: TypeAnnotation,
     pub docs: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum TypeAnnotation {
     TagUnion {
         tags: Vec<Tag>,
@@ -61,7 +63,7 @@ pub enum TypeAnnotation {
     NoTypeAnn,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum RecordField {
     RecordField {
         name: String,
@@ -76,7 +78,7 @@ pub enum RecordField {
     },
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct AbilityMember {
     pub name: String,
     pub type_annotation: TypeAnnotation,
@@ -84,7 +86,7 @@ pub struct AbilityMember {
     pub docs: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Tag {
     pub name: String,
     pub values: Vec<TypeAnnotation>,
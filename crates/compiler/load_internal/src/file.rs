@@ -75,6 +75,7 @@ use {
     roc_packaging::https::PackageMetadata,
 };
 
+use crate::type_cache::{self, CacheFile, CacheKey};
 pub use crate::work::Phase;
 use crate::work::{DepCycle, Dependencies};
 
@@ -155,6 +156,7 @@ struct ModuleCache<'a> {
     documentation: VecMap<ModuleId, ModuleDocumentation>,
     can_problems: MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
     type_problems: MutMap<ModuleId, Vec<TypeError>>,
+    cache_keys: MutMap<ModuleId, CacheKey>,
 
     sources: MutMap<ModuleId, (PathBuf, &'a str)>,
 }
@@ -223,6 +225,7 @@ impl Default for ModuleCache<'_> {
             documentation: Default::default(),
             can_problems: Default::default(),
             type_problems: Default::default(),
+            cache_keys: Default::default(),
             sources: Default::default(),
         }
     }
@@ -378,10 +381,50 @@ fn start_phase<'a>(
                     }
                 }
 
+                // The root module isn't in module_names, because nothing imports it by name.
+                // It's also the module most likely to have just been edited (the REPL generates
+                // a new one for every input!) so there's no point in caching its types anyway.
+                let opt_module_name = state.module_cache.module_names.get(&module_id);
+                let opt_cache_key = opt_module_name.and_then(|module_name| {
+                    let cache_keys = &state.module_cache.cache_keys;
+
+                    // If an import has no key, we can't tell whether it changed.
+                    let import_keys: Option<Vec<&CacheKey>> = parsed
+                        .imported_modules
+                        .keys()
+                        .map(|imported| cache_keys.get(imported))
+                        .collect();
+
+                    import_keys
+                        .map(|import_keys| CacheKey::new(module_name, parsed.src, import_keys))
+                });
+
+                if let Some(cache_key) = opt_cache_key {
+                    state.module_cache.cache_keys.insert(module_id, cache_key);
+                }
+
                 let skip_constraint_gen = {
                     // Give this its own scope to make sure that the Guard from the lock() is dropped
                     // immediately after contains_key returns
-                    state.cached_types.lock().contains_key(&module_id)
+                    let mut cached_types = state.cached_types.lock();
+
+                    if let (Some(cache_dir), Some(cache_key), false) = (
+                        &state.type_cache_dir,
+                        opt_cache_key,
+                        module_id.is_builtin() || cached_types.contains_key(&module_id),
+                    ) {
+                        let module_ids = state.arc_modules.lock();
+
+                        if let Some(type_state) =
+                            type_cache::read(cache_dir, &cache_key, &module_ids)
+                        {
+                            log!("using cached types for {:?}", module_id);
+
+                            cached_types.insert(module_id, type_state);
+                        }
+                    }
+
+                    cached_types.contains_key(&module_id)
                 };
 
                 BuildTask::CanonicalizeAndConstrain {
@@ -416,6 +459,17 @@ fn start_phase<'a>(
 
                 let derived_module = SharedDerivedModule::clone(&state.derived_module);
 
+                // Builtins are cached when the compiler itself is built
+                let opt_cache_file = match (
+                    &state.type_cache_dir,
+                    state.module_cache.cache_keys.get(&module_id),
+                ) {
+                    (Some(cache_dir), Some(cache_key)) if !module_id.is_builtin() => Some(
+                        CacheFile::new(cache_dir, cache_key, &state.arc_modules.lock()),
+                    ),
+                    _ => None,
+                };
+
                 BuildTask::solve_module(
                     module,
                     ident_ids,
@@ -430,6 +484,7 @@ fn start_phase<'a>(
                     dep_idents,
                     declarations,
                     state.cached_types.clone(),
+                    opt_cache_file,
                     derived_module,
                 )
             }
@@ -1022,9 +1077,12 @@ struct State<'a> {
 
     make_specializations_pass: MakeSpecializationsPass,
 
-    // cached types (used for builtin modules, could include packages in the future too)
+    // cached types (used for builtin modules, and for other modules found in the type cache)
     cached_types: CachedTypeState,
 
+    /// Where to persist the type state of solved modules, if anywhere
+    type_cache_dir: Option<PathBuf>,
+
     layout_interner: GlobalLayoutInterner<'a>,
 }
 
@@ -1043,6 +1101,7 @@ impl<'a> State<'a> {
         arc_modules: Arc<Mutex<PackageModuleIds<'a>>>,
         ident_ids_by_module: SharedIdentIdsByModule,
         cached_types: MutMap<ModuleId, TypeState>,
+        type_cache_dir: Option<PathBuf>,
         render: RenderTarget,
         palette: Palette,
         number_of_workers: usize,
@@ -1078,6 +1137,7 @@ impl<'a> State<'a> {
             timings: MutMap::default(),
             layout_caches: std::vec::Vec::with_capacity(number_of_workers),
             cached_types: Arc::new(Mutex::new(cached_types)),
+            type_cache_dir,
             render,
             palette,
             exec_mode,
@@ -1100,6 +1160,9 @@ pub struct ModuleTiming {
     // indexed by make specializations pass
     pub make_specializations: Vec<Duration>,
     // TODO pub monomorphize: Duration,
+    /// Whether the module's solved types came from the cached types (of the builtins, or from the
+    /// type cache), which skips constraint generation and solving.
+    pub types_from_cache: bool,
     /// Total duration will always be more than the sum of the other fields, due
     /// to things like state lookups in between phases, waiting on other threads, etc.
     start_time: Instant,
@@ -1117,6 +1180,7 @@ impl ModuleTiming {
             solve: Duration::default(),
             find_specializations: Duration::default(),
             make_specializations: Vec::with_capacity(2),
+            types_from_cache: false,
             start_time,
             end_time: start_time, // just for now; we'll overwrite this at the end
         }
@@ -1137,6 +1201,7 @@ impl ModuleTiming {
             solve,
             find_specializations,
             make_specializations,
+            types_from_cache: _,
             start_time,
             end_time,
         } = self;
@@ -1239,6 +1304,7 @@ enum BuildTask<'a> {
         declarations: Declarations,
         dep_idents: IdentIdsByModule,
         cached_subs: CachedTypeState,
+        opt_cache_file: Option<CacheFile>,
         derived_module: SharedDerivedModule,
     },
     BuildPendingSpecializations {
@@ -1670,6 +1736,7 @@ pub fn load_single_threaded<'a>(
         arc_modules,
         ident_ids_by_module,
         cached_types,
+        type_cache::cache_dir(roc_cache_dir),
         render,
         palette,
         number_of_workers,
@@ -2000,6 +2067,7 @@ fn load_multi_threaded<'a>(
         arc_modules,
         ident_ids_by_module,
         cached_types,
+        type_cache::cache_dir(roc_cache_dir),
         render,
         palette,
         num_workers,
@@ -4769,6 +4837,7 @@ impl<'a> BuildTask<'a> {
         dep_idents: IdentIdsByModule,
         declarations: Declarations,
         cached_subs: CachedTypeState,
+        opt_cache_file: Option<CacheFile>,
         derived_module: SharedDerivedModule,
     ) -> Self {
         let exposed_by_module = exposed_types.retain_modules(imported_modules.keys());
@@ -4790,6 +4859,7 @@ impl<'a> BuildTask<'a> {
             dep_idents,
             module_timing,
             cached_subs,
+            opt_cache_file,
             derived_module,
         }
     }
@@ -5153,6 +5223,44 @@ fn run_solve_solve(
     )
 }
 
+/// What `run_solve_solve` returns
+type SolvedParts = (
+    Solved<Subs>,
+    ResolvedImplementations,
+    Vec<(Symbol, Variable)>,
+    Vec<TypeError>,
+    AbilitiesStore,
+);
+
+/// Persist the result of solving a module that had no type problems, and hand it back.
+fn write_type_cache(cache_file: &CacheFile, solved: SolvedParts) -> SolvedParts {
+    let (solved_subs, solved_implementations, exposed_vars_by_symbol, problems, abilities) = solved;
+
+    let type_state = TypeState {
+        subs: solved_subs.into_inner(),
+        exposed_vars_by_symbol,
+        abilities,
+        solved_implementations,
+    };
+
+    type_cache::write(cache_file, &type_state);
+
+    let TypeState {
+        subs,
+        exposed_vars_by_symbol,
+        abilities,
+        solved_implementations,
+    } = type_state;
+
+    (
+        Solved(subs),
+        solved_implementations,
+        exposed_vars_by_symbol,
+        problems,
+        abilities,
+    )
+}
+
 fn run_solve<'a>(
     module: Module,
     ident_ids: IdentIds,
@@ -5166,6 +5274,7 @@ fn run_solve<'a>(
     decls: Declarations,
     dep_idents: IdentIdsByModule,
     cached_types: CachedTypeState,
+    opt_cache_file: Option<CacheFile>,
     derived_module: SharedDerivedModule,
 ) -> Msg<'a> {
     let solve_start = Instant::now();
@@ -5180,10 +5289,14 @@ fn run_solve<'a>(
     let loc_dbgs = std::mem::take(&mut module.loc_dbgs);
     let module = module;
 
+    // Take the cached types out in a separate statement, so the lock is released before solving.
+    let opt_cached = cached_types.lock().remove(&module_id);
+    module_timing.types_from_cache = opt_cached.is_some();
+
     let (solved_subs, solved_implementations, exposed_vars_by_symbol, problems, abilities_store) = {
-        if module_id.is_builtin() {
-            match cached_types.lock().remove(&module_id) {
-                None => run_solve_solve(
+        match opt_cached {
+            None => {
+                let solved = run_solve_solve(
                     exposed_for_module,
                     types,
                     constraints,
@@ -5192,31 +5305,27 @@ fn run_solve<'a>(
                     var_store,
                     module,
                     derived_module,
-                ),
-                Some(TypeState {
-                    subs,
-                    exposed_vars_by_symbol,
-                    abilities,
-                    solved_implementations,
-                }) => (
-                    Solved(subs),
-                    solved_implementations,
-                    exposed_vars_by_symbol,
-                    vec![],
-                    abilities,
-                ),
+                );
+
+                match opt_cache_file {
+                    Some(cache_file) if solved.3.is_empty() => {
+                        write_type_cache(&cache_file, solved)
+                    }
+                    _ => solved,
+                }
             }
-        } else {
-            run_solve_solve(
-                exposed_for_module,
-                types,
-                constraints,
-                constraint,
-                pending_derives,
-                var_store,
-                module,
-                derived_module,
-            )
+            Some(TypeState {
+                subs,
+                exposed_vars_by_symbol,
+                abilities,
+                solved_implementations,
+            }) => (
+                Solved(subs),
+                solved_implementations,
+                exposed_vars_by_symbol,
+                vec![],
+                abilities,
+            ),
         }
     };
 
@@ -5767,6 +5876,7 @@ fn make_specializations<'a>(
         abilities: AbilitiesView::World(&world_abilities),
         exposed_by_module,
         derived_module: &derived_module,
+        region: Region::zero(),
     };
 
     let mut procs = Procs::new_in(arena);
@@ -5867,6 +5977,7 @@ fn build_pending_specializations<'a>(
         abilities: AbilitiesView::Module(&abilities_store),
         exposed_by_module,
         derived_module: &derived_module,
+        region: Region::zero(),
     };
 
     let layout_cache_snapshot = layout_cache.snapshot();
@@ -5953,6 +6064,7 @@ fn build_pending_specializations<'a>(
                             body_var: expr_var,
                             // This is a 0-arity thunk, so it cannot be recursive
                             is_self_recursive: false,
+                            region: body.region,
                         };
 
                         procs_base.partial_procs.insert(symbol, proc);
@@ -6092,6 +6204,7 @@ fn build_pending_specializations<'a>(
                     body_var: expr_var,
                     // This is a 0-arity thunk, so it cannot be recursive
                     is_self_recursive: false,
+                    region: body.region,
                 };
 
                 procs_base.partial_procs.insert(symbol, proc);
@@ -6161,6 +6274,7 @@ fn build_pending_specializations<'a>(
                     body_var: expr_var,
                     // This is a 0-arity thunk, so it cannot be recursive
                     is_self_recursive: false,
+                    region: body.region,
                 };
 
                 // extend the region of the expect expression with the region of the preceding
@@ -6234,6 +6348,7 @@ fn build_pending_specializations<'a>(
                     body_var: expr_var,
                     // This is a 0-arity thunk, so it cannot be recursive
                     is_self_recursive: false,
+                    region: body.region,
                 };
 
                 // extend the region of the expect expression with the region of the preceding
@@ -6348,6 +6463,7 @@ fn load_derived_partial_procs<'a>(
             abilities: AbilitiesView::World(world_abilities),
             exposed_by_module,
             derived_module,
+            region: Region::zero(),
         };
 
         let partial_proc = match derived_expr {
@@ -6385,6 +6501,8 @@ fn load_derived_partial_procs<'a>(
                     body_var: derived_expr_var,
                     // This is a 0-arity thunk, so it cannot be recursive
                     is_self_recursive: false,
+                    // Derived implementations have no source code
+                    region: Region::zero(),
                 }
             }
         };
@@ -6469,6 +6587,7 @@ fn run_task<'a>(
             declarations,
             dep_idents,
             cached_subs,
+            opt_cache_file,
             derived_module,
         } => Ok(run_solve(
             module,
@@ -6483,6 +6602,7 @@ fn run_task<'a>(
             declarations,
             dep_idents,
             cached_subs,
+            opt_cache_file,
             derived_module,
         )),
         BuildPendingSpecializations {
@@ -6603,6 +6723,7 @@ fn to_import_cycle_report(
         doc,
         title: "IMPORT CYCLE".to_string(),
         severity: Severity::RuntimeError,
+        code: None,
     };
 
     let mut buf = String::new();
@@ -6652,6 +6773,7 @@ fn to_incorrect_module_name_report<'a>(
         doc,
         title: "INCORRECT MODULE NAME".to_string(),
         severity: Severity::RuntimeError,
+        code: None,
     };
 
     let mut buf = String::new();
@@ -6731,6 +6853,7 @@ fn to_missing_platform_report(module_id: ModuleId, other: &PlatformPath) -> Stri
                     doc,
                     title: "NO PLATFORM".to_string(),
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
             RootIsInterface => {
@@ -6746,6 +6869,7 @@ fn to_missing_platform_report(module_id: ModuleId, other: &PlatformPath) -> Stri
                     doc,
                     title: "NO PLATFORM".to_string(),
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
             RootIsHosted => {
@@ -6761,6 +6885,7 @@ fn to_missing_platform_report(module_id: ModuleId, other: &PlatformPath) -> Stri
                     doc,
                     title: "NO PLATFORM".to_string(),
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
             RootIsPlatformModule => {
@@ -6776,6 +6901,7 @@ fn to_missing_platform_report(module_id: ModuleId, other: &PlatformPath) -> Stri
                     doc,
                     title: "NO PLATFORM".to_string(),
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
         }
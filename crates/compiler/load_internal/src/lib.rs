@@ -6,6 +6,7 @@
 use roc_module::symbol::ModuleId;
 pub mod docs;
 pub mod file;
+mod type_cache;
 mod work;
 
 #[cfg(target_family = "wasm")]
@@ -0,0 +1,315 @@
+//! A persistent, on-disk cache of the solved types of non-builtin modules.
+//!
+//! Builtins get their [TypeState] serialized at build time (see `crates/compiler/load/build.rs`).
+//! Every other module (apps, packages, platforms) gets its [TypeState] written to the roc cache
+//! directory after it has been solved without problems. On the next run, a module whose cache key
+//! matches reuses that [TypeState] and skips constraint generation and solving entirely.
+//!
+//! Only types are cached. Such a module is still parsed and canonicalized, because reporting,
+//! docs and monomorphization need its canonical declarations, and those have no serialized form.
+//! Skipping parsing and canonicalization too would need one for `roc_can::expr::Declarations`.
+//!
+//! The cache key of a module is a hash of
+//!
+//! - the cache format version,
+//! - the module's (package-qualified) name,
+//! - the module's source, and
+//! - the cache keys of every module it imports.
+//!
+//! Because the keys of imports are included, a change to a module invalidates every module that
+//! (transitively) depends on it.
+//!
+//! Module ids are handed out in load order, so the same module can have a different id in the
+//! next compilation. That's why they are not part of the key. Instead, every cache entry records
+//! the names of the modules that the ids in its symbols referred to, and the symbols are moved
+//! over to the module ids of the current compilation when the entry is read.
+use roc_can::module::TypeState;
+use roc_module::ident::ModuleName;
+use roc_module::symbol::{ModuleId, PQModuleName, PackageModuleIds, PackageQualified, Symbol};
+use roc_packaging::cache::RocCacheDir;
+use std::path::{Path, PathBuf};
+
+/// Bump this whenever the serialized format of [TypeState] changes (e.g. the layout of `Subs`,
+/// `AbilitiesStore` or the solved implementations), so that stale cache entries are ignored.
+const CACHE_FORMAT_VERSION: u32 = 3;
+
+/// The name of the subdirectory of the roc cache dir where type state is stored.
+const TYPE_CACHE_SUBDIR: &str = "types";
+
+const CHECKSUM_LEN: usize = blake3::OUT_LEN;
+
+/// At least the alignment of every type in a serialized [TypeState]
+const ALIGNMENT: usize = 16;
+
+#[derive(Clone, Copy)]
+#[repr(align(16))]
+struct AlignedChunk {
+    _bytes: [u8; ALIGNMENT],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheKey(blake3::Hash);
+
+impl CacheKey {
+    pub fn new<'k>(
+        module_name: &PQModuleName<'_>,
+        src: &str,
+        import_keys: impl IntoIterator<Item = &'k CacheKey>,
+    ) -> Self {
+        let mut hasher = blake3::Hasher::new();
+
+        hasher.update(&CACHE_FORMAT_VERSION.to_le_bytes());
+
+        match module_name {
+            PackageQualified::Unqualified(name) => {
+                hasher.update(&[0]);
+                hash_str(&mut hasher, name.as_str());
+            }
+            PackageQualified::Qualified(shorthand, name) => {
+                hasher.update(&[1]);
+                hash_str(&mut hasher, shorthand);
+                hash_str(&mut hasher, name.as_str());
+            }
+        }
+
+        hash_str(&mut hasher, src);
+
+        // The order in which imports are visited is not deterministic, so sort their keys first.
+        let mut import_keys: Vec<[u8; blake3::OUT_LEN]> = import_keys
+            .into_iter()
+            .map(|key| *key.0.as_bytes())
+            .collect();
+        import_keys.sort_unstable();
+
+        hasher.update(&(import_keys.len() as u64).to_le_bytes());
+
+        for key in import_keys {
+            hasher.update(&key);
+        }
+
+        CacheKey(hasher.finalize())
+    }
+
+    fn file_name(&self) -> String {
+        format!("{}.dat", self.0.to_hex())
+    }
+}
+
+fn hash_str(hasher: &mut blake3::Hasher, string: &str) {
+    // Prefix with the length so that e.g. ("ab", "c") and ("a", "bc") hash differently.
+    hasher.update(&(string.len() as u64).to_le_bytes());
+    hasher.update(string.as_bytes());
+}
+
+/// The directory to store type state in, if we are allowed to write to the roc cache at all.
+pub fn cache_dir(roc_cache_dir: RocCacheDir<'_>) -> Option<PathBuf> {
+    match roc_cache_dir {
+        RocCacheDir::Persistent(dir) if cfg!(not(target_family = "wasm")) => {
+            Some(dir.join(TYPE_CACHE_SUBDIR))
+        }
+        _ => None,
+    }
+}
+
+fn cache_file(cache_dir: &Path, key: &CacheKey) -> PathBuf {
+    cache_dir.join(key.file_name())
+}
+
+/// Where to write the solved types of a module, along with the names of the modules that the
+/// module ids in its symbols refer to.
+#[derive(Debug)]
+pub struct CacheFile {
+    path: PathBuf,
+    module_names: Vec<u8>,
+}
+
+impl CacheFile {
+    /// `module_ids` must know every module whose symbols can show up in the module's types,
+    /// which is the case once all of the module's imports have been loaded.
+    pub fn new(cache_dir: &Path, key: &CacheKey, module_ids: &PackageModuleIds<'_>) -> Self {
+        let mut module_names = Vec::new();
+        let names: Vec<&PQModuleName> = module_ids.available_modules().collect();
+
+        // Module names are indexed by module id
+        module_names.extend_from_slice(&(names.len() as u64).to_le_bytes());
+
+        for name in names {
+            match name {
+                PackageQualified::Unqualified(name) => {
+                    module_names.push(0);
+                    write_str(&mut module_names, name.as_str());
+                }
+                PackageQualified::Qualified(shorthand, name) => {
+                    module_names.push(1);
+                    write_str(&mut module_names, shorthand);
+                    write_str(&mut module_names, name.as_str());
+                }
+            }
+        }
+
+        Self {
+            path: cache_file(cache_dir, key),
+            module_names,
+        }
+    }
+}
+
+fn write_str(bytes: &mut Vec<u8>, string: &str) {
+    bytes.extend_from_slice(&(string.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(string.as_bytes());
+}
+
+fn read_u64(bytes: &mut &[u8]) -> Option<u64> {
+    if bytes.len() < 8 {
+        return None;
+    }
+
+    let (int_bytes, rest) = bytes.split_at(8);
+    *bytes = rest;
+
+    Some(u64::from_le_bytes(int_bytes.try_into().unwrap()))
+}
+
+fn read_str<'b>(bytes: &mut &'b [u8]) -> Option<&'b str> {
+    let len = read_u64(bytes)? as usize;
+
+    if bytes.len() < len {
+        return None;
+    }
+
+    let (string_bytes, rest) = bytes.split_at(len);
+    *bytes = rest;
+
+    std::str::from_utf8(string_bytes).ok()
+}
+
+/// Reads the module names written by [CacheFile::new], and looks up the id each of them has in
+/// the current compilation. A module that isn't loaded (yet) has no id.
+fn read_module_ids(
+    mut bytes: &[u8],
+    module_ids: &PackageModuleIds<'_>,
+) -> Option<Vec<Option<ModuleId>>> {
+    let count = read_u64(&mut bytes)?;
+    let mut current_ids = Vec::new();
+
+    for _ in 0..count {
+        let current_id = match bytes.first()? {
+            0 => {
+                bytes = &bytes[1..];
+                let name = read_str(&mut bytes)?;
+
+                module_ids.get_id(&PackageQualified::Unqualified(ModuleName::from(name)))
+            }
+            1 => {
+                bytes = &bytes[1..];
+                let shorthand = read_str(&mut bytes)?;
+                let name = read_str(&mut bytes)?;
+
+                module_ids.get_id(&PackageQualified::Qualified(
+                    shorthand,
+                    ModuleName::from(name),
+                ))
+            }
+            _ => return None,
+        };
+
+        current_ids.push(current_id);
+    }
+
+    if bytes.is_empty() {
+        Some(current_ids)
+    } else {
+        None
+    }
+}
+
+/// Read the type state stored for the given key, if there is one and it is intact, with its
+/// symbols moved over to the module ids in `module_ids`.
+pub fn read(
+    cache_dir: &Path,
+    key: &CacheKey,
+    module_ids: &PackageModuleIds<'_>,
+) -> Option<TypeState> {
+    let bytes = std::fs::read(cache_file(cache_dir, key)).ok()?;
+
+    if bytes.len() < CHECKSUM_LEN {
+        return None;
+    }
+
+    let (checksum, payload) = bytes.split_at(CHECKSUM_LEN);
+
+    // Never hand a truncated or otherwise corrupted file to the deserializer; it trusts its input.
+    if blake3::hash(payload).as_bytes() != checksum {
+        return None;
+    }
+
+    // The serialized slices are padded to their alignment relative to the start of the payload,
+    // so it has to be deserialized from memory that is at least as aligned as any of them.
+    let chunk = AlignedChunk {
+        _bytes: [0; ALIGNMENT],
+    };
+    let mut aligned = vec![chunk; payload.len() / ALIGNMENT + 1];
+    let aligned_payload =
+        unsafe { std::slice::from_raw_parts_mut(aligned.as_mut_ptr() as *mut u8, payload.len()) };
+    aligned_payload.copy_from_slice(payload);
+
+    let (mut type_state, offset) = TypeState::deserialize(aligned_payload);
+
+    if offset > payload.len() {
+        return None;
+    }
+
+    let current_ids = read_module_ids(&payload[offset..], module_ids)?;
+
+    let mut all_known = true;
+    type_state.remap_symbols(&mut |symbol| match current_ids
+        .get(symbol.module_id().to_zero_indexed())
+    {
+        Some(Some(module_id)) => Symbol::new(*module_id, symbol.ident_id()),
+        _ => {
+            all_known = false;
+            symbol
+        }
+    });
+
+    if all_known {
+        Some(type_state)
+    } else {
+        None
+    }
+}
+
+/// Write the type state for the given cache file. Failing to write is not an error; it just means
+/// the module will have to be solved again next time.
+pub fn write(cache_file: &CacheFile, type_state: &TypeState) {
+    let mut payload = Vec::new();
+
+    if type_state.serialize(&mut payload).is_err() {
+        return;
+    }
+
+    payload.extend_from_slice(&cache_file.module_names);
+
+    let parent = match cache_file.path.parent() {
+        Some(parent) => parent,
+        None => return,
+    };
+
+    if std::fs::create_dir_all(parent).is_err() {
+        return;
+    }
+
+    let mut bytes = Vec::with_capacity(CHECKSUM_LEN + payload.len());
+    bytes.extend_from_slice(blake3::hash(&payload).as_bytes());
+    bytes.extend_from_slice(&payload);
+
+    // Write to a temporary file first and then rename it into place, so that a concurrent `roc`
+    // process never observes a partially written cache entry.
+    if let Ok(mut tempfile) = tempfile::NamedTempFile::new_in(parent) {
+        use std::io::Write;
+
+        if tempfile.write_all(&bytes).is_ok() {
+            let _ = tempfile.persist(&cache_file.path);
+        }
+    }
+}
@@ -33,19 +33,20 @@ use roc_types::pretty_print::DebugPrint;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-fn load_and_typecheck(
-    arena: &Bump,
+fn load_and_typecheck<'a>(
+    arena: &'a Bump,
     filename: PathBuf,
     exposed_types: ExposedByModule,
     target_info: TargetInfo,
-) -> Result<LoadedModule, LoadingProblem> {
+    roc_cache_dir: RocCacheDir<'_>,
+) -> Result<LoadedModule, LoadingProblem<'a>> {
     use LoadResult::*;
 
     let load_start = LoadStart::from_path(
         arena,
         filename,
         RenderTarget::Generic,
-        RocCacheDir::Disallowed,
+        roc_cache_dir,
         DEFAULT_PALETTE,
     )?;
     let load_config = LoadConfig {
@@ -61,7 +62,7 @@ fn load_and_typecheck(
         load_start,
         exposed_types,
         Default::default(), // these tests will re-compile the builtins
-        RocCacheDir::Disallowed,
+        roc_cache_dir,
         load_config,
     )? {
         Monomorphized(_) => unreachable!(""),
@@ -174,7 +175,13 @@ fn multiple_modules_help<'a>(
         writeln!(file, "{}", source)?;
         file_handles.push(file);
 
-        load_and_typecheck(arena, full_file_path, Default::default(), TARGET_INFO)
+        load_and_typecheck(
+            arena,
+            full_file_path,
+            Default::default(),
+            TARGET_INFO,
+            RocCacheDir::Disallowed,
+        )
     };
 
     Ok(result)
@@ -188,7 +195,13 @@ fn load_fixture(
     let src_dir = fixtures_dir().join(dir_name);
     let filename = src_dir.join(format!("{}.roc", module_name));
     let arena = Bump::new();
-    let loaded = load_and_typecheck(&arena, filename, subs_by_module, TARGET_INFO);
+    let loaded = load_and_typecheck(
+        &arena,
+        filename,
+        subs_by_module,
+        TARGET_INFO,
+        RocCacheDir::Disallowed,
+    );
     let mut loaded_module = match loaded {
         Ok(x) => x,
         Err(roc_load_internal::file::LoadingProblem::FormattedReport(report)) => {
@@ -345,7 +358,13 @@ fn interface_with_deps() {
     let src_dir = fixtures_dir().join("interface_with_deps");
     let filename = src_dir.join("Primary.roc");
     let arena = Bump::new();
-    let loaded = load_and_typecheck(&arena, filename, subs_by_module, TARGET_INFO);
+    let loaded = load_and_typecheck(
+        &arena,
+        filename,
+        subs_by_module,
+        TARGET_INFO,
+        RocCacheDir::Disallowed,
+    );
 
     let mut loaded_module = loaded.expect("Test module failed to load");
     let home = loaded_module.module_id;
@@ -413,6 +432,170 @@ fn import_alias() {
     );
 }
 
+/// The names of the non-builtin modules whose types came from the type cache
+fn modules_from_type_cache(loaded_module: &LoadedModule) -> Vec<String> {
+    let mut names: Vec<String> = loaded_module
+        .timings
+        .iter()
+        .filter(|(module_id, timing)| !module_id.is_builtin() && timing.types_from_cache)
+        .map(|(module_id, _)| {
+            loaded_module
+                .interns
+                .module_name(*module_id)
+                .as_str()
+                .to_string()
+        })
+        .collect();
+
+    names.sort();
+    names
+}
+
+#[test]
+fn type_cache_reused_between_loads() {
+    let cache_dir = tempfile::tempdir().unwrap();
+    let types_dir = cache_dir.path().join("types");
+    let filename = fixtures_dir()
+        .join("interface_with_deps")
+        .join("ImportAlias.roc");
+
+    let count_entries = || std::fs::read_dir(&types_dir).unwrap().count();
+
+    let mut from_cache = Vec::new();
+    let mut entries_after_first_load = 0;
+
+    for _ in 0..2 {
+        let arena = Bump::new();
+        let loaded_module = load_and_typecheck(
+            &arena,
+            filename.clone(),
+            Default::default(),
+            TARGET_INFO,
+            RocCacheDir::Persistent(cache_dir.path()),
+        )
+        .expect("Test module failed to load");
+
+        if entries_after_first_load == 0 {
+            entries_after_first_load = count_entries();
+        }
+
+        from_cache.push(modules_from_type_cache(&loaded_module));
+
+        expect_types(
+            loaded_module,
+            hashmap! {
+                "unit" => "Dep1.Unit",
+            },
+        );
+    }
+
+    // Dep1 and Dep3.Blah were written on the first load (ImportAlias is the root module, which
+    // never gets cached). Nothing changed since, so the second load must have used exactly those
+    // cache entries.
+    assert_eq!(entries_after_first_load, 2);
+    assert_eq!(count_entries(), entries_after_first_load);
+    assert_eq!(from_cache[0], Vec::<String>::new());
+    assert_eq!(from_cache[1], vec!["Dep1", "Dep3.Blah"]);
+}
+
+#[test]
+fn type_cache_reused_under_different_module_ids() {
+    let cache_dir = tempfile::tempdir().unwrap();
+    let fixture_dir = fixtures_dir().join("interface_with_deps");
+
+    let load = |module_name: &str| {
+        let arena = Bump::new();
+        let loaded_module = load_and_typecheck(
+            &arena,
+            fixture_dir.join(format!("{}.roc", module_name)),
+            Default::default(),
+            TARGET_INFO,
+            RocCacheDir::Persistent(cache_dir.path()),
+        )
+        .expect("Test module failed to load");
+
+        let blah_id = loaded_module
+            .interns
+            .module_ids
+            .get_id(&"Dep3.Blah".into())
+            .unwrap();
+
+        (loaded_module, blah_id)
+    };
+
+    let (import_alias, blah_id_before) = load("ImportAlias");
+
+    assert_eq!(modules_from_type_cache(&import_alias), Vec::<String>::new());
+
+    // Primary imports Dep2 before Dep3.Blah, so Dep3.Blah gets another module id this time. The
+    // cached types of Dep1 and Dep3.Blah must still be used, with their symbols moved over.
+    let (primary, blah_id_after) = load("Primary");
+
+    assert_ne!(blah_id_before, blah_id_after);
+    assert_eq!(modules_from_type_cache(&primary), vec!["Dep1", "Dep3.Blah"]);
+
+    expect_types(
+        primary,
+        hashmap! {
+            "blah2" => "Float *",
+            "blah3" => "Str",
+            "str" => "Str",
+            "alwaysThree" => "* -> Float *",
+            "identity" => "a -> a",
+            "z" => "Float *",
+            "w" => "Dep1.Identity {}",
+            "succeed" => "a -> Dep1.Identity a",
+            "yay" => "Res.Res {} err",
+            "withDefault" => "Res.Res a err, a -> a",
+        },
+    );
+}
+
+#[test]
+fn type_cache_invalidated_by_changed_dependency() {
+    let cache_dir = tempfile::tempdir().unwrap();
+    let src_dir = tempfile::tempdir().unwrap();
+    let fixture_dir = fixtures_dir().join("interface_with_deps");
+
+    std::fs::create_dir(src_dir.path().join("Dep3")).unwrap();
+
+    for file in ["ImportAlias.roc", "Dep1.roc", "Dep3/Blah.roc"] {
+        std::fs::copy(fixture_dir.join(file), src_dir.path().join(file)).unwrap();
+    }
+
+    let load = || {
+        let arena = Bump::new();
+        let loaded_module = load_and_typecheck(
+            &arena,
+            src_dir.path().join("ImportAlias.roc"),
+            Default::default(),
+            TARGET_INFO,
+            RocCacheDir::Persistent(cache_dir.path()),
+        )
+        .expect("Test module failed to load");
+
+        modules_from_type_cache(&loaded_module)
+    };
+
+    assert_eq!(load(), Vec::<String>::new());
+    assert_eq!(load(), vec!["Dep1", "Dep3.Blah"]);
+
+    // Dep1 imports Dep3.Blah, so changing Dep3.Blah must invalidate both of them
+    let blah = src_dir.path().join("Dep3").join("Blah.roc");
+    let src = std::fs::read_to_string(&blah).unwrap();
+    std::fs::write(&blah, format!("{}\nunused = 42\n", src)).unwrap();
+
+    assert_eq!(load(), Vec::<String>::new());
+    assert_eq!(load(), vec!["Dep1", "Dep3.Blah"]);
+
+    // Dep1 isn't imported by Dep3.Blah, so changing it must leave Dep3.Blah's entry alone
+    let dep1 = src_dir.path().join("Dep1.roc");
+    let src = std::fs::read_to_string(&dep1).unwrap();
+    std::fs::write(&dep1, format!("{}\nunused = 42\n", src)).unwrap();
+
+    assert_eq!(load(), vec!["Dep3.Blah"]);
+}
+
 #[test]
 fn test_load_and_typecheck() {
     let subs_by_module = Default::default();
@@ -618,7 +801,7 @@ fn parse_problem() {
             report,
             indoc!(
                 "
-                    ── UNFINISHED LIST ──────────────────────────────────── tmp/parse_problem/Main ─
+                    ── UNFINISHED LIST [R0127] ──────────────────────────── tmp/parse_problem/Main ─
 
                     I am partway through started parsing a list, but I got stuck here:
 
@@ -825,7 +1008,7 @@ fn opaque_wrapped_unwrapped_outside_defining_module() {
         err,
         indoc!(
             r#"
-                ── OPAQUE TYPE DECLARED OUTSIDE SCOPE ─ ...rapped_outside_defining_module/Main ─
+                ── OPAQUE TYPE DECLARED OUTSIDE SCOPE [R0252] ─ ...utside_defining_module/Main ─
 
                 The unwrapped opaque type Age referenced here:
 
@@ -839,7 +1022,7 @@ fn opaque_wrapped_unwrapped_outside_defining_module() {
 
                 Note: Opaque types can only be wrapped and unwrapped in the module they are defined in!
 
-                ── OPAQUE TYPE DECLARED OUTSIDE SCOPE ─ ...rapped_outside_defining_module/Main ─
+                ── OPAQUE TYPE DECLARED OUTSIDE SCOPE [R0252] ─ ...utside_defining_module/Main ─
 
                 The unwrapped opaque type Age referenced here:
 
@@ -853,7 +1036,7 @@ fn opaque_wrapped_unwrapped_outside_defining_module() {
 
                 Note: Opaque types can only be wrapped and unwrapped in the module they are defined in!
 
-                ── UNUSED IMPORT ─── tmp/opaque_wrapped_unwrapped_outside_defining_module/Main ─
+                ── UNUSED IMPORT [R0202] ─ ...e_wrapped_unwrapped_outside_defining_module/Main ─
 
                 Nothing from Age is used in this module.
 
@@ -908,7 +1091,7 @@ fn issue_2863_module_type_does_not_exist() {
                 report,
                 indoc!(
                     "
-                        ── UNRECOGNIZED NAME ────────── tmp/issue_2863_module_type_does_not_exist/Main ─
+                        ── UNRECOGNIZED NAME [R0250] ── tmp/issue_2863_module_type_does_not_exist/Main ─
 
                         Nothing is named `DoesNotExist` in this scope.
 
@@ -984,7 +1167,7 @@ fn module_doesnt_match_file_path() {
         err,
         indoc!(
             r#"
-            ── WEIRD MODULE NAME ─────────────────── tmp/module_doesnt_match_file_path/Age ─
+            ── WEIRD MODULE NAME [R0113] ─────────── tmp/module_doesnt_match_file_path/Age ─
 
             This module name does not correspond with the file path it is defined
             in:
@@ -354,7 +354,7 @@ impl ModuleId {
         ModuleId(unsafe { NonZeroU32::new_unchecked(id as u32) })
     }
 
-    const fn to_zero_indexed(self) -> usize {
+    pub const fn to_zero_indexed(self) -> usize {
         (self.0.get() - 1) as usize
     }
 
@@ -328,6 +328,7 @@ impl<'a> ParamMap<'a> {
                     parameters: xs,
                     remainder: v,
                     body: b,
+                    region: _,
                 } => {
                     self.join_points
                         .insert(*j, Self::init_borrow_params(arena, interner, xs));
@@ -335,7 +336,7 @@ impl<'a> ParamMap<'a> {
                     stack.push(v);
                     stack.push(b);
                 }
-                Let(_, _, _, cont) => {
+                Let(_, _, _, cont, _) => {
                     stack.push(cont);
                 }
 
@@ -353,7 +354,7 @@ impl<'a> ParamMap<'a> {
                 }
                 Refcounting(_, _) => unreachable!("these have not been introduced yet"),
 
-                Ret(_) | Jump(_, _) | Crash(..) => {
+                Ret(..) | Jump(_, _) | Crash(..) => {
                     // these are terminal, do nothing
                 }
             }
@@ -772,7 +773,7 @@ impl<'a> BorrowInfState<'a> {
                 arguments: ys,
                 ..
             }),
-            Stmt::Ret(z),
+            Stmt::Ret(z, _),
         ) = (v, b)
         {
             let top_level = ProcLayout::new(self.arena, arg_layouts, g.niche(), *ret_layout);
@@ -813,6 +814,7 @@ impl<'a> BorrowInfState<'a> {
                 parameters: ys,
                 remainder: v,
                 body: b,
+                region: _,
             } => {
                 let old = self.param_set.clone();
                 self.update_param_set(ys);
@@ -823,12 +825,12 @@ impl<'a> BorrowInfState<'a> {
                 self.collect_stmt(interner, param_map, b);
             }
 
-            Let(x, v, _, mut b) => {
+            Let(x, v, _, mut b, _) => {
                 let mut stack = Vec::new_in(self.arena);
 
                 stack.push((*x, v));
 
-                while let Stmt::Let(symbol, expr, _, tail) = b {
+                while let Stmt::Let(symbol, expr, _, tail, _) = b {
                     b = tail;
                     stack.push((*symbol, expr));
                 }
@@ -886,7 +888,7 @@ impl<'a> BorrowInfState<'a> {
                 self.own_var(*msg);
             }
 
-            Ret(_) => {
+            Ret(_, _) => {
                 // these are terminal, do nothing
             }
         }
@@ -1074,7 +1076,7 @@ fn call_info_stmt<'a>(arena: &'a Bump, stmt: &Stmt<'a>, info: &mut CallInfo<'a>)
                 stack.push(v);
                 stack.push(b);
             }
-            Let(_, expr, _, cont) => {
+            Let(_, expr, _, cont, _) => {
                 if let Expr::Call(call) = expr {
                     call_info_call(call, info);
                 }
@@ -1095,7 +1097,7 @@ fn call_info_stmt<'a>(arena: &'a Bump, stmt: &Stmt<'a>, info: &mut CallInfo<'a>)
 
             Refcounting(_, _) => unreachable!("these have not been introduced yet"),
 
-            Ret(_) | Jump(_, _) | Crash(..) => {
+            Ret(..) | Jump(_, _) | Crash(..) => {
                 // these are terminal, do nothing
             }
         }
@@ -1,6 +1,7 @@
 use bumpalo::collections::vec::Vec;
 use roc_module::low_level::LowLevel;
 use roc_module::symbol::{IdentIds, Symbol};
+use roc_region::all::Region;
 
 use crate::borrow::Ownership;
 use crate::ir::{
@@ -61,7 +62,9 @@ pub fn eq_generic<'a>(
             Expr::Literal(Literal::Int(0i128.to_ne_bytes())),
             LAYOUT_BOOL,
             root.arena.alloc(main_body),
+            Region::zero(),
         )),
+        Region::zero(),
     )
 }
 
@@ -111,13 +114,17 @@ fn if_pointers_equal_return_true<'a>(
                     branches: root.arena.alloc([(
                         1,
                         BranchInfo::None,
-                        Stmt::Ret(Symbol::BOOL_TRUE),
+                        Stmt::Ret(Symbol::BOOL_TRUE, Region::zero()),
                     )]),
                     default_branch: (BranchInfo::None, following),
                     ret_layout: LAYOUT_BOOL,
+                    region: Region::zero(),
                 }),
+                Region::zero(),
             )),
+            Region::zero(),
         )),
+        Region::zero(),
     )
 }
 
@@ -129,11 +136,14 @@ fn if_false_return_false<'a>(
     Stmt::Switch {
         cond_symbol: symbol,
         cond_layout: LAYOUT_BOOL,
-        branches: root
-            .arena
-            .alloc([(0, BranchInfo::None, Stmt::Ret(Symbol::BOOL_FALSE))]),
+        branches: root.arena.alloc([(
+            0,
+            BranchInfo::None,
+            Stmt::Ret(Symbol::BOOL_FALSE, Region::zero()),
+        )]),
         default_branch: (BranchInfo::None, following),
         ret_layout: LAYOUT_BOOL,
+        region: Region::zero(),
     }
 }
 
@@ -144,7 +154,7 @@ fn eq_struct<'a>(
     layout_interner: &mut STLayoutInterner<'a>,
     field_layouts: &'a [InLayout<'a>],
 ) -> Stmt<'a> {
-    let mut else_stmt = Stmt::Ret(Symbol::BOOL_TRUE);
+    let mut else_stmt = Stmt::Ret(Symbol::BOOL_TRUE, Region::zero());
     for (i, layout) in field_layouts.iter().enumerate().rev() {
         let field1_sym = root.create_symbol(ident_ids, &format!("field_1_{}", i));
         let field1_expr = Expr::StructAtIndex {
@@ -152,7 +162,7 @@ fn eq_struct<'a>(
             field_layouts,
             structure: ARG_1,
         };
-        let field1_stmt = |next| Stmt::Let(field1_sym, field1_expr, *layout, next);
+        let field1_stmt = |next| Stmt::Let(field1_sym, field1_expr, *layout, next, Region::zero());
 
         let field2_sym = root.create_symbol(ident_ids, &format!("field_2_{}", i));
         let field2_expr = Expr::StructAtIndex {
@@ -160,7 +170,7 @@ fn eq_struct<'a>(
             field_layouts,
             structure: ARG_2,
         };
-        let field2_stmt = |next| Stmt::Let(field2_sym, field2_expr, *layout, next);
+        let field2_stmt = |next| Stmt::Let(field2_sym, field2_expr, *layout, next, Region::zero());
 
         let eq_call_expr = root
             .call_specialized_op(
@@ -174,7 +184,8 @@ fn eq_struct<'a>(
 
         let eq_call_name = format!("eq_call_{}", i);
         let eq_call_sym = root.create_symbol(ident_ids, &eq_call_name);
-        let eq_call_stmt = |next| Stmt::Let(eq_call_sym, eq_call_expr, LAYOUT_BOOL, next);
+        let eq_call_stmt =
+            |next| Stmt::Let(eq_call_sym, eq_call_expr, LAYOUT_BOOL, next, Region::zero());
 
         else_stmt = field1_stmt(root.arena.alloc(
             //
@@ -309,6 +320,7 @@ fn eq_tag_union_help<'a>(
             },
             tag_id_layout,
             next,
+            Region::zero(),
         )
     };
 
@@ -322,6 +334,7 @@ fn eq_tag_union_help<'a>(
             },
             tag_id_layout,
             next,
+            Region::zero(),
         )
     };
 
@@ -333,11 +346,14 @@ fn eq_tag_union_help<'a>(
         },
         arguments: root.arena.alloc([tag_id_a, tag_id_b]),
     });
-    let tag_ids_eq_stmt = |next| Stmt::Let(tag_ids_eq, tag_ids_expr, LAYOUT_BOOL, next);
+    let tag_ids_eq_stmt =
+        |next| Stmt::Let(tag_ids_eq, tag_ids_expr, LAYOUT_BOOL, next, Region::zero());
 
-    let if_equal_ids_branches =
-        root.arena
-            .alloc([(0, BranchInfo::None, Stmt::Ret(Symbol::BOOL_FALSE))]);
+    let if_equal_ids_branches = root.arena.alloc([(
+        0,
+        BranchInfo::None,
+        Stmt::Ret(Symbol::BOOL_FALSE, Region::zero()),
+    )]);
 
     //
     // Switch statement by tag ID
@@ -347,13 +363,15 @@ fn eq_tag_union_help<'a>(
 
     // If there's a null tag, check it first. We might not need to load any data from memory.
     match nullable_id {
-        NullableId::Wrapped(id) => {
-            tag_branches.push((id as u64, BranchInfo::None, Stmt::Ret(Symbol::BOOL_TRUE)))
-        }
+        NullableId::Wrapped(id) => tag_branches.push((
+            id as u64,
+            BranchInfo::None,
+            Stmt::Ret(Symbol::BOOL_TRUE, Region::zero()),
+        )),
         NullableId::Unwrapped(id) => tag_branches.push((
             id as TagIdIntType as u64,
             BranchInfo::None,
-            Stmt::Ret(Symbol::BOOL_TRUE),
+            Stmt::Ret(Symbol::BOOL_TRUE, Region::zero()),
         )),
         _ => (),
     }
@@ -408,6 +426,7 @@ fn eq_tag_union_help<'a>(
             )),
         ),
         ret_layout: LAYOUT_BOOL,
+        region: Region::zero(),
     };
 
     let if_equal_ids_stmt = Stmt::Switch {
@@ -416,6 +435,7 @@ fn eq_tag_union_help<'a>(
         branches: if_equal_ids_branches,
         default_branch: (BranchInfo::None, root.arena.alloc(tag_switch_stmt)),
         ret_layout: LAYOUT_BOOL,
+        region: Region::zero(),
     };
 
     //
@@ -452,6 +472,7 @@ fn eq_tag_union_help<'a>(
             parameters: root.arena.alloc_slice_fill_iter(loop_params_iter),
             body: root.arena.alloc(compare_ptr_or_value),
             remainder: root.arena.alloc(loop_start),
+            region: Region::zero(),
         }
     }
 }
@@ -477,7 +498,10 @@ fn eq_tag_fields<'a>(
     let (tailrec_index, innermost_stmt) = match rec_ptr_index {
         None => {
             // This tag has no RecursivePointers. Set tailrec_index out of range.
-            (field_layouts.len(), Stmt::Ret(Symbol::BOOL_TRUE))
+            (
+                field_layouts.len(),
+                Stmt::Ret(Symbol::BOOL_TRUE, Region::zero()),
+            )
         }
 
         Some(i) => {
@@ -514,8 +538,10 @@ fn eq_tag_fields<'a>(
                             //
                             Stmt::Jump(tailrec_loop, root.arena.alloc([field1_sym, field2_sym])),
                         ),
+                        Region::zero(),
                     ),
                 ),
+                Region::zero(),
             );
 
             (i, inner)
@@ -585,10 +611,13 @@ fn eq_tag_fields<'a>(
                                     ),
                                 ),
                             ),
+                            Region::zero(),
                         ),
                     ),
+                    Region::zero(),
                 ),
             ),
+            Region::zero(),
         )
     }
     stmt
@@ -633,11 +662,14 @@ fn eq_boxed<'a>(
                         result,
                         eq_call_expr,
                         LAYOUT_BOOL,
-                        root.arena.alloc(Stmt::Ret(result)),
+                        root.arena.alloc(Stmt::Ret(result, Region::zero())),
+                        Region::zero(),
                     ),
                 ),
+                Region::zero(),
             ),
         ),
+        Region::zero(),
     )
 }
 
@@ -687,8 +719,24 @@ fn eq_list<'a>(
         field_layouts: root.arena.alloc([box_layout, layout_isize]),
         structure: ARG_2,
     };
-    let elements_1_stmt = |next| Stmt::Let(elements_1, elements_1_expr, box_layout, next);
-    let elements_2_stmt = |next| Stmt::Let(elements_2, elements_2_expr, box_layout, next);
+    let elements_1_stmt = |next| {
+        Stmt::Let(
+            elements_1,
+            elements_1_expr,
+            box_layout,
+            next,
+            Region::zero(),
+        )
+    };
+    let elements_2_stmt = |next| {
+        Stmt::Let(
+            elements_2,
+            elements_2_expr,
+            box_layout,
+            next,
+            Region::zero(),
+        )
+    };
 
     // Cast to integers
     let start_1 = root.create_symbol(ident_ids, "start_1");
@@ -710,7 +758,7 @@ fn eq_list<'a>(
             .stack_size(layout_interner, root.target_info) as i128)
             .to_ne_bytes(),
     ));
-    let size_stmt = |next| Stmt::Let(size, size_expr, layout_isize, next);
+    let size_stmt = |next| Stmt::Let(size, size_expr, layout_isize, next, Region::zero());
 
     // let list_size = len_1 * size
     let list_size = root.create_symbol(ident_ids, "list_size");
@@ -775,8 +823,8 @@ fn eq_list<'a>(
         tag_id: 0,
         index: 0,
     };
-    let elem1_stmt = |next| Stmt::Let(elem1, elem1_expr, elem_layout, next);
-    let elem2_stmt = |next| Stmt::Let(elem2, elem2_expr, elem_layout, next);
+    let elem1_stmt = |next| Stmt::Let(elem1, elem1_expr, elem_layout, next, Region::zero());
+    let elem2_stmt = |next| Stmt::Let(elem2, elem2_expr, elem_layout, next, Region::zero());
 
     // Compare the two current elements
     let eq_elems = root.create_symbol(ident_ids, "eq_elems");
@@ -785,7 +833,8 @@ fn eq_list<'a>(
         .call_specialized_op(ident_ids, ctx, layout_interner, elem_layout, eq_elems_args)
         .unwrap();
 
-    let eq_elems_stmt = |next| Stmt::Let(eq_elems, eq_elems_expr, LAYOUT_BOOL, next);
+    let eq_elems_stmt =
+        |next| Stmt::Let(eq_elems, eq_elems_expr, LAYOUT_BOOL, next, Region::zero());
 
     // If current elements are equal, loop back again
     let next_1 = root.create_symbol(ident_ids, "next_1");
@@ -825,9 +874,11 @@ fn eq_list<'a>(
         cond_symbol: is_end,
         cond_layout: LAYOUT_BOOL,
         ret_layout: LAYOUT_BOOL,
-        branches: root
-            .arena
-            .alloc([(1, BranchInfo::None, Stmt::Ret(Symbol::BOOL_TRUE))]),
+        branches: root.arena.alloc([(
+            1,
+            BranchInfo::None,
+            Stmt::Ret(Symbol::BOOL_TRUE, Region::zero()),
+        )]),
         default_branch: (
             BranchInfo::None,
             root.arena.alloc(
@@ -850,6 +901,7 @@ fn eq_list<'a>(
                 )),
             ),
         ),
+        region: Region::zero(),
     };
 
     let joinpoint_loop = Stmt::Join {
@@ -865,6 +917,7 @@ fn eq_list<'a>(
         remainder: root
             .arena
             .alloc(Stmt::Jump(elems_loop, root.arena.alloc([start_1, start_2]))),
+        region: Region::zero(),
     };
 
     let if_different_lengths = if_false_return_false(
@@ -2,6 +2,7 @@ use bumpalo::collections::vec::Vec;
 use bumpalo::Bump;
 use roc_module::low_level::LowLevel;
 use roc_module::symbol::{IdentIds, ModuleId, Symbol};
+use roc_region::all::Region;
 use roc_target::TargetInfo;
 
 use crate::ir::{
@@ -417,6 +418,7 @@ impl<'a> CodeGenHelp<'a> {
             ret_layout,
             is_self_recursive: SelfRecursive::NotSelfRecursive,
             host_exposed_layouts: HostExposedLayouts::NotHostExposed,
+            region: Region::zero(),
         });
 
         proc_symbol
@@ -693,10 +695,14 @@ impl<'a> CallerProc<'a> {
                         unit_symbol,
                         Expr::Struct(&[]),
                         Layout::UNIT,
-                        arena.alloc(Stmt::Ret(unit_symbol)),
+                        arena.alloc(Stmt::Ret(unit_symbol, Region::zero())),
+                        Region::zero(),
                     )),
+                    Region::zero(),
                 )),
+                Region::zero(),
             )),
+            Region::zero(),
         );
 
         if let Some(capture_layout) = capture_layout {
@@ -705,6 +711,7 @@ impl<'a> CallerProc<'a> {
                 unbox_capture,
                 capture_layout,
                 arena.alloc(body),
+                Region::zero(),
             );
         }
 
@@ -724,6 +731,7 @@ impl<'a> CallerProc<'a> {
             ret_layout: Layout::UNIT,
             is_self_recursive: SelfRecursive::NotSelfRecursive,
             host_exposed_layouts: HostExposedLayouts::NotHostExposed,
+            region: Region::zero(),
         };
 
         if false {
@@ -769,6 +777,7 @@ fn let_lowlevel<'a>(
         }),
         result_layout,
         next,
+        Region::zero(),
     )
 }
 
@@ -4,6 +4,7 @@ use bumpalo::collections::vec::Vec;
 use bumpalo::collections::CollectIn;
 use roc_module::low_level::{LowLevel, LowLevel::*};
 use roc_module::symbol::{IdentIds, Symbol};
+use roc_region::all::Region;
 use roc_target::PtrWidth;
 
 use crate::borrow::Ownership;
@@ -39,7 +40,8 @@ pub fn refcount_stmt<'a>(
             // Define a constant for the amount to increment
             let amount_sym = root.create_symbol(ident_ids, "amount");
             let amount_expr = Expr::Literal(Literal::Int((*amount as i128).to_ne_bytes()));
-            let amount_stmt = |next| Stmt::Let(amount_sym, amount_expr, layout_isize, next);
+            let amount_stmt =
+                |next| Stmt::Let(amount_sym, amount_expr, layout_isize, next, Region::zero());
 
             // Call helper proc, passing the Roc structure and constant amount
             let call_result_empty = root.create_symbol(ident_ids, "call_result_empty");
@@ -53,7 +55,13 @@ pub fn refcount_stmt<'a>(
                 )
                 .unwrap();
 
-            let call_stmt = Stmt::Let(call_result_empty, call_expr, LAYOUT_UNIT, following);
+            let call_stmt = Stmt::Let(
+                call_result_empty,
+                call_expr,
+                LAYOUT_UNIT,
+                following,
+                Region::zero(),
+            );
             arena.alloc(amount_stmt(arena.alloc(call_stmt)))
         }
 
@@ -69,7 +77,13 @@ pub fn refcount_stmt<'a>(
                     arena.alloc([*structure]),
                 )
                 .unwrap();
-            let call_stmt = Stmt::Let(call_result_empty, call_expr, LAYOUT_UNIT, following);
+            let call_stmt = Stmt::Let(
+                call_result_empty,
+                call_expr,
+                LAYOUT_UNIT,
+                following,
+                Region::zero(),
+            );
             arena.alloc(call_stmt)
         }
 
@@ -110,6 +124,7 @@ pub fn refcount_stmt<'a>(
                             parameters: &[],
                             body: following,
                             remainder: arena.alloc(rc_stmt),
+                            region: Region::zero(),
                         };
                         arena.alloc(join)
                     }
@@ -254,6 +269,7 @@ pub fn refcount_reset_proc_body<'a>(
                 },
                 tag_id_layout,
                 next,
+                Region::zero(),
             )
         };
 
@@ -268,7 +284,7 @@ pub fn refcount_reset_proc_body<'a>(
             structure,
             tag_id_sym,
             tag_id_layout,
-            Stmt::Ret(addr),
+            Stmt::Ret(addr, Region::zero()),
         );
 
         tag_id_stmt(root.arena.alloc(
@@ -289,12 +305,20 @@ pub fn refcount_reset_proc_body<'a>(
                 root.arena.alloc([structure]),
             )
             .unwrap();
-        let decrement_stmt = |next| Stmt::Let(decrement_unit, decrement_expr, LAYOUT_UNIT, next);
+        let decrement_stmt = |next| {
+            Stmt::Let(
+                decrement_unit,
+                decrement_expr,
+                LAYOUT_UNIT,
+                next,
+                Region::zero(),
+            )
+        };
 
         // Zero
         let zero = root.create_symbol(ident_ids, "zero");
         let zero_expr = Expr::Literal(Literal::Int(0i128.to_ne_bytes()));
-        let zero_stmt = |next| Stmt::Let(zero, zero_expr, root.layout_isize, next);
+        let zero_stmt = |next| Stmt::Let(zero, zero_expr, root.layout_isize, next, Region::zero());
 
         // Null pointer with union layout
         let null = root.create_symbol(ident_ids, "null");
@@ -307,7 +331,7 @@ pub fn refcount_reset_proc_body<'a>(
                 //
                 null_stmt(root.arena.alloc(
                     //
-                    Stmt::Ret(null),
+                    Stmt::Ret(null, Region::zero()),
                 )),
             )),
         ))
@@ -319,6 +343,7 @@ pub fn refcount_reset_proc_body<'a>(
         branches: root.arena.alloc([(1, BranchInfo::None, then_stmt)]),
         default_branch: (BranchInfo::None, root.arena.alloc(else_stmt)),
         ret_layout: layout,
+        region: Region::zero(),
     };
 
     // Uniqueness test
@@ -345,6 +370,7 @@ pub fn refcount_reset_proc_body<'a>(
         refcount_1_expr,
         root.layout_isize,
         root.arena.alloc(is_unique_stmt),
+        Region::zero(),
     );
 
     // Refcount value
@@ -359,6 +385,7 @@ pub fn refcount_reset_proc_body<'a>(
         rc_expr,
         root.layout_isize,
         root.arena.alloc(refcount_1_stmt),
+        Region::zero(),
     );
 
     // Refcount pointer
@@ -430,10 +457,12 @@ pub fn refcount_resetref_proc_body<'a>(
                     root.layout_isize,
                     root.arena.alloc(
                         //
-                        Stmt::Ret(alloc_addr),
+                        Stmt::Ret(alloc_addr, Region::zero()),
                     ),
+                    Region::zero(),
                 ),
             ),
+            Region::zero(),
         )
     };
 
@@ -449,12 +478,20 @@ pub fn refcount_resetref_proc_body<'a>(
                 root.arena.alloc([structure]),
             )
             .unwrap();
-        let decrement_stmt = |next| Stmt::Let(decrement_unit, decrement_expr, LAYOUT_UNIT, next);
+        let decrement_stmt = |next| {
+            Stmt::Let(
+                decrement_unit,
+                decrement_expr,
+                LAYOUT_UNIT,
+                next,
+                Region::zero(),
+            )
+        };
 
         // Zero
         let zero = root.create_symbol(ident_ids, "zero");
         let zero_expr = Expr::Literal(Literal::Int(0i128.to_ne_bytes()));
-        let zero_stmt = |next| Stmt::Let(zero, zero_expr, root.layout_isize, next);
+        let zero_stmt = |next| Stmt::Let(zero, zero_expr, root.layout_isize, next, Region::zero());
 
         // Null pointer with union layout
         let null = root.create_symbol(ident_ids, "null");
@@ -467,7 +504,7 @@ pub fn refcount_resetref_proc_body<'a>(
                 //
                 null_stmt(root.arena.alloc(
                     //
-                    Stmt::Ret(null),
+                    Stmt::Ret(null, Region::zero()),
                 )),
             )),
         ))
@@ -479,6 +516,7 @@ pub fn refcount_resetref_proc_body<'a>(
         branches: root.arena.alloc([(1, BranchInfo::None, then_stmt)]),
         default_branch: (BranchInfo::None, root.arena.alloc(else_stmt)),
         ret_layout: layout,
+        region: Region::zero(),
     };
 
     // Uniqueness test
@@ -505,6 +543,7 @@ pub fn refcount_resetref_proc_body<'a>(
         refcount_1_expr,
         root.layout_isize,
         root.arena.alloc(is_unique_stmt),
+        Region::zero(),
     );
 
     // Refcount value
@@ -519,6 +558,7 @@ pub fn refcount_resetref_proc_body<'a>(
         rc_expr,
         root.layout_isize,
         root.arena.alloc(refcount_1_stmt),
+        Region::zero(),
     );
 
     // Refcount pointer
@@ -547,8 +587,14 @@ fn rc_return_stmt<'a>(
         Stmt::Jump(jp_decref, &[])
     } else {
         let unit = root.create_symbol(ident_ids, "unit");
-        let ret_stmt = root.arena.alloc(Stmt::Ret(unit));
-        Stmt::Let(unit, Expr::Struct(&[]), LAYOUT_UNIT, ret_stmt)
+        let ret_stmt = root.arena.alloc(Stmt::Ret(unit, Region::zero()));
+        Stmt::Let(
+            unit,
+            Expr::Struct(&[]),
+            LAYOUT_UNIT,
+            ret_stmt,
+            Region::zero(),
+        )
     }
 }
 
@@ -613,14 +659,22 @@ pub fn rc_ptr_from_data_ptr_help<'a>(
         },
         arguments: root.arena.alloc([structure]),
     });
-    let as_int_stmt = |next| Stmt::Let(as_int_sym, as_int_expr, root.layout_isize, next);
+    let as_int_stmt = |next| {
+        Stmt::Let(
+            as_int_sym,
+            as_int_expr,
+            root.layout_isize,
+            next,
+            Region::zero(),
+        )
+    };
 
     // Mask for lower bits (for tag union id)
     let mask_sym = root.create_symbol(ident_ids, "mask");
     let mask_expr = Expr::Literal(Literal::Int(
         (root.target_info.ptr_width() as i128).neg().to_ne_bytes(),
     ));
-    let mask_stmt = |next| Stmt::Let(mask_sym, mask_expr, root.layout_isize, next);
+    let mask_stmt = |next| Stmt::Let(mask_sym, mask_expr, root.layout_isize, next, Region::zero());
 
     let and_expr = Expr::Call(Call {
         call_type: CallType::LowLevel {
@@ -629,14 +683,22 @@ pub fn rc_ptr_from_data_ptr_help<'a>(
         },
         arguments: root.arena.alloc([as_int_sym, mask_sym]),
     });
-    let and_stmt = |next| Stmt::Let(addr_sym, and_expr, root.layout_isize, next);
+    let and_stmt = |next| Stmt::Let(addr_sym, and_expr, root.layout_isize, next, Region::zero());
 
     // Pointer size constant
     let ptr_size_sym = root.create_symbol(ident_ids, "ptr_size");
     let ptr_size_expr = Expr::Literal(Literal::Int(
         (root.target_info.ptr_width() as i128).to_ne_bytes(),
     ));
-    let ptr_size_stmt = |next| Stmt::Let(ptr_size_sym, ptr_size_expr, root.layout_isize, next);
+    let ptr_size_stmt = |next| {
+        Stmt::Let(
+            ptr_size_sym,
+            ptr_size_expr,
+            root.layout_isize,
+            next,
+            Region::zero(),
+        )
+    };
 
     // Refcount address
     let rc_addr_sym = root.create_symbol(ident_ids, "rc_addr");
@@ -647,7 +709,15 @@ pub fn rc_ptr_from_data_ptr_help<'a>(
         },
         arguments: root.arena.alloc([addr_sym, ptr_size_sym]),
     });
-    let sub_stmt = |next| Stmt::Let(rc_addr_sym, sub_expr, Layout::usize(root.target_info), next);
+    let sub_stmt = |next| {
+        Stmt::Let(
+            rc_addr_sym,
+            sub_expr,
+            Layout::usize(root.target_info),
+            next,
+            Region::zero(),
+        )
+    };
 
     // Typecast the refcount address from integer to pointer
     let cast_expr = Expr::Call(Call {
@@ -657,7 +727,7 @@ pub fn rc_ptr_from_data_ptr_help<'a>(
         },
         arguments: root.arena.alloc([rc_addr_sym]),
     });
-    let cast_stmt = |next| Stmt::Let(rc_ptr_sym, cast_expr, recursion_ptr, next);
+    let cast_stmt = |next| Stmt::Let(rc_ptr_sym, cast_expr, recursion_ptr, next, Region::zero());
 
     if mask_lower_bits {
         as_int_stmt(root.arena.alloc(
@@ -715,14 +785,28 @@ fn modify_refcount<'a>(
                 },
                 arguments: root.arena.alloc([rc_ptr, Symbol::ARG_2]),
             });
-            Stmt::Let(zig_call_result, zig_call_expr, LAYOUT_UNIT, following)
+            Stmt::Let(
+                zig_call_result,
+                zig_call_expr,
+                LAYOUT_UNIT,
+                following,
+                Region::zero(),
+            )
         }
 
         HelperOp::Dec | HelperOp::DecRef(_) => {
             debug_assert!(alignment >= root.target_info.ptr_width() as u32);
             let alignment_sym = root.create_symbol(ident_ids, "alignment");
             let alignment_expr = Expr::Literal(Literal::Int((alignment as i128).to_ne_bytes()));
-            let alignment_stmt = |next| Stmt::Let(alignment_sym, alignment_expr, LAYOUT_U32, next);
+            let alignment_stmt = |next| {
+                Stmt::Let(
+                    alignment_sym,
+                    alignment_expr,
+                    LAYOUT_U32,
+                    next,
+                    Region::zero(),
+                )
+            };
 
             let zig_call_expr = Expr::Call(Call {
                 call_type: CallType::LowLevel {
@@ -731,7 +815,13 @@ fn modify_refcount<'a>(
                 },
                 arguments: root.arena.alloc([rc_ptr, alignment_sym]),
             });
-            let zig_call_stmt = Stmt::Let(zig_call_result, zig_call_expr, LAYOUT_UNIT, following);
+            let zig_call_stmt = Stmt::Let(
+                zig_call_result,
+                zig_call_expr,
+                LAYOUT_UNIT,
+                following,
+                Region::zero(),
+            );
 
             alignment_stmt(root.arena.alloc(
                 //
@@ -762,12 +852,20 @@ fn refcount_str<'a>(
         field_layouts,
         structure: string,
     };
-    let last_word_stmt = |next| Stmt::Let(last_word, last_word_expr, layout_isize, next);
+    let last_word_stmt = |next| {
+        Stmt::Let(
+            last_word,
+            last_word_expr,
+            layout_isize,
+            next,
+            Region::zero(),
+        )
+    };
 
     // Zero
     let zero = root.create_symbol(ident_ids, "zero");
     let zero_expr = Expr::Literal(Literal::Int(0i128.to_ne_bytes()));
-    let zero_stmt = |next| Stmt::Let(zero, zero_expr, layout_isize, next);
+    let zero_stmt = |next| Stmt::Let(zero, zero_expr, layout_isize, next, Region::zero());
 
     // is_big_str = (last_word >= 0);
     // Treat last word as isize so that the small string flag is the same as the sign bit
@@ -779,7 +877,15 @@ fn refcount_str<'a>(
         },
         arguments: root.arena.alloc([last_word, zero]),
     });
-    let is_big_str_stmt = |next| Stmt::Let(is_big_str, is_big_str_expr, LAYOUT_BOOL, next);
+    let is_big_str_stmt = |next| {
+        Stmt::Let(
+            is_big_str,
+            is_big_str_expr,
+            LAYOUT_BOOL,
+            next,
+            Region::zero(),
+        )
+    };
 
     // Get the pointer to the string elements
     let elements = root.create_symbol(ident_ids, "elements");
@@ -788,7 +894,8 @@ fn refcount_str<'a>(
         field_layouts,
         structure: string,
     };
-    let elements_stmt = |next| Stmt::Let(elements, elements_expr, layout_isize, next);
+    let elements_stmt =
+        |next| Stmt::Let(elements, elements_expr, layout_isize, next, Region::zero());
 
     // A pointer to the refcount value itself
     let rc_ptr = root.create_symbol(ident_ids, "rc_ptr");
@@ -830,6 +937,7 @@ fn refcount_str<'a>(
             root.arena.alloc(rc_return_stmt(root, ident_ids, ctx)),
         ),
         ret_layout: LAYOUT_UNIT,
+        region: Region::zero(),
     };
 
     // Combine the statements in sequence
@@ -869,7 +977,7 @@ fn refcount_list<'a>(
     // Zero
     let zero = root.create_symbol(ident_ids, "zero");
     let zero_expr = Expr::Literal(Literal::Int(0i128.to_ne_bytes()));
-    let zero_stmt = |next| Stmt::Let(zero, zero_expr, layout_isize, next);
+    let zero_stmt = |next| Stmt::Let(zero, zero_expr, layout_isize, next, Region::zero());
 
     // let is_empty = lowlevel Eq len zero
     let is_empty = root.create_symbol(ident_ids, "is_empty");
@@ -880,7 +988,8 @@ fn refcount_list<'a>(
         },
         arguments: root.arena.alloc([len, zero]),
     });
-    let is_empty_stmt = |next| Stmt::Let(is_empty, is_empty_expr, LAYOUT_BOOL, next);
+    let is_empty_stmt =
+        |next| Stmt::Let(is_empty, is_empty_expr, LAYOUT_BOOL, next, Region::zero());
 
     // get elements pointer
     let elements = root.create_symbol(ident_ids, "elements");
@@ -889,7 +998,7 @@ fn refcount_list<'a>(
         field_layouts: arena.alloc([box_layout, layout_isize, layout_isize]),
         structure,
     };
-    let elements_stmt = |next| Stmt::Let(elements, elements_expr, box_layout, next);
+    let elements_stmt = |next| Stmt::Let(elements, elements_expr, box_layout, next, Region::zero());
 
     //
     // modify refcount of the list and its elements
@@ -960,6 +1069,7 @@ fn refcount_list<'a>(
             .alloc([(1, BranchInfo::None, rc_return_stmt(root, ident_ids, ctx))]),
         default_branch: (BranchInfo::None, non_empty_branch),
         ret_layout: LAYOUT_UNIT,
+        region: Region::zero(),
     };
 
     len_stmt(arena.alloc(
@@ -1003,7 +1113,15 @@ fn refcount_list_elems<'a>(
     let elem_size_expr = Expr::Literal(Literal::Int(
         (layout_interner.stack_size(elem_layout) as i128).to_ne_bytes(),
     ));
-    let elem_size_stmt = |next| Stmt::Let(elem_size, elem_size_expr, layout_isize, next);
+    let elem_size_stmt = |next| {
+        Stmt::Let(
+            elem_size,
+            elem_size_expr,
+            layout_isize,
+            next,
+            Region::zero(),
+        )
+    };
 
     // let list_size = len * size
     let list_size = root.create_symbol(ident_ids, "list_size");
@@ -1046,7 +1164,7 @@ fn refcount_list_elems<'a>(
     // Dereference the box pointer to get the current element
     let elem = root.create_symbol(ident_ids, "elem");
     let elem_expr = Expr::ExprUnbox { symbol: box_ptr };
-    let elem_stmt = |next| Stmt::Let(elem, elem_expr, elem_layout, next);
+    let elem_stmt = |next| Stmt::Let(elem, elem_expr, elem_layout, next, Region::zero());
 
     //
     // Modify element refcount
@@ -1057,7 +1175,15 @@ fn refcount_list_elems<'a>(
     let mod_elem_expr = root
         .call_specialized_op(ident_ids, ctx, layout_interner, elem_layout, mod_elem_args)
         .unwrap();
-    let mod_elem_stmt = |next| Stmt::Let(mod_elem_unit, mod_elem_expr, LAYOUT_UNIT, next);
+    let mod_elem_stmt = |next| {
+        Stmt::Let(
+            mod_elem_unit,
+            mod_elem_expr,
+            LAYOUT_UNIT,
+            next,
+            Region::zero(),
+        )
+    };
 
     //
     // Next loop iteration
@@ -1102,6 +1228,7 @@ fn refcount_list_elems<'a>(
                 )),
             ))),
         ),
+        region: Region::zero(),
     };
 
     let joinpoint_loop = Stmt::Join {
@@ -1117,6 +1244,7 @@ fn refcount_list_elems<'a>(
         remainder: root
             .arena
             .alloc(Stmt::Jump(elems_loop, arena.alloc([start]))),
+        region: Region::zero(),
     };
 
     start_stmt(arena.alloc(
@@ -1152,14 +1280,22 @@ fn refcount_struct<'a>(
                 field_layouts,
                 structure,
             };
-            let field_val_stmt = |next| Stmt::Let(field_val, field_val_expr, *field_layout, next);
+            let field_val_stmt = |next| {
+                Stmt::Let(
+                    field_val,
+                    field_val_expr,
+                    *field_layout,
+                    next,
+                    Region::zero(),
+                )
+            };
 
             let mod_unit = root.create_symbol(ident_ids, &format!("mod_field_{}", i));
             let mod_args = refcount_args(root, ctx, field_val);
             let mod_expr = root
                 .call_specialized_op(ident_ids, ctx, layout_interner, *field_layout, mod_args)
                 .unwrap();
-            let mod_stmt = |next| Stmt::Let(mod_unit, mod_expr, LAYOUT_UNIT, next);
+            let mod_stmt = |next| Stmt::Let(mod_unit, mod_expr, LAYOUT_UNIT, next, Region::zero());
 
             stmt = field_val_stmt(root.arena.alloc(
                 //
@@ -1347,6 +1483,7 @@ fn refcount_union_nonrec<'a>(
             },
             tag_id_layout,
             next,
+            Region::zero(),
         )
     };
 
@@ -1438,6 +1575,7 @@ fn refcount_union_contents<'a>(
         branches: tag_branches.into_bump_slice(),
         default_branch: (BranchInfo::None, root.arena.alloc(default_stmt)),
         ret_layout: LAYOUT_UNIT,
+        region: Region::zero(),
     };
 
     Stmt::Join {
@@ -1445,6 +1583,7 @@ fn refcount_union_contents<'a>(
         parameters: &[],
         body: root.arena.alloc(next_stmt),
         remainder: root.arena.alloc(tag_id_switch),
+        region: Region::zero(),
     }
 }
 
@@ -1471,6 +1610,7 @@ fn refcount_union_rec<'a>(
             },
             tag_id_layout,
             next,
+            Region::zero(),
         )
     };
 
@@ -1558,6 +1698,7 @@ fn refcount_union_tailrec<'a>(
             },
             tag_id_layout,
             next,
+            Region::zero(),
         )
     };
 
@@ -1577,6 +1718,7 @@ fn refcount_union_tailrec<'a>(
             branches: root.arena.alloc([(0, BranchInfo::None, exit_stmt)]),
             default_branch: (BranchInfo::None, root.arena.alloc(jump_to_loop)),
             ret_layout: LAYOUT_UNIT,
+            region: Region::zero(),
         };
         let loop_or_exit_based_on_next_addr = {
             let_lowlevel(
@@ -1649,7 +1791,13 @@ fn refcount_union_tailrec<'a>(
                             };
                             let jump_params = root.arena.alloc([field_val]);
                             let jump = root.arena.alloc(Stmt::Jump(jp_modify_union, jump_params));
-                            tail_stmt = Some(Stmt::Let(field_val, field_val_expr, *field, jump));
+                            tail_stmt = Some(Stmt::Let(
+                                field_val,
+                                field_val_expr,
+                                *field,
+                                jump,
+                                Region::zero(),
+                            ));
                         }
                     }
 
@@ -1657,7 +1805,8 @@ fn refcount_union_tailrec<'a>(
                 } else {
                     let zero = root.create_symbol(ident_ids, "zero");
                     let zero_expr = Expr::Literal(Literal::Int(0i128.to_ne_bytes()));
-                    let zero_stmt = |next| Stmt::Let(zero, zero_expr, root.layout_isize, next);
+                    let zero_stmt =
+                        |next| Stmt::Let(zero, zero_expr, root.layout_isize, next, Region::zero());
 
                     let null = root.create_symbol(ident_ids, "null");
                     let null_stmt =
@@ -1706,6 +1855,7 @@ fn refcount_union_tailrec<'a>(
             branches: tag_branches.into_bump_slice(),
             default_branch: (BranchInfo::None, root.arena.alloc(default_stmt)),
             ret_layout: LAYOUT_UNIT,
+            region: Region::zero(),
         };
 
         let jp_param = Param {
@@ -1719,6 +1869,7 @@ fn refcount_union_tailrec<'a>(
             parameters: root.arena.alloc([jp_param]),
             body: root.arena.alloc(rc_structure_stmt),
             remainder: root.arena.alloc(tag_id_switch),
+            region: Region::zero(),
         }
     };
 
@@ -1740,6 +1891,7 @@ fn refcount_union_tailrec<'a>(
         parameters: root.arena.alloc([loop_param]),
         body: root.arena.alloc(loop_body),
         remainder: root.arena.alloc(loop_init),
+        region: Region::zero(),
     }
 }
 
@@ -1765,14 +1917,22 @@ fn refcount_tag_fields<'a>(
                 index: *i as u64,
                 structure,
             };
-            let field_val_stmt = |next| Stmt::Let(field_val, field_val_expr, *field_layout, next);
+            let field_val_stmt = |next| {
+                Stmt::Let(
+                    field_val,
+                    field_val_expr,
+                    *field_layout,
+                    next,
+                    Region::zero(),
+                )
+            };
 
             let mod_unit = root.create_symbol(ident_ids, &format!("mod_field_{}_{}", tag_id, i));
             let mod_args = refcount_args(root, ctx, field_val);
             let mod_expr = root
                 .call_specialized_op(ident_ids, ctx, layout_interner, *field_layout, mod_args)
                 .unwrap();
-            let mod_stmt = |next| Stmt::Let(mod_unit, mod_expr, LAYOUT_UNIT, next);
+            let mod_stmt = |next| Stmt::Let(mod_unit, mod_expr, LAYOUT_UNIT, next, Region::zero());
 
             stmt = field_val_stmt(root.arena.alloc(
                 //
@@ -1851,7 +2011,9 @@ fn refcount_boxed<'a>(
                 mod_inner_expr,
                 LAYOUT_UNIT,
                 arena.alloc(get_rc_and_modify_outer),
+                Region::zero(),
             )),
+            Region::zero(),
         )
     } else {
         get_rc_and_modify_outer
@@ -269,7 +269,7 @@ impl<'a, 'r> Ctx<'a, 'r> {
         self.line += 1;
 
         match body {
-            Stmt::Let(x, e, x_layout, rest) => {
+            Stmt::Let(x, e, x_layout, rest, _) => {
                 if let Some(e_layout) = self.check_expr(e) {
                     if self.not_equiv(e_layout, *x_layout) {
                         self.problem(ProblemKind::SymbolDefMismatch {
@@ -288,6 +288,7 @@ impl<'a, 'r> Ctx<'a, 'r> {
                 branches,
                 default_branch,
                 ret_layout: _,
+                region: _,
             } => {
                 self.check_sym_layout(*cond_symbol, *cond_layout, UseKind::SwitchCond);
                 let layout = self.resolve(*cond_layout);
@@ -311,7 +312,7 @@ impl<'a, 'r> Ctx<'a, 'r> {
                 let (_branch_info, default_branch) = default_branch;
                 self.in_scope(|ctx| ctx.check_stmt(default_branch));
             }
-            &Stmt::Ret(sym) => self.check_sym_layout(sym, self.ret_layout, UseKind::Ret),
+            &Stmt::Ret(sym, _) => self.check_sym_layout(sym, self.ret_layout, UseKind::Ret),
             &Stmt::Refcounting(rc, rest) => {
                 self.check_modify_rc(rc);
                 self.check_stmt(rest);
@@ -344,6 +345,7 @@ impl<'a, 'r> Ctx<'a, 'r> {
                 parameters,
                 body,
                 remainder,
+                region: _,
             } => {
                 if let Some((old_line, _)) = self.joinpoints.insert(id, (self.line, parameters)) {
                     self.problem(ProblemKind::RedefinedJoinPoint { id, old_line })
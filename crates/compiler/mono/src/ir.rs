@@ -66,14 +66,14 @@ pub fn pretty_print_ir_symbols() -> bool {
 
 roc_error_macros::assert_sizeof_wasm!(Literal, 24);
 roc_error_macros::assert_sizeof_wasm!(Expr, 48);
-roc_error_macros::assert_sizeof_wasm!(Stmt, 64);
+roc_error_macros::assert_sizeof_wasm!(Stmt, 72);
 roc_error_macros::assert_sizeof_wasm!(ProcLayout, 20);
 roc_error_macros::assert_sizeof_wasm!(Call, 44);
 roc_error_macros::assert_sizeof_wasm!(CallType, 36);
 
 roc_error_macros::assert_sizeof_non_wasm!(Literal, 3 * 8);
 roc_error_macros::assert_sizeof_non_wasm!(Expr, 9 * 8);
-roc_error_macros::assert_sizeof_non_wasm!(Stmt, 12 * 8);
+roc_error_macros::assert_sizeof_non_wasm!(Stmt, 13 * 8);
 roc_error_macros::assert_sizeof_non_wasm!(ProcLayout, 5 * 8);
 roc_error_macros::assert_sizeof_non_wasm!(Call, 9 * 8);
 roc_error_macros::assert_sizeof_non_wasm!(CallType, 7 * 8);
@@ -85,6 +85,7 @@ fn runtime_error<'a>(env: &mut Env<'a, '_>, msg: &'a str) -> Stmt<'a> {
         Expr::Literal(Literal::Str(msg)),
         Layout::STR,
         env.arena.alloc(Stmt::Crash(sym, CrashTag::Roc)),
+        env.region,
     )
 }
 
@@ -206,6 +207,8 @@ pub struct PartialProc<'a> {
     pub body: roc_can::expr::Expr,
     pub body_var: Variable,
     pub is_self_recursive: bool,
+    /// Where the body is in the source code of its module
+    pub region: Region,
 }
 
 impl<'a> PartialProc<'a> {
@@ -235,6 +238,7 @@ impl<'a> PartialProc<'a> {
                     body: body.value,
                     body_var: ret_var,
                     is_self_recursive,
+                    region: body.region,
                 }
             }
 
@@ -252,6 +256,7 @@ impl<'a> PartialProc<'a> {
                     body: roc_can::expr::Expr::RuntimeError(error.value),
                     body_var: ret_var,
                     is_self_recursive: false,
+                    region: error.region,
                 }
             }
         }
@@ -304,6 +309,9 @@ pub struct Proc<'a> {
     pub ret_layout: InLayout<'a>,
     pub is_self_recursive: SelfRecursive,
     pub host_exposed_layouts: HostExposedLayouts<'a>,
+    /// Where this proc is defined in the source code of its module.
+    /// Procs generated by the compiler have no source, and use `Region::zero()`.
+    pub region: Region,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -1118,6 +1126,7 @@ impl<'a> Procs<'a> {
                                         body: body.value,
                                         body_var: ret_var,
                                         is_self_recursive,
+                                        region: body.region,
                                     };
 
                                     self.partial_procs.insert(name.name(), partial_proc);
@@ -1146,6 +1155,7 @@ impl<'a> Procs<'a> {
                                     body: body.value,
                                     body_var: ret_var,
                                     is_self_recursive,
+                                    region: body.region,
                                 };
 
                                 self.partial_procs.insert(name.name(), partial_proc)
@@ -1380,6 +1390,8 @@ pub struct Env<'a, 'i> {
     pub abilities: AbilitiesView<'i>,
     pub exposed_by_module: &'i ExposedByModule,
     pub derived_module: &'i SharedDerivedModule,
+    /// The region of the source code being specialized, which statements are attributed to.
+    pub region: Region,
 }
 
 impl<'a, 'i> Env<'a, 'i> {
@@ -1403,6 +1415,20 @@ impl<'a, 'i> Env<'a, 'i> {
         id
     }
 
+    /// Attributes the statements generated by `f` to `region`.
+    /// Code without a region of its own, like code added by desugaring, keeps the current one.
+    fn with_region<T>(&mut self, region: Region, f: impl FnOnce(&mut Self) -> T) -> T {
+        if region.is_empty() {
+            return f(self);
+        }
+
+        let outer_region = std::mem::replace(&mut self.region, region);
+        let result = f(self);
+        self.region = outer_region;
+
+        result
+    }
+
     pub fn is_imported_symbol(&self, symbol: Symbol) -> bool {
         let sym_module = symbol.module_id();
         sym_module != self.home
@@ -1502,6 +1528,7 @@ pub fn cond<'a>(
         ret_layout,
         branches,
         default_branch,
+        region: env.region,
     }
 }
 
@@ -1510,9 +1537,11 @@ pub type Stores<'a> = &'a [(Symbol, Layout<'a>, Expr<'a>)];
 /// The specialized type of a lookup. Represented as a type-variable.
 pub type LookupType = Variable;
 
+/// `Let`, `Switch`, `Ret` and `Join` carry the region of the source code they were generated from.
+/// Statements generated by the compiler itself use `Region::zero()`.
 #[derive(Clone, Debug, PartialEq)]
 pub enum Stmt<'a> {
-    Let(Symbol, Expr<'a>, InLayout<'a>, &'a Stmt<'a>),
+    Let(Symbol, Expr<'a>, InLayout<'a>, &'a Stmt<'a>, Region),
     Switch {
         /// This *must* stand for an integer, because Switch potentially compiles to a jump table.
         cond_symbol: Symbol,
@@ -1524,8 +1553,9 @@ pub enum Stmt<'a> {
         default_branch: (BranchInfo<'a>, &'a Stmt<'a>),
         /// Each branch must return a value of this type.
         ret_layout: InLayout<'a>,
+        region: Region,
     },
-    Ret(Symbol),
+    Ret(Symbol, Region),
     Refcounting(ModifyRc, &'a Stmt<'a>),
     Expect {
         condition: Symbol,
@@ -1560,6 +1590,7 @@ pub enum Stmt<'a> {
         body: &'a Stmt<'a>,
         /// what happens after _defining_ the join point
         remainder: &'a Stmt<'a>,
+        region: Region,
     },
     Jump(JoinPointId, &'a [Symbol]),
     Crash(Symbol, CrashTag),
@@ -2107,7 +2138,7 @@ impl<'a> Stmt<'a> {
         use Stmt::*;
 
         match self {
-            Let(symbol, expr, layout, cont) => alloc
+            Let(symbol, expr, layout, cont, _) => alloc
                 .text("let ")
                 .append(symbol_to_doc(alloc, *symbol, pretty))
                 .append(" : ")
@@ -2154,7 +2185,7 @@ impl<'a> Stmt<'a> {
                 .append(alloc.hardline())
                 .append(remainder.to_doc(alloc, interner, pretty)),
 
-            Ret(symbol) => alloc
+            Ret(symbol, _) => alloc
                 .text("ret ")
                 .append(symbol_to_doc(alloc, *symbol, pretty))
                 .append(";"),
@@ -2223,6 +2254,7 @@ impl<'a> Stmt<'a> {
                 parameters,
                 body: continuation,
                 remainder,
+                region: _,
             } => {
                 let it = parameters
                     .iter()
@@ -2297,6 +2329,7 @@ impl<'a> Stmt<'a> {
             branches: &*arena.alloc([then_branch]),
             default_branch: else_branch,
             ret_layout: return_layout,
+            region: Region::zero(),
         }
     }
 }
@@ -2332,12 +2365,22 @@ fn from_can_let<'a>(
         };
     }
 
+    // The rest of the program is attributed to its own region, and the def to the def's.
+    let def_region = def.loc_expr.region;
+    macro_rules! lower_cont {
+        () => {
+            env.with_region(cont.region, |env| {
+                lower_rest!(env, procs, layout_cache, variable, cont.value)
+            })
+        };
+    }
+
     if let roc_can::pattern::Pattern::Identifier(symbol) = &def.loc_pattern.value {
         return match def.loc_expr.value {
             Closure(closure_data) => {
                 register_capturing_closure(env, procs, layout_cache, *symbol, closure_data);
 
-                lower_rest!(variable, cont.value)
+                lower_cont!()
             }
             RecordAccessor(accessor_data) => {
                 let fresh_record_symbol = env.unique_symbol();
@@ -2345,7 +2388,7 @@ fn from_can_let<'a>(
                 debug_assert_eq!(*symbol, closure_data.name);
                 register_noncapturing_closure(env, procs, *symbol, closure_data);
 
-                lower_rest!(variable, cont.value)
+                lower_cont!()
             }
             Var(original, _) | AbilityMember(original, _, _)
                 if procs.get_partial_proc(original).is_none() =>
@@ -2566,7 +2609,7 @@ fn from_can_let<'a>(
                 };
                 procs.symbol_specializations.mark_eligible(*symbol);
 
-                let mut stmt = lower_rest!(variable, cont.value);
+                let mut stmt = lower_cont!();
 
                 let needed_specializations = procs.symbol_specializations.remove(*symbol).unwrap();
                 let zero_specialization = if needed_specializations.is_empty() {
@@ -2579,6 +2622,11 @@ fn from_can_let<'a>(
                 };
 
                 // Layer on the specialized numbers
+                let region = if def_region.is_empty() {
+                    env.region
+                } else {
+                    def_region
+                };
                 for (layout, sym) in needed_specializations
                     .into_iter()
                     .map(|(lay, (sym, _))| (lay, sym))
@@ -2590,23 +2638,28 @@ fn from_can_let<'a>(
                         Expr::Literal(literal.to_expr_literal()),
                         layout,
                         env.arena.alloc(stmt),
+                        region,
                     );
                 }
 
                 stmt
             }
             _ => {
-                let rest = lower_rest!(variable, cont.value);
+                let rest = lower_cont!();
+                let symbol = *symbol;
+                let (expr, expr_var) = (def.loc_expr.value, def.expr_var);
 
-                with_hole(
-                    env,
-                    def.loc_expr.value,
-                    def.expr_var,
-                    procs,
-                    layout_cache,
-                    *symbol,
-                    env.arena.alloc(rest),
-                )
+                env.with_region(def_region, |env| {
+                    with_hole(
+                        env,
+                        expr,
+                        expr_var,
+                        procs,
+                        layout_cache,
+                        symbol,
+                        env.arena.alloc(rest),
+                    )
+                })
             }
         };
     }
@@ -2619,34 +2672,36 @@ fn from_can_let<'a>(
         };
 
     // convert the continuation
-    let mut stmt = lower_rest!(variable, cont.value);
+    let mut stmt = lower_cont!();
 
-    // layer on any default record fields
-    for (symbol, variable, expr) in assignments {
-        let hole = env.arena.alloc(stmt);
-        stmt = with_hole(env, expr, variable, procs, layout_cache, symbol, hole);
-    }
-
-    match def.loc_expr.value {
-        roc_can::expr::Expr::Var(outer_symbol, _) if !procs.is_module_thunk(outer_symbol) => {
-            store_pattern(env, procs, layout_cache, &mono_pattern, outer_symbol, stmt)
+    env.with_region(def_region, |env| {
+        // layer on any default record fields
+        for (symbol, variable, expr) in assignments {
+            let hole = env.arena.alloc(stmt);
+            stmt = with_hole(env, expr, variable, procs, layout_cache, symbol, hole);
         }
-        _ => {
-            let outer_symbol = env.unique_symbol();
-            stmt = store_pattern(env, procs, layout_cache, &mono_pattern, outer_symbol, stmt);
 
-            // convert the def body, store in outer_symbol
-            with_hole(
-                env,
-                def.loc_expr.value,
-                def.expr_var,
-                procs,
-                layout_cache,
-                outer_symbol,
-                env.arena.alloc(stmt),
-            )
+        match def.loc_expr.value {
+            roc_can::expr::Expr::Var(outer_symbol, _) if !procs.is_module_thunk(outer_symbol) => {
+                store_pattern(env, procs, layout_cache, &mono_pattern, outer_symbol, stmt)
+            }
+            _ => {
+                let outer_symbol = env.unique_symbol();
+                stmt = store_pattern(env, procs, layout_cache, &mono_pattern, outer_symbol, stmt);
+
+                // convert the def body, store in outer_symbol
+                with_hole(
+                    env,
+                    def.loc_expr.value,
+                    def.expr_var,
+                    procs,
+                    layout_cache,
+                    outer_symbol,
+                    env.arena.alloc(stmt),
+                )
+            }
         }
-    }
+    })
 }
 
 /// turn record/tag patterns into a when expression, e.g.
@@ -3170,6 +3225,7 @@ fn generate_runtime_error_function<'a>(
         ret_layout,
         is_self_recursive: SelfRecursive::NotSelfRecursive,
         host_exposed_layouts: HostExposedLayouts::NotHostExposed,
+        region: Region::zero(),
     }
 }
 
@@ -3250,7 +3306,7 @@ fn generate_host_exposed_function<'a>(
         }
         RawFunctionLayout::ZeroArgumentThunk(result) => {
             let assigned = env.unique_symbol();
-            let hole = env.arena.alloc(Stmt::Ret(assigned));
+            let hole = env.arena.alloc(Stmt::Ret(assigned, Region::zero()));
             let forced = force_thunk(env, function_name, result, assigned, hole);
 
             let lambda_name = LambdaName::no_niche(function_name);
@@ -3262,6 +3318,7 @@ fn generate_host_exposed_function<'a>(
                 ret_layout: result,
                 is_self_recursive: SelfRecursive::NotSelfRecursive,
                 host_exposed_layouts: HostExposedLayouts::NotHostExposed,
+                region: Region::zero(),
             };
 
             let top_level = ProcLayout::from_raw_named(env.arena, lambda_name, layout);
@@ -3303,7 +3360,7 @@ fn generate_host_exposed_lambda_set<'a>(
     // this should also be reflected in the TopLevel signature
     top_level_arguments.push(lambda_set_layout);
 
-    let hole = env.arena.alloc(Stmt::Ret(assigned));
+    let hole = env.arena.alloc(Stmt::Ret(assigned, Region::zero()));
 
     let body = match_on_lambda_set(
         env,
@@ -3326,6 +3383,7 @@ fn generate_host_exposed_lambda_set<'a>(
         ret_layout: return_layout,
         is_self_recursive: SelfRecursive::NotSelfRecursive,
         host_exposed_layouts: HostExposedLayouts::NotHostExposed,
+        region: Region::zero(),
     };
 
     let top_level = ProcLayout::new(
@@ -3387,11 +3445,14 @@ fn specialize_proc_help<'a>(
 
     let body = partial_proc.body.clone();
     let body_var = partial_proc.body_var;
+    let region = partial_proc.region;
 
     // host-exposed functions are tagged on later
     let host_exposed_layouts = HostExposedLayouts::NotHostExposed;
 
-    let mut specialized_body = from_can(env, body_var, body, procs, layout_cache);
+    let mut specialized_body = env.with_region(region, |env| {
+        from_can(env, body_var, body, procs, layout_cache)
+    });
 
     let specialized_proc = match specialized {
         SpecializedLayout::FunctionPointerBody {
@@ -3422,6 +3483,7 @@ fn specialize_proc_help<'a>(
                 ret_layout,
                 is_self_recursive: recursivity,
                 host_exposed_layouts,
+                region,
             }
         }
         SpecializedLayout::FunctionBody {
@@ -3516,6 +3578,7 @@ fn specialize_proc_help<'a>(
                                     expr,
                                     layout,
                                     env.arena.alloc(specialized_body),
+                                    env.region,
                                 );
                             }
                         }
@@ -3572,6 +3635,7 @@ fn specialize_proc_help<'a>(
                                     expr,
                                     **layout,
                                     env.arena.alloc(specialized_body),
+                                    env.region,
                                 );
                             }
                         }
@@ -3621,6 +3685,7 @@ fn specialize_proc_help<'a>(
                 ret_layout,
                 is_self_recursive: recursivity,
                 host_exposed_layouts,
+                region,
             }
         }
     };
@@ -4118,6 +4183,7 @@ pub fn with_hole<'a>(
             Expr::Literal(Literal::Str(arena.alloc(string))),
             Layout::STR,
             hole,
+            env.region,
         ),
 
         IngestedFile(_, bytes, var) => {
@@ -4135,7 +4201,7 @@ pub fn with_hole<'a>(
                         elems: elements.into_bump_slice(),
                     };
 
-                    Stmt::Let(assigned, expr, interned, hole)
+                    Stmt::Let(assigned, expr, interned, hole, env.region)
                 }
                 Layout::Builtin(Builtin::Str) => Stmt::Let(
                     assigned,
@@ -4147,6 +4213,7 @@ pub fn with_hole<'a>(
                     )),
                     Layout::STR,
                     hole,
+                    env.region,
                 ),
                 _ => {
                     // This will not manifest as a real runtime error and is just returned to have a value here.
@@ -4165,6 +4232,7 @@ pub fn with_hole<'a>(
                 Expr::Literal(Literal::Int((character as i128).to_ne_bytes())),
                 layout,
                 hole,
+                env.region,
             )
         }
         LetNonRec(def, cont) => from_can_let(
@@ -4413,33 +4481,37 @@ pub fn with_hole<'a>(
                 (Ok(ret_layout), Ok(cond_layout)) => {
                     // if the hole is a return, then we don't need to merge the two
                     // branches together again, we can just immediately return
-                    let is_terminated = matches!(hole, Stmt::Ret(_));
+                    let is_terminated = matches!(hole, Stmt::Ret(..));
 
                     if is_terminated {
                         let terminator = hole;
 
-                        let mut stmt = with_hole(
-                            env,
-                            final_else.value,
-                            branch_var,
-                            procs,
-                            layout_cache,
-                            assigned,
-                            terminator,
-                        );
-
-                        for (loc_cond, loc_then) in branches.into_iter().rev() {
-                            let branching_symbol = env.unique_symbol();
-
-                            let then = with_hole(
+                        let mut stmt = env.with_region(final_else.region, |env| {
+                            with_hole(
                                 env,
-                                loc_then.value,
+                                final_else.value,
                                 branch_var,
                                 procs,
                                 layout_cache,
                                 assigned,
                                 terminator,
-                            );
+                            )
+                        });
+
+                        for (loc_cond, loc_then) in branches.into_iter().rev() {
+                            let branching_symbol = env.unique_symbol();
+
+                            let then = env.with_region(loc_then.region, |env| {
+                                with_hole(
+                                    env,
+                                    loc_then.value,
+                                    branch_var,
+                                    procs,
+                                    layout_cache,
+                                    assigned,
+                                    terminator,
+                                )
+                            });
 
                             stmt = cond(env, branching_symbol, cond_layout, then, stmt, ret_layout);
 
@@ -4463,15 +4535,17 @@ pub fn with_hole<'a>(
                             .arena
                             .alloc(Stmt::Jump(id, env.arena.alloc([assigned_in_jump])));
 
-                        let mut stmt = with_hole(
-                            env,
-                            final_else.value,
-                            branch_var,
-                            procs,
-                            layout_cache,
-                            assigned_in_jump,
-                            terminator,
-                        );
+                        let mut stmt = env.with_region(final_else.region, |env| {
+                            with_hole(
+                                env,
+                                final_else.value,
+                                branch_var,
+                                procs,
+                                layout_cache,
+                                assigned_in_jump,
+                                terminator,
+                            )
+                        });
 
                         for (loc_cond, loc_then) in branches.into_iter().rev() {
                             let branching_symbol = possible_reuse_symbol_or_specialize(
@@ -4482,15 +4556,17 @@ pub fn with_hole<'a>(
                                 cond_var,
                             );
 
-                            let then = with_hole(
-                                env,
-                                loc_then.value,
-                                branch_var,
-                                procs,
-                                layout_cache,
-                                assigned_in_jump,
-                                terminator,
-                            );
+                            let then = env.with_region(loc_then.region, |env| {
+                                with_hole(
+                                    env,
+                                    loc_then.value,
+                                    branch_var,
+                                    procs,
+                                    layout_cache,
+                                    assigned_in_jump,
+                                    terminator,
+                                )
+                            });
 
                             stmt = cond(env, branching_symbol, cond_layout, then, stmt, ret_layout);
 
@@ -4523,6 +4599,7 @@ pub fn with_hole<'a>(
                             parameters: env.arena.alloc([param]),
                             remainder: env.arena.alloc(stmt),
                             body: hole,
+                            region: env.region,
                         }
                     }
                 }
@@ -4534,7 +4611,7 @@ pub fn with_hole<'a>(
         When {
             cond_var,
             expr_var,
-            region: _,
+            region,
             loc_cond,
             branches,
             branches_cond_var: _,
@@ -4550,17 +4627,19 @@ pub fn with_hole<'a>(
 
             let id = JoinPointId(env.unique_symbol());
 
-            let mut stmt = from_can_when(
-                env,
-                cond_var,
-                expr_var,
-                cond_symbol,
-                branches,
-                exhaustive,
-                layout_cache,
-                procs,
-                Some(id),
-            );
+            let mut stmt = env.with_region(region, |env| {
+                from_can_when(
+                    env,
+                    cond_var,
+                    expr_var,
+                    cond_symbol,
+                    branches,
+                    exhaustive,
+                    layout_cache,
+                    procs,
+                    Some(id),
+                )
+            });
 
             // define the `when` condition
             stmt = assign_to_symbol(
@@ -4588,6 +4667,7 @@ pub fn with_hole<'a>(
                 parameters: env.arena.alloc([param]),
                 remainder: env.arena.alloc(stmt),
                 body: env.arena.alloc(hole),
+                region: env.region,
             }
         }
 
@@ -4604,13 +4684,13 @@ pub fn with_hole<'a>(
                     let expr = Expr::EmptyArray;
                     let list_layout =
                         layout_cache.put_in(Layout::Builtin(Builtin::List(elem_layout)));
-                    Stmt::Let(assigned, expr, list_layout, hole)
+                    Stmt::Let(assigned, expr, list_layout, hole, env.region)
                 }
                 Err(LayoutProblem::UnresolvedTypeVar(_)) => {
                     let expr = Expr::EmptyArray;
                     let list_layout =
                         layout_cache.put_in(Layout::Builtin(Builtin::List(Layout::VOID)));
-                    Stmt::Let(assigned, expr, list_layout, hole)
+                    Stmt::Let(assigned, expr, list_layout, hole, env.region)
                 }
                 Err(LayoutProblem::Erroneous) => panic!("list element is error type"),
             }
@@ -4657,7 +4737,7 @@ pub fn with_hole<'a>(
 
             let list_layout = layout_cache.put_in(Layout::Builtin(Builtin::List(elem_layout)));
 
-            let stmt = Stmt::Let(assigned, expr, list_layout, hole);
+            let stmt = Stmt::Let(assigned, expr, list_layout, hole, env.region);
 
             let iter = symbol_exprs
                 .into_iter()
@@ -4993,7 +5073,7 @@ pub fn with_hole<'a>(
                 stmt
             } else {
                 let expr = Expr::Struct(symbols);
-                let mut stmt = Stmt::Let(assigned, expr, record_layout, hole);
+                let mut stmt = Stmt::Let(assigned, expr, record_layout, hole, env.region);
 
                 let it = field_layouts.iter().zip(symbols.iter()).zip(fields);
 
@@ -5030,8 +5110,13 @@ pub fn with_hole<'a>(
                                 index,
                                 field_layouts,
                             };
-                            stmt =
-                                Stmt::Let(*symbol, access_expr, *field_layout, arena.alloc(stmt));
+                            stmt = Stmt::Let(
+                                *symbol,
+                                access_expr,
+                                *field_layout,
+                                arena.alloc(stmt),
+                                env.region,
+                            );
 
                             if structure_needs_specialization {
                                 stmt = specialize_symbol(
@@ -5533,13 +5618,25 @@ pub fn with_hole<'a>(
                     debug_assert_eq!(arg_symbols.len(), 1);
                     let x = arg_symbols[0];
 
-                    Stmt::Let(assigned, Expr::ExprBox { symbol: x }, layout, hole)
+                    Stmt::Let(
+                        assigned,
+                        Expr::ExprBox { symbol: x },
+                        layout,
+                        hole,
+                        env.region,
+                    )
                 }
                 UnboxExpr => {
                     debug_assert_eq!(arg_symbols.len(), 1);
                     let x = arg_symbols[0];
 
-                    Stmt::Let(assigned, Expr::ExprUnbox { symbol: x }, layout, hole)
+                    Stmt::Let(
+                        assigned,
+                        Expr::ExprUnbox { symbol: x },
+                        layout,
+                        hole,
+                        env.region,
+                    )
                 }
                 _ => {
                     let call = self::Call {
@@ -5617,7 +5714,7 @@ fn compile_struct_like_access<'a>(
                 .from_var(env.arena, elem_var, env.subs)
                 .unwrap_or_else(|err| panic!("TODO turn fn_var into a RuntimeError {:?}", err));
 
-            Stmt::Let(assigned, expr, layout, hole)
+            Stmt::Let(assigned, expr, layout, hole, env.region)
         }
     };
 
@@ -5698,7 +5795,13 @@ fn compile_struct_like<'a, L, UnusedLayout>(
         substitute_in_exprs(env.arena, &mut hole, assigned, *only_field);
         hole
     } else {
-        Stmt::Let(assigned, Expr::Struct(elem_symbols), layout, hole)
+        Stmt::Let(
+            assigned,
+            Expr::Struct(elem_symbols),
+            layout,
+            hole,
+            env.region,
+        )
     };
 
     for (opt_field, symbol) in can_elems.into_iter().rev().zip(elem_symbols.iter().rev()) {
@@ -5887,7 +5990,13 @@ where
                 arguments: symbols,
             };
 
-            Stmt::Let(assigned, expr, lambda_set_layout, env.arena.alloc(hole))
+            Stmt::Let(
+                assigned,
+                expr,
+                lambda_set_layout,
+                env.arena.alloc(hole),
+                env.region,
+            )
         }
         ClosureRepresentation::AlphabeticOrderStruct(field_layouts) => {
             debug_assert_eq!(field_layouts.len(), symbols.len());
@@ -5924,7 +6033,7 @@ where
 
             let expr = Expr::Struct(symbols);
 
-            Stmt::Let(assigned, expr, lambda_set_layout, hole)
+            Stmt::Let(assigned, expr, lambda_set_layout, hole, env.region)
         }
         ClosureRepresentation::UnwrappedCapture(_layout) => {
             debug_assert_eq!(symbols.len(), 1);
@@ -5953,7 +6062,7 @@ where
                 let tag_id = name.name() != lambda_set.iter_set().next().unwrap().name();
                 let expr = Expr::Literal(Literal::Bool(tag_id));
 
-                Stmt::Let(assigned, expr, lambda_set_layout, hole)
+                Stmt::Let(assigned, expr, lambda_set_layout, hole, env.region)
             }
             EnumDispatch::U8 => {
                 debug_assert_eq!(symbols.len(), 0);
@@ -5966,7 +6075,7 @@ where
 
                 let expr = Expr::Literal(Literal::Byte(tag_id));
 
-                Stmt::Let(assigned, expr, lambda_set_layout, hole)
+                Stmt::Let(assigned, expr, lambda_set_layout, hole, env.region)
             }
         },
     };
@@ -6019,12 +6128,13 @@ fn convert_tag_union<'a>(
             "The `[]` type has no constructors, source var {:?}",
             variant_var
         ),
-        Unit => Stmt::Let(assigned, Expr::Struct(&[]), Layout::UNIT, hole),
+        Unit => Stmt::Let(assigned, Expr::Struct(&[]), Layout::UNIT, hole, env.region),
         BoolUnion { ttrue, .. } => Stmt::Let(
             assigned,
             Expr::Literal(Literal::Bool(&tag_name == ttrue.expect_tag_ref())),
             Layout::BOOL,
             hole,
+            env.region,
         ),
         ByteUnion(tag_names) => {
             let opt_tag_id = tag_names
@@ -6037,6 +6147,7 @@ fn convert_tag_union<'a>(
                     Expr::Literal(Literal::Byte(tag_id as u8)),
                     Layout::U8,
                     hole,
+                    env.region,
                 ),
                 None => runtime_error(env, "tag must be in its own type"),
             }
@@ -6063,7 +6174,13 @@ fn convert_tag_union<'a>(
                 substitute_in_exprs(env.arena, &mut hole, assigned, *only_field);
                 hole
             } else {
-                Stmt::Let(assigned, Expr::Struct(field_symbols), layout, hole)
+                Stmt::Let(
+                    assigned,
+                    Expr::Struct(field_symbols),
+                    layout,
+                    hole,
+                    env.region,
+                )
             };
 
             let iter = field_symbols_temp.into_iter().map(|(_, _, data)| data);
@@ -6097,7 +6214,13 @@ fn convert_tag_union<'a>(
                     substitute_in_exprs(env.arena, &mut hole, assigned, *only_field);
                     hole
                 } else {
-                    Stmt::Let(assigned, Expr::Struct(field_symbols), layout, hole)
+                    Stmt::Let(
+                        assigned,
+                        Expr::Struct(field_symbols),
+                        layout,
+                        hole,
+                        env.region,
+                    )
                 };
 
                 let iter = field_symbols_temp.into_iter().map(|(_, _, data)| data);
@@ -6253,7 +6376,7 @@ fn convert_tag_union<'a>(
 
             let union_layout = layout_cache.put_in(Layout::Union(union_layout));
 
-            let stmt = Stmt::Let(assigned, tag, union_layout, hole);
+            let stmt = Stmt::Let(assigned, tag, union_layout, hole, env.region);
             let iter = field_symbols_temp
                 .into_iter()
                 .map(|x| x.2 .0)
@@ -6548,7 +6671,7 @@ pub fn from_can<'a>(
         When {
             cond_var,
             expr_var,
-            region: _,
+            region,
             loc_cond,
             branches,
             branches_cond_var: _,
@@ -6562,17 +6685,19 @@ pub fn from_can<'a>(
                 cond_var,
             );
 
-            let stmt = from_can_when(
-                env,
-                cond_var,
-                expr_var,
-                cond_symbol,
-                branches,
-                exhaustive,
-                layout_cache,
-                procs,
-                None,
-            );
+            let stmt = env.with_region(region, |env| {
+                from_can_when(
+                    env,
+                    cond_var,
+                    expr_var,
+                    cond_symbol,
+                    branches,
+                    exhaustive,
+                    layout_cache,
+                    procs,
+                    None,
+                )
+            });
 
             // define the `when` condition
             assign_to_symbol(
@@ -6598,7 +6723,9 @@ pub fn from_can<'a>(
                 .from_var(env.arena, cond_var, env.subs)
                 .expect("invalid cond_layout");
 
-            let mut stmt = from_can(env, branch_var, final_else.value, procs, layout_cache);
+            let mut stmt = env.with_region(final_else.region, |env| {
+                from_can(env, branch_var, final_else.value, procs, layout_cache)
+            });
 
             for (loc_cond, loc_then) in branches.into_iter().rev() {
                 let branching_symbol = possible_reuse_symbol_or_specialize(
@@ -6608,7 +6735,9 @@ pub fn from_can<'a>(
                     &loc_cond.value,
                     cond_var,
                 );
-                let then = from_can(env, branch_var, loc_then.value, procs, layout_cache);
+                let then = env.with_region(loc_then.region, |env| {
+                    from_can(env, branch_var, loc_then.value, procs, layout_cache)
+                });
 
                 stmt = cond(env, branching_symbol, cond_layout, then, stmt, ret_layout);
 
@@ -6631,7 +6760,9 @@ pub fn from_can<'a>(
             loc_continuation,
             lookups_in_cond,
         } => {
-            let rest = from_can(env, variable, loc_continuation.value, procs, layout_cache);
+            let rest = env.with_region(loc_continuation.region, |env| {
+                from_can(env, variable, loc_continuation.value, procs, layout_cache)
+            });
             let cond_symbol = env.unique_symbol();
 
             let mut lookups = Vec::with_capacity_in(lookups_in_cond.len(), env.arena);
@@ -6678,15 +6809,17 @@ pub fn from_can<'a>(
                 remainder: env.arena.alloc(rest),
             };
 
-            stmt = with_hole(
-                env,
-                loc_condition.value,
-                Variable::BOOL,
-                procs,
-                layout_cache,
-                cond_symbol,
-                env.arena.alloc(stmt),
-            );
+            stmt = env.with_region(loc_condition.region, |env| {
+                with_hole(
+                    env,
+                    loc_condition.value,
+                    Variable::BOOL,
+                    procs,
+                    layout_cache,
+                    cond_symbol,
+                    env.arena.alloc(stmt),
+                )
+            });
 
             // Now that the condition has been specialized, export the specialized types of our
             // lookups into the expectation subs.
@@ -6700,7 +6833,9 @@ pub fn from_can<'a>(
             loc_continuation,
             lookups_in_cond,
         } => {
-            let rest = from_can(env, variable, loc_continuation.value, procs, layout_cache);
+            let rest = env.with_region(loc_continuation.region, |env| {
+                from_can(env, variable, loc_continuation.value, procs, layout_cache)
+            });
             let cond_symbol = env.unique_symbol();
 
             let mut lookups = Vec::with_capacity_in(lookups_in_cond.len(), env.arena);
@@ -6747,15 +6882,17 @@ pub fn from_can<'a>(
                 remainder: env.arena.alloc(rest),
             };
 
-            stmt = with_hole(
-                env,
-                loc_condition.value,
-                Variable::BOOL,
-                procs,
-                layout_cache,
-                cond_symbol,
-                env.arena.alloc(stmt),
-            );
+            stmt = env.with_region(loc_condition.region, |env| {
+                with_hole(
+                    env,
+                    loc_condition.value,
+                    Variable::BOOL,
+                    procs,
+                    layout_cache,
+                    cond_symbol,
+                    env.arena.alloc(stmt),
+                )
+            });
 
             store_specialized_expectation_lookups(env, lookup_variables, specialized_variables);
 
@@ -6768,7 +6905,9 @@ pub fn from_can<'a>(
             variable,
             symbol: dbg_symbol,
         } => {
-            let rest = from_can(env, variable, loc_continuation.value, procs, layout_cache);
+            let rest = env.with_region(loc_continuation.region, |env| {
+                from_can(env, variable, loc_continuation.value, procs, layout_cache)
+            });
 
             let spec_var = env
                 .expectation_subs
@@ -6836,7 +6975,7 @@ pub fn from_can<'a>(
         LetNonRec(def, cont) => from_can_let(env, procs, layout_cache, def, cont, variable, None),
         _ => {
             let symbol = env.unique_symbol();
-            let hole = env.arena.alloc(Stmt::Ret(symbol));
+            let hole = env.arena.alloc(Stmt::Ret(symbol, env.region));
             with_hole(env, can_expr, variable, procs, layout_cache, symbol, hole)
         }
     }
@@ -6866,7 +7005,7 @@ fn to_opt_branches<'a>(
 ) -> std::vec::Vec<(
     Pattern<'a>,
     Option<Loc<roc_can::expr::Expr>>,
-    roc_can::expr::Expr,
+    Loc<roc_can::expr::Expr>,
 )> {
     debug_assert!(!branches.is_empty());
 
@@ -6911,14 +7050,14 @@ fn to_opt_branches<'a>(
                     };
 
                     // TODO remove clone?
-                    opt_branches.push((mono_pattern, when_branch.guard.clone(), loc_expr.value));
+                    opt_branches.push((mono_pattern, when_branch.guard.clone(), loc_expr));
                 }
                 Err(runtime_error) => {
                     // TODO remove clone?
                     opt_branches.push((
                         Pattern::Underscore,
                         when_branch.guard.clone(),
-                        roc_can::expr::Expr::RuntimeError(runtime_error),
+                        Loc::at_zero(roc_can::expr::Expr::RuntimeError(runtime_error)),
                     ));
                 }
             }
@@ -6931,7 +7070,9 @@ fn to_opt_branches<'a>(
         opt_branches.push((
             Pattern::Underscore,
             None,
-            roc_can::expr::Expr::RuntimeError(roc_problem::can::RuntimeError::NonExhaustivePattern),
+            Loc::at_zero(roc_can::expr::Expr::RuntimeError(
+                roc_problem::can::RuntimeError::NonExhaustivePattern,
+            )),
         ));
     }
 
@@ -6972,7 +7113,7 @@ fn from_can_when<'a>(
     let arena = env.arena;
     let it = opt_branches
         .into_iter()
-        .filter_map(|(pattern, opt_guard, can_expr)| {
+        .filter_map(|(pattern, opt_guard, loc_can_expr)| {
             // If the pattern has a void layout we can drop it; however, we must still perform the
             // work of building the body, because that may contain specializations we must
             // discover for use elsewhere. See
@@ -6989,16 +7130,24 @@ fn from_can_when<'a>(
                 None
             };
 
-            let branch_stmt = match join_point {
-                None => from_can(env, expr_var, can_expr, procs, layout_cache),
+            let branch_stmt = env.with_region(loc_can_expr.region, |env| match join_point {
+                None => from_can(env, expr_var, loc_can_expr.value, procs, layout_cache),
                 Some(id) => {
                     let symbol = env.unique_symbol();
                     let arguments = bumpalo::vec![in env.arena; symbol].into_bump_slice();
                     let jump = env.arena.alloc(Stmt::Jump(id, arguments));
 
-                    with_hole(env, can_expr, expr_var, procs, layout_cache, symbol, jump)
+                    with_hole(
+                        env,
+                        loc_can_expr.value,
+                        expr_var,
+                        procs,
+                        layout_cache,
+                        symbol,
+                        jump,
+                    )
                 }
-            };
+            });
 
             use decision_tree::Guard;
             let result = if let Some(loc_expr) = opt_guard {
@@ -7159,7 +7308,7 @@ fn substitute_in_stmt_help<'a>(
     use Stmt::*;
 
     match stmt {
-        Let(symbol, expr, layout, cont) => {
+        Let(symbol, expr, layout, cont, region) => {
             let opt_cont = substitute_in_stmt_help(arena, cont, subs);
             let opt_expr = substitute_in_expr(arena, expr, subs);
 
@@ -7167,7 +7316,7 @@ fn substitute_in_stmt_help<'a>(
                 let cont = opt_cont.unwrap_or(cont);
                 let expr = opt_expr.unwrap_or_else(|| expr.clone());
 
-                Some(arena.alloc(Let(*symbol, expr, *layout, cont)))
+                Some(arena.alloc(Let(*symbol, expr, *layout, cont, *region)))
             } else {
                 None
             }
@@ -7177,6 +7326,7 @@ fn substitute_in_stmt_help<'a>(
             parameters,
             remainder,
             body: continuation,
+            region,
         } => {
             let opt_remainder = substitute_in_stmt_help(arena, remainder, subs);
             let opt_continuation = substitute_in_stmt_help(arena, continuation, subs);
@@ -7190,6 +7340,7 @@ fn substitute_in_stmt_help<'a>(
                     parameters,
                     remainder,
                     body: continuation,
+                    region: *region,
                 }))
             } else {
                 None
@@ -7201,6 +7352,7 @@ fn substitute_in_stmt_help<'a>(
             branches,
             default_branch,
             ret_layout,
+            region,
         } => {
             let mut did_change = false;
 
@@ -7255,13 +7407,14 @@ fn substitute_in_stmt_help<'a>(
                     default_branch,
                     branches,
                     ret_layout: *ret_layout,
+                    region: *region,
                 }))
             } else {
                 None
             }
         }
-        Ret(s) => match substitute(subs, *s) {
-            Some(s) => Some(arena.alloc(Ret(s))),
+        Ret(s, region) => match substitute(subs, *s) {
+            Some(s) => Some(arena.alloc(Ret(s, *region))),
             None => None,
         },
         Refcounting(modify, cont) => {
@@ -7722,7 +7875,13 @@ fn force_thunk<'a>(
 }
 
 fn let_empty_struct<'a>(assigned: Symbol, hole: &'a Stmt<'a>) -> Stmt<'a> {
-    Stmt::Let(assigned, Expr::Struct(&[]), Layout::UNIT, hole)
+    Stmt::Let(
+        assigned,
+        Expr::Struct(&[]),
+        Layout::UNIT,
+        hole,
+        Region::zero(),
+    )
 }
 
 /// If the symbol is a function or polymorphic value, make sure it is properly specialized
@@ -7971,30 +8130,32 @@ fn assign_to_symbol<'a>(
     result: Stmt<'a>,
 ) -> Stmt<'a> {
     use ReuseSymbol::*;
-    match can_reuse_symbol(env, procs, &loc_arg.value, arg_var) {
-        Imported(original) | LocalFunction(original) | UnspecializedExpr(original) => {
-            // for functions we must make sure they are specialized correctly
-            specialize_symbol(
+    env.with_region(loc_arg.region, |env| {
+        match can_reuse_symbol(env, procs, &loc_arg.value, arg_var) {
+            Imported(original) | LocalFunction(original) | UnspecializedExpr(original) => {
+                // for functions we must make sure they are specialized correctly
+                specialize_symbol(
+                    env,
+                    procs,
+                    layout_cache,
+                    Some(arg_var),
+                    symbol,
+                    env.arena.alloc(result),
+                    original,
+                )
+            }
+            Value(_symbol) => result,
+            NotASymbol => with_hole(
                 env,
+                loc_arg.value,
+                arg_var,
                 procs,
                 layout_cache,
-                Some(arg_var),
                 symbol,
                 env.arena.alloc(result),
-                original,
-            )
+            ),
         }
-        Value(_symbol) => result,
-        NotASymbol => with_hole(
-            env,
-            loc_arg.value,
-            arg_var,
-            procs,
-            layout_cache,
-            symbol,
-            env.arena.alloc(result),
-        ),
-    }
+    })
 }
 
 fn assign_to_symbols<'a, I>(
@@ -8034,13 +8195,13 @@ fn add_needed_external<'a>(
 }
 
 fn build_call<'a>(
-    _env: &mut Env<'a, '_>,
+    env: &mut Env<'a, '_>,
     call: Call<'a>,
     assigned: Symbol,
     return_layout: InLayout<'a>,
     hole: &'a Stmt<'a>,
 ) -> Stmt<'a> {
-    Stmt::Let(assigned, Expr::Call(call), return_layout, hole)
+    Stmt::Let(assigned, Expr::Call(call), return_layout, hole, env.region)
 }
 
 /// See https://github.com/roc-lang/roc/issues/1549
@@ -8776,7 +8937,7 @@ fn assign_num_literal_expr<'a>(
     let literal =
         make_num_literal(&layout_cache.interner, layout, num_str, num_value).to_expr_literal();
 
-    Stmt::Let(assigned, Expr::Literal(literal), layout, hole)
+    Stmt::Let(assigned, Expr::Literal(literal), layout, hole, env.region)
 }
 
 type ToLowLevelCallArguments<'a> = (
@@ -8832,6 +8993,7 @@ where
                 expr,
                 union_layout.tag_id_layout(),
                 env.arena.alloc(result),
+                env.region,
             )
         }
         ClosureCallOptions::Struct { .. } => match lambda_set.iter_set().next() {
@@ -8967,6 +9129,7 @@ where
         branches: branches.into_bump_slice(),
         default_branch,
         ret_layout: return_layout,
+        region: env.region,
     };
 
     let param = Param {
@@ -8980,6 +9143,7 @@ where
         parameters: &*env.arena.alloc([param]),
         body: hole,
         remainder: env.arena.alloc(switch),
+        region: env.region,
     }
 }
 
@@ -9031,6 +9195,7 @@ fn match_on_lambda_set<'a>(
                 expr,
                 union_layout.tag_id_layout(),
                 env.arena.alloc(result),
+                env.region,
             )
         }
         ClosureCallOptions::Struct {
@@ -9154,7 +9319,7 @@ fn union_lambda_set_to_switch<'a>(
     }
 
     let (opt_join, branch_assigned, branch_hole) = match hole {
-        Stmt::Ret(_) => {
+        Stmt::Ret(..) => {
             // No need to jump to a joinpoint, inline the return in each statement as-is.
             // This makes further analyses, like TCO, easier as well.
             (None, assigned, hole)
@@ -9205,6 +9370,7 @@ fn union_lambda_set_to_switch<'a>(
         branches: branches.into_bump_slice(),
         default_branch,
         ret_layout: return_layout,
+        region: env.region,
     };
 
     match opt_join {
@@ -9221,6 +9387,7 @@ fn union_lambda_set_to_switch<'a>(
                 parameters: &*env.arena.alloc([param]),
                 body: hole,
                 remainder: env.arena.alloc(switch),
+                region: env.region,
             }
         }
     }
@@ -9331,7 +9498,7 @@ fn enum_lambda_set_to_switch<'a>(
     debug_assert_ne!(lambda_set.len(), 0);
 
     let (opt_join, branch_assigned, branch_hole) = match hole {
-        Stmt::Ret(_) => {
+        Stmt::Ret(..) => {
             // No need to jump to a joinpoint, inline the return in each statement as-is.
             // This makes further analyses, like TCO, easier as well.
             (None, assigned, hole)
@@ -9372,6 +9539,7 @@ fn enum_lambda_set_to_switch<'a>(
         branches: branches.into_bump_slice(),
         default_branch,
         ret_layout: return_layout,
+        region: env.region,
     };
 
     match opt_join {
@@ -9388,6 +9556,7 @@ fn enum_lambda_set_to_switch<'a>(
                 parameters: &*env.arena.alloc([param]),
                 body: hole,
                 remainder: env.arena.alloc(switch),
+                region: env.region,
             }
         }
     }
@@ -9475,6 +9644,7 @@ where
         branches: branches.into_bump_slice(),
         default_branch,
         ret_layout: return_layout,
+        region: env.region,
     };
 
     let param = Param {
@@ -9488,6 +9658,7 @@ where
         parameters: &*env.arena.alloc([param]),
         body: hole,
         remainder: env.arena.alloc(switch),
+        region: env.region,
     }
 }
 
@@ -9702,7 +9873,7 @@ where
 
         home.register_debug_idents(ident_ids);
 
-        let ret_stmt = arena.alloc(Stmt::Ret(result));
+        let ret_stmt = arena.alloc(Stmt::Ret(result, Region::zero()));
 
         let field_get_expr = Expr::StructAtIndex {
             index: index as u64,
@@ -9710,7 +9881,7 @@ where
             structure: unboxed,
         };
 
-        let field_get_stmt = Stmt::Let(result, field_get_expr, *field, ret_stmt);
+        let field_get_stmt = Stmt::Let(result, field_get_expr, *field, ret_stmt, Region::zero());
 
         let unbox_expr = Expr::ExprUnbox { symbol: argument };
 
@@ -9719,6 +9890,7 @@ where
             unbox_expr,
             interned_unboxed_struct_layout,
             arena.alloc(field_get_stmt),
+            Region::zero(),
         );
 
         let proc = Proc {
@@ -9729,6 +9901,7 @@ where
             ret_layout: *field,
             is_self_recursive: SelfRecursive::NotSelfRecursive,
             host_exposed_layouts: HostExposedLayouts::NotHostExposed,
+            region: Region::zero(),
         };
 
         answer.push(GlueProc {
@@ -9801,7 +9974,7 @@ where
 
         home.register_debug_idents(ident_ids);
 
-        let ret_stmt = arena.alloc(Stmt::Ret(result));
+        let ret_stmt = arena.alloc(Stmt::Ret(result, Region::zero()));
 
         let field_get_expr = Expr::UnionAtIndex {
             structure: unboxed,
@@ -9810,11 +9983,17 @@ where
             index: index as u64,
         };
 
-        let field_get_stmt = Stmt::Let(result, field_get_expr, *field, ret_stmt);
+        let field_get_stmt = Stmt::Let(result, field_get_expr, *field, ret_stmt, Region::zero());
 
         let unbox_expr = Expr::ExprUnbox { symbol: argument };
 
-        let unbox_stmt = Stmt::Let(unboxed, unbox_expr, interned, arena.alloc(field_get_stmt));
+        let unbox_stmt = Stmt::Let(
+            unboxed,
+            unbox_expr,
+            interned,
+            arena.alloc(field_get_stmt),
+            Region::zero(),
+        );
 
         let proc = Proc {
             name: LambdaName::no_niche(symbol),
@@ -9824,6 +10003,7 @@ where
             ret_layout: *field,
             is_self_recursive: SelfRecursive::NotSelfRecursive,
             host_exposed_layouts: HostExposedLayouts::NotHostExposed,
+            region: Region::zero(),
         };
 
         answer.push(GlueProc {
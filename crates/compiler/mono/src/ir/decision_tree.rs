@@ -1544,6 +1544,7 @@ pub(crate) fn optimize_when<'a>(
             parameters: join_params,
             body: env.arena.alloc(join_body),
             remainder: env.arena.alloc(stmt),
+            region: env.region,
         };
     }
 
@@ -1932,6 +1933,7 @@ fn compile_test_help<'a>(
         ret_layout,
         branches,
         default_branch,
+        region: env.region,
     };
 
     let op = match cmp {
@@ -1947,11 +1949,17 @@ fn compile_test_help<'a>(
     });
 
     // write to the test symbol
-    cond = Stmt::Let(test_symbol, test, Layout::BOOL, arena.alloc(cond));
+    cond = Stmt::Let(
+        test_symbol,
+        test,
+        Layout::BOOL,
+        arena.alloc(cond),
+        env.region,
+    );
 
     // stores are in top-to-bottom order, so we have to add them in reverse
     for (symbol, layout, expr) in stores.into_iter().rev() {
-        cond = Stmt::Let(symbol, expr, layout, arena.alloc(cond));
+        cond = Stmt::Let(symbol, expr, layout, arena.alloc(cond), env.region);
     }
 
     cond
@@ -2113,6 +2121,7 @@ fn decide_to_branching<'a>(
                 parameters: arena.alloc([param]),
                 body: arena.alloc(decide),
                 remainder: arena.alloc(stmt),
+                region: env.region,
             };
 
             store_pattern(env, procs, layout_cache, &pattern, cond_symbol, join)
@@ -2191,6 +2200,7 @@ fn decide_to_branching<'a>(
                     parameters: &[],
                     body: fail,
                     remainder: arena.alloc(test_stmt),
+                    region: env.region,
                 }
             }
         }
@@ -2295,6 +2305,7 @@ fn decide_to_branching<'a>(
                     branches: branches.into_bump_slice(),
                     default_branch: (default_branch_info, env.arena.alloc(default_branch)),
                     ret_layout,
+                    region: env.region,
                 };
 
                 let expr = Expr::GetTagId {
@@ -2307,6 +2318,7 @@ fn decide_to_branching<'a>(
                     expr,
                     union_layout.tag_id_layout(),
                     env.arena.alloc(temp),
+                    env.region,
                 )
             } else if let Layout::Builtin(Builtin::List(_)) = inner_cond_layout_raw {
                 let len_symbol = env.unique_symbol();
@@ -2317,6 +2329,7 @@ fn decide_to_branching<'a>(
                     branches: branches.into_bump_slice(),
                     default_branch: (default_branch_info, env.arena.alloc(default_branch)),
                     ret_layout,
+                    region: env.region,
                 };
 
                 let len_expr = Expr::Call(Call {
@@ -2332,6 +2345,7 @@ fn decide_to_branching<'a>(
                     len_expr,
                     Layout::usize(env.target_info),
                     env.arena.alloc(switch),
+                    env.region,
                 )
             } else {
                 Stmt::Switch {
@@ -2340,11 +2354,12 @@ fn decide_to_branching<'a>(
                     branches: branches.into_bump_slice(),
                     default_branch: (default_branch_info, env.arena.alloc(default_branch)),
                     ret_layout,
+                    region: env.region,
                 }
             };
 
             for (symbol, layout, expr) in cond_stores_vec.into_iter().rev() {
-                switch = Stmt::Let(symbol, expr, layout, env.arena.alloc(switch));
+                switch = Stmt::Let(symbol, expr, layout, env.arena.alloc(switch), env.region);
             }
 
             // make a jump table based on the tests
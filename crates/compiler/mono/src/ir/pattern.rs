@@ -1478,7 +1478,13 @@ fn store_list_pattern<'a>(
 
                 // store immediately in the given symbol
                 (
-                    Stmt::Let(*symbol, load, element_layout, env.arena.alloc(stmt)),
+                    Stmt::Let(
+                        *symbol,
+                        load,
+                        element_layout,
+                        env.arena.alloc(stmt),
+                        env.region,
+                    ),
                     needed_stores,
                 )
             }
@@ -1505,7 +1511,13 @@ fn store_list_pattern<'a>(
                         // only if we bind one of its (sub)fields to a used name should we
                         // extract the field
                         (
-                            Stmt::Let(symbol, load, element_layout, env.arena.alloc(stmt)),
+                            Stmt::Let(
+                                symbol,
+                                load,
+                                element_layout,
+                                env.arena.alloc(stmt),
+                                env.region,
+                            ),
                             needed_stores,
                         )
                     }
@@ -1522,7 +1534,7 @@ fn store_list_pattern<'a>(
 
         stmt = store_loaded;
         for (sym, lay, expr) in needed_stores.rev() {
-            stmt = Stmt::Let(sym, expr, lay, env.arena.alloc(stmt));
+            stmt = Stmt::Let(sym, expr, lay, env.arena.alloc(stmt), env.region);
         }
     }
 
@@ -1566,7 +1578,7 @@ fn store_tag_pattern<'a>(
         match argument {
             Identifier(symbol) => {
                 // store immediately in the given symbol
-                stmt = Stmt::Let(*symbol, load, arg_layout, env.arena.alloc(stmt));
+                stmt = Stmt::Let(*symbol, load, arg_layout, env.arena.alloc(stmt), env.region);
                 is_productive = true;
             }
             Underscore => {
@@ -1589,7 +1601,8 @@ fn store_tag_pattern<'a>(
                         stmt = new;
                         // only if we bind one of its (sub)fields to a used name should we
                         // extract the field
-                        stmt = Stmt::Let(symbol, load, arg_layout, env.arena.alloc(stmt));
+                        stmt =
+                            Stmt::Let(symbol, load, arg_layout, env.arena.alloc(stmt), env.region);
                     }
                     StorePattern::NotProductive(new) => {
                         // do nothing
@@ -1641,7 +1654,7 @@ fn store_newtype_pattern<'a>(
 
         match argument {
             Identifier(symbol) => {
-                stmt = Stmt::Let(*symbol, load, arg_layout, env.arena.alloc(stmt));
+                stmt = Stmt::Let(*symbol, load, arg_layout, env.arena.alloc(stmt), env.region);
                 is_productive = true;
             }
             Underscore => {
@@ -1664,7 +1677,8 @@ fn store_newtype_pattern<'a>(
                         stmt = new;
                         // only if we bind one of its (sub)fields to a used name should we
                         // extract the field
-                        stmt = Stmt::Let(symbol, load, arg_layout, env.arena.alloc(stmt));
+                        stmt =
+                            Stmt::Let(symbol, load, arg_layout, env.arena.alloc(stmt), env.region);
                     }
                     StorePattern::NotProductive(new) => {
                         // do nothing
@@ -1703,7 +1717,13 @@ fn store_tuple_destruct<'a>(
 
     match &destruct.pat {
         Identifier(symbol) => {
-            stmt = Stmt::Let(*symbol, load, destruct.layout, env.arena.alloc(stmt));
+            stmt = Stmt::Let(
+                *symbol,
+                load,
+                destruct.layout,
+                env.arena.alloc(stmt),
+                env.region,
+            );
         }
         Underscore => {
             // important that this is special-cased to do nothing: mono record patterns will extract all the
@@ -1737,7 +1757,13 @@ fn store_tuple_destruct<'a>(
             match store_pattern_help(env, procs, layout_cache, &destruct.pat, symbol, stmt) {
                 StorePattern::Productive(new) => {
                     stmt = new;
-                    stmt = Stmt::Let(symbol, load, destruct.layout, env.arena.alloc(stmt));
+                    stmt = Stmt::Let(
+                        symbol,
+                        load,
+                        destruct.layout,
+                        env.arena.alloc(stmt),
+                        env.region,
+                    );
                 }
                 StorePattern::NotProductive(stmt) => return StorePattern::NotProductive(stmt),
             }
@@ -1768,11 +1794,23 @@ fn store_record_destruct<'a>(
 
     match &destruct.typ {
         DestructType::Required(symbol) => {
-            stmt = Stmt::Let(*symbol, load, destruct.layout, env.arena.alloc(stmt));
+            stmt = Stmt::Let(
+                *symbol,
+                load,
+                destruct.layout,
+                env.arena.alloc(stmt),
+                env.region,
+            );
         }
         DestructType::Guard(guard_pattern) => match &guard_pattern {
             Identifier(symbol) => {
-                stmt = Stmt::Let(*symbol, load, destruct.layout, env.arena.alloc(stmt));
+                stmt = Stmt::Let(
+                    *symbol,
+                    load,
+                    destruct.layout,
+                    env.arena.alloc(stmt),
+                    env.region,
+                );
             }
             Underscore => {
                 // important that this is special-cased to do nothing: mono record patterns will extract all the
@@ -1803,7 +1841,13 @@ fn store_record_destruct<'a>(
                 match store_pattern_help(env, procs, layout_cache, guard_pattern, symbol, stmt) {
                     StorePattern::Productive(new) => {
                         stmt = new;
-                        stmt = Stmt::Let(symbol, load, destruct.layout, env.arena.alloc(stmt));
+                        stmt = Stmt::Let(
+                            symbol,
+                            load,
+                            destruct.layout,
+                            env.arena.alloc(stmt),
+                            env.region,
+                        );
                     }
                     StorePattern::NotProductive(stmt) => return StorePattern::NotProductive(stmt),
                 }
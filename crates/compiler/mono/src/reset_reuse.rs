@@ -18,6 +18,7 @@ use bumpalo::collections::vec::Vec;
 use bumpalo::collections::CollectIn;
 use roc_collections::{MutMap, MutSet};
 use roc_module::symbol::{IdentIds, ModuleId, Symbol};
+use roc_region::all::Region;
 
 /**
  Insert reset and reuse operations into the IR.
@@ -98,13 +99,13 @@ fn insert_reset_reuse_operations_stmt<'a, 'i>(
     stmt: &'a Stmt<'a>,
 ) -> &'a Stmt<'a> {
     match stmt {
-        Stmt::Let(_, _, _, _) => {
+        Stmt::Let(..) => {
             // Collect all the subsequent let bindings (including the current one).
             // To prevent the stack from overflowing when there are many let bindings.
             let mut triples = vec![];
             let mut current_stmt = stmt;
-            while let Stmt::Let(binding, expr, layout, next_stmt) = current_stmt {
-                triples.push((binding, expr, layout));
+            while let Stmt::Let(binding, expr, layout, next_stmt, region) = current_stmt {
+                triples.push((binding, expr, layout, region));
                 current_stmt = next_stmt
             }
 
@@ -113,13 +114,13 @@ fn insert_reset_reuse_operations_stmt<'a, 'i>(
                 "Expected at least one let binding in the vector"
             );
             debug_assert!(
-                !matches!(current_stmt, Stmt::Let(_, _, _, _)),
+                !matches!(current_stmt, Stmt::Let(..)),
                 "All let bindings should be in the vector"
             );
 
             // Update the triplets with reuse operations. Making sure to update the environment before the next let binding.
             let mut new_triplets = vec![];
-            for (binding, expr, layout) in triples {
+            for (binding, expr, layout, region) in triples {
                 let new_expr = match expr {
                     Expr::Tag {
                         tag_layout,
@@ -161,7 +162,7 @@ fn insert_reset_reuse_operations_stmt<'a, 'i>(
                 };
 
                 environment.add_symbol_layout(*binding, layout);
-                new_triplets.push((binding, new_expr, layout))
+                new_triplets.push((binding, new_expr, layout, region))
             }
 
             let new_continuation = insert_reset_reuse_operations_stmt(
@@ -176,8 +177,14 @@ fn insert_reset_reuse_operations_stmt<'a, 'i>(
 
             new_triplets.into_iter().rev().fold(
                 new_continuation,
-                |new_continuation, (binding, new_expr, layout)| {
-                    arena.alloc(Stmt::Let(*binding, new_expr, *layout, new_continuation))
+                |new_continuation, (binding, new_expr, layout, region)| {
+                    arena.alloc(Stmt::Let(
+                        *binding,
+                        new_expr,
+                        *layout,
+                        new_continuation,
+                        *region,
+                    ))
                 },
             )
         }
@@ -187,6 +194,7 @@ fn insert_reset_reuse_operations_stmt<'a, 'i>(
             branches,
             default_branch,
             ret_layout,
+            region,
         } => {
             let new_branches = branches
                 .iter()
@@ -339,6 +347,7 @@ fn insert_reset_reuse_operations_stmt<'a, 'i>(
                 branches: newer_branches,
                 default_branch: newer_default_branch,
                 ret_layout: *ret_layout,
+                region: *region,
             })
         }
         Stmt::Refcounting(rc, continuation) => {
@@ -423,6 +432,7 @@ fn insert_reset_reuse_operations_stmt<'a, 'i>(
                             // TODO not sure what the layout should be for a reset token. Currently it is the layout of the symbol.
                             *layout,
                             new_continuation,
+                            Region::zero(),
                         ));
                     }
                 }
@@ -432,7 +442,7 @@ fn insert_reset_reuse_operations_stmt<'a, 'i>(
 
             arena.alloc(Stmt::Refcounting(*rc, new_continuation))
         }
-        Stmt::Ret(_) => {
+        Stmt::Ret(..) => {
             // The return statement just doesn't consume any tokens. Dropping these tokens will be handled before.
             stmt
         }
@@ -512,6 +522,7 @@ fn insert_reset_reuse_operations_stmt<'a, 'i>(
             parameters,
             body,
             remainder,
+            region,
         } => {
             // First we evaluate the remainder, to see what reuse tokens are available at each jump. We generate code as if no reuse tokens are used.
             // Then we evaluate the body, to see what reuse tokens are consumed by the body.
@@ -674,6 +685,7 @@ fn insert_reset_reuse_operations_stmt<'a, 'i>(
                     parameters,
                     body: first_pass_body,
                     remainder: first_pass_remainder,
+                    region: *region,
                 });
             }
 
@@ -758,6 +770,7 @@ fn insert_reset_reuse_operations_stmt<'a, 'i>(
                     parameters: extended_parameters,
                     body: first_pass_body,
                     remainder: second_pass_remainder,
+                    region: *region,
                 });
             }
 
@@ -804,6 +817,7 @@ fn insert_reset_reuse_operations_stmt<'a, 'i>(
                 parameters: extended_parameters,
                 body: second_pass_body,
                 remainder: second_pass_remainder,
+                region: *region,
             })
         }
         Stmt::Jump(id, arguments) => {
@@ -853,7 +867,13 @@ fn insert_reset_reuse_operations_stmt<'a, 'i>(
                     void_pointer_layout_symbols.into_iter().fold(
                         arena.alloc(Stmt::Jump(*id, extended_arguments)),
                         |child, (layout, symbol)| {
-                            arena.alloc(Stmt::Let(symbol, Expr::NullPointer, layout, child))
+                            arena.alloc(Stmt::Let(
+                                symbol,
+                                Expr::NullPointer,
+                                layout,
+                                child,
+                                Region::zero(),
+                            ))
                         },
                     )
                 }
@@ -894,7 +914,13 @@ fn insert_reset_reuse_operations_stmt<'a, 'i>(
                     void_pointer_layout_symbols.into_iter().fold(
                         arena.alloc(Stmt::Jump(*id, extended_arguments)),
                         |child, (layout, symbol)| {
-                            arena.alloc(Stmt::Let(symbol, Expr::NullPointer, layout, child))
+                            arena.alloc(Stmt::Let(
+                                symbol,
+                                Expr::NullPointer,
+                                layout,
+                                child,
+                                Region::zero(),
+                            ))
                         },
                     )
                 }
@@ -6,6 +6,7 @@ use crate::layout::{InLayout, LambdaName};
 use bumpalo::collections::Vec;
 use bumpalo::Bump;
 use roc_module::symbol::Symbol;
+use roc_region::all::Region;
 
 /// Make tail calls into loops (using join points)
 ///
@@ -63,6 +64,7 @@ pub fn make_tail_recursive<'a>(
         remainder: jump,
         parameters: params,
         body: new_stmt,
+        region: Region::zero(),
     };
 
     Some(join)
@@ -100,7 +102,8 @@ fn insert_jumps<'a>(
                 arguments,
             }),
             _,
-            Stmt::Ret(rsym),
+            Stmt::Ret(rsym, _),
+            _,
         ) if symbol == rsym && is_equal_function(*fsym, arg_layouts, *ret_layout) => {
             // replace the call and return with a jump
 
@@ -109,7 +112,7 @@ fn insert_jumps<'a>(
             Some(arena.alloc(jump))
         }
 
-        Let(symbol, expr, layout, cont) => {
+        Let(symbol, expr, layout, cont, region) => {
             let opt_cont = insert_jumps(
                 arena,
                 cont,
@@ -122,7 +125,7 @@ fn insert_jumps<'a>(
             if opt_cont.is_some() {
                 let cont = opt_cont.unwrap_or(cont);
 
-                Some(arena.alloc(Let(*symbol, expr.clone(), *layout, cont)))
+                Some(arena.alloc(Let(*symbol, expr.clone(), *layout, cont, *region)))
             } else {
                 None
             }
@@ -133,6 +136,7 @@ fn insert_jumps<'a>(
             parameters,
             remainder,
             body: continuation,
+            region,
         } => {
             let opt_remainder = insert_jumps(
                 arena,
@@ -160,6 +164,7 @@ fn insert_jumps<'a>(
                     parameters,
                     remainder,
                     body: continuation,
+                    region: *region,
                 }))
             } else {
                 None
@@ -171,6 +176,7 @@ fn insert_jumps<'a>(
             branches,
             default_branch,
             ret_layout,
+            region,
         } => {
             let opt_default = insert_jumps(
                 arena,
@@ -231,6 +237,7 @@ fn insert_jumps<'a>(
                     default_branch,
                     branches,
                     ret_layout: *ret_layout,
+                    region: *region,
                 }))
             } else {
                 None
@@ -318,7 +325,7 @@ fn insert_jumps<'a>(
             None => None,
         },
 
-        Ret(_) => None,
+        Ret(..) => None,
         Jump(_, _) => None,
         Crash(..) => None,
     }
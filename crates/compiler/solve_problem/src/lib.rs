@@ -56,6 +56,39 @@ impl TypeError {
             TypeError::IngestedFileUnsupportedType(..) => Fatal,
         }
     }
+
+    /// Returns the region this problem is reported at, if there is one.
+    /// Some problems have more than one region; in those cases, this picks
+    /// the one that the report primarily points at.
+    pub fn region(&self) -> Option<Region> {
+        match self {
+            TypeError::BadExpr(region, ..)
+            | TypeError::BadPattern(region, ..)
+            | TypeError::CircularType(region, ..)
+            | TypeError::BadExprMissingAbility(region, ..)
+            | TypeError::BadPatternMissingAbility(region, ..)
+            | TypeError::StructuralSpecialization { region, .. }
+            | TypeError::WrongSpecialization { region, .. }
+            | TypeError::Exhaustive(roc_exhaustive::Error::Incomplete(region, ..))
+            | TypeError::Exhaustive(roc_exhaustive::Error::Redundant {
+                branch_region: region,
+                ..
+            })
+            | TypeError::Exhaustive(roc_exhaustive::Error::Unmatchable {
+                branch_region: region,
+                ..
+            })
+            | TypeError::UnfulfilledAbility(Unfulfilled::OpaqueUnderivable {
+                derive_region: region,
+                ..
+            }) => Some(*region),
+            TypeError::CircularDef(entries) => entries.first().map(|entry| entry.symbol_region),
+            TypeError::UnexposedLookup(_)
+            | TypeError::UnfulfilledAbility(_)
+            | TypeError::IngestedFileBadUtf8(..)
+            | TypeError::IngestedFileUnsupportedType(..) => None,
+        }
+    }
 }
 
 #[derive(PartialEq, Eq, Debug, Clone)]
@@ -1578,7 +1578,6 @@ mod hash {
         }
 
         #[test]
-        #[cfg(not(feature = "gen-wasm"))] // shr not implemented for U128
         fn i128() {
             assert_evals_to!(
                 &build_test("-2i128"),
@@ -1590,7 +1589,6 @@ mod hash {
         }
 
         #[test]
-        #[cfg(not(feature = "gen-wasm"))] // shr not implemented for U128
         fn u128() {
             assert_evals_to!(
                 &build_test("Num.maxU128 - 1"),
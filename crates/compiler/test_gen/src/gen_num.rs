@@ -560,16 +560,12 @@ fn various_sized_abs() {
     assert_evals_to!("Num.abs -6i16", 6, i16);
     assert_evals_to!("Num.abs -6i32", 6, i32);
     assert_evals_to!("Num.abs -6i64", 6, i64);
-    if !cfg!(feature = "gen-wasm") {
-        assert_evals_to!("Num.abs -6i128", 6, i128);
-    }
+    assert_evals_to!("Num.abs -6i128", 6, i128);
     assert_evals_to!("Num.abs 6u8", 6, u8);
     assert_evals_to!("Num.abs 6u16", 6, u16);
     assert_evals_to!("Num.abs 6u32", 6, u32);
     assert_evals_to!("Num.abs 6u64", 6, u64);
-    if !cfg!(feature = "gen-wasm") {
-        assert_evals_to!("Num.abs 6u128", 6, u128);
-    }
+    assert_evals_to!("Num.abs 6u128", 6, u128);
 }
 
 #[test]
@@ -1310,6 +1306,26 @@ fn bitwise_or() {
     assert_evals_to!("Num.bitwiseOr 1 2", 3, i64);
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn bitwise_large_ints() {
+    assert_evals_to!(
+        "Num.bitwiseAnd 0xffff_0000_0000_0000_0000_0000_0000_ffffu128 0x0ff0_0000_0000_0000_0000_0000_0000_0ff0u128",
+        0x0ff0_0000_0000_0000_0000_0000_0000_0ff0u128,
+        u128
+    );
+    assert_evals_to!(
+        "Num.bitwiseXor 0xffff_0000_0000_0000_0000_0000_0000_ffffu128 0x0ff0_0000_0000_0000_0000_0000_0000_0ff0u128",
+        0xf00f_0000_0000_0000_0000_0000_0000_f00fu128,
+        u128
+    );
+    assert_evals_to!(
+        "Num.bitwiseOr -0x1_0000_0000_0000_0000i128 1i128",
+        -0xffff_ffff_ffff_ffffi128,
+        i128
+    );
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn lt_u8() {
@@ -1713,6 +1729,18 @@ fn float_to_float() {
     assert_evals_to!("Num.toFrac 0.5", 0.5, f64);
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn large_int_to_frac() {
+    // above Num.maxI128, so this would be negative if converted as a signed integer
+    assert_evals_to!(
+        "Num.toFrac 0xffff_0000_0000_0000_0000_0000_0000_0000u128",
+        0xffff_0000_0000_0000_0000_0000_0000_0000u128 as f64,
+        f64
+    );
+    assert_evals_to!("Num.toFrac Num.minI128", i128::MIN as f64, f64);
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn int_compare() {
@@ -1721,6 +1749,27 @@ fn int_compare() {
     assert_evals_to!("Num.compare 1 0", RocOrder::Gt, RocOrder);
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn large_int_compare() {
+    assert_evals_to!("Num.compare -1i128 1i128", RocOrder::Lt, RocOrder);
+    assert_evals_to!(
+        "Num.compare Num.maxI128 Num.maxI128",
+        RocOrder::Eq,
+        RocOrder
+    );
+    assert_evals_to!("Num.compare Num.maxU128 1u128", RocOrder::Gt, RocOrder);
+    assert_evals_to!(
+        "0x1_0000_0000_0000_0000u128 > 0xffff_ffff_ffff_ffffu128",
+        true,
+        bool
+    );
+    assert_evals_to!("-0x1_0000_0000_0000_0000i128 < -1i128", true, bool);
+    assert_evals_to!("Num.minI128 <= Num.maxI128", true, bool);
+    assert_evals_to!("1.5dec >= 2.5dec", false, bool);
+    assert_evals_to!("-1.5dec < 0.5dec", true, bool);
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn float_compare() {
@@ -2106,6 +2155,36 @@ fn shift_right_zf_by() {
     assert_evals_to!("Num.shiftRightZfBy 0b1000_0000u8 12", 0b0000_0000u8, u8);
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn shift_large_ints() {
+    assert_evals_to!("Num.shiftLeftBy 1u128 100", 1u128 << 100, u128);
+    assert_evals_to!(
+        "Num.shiftRightBy -0x1_0000_0000_0000_0000i128 4",
+        -0x1000_0000_0000_0000i128,
+        i128
+    );
+    assert_evals_to!("Num.shiftRightBy (Num.shiftLeftBy 1i128 70) 70", 1, i128);
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn div_rem_large_ints() {
+    assert_evals_to!(
+        "Num.divTrunc 0x1_0000_0000_0000_0000u128 3u128",
+        0x5555_5555_5555_5555u128,
+        u128
+    );
+    assert_evals_to!("Num.divTrunc -100i128 7i128", -14, i128);
+    assert_evals_to!("Num.rem -100i128 7i128", -2, i128);
+    assert_evals_to!(
+        "Num.isMultipleOf 0x1_0000_0000_0000_0000u128 16u128",
+        true,
+        bool
+    );
+    assert_evals_to!("Num.isMultipleOf -100i128 7i128", false, bool);
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn shift_right_cast_i8() {
@@ -2487,12 +2566,12 @@ num_conversion_tests! {
     "Num.toI64", i64, (
         to_i64_same_width, "15u64", 15, ["gen-wasm"]
         to_i64_extend, "15i8", 15, ["gen-wasm"]
-        to_i64_truncate, "115i128", 115
-        to_i64_truncate_wraps, "10_000_000_000_000_000_000i128", -8446744073709551616
+        to_i64_truncate, "115i128", 115, ["gen-wasm"]
+        to_i64_truncate_wraps, "10_000_000_000_000_000_000i128", -8446744073709551616, ["gen-wasm"]
     )
     "Num.toI128", i128, (
-        to_i128_same_width, "15u128", 15
-        to_i128_extend, "15i8", 15
+        to_i128_same_width, "15u128", 15, ["gen-wasm"]
+        to_i128_extend, "15i8", 15, ["gen-wasm"]
     )
     "Num.toU8", u8, (
         to_u8_same_width, "15i8", 15, ["gen-wasm"]
@@ -2514,29 +2593,29 @@ num_conversion_tests! {
     "Num.toU64", u64, (
         to_u64_same_width, "15i64", 15, ["gen-wasm"]
         to_u64_extend, "15i8", 15, ["gen-wasm"]
-        to_u64_truncate, "115i128", 115
-        to_u64_truncate_wraps, "10_000_000_000_000_000_000_000i128", 1864712049423024128
+        to_u64_truncate, "115i128", 115, ["gen-wasm"]
+        to_u64_truncate_wraps, "10_000_000_000_000_000_000_000i128", 1864712049423024128, ["gen-wasm"]
     )
     "Num.toU128", u128, (
-        to_u128_same_width, "15i128", 15
-        to_u128_extend, "15i8", 15
+        to_u128_same_width, "15i128", 15, ["gen-wasm"]
+        to_u128_extend, "15i8", 15, ["gen-wasm"]
     )
     "Num.toNat", usize, (
         to_nat_same_width, "15i64", 15, ["gen-wasm"]
         to_nat_extend, "15i8", 15, ["gen-wasm"]
-        to_nat_truncate, "115i128", 115
+        to_nat_truncate, "115i128", 115, ["gen-wasm"]
     )
     "Num.toF32", f32, (
         to_f32_from_i8, "15i8", 15.0
         to_f32_from_i16, "15i16", 15.0
         to_f32_from_i32, "15i32", 15.0
         to_f32_from_i64, "15i64", 15.0
-        to_f32_from_i128, "15i128", 15.0
+        to_f32_from_i128, "15i128", 15.0, ["gen-wasm"]
         to_f32_from_u8, "15u8", 15.0
         to_f32_from_u16, "15u16", 15.0
         to_f32_from_u32, "15u32", 15.0
         to_f32_from_u64, "15u64", 15.0
-        to_f32_from_u128, "15u128", 15.0
+        to_f32_from_u128, "15u128", 15.0, ["gen-wasm"]
         to_f32_from_nat, "15nat", 15.0
         to_f32_from_f32, "1.5f32", 1.5
         to_f32_from_f64, "1.5f64", 1.5
@@ -2546,12 +2625,12 @@ num_conversion_tests! {
         to_f64_from_i16, "15i16", 15.0
         to_f64_from_i32, "15i32", 15.0
         to_f64_from_i64, "15i64", 15.0
-        to_f64_from_i128, "15i128", 15.0
+        to_f64_from_i128, "15i128", 15.0, ["gen-wasm"]
         to_f64_from_u8, "15u8", 15.0
         to_f64_from_u16, "15u16", 15.0
         to_f64_from_u32, "15u32", 15.0
         to_f64_from_u64, "15u64", 15.0
-        to_f64_from_u128, "15u128", 15.0
+        to_f64_from_u128, "15u128", 15.0, ["gen-wasm"]
         to_f64_from_nat, "15nat", 15.0
         to_f64_from_f32, "1.5f32", 1.5
         to_f64_from_f64, "1.5f64", 1.5
@@ -4011,7 +4090,7 @@ fn num_abs_diff_int() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn num_abs_diff_large_bits() {
     assert_evals_to!(r#"Num.absDiff 0u128 0u128"#, 0, u128);
     assert_evals_to!(r#"Num.absDiff 1u128 2u128"#, 1, u128);
@@ -4044,7 +4123,7 @@ fn num_abs_int_min_overflow() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 #[should_panic(expected = r#"Roc failed with message: "integer subtraction overflowed!"#)]
 fn num_abs_large_bits_min_overflow() {
     assert_evals_to!(r#"Num.absDiff Num.minI128 0"#, 0, i128);
@@ -4480,3 +4480,109 @@ fn pass_lambda_set_to_function() {
         i64
     );
 }
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev", feature = "gen-wasm"))]
+fn inline_expect_and_dbg() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" provides [main] to "./platform"
+
+            main =
+                x = 41
+                expect x == 41
+                expect x == 0
+                dbg x
+
+                x + 1
+            "#
+        ),
+        42,
+        i64
+    );
+}
+
+#[test]
+#[cfg(feature = "gen-llvm")]
+fn debug_info_points_into_roc_source() {
+    let ir = crate::helpers::llvm::llvm_ir_with_debug_info(indoc!(
+        r#"
+        app "test" provides [main] to "./platform"
+
+        double : I64 -> I64
+        double = \x ->
+            expect x > 0
+            x * 2
+
+        main = double 21
+        "#
+    ));
+
+    assert!(ir.contains(r#"!DIFile(filename: "Test.roc""#), "{}", ir);
+
+    // `double` is defined on line 4, where its body starts
+    let subprogram = ir
+        .lines()
+        .find(|line| line.contains("!DISubprogram(name: ") && line.contains(".double\""))
+        .unwrap_or_else(|| panic!("no debug info for double in:\n{}", ir));
+
+    assert!(subprogram.contains("line: 4,"), "{}", subprogram);
+
+    // the expect's condition is at line 5, column 12
+    assert!(ir.contains("!DILocation(line: 5, column: 12,"), "{}", ir);
+}
+
+#[test]
+#[cfg(feature = "gen-llvm")]
+fn debug_info_steps_through_statements() {
+    let ir = crate::helpers::llvm::llvm_ir_with_debug_info(indoc!(
+        r#"
+        app "test" provides [main] to "./platform"
+
+        addThenDouble : I64 -> I64
+        addThenDouble = \x ->
+            y = x + 1
+            y * 2
+
+        main = addThenDouble 20
+        "#
+    ));
+
+    // every line of the body gets its own location, not just the line where it starts
+    assert!(ir.contains("!DILocation(line: 5, column: 9,"), "{}", ir);
+    assert!(ir.contains("!DILocation(line: 6, column: 5,"), "{}", ir);
+}
+
+#[test]
+#[cfg(feature = "gen-wasm")]
+fn inline_expect_and_dbg_reach_host() {
+    let src = indoc!(
+        r#"
+        app "test" provides [main] to "./platform"
+
+        main =
+            x = 41
+            expect x == 41
+            expect x == 0
+            dbg x
+
+            x + 1
+        "#
+    );
+    let reports =
+        crate::helpers::wasm::expect_reports_help::<i64>(src, std::marker::PhantomData).unwrap();
+
+    // Only the failing expect is reported, followed by the dbg
+    assert_eq!(reports.len(), 2);
+
+    let (expect, dbg) = (&reports[0], &reports[1]);
+
+    assert!(!expect.is_dbg);
+    assert_eq!(&src[expect.region.clone()], "x == 0");
+    assert_eq!(expect.lookup_i64(0), 41);
+
+    assert!(dbg.is_dbg);
+    assert_eq!(dbg.module_id, expect.module_id);
+    assert_eq!(dbg.lookup_i64(0), 41);
+}
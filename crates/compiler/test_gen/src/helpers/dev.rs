@@ -2,9 +2,10 @@ use libloading::Library;
 use roc_build::link::{link, LinkType};
 use roc_builtins::bitcode;
 use roc_load::{EntryPoint, ExecutionMode, LoadConfig, Threading};
-use roc_mono::ir::SingleEntryPoint;
+use roc_mono::ir::{CrashTag, SingleEntryPoint};
 use roc_packaging::cache::RocCacheDir;
 use roc_region::all::LineInfo;
+use roc_std::RocStr;
 use tempfile::tempdir;
 
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
@@ -190,7 +191,7 @@ pub fn helper(
         module_id,
         exposed_to_host: exposed_to_host.top_level_values.keys().copied().collect(),
         lazy_literals,
-        generate_allocators: true, // Needed for testing, since we don't have a platform
+        mode: roc_gen_dev::AssemblyBackendMode::Test, // Needed for testing, since we don't have a platform
     };
 
     let target = target_lexicon::Triple::host();
@@ -243,6 +244,31 @@ pub fn helper(
     (main_fn_name, delayed_errors, lib)
 }
 
+/// Returns the message and tag of the crash in the last call to the test's `main`, if it crashed.
+#[allow(dead_code)]
+pub fn roc_crash(lib: &Library) -> Option<(String, CrashTag)> {
+    unsafe {
+        let tag: libloading::Symbol<unsafe extern "C" fn() -> u32> = lib
+            .get(bitcode::UTILS_TEST_PANIC_TAG.as_bytes())
+            .expect("the builtins have a test implementation of roc_panic");
+
+        match tag() {
+            0 => None,
+            n => {
+                let msg: libloading::Symbol<unsafe extern "C" fn() -> *const RocStr> = lib
+                    .get(bitcode::UTILS_TEST_PANIC_MSG.as_bytes())
+                    .expect("the builtins have a test implementation of roc_panic");
+                let msg = &*msg();
+                let tag = (n - 1)
+                    .try_into()
+                    .unwrap_or_else(|_| panic!("received illegal tag: {}", n - 1));
+
+                Some((msg.as_str().to_owned(), tag))
+            }
+        }
+    }
+}
+
 #[allow(unused_macros)]
 macro_rules! assert_evals_to {
     ($src:expr, $expected:expr, $ty:ty) => {{
@@ -272,6 +298,18 @@ macro_rules! assert_evals_to {
             $crate::helpers::dev::helper(&arena, $src, $leak, $lazy_literals);
 
         let transform = |success| {
+            if let Some((msg, tag)) = $crate::helpers::dev::roc_crash(&lib) {
+                // the value was never written, so it must not be dropped
+                let _ = std::mem::ManuallyDrop::new(success);
+
+                match tag {
+                    roc_mono::ir::CrashTag::Roc => panic!(r#"Roc failed with message: "{}""#, msg),
+                    roc_mono::ir::CrashTag::User => {
+                        panic!(r#"User crash with message: "{}""#, msg)
+                    }
+                }
+            }
+
             let expected = $expected;
             #[allow(clippy::redundant_closure_call)]
             let given = $transform(success);
@@ -6,6 +6,7 @@ use libloading::Library;
 use roc_build::link::llvm_module_to_dylib;
 use roc_collections::all::MutSet;
 use roc_command_utils::zig;
+use roc_gen_llvm::llvm::debug_info::DebugSources;
 use roc_gen_llvm::llvm::externs::add_default_roc_externs;
 use roc_gen_llvm::{llvm::build::LlvmBackendMode, run_roc::RocCallResult};
 use roc_load::{EntryPoint, ExecutionMode, LoadConfig, LoadMonomorphizedError, Threading};
@@ -51,6 +52,7 @@ fn create_llvm_module<'a>(
     config: HelperConfig,
     context: &'a inkwell::context::Context,
     target: &Triple,
+    emit_debug_info: bool,
 ) -> (&'static str, String, &'a Module<'a>) {
     let target_info = roc_target::TargetInfo::from(target);
 
@@ -77,7 +79,7 @@ fn create_llvm_module<'a>(
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
-        filename,
+        filename.clone(),
         module_src,
         src_dir,
         RocCacheDir::Disallowed,
@@ -107,11 +109,11 @@ fn create_llvm_module<'a>(
     // errors whose reporting we delay (so we can see that code gen generates runtime errors)
     let mut delayed_errors = Vec::new();
 
-    for (home, (module_path, src)) in loaded.sources {
+    for (home, (module_path, src)) in loaded.sources.iter() {
         use roc_reporting::report::{can_problem, type_problem, RocDocAllocator};
 
-        let can_problems = loaded.can_problems.remove(&home).unwrap_or_default();
-        let type_problems = loaded.type_problems.remove(&home).unwrap_or_default();
+        let can_problems = loaded.can_problems.remove(home).unwrap_or_default();
+        let type_problems = loaded.type_problems.remove(home).unwrap_or_default();
 
         let error_count = can_problems.len() + type_problems.len();
 
@@ -119,12 +121,12 @@ fn create_llvm_module<'a>(
             continue;
         }
 
-        let line_info = LineInfo::new(&src);
+        let line_info = LineInfo::new(src);
         let src_lines: Vec<&str> = src.split('\n').collect();
         let palette = DEFAULT_PALETTE;
 
         // Report parsing and canonicalization problems
-        let alloc = RocDocAllocator::new(&src_lines, home, &interns);
+        let alloc = RocDocAllocator::new(&src_lines, *home, &interns);
 
         use roc_problem::can::Problem::*;
         for problem in can_problems.into_iter() {
@@ -184,7 +186,14 @@ fn create_llvm_module<'a>(
     let (module_pass, function_pass) =
         roc_gen_llvm::llvm::build::construct_optimization_passes(module, config.opt_level);
 
-    let (dibuilder, compile_unit) = roc_gen_llvm::llvm::build::Env::new_debug_info(module);
+    let (dibuilder, compile_unit) = if emit_debug_info {
+        // strip Zig debug stuff first, so that only the app's debug info is left
+        module.strip_debug_info();
+
+        roc_gen_llvm::llvm::build::Env::new_debug_info_for_file(module, &filename)
+    } else {
+        roc_gen_llvm::llvm::build::Env::new_debug_info(module)
+    };
 
     // mark our zig-defined builtins as internal
     use inkwell::attributes::{Attribute, AttributeLoc};
@@ -226,10 +235,17 @@ fn create_llvm_module<'a>(
         mode: config.mode,
         // important! we don't want any procedures to get the C calling convention
         exposed_to_host: MutSet::default(),
+        debug_sources: if emit_debug_info {
+            DebugSources::new(&dibuilder, &loaded.sources)
+        } else {
+            DebugSources::default()
+        },
     };
 
     // strip Zig debug stuff
-    module.strip_debug_info();
+    if !emit_debug_info {
+        module.strip_debug_info();
+    }
 
     // Add roc_alloc, roc_realloc, and roc_dealloc, since the repl has no
     // platform to provide them.
@@ -274,7 +290,9 @@ fn create_llvm_module<'a>(
     env.dibuilder.finalize();
 
     // strip all debug info: we don't use it at the moment and causes weird validation issues
-    module.strip_debug_info();
+    if !emit_debug_info {
+        module.strip_debug_info();
+    }
 
     // Uncomment this to see the module's un-optimized LLVM instruction output:
     // env.module.print_to_stderr();
@@ -317,6 +335,24 @@ pub struct HelperConfig {
     pub opt_level: OptLevel,
 }
 
+/// The LLVM IR of an app, with debug info that points into its source
+#[allow(dead_code)]
+pub fn llvm_ir_with_debug_info(src: &str) -> String {
+    let arena = bumpalo::Bump::new();
+    let context = inkwell::context::Context::create();
+    let target = target_lexicon::Triple::host();
+    let config = HelperConfig {
+        mode: LlvmBackendMode::GenTest,
+        ignore_problems: false,
+        add_debug_info: false,
+        opt_level: OptLevel::Development,
+    };
+
+    let (_, _, module) = create_llvm_module(&arena, src, config, &context, &target, true);
+
+    module.print_to_string().to_string()
+}
+
 #[allow(dead_code)]
 #[inline(never)]
 pub fn helper<'a>(
@@ -328,7 +364,7 @@ pub fn helper<'a>(
     let target = target_lexicon::Triple::host();
 
     let (main_fn_name, delayed_errors, module) =
-        create_llvm_module(arena, src, config, context, &target);
+        create_llvm_module(arena, src, config, context, &target, false);
 
     let res_lib = if config.add_debug_info {
         let module = annotate_with_debug_info(module, context);
@@ -418,7 +454,7 @@ fn compile_to_wasm_bytes<'a>(
     let target = wasm32_target_tripple();
 
     let (_main_fn_name, _delayed_errors, llvm_module) =
-        create_llvm_module(arena, src, config, context, &target);
+        create_llvm_module(arena, src, config, context, &target, false);
 
     let content_hash = crate::helpers::src_hash(src);
     let wasm_file = llvm_module_to_wasm_file(&TEMP_DIR, content_hash, llvm_module);
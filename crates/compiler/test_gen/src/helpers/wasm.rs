@@ -184,8 +184,47 @@ where
     run_wasm_test_bytes::<T>(TEST_WRAPPER_NAME, wasm_bytes)
 }
 
+/// A failed expect or a `dbg` that the app reported to the test host, decoded from its frame
+#[derive(Debug)]
+pub struct ExpectReport {
+    pub is_dbg: bool,
+    /// For an expect, the source range of its condition
+    pub region: std::ops::Range<usize>,
+    pub module_id: u32,
+    frame_addr: usize,
+    /// A copy of the app's memory when it made the report, since the frame lives on its stack
+    memory: Vec<u8>,
+}
+
+impl ExpectReport {
+    const HEADER_SIZE: usize = 3 * 4;
+    const LOOKUP_SIZE: usize = 8 + 4;
+
+    fn decode(is_dbg: bool, memory: &[u8], frame_addr: i32) -> Self {
+        let start = read_i32(memory, frame_addr) as usize;
+        let end = read_i32(memory, frame_addr + 4) as usize;
+
+        ExpectReport {
+            is_dbg,
+            region: start..end,
+            module_id: read_i32(memory, frame_addr + 8) as u32,
+            frame_addr: frame_addr as usize,
+            memory: memory.to_vec(),
+        }
+    }
+
+    /// The value of the lookup at this index, which the test knows to be an I64
+    pub fn lookup_i64(&self, index: usize) -> i64 {
+        let entry = self.frame_addr + Self::HEADER_SIZE + Self::LOOKUP_SIZE * index;
+        let value_addr = u64::from_le_bytes(self.memory[entry..][..8].try_into().unwrap());
+
+        i64::from_le_bytes(self.memory[value_addr as usize..][..8].try_into().unwrap())
+    }
+}
+
 struct TestDispatcher<'a> {
     wasi: WasiDispatcher<'a>,
+    reports: Vec<ExpectReport>,
 }
 
 impl<'a> ImportDispatcher for TestDispatcher<'a> {
@@ -208,6 +247,17 @@ impl<'a> ImportDispatcher for TestDispatcher<'a> {
                 tag => format!(r#"Got an invald panic tag: "{}""#, tag),
             };
             panic!("{}", msg)
+        } else if module_name == "env"
+            && (function_name == "send_dbg_to_rust"
+                || function_name == "send_expect_failed_to_rust")
+        {
+            let frame_addr = arguments[0].expect_i32().unwrap();
+            let is_dbg = function_name == "send_dbg_to_rust";
+
+            self.reports
+                .push(ExpectReport::decode(is_dbg, memory, frame_addr));
+
+            None
         } else {
             panic!(
                 "TestDispatcher does not implement {}.{}",
@@ -241,6 +291,7 @@ where
 {
     let dispatcher = TestDispatcher {
         wasi: wasi::WasiDispatcher::default(),
+        reports: Vec::new(),
     };
     let is_debug_mode = roc_debug_flags::dbg_set!(roc_debug_flags::ROC_LOG_WASM_INTERP);
     let mut inst = Instance::for_module(&arena, &module, dispatcher, is_debug_mode)?;
@@ -251,6 +302,35 @@ where
     Ok(output)
 }
 
+/// Run the app, and return the expect failures and `dbg`s it reported, in order
+#[allow(dead_code)]
+pub fn expect_reports_help<T>(
+    src: &str,
+    phantom: PhantomData<T>,
+) -> Result<Vec<ExpectReport>, String>
+where
+    T: FromWasm32Memory + Wasm32Result,
+{
+    let arena = bumpalo::Bump::new();
+
+    let wasm_bytes = crate::helpers::wasm::compile_to_wasm_bytes(&arena, src, phantom);
+
+    let require_relocatable = false;
+    let module = WasmModule::preload(&arena, &wasm_bytes, require_relocatable)
+        .map_err(|e| format!("{:?}", e))?;
+
+    let dispatcher = TestDispatcher {
+        wasi: wasi::WasiDispatcher::default(),
+        reports: Vec::new(),
+    };
+    let is_debug_mode = roc_debug_flags::dbg_set!(roc_debug_flags::ROC_LOG_WASM_INTERP);
+    let mut inst = Instance::for_module(&arena, &module, dispatcher, is_debug_mode)?;
+
+    inst.call_export(TEST_WRAPPER_NAME, [])?;
+
+    Ok(inst.import_dispatcher.reports)
+}
+
 #[allow(dead_code)]
 pub fn assert_wasm_refcounts_help<T>(
     src: &str,
@@ -270,6 +350,7 @@ where
 
     let dispatcher = TestDispatcher {
         wasi: wasi::WasiDispatcher::default(),
+        reports: Vec::new(),
     };
     let is_debug_mode = roc_debug_flags::dbg_set!(roc_debug_flags::ROC_LOG_WASM_INTERP);
     let mut inst = Instance::for_module(&arena, &module, dispatcher, is_debug_mode)?;
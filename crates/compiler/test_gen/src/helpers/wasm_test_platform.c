@@ -134,6 +134,22 @@ void roc_panic(void* msg, unsigned int tag_id)
 
 //--------------------------
 
+extern void send_dbg_to_rust(void* frame);
+
+void roc_dbg(void* frame)
+{
+    send_dbg_to_rust(frame);
+}
+
+extern void send_expect_failed_to_rust(void* frame);
+
+void roc_expect_failed(void* frame)
+{
+    send_expect_failed_to_rust(frame);
+}
+
+//--------------------------
+
 void roc_memcpy(void *dest, const void *src, size_t n)
 {
     memcpy(dest, src, n);
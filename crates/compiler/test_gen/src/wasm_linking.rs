@@ -18,6 +18,7 @@ use roc_mono::ir::{
     UpdateModeId,
 };
 use roc_mono::layout::{LambdaName, Layout, Niche, STLayoutInterner};
+use roc_region::all::Region;
 use roc_wasm_interp::{wasi, ImportDispatcher, Instance, WasiDispatcher};
 use roc_wasm_module::{Value, WasmModule};
 
@@ -117,6 +118,7 @@ fn build_app_mono<'a>(
         ret_layout: int_layout,
         is_self_recursive: SelfRecursive::NotSelfRecursive,
         host_exposed_layouts: HostExposedLayouts::NotHostExposed,
+        region: Region::zero(),
     };
 
     let proc_layout = ProcLayout {
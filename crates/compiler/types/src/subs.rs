@@ -298,6 +298,27 @@ impl Subs {
 
         (tag_names, offset)
     }
+
+    /// Replace every symbol stored in these subs with `f(symbol)`, e.g. to move the symbols of
+    /// deserialized subs over to the module ids of the current compilation.
+    pub fn remap_symbols(&mut self, f: &mut impl FnMut(Symbol) -> Symbol) {
+        for symbol in self.symbol_names.iter_mut() {
+            *symbol = f(*symbol);
+        }
+
+        for Uls(_, symbol, _) in self.unspecialized_lambda_sets.iter_mut() {
+            *symbol = f(*symbol);
+        }
+
+        for content in self.utable.contents_mut() {
+            match content {
+                Content::Alias(symbol, ..) | Content::Structure(FlatType::Apply(symbol, _)) => {
+                    *symbol = f(*symbol);
+                }
+                _ => {}
+            }
+        }
+    }
 }
 
 /// Mapping of variables to [Content::LambdaSet]s containing unspecialized lambda sets depending on
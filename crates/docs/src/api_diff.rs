@@ -0,0 +1,553 @@
+//! Compares the exposed API of two versions of a package, to tell whether upgrading from one to
+//! the other could break code that depends on it.
+//!
+//! This works on the type annotations from the docs, not on solved types, so it's conservative:
+//! any change to an annotation counts as breaking, even one (like adding a tag to a tag union
+//! that's only ever returned) which callers couldn't actually notice. Renaming type variables
+//! doesn't count as a change, though. Exposed values without annotations can only be checked
+//! for whether they still exist.
+use roc_load::docs::{DocEntry, ModuleDocumentation, RecordField, TypeAnnotation};
+use roc_load::LoadedModule;
+use std::collections::BTreeMap;
+
+/// Everything a package exposes, by module name and then by name within the module.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Api {
+    modules: BTreeMap<String, BTreeMap<String, Item>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Item {
+    /// A value, along with its annotation (if it has one we can compare), e.g. `len : Str -> Nat`
+    Value(Option<String>),
+    /// A type alias, e.g. `Pair a : (a, a)`
+    Alias { header: String, definition: String },
+    /// Only the name and type variables of an opaque type are part of the API, e.g. `Dict k v`.
+    /// (The same goes for an alias of types which aren't exposed.)
+    Opaque { header: String },
+    /// An ability and the signature of each of its members
+    Ability {
+        header: String,
+        members: BTreeMap<String, String>,
+    },
+}
+
+impl Item {
+    /// How this item would appear in the package's docs, more or less
+    fn describe(&self, name: &str) -> String {
+        match self {
+            Item::Value(Some(ann)) => format!("{} : {}", name, ann),
+            Item::Value(None) => name.to_string(),
+            Item::Alias { header, definition } => format!("{} : {}", header, definition),
+            Item::Opaque { header } => header.clone(),
+            Item::Ability { header, members } => {
+                let members: Vec<String> = members
+                    .iter()
+                    .map(|(member, ann)| format!("{} : {}", member, ann))
+                    .collect();
+
+                format!("{} has [{}]", header, members.join(", "))
+            }
+        }
+    }
+}
+
+impl Api {
+    /// The API of every module the loaded package exposes
+    pub fn from_loaded(loaded_module: &LoadedModule) -> Self {
+        let mut api = Api::default();
+
+        for module_docs in loaded_module.docs_by_module.values() {
+            api.add_module(module_docs, |symbol| {
+                symbol.as_str(&loaded_module.interns).to_string()
+            });
+        }
+
+        api
+    }
+
+    fn add_module(
+        &mut self,
+        module_docs: &ModuleDocumentation,
+        symbol_name: impl Fn(roc_module::symbol::Symbol) -> String,
+    ) {
+        let items = self.modules.entry(module_docs.name.clone()).or_default();
+
+        for entry in module_docs.entries.iter() {
+            if let DocEntry::DocDef(doc_def) = entry {
+                if !module_docs.exposed_symbols.contains(&doc_def.symbol) {
+                    continue;
+                }
+
+                let item = to_item(
+                    &doc_def.name,
+                    &doc_def.type_vars,
+                    &doc_def.type_annotation,
+                    is_type_name(&doc_def.name),
+                );
+
+                items.insert(doc_def.name.clone(), item);
+            }
+        }
+
+        // Exposed values without annotations don't get docs entries, but they're still API.
+        for symbol in module_docs.exposed_symbols.iter() {
+            items
+                .entry(symbol_name(*symbol))
+                .or_insert(Item::Value(None));
+        }
+    }
+
+    pub fn insert(&mut self, module_name: &str, name: &str, item: Item) {
+        self.modules
+            .entry(module_name.to_string())
+            .or_default()
+            .insert(name.to_string(), item);
+    }
+}
+
+/// Types and abilities are capitalized; values aren't.
+fn is_type_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_uppercase())
+}
+
+fn to_item(name: &str, type_vars: &[String], ann: &TypeAnnotation, is_type: bool) -> Item {
+    let mut names = VarNames::default();
+    let header = if type_vars.is_empty() {
+        name.to_string()
+    } else {
+        let vars: Vec<String> = type_vars
+            .iter()
+            .map(|var| names.get(var).to_string())
+            .collect();
+
+        format!("{} {}", name, vars.join(" "))
+    };
+
+    match ann {
+        TypeAnnotation::Ability { members } => Item::Ability {
+            header,
+            members: members
+                .iter()
+                .map(|member| {
+                    // Each member's type variables are independent of the other members'.
+                    let mut names = names.clone();
+                    let mut buf = String::new();
+
+                    push_type(&mut buf, &member.type_annotation, false, &mut names);
+
+                    for (index, (var, abilities)) in member.able_variables.iter().enumerate() {
+                        buf.push_str(if index == 0 { " | " } else { ", " });
+                        buf.push_str(names.get(var));
+                        buf.push_str(" has ");
+
+                        for (index, ability) in abilities.iter().enumerate() {
+                            if index > 0 {
+                                buf.push_str(" & ");
+                            }
+
+                            push_type(&mut buf, ability, false, &mut names);
+                        }
+                    }
+
+                    (member.name.clone(), buf)
+                })
+                .collect(),
+        },
+        // The docs leave out the definitions of opaque types, and of aliases which refer to
+        // types that aren't exposed.
+        TypeAnnotation::NoTypeAnn if is_type => Item::Opaque { header },
+        TypeAnnotation::NoTypeAnn => Item::Value(None),
+        _ if is_type => {
+            let mut definition = String::new();
+
+            push_type(&mut definition, ann, false, &mut names);
+
+            Item::Alias { header, definition }
+        }
+        _ => {
+            let mut buf = String::new();
+
+            push_type(&mut buf, ann, false, &mut names);
+
+            Item::Value(Some(buf))
+        }
+    }
+}
+
+/// Renames type variables in order of appearance, so `a -> b` and `x -> y` look the same.
+#[derive(Default, Clone)]
+struct VarNames {
+    names: Vec<(String, String)>,
+}
+
+impl VarNames {
+    fn get(&mut self, var: &str) -> &str {
+        let index = match self.names.iter().position(|(original, _)| original == var) {
+            Some(index) => index,
+            None => {
+                let renamed = var_name(self.names.len());
+
+                self.names.push((var.to_string(), renamed));
+
+                self.names.len() - 1
+            }
+        };
+
+        &self.names[index].1
+    }
+}
+
+/// a, b, ..., z, a1, b1, ...
+fn var_name(index: usize) -> String {
+    let letter = (b'a' + (index % 26) as u8) as char;
+
+    match index / 26 {
+        0 => letter.to_string(),
+        n => format!("{}{}", letter, n),
+    }
+}
+
+/// Write the annotation on one line, in roughly the syntax it was written in.
+fn push_type(buf: &mut String, ann: &TypeAnnotation, needs_parens: bool, names: &mut VarNames) {
+    match ann {
+        TypeAnnotation::TagUnion { tags, extension } => {
+            buf.push('[');
+
+            for (index, tag) in tags.iter().enumerate() {
+                if index > 0 {
+                    buf.push_str(", ");
+                }
+
+                buf.push_str(&tag.name);
+
+                for value in tag.values.iter() {
+                    buf.push(' ');
+                    push_type(buf, value, true, names);
+                }
+            }
+
+            buf.push(']');
+            push_type(buf, extension, true, names);
+        }
+        TypeAnnotation::Function { args, output } => {
+            if needs_parens {
+                buf.push('(');
+            }
+
+            for (index, arg) in args.iter().enumerate() {
+                if index > 0 {
+                    buf.push_str(", ");
+                }
+
+                push_type(
+                    buf,
+                    arg,
+                    matches!(arg, TypeAnnotation::Function { .. }),
+                    names,
+                );
+            }
+
+            buf.push_str(" -> ");
+            push_type(buf, output, false, names);
+
+            if needs_parens {
+                buf.push(')');
+            }
+        }
+        TypeAnnotation::ObscuredTagUnion => buf.push_str("[@..]"),
+        TypeAnnotation::ObscuredRecord => buf.push_str("{ @.. }"),
+        TypeAnnotation::BoundVariable(var) => buf.push_str(names.get(var)),
+        TypeAnnotation::Apply { name, parts } => {
+            let parens = needs_parens && !parts.is_empty();
+
+            if parens {
+                buf.push('(');
+            }
+
+            buf.push_str(name);
+
+            for part in parts.iter() {
+                buf.push(' ');
+                push_type(buf, part, true, names);
+            }
+
+            if parens {
+                buf.push(')');
+            }
+        }
+        TypeAnnotation::Record { fields, extension } => {
+            if fields.is_empty() {
+                buf.push_str("{}");
+            } else {
+                buf.push_str("{ ");
+
+                for (index, field) in fields.iter().enumerate() {
+                    if index > 0 {
+                        buf.push_str(", ");
+                    }
+
+                    match field {
+                        RecordField::RecordField {
+                            name,
+                            type_annotation,
+                        } => {
+                            buf.push_str(name);
+                            buf.push_str(" : ");
+                            push_type(buf, type_annotation, false, names);
+                        }
+                        RecordField::OptionalField {
+                            name,
+                            type_annotation,
+                        } => {
+                            buf.push_str(name);
+                            buf.push_str(" ? ");
+                            push_type(buf, type_annotation, false, names);
+                        }
+                        RecordField::LabelOnly { name } => buf.push_str(name),
+                    }
+                }
+
+                buf.push_str(" }");
+            }
+
+            push_type(buf, extension, true, names);
+        }
+        TypeAnnotation::Ability { .. } => {
+            // Abilities are only ever at the top level of an annotation, where to_item handles them.
+        }
+        TypeAnnotation::Wildcard => buf.push('*'),
+        TypeAnnotation::NoTypeAnn => {}
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    ModuleAdded,
+    ModuleRemoved,
+    Added(String),
+    Removed(String),
+    /// The old and new descriptions of something which changed in a way that could break callers
+    Changed {
+        old: String,
+        new: String,
+    },
+    /// An exposed value which isn't annotated in one of the versions, so its type couldn't be
+    /// compared. This doesn't break anything (as far as we can tell) but is worth pointing out.
+    Unannotated(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiChange {
+    pub module_name: String,
+    /// None for changes to the module as a whole
+    pub item: Option<String>,
+    pub change: Change,
+}
+
+impl ApiChange {
+    pub fn is_breaking(&self) -> bool {
+        matches!(
+            self.change,
+            Change::ModuleRemoved | Change::Removed(_) | Change::Changed { .. }
+        )
+    }
+
+    /// Anything that's not breaking, but still makes a difference to what's exposed
+    pub fn is_additive(&self) -> bool {
+        matches!(self.change, Change::ModuleAdded | Change::Added(_))
+    }
+
+    /// e.g. `Json.toUtf8`
+    pub fn qualified_name(&self) -> String {
+        match &self.item {
+            Some(item) => format!("{}.{}", self.module_name, item),
+            None => self.module_name.clone(),
+        }
+    }
+}
+
+/// Every difference between the two APIs, in module and then item order.
+pub fn diff(old: &Api, new: &Api) -> Vec<ApiChange> {
+    let mut changes = Vec::new();
+
+    let module_names = {
+        let mut names: Vec<&String> = old.modules.keys().chain(new.modules.keys()).collect();
+
+        names.sort();
+        names.dedup();
+
+        names
+    };
+
+    for module_name in module_names {
+        let (old_items, new_items) =
+            match (old.modules.get(module_name), new.modules.get(module_name)) {
+                (Some(old_items), Some(new_items)) => (old_items, new_items),
+                (Some(_), None) => {
+                    changes.push(ApiChange {
+                        module_name: module_name.clone(),
+                        item: None,
+                        change: Change::ModuleRemoved,
+                    });
+
+                    continue;
+                }
+                (None, Some(_)) => {
+                    changes.push(ApiChange {
+                        module_name: module_name.clone(),
+                        item: None,
+                        change: Change::ModuleAdded,
+                    });
+
+                    continue;
+                }
+                (None, None) => unreachable!(),
+            };
+
+        let mut names: Vec<&String> = old_items.keys().chain(new_items.keys()).collect();
+
+        names.sort();
+        names.dedup();
+
+        for name in names {
+            let change = match (old_items.get(name), new_items.get(name)) {
+                (Some(old_item), Some(new_item)) => match (old_item, new_item) {
+                    _ if old_item == new_item => continue,
+                    (Item::Value(None), Item::Value(_)) | (Item::Value(_), Item::Value(None)) => {
+                        Change::Unannotated(new_item.describe(name))
+                    }
+                    _ => Change::Changed {
+                        old: old_item.describe(name),
+                        new: new_item.describe(name),
+                    },
+                },
+                (Some(old_item), None) => Change::Removed(old_item.describe(name)),
+                (None, Some(new_item)) => Change::Added(new_item.describe(name)),
+                (None, None) => unreachable!(),
+            };
+
+            changes.push(ApiChange {
+                module_name: module_name.clone(),
+                item: Some(name.clone()),
+                change,
+            });
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod test_api_diff {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn apply(name: &str, parts: Vec<TypeAnnotation>) -> TypeAnnotation {
+        TypeAnnotation::Apply {
+            name: name.to_string(),
+            parts,
+        }
+    }
+
+    fn var(name: &str) -> TypeAnnotation {
+        TypeAnnotation::BoundVariable(name.to_string())
+    }
+
+    fn function(args: Vec<TypeAnnotation>, output: TypeAnnotation) -> TypeAnnotation {
+        TypeAnnotation::Function {
+            args,
+            output: Box::new(output),
+        }
+    }
+
+    #[test]
+    fn renaming_type_variables_is_not_a_change() {
+        let map = |a: &str, b: &str| {
+            to_item(
+                "map",
+                &[],
+                &function(
+                    vec![apply("List", vec![var(a)]), function(vec![var(a)], var(b))],
+                    apply("List", vec![var(b)]),
+                ),
+                false,
+            )
+        };
+
+        assert_eq!(
+            map("elem", "result"),
+            Item::Value(Some("List a, (a -> b) -> List b".to_string()))
+        );
+        assert_eq!(map("x", "y"), map("elem", "result"));
+        assert_ne!(map("x", "x"), map("x", "y"));
+    }
+
+    #[test]
+    fn opaque_types_only_expose_their_header() {
+        assert_eq!(
+            to_item(
+                "Dict",
+                &["k".to_string(), "v".to_string()],
+                &TypeAnnotation::NoTypeAnn,
+                true
+            ),
+            Item::Opaque {
+                header: "Dict a b".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn classifies_changes() {
+        let mut old = Api::default();
+        let mut new = Api::default();
+
+        old.insert("Str", "len", Item::Value(Some("Str -> Nat".to_string())));
+        new.insert("Str", "len", Item::Value(Some("Str -> U64".to_string())));
+        old.insert("Str", "trim", Item::Value(None));
+        new.insert("Str", "trim", Item::Value(Some("Str -> Str".to_string())));
+        old.insert("Str", "gone", Item::Value(None));
+        // Unannotated in both versions, so there are no types to compare.
+        old.insert("Str", "same", Item::Value(None));
+        new.insert("Str", "same", Item::Value(None));
+        new.insert(
+            "Str",
+            "isEmpty",
+            Item::Value(Some("Str -> Bool".to_string())),
+        );
+        old.insert("Old", "x", Item::Value(None));
+        new.insert("New", "x", Item::Value(None));
+
+        let changes = diff(&old, &new);
+        let summary: Vec<(String, bool, bool)> = changes
+            .iter()
+            .map(|change| {
+                (
+                    change.qualified_name(),
+                    change.is_breaking(),
+                    change.is_additive(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("New".to_string(), false, true),
+                ("Old".to_string(), true, false),
+                ("Str.gone".to_string(), true, false),
+                ("Str.isEmpty".to_string(), false, true),
+                ("Str.len".to_string(), true, false),
+                ("Str.trim".to_string(), false, false),
+            ]
+        );
+        assert_eq!(
+            changes[4].change,
+            Change::Changed {
+                old: "len : Str -> Nat".to_string(),
+                new: "len : Str -> U64".to_string()
+            }
+        );
+    }
+}
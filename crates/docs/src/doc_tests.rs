@@ -0,0 +1,344 @@
+//! Turns the examples in doc comments into top-level `expect`s, so that `roc test --doc` and
+//! `roc docs --check` can tell us when an example no longer compiles (or no longer holds).
+//!
+//! A fenced code block counts as an example if it's Roc code (its info string is empty or `roc`),
+//! it isn't marked `unchecked`, and it has at least one top-level `expect` in it. Other code
+//! blocks, like `Str.countGraphemes "🕊"`, are just for illustration.
+//!
+//! Each example becomes the body of its own top-level `expect`, appended to a copy of the module
+//! it's documenting, so it sees everything that module has in scope. The copies live in a
+//! temporary directory next to the package, so relative paths to other packages still resolve.
+use pulldown_cmark::{CodeBlockKind, Event, Tag};
+use roc_load::docs::DocEntry;
+use roc_load::LoadedModule;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use walkdir::WalkDir;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocTest {
+    /// e.g. "Json"
+    pub module_name: String,
+    /// The def whose doc comment this example is in, or None for module-level docs
+    pub item: Option<String>,
+    /// Which of the examples in this doc comment this is, starting at 1
+    pub index: usize,
+    /// The file the doc comment is in
+    pub path: PathBuf,
+    /// The 1-based line of the example's opening fence, if we could find it in the file
+    pub line: Option<u32>,
+    pub code: String,
+}
+
+impl DocTest {
+    /// e.g. `src/Json.roc#toUtf8`, or `src/Json.roc#toUtf8[2]` for its second example
+    pub fn name(&self) -> String {
+        let item = self.item.as_deref().unwrap_or("module");
+
+        if self.index == 1 {
+            format!("{}#{}", self.path.display(), item)
+        } else {
+            format!("{}#{}[{}]", self.path.display(), item, self.index)
+        }
+    }
+}
+
+/// Find the examples in the docs of every module the package exposes, in source order.
+pub fn doc_tests(loaded_module: &LoadedModule) -> Vec<DocTest> {
+    let mut answer = Vec::new();
+
+    for (module_id, module_docs) in loaded_module.docs_by_module.iter() {
+        let (path, src) = match loaded_module.sources.get(module_id) {
+            Some(source) => source,
+            None => continue,
+        };
+        let mut search_from = 0;
+
+        for entry in module_docs.entries.iter() {
+            let (item, docs) = match entry {
+                DocEntry::DocDef(doc_def) => match &doc_def.docs {
+                    Some(docs) => (Some(doc_def.name.clone()), docs),
+                    None => continue,
+                },
+                DocEntry::DetachedDoc(docs) => (None, docs),
+            };
+
+            for (index, code) in examples(docs).into_iter().enumerate() {
+                let line = match find_example(src, &code, search_from) {
+                    Some(line_index) => {
+                        search_from = line_index + 1;
+
+                        Some(line_index as u32)
+                    }
+                    None => None,
+                };
+
+                answer.push(DocTest {
+                    module_name: module_docs.name.clone(),
+                    item: item.clone(),
+                    index: index + 1,
+                    path: path.clone(),
+                    line,
+                    code,
+                });
+            }
+        }
+    }
+
+    answer
+}
+
+/// The contents of each fenced code block in this markdown which is a checkable example.
+fn examples(markdown: &str) -> Vec<String> {
+    let mut answer = Vec::new();
+    let mut current: Option<String> = None;
+
+    for event in pulldown_cmark::Parser::new(markdown) {
+        match event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) if is_roc_example(&info) => {
+                current = Some(String::new());
+            }
+            Event::Text(text) => {
+                if let Some(code) = current.as_mut() {
+                    code.push_str(&text);
+                }
+            }
+            Event::End(Tag::CodeBlock(_)) => {
+                if let Some(code) = current.take() {
+                    if code.lines().any(is_toplevel_expect) {
+                        answer.push(code);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    answer
+}
+
+/// The info string is what follows the opening fence, e.g. "roc" in ```` ```roc ````
+fn is_roc_example(info: &str) -> bool {
+    let mut words = info.split(|c: char| c == ',' || c.is_whitespace());
+
+    matches!(words.next(), None | Some("") | Some("roc"))
+        && !info.contains("unchecked")
+        && !info.contains("repl")
+}
+
+fn is_toplevel_expect(line: &str) -> bool {
+    line == "expect" || line.starts_with("expect ")
+}
+
+/// Doc comments don't remember where they came from, so find the example in the module's source,
+/// starting from the given (0-based) line. Returns the 1-based line of the opening fence.
+fn find_example(src: &str, code: &str, search_from: usize) -> Option<usize> {
+    let doc_lines: Vec<Option<&str>> = src
+        .lines()
+        .map(|line| {
+            line.trim_start().strip_prefix("##").map(|content| {
+                // Doc comments are usually written `## like this`, and the space isn't content.
+                content.strip_prefix(' ').unwrap_or(content)
+            })
+        })
+        .collect();
+    let code_lines: Vec<&str> = code.lines().collect();
+
+    (search_from..doc_lines.len()).find_map(|fence| {
+        let opens_example =
+            matches!(doc_lines[fence], Some(line) if line.trim_start().starts_with("```"));
+        let has_code = code_lines.iter().enumerate().all(|(offset, code_line)| {
+            matches!(doc_lines.get(fence + 1 + offset), Some(Some(line)) if line == code_line)
+        });
+
+        if opens_example && has_code {
+            Some(fence + 1)
+        } else {
+            None
+        }
+    })
+}
+
+/// Turn an example into a top-level expect. If the example ends in an `expect`, that becomes
+/// the result of the top-level expect; otherwise the example's own `expect`s do the checking.
+///
+/// Example:
+///
+/// ```text
+/// x = 5                   expect
+///                             x = 5
+/// expect x + 1 == 6   =>
+///                             x + 1 == 6
+/// ```
+fn to_toplevel_expect(test: &DocTest) -> String {
+    let mut lines: Vec<String> = test.code.lines().map(str::to_string).collect();
+
+    while matches!(lines.last(), Some(line) if line.trim().is_empty()) {
+        lines.pop();
+    }
+
+    let last_expect = lines.iter().rposition(|line| is_toplevel_expect(line));
+    let ends_in_expect = match last_expect {
+        Some(index) => {
+            // Anything after the last expect must be a continuation of it (so, indented).
+            lines[index].len() > "expect ".len()
+                && lines[index + 1..]
+                    .iter()
+                    .all(|line| line.trim().is_empty() || line.starts_with(' '))
+        }
+        None => false,
+    };
+
+    if ends_in_expect {
+        let index = last_expect.unwrap();
+
+        lines[index] = lines[index]["expect ".len()..].to_string();
+    } else {
+        lines.push("Bool.true".to_string());
+    }
+
+    let mut buf = format!("\n# Example from {}\nexpect\n", test.name());
+
+    for line in lines {
+        if !line.trim().is_empty() {
+            buf.push_str("    ");
+            buf.push_str(&line);
+        }
+
+        buf.push('\n');
+    }
+
+    buf
+}
+
+/// A copy of a package with its examples appended to its modules as top-level expects.
+/// The copy is deleted when this is dropped.
+pub struct DocTestPackage {
+    dir: TempDir,
+    root_dir: PathBuf,
+    /// The copy of the package's root module; check or test this.
+    pub root_file: PathBuf,
+    /// Which lines of which copied file each example's expect occupies (0-based, end exclusive)
+    examples: Vec<(PathBuf, u32, u32, DocTest)>,
+}
+
+impl DocTestPackage {
+    /// Copy the package that `root_file` is the root module of, with the given examples added.
+    pub fn new(root_file: &Path, tests: Vec<DocTest>) -> io::Result<Self> {
+        let root_file = root_file.canonicalize()?;
+        let root_dir = root_file.parent().unwrap().to_path_buf();
+        let parent_dir = root_dir.parent().unwrap_or(&root_dir);
+        let dir = tempfile::Builder::new()
+            .prefix(".roc-doc-tests-")
+            .tempdir_in(parent_dir)?;
+
+        // Only .roc files are needed to typecheck and test, so don't bother with anything else.
+        for entry in WalkDir::new(&root_dir).into_iter().filter_entry(|entry| {
+            entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.')
+        }) {
+            let entry = entry?;
+            let relative = entry.path().strip_prefix(&root_dir).unwrap();
+            let dest = dir.path().join(relative);
+
+            if entry.file_type().is_dir() {
+                fs::create_dir_all(dest)?;
+            } else if entry.path().extension().map_or(false, |ext| ext == "roc") {
+                fs::copy(entry.path(), dest)?;
+            }
+        }
+
+        let mut examples = Vec::with_capacity(tests.len());
+
+        for test in tests {
+            let relative = match test.path.canonicalize()?.strip_prefix(&root_dir) {
+                Ok(relative) => relative.to_path_buf(),
+                // This module isn't part of the package, so it wasn't copied.
+                Err(_) => continue,
+            };
+            let dest = dir.path().join(relative);
+            let mut src = fs::read_to_string(&dest)?;
+
+            if !src.ends_with('\n') {
+                src.push('\n');
+            }
+
+            let expect = to_toplevel_expect(&test);
+            let start = src.lines().count() as u32;
+            let end = start + expect.lines().count() as u32;
+
+            src.push_str(&expect);
+            fs::write(&dest, src)?;
+
+            examples.push((dest, start, end, test));
+        }
+
+        Ok(Self {
+            root_file: dir.path().join(root_file.file_name().unwrap()),
+            root_dir,
+            dir,
+            examples,
+        })
+    }
+
+    /// The example whose expect is at this (0-based) line of this copied file, if any.
+    pub fn example_at(&self, file: &Path, line: u32) -> Option<&DocTest> {
+        self.examples
+            .iter()
+            .find(|(path, start, end, _)| path == file && (*start..*end).contains(&line))
+            .map(|(_, _, _, test)| test)
+    }
+
+    /// The original of a copied file, so problems can be reported where the user can fix them.
+    pub fn original_path(&self, file: &Path) -> PathBuf {
+        match file.strip_prefix(self.dir.path()) {
+            Ok(relative) => self.root_dir.join(relative),
+            Err(_) => file.to_path_buf(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_doc_tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn doc_test(code: &str) -> DocTest {
+        DocTest {
+            module_name: "Foo".to_string(),
+            item: Some("bar".to_string()),
+            index: 1,
+            path: PathBuf::from("Foo.roc"),
+            line: None,
+            code: code.to_string(),
+        }
+    }
+
+    #[test]
+    fn only_roc_blocks_with_expects_are_examples() {
+        let markdown = "Some prose.\n\n```\nStr.countGraphemes \"🕊\"\n```\n\n```roc\nx = 5\nexpect x == 5\n```\n\n```unchecked\nexpect 1 == 2\n```\n\n```python\nexpect = True\n```\n";
+
+        assert_eq!(examples(markdown), vec!["x = 5\nexpect x == 5\n"]);
+    }
+
+    #[test]
+    fn trailing_expect_becomes_the_result() {
+        assert_eq!(
+            to_toplevel_expect(&doc_test("x = 5\n\nexpect\n    x\n    == 5\n")),
+            "\n# Example from Foo.roc#bar\nexpect\n    x = 5\n\n    expect\n        x\n        == 5\n    Bool.true\n"
+        );
+        assert_eq!(
+            to_toplevel_expect(&doc_test("x = 5\n\nexpect x\n    == 5\n")),
+            "\n# Example from Foo.roc#bar\nexpect\n    x = 5\n\n    x\n        == 5\n"
+        );
+    }
+
+    #[test]
+    fn finds_examples_in_the_source() {
+        let src = "interface Foo\n    exposes [bar]\n    imports []\n\n## Example:\n##\n## ```\n## expect bar == 1\n## ```\nbar = 1\n";
+
+        assert_eq!(find_example(src, "expect bar == 1\n", 0), Some(7));
+        assert_eq!(find_example(src, "expect bar == 2\n", 0), None);
+    }
+}
@@ -2,33 +2,89 @@
 //! [roc-lang.org/builtins/Num](https://www.roc-lang.org/builtins/Num).
 extern crate pulldown_cmark;
 extern crate roc_load;
+
+pub mod api_diff;
+pub mod doc_tests;
+
 use bumpalo::Bump;
 use roc_can::scope::Scope;
-use roc_collections::VecSet;
+use roc_collections::{VecMap, VecSet};
 use roc_load::docs::{DocEntry, TypeAnnotation};
 use roc_load::docs::{ModuleDocumentation, RecordField};
 use roc_load::{ExecutionMode, LoadConfig, LoadedModule, LoadingProblem, Threading};
-use roc_module::symbol::{Interns, Symbol};
+use roc_module::symbol::{ModuleId, Symbol};
 use roc_packaging::cache::{self, RocCacheDir};
+use roc_packaging::manifest::{Manifest, MANIFEST_FILENAME};
+use roc_parse::ast::Header;
 use roc_parse::ident::{parse_ident, Accessor, Ident};
+use roc_parse::module::parse_header;
 use roc_parse::state::State;
 use roc_region::all::Region;
+use serde::Serialize;
 use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
 
-const BUILD_DIR: &str = "./generated-docs";
+pub const BUILD_DIR: &str = "./generated-docs";
+
+/// The name of the machine-readable dump that `emit_json` writes into the output dir.
+pub const JSON_FILENAME: &str = "docs.json";
+
+/// Every package's root module has this name.
+const PACKAGE_ROOT_FILENAME: &str = "main.roc";
 
 const LINK_SVG: &str = include_str!("./static/link.svg");
 
-pub fn generate_docs_html(root_file: PathBuf) {
-    let build_dir = Path::new(BUILD_DIR);
-    let loaded_module = load_module_for_docs(root_file);
+pub struct DocsOptions {
+    /// Where to write the docs. Anything already in this directory gets deleted first!
+    pub output_dir: PathBuf,
+    /// The version to display. If this is omitted, we look for the version in the
+    /// `roc-package.json` manifest next to the root module (which `roc package` generates).
+    pub version: Option<String>,
+    /// The URL under which other packages' docs live, e.g. "/" for links like `/json/Decode#fromBytes`.
+    /// This is how we link to types and values from the packages this one depends on.
+    pub packages_url: String,
+    /// Write a JSON dump of every exposed module's documentation alongside the HTML.
+    pub emit_json: bool,
+}
+
+impl Default for DocsOptions {
+    fn default() -> Self {
+        Self {
+            output_dir: PathBuf::from(BUILD_DIR),
+            version: None,
+            packages_url: "/".to_string(),
+            emit_json: false,
+        }
+    }
+}
 
-    // TODO get these from the platform's source file rather than hardcoding them!
-    let package_name = "Documentation".to_string();
-    let version = String::new();
+/// What the links in a module's docs need in order to resolve.
+struct LinkContext<'a> {
+    loaded_module: &'a LoadedModule,
+    all_exposed_symbols: &'a VecSet<Symbol>,
+    /// The package names of modules which came from dependencies rather than the root package
+    dependency_packages: &'a VecMap<ModuleId, String>,
+    packages_url: &'a str,
+}
 
-    // Clear out the generated-docs dir (we'll create a fresh one at the end)
+pub fn generate_docs_html(root_file: PathBuf, options: &DocsOptions) {
+    let build_dir = options.output_dir.as_path();
+    let loaded_module = load_module_for_docs(root_file.clone());
+
+    let package_name = read_package_name(&root_file).unwrap_or_else(|| {
+        // Apps and interface modules don't have a package name, so use the file's name instead.
+        root_file
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Documentation".to_string())
+    });
+    let version = match &options.version {
+        Some(version) => version.clone(),
+        None => read_manifest_version(&root_file).unwrap_or_default(),
+    };
+
+    // Clear out the output dir (we'll create a fresh one at the end)
     if build_dir.exists() {
         fs::remove_dir_all(build_dir)
             .expect("TODO gracefully handle being unable to delete build dir");
@@ -87,6 +143,14 @@ pub fn generate_docs_html(root_file: PathBuf) {
         set
     };
 
+    let dependency_packages = dependency_packages(&root_file, &loaded_module);
+    let link_ctx = LinkContext {
+        loaded_module: &loaded_module,
+        all_exposed_symbols: &all_exposed_symbols,
+        dependency_packages: &dependency_packages,
+        packages_url: options.packages_url.as_str(),
+    };
+
     // Write each package's module docs html file
     for module_docs in loaded_module.docs_by_module.values() {
         let module_name = module_docs.name.as_str();
@@ -106,17 +170,121 @@ pub fn generate_docs_html(root_file: PathBuf) {
             )
             .replace(
                 "<!-- Module Docs -->",
-                render_module_documentation(module_docs, &loaded_module, &all_exposed_symbols)
-                    .as_str(),
+                render_module_documentation(module_docs, &link_ctx).as_str(),
             );
 
         fs::write(module_dir.join("index.html"), rendered_module)
             .expect("TODO gracefully handle failing to write index.html inside module's dir");
     }
 
+    if options.emit_json {
+        let mut modules: Vec<ModuleJson> = loaded_module
+            .docs_by_module
+            .values()
+            .map(|module| ModuleJson {
+                name: module.name.as_str(),
+                entries: module
+                    .entries
+                    .iter()
+                    .filter(|entry| match entry {
+                        DocEntry::DocDef(doc_def) => all_exposed_symbols.contains(&doc_def.symbol),
+                        DocEntry::DetachedDoc(_) => true,
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        // Modules finish loading in whatever order the threads get to them; sort them so that
+        // the output is stable enough to diff.
+        modules.sort_by(|a, b| a.name.cmp(b.name));
+
+        let package_json = PackageJson {
+            name: package_name.as_str(),
+            version: version.as_str(),
+            modules,
+        };
+        let file = fs::File::create(build_dir.join(JSON_FILENAME))
+            .expect("TODO gracefully handle failing to create the docs JSON file");
+
+        serde_json::to_writer_pretty(io::BufWriter::new(file), &package_json)
+            .expect("TODO gracefully handle failing to write the docs JSON file");
+    }
+
     println!("🎉 Docs generated in {}", build_dir.display());
 }
 
+/// The JSON representation of a package's docs, as written by [DocsOptions::emit_json].
+#[derive(Serialize)]
+struct PackageJson<'a> {
+    name: &'a str,
+    version: &'a str,
+    modules: Vec<ModuleJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct ModuleJson<'a> {
+    name: &'a str,
+    /// Only the entries that are exposed, plus any detached doc comments
+    entries: Vec<&'a DocEntry>,
+}
+
+/// The name from a `package` or `platform` header, if that's what this file has.
+fn read_package_name(path: &Path) -> Option<String> {
+    let src = fs::read(path).ok()?;
+    let arena = Bump::new();
+    let (module, _) = parse_header(&arena, State::new(&src)).ok()?;
+
+    match module.header {
+        Header::Package(header) => Some(header.name.value.to_str().to_string()),
+        Header::Platform(header) => Some(header.name.value.to_str().to_string()),
+        Header::App(_) | Header::Interface(_) | Header::Hosted(_) => None,
+    }
+}
+
+/// The version recorded in the manifest `roc package` generates, if there's one next to the
+/// root module. Packages downloaded from a URL always have one.
+fn read_manifest_version(root_file: &Path) -> Option<String> {
+    let manifest_path = root_file.parent()?.join(MANIFEST_FILENAME);
+    let json = fs::read_to_string(manifest_path).ok()?;
+    let manifest = Manifest::from_json(&json).ok()?;
+
+    Some(manifest.version)
+}
+
+/// Figure out which package each of the loaded modules came from, for every module that isn't
+/// part of the root package (or a builtin). A package is a directory with a `main.roc` that has
+/// a `package` or `platform` header, so we look for the nearest one above each module.
+fn dependency_packages(root_file: &Path, loaded_module: &LoadedModule) -> VecMap<ModuleId, String> {
+    let root_dir = root_file.parent().unwrap_or_else(|| Path::new("."));
+    let mut names_by_dir: VecMap<PathBuf, Option<String>> = VecMap::default();
+    let mut answer = VecMap::default();
+
+    for (module_id, (path, _)) in loaded_module.sources.iter() {
+        if module_id.is_builtin() {
+            continue;
+        }
+
+        for dir in path.ancestors().skip(1) {
+            if dir == root_dir {
+                // This module is part of the package we're documenting.
+                break;
+            }
+
+            let name = names_by_dir.get_or_insert(dir.to_path_buf(), || {
+                read_package_name(&dir.join(PACKAGE_ROOT_FILENAME))
+            });
+
+            if let Some(name) = name.clone() {
+                answer.insert(*module_id, name);
+
+                break;
+            }
+        }
+    }
+
+    answer
+}
+
 fn sidebar_link_url(module_name: &str) -> String {
     format!("{}{}", base_url(), module_name)
 }
@@ -125,11 +293,7 @@ fn page_title(package_name: &str, module_name: &str) -> String {
     format!("<title>{module_name} - {package_name}</title>")
 }
 
-fn render_module_documentation(
-    module: &ModuleDocumentation,
-    root_module: &LoadedModule,
-    all_exposed_symbols: &VecSet<Symbol>,
-) -> String {
+fn render_module_documentation(module: &ModuleDocumentation, ctx: &LinkContext) -> String {
     let mut buf = String::new();
 
     push_html(&mut buf, "h2", vec![("class", "module-name")], {
@@ -149,7 +313,7 @@ fn render_module_documentation(
         match entry {
             DocEntry::DocDef(doc_def) => {
                 // Only render entries that are exposed
-                if all_exposed_symbols.contains(&doc_def.symbol) {
+                if ctx.all_exposed_symbols.contains(&doc_def.symbol) {
                     buf.push_str("<section>");
 
                     let name = doc_def.name.as_str();
@@ -168,7 +332,14 @@ fn render_module_documentation(
 
                     if !matches!(type_ann, TypeAnnotation::NoTypeAnn) {
                         content.push_str(" : ");
-                        type_annotation_to_html(0, &mut content, type_ann, false);
+                        type_annotation_to_html(
+                            0,
+                            &mut content,
+                            type_ann,
+                            false,
+                            ctx,
+                            &module.scope,
+                        );
                     }
 
                     push_html(
@@ -179,26 +350,14 @@ fn render_module_documentation(
                     );
 
                     if let Some(docs) = &doc_def.docs {
-                        markdown_to_html(
-                            &mut buf,
-                            all_exposed_symbols,
-                            &module.scope,
-                            docs,
-                            root_module,
-                        );
+                        markdown_to_html(&mut buf, ctx, &module.scope, docs);
                     }
 
                     buf.push_str("</section>");
                 }
             }
             DocEntry::DetachedDoc(docs) => {
-                markdown_to_html(
-                    &mut buf,
-                    all_exposed_symbols,
-                    &module.scope,
-                    docs,
-                    root_module,
-                );
+                markdown_to_html(&mut buf, ctx, &module.scope, docs);
             }
         };
     }
@@ -350,6 +509,18 @@ fn render_sidebar<'a, I: Iterator<Item = &'a ModuleDocumentation>>(modules: I) -
 }
 
 pub fn load_module_for_docs(filename: PathBuf) -> LoadedModule {
+    match try_load_module_for_docs(filename) {
+        Ok(loaded) => loaded,
+        Err(report) => {
+            eprintln!("{}", report);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Like [load_module_for_docs], but gives back the report instead of exiting
+/// if the module can't be loaded
+pub fn try_load_module_for_docs(filename: PathBuf) -> Result<LoadedModule, String> {
     let arena = Bump::new();
     let load_config = LoadConfig {
         target_info: roc_target::TargetInfo::default_x86_64(), // This is just type-checking for docs, so "target" doesn't matter
@@ -364,11 +535,8 @@ pub fn load_module_for_docs(filename: PathBuf) -> LoadedModule {
         RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
         load_config,
     ) {
-        Ok(loaded) => loaded,
-        Err(LoadingProblem::FormattedReport(report)) => {
-            eprintln!("{}", report);
-            std::process::exit(1);
-        }
+        Ok(loaded) => Ok(loaded),
+        Err(LoadingProblem::FormattedReport(report)) => Err(report),
         Err(e) => panic!("{:?}", e),
     }
 }
@@ -391,6 +559,8 @@ fn type_annotation_to_html(
     buf: &mut String,
     type_ann: &TypeAnnotation,
     needs_parens: bool,
+    ctx: &LinkContext,
+    scope: &Scope,
 ) {
     let is_multiline = should_be_multiline(type_ann);
     match type_ann {
@@ -425,7 +595,14 @@ fn type_annotation_to_html(
 
                     for type_value in &tag.values {
                         buf.push(' ');
-                        type_annotation_to_html(next_indent_level, buf, type_value, true);
+                        type_annotation_to_html(
+                            next_indent_level,
+                            buf,
+                            type_value,
+                            true,
+                            ctx,
+                            scope,
+                        );
                     }
 
                     if is_multiline {
@@ -444,23 +621,23 @@ fn type_annotation_to_html(
                 buf.push(']');
             }
 
-            type_annotation_to_html(indent_level, buf, extension, true);
+            type_annotation_to_html(indent_level, buf, extension, true, ctx, scope);
         }
         TypeAnnotation::BoundVariable(var_name) => {
             buf.push_str(var_name);
         }
         TypeAnnotation::Apply { name, parts } => {
             if parts.is_empty() {
-                buf.push_str(name);
+                push_type_name(buf, name, ctx, scope);
             } else {
                 if needs_parens {
                     buf.push('(');
                 }
 
-                buf.push_str(name);
+                push_type_name(buf, name, ctx, scope);
                 for part in parts {
                     buf.push(' ');
-                    type_annotation_to_html(indent_level, buf, part, true);
+                    type_annotation_to_html(indent_level, buf, part, true, ctx, scope);
                 }
 
                 if needs_parens {
@@ -508,13 +685,27 @@ fn type_annotation_to_html(
                             type_annotation, ..
                         } => {
                             buf.push_str(" : ");
-                            type_annotation_to_html(next_indent_level, buf, type_annotation, false);
+                            type_annotation_to_html(
+                                next_indent_level,
+                                buf,
+                                type_annotation,
+                                false,
+                                ctx,
+                                scope,
+                            );
                         }
                         RecordField::OptionalField {
                             type_annotation, ..
                         } => {
                             buf.push_str(" ? ");
-                            type_annotation_to_html(next_indent_level, buf, type_annotation, false);
+                            type_annotation_to_html(
+                                next_indent_level,
+                                buf,
+                                type_annotation,
+                                false,
+                                ctx,
+                                scope,
+                            );
                         }
                         RecordField::LabelOnly { .. } => {}
                     }
@@ -537,7 +728,7 @@ fn type_annotation_to_html(
                 buf.push('}');
             }
 
-            type_annotation_to_html(indent_level, buf, extension, true);
+            type_annotation_to_html(indent_level, buf, extension, true, ctx, scope);
         }
         TypeAnnotation::Function { args, output } => {
             let mut paren_is_open = false;
@@ -556,7 +747,7 @@ fn type_annotation_to_html(
 
                 let child_needs_parens =
                     matches!(arg, TypeAnnotation::Function { args: _, output: _ });
-                type_annotation_to_html(indent_level, buf, arg, child_needs_parens);
+                type_annotation_to_html(indent_level, buf, arg, child_needs_parens, ctx, scope);
 
                 if peekable_args.peek().is_some() {
                     buf.push_str(", ");
@@ -576,7 +767,7 @@ fn type_annotation_to_html(
                 next_indent_level += 1;
             }
 
-            type_annotation_to_html(next_indent_level, buf, output, false);
+            type_annotation_to_html(next_indent_level, buf, output, false, ctx, scope);
             if needs_parens && paren_is_open {
                 buf.push(')');
             }
@@ -671,68 +862,95 @@ struct DocUrl {
     title: String,
 }
 
-fn doc_url<'a>(
-    all_exposed_symbols: &VecSet<Symbol>,
+/// Find the symbol that a doc link (e.g. `[Str.join]`) or a type name refers to.
+fn resolve_symbol(
+    ctx: &LinkContext,
     scope: &Scope,
-    interns: &'a Interns,
-    mut module_name: &'a str,
+    module_name: &str,
     ident: &str,
-) -> DocUrl {
+) -> Result<Symbol, String> {
+    let interns = &ctx.loaded_module.interns;
+
     if module_name.is_empty() {
         // This is an unqualified lookup, so look for the ident
-        // in scope!
-        match scope.lookup_str(ident, Region::zero()) {
-            Ok(symbol) => {
-                // Get the exact module_name from scope. It could be the
-                // current module's name, but it also could be a different
-                // module - for example, if this is in scope from an
-                // unqualified import.
-                module_name = symbol.module_string(interns);
-            }
-            Err(_) => {
-                // TODO return Err here
-                panic!(
-                    "Tried to generate an automatic link in docs for symbol `{}`, but that symbol was not in scope in this module.",
-                    ident
-                );
-            }
-        }
+        // in scope! It could be from the current module, but it also
+        // could be from a different module - for example, if this is
+        // in scope from an unqualified import.
+        scope.lookup_str(ident, Region::zero()).map_err(|_| {
+            format!(
+                "Tried to generate an automatic link in docs for symbol `{}`, but that symbol was not in scope in this module.",
+                ident
+            )
+        })
     } else {
-        match interns.module_ids.get_id(&module_name.into()) {
-            Some(module_id) => {
-                let symbol = interns.symbol(module_id, ident.into());
-
-                if symbol.is_builtin() {
-                    // We can always generate links for builtin modules.
-                    // TODO add a `--include-builtins` CLI flag for generating offline docs locally
-                    // which include builtins; if that flag is omitted, have this code path generate
-                    // a link directly to the builtin docs on roc-lang.org instead of to a localhost
-                    // URL that will 404.
-                    module_name = symbol.module_string(interns);
-                }
-                // Note: You can do qualified lookups on your own module, e.g.
-                // if I'm in the Foo module, I can do a `Foo.bar` lookup.
-                else if !all_exposed_symbols.contains(&symbol) {
-                    // TODO return Err here
-                    panic!(
-                            "Tried to generate an automatic link in docs for `{}.{}`, but `{}` does not expose `{}`.",
-                            module_name, ident, module_name, ident);
-                }
-
-                // This is a valid symbol for this dependency,
-                // so proceed using the current module's name.
-                //
-                // TODO: In the future, this is where we'll
-                // incorporate the package name into the link.
-            }
-            None => {
-                // TODO return Err here
-                panic!("Tried to generate a doc link for `{}.{}` but the `{}` module was not imported!", module_name, ident, module_name);
-            }
+        let module_id = interns
+            .module_ids
+            .get_id(&module_name.into())
+            .ok_or_else(|| {
+                format!(
+                    "Tried to generate a doc link for `{}.{}` but the `{}` module was not imported!",
+                    module_name, ident, module_name
+                )
+            })?;
+        let not_exposed = || {
+            format!(
+                "Tried to generate an automatic link in docs for `{}.{}`, but `{}` does not expose `{}`.",
+                module_name, ident, module_name, ident
+            )
+        };
+        let symbol = interns
+            .all_ident_ids
+            .get(&module_id)
+            .and_then(|ident_ids| ident_ids.get_id(ident))
+            .map(|ident_id| Symbol::new(module_id, ident_id))
+            .ok_or_else(not_exposed)?;
+
+        // Builtins and other packages have docs of their own, so we can always link to them.
+        // Otherwise, this must be exposed by one of our modules. (Note: You can do qualified
+        // lookups on your own module, e.g. if I'm in the Foo module, I can do a `Foo.bar` lookup.)
+        if symbol.is_builtin()
+            || ctx.dependency_packages.contains_key(&module_id)
+            || ctx.all_exposed_symbols.contains(&symbol)
+        {
+            Ok(symbol)
+        } else {
+            Err(not_exposed())
         }
     }
+}
 
-    let mut url = base_url();
+fn doc_url(ctx: &LinkContext, symbol: Symbol) -> DocUrl {
+    let interns = &ctx.loaded_module.interns;
+    let module_name = symbol.module_string(interns).as_str();
+    let ident = symbol.as_str(interns);
+
+    let mut url = match ctx.dependency_packages.get(&symbol.module_id()) {
+        Some(package_name) => {
+            // This symbol comes from one of the packages we depend on, so link to its docs.
+            //
+            // Example:
+            //
+            // packages_url: "/", package_name: "json" => "/json/"
+            let mut url_str = ctx.packages_url.to_string();
+
+            if !url_str.ends_with('/') {
+                url_str.push('/');
+            }
+
+            url_str.push_str(package_name);
+            url_str.push('/');
+
+            url_str
+        }
+        None => {
+            // We can always generate links for builtin modules.
+            // TODO add a `--include-builtins` CLI flag for generating offline docs locally
+            // which include builtins; if that flag is omitted, have this code path generate
+            // a link directly to the builtin docs on roc-lang.org instead of to a localhost
+            // URL that will 404.
+            base_url()
+        }
+    };
 
     // Example:
     //
@@ -747,13 +965,37 @@ fn doc_url<'a>(
     }
 }
 
-fn markdown_to_html(
-    buf: &mut String,
-    all_exposed_symbols: &VecSet<Symbol>,
-    scope: &Scope,
-    markdown: &str,
-    loaded_module: &LoadedModule,
-) {
+/// Type names link to their docs, as long as those are docs we (or another package) generate.
+/// Builtins are left unlinked, since they're used everywhere and it'd just be noise, and so are
+/// types which aren't exposed, since they have no docs to link to.
+fn push_type_name(buf: &mut String, name: &str, ctx: &LinkContext, scope: &Scope) {
+    let (module_name, ident) = match name.rsplit_once('.') {
+        Some((module_name, ident)) => (module_name, ident),
+        None => ("", name),
+    };
+
+    match resolve_symbol(ctx, scope, module_name, ident) {
+        Ok(symbol)
+            if !symbol.is_builtin()
+                && (ctx.all_exposed_symbols.contains(&symbol)
+                    || ctx.dependency_packages.contains_key(&symbol.module_id())) =>
+        {
+            let DocUrl { url, title } = doc_url(ctx, symbol);
+
+            push_html(
+                buf,
+                "a",
+                vec![("href", url.as_str()), ("title", title.as_str())],
+                name,
+            );
+        }
+        _ => {
+            buf.push_str(name);
+        }
+    }
+}
+
+fn markdown_to_html(buf: &mut String, ctx: &LinkContext, scope: &Scope, markdown: &str) {
     use pulldown_cmark::{BrokenLink, CodeBlockKind, CowStr, Event, LinkType, Tag::*};
 
     let mut arena = Bump::new();
@@ -778,13 +1020,10 @@ fn markdown_to_html(
 
                         match iter.next() {
                             Some(Accessor::RecordField(symbol_name)) if iter.next().is_none() => {
-                                let DocUrl { url, title } = doc_url(
-                                    all_exposed_symbols,
-                                    scope,
-                                    &loaded_module.interns,
-                                    module_name,
-                                    symbol_name,
-                                );
+                                // TODO return Err here instead of panicking
+                                let symbol = resolve_symbol(ctx, scope, module_name, symbol_name)
+                                    .unwrap_or_else(|problem| panic!("{}", problem));
+                                let DocUrl { url, title } = doc_url(ctx, symbol);
 
                                 Some((url.into(), title.into()))
                             }
@@ -799,13 +1038,10 @@ fn markdown_to_html(
                     Ok((_, Ident::Tag(type_name), _)) => {
                         // This looks like a tag name, but it could
                         // be a type alias that's in scope, e.g. [I64]
-                        let DocUrl { url, title } = doc_url(
-                            all_exposed_symbols,
-                            scope,
-                            &loaded_module.interns,
-                            "",
-                            type_name,
-                        );
+                        // TODO return Err here instead of panicking
+                        let symbol = resolve_symbol(ctx, scope, "", type_name)
+                            .unwrap_or_else(|problem| panic!("{}", problem));
+                        let DocUrl { url, title } = doc_url(ctx, symbol);
 
                         Some((url.into(), title.into()))
                     }
@@ -905,3 +1141,227 @@ fn markdown_to_html(
 
     pulldown_cmark::html::push_html(buf, docs_parser.into_iter());
 }
+
+#[cfg(test)]
+mod test_docs {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn write_files(dir: &Path, files: &[(&str, &str)]) {
+        for (path, contents) in files {
+            let path = dir.join(path);
+
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+    }
+
+    #[test]
+    fn package_name_from_header() {
+        let dir = tempfile::tempdir().unwrap();
+
+        write_files(
+            dir.path(),
+            &[
+                ("json/main.roc", "package \"json\"\n    exposes []\n    packages {}\n"),
+                (
+                    "platform/main.roc",
+                    "platform \"cli\"\n    requires {} { main : Str }\n    exposes []\n    packages {}\n    imports []\n    provides [mainForHost]\n\nmainForHost : Str\nmainForHost = main\n",
+                ),
+                ("Foo.roc", "interface Foo\n    exposes []\n    imports []\n"),
+            ],
+        );
+
+        assert_eq!(
+            read_package_name(&dir.path().join("json/main.roc")),
+            Some("json".to_string())
+        );
+        assert_eq!(
+            read_package_name(&dir.path().join("platform/main.roc")),
+            Some("cli".to_string())
+        );
+        assert_eq!(read_package_name(&dir.path().join("Foo.roc")), None);
+        assert_eq!(read_package_name(&dir.path().join("Missing.roc")), None);
+    }
+
+    #[test]
+    fn version_from_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let root_file = dir.path().join("main.roc");
+
+        assert_eq!(read_manifest_version(&root_file), None);
+
+        let manifest = Manifest {
+            name: "json".to_string(),
+            version: "1.2.3".to_string(),
+            license: None,
+            exposes: vec![],
+            dependencies: vec![],
+        };
+
+        fs::write(
+            dir.path().join(MANIFEST_FILENAME),
+            serde_json::to_string(&manifest).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(read_manifest_version(&root_file), Some("1.2.3".to_string()));
+    }
+
+    /// The loader can't load a package whose modules import from its `packages` yet, so the
+    /// "dependency" here is a directory inside the package with a package header of its own.
+    /// That's all [dependency_packages] looks at, so the links come out the same.
+    const PACKAGE_FILES: &[(&str, &str)] = &[
+        (
+            "pkg/main.roc",
+            indoc::indoc!(
+                r#"
+                package "pkg"
+                    exposes [Foo]
+                    packages {}
+                "#
+            ),
+        ),
+        (
+            "pkg/Foo.roc",
+            indoc::indoc!(
+                r#"
+                interface Foo
+                    exposes [toStr]
+                    imports [Dep.Bar]
+
+                ## Shows a `Bar`.
+                toStr : Bar.Bar -> Str
+                toStr = \bar -> Bar.name bar
+
+                hidden = 1
+                "#
+            ),
+        ),
+        (
+            "pkg/Dep/main.roc",
+            indoc::indoc!(
+                r#"
+                package "dep"
+                    exposes [Bar]
+                    packages {}
+                "#
+            ),
+        ),
+        (
+            "pkg/Dep/Bar.roc",
+            indoc::indoc!(
+                r#"
+                interface Dep.Bar
+                    exposes [Bar, name]
+                    imports []
+
+                Bar := Str
+
+                name : Bar -> Str
+                name = \@Bar str -> str
+                "#
+            ),
+        ),
+    ];
+
+    fn symbol(loaded_module: &LoadedModule, module_name: &str, ident: &str) -> Symbol {
+        let interns = &loaded_module.interns;
+        let module_id = interns.module_ids.get_id(&module_name.into()).unwrap();
+        let ident_id = interns
+            .all_ident_ids
+            .get(&module_id)
+            .and_then(|ident_ids| ident_ids.get_id(ident))
+            .unwrap();
+
+        Symbol::new(module_id, ident_id)
+    }
+
+    #[test]
+    fn links_to_dependency_docs() {
+        let dir = tempfile::tempdir().unwrap();
+
+        write_files(dir.path(), PACKAGE_FILES);
+
+        let root_file = dir.path().join("pkg/main.roc");
+        let loaded_module = load_module_for_docs(root_file.clone());
+        let dependency_packages = dependency_packages(&root_file, &loaded_module);
+        let to_str = symbol(&loaded_module, "Foo", "toStr");
+        let name = symbol(&loaded_module, "Dep.Bar", "name");
+
+        assert_eq!(
+            dependency_packages.iter().collect::<Vec<_>>(),
+            [(&name.module_id(), &"dep".to_string())]
+        );
+
+        let mut all_exposed_symbols = VecSet::default();
+
+        all_exposed_symbols.insert(to_str);
+
+        let ctx = LinkContext {
+            loaded_module: &loaded_module,
+            all_exposed_symbols: &all_exposed_symbols,
+            dependency_packages: &dependency_packages,
+            packages_url: "https://example.com/packages",
+        };
+
+        assert_eq!(doc_url(&ctx, to_str).url, "/Foo#toStr");
+        assert_eq!(
+            doc_url(&ctx, name).url,
+            "https://example.com/packages/dep/Dep.Bar#name"
+        );
+    }
+
+    #[test]
+    fn json_output() {
+        let dir = tempfile::tempdir().unwrap();
+
+        write_files(dir.path(), PACKAGE_FILES);
+
+        // The version comes from the manifest when it isn't given explicitly.
+        let manifest = Manifest {
+            name: "pkg".to_string(),
+            version: "2.0.0".to_string(),
+            license: None,
+            exposes: vec!["Foo".to_string()],
+            dependencies: vec![],
+        };
+
+        fs::write(
+            dir.path().join("pkg").join(MANIFEST_FILENAME),
+            serde_json::to_string(&manifest).unwrap(),
+        )
+        .unwrap();
+
+        let options = DocsOptions {
+            output_dir: dir.path().join("out"),
+            emit_json: true,
+            ..DocsOptions::default()
+        };
+
+        generate_docs_html(dir.path().join("pkg/main.roc"), &options);
+
+        let html = fs::read_to_string(options.output_dir.join("Foo/index.html")).unwrap();
+
+        assert!(html.contains("<title>Foo - pkg</title>"), "{html}");
+        assert!(html.contains("2.0.0"), "{html}");
+
+        let json = fs::read_to_string(options.output_dir.join(JSON_FILENAME)).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(json["name"], "pkg");
+        assert_eq!(json["version"], "2.0.0");
+
+        let modules = json["modules"].as_array().unwrap();
+
+        assert_eq!(modules.len(), 1, "{json}");
+        assert_eq!(modules[0]["name"], "Foo");
+
+        // Only exposed defs are included.
+        let entries = modules[0]["entries"].to_string();
+
+        assert!(entries.contains("toStr"), "{entries}");
+        assert!(entries.contains("Shows a `Bar`."), "{entries}");
+        assert!(!entries.contains("hidden"), "{entries}");
+    }
+}
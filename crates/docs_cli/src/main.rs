@@ -1,6 +1,6 @@
 //! Provides a binary that is only used for static build servers.
 use clap::{Arg, Command};
-use roc_docs::generate_docs_html;
+use roc_docs::{generate_docs_html, DocsOptions};
 use std::io;
 use std::path::PathBuf;
 
@@ -21,7 +21,10 @@ fn main() -> io::Result<()> {
         .get_matches();
 
     // Populate roc_files
-    generate_docs_html(PathBuf::from(matches.value_of_os(ROC_FILE).unwrap()));
+    generate_docs_html(
+        PathBuf::from(matches.value_of_os(ROC_FILE).unwrap()),
+        &DocsOptions::default(),
+    );
 
     Ok(())
 }
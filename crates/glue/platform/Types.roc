@@ -1,5 +1,5 @@
 interface Types
-    exposes [Types, shape, size, alignment, target, walkShapes, entryPoints]
+    exposes [Types, shape, size, alignment, offsets, target, walkShapes, entryPoints]
     imports [Shape.{ Shape }, TypeId.{ TypeId }, Target.{ Target }, InternalTypeId]
 
 # TODO: switch AssocList uses to Dict once roc_std is updated.
@@ -12,6 +12,10 @@ Types := {
     sizes : List U32,
     aligns : List U32,
 
+    ## The offset of each field within a struct-like type, in the same order as its fields.
+    ## Empty for types which don't have fields.
+    offsets : List (List U32),
+
     # Needed to check for duplicates
     typesByName : List Tuple1,
 
@@ -57,6 +61,18 @@ alignment = \@Types types, id ->
 
             crash "TypeId #\(idStr) was not found in Types. This should never happen, and means there was a bug in `roc glue`. If you have time, please open an issue at <https://github.com/roc-lang/roc/issues>"
 
+## The offsets of a struct's (or tag union payload's, or single-tag union's) fields,
+## in the same order as its fields. These are where Roc expects each field to be,
+## so glue can check that the host language lays the fields out the same way.
+offsets : Types, TypeId -> List U32
+offsets = \@Types types, id ->
+    when List.get types.offsets (InternalTypeId.toNat id) is
+        Ok answer -> answer
+        Err OutOfBounds ->
+            idStr = Num.toStr (InternalTypeId.toNat id)
+
+            crash "TypeId #\(idStr) was not found in Types. This should never happen, and means there was a bug in `roc glue`. If you have time, please open an issue at <https://github.com/roc-lang/roc/issues>"
+
 size : Types, TypeId -> U32
 size = \@Types types, id ->
     when List.get types.sizes (InternalTypeId.toNat id) is
@@ -0,0 +1,1252 @@
+app "c-glue"
+    packages { pf: "../platform/main.roc" }
+    imports [pf.Types.{ Types }, pf.Shape.{ Shape, RocFn }, pf.File.{ File }, pf.TypeId.{ TypeId }]
+    provides [makeGlue] to pf
+
+makeGlue : List Types -> Result (List File) Str
+makeGlue = \typesByArch ->
+    archIncludes =
+        walkWithIndex typesByArch "" \content, index, types ->
+            arch = (Types.target types).architecture
+            archStr = archName arch
+            condition = archCondition arch
+            directive = if index == 0 then "#if" else "#elif"
+
+            Str.concat
+                content
+                """
+                \(directive) \(condition)
+                #include "\(archStr).h"
+
+                """
+
+    mainHeader =
+        """
+        \(fileHeader)
+        \(archIncludes)#else
+        #error "This architecture was not included when running `roc glue`."
+        #endif
+
+        """
+
+    typesByArch
+    |> List.map convertTypesToFile
+    |> List.append { name: "roc_app.h", content: mainHeader }
+    |> Ok
+
+convertTypesToFile : Types -> File
+convertTypesToFile = \types ->
+    archStr = archName (Types.target types).architecture
+
+    # C needs a type to be fully declared before it can be used by value,
+    # so declare the types in dependency order.
+    declarations =
+        declarationOrder types
+        |> List.walk "" \buf, id -> generateDeclaration buf types id
+
+    # Recursive tag unions are represented as pointers, which means their payloads can refer
+    # to them before the payloads are declared. The functions for working with them need the
+    # payloads to be declared, though, so they go after all the declarations.
+    helpers =
+        Types.walkShapes types "" \buf, _, id -> generateHelpers buf types id
+
+    content =
+        fileHeader
+        |> Str.concat (prelude types)
+        |> Str.concat declarations
+        |> Str.concat helpers
+        |> generateEntryPoints types
+
+    { name: "\(archStr).h", content }
+
+declarationOrder : Types -> List TypeId
+declarationOrder = \types ->
+    initial = { visited: Set.empty {}, order: [] }
+
+    Types.walkShapes types initial \state, _, id -> visit types state id
+    |> .order
+
+visit : Types, { visited : Set TypeId, order : List TypeId }, TypeId -> { visited : Set TypeId, order : List TypeId }
+visit = \types, state, id ->
+    if Set.contains state.visited id then
+        state
+    else
+        visited = Set.insert state.visited id
+        afterDeps =
+            List.walk (valueDeps types id) { state & visited: visited } \accum, depId ->
+                visit types accum depId
+
+        { afterDeps & order: List.append afterDeps.order id }
+
+## The types which must be declared before this one, because it contains them by value.
+valueDeps : Types, TypeId -> List TypeId
+valueDeps = \types, id ->
+    when Types.shape types id is
+        Struct { fields } -> List.map (structFields fields) .id
+        TagUnionPayload { fields } -> List.map (payloadFields fields) .id
+        TagUnion (SingleTagStruct { payload }) -> List.map (singleTagPayloadFields payload) .id
+        TagUnion (NonRecursive { tags }) ->
+            List.keepOks tags \{ payload } ->
+                when payload is
+                    Some payloadId -> Ok payloadId
+                    None -> Err NoPayload
+
+        RocResult okId errId -> [okId, errId]
+        RecursivePointer content -> [content]
+        Function rocFn -> [rocFn.lambdaSet]
+        _ -> []
+
+generateDeclaration : Str, Types, TypeId -> Str
+generateDeclaration = \buf, types, id ->
+    when Types.shape types id is
+        Struct { name, fields } ->
+            generateStruct buf types id (escapeKW name) (structFields fields)
+
+        TagUnionPayload { name, fields } ->
+            generateStruct buf types id (escapeKW name) (payloadFields fields)
+
+        TagUnion (SingleTagStruct { name, payload }) ->
+            generateStruct buf types id (escapeKW name) (singleTagPayloadFields payload)
+
+        TagUnion (Enumeration { name, tags, size }) ->
+            escapedName = escapeKW name
+
+            buf
+            |> generateEnumeration escapedName tags size
+            |> generateLayoutChecks types id escapedName
+
+        TagUnion (NonRecursive { name, tags, discriminantSize, discriminantOffset }) ->
+            if List.isEmpty tags then
+                buf
+            else
+                generateNonRecursiveTagUnion buf types id (escapeKW name) tags discriminantSize discriminantOffset
+
+        TagUnion (Recursive { name, tags, discriminantSize }) ->
+            escapedName = escapeKW name
+
+            buf
+            |> generateEnumeration "discriminant_\(escapedName)" (List.map tags .name) discriminantSize
+            |> generatePointer types id escapedName
+
+        TagUnion (NullableWrapped { name, tags, discriminantSize }) ->
+            escapedName = escapeKW name
+
+            buf
+            |> generateEnumeration "discriminant_\(escapedName)" (List.map tags .name) discriminantSize
+            |> generatePointer types id escapedName
+
+        TagUnion (NullableUnwrapped { name, nullTag, nonNullTag, whichTagIsNull }) ->
+            escapedName = escapeKW name
+            tagNames =
+                when whichTagIsNull is
+                    FirstTagIsNull -> [nullTag, nonNullTag]
+                    SecondTagIsNull -> [nonNullTag, nullTag]
+
+            buf
+            |> generateEnumeration "discriminant_\(escapedName)" tagNames 1
+            |> generatePointer types id escapedName
+
+        TagUnion (NonNullableUnwrapped { name }) ->
+            generatePointer buf types id (escapeKW name)
+
+        RocResult okId errId ->
+            generateResult buf types id okId errId
+
+        RocDict _ _ | RocSet _ ->
+            generateOpaque buf types id
+
+        Function rocFn ->
+            if rocFn.isToplevel then
+                buf
+            else
+                generateClosure buf types id rocFn
+
+        RecursivePointer _ | Unit | Unsized | EmptyTagUnion | Num _ | Bool | RocStr | RocList _ | RocBox _ ->
+            # These are either declared in the prelude, or are pointers to types declared elsewhere.
+            buf
+
+generateHelpers : Str, Types, TypeId -> Str
+generateHelpers = \buf, types, id ->
+    when Types.shape types id is
+        TagUnion (Recursive { name, tags }) ->
+            generateRecursiveHelpers buf types (escapeKW name) tags None
+
+        TagUnion (NullableWrapped { name, indexOfNullTag, tags }) ->
+            generateRecursiveHelpers buf types (escapeKW name) tags (Some indexOfNullTag)
+
+        TagUnion (NullableUnwrapped { name, nullTag, nonNullTag, nonNullPayload }) ->
+            generateNullableUnwrappedHelpers buf types (escapeKW name) nullTag nonNullTag nonNullPayload
+
+        TagUnion (NonNullableUnwrapped { name, tagName, payload }) ->
+            generateNonNullableUnwrappedHelpers buf types (escapeKW name) tagName payload
+
+        Function rocFn ->
+            if rocFn.isToplevel then
+                buf
+            else
+                generateClosureCall buf types id rocFn
+
+        _ ->
+            buf
+
+structFields = \fields ->
+    when fields is
+        HasNoClosure xs -> List.map xs \{ name, id } -> { name: escapeKW name, id }
+        HasClosure xs -> List.map xs \{ name, id } -> { name: escapeKW name, id }
+
+payloadFields = \fields ->
+    # Tag union payloads have numbered fields, so we prefix them
+    # with an "f" because C doesn't allow struct fields to be numbers.
+    when fields is
+        HasNoClosure xs -> List.map xs \{ name, id } -> { name: "f\(name)", id }
+        HasClosure xs -> List.map xs \{ name, id } -> { name: "f\(name)", id }
+
+singleTagPayloadFields = \payload ->
+    when payload is
+        HasClosure xs -> List.map xs \{ name, id } -> { name: escapeKW name, id }
+        HasNoClosure xs ->
+            List.mapWithIndex xs \{ id }, index ->
+                indexStr = Num.toStr index
+
+                { name: "f\(indexStr)", id }
+
+generateStruct : Str, Types, TypeId, Str, List { name : Str, id : TypeId } -> Str
+generateStruct = \buf, types, id, name, fields ->
+    if isZeroSized types id then
+        # C doesn't have zero-sized types, so these get left out wherever they would be used.
+        buf
+    else
+        fieldDecls =
+            fields
+            |> List.keepIf \field -> !(isZeroSized types field.id)
+            |> List.map \field ->
+                type = typeName types field.id
+                fieldName = field.name
+
+                "\(indent)\(type) \(fieldName);\n"
+            |> Str.joinWith ""
+
+        buf
+        |> Str.concat "typedef struct \(name) {\n\(fieldDecls)} \(name);\n\n"
+        |> generateOffsetChecks types id name fields
+        |> generateLayoutChecks types id name
+
+generateOffsetChecks = \buf, types, id, name, fields ->
+    List.map2 fields (Types.offsets types id) \field, offset -> { field, offset }
+    |> List.keepIf \{ field } -> !(isZeroSized types field.id)
+    |> List.walk buf \accum, { field, offset } ->
+        fieldName = field.name
+        offsetStr = Num.toStr offset
+
+        Str.concat accum "_Static_assert(offsetof(\(name), \(fieldName)) == \(offsetStr), \"Roc expects \(name).\(fieldName) to be at an offset of \(offsetStr) bytes\");\n"
+
+generateEnumeration : Str, Str, List Str, U32 -> Str
+generateEnumeration = \buf, name, tags, size ->
+    intType = discriminantType size
+    constants =
+        tags
+        |> List.mapWithIndex \tag, index ->
+            indexStr = Num.toStr index
+
+            "\(indent)\(name)_\(tag) = \(indexStr),\n"
+        |> Str.joinWith ""
+
+    Str.concat buf "typedef \(intType) \(name);\n\nenum {\n\(constants)};\n\n"
+
+generateNonRecursiveTagUnion = \buf, types, id, name, tags, discriminantSize, discriminantOffset ->
+    discriminantName = "discriminant_\(name)"
+    unionName = "union_\(name)"
+    offsetStr = Num.toStr discriminantOffset
+    sizeStr = Num.toStr (getSizeRoundedToAlignment types id)
+    payloadTags = tagsWithPayloads types tags
+
+    unionDecl =
+        if List.isEmpty payloadTags then
+            ""
+        else
+            unionFields =
+                payloadTags
+                |> List.map \{ tagName, payloadId } ->
+                    type = typeName types payloadId
+
+                    "\(indent)\(type) \(tagName);\n"
+                |> Str.joinWith ""
+
+            "typedef union \(unionName) {\n\(unionFields)} \(unionName);\n\n"
+
+    payloadField =
+        if List.isEmpty payloadTags then
+            ""
+        else
+            "\(indent)\(unionName) payload;\n"
+
+    tagFunctions =
+        tags
+        |> List.map \{ name: tagName, payload } ->
+            isFn =
+                """
+                static inline bool \(name)_is_\(tagName)(const \(name) *self) {
+                    return \(name)_discriminant(self) == \(discriminantName)_\(tagName);
+                }
+
+                """
+
+            when nonZeroPayload types payload is
+                Some payloadId ->
+                    type = typeName types payloadId
+
+                    """
+                    \(isFn)
+                    static inline \(name) \(name)_make_\(tagName)(\(type) payload) {
+                        \(name) answer;
+
+                        memset(&answer, 0, sizeof(answer));
+                        answer.payload.\(tagName) = payload;
+                        \(name)_set_discriminant(&answer, \(discriminantName)_\(tagName));
+
+                        return answer;
+                    }
+
+                    static inline \(type) \(name)_get_\(tagName)(const \(name) *self) {
+                        return self->payload.\(tagName);
+                    }
+
+                    """
+
+                None ->
+                    """
+                    \(isFn)
+                    static inline \(name) \(name)_make_\(tagName)(void) {
+                        \(name) answer;
+
+                        memset(&answer, 0, sizeof(answer));
+                        \(name)_set_discriminant(&answer, \(discriminantName)_\(tagName));
+
+                        return answer;
+                    }
+
+                    """
+        |> Str.joinWith "\n"
+
+    buf
+    |> generateEnumeration discriminantName (List.map tags .name) discriminantSize
+    |> Str.concat unionDecl
+    |> Str.concat
+        """
+        // The discriminant is stored after the largest payload, in bytes that are
+        // padding for any smaller payloads.
+        typedef union \(name) {
+        \(payloadField)    unsigned char bytes[\(sizeStr)];
+        } \(name);
+
+
+        """
+    |> generateLayoutChecks types id name
+    |> Str.concat
+        """
+        \(docDiscriminant)
+        static inline \(discriminantName) \(name)_discriminant(const \(name) *self) {
+            \(discriminantName) discriminant;
+
+            memcpy(&discriminant, self->bytes + \(offsetStr), sizeof(discriminant));
+
+            return discriminant;
+        }
+
+        static inline void \(name)_set_discriminant(\(name) *self, \(discriminantName) discriminant) {
+            memcpy(self->bytes + \(offsetStr), &discriminant, sizeof(discriminant));
+        }
+
+        \(tagFunctions)
+
+        """
+
+generatePointer = \buf, types, id, name ->
+    buf
+    |> Str.concat
+        """
+        // A pointer to a reference-counted heap allocation. Recursive tag unions store
+        // which tag they are in the pointer's lowest bits, where possible.
+        typedef struct \(name) {
+            uintptr_t pointer;
+        } \(name);
+
+
+        """
+    |> generateLayoutChecks types id name
+
+generateRecursiveHelpers = \buf, types, name, tags, nullTagIndex ->
+    discriminantName = "discriminant_\(name)"
+    unionName = "union_\(name)"
+    payloadTags = tagsWithPayloads types tags
+
+    unionFields =
+        if List.isEmpty payloadTags then
+            "\(indent)unsigned char empty;\n"
+        else
+            payloadTags
+            |> List.map \{ tagName, payloadId } ->
+                type = typeName types payloadId
+
+                "\(indent)\(type) \(tagName);\n"
+            |> Str.joinWith ""
+
+    nullCheck =
+        when nullTagIndex is
+            Some index ->
+                indexStr = Num.toStr index
+
+                """
+                    if (self.pointer == 0) {
+                        return (\(discriminantName))\(indexStr);
+                    }
+
+
+                """
+
+            None ->
+                ""
+
+    tagFunctions =
+        tags
+        |> List.mapWithIndex \{ name: tagName, payload }, index ->
+            if Some (Num.intCast index) == nullTagIndex then
+                """
+                static inline bool \(name)_is_\(tagName)(\(name) self) {
+                    return self.pointer == 0;
+                }
+
+                static inline \(name) \(name)_make_\(tagName)(void) {
+                    \(name) answer = { 0 };
+
+                    return answer;
+                }
+
+                """
+            else
+                isFn =
+                    """
+                    static inline bool \(name)_is_\(tagName)(\(name) self) {
+                        return \(name)_discriminant(self) == \(discriminantName)_\(tagName);
+                    }
+
+                    """
+
+                when nonZeroPayload types payload is
+                    Some payloadId ->
+                        type = typeName types payloadId
+
+                        """
+                        \(isFn)
+                        static inline \(name) \(name)_make_\(tagName)(\(type) payload) {
+                            \(unionName) *data = roc_alloc_refcounted(sizeof(\(unionName)), _Alignof(\(unionName)));
+                            \(name) answer;
+
+                            data->\(tagName) = payload;
+                            answer.pointer = (uintptr_t)data | \(discriminantName)_\(tagName);
+
+                            return answer;
+                        }
+
+                        static inline \(type) \(name)_get_\(tagName)(\(name) self) {
+                            return \(name)_payload(self)->\(tagName);
+                        }
+
+                        """
+
+                    None ->
+                        """
+                        \(isFn)
+                        static inline \(name) \(name)_make_\(tagName)(void) {
+                            \(unionName) *data = roc_alloc_refcounted(sizeof(\(unionName)), _Alignof(\(unionName)));
+                            \(name) answer;
+
+                            answer.pointer = (uintptr_t)data | \(discriminantName)_\(tagName);
+
+                            return answer;
+                        }
+
+                        """
+        |> Str.joinWith "\n"
+
+    Str.concat
+        buf
+        """
+        typedef union \(unionName) {
+        \(unionFields)} \(unionName);
+
+        \(docDiscriminant)
+        static inline \(discriminantName) \(name)_discriminant(\(name) self) {
+        \(nullCheck)    return (\(discriminantName))(self.pointer & ROC_TAG_MASK);
+        }
+
+        static inline \(unionName) *\(name)_payload(\(name) self) {
+            return (\(unionName) *)(self.pointer & ~ROC_TAG_MASK);
+        }
+
+        \(tagFunctions)
+        \(docRefcounting)
+        static inline void \(name)_incref(\(name) self) {
+            roc_incref(\(name)_payload(self));
+        }
+
+        static inline bool \(name)_is_unique(\(name) self) {
+            return roc_is_unique(\(name)_payload(self));
+        }
+
+        static inline void \(name)_decref(\(name) self) {
+            roc_decref(\(name)_payload(self), _Alignof(\(unionName)));
+        }
+
+
+        """
+
+generateNullableUnwrappedHelpers = \buf, types, name, nullTag, nonNullTag, nonNullPayload ->
+    discriminantName = "discriminant_\(name)"
+    payloadType = typeName types nonNullPayload
+    pointerHelpers = generatePointerHelpers name nonNullTag payloadType
+
+    Str.concat
+        buf
+        """
+        \(docDiscriminant)
+        static inline \(discriminantName) \(name)_discriminant(\(name) self) {
+            return self.pointer == 0 ? \(discriminantName)_\(nullTag) : \(discriminantName)_\(nonNullTag);
+        }
+
+        static inline bool \(name)_is_\(nullTag)(\(name) self) {
+            return self.pointer == 0;
+        }
+
+        static inline bool \(name)_is_\(nonNullTag)(\(name) self) {
+            return self.pointer != 0;
+        }
+
+        static inline \(name) \(name)_make_\(nullTag)(void) {
+            \(name) answer = { 0 };
+
+            return answer;
+        }
+
+        \(pointerHelpers)
+        """
+
+generateNonNullableUnwrappedHelpers = \buf, types, name, tagName, payload ->
+    payloadType = typeName types payload
+
+    Str.concat buf (generatePointerHelpers name tagName payloadType)
+
+generatePointerHelpers = \name, tagName, payloadType ->
+    """
+    static inline \(name) \(name)_make_\(tagName)(\(payloadType) payload) {
+        \(payloadType) *data = roc_alloc_refcounted(sizeof(\(payloadType)), _Alignof(\(payloadType)));
+        \(name) answer;
+
+        *data = payload;
+        answer.pointer = (uintptr_t)data;
+
+        return answer;
+    }
+
+    static inline \(payloadType) \(name)_get_\(tagName)(\(name) self) {
+        return *(\(payloadType) *)self.pointer;
+    }
+
+    \(docRefcounting)
+    static inline void \(name)_incref(\(name) self) {
+        roc_incref((void *)self.pointer);
+    }
+
+    static inline bool \(name)_is_unique(\(name) self) {
+        return roc_is_unique((void *)self.pointer);
+    }
+
+    static inline void \(name)_decref(\(name) self) {
+        roc_decref((void *)self.pointer, _Alignof(\(payloadType)));
+    }
+
+
+    """
+
+generateResult = \buf, types, id, okId, errId ->
+    name = typeName types id
+    sizeStr = Num.toStr (getSizeRoundedToAlignment types id)
+
+    # Like a two-tag NonRecursive union, where Err comes first because tags are sorted.
+    offsetStr =
+        max (Types.size types okId) (Types.size types errId)
+        |> roundUpToAlignment (max (Types.alignment types okId) (Types.alignment types errId))
+        |> Num.toStr
+
+    okField =
+        if isZeroSized types okId then
+            ""
+        else
+            okType = typeName types okId
+
+            "\(indent)\(okType) ok;\n"
+
+    errField =
+        if isZeroSized types errId then
+            ""
+        else
+            errType = typeName types errId
+
+            "\(indent)\(errType) err;\n"
+
+    makeFn = \variant, typeId, tagId ->
+        if isZeroSized types typeId then
+            """
+            static inline \(name) \(name)_make_\(variant)(void) {
+                \(name) answer;
+
+                memset(&answer, 0, sizeof(answer));
+                answer.bytes[\(offsetStr)] = \(tagId);
+
+                return answer;
+            }
+
+            """
+        else
+            type = typeName types typeId
+
+            """
+            static inline \(name) \(name)_make_\(variant)(\(type) payload) {
+                \(name) answer;
+
+                memset(&answer, 0, sizeof(answer));
+                answer.\(variant) = payload;
+                answer.bytes[\(offsetStr)] = \(tagId);
+
+                return answer;
+            }
+
+            """
+
+    makeOk = makeFn "ok" okId "1"
+    makeErr = makeFn "err" errId "0"
+
+    buf
+    |> Str.concat "typedef union \(name) {\n\(okField)\(errField)\(indent)unsigned char bytes[\(sizeStr)];\n} \(name);\n\n"
+    |> generateLayoutChecks types id name
+    |> Str.concat
+        """
+        static inline bool \(name)_is_ok(const \(name) *self) {
+            return self->bytes[\(offsetStr)] == 1;
+        }
+
+        \(makeOk)
+        \(makeErr)
+        """
+
+generateOpaque = \buf, types, id ->
+    name = typeName types id
+    sizeStr = Num.toStr (getSizeRoundedToAlignment types id)
+    alignStr = Num.toStr (Types.alignment types id)
+
+    buf
+    |> Str.concat
+        """
+        // C glue can't look inside dictionaries and sets yet, but this has the right
+        // size and alignment to pass them between Roc and the host.
+        typedef struct \(name) {
+            _Alignas(\(alignStr)) unsigned char bytes[\(sizeStr)];
+        } \(name);
+
+
+        """
+    |> generateLayoutChecks types id name
+
+generateClosure = \buf, types, id, rocFn ->
+    if isZeroSized types rocFn.lambdaSet then
+        # There's no closure data to store, so generateClosureCall
+        # will make a function which doesn't need any.
+        buf
+    else
+        name = escapeKW rocFn.functionName
+        lambdaSet = typeName types rocFn.lambdaSet
+
+        buf
+        |> Str.concat "typedef struct \(name) {\n\(indent)\(lambdaSet) closure_data;\n} \(name);\n\n"
+        |> generateLayoutChecks types id name
+
+generateClosureCall = \buf, types, _id, rocFn ->
+    name = escapeKW rocFn.functionName
+    externName = rocFn.externName
+    hasClosureData = !(isZeroSized types rocFn.lambdaSet)
+    hasOutput = !(isZeroSized types rocFn.ret)
+    ret = if hasOutput then typeName types rocFn.ret else "void"
+
+    # Zero-sized arguments don't need to be passed in, so pass NULL for them.
+    args =
+        rocFn.args
+        |> List.mapWithIndex \argId, index ->
+            indexStr = Num.toStr index
+
+            if isZeroSized types argId then
+                { externParam: "const void *arg\(indexStr)", param: "", callArg: "NULL" }
+            else
+                type = typeName types argId
+
+                { externParam: "const \(type) *arg\(indexStr)", param: "\(type) arg\(indexStr)", callArg: "&arg\(indexStr)" }
+
+    externParams =
+        List.map args .externParam
+        |> List.append "void *closure_data"
+        |> List.append (if hasOutput then "\(ret) *output" else "void *output")
+        |> Str.joinWith ", "
+
+    params =
+        List.map args .param
+        |> List.prepend (if hasClosureData then "\(name) *self" else "")
+        |> List.keepIf \param -> !(Str.isEmpty param)
+        |> Str.joinWith ", "
+        |> \str -> if Str.isEmpty str then "void" else str
+
+    callArgs =
+        List.map args .callArg
+        |> List.append (if hasClosureData then "&self->closure_data" else "NULL")
+        |> List.append (if hasOutput then "&output" else "NULL")
+        |> Str.joinWith ", "
+
+    body =
+        if hasOutput then
+            """
+                \(ret) output;
+
+                \(externName)(\(callArgs));
+
+                return output;
+            """
+        else
+            """
+                \(externName)(\(callArgs));
+            """
+
+    Str.concat
+        buf
+        """
+        extern void \(externName)(\(externParams));
+
+        static inline \(ret) \(name)_call(\(params)) {
+        \(body)
+        }
+
+
+        """
+
+generateEntryPoints : Str, Types -> Str
+generateEntryPoints = \buf, types ->
+    List.walk (Types.entryPoints types) buf \accum, T name id -> generateEntryPoint accum types name id
+
+generateEntryPoint : Str, Types, Str, TypeId -> Str
+generateEntryPoint = \buf, types, name, id ->
+    { argIds, retId } =
+        when Types.shape types id is
+            Function rocFn -> { argIds: rocFn.args, retId: rocFn.ret }
+            _ -> { argIds: [], retId: id }
+
+    # Zero-sized arguments take up no space in the C calling convention, so leave them out.
+    args =
+        argIds
+        |> List.mapWithIndex \argId, index -> { argId, index }
+        |> List.keepIf \{ argId } -> !(isZeroSized types argId)
+        |> List.map \{ argId, index } ->
+            type = typeName types argId
+            indexStr = Num.toStr index
+
+            { param: "\(type) arg\(indexStr)", callArg: "arg\(indexStr)" }
+
+    hasOutput = !(isZeroSized types retId)
+    ret = if hasOutput then typeName types retId else "void"
+    retParam = if hasOutput then "\(ret) *ret" else "void *ret"
+    externParams =
+        List.map args .param
+        |> List.prepend retParam
+        |> Str.joinWith ", "
+    params =
+        List.map args .param
+        |> Str.joinWith ", "
+        |> \str -> if Str.isEmpty str then "void" else str
+    callArgs =
+        List.map args .callArg
+        |> List.prepend "&ret"
+        |> Str.joinWith ", "
+    body =
+        if hasOutput then
+            """
+                \(ret) ret;
+
+                roc__\(name)_1_exposed_generic(\(callArgs));
+
+                return ret;
+            """
+        else
+            """
+                unsigned char ret;
+
+                roc__\(name)_1_exposed_generic(\(callArgs));
+            """
+
+    Str.concat
+        buf
+        """
+        extern void roc__\(name)_1_exposed_generic(\(externParams));
+
+        static inline \(ret) roc_\(name)(\(params)) {
+        \(body)
+        }
+
+
+        """
+
+generateLayoutChecks = \buf, types, id, name ->
+    if containsUnsized types (Types.shape types id) then
+        buf
+    else
+        sizeStr = Num.toStr (getSizeRoundedToAlignment types id)
+        alignStr = Num.toStr (Types.alignment types id)
+
+        Str.concat
+            buf
+            """
+            _Static_assert(sizeof(\(name)) == \(sizeStr), "Roc expects \(name) to have a size of \(sizeStr) bytes");
+            _Static_assert(_Alignof(\(name)) == \(alignStr), "Roc expects \(name) to have an alignment of \(alignStr) bytes");
+
+
+            """
+
+## Whether values of this type contain an unsized value directly, rather than behind a pointer.
+## Unsized values are given a placeholder type, so the layout of these types can't be checked.
+containsUnsized = \types, type ->
+    when type is
+        Unsized -> Bool.true
+        Function rocFn -> containsUnsized types (Types.shape types rocFn.lambdaSet)
+        RocResult okId errId ->
+            containsUnsized types (Types.shape types okId)
+            || containsUnsized types (Types.shape types errId)
+
+        TagUnion (NonRecursive { tags }) ->
+            List.any tags \{ payload } ->
+                when payload is
+                    Some id -> containsUnsized types (Types.shape types id)
+                    None -> Bool.false
+
+        TagUnion (SingleTagStruct { payload: HasNoClosure fields }) ->
+            List.any fields \{ id } -> containsUnsized types (Types.shape types id)
+
+        TagUnion (SingleTagStruct { payload: HasClosure fields }) ->
+            List.any fields \{ id } -> containsUnsized types (Types.shape types id)
+
+        Struct { fields: HasNoClosure fields } | TagUnionPayload { fields: HasNoClosure fields } ->
+            List.any fields \{ id } -> containsUnsized types (Types.shape types id)
+
+        Struct { fields: HasClosure fields } | TagUnionPayload { fields: HasClosure fields } ->
+            List.any fields \{ id } -> containsUnsized types (Types.shape types id)
+
+        _ -> Bool.false
+
+prelude : Types -> Str
+prelude = \types ->
+    arch = (Types.target types).architecture
+    tagMask = pointerTagMask arch
+    align128 = Num.toStr (alignmentOf128BitNums types)
+
+    """
+    #include <stdbool.h>
+    #include <stddef.h>
+    #include <stdint.h>
+    #include <string.h>
+
+    // The host must define these, so that Roc code can allocate memory.
+    extern void *roc_alloc(size_t size, unsigned int alignment);
+    extern void *roc_realloc(void *ptr, size_t new_size, size_t old_size, unsigned int alignment);
+    extern void roc_dealloc(void *ptr, unsigned int alignment);
+
+    typedef struct RocStr {
+        char *bytes;
+        size_t len;
+        size_t capacity;
+    } RocStr;
+
+    // C doesn't have generics, so all lists have this type, regardless of what their elements are.
+    typedef struct RocList {
+        void *elements;
+        size_t length;
+        size_t capacity;
+    } RocList;
+
+    typedef void *RocBox;
+
+    typedef struct RocI128 {
+        _Alignas(\(align128)) uint64_t lo;
+        int64_t hi;
+    } RocI128;
+
+    typedef struct RocU128 {
+        _Alignas(\(align128)) uint64_t lo;
+        uint64_t hi;
+    } RocU128;
+
+    // A fixed-point decimal: a 128-bit integer which is 10^18 times the number it represents.
+    typedef struct RocDec {
+        _Alignas(\(align128)) uint64_t lo;
+        int64_t hi;
+    } RocDec;
+
+    // Heap allocations have their reference count stored just before their data.
+    // A count of ROC_REFCOUNT_ONE means there is exactly one reference to the data,
+    // and ROC_REFCOUNT_READONLY means the data is never freed (e.g. it's a constant).
+    #define ROC_REFCOUNT_ONE ((intptr_t)INTPTR_MIN)
+    #define ROC_REFCOUNT_READONLY ((intptr_t)0)
+
+    // The bits of a pointer which recursive tag unions use to store which tag they are.
+    #define ROC_TAG_MASK ((uintptr_t)\(tagMask))
+
+    static inline intptr_t *roc_refcount_ptr(void *data) {
+        return ((intptr_t *)data) - 1;
+    }
+
+    static inline size_t roc_allocation_offset(size_t alignment) {
+        return alignment > sizeof(intptr_t) ? alignment : sizeof(intptr_t);
+    }
+
+    // Allocates data with a reference count of one, using roc_alloc.
+    static inline void *roc_alloc_refcounted(size_t size, size_t alignment) {
+        size_t offset = roc_allocation_offset(alignment);
+        char *data = (char *)roc_alloc(offset + size, (unsigned int)alignment) + offset;
+
+        *roc_refcount_ptr(data) = ROC_REFCOUNT_ONE;
+
+        return data;
+    }
+
+    static inline bool roc_is_unique(void *data) {
+        return data == NULL || *roc_refcount_ptr(data) == ROC_REFCOUNT_ONE;
+    }
+
+    static inline void roc_incref(void *data) {
+        if (data != NULL) {
+            intptr_t *refcount = roc_refcount_ptr(data);
+
+            if (*refcount != ROC_REFCOUNT_READONLY) {
+                *refcount += 1;
+            }
+        }
+    }
+
+    // If this was the last reference, frees the data using roc_dealloc.
+    // This does not decrement the reference counts of anything the data refers to,
+    // so if roc_is_unique returns true, decrement those first!
+    static inline void roc_decref(void *data, size_t alignment) {
+        if (data != NULL) {
+            intptr_t *refcount = roc_refcount_ptr(data);
+
+            if (*refcount == ROC_REFCOUNT_ONE) {
+                roc_dealloc((char *)data - roc_allocation_offset(alignment), (unsigned int)alignment);
+            } else if (*refcount != ROC_REFCOUNT_READONLY) {
+                *refcount -= 1;
+            }
+        }
+    }
+
+    // Strings of fewer than sizeof(RocStr) bytes are stored inside the RocStr itself,
+    // with their length in the last byte.
+    static inline bool roc_str_is_small(const RocStr *str) {
+        return ((intptr_t)str->capacity) < 0;
+    }
+
+    static inline size_t roc_str_len(const RocStr *str) {
+        if (roc_str_is_small(str)) {
+            return ((const unsigned char *)str)[sizeof(RocStr) - 1] & 0x7F;
+        } else {
+            // The highest bit is set if this string is a slice of another one.
+            return str->len & (SIZE_MAX >> 1);
+        }
+    }
+
+    static inline const char *roc_str_bytes(const RocStr *str) {
+        return roc_str_is_small(str) ? (const char *)str : str->bytes;
+    }
+
+    // Copies the given bytes (which must be valid UTF-8) into a new RocStr.
+    static inline RocStr roc_str_from_bytes(const char *bytes, size_t len) {
+        RocStr answer;
+
+        memset(&answer, 0, sizeof(answer));
+
+        if (len < sizeof(RocStr)) {
+            memcpy(&answer, bytes, len);
+            ((unsigned char *)&answer)[sizeof(RocStr) - 1] = (unsigned char)(len | 0x80);
+        } else {
+            answer.bytes = roc_alloc_refcounted(len, _Alignof(intptr_t));
+            answer.len = len;
+            answer.capacity = len;
+
+            memcpy(answer.bytes, bytes, len);
+        }
+
+        return answer;
+    }
+
+    static inline void *roc_str_refcounted_data(const RocStr *str) {
+        if (roc_str_is_small(str)) {
+            return NULL;
+        } else if (((intptr_t)str->len) < 0) {
+            // This is a slice of another string, so its capacity holds that string's data pointer.
+            return (void *)(str->capacity << 1);
+        } else {
+            return str->bytes;
+        }
+    }
+
+    static inline void roc_str_incref(const RocStr *str) {
+        roc_incref(roc_str_refcounted_data(str));
+    }
+
+    static inline void roc_str_decref(const RocStr *str) {
+        roc_decref(roc_str_refcounted_data(str), _Alignof(intptr_t));
+    }
+
+    static inline size_t roc_list_len(const RocList *list) {
+        // The highest bit is set if this list is a slice of another one.
+        return list->length & (SIZE_MAX >> 1);
+    }
+
+    static inline void *roc_list_refcounted_data(const RocList *list) {
+        if (((intptr_t)(list->length | list->capacity)) < 0) {
+            // This is a slice of another list, so its capacity holds that list's data pointer.
+            return (void *)(list->capacity << 1);
+        } else {
+            return list->elements;
+        }
+    }
+
+    static inline void roc_list_incref(const RocList *list) {
+        roc_incref(roc_list_refcounted_data(list));
+    }
+
+    // This does not decrement the reference counts of the list's elements.
+    static inline void roc_list_decref(const RocList *list, size_t element_alignment) {
+        size_t alignment = element_alignment > _Alignof(intptr_t) ? element_alignment : _Alignof(intptr_t);
+
+        roc_decref(roc_list_refcounted_data(list), alignment);
+    }
+
+
+    """
+
+typeName = \types, id ->
+    when Types.shape types id is
+        Unit -> "void"
+        Unsized -> "RocList"
+        EmptyTagUnion -> "void"
+        RocStr -> "RocStr"
+        Bool -> "bool"
+        Num U8 -> "uint8_t"
+        Num U16 -> "uint16_t"
+        Num U32 -> "uint32_t"
+        Num U64 -> "uint64_t"
+        Num U128 -> "RocU128"
+        Num I8 -> "int8_t"
+        Num I16 -> "int16_t"
+        Num I32 -> "int32_t"
+        Num I64 -> "int64_t"
+        Num I128 -> "RocI128"
+        Num F32 -> "float"
+        Num F64 -> "double"
+        Num Dec -> "RocDec"
+        RocList _ -> "RocList"
+        RocBox _ -> "RocBox"
+        RocDict _ _ ->
+            idStr = Num.toStr id
+
+            "RocDict_\(idStr)"
+
+        RocSet _ ->
+            idStr = Num.toStr id
+
+            "RocSet_\(idStr)"
+
+        RocResult _ _ ->
+            # C doesn't have generics, so each kind of Result gets its own type.
+            idStr = Num.toStr id
+
+            "RocResult_\(idStr)"
+
+        RecursivePointer content ->
+            typeName types content
+
+        Struct { name } -> escapeKW name
+        TagUnionPayload { name } -> escapeKW name
+        TagUnion (NonRecursive { name }) -> escapeKW name
+        TagUnion (Recursive { name }) -> escapeKW name
+        TagUnion (Enumeration { name }) -> escapeKW name
+        TagUnion (NullableWrapped { name }) -> escapeKW name
+        TagUnion (NullableUnwrapped { name }) -> escapeKW name
+        TagUnion (NonNullableUnwrapped { name }) -> escapeKW name
+        TagUnion (SingleTagStruct { name }) -> escapeKW name
+        Function { functionName } -> escapeKW functionName
+
+tagsWithPayloads = \types, tags ->
+    List.keepOks tags \{ name: tagName, payload } ->
+        when nonZeroPayload types payload is
+            Some payloadId -> Ok { tagName, payloadId }
+            None -> Err NoPayload
+
+nonZeroPayload = \types, payload ->
+    when payload is
+        Some payloadId ->
+            if isZeroSized types payloadId then
+                None
+            else
+                Some payloadId
+
+        None ->
+            None
+
+isZeroSized : Types, TypeId -> Bool
+isZeroSized = \types, id ->
+    when Types.shape types id is
+        # Unit is recorded with a size of 1, but it never takes up any space.
+        Unit | EmptyTagUnion -> Bool.true
+        _ -> Types.size types id == 0
+
+discriminantType = \size ->
+    when size is
+        2 -> "uint16_t"
+        4 -> "uint32_t"
+        8 -> "uint64_t"
+        _ -> "uint8_t"
+
+alignmentOf128BitNums = \types ->
+    Types.walkShapes types 16 \answer, shape, id ->
+        when shape is
+            Num I128 | Num U128 | Num Dec -> Types.alignment types id
+            _ -> answer
+
+pointerTagMask = \arch ->
+    when arch is
+        Aarch64 | X86x64 -> "7"
+        Aarch32 | Wasm32 | X86x32 -> "3"
+
+getSizeRoundedToAlignment = \types, id ->
+    alignment = Types.alignment types id
+
+    Types.size types id
+    |> roundUpToAlignment alignment
+
+roundUpToAlignment = \width, alignment ->
+    when alignment is
+        0 -> width
+        1 -> width
+        _ ->
+            if width % alignment > 0 then
+                width + alignment - (width % alignment)
+            else
+                width
+
+max = \a, b -> if a >= b then a else b
+
+walkWithIndex = \list, originalState, f ->
+    stateWithId =
+        List.walk list { id: 0nat, state: originalState } \{ id, state }, elem ->
+            nextState = f state id elem
+
+            { id: id + 1, state: nextState }
+
+    stateWithId.state
+
+archName = \arch ->
+    when arch is
+        Aarch32 ->
+            "arm"
+
+        Aarch64 ->
+            "aarch64"
+
+        Wasm32 ->
+            "wasm32"
+
+        X86x32 ->
+            "x86"
+
+        X86x64 ->
+            "x86_64"
+
+archCondition = \arch ->
+    when arch is
+        Aarch32 ->
+            "defined(__arm__) || defined(_M_ARM)"
+
+        Aarch64 ->
+            "defined(__aarch64__) || defined(_M_ARM64)"
+
+        Wasm32 ->
+            "defined(__wasm32__)"
+
+        X86x32 ->
+            "defined(__i386__) || defined(_M_IX86)"
+
+        X86x64 ->
+            "defined(__x86_64__) || defined(_M_X64)"
+
+fileHeader =
+    """
+    // ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+    #pragma once
+
+
+    """
+
+indent = "    "
+docDiscriminant = "// Returns which tag this tag union holds. Note that this never includes a payload!"
+docRefcounting = "// Decrementing does not decrement the payload's reference counts; if this is unique, do that first!"
+
+reservedKeywords = Set.fromList [
+    "auto",
+    "bool",
+    "break",
+    "case",
+    "char",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extern",
+    "false",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "register",
+    "restrict",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "struct",
+    "switch",
+    "true",
+    "typedef",
+    "union",
+    "unsigned",
+    "void",
+    "volatile",
+    "while",
+]
+
+escapeKW = \input ->
+    # C has no raw identifiers, so add an underscore to names which are reserved keywords.
+    if Set.contains reservedKeywords input then
+        "\(input)_"
+    else
+        input
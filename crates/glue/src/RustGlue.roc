@@ -37,7 +37,9 @@ convertTypesToFile = \types ->
                     generateStruct buf types id name (nameTagUnionPayloadFields fields) Private
 
                 TagUnion (Enumeration { name, tags, size }) ->
-                    generateEnumeration buf types type name tags size
+                    buf
+                    |> generateEnumeration types type name tags size
+                    |> generateLayoutChecks types id (escapeKW name) []
 
                 TagUnion (NonRecursive { name, tags, discriminantSize, discriminantOffset }) ->
                     if !(List.isEmpty tags) then
@@ -58,16 +60,34 @@ convertTypesToFile = \types ->
                     generateNullableUnwrapped buf types id name nullTag nonNullTag nonNullPayload whichTagIsNull
 
                 TagUnion (SingleTagStruct { name, tagName, payload }) ->
-                    generateSingleTagStruct buf types name tagName payload
+                    when payload is
+                        HasNoClosure fields ->
+                            fieldNames =
+                                List.mapWithIndex fields \{ id: fieldId }, index ->
+                                    indexStr = Num.toStr index
+
+                                    { name: "f\(indexStr)", id: fieldId }
+
+                            buf
+                            |> generateSingleTagStruct types name tagName payload
+                            |> generateLayoutChecks types id (escapeKW name) fieldNames
+
+                        HasClosure _ ->
+                            generateSingleTagStruct buf types name tagName payload
 
                 TagUnion (NonNullableUnwrapped { name, tagName, payload }) ->
-                    generateNonNullableUnwrapped buf types name tagName payload 0 0 None
+                    buf
+                    |> generateNonNullableUnwrapped types name tagName payload 0 0 None
+                    |> generateLayoutChecks types id (escapeKW name) []
 
                 Function rocFn ->
                     if rocFn.isToplevel then
                         buf
                     else
-                        generateFunction buf types rocFn
+                        buf
+                        |> generateFunction types rocFn
+                        |> Str.concat "\n\n"
+                        |> generateLayoutChecks types id rocFn.functionName []
 
                 RecursivePointer _ ->
                     # This is recursively pointing to a type that should already have been added,
@@ -249,11 +269,89 @@ generateStruct = \buf, types, id, name, structFields, visibility ->
 
     structType = Types.shape types id
 
+    fields =
+        when structFields is
+            HasNoClosure xs -> List.map xs \{ name: fieldName, id: fieldId } -> { name: escapeKW fieldName, id: fieldId }
+            HasClosure xs -> List.map xs \{ name: fieldName, id: fieldId } -> { name: escapeKW fieldName, id: fieldId }
+
     buf
     |> generateDeriveStr types structType IncludeDebug
     |> Str.concat "#[repr(\(repr))]\n\(pub) struct \(escapedName) {\n"
     |> generateStructFields types Public structFields
     |> Str.concat "}\n\n"
+    |> generateLayoutChecks types id escapedName fields
+
+## Assert at compile time that the generated type has the size and alignment
+## Roc expects, and that its fields (if any) are where Roc expects them to be.
+generateLayoutChecks = \buf, types, id, name, fields ->
+    if containsUnsized types (Types.shape types id) then
+        buf
+    else
+        sizeOfSelf = Num.toStr (Types.size types id)
+        alignment = Types.alignment types id
+        alignOfSelf = Num.toStr alignment
+
+        # Roc gives types with no fields an alignment of 0, which Rust types can't have.
+        alignCheck =
+            if alignment == 0 then
+                ""
+            else
+                "const _ALIGN_CHECK_\(name): () = assert!(core::mem::align_of::<\(name)>() == \(alignOfSelf));\n"
+
+        offsetChecks =
+            List.map2 fields (Types.offsets types id) \field, offset -> { field, offset }
+            |> List.keepIf \{ field } -> !(isZeroSized types field.id)
+            |> List.map \{ field, offset } ->
+                fieldName = field.name
+                checkName = Str.replaceEach fieldName "r#" "" |> Result.withDefault fieldName
+                offsetStr = Num.toStr offset
+
+                "const _OFFSET_CHECK_\(name)_\(checkName): () = assert!(roc_offset_of!(\(name), \(fieldName)) == \(offsetStr));\n"
+            |> Str.joinWith ""
+
+        Str.concat
+            buf
+            """
+            const _SIZE_CHECK_\(name): () = assert!(core::mem::size_of::<\(name)>() == \(sizeOfSelf));
+            \(alignCheck)\(offsetChecks)
+
+            """
+
+## Whether values of this type contain an unsized value directly, rather than behind a pointer.
+## Unsized values are given a placeholder type, so the layout of these types can't be checked.
+containsUnsized = \types, type ->
+    when type is
+        Unsized -> Bool.true
+        Function rocFn -> containsUnsized types (Types.shape types rocFn.lambdaSet)
+        RocResult okId errId ->
+            containsUnsized types (Types.shape types okId)
+            || containsUnsized types (Types.shape types errId)
+
+        TagUnion (NonRecursive { tags }) ->
+            List.any tags \{ payload } ->
+                when payload is
+                    Some id -> containsUnsized types (Types.shape types id)
+                    None -> Bool.false
+
+        TagUnion (SingleTagStruct { payload: HasNoClosure fields }) ->
+            List.any fields \{ id } -> containsUnsized types (Types.shape types id)
+
+        TagUnion (SingleTagStruct { payload: HasClosure fields }) ->
+            List.any fields \{ id } -> containsUnsized types (Types.shape types id)
+
+        Struct { fields: HasNoClosure fields } | TagUnionPayload { fields: HasNoClosure fields } ->
+            List.any fields \{ id } -> containsUnsized types (Types.shape types id)
+
+        Struct { fields: HasClosure fields } | TagUnionPayload { fields: HasClosure fields } ->
+            List.any fields \{ id } -> containsUnsized types (Types.shape types id)
+
+        _ -> Bool.false
+
+isZeroSized = \types, id ->
+    when Types.shape types id is
+        # Unit is recorded with a size of 1, but it never takes up any space.
+        Unit | EmptyTagUnion -> Bool.true
+        _ -> Types.size types id == 0
 
 generateStructFields = \buf, types, visibility, structFields ->
     when structFields is
@@ -1915,6 +2013,18 @@ fileHeader =
     #![allow(clippy::needless_borrow)]
     #![allow(clippy::clone_on_copy)]
 
+    /// Like `core::mem::offset_of!`, which isn't available in older versions of Rust.
+    /// Used to check that fields are where Roc expects them to be.
+    #[allow(unused_macros)]
+    macro_rules! roc_offset_of {
+        ($type:ty, $field:tt) => {{
+            let uninit = core::mem::MaybeUninit::<$type>::uninit();
+            let base = uninit.as_ptr();
+
+            unsafe { (core::ptr::addr_of!((*base).$field) as *const u8).offset_from(base as *const u8) as usize }
+        }};
+    }
+
 
 
     """
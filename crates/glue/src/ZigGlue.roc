@@ -0,0 +1,1223 @@
+app "zig-glue"
+    packages { pf: "../platform/main.roc" }
+    imports [pf.Types.{ Types }, pf.Shape.{ Shape, RocFn }, pf.File.{ File }, pf.TypeId.{ TypeId }]
+    provides [makeGlue] to pf
+
+makeGlue : List Types -> Result (List File) Str
+makeGlue = \typesByArch ->
+    archImports =
+        List.walk typesByArch "" \content, types ->
+            arch = (Types.target types).architecture
+            archStr = archName arch
+            zigArch = zigArchName arch
+
+            Str.concat content "\(indent).\(zigArch) => @import(\"\(archStr).zig\"),\n"
+
+    mainFile =
+        """
+        \(fileHeader)
+        const builtin = @import("builtin");
+
+        pub usingnamespace switch (builtin.cpu.arch) {
+        \(archImports)    else => @compileError("This architecture was not included when running `roc glue`."),
+        };
+
+        """
+
+    typesByArch
+    |> List.map convertTypesToFile
+    |> List.append { name: "main.zig", content: mainFile }
+    |> Ok
+
+convertTypesToFile : Types -> File
+convertTypesToFile = \types ->
+    archStr = archName (Types.target types).architecture
+
+    # Zig resolves declarations lazily, so unlike in C, these can go in any order.
+    content =
+        Types.walkShapes types (Str.concat fileHeader (prelude types)) \buf, _, id ->
+            generateDeclaration buf types id
+        |> generateEntryPoints types
+
+    { name: "\(archStr).zig", content }
+
+generateDeclaration : Str, Types, TypeId -> Str
+generateDeclaration = \buf, types, id ->
+    when Types.shape types id is
+        Struct { name, fields } ->
+            generateStruct buf types id (escapeKW name) (structFields fields)
+
+        TagUnionPayload { name, fields } ->
+            generateStruct buf types id (escapeKW name) (payloadFields fields)
+
+        TagUnion (SingleTagStruct { name, payload }) ->
+            generateStruct buf types id (escapeKW name) (singleTagPayloadFields payload)
+
+        TagUnion (Enumeration { name, tags, size }) ->
+            escapedName = escapeKW name
+
+            buf
+            |> generateEnumeration escapedName tags size
+            |> generateLayoutChecks types id escapedName
+
+        TagUnion (NonRecursive { name, tags, discriminantSize, discriminantOffset }) ->
+            if List.isEmpty tags then
+                buf
+            else
+                generateNonRecursiveTagUnion buf types id (escapeKW name) tags discriminantSize discriminantOffset
+
+        TagUnion (Recursive { name, tags, discriminantSize }) ->
+            escapedName = escapeKW name
+
+            buf
+            |> generateEnumeration "discriminant_\(escapedName)" (List.map tags .name) discriminantSize
+            |> generateRecursiveTagUnion types id escapedName tags discriminantSize None
+
+        TagUnion (NullableWrapped { name, indexOfNullTag, tags, discriminantSize }) ->
+            escapedName = escapeKW name
+
+            buf
+            |> generateEnumeration "discriminant_\(escapedName)" (List.map tags .name) discriminantSize
+            |> generateRecursiveTagUnion types id escapedName tags discriminantSize (Some indexOfNullTag)
+
+        TagUnion (NullableUnwrapped { name, nullTag, nonNullTag, nonNullPayload, whichTagIsNull }) ->
+            escapedName = escapeKW name
+            tagNames =
+                when whichTagIsNull is
+                    FirstTagIsNull -> [nullTag, nonNullTag]
+                    SecondTagIsNull -> [nonNullTag, nullTag]
+
+            buf
+            |> generateEnumeration "discriminant_\(escapedName)" tagNames 1
+            |> generateNullableUnwrapped types id escapedName nullTag nonNullTag nonNullPayload
+
+        TagUnion (NonNullableUnwrapped { name, tagName, payload }) ->
+            generateNonNullableUnwrapped buf types id (escapeKW name) tagName payload
+
+        RocResult okId errId ->
+            generateResult buf types id okId errId
+
+        RocDict _ _ | RocSet _ ->
+            generateOpaque buf types id
+
+        Function rocFn ->
+            if rocFn.isToplevel then
+                buf
+            else
+                generateClosure buf types id rocFn
+
+        RecursivePointer _ | Unit | Unsized | EmptyTagUnion | Num _ | Bool | RocStr | RocList _ | RocBox _ ->
+            # These are either declared in the prelude, or refer to types declared elsewhere.
+            buf
+
+structFields = \fields ->
+    when fields is
+        HasNoClosure xs -> List.map xs \{ name, id } -> { name: escapeKW name, rawName: name, id }
+        HasClosure xs -> List.map xs \{ name, id } -> { name: escapeKW name, rawName: name, id }
+
+payloadFields = \fields ->
+    # Tag union payloads have numbered fields, so we prefix them
+    # with an "f" because Zig doesn't allow struct fields to be numbers.
+    when fields is
+        HasNoClosure xs -> List.map xs \{ name, id } -> { name: "f\(name)", rawName: "f\(name)", id }
+        HasClosure xs -> List.map xs \{ name, id } -> { name: "f\(name)", rawName: "f\(name)", id }
+
+singleTagPayloadFields = \payload ->
+    when payload is
+        HasClosure xs -> List.map xs \{ name, id } -> { name: escapeKW name, rawName: name, id }
+        HasNoClosure xs ->
+            List.mapWithIndex xs \{ id }, index ->
+                indexStr = Num.toStr index
+
+                { name: "f\(indexStr)", rawName: "f\(indexStr)", id }
+
+generateStruct : Str, Types, TypeId, Str, List { name : Str, rawName : Str, id : TypeId } -> Str
+generateStruct = \buf, types, id, name, fields ->
+    if isZeroSized types id then
+        # Zero-sized types can't be in extern structs, so these get left out wherever they would be used.
+        buf
+    else
+        fieldDecls =
+            fields
+            |> List.keepIf \field -> !(isZeroSized types field.id)
+            |> List.map \field ->
+                type = fieldType types field.id
+                fieldName = field.name
+
+                "\(indent)\(fieldName): \(type),\n"
+            |> Str.joinWith ""
+
+        buf
+        |> Str.concat "pub const \(name) = extern struct {\n\(fieldDecls)};\n\n"
+        |> generateLayoutChecks types id name
+        |> generateOffsetChecks types id name fields
+
+generateOffsetChecks = \buf, types, id, name, fields ->
+    checks =
+        List.map2 fields (Types.offsets types id) \field, offset -> { field, offset }
+        |> List.keepIf \{ field } -> !(isZeroSized types field.id)
+        |> List.map \{ field, offset } ->
+            fieldName = field.rawName
+            offsetStr = Num.toStr offset
+
+            "\(indent)if (@offsetOf(\(name), \"\(fieldName)\") != \(offsetStr)) @compileError(\"Roc expects \(name).\(fieldName) to be at an offset of \(offsetStr) bytes\");\n"
+        |> Str.joinWith ""
+
+    if Str.isEmpty checks then
+        buf
+    else
+        Str.concat buf "comptime {\n\(checks)}\n\n"
+
+generateEnumeration : Str, Str, List Str, U32 -> Str
+generateEnumeration = \buf, name, tags, size ->
+    intType = discriminantType size
+    variants =
+        tags
+        |> List.mapWithIndex \tag, index ->
+            indexStr = Num.toStr index
+
+            "\(indent)\(tag) = \(indexStr),\n"
+        |> Str.joinWith ""
+
+    Str.concat buf "pub const \(name) = enum(\(intType)) {\n\(variants)};\n\n"
+
+generateNonRecursiveTagUnion = \buf, types, id, name, tags, discriminantSize, discriminantOffset ->
+    discriminantName = "discriminant_\(name)"
+    unionName = "union_\(name)"
+    intType = discriminantType discriminantSize
+    offsetStr = Num.toStr discriminantOffset
+    sizeStr = Num.toStr (getSizeRoundedToAlignment types id)
+    payloadTags = tagsWithPayloads types tags
+
+    unionDecl =
+        if List.isEmpty payloadTags then
+            ""
+        else
+            unionFields =
+                payloadTags
+                |> List.map \{ tagName, payloadId } ->
+                    type = fieldType types payloadId
+
+                    "\(indent)\(tagName): \(type),\n"
+                |> Str.joinWith ""
+
+            "pub const \(unionName) = extern union {\n\(unionFields)};\n\n"
+
+    payloadField =
+        if List.isEmpty payloadTags then
+            ""
+        else
+            "\(indent)payload: \(unionName),\n"
+
+    tagFunctions =
+        tags
+        |> List.map \{ name: tagName, payload } ->
+            isFn =
+                """
+                    pub fn is\(tagName)(self: \(name)) bool {
+                        return self.discriminant() == .\(tagName);
+                    }
+
+                """
+
+            when nonZeroPayload types payload is
+                Some payloadId ->
+                    type = typeName types payloadId
+
+                    """
+                    \(isFn)
+                        pub fn make\(tagName)(payload: \(type)) \(name) {
+                            var answer = std.mem.zeroes(\(name));
+
+                            answer.payload.\(tagName) = payload;
+                            answer.setDiscriminant(.\(tagName));
+
+                            return answer;
+                        }
+
+                        pub fn get\(tagName)(self: \(name)) \(type) {
+                            return self.payload.\(tagName);
+                        }
+
+                    """
+
+                None ->
+                    """
+                    \(isFn)
+                        pub fn make\(tagName)() \(name) {
+                            var answer = std.mem.zeroes(\(name));
+
+                            answer.setDiscriminant(.\(tagName));
+
+                            return answer;
+                        }
+
+                    """
+        |> Str.joinWith "\n"
+
+    buf
+    |> generateEnumeration discriminantName (List.map tags .name) discriminantSize
+    |> Str.concat unionDecl
+    |> Str.concat
+        """
+        // The discriminant is stored after the largest payload, in bytes that are
+        // padding for any smaller payloads.
+        pub const \(name) = extern union {
+        \(payloadField)    bytes: [\(sizeStr)]u8,
+
+            \(docDiscriminant)
+            pub fn discriminant(self: \(name)) \(discriminantName) {
+                return @intToEnum(\(discriminantName), @ptrCast(*align(1) const \(intType), &self.bytes[\(offsetStr)]).*);
+            }
+
+            fn setDiscriminant(self: *\(name), tag: \(discriminantName)) void {
+                @ptrCast(*align(1) \(intType), &self.bytes[\(offsetStr)]).* = @enumToInt(tag);
+            }
+
+        \(tagFunctions)};
+
+
+        """
+    |> generateLayoutChecks types id name
+
+generateRecursiveTagUnion = \buf, types, id, name, tags, discriminantSize, nullTagIndex ->
+    discriminantName = "discriminant_\(name)"
+    intType = discriminantType discriminantSize
+    unionName = "union_\(name)"
+    payloadTags = tagsWithPayloads types tags
+
+    unionFields =
+        if List.isEmpty payloadTags then
+            "\(indent)empty: u8,\n"
+        else
+            payloadTags
+            |> List.map \{ tagName, payloadId } ->
+                type = fieldType types payloadId
+
+                "\(indent)\(tagName): \(type),\n"
+            |> Str.joinWith ""
+
+    nullCheck =
+        when nullTagIndex is
+            Some index ->
+                indexStr = Num.toStr index
+
+                """
+                        if (self.pointer == 0) {
+                            return @intToEnum(\(discriminantName), \(indexStr));
+                        }
+
+
+                """
+
+            None ->
+                ""
+
+    tagFunctions =
+        tags
+        |> List.mapWithIndex \{ name: tagName, payload }, index ->
+            if Some (Num.intCast index) == nullTagIndex then
+                """
+                    pub fn is\(tagName)(self: \(name)) bool {
+                        return self.pointer == 0;
+                    }
+
+                    pub fn make\(tagName)() \(name) {
+                        return .{ .pointer = 0 };
+                    }
+
+                """
+            else
+                isFn =
+                    """
+                        pub fn is\(tagName)(self: \(name)) bool {
+                            return self.discriminant() == .\(tagName);
+                        }
+
+                    """
+
+                when nonZeroPayload types payload is
+                    Some payloadId ->
+                        type = typeName types payloadId
+
+                        """
+                        \(isFn)
+                            pub fn make\(tagName)(payload: \(type)) \(name) {
+                                const data = rocAllocRefcounted(@sizeOf(\(unionName)), @alignOf(\(unionName)));
+
+                                @intToPtr(*\(unionName), data).\(tagName) = payload;
+
+                                return .{ .pointer = data | @enumToInt(\(discriminantName).\(tagName)) };
+                            }
+
+                            pub fn get\(tagName)(self: \(name)) \(type) {
+                                return self.payloadPtr().\(tagName);
+                            }
+
+                        """
+
+                    None ->
+                        """
+                        \(isFn)
+                            pub fn make\(tagName)() \(name) {
+                                const data = rocAllocRefcounted(@sizeOf(\(unionName)), @alignOf(\(unionName)));
+
+                                return .{ .pointer = data | @enumToInt(\(discriminantName).\(tagName)) };
+                            }
+
+                        """
+        |> Str.joinWith "\n"
+
+    buf
+    |> Str.concat
+        """
+        pub const \(unionName) = extern union {
+        \(unionFields)};
+
+        // A pointer to a reference-counted heap allocation, which stores
+        // which tag it is in its lowest bits.
+        pub const \(name) = extern struct {
+            pointer: usize,
+
+            \(docDiscriminant)
+            pub fn discriminant(self: \(name)) \(discriminantName) {
+        \(nullCheck)        return @intToEnum(\(discriminantName), @truncate(\(intType), self.pointer & tag_mask));
+            }
+
+            fn payloadPtr(self: \(name)) *\(unionName) {
+                return @intToPtr(*\(unionName), self.pointer & ~tag_mask);
+            }
+
+        \(tagFunctions)
+            \(docRefcounting)
+            pub fn incref(self: \(name)) void {
+                rocIncref(self.pointer & ~tag_mask);
+            }
+
+            pub fn isUnique(self: \(name)) bool {
+                return rocIsUnique(self.pointer & ~tag_mask);
+            }
+
+            pub fn decref(self: \(name)) void {
+                rocDecref(self.pointer & ~tag_mask, @alignOf(\(unionName)));
+            }
+        };
+
+
+        """
+    |> generateLayoutChecks types id name
+
+generateNullableUnwrapped = \buf, types, id, name, nullTag, nonNullTag, nonNullPayload ->
+    discriminantName = "discriminant_\(name)"
+    payloadType = typeName types nonNullPayload
+    pointerFunctions = generatePointerFunctions name nonNullTag payloadType
+
+    buf
+    |> Str.concat
+        """
+        // A pointer to a reference-counted heap allocation, or null.
+        pub const \(name) = extern struct {
+            pointer: usize,
+
+            \(docDiscriminant)
+            pub fn discriminant(self: \(name)) \(discriminantName) {
+                return if (self.pointer == 0) .\(nullTag) else .\(nonNullTag);
+            }
+
+            pub fn is\(nullTag)(self: \(name)) bool {
+                return self.pointer == 0;
+            }
+
+            pub fn is\(nonNullTag)(self: \(name)) bool {
+                return self.pointer != 0;
+            }
+
+            pub fn make\(nullTag)() \(name) {
+                return .{ .pointer = 0 };
+            }
+
+        \(pointerFunctions)};
+
+
+        """
+    |> generateLayoutChecks types id name
+
+generateNonNullableUnwrapped = \buf, types, id, name, tagName, payload ->
+    payloadType = typeName types payload
+    pointerFunctions = generatePointerFunctions name tagName payloadType
+
+    buf
+    |> Str.concat
+        """
+        // A pointer to a reference-counted heap allocation.
+        pub const \(name) = extern struct {
+            pointer: usize,
+
+        \(pointerFunctions)};
+
+
+        """
+    |> generateLayoutChecks types id name
+
+generatePointerFunctions = \name, tagName, payloadType ->
+    """
+        pub fn make\(tagName)(payload: \(payloadType)) \(name) {
+            const data = rocAllocRefcounted(@sizeOf(\(payloadType)), @alignOf(\(payloadType)));
+
+            @intToPtr(*\(payloadType), data).* = payload;
+
+            return .{ .pointer = data };
+        }
+
+        pub fn get\(tagName)(self: \(name)) \(payloadType) {
+            return @intToPtr(*\(payloadType), self.pointer).*;
+        }
+
+        \(docRefcounting)
+        pub fn incref(self: \(name)) void {
+            rocIncref(self.pointer);
+        }
+
+        pub fn isUnique(self: \(name)) bool {
+            return rocIsUnique(self.pointer);
+        }
+
+        pub fn decref(self: \(name)) void {
+            rocDecref(self.pointer, @alignOf(\(payloadType)));
+        }
+
+    """
+
+generateResult = \buf, types, id, okId, errId ->
+    name = typeName types id
+    sizeStr = Num.toStr (getSizeRoundedToAlignment types id)
+
+    # Like a two-tag NonRecursive union, where Err comes first because tags are sorted.
+    offsetStr =
+        max (Types.size types okId) (Types.size types errId)
+        |> roundUpToAlignment (max (Types.alignment types okId) (Types.alignment types errId))
+        |> Num.toStr
+
+    okField =
+        if isZeroSized types okId then
+            ""
+        else
+            okType = fieldType types okId
+
+            "\(indent)ok: \(okType),\n"
+
+    errField =
+        if isZeroSized types errId then
+            ""
+        else
+            errType = fieldType types errId
+
+            "\(indent)err: \(errType),\n"
+
+    makeFn = \tagName, variant, typeId, tagId ->
+        if isZeroSized types typeId then
+            """
+                pub fn make\(tagName)() \(name) {
+                    var answer = std.mem.zeroes(\(name));
+
+                    answer.bytes[\(offsetStr)] = \(tagId);
+
+                    return answer;
+                }
+
+            """
+        else
+            type = typeName types typeId
+
+            """
+                pub fn make\(tagName)(payload: \(type)) \(name) {
+                    var answer = std.mem.zeroes(\(name));
+
+                    answer.\(variant) = payload;
+                    answer.bytes[\(offsetStr)] = \(tagId);
+
+                    return answer;
+                }
+
+            """
+
+    makeOk = makeFn "Ok" "ok" okId "1"
+    makeErr = makeFn "Err" "err" errId "0"
+
+    buf
+    |> Str.concat
+        """
+        pub const \(name) = extern union {
+        \(okField)\(errField)    bytes: [\(sizeStr)]u8,
+
+            pub fn isOk(self: \(name)) bool {
+                return self.bytes[\(offsetStr)] == 1;
+            }
+
+        \(makeOk)
+        \(makeErr)};
+
+
+        """
+    |> generateLayoutChecks types id name
+
+generateOpaque = \buf, types, id ->
+    name = typeName types id
+    sizeStr = Num.toStr (getSizeRoundedToAlignment types id)
+    alignStr = Num.toStr (Types.alignment types id)
+
+    buf
+    |> Str.concat
+        """
+        // Zig glue can't look inside dictionaries and sets yet, but this has the right
+        // size and alignment to pass them between Roc and the host.
+        pub const \(name) = extern struct {
+            bytes: [\(sizeStr)]u8 align(\(alignStr)),
+        };
+
+
+        """
+    |> generateLayoutChecks types id name
+
+generateClosure = \buf, types, id, rocFn ->
+    name = escapeKW rocFn.functionName
+    externName = rocFn.externName
+    hasClosureData = !(isZeroSized types rocFn.lambdaSet)
+    hasOutput = !(isZeroSized types rocFn.ret)
+    ret = if hasOutput then typeName types rocFn.ret else "void"
+
+    # Zero-sized arguments don't need to be passed in, so pass null for them.
+    args =
+        rocFn.args
+        |> List.mapWithIndex \argId, index ->
+            indexStr = Num.toStr index
+
+            if isZeroSized types argId then
+                { externParam: "arg\(indexStr): ?*const anyopaque", param: "", callArg: "null" }
+            else
+                type = typeName types argId
+
+                { externParam: "arg\(indexStr): *const \(type)", param: "arg\(indexStr): \(type)", callArg: "&arg\(indexStr)" }
+
+    externParams =
+        List.map args .externParam
+        |> List.append "closure_data: ?*anyopaque"
+        |> List.append (if hasOutput then "output: *\(ret)" else "output: ?*anyopaque")
+        |> Str.joinWith ", "
+
+    params =
+        List.map args .param
+        |> List.prepend (if hasClosureData then "self: *\(name)" else "")
+        |> List.keepIf \param -> !(Str.isEmpty param)
+        |> Str.joinWith ", "
+
+    callArgs =
+        List.map args .callArg
+        |> List.append (if hasClosureData then "&self.closure_data" else "null")
+        |> List.append (if hasOutput then "&output" else "null")
+        |> Str.joinWith ", "
+
+    body =
+        if hasOutput then
+            """
+                    var output: \(ret) = undefined;
+
+                    \(externName)(\(callArgs));
+
+                    return output;
+            """
+        else
+            """
+                    \(externName)(\(callArgs));
+            """
+
+    callFn =
+        """
+            pub fn call(\(params)) \(ret) {
+        \(body)
+            }
+        """
+
+    decl =
+        if hasClosureData then
+            lambdaSet = fieldType types rocFn.lambdaSet
+
+            """
+            pub const \(name) = extern struct {
+                closure_data: \(lambdaSet),
+
+            \(callFn)
+            };
+
+
+            """
+            |> generateLayoutChecks types id name
+        else
+            # There's no closure data to store, so this is only a namespace for the call function.
+            """
+            pub const \(name) = struct {
+            \(callFn)
+            };
+
+
+            """
+
+    buf
+    |> Str.concat "extern fn \(externName)(\(externParams)) callconv(.C) void;\n\n"
+    |> Str.concat decl
+
+generateEntryPoints : Str, Types -> Str
+generateEntryPoints = \buf, types ->
+    List.walk (Types.entryPoints types) buf \accum, T name id -> generateEntryPoint accum types name id
+
+generateEntryPoint : Str, Types, Str, TypeId -> Str
+generateEntryPoint = \buf, types, name, id ->
+    { argIds, retId } =
+        when Types.shape types id is
+            Function rocFn -> { argIds: rocFn.args, retId: rocFn.ret }
+            _ -> { argIds: [], retId: id }
+
+    # Zero-sized arguments take up no space in the C calling convention, so leave them out.
+    args =
+        argIds
+        |> List.mapWithIndex \argId, index -> { argId, index }
+        |> List.keepIf \{ argId } -> !(isZeroSized types argId)
+        |> List.map \{ argId, index } ->
+            type = typeName types argId
+            indexStr = Num.toStr index
+
+            { param: "arg\(indexStr): \(type)", callArg: "arg\(indexStr)" }
+
+    hasOutput = !(isZeroSized types retId)
+    ret = if hasOutput then typeName types retId else "void"
+    retParam = if hasOutput then "ret: *\(ret)" else "ret: *u8"
+    externParams =
+        List.map args .param
+        |> List.prepend retParam
+        |> Str.joinWith ", "
+    params =
+        List.map args .param
+        |> Str.joinWith ", "
+    callArgs =
+        List.map args .callArg
+        |> List.prepend "&ret"
+        |> Str.joinWith ", "
+    body =
+        if hasOutput then
+            """
+                var ret: \(ret) = undefined;
+
+                roc__\(name)_1_exposed_generic(\(callArgs));
+
+                return ret;
+            """
+        else
+            """
+                var ret: u8 = undefined;
+
+                roc__\(name)_1_exposed_generic(\(callArgs));
+            """
+    escapedName = escapeKW name
+
+    Str.concat
+        buf
+        """
+        extern fn roc__\(name)_1_exposed_generic(\(externParams)) callconv(.C) void;
+
+        pub fn \(escapedName)(\(params)) \(ret) {
+        \(body)
+        }
+
+
+        """
+
+generateLayoutChecks = \buf, types, id, name ->
+    if containsUnsized types (Types.shape types id) then
+        buf
+    else
+        sizeStr = Num.toStr (getSizeRoundedToAlignment types id)
+        alignStr = Num.toStr (Types.alignment types id)
+
+        Str.concat
+            buf
+            """
+            comptime {
+                if (@sizeOf(\(name)) != \(sizeStr)) @compileError("Roc expects \(name) to have a size of \(sizeStr) bytes");
+                if (@alignOf(\(name)) != \(alignStr)) @compileError("Roc expects \(name) to have an alignment of \(alignStr) bytes");
+            }
+
+
+            """
+
+## Whether values of this type contain an unsized value directly, rather than behind a pointer.
+## Unsized values are given a placeholder type, so the layout of these types can't be checked.
+containsUnsized = \types, type ->
+    when type is
+        Unsized -> Bool.true
+        Function rocFn -> containsUnsized types (Types.shape types rocFn.lambdaSet)
+        RocResult okId errId ->
+            containsUnsized types (Types.shape types okId)
+            || containsUnsized types (Types.shape types errId)
+
+        TagUnion (NonRecursive { tags }) ->
+            List.any tags \{ payload } ->
+                when payload is
+                    Some id -> containsUnsized types (Types.shape types id)
+                    None -> Bool.false
+
+        TagUnion (SingleTagStruct { payload: HasNoClosure fields }) ->
+            List.any fields \{ id } -> containsUnsized types (Types.shape types id)
+
+        TagUnion (SingleTagStruct { payload: HasClosure fields }) ->
+            List.any fields \{ id } -> containsUnsized types (Types.shape types id)
+
+        Struct { fields: HasNoClosure fields } | TagUnionPayload { fields: HasNoClosure fields } ->
+            List.any fields \{ id } -> containsUnsized types (Types.shape types id)
+
+        Struct { fields: HasClosure fields } | TagUnionPayload { fields: HasClosure fields } ->
+            List.any fields \{ id } -> containsUnsized types (Types.shape types id)
+
+        _ -> Bool.false
+
+prelude : Types -> Str
+prelude = \types ->
+    arch = (Types.target types).architecture
+    tagMask = pointerTagMask arch
+    align128 = Num.toStr (alignmentOf128BitNums types)
+
+    """
+    const std = @import("std");
+
+    // The host must define these, so that Roc code can allocate memory.
+    extern fn roc_alloc(size: usize, alignment: u32) callconv(.C) ?*anyopaque;
+    extern fn roc_dealloc(c_ptr: *anyopaque, alignment: u32) callconv(.C) void;
+
+    // Heap allocations have their reference count stored just before their data.
+    // A count of REFCOUNT_ONE means there is exactly one reference to the data,
+    // and REFCOUNT_READONLY means the data is never freed (e.g. it's a constant).
+    pub const REFCOUNT_ONE: isize = std.math.minInt(isize);
+    pub const REFCOUNT_READONLY: isize = 0;
+
+    // The bits of a pointer which recursive tag unions use to store which tag they are.
+    const tag_mask: usize = \(tagMask);
+
+    fn allocationOffset(alignment: u32) usize {
+        return std.math.max(alignment, @sizeOf(usize));
+    }
+
+    pub fn refcountPtr(data: usize) *isize {
+        return @intToPtr(*isize, data - @sizeOf(isize));
+    }
+
+    // Allocates data with a reference count of one using roc_alloc, and returns its address.
+    pub fn rocAllocRefcounted(size: usize, alignment: u32) usize {
+        const offset = allocationOffset(alignment);
+        const allocation = roc_alloc(offset + size, alignment) orelse @panic("roc_alloc returned null");
+        const data = @ptrToInt(allocation) + offset;
+
+        refcountPtr(data).* = REFCOUNT_ONE;
+
+        return data;
+    }
+
+    pub fn rocIsUnique(data: usize) bool {
+        return data == 0 or refcountPtr(data).* == REFCOUNT_ONE;
+    }
+
+    pub fn rocIncref(data: usize) void {
+        if (data == 0) return;
+
+        const refcount = refcountPtr(data);
+
+        if (refcount.* != REFCOUNT_READONLY) {
+            refcount.* += 1;
+        }
+    }
+
+    // If this was the last reference, frees the data using roc_dealloc.
+    // This does not decrement the reference counts of anything the data refers to,
+    // so if rocIsUnique returns true, decrement those first!
+    pub fn rocDecref(data: usize, alignment: u32) void {
+        if (data == 0) return;
+
+        const refcount = refcountPtr(data);
+
+        if (refcount.* == REFCOUNT_ONE) {
+            roc_dealloc(@intToPtr(*anyopaque, data - allocationOffset(alignment)), alignment);
+        } else if (refcount.* != REFCOUNT_READONLY) {
+            refcount.* -= 1;
+        }
+    }
+
+    pub const RocStr = extern struct {
+        bytes: ?[*]u8,
+        length: usize,
+        capacity: usize,
+
+        // Strings of fewer than @sizeOf(RocStr) bytes are stored inside the RocStr itself,
+        // with their length in the last byte.
+        pub fn isSmall(self: *const RocStr) bool {
+            return @bitCast(isize, self.capacity) < 0;
+        }
+
+        pub fn len(self: *const RocStr) usize {
+            if (self.isSmall()) {
+                return @ptrCast([*]const u8, self)[@sizeOf(RocStr) - 1] & 0x7F;
+            } else {
+                // The highest bit is set if this string is a slice of another one.
+                return self.length & (std.math.maxInt(usize) >> 1);
+            }
+        }
+
+        pub fn asSlice(self: *const RocStr) []const u8 {
+            if (self.isSmall()) {
+                return @ptrCast([*]const u8, self)[0..self.len()];
+            } else if (self.bytes) |bytes| {
+                return bytes[0..self.len()];
+            } else {
+                return "";
+            }
+        }
+
+        // Copies the given bytes (which must be valid UTF-8) into a new RocStr.
+        pub fn fromSlice(slice: []const u8) RocStr {
+            var answer = std.mem.zeroes(RocStr);
+
+            if (slice.len < @sizeOf(RocStr)) {
+                const bytes = @ptrCast([*]u8, &answer);
+
+                std.mem.copy(u8, bytes[0..slice.len], slice);
+                bytes[@sizeOf(RocStr) - 1] = @intCast(u8, slice.len) | 0x80;
+            } else {
+                const data = @intToPtr([*]u8, rocAllocRefcounted(slice.len, @alignOf(usize)));
+
+                std.mem.copy(u8, data[0..slice.len], slice);
+                answer.bytes = data;
+                answer.length = slice.len;
+                answer.capacity = slice.len;
+            }
+
+            return answer;
+        }
+
+        fn refcountedData(self: *const RocStr) usize {
+            if (self.isSmall()) {
+                return 0;
+            } else if (@bitCast(isize, self.length) < 0) {
+                // This is a slice of another string, so its capacity holds that string's data pointer.
+                return self.capacity << 1;
+            } else if (self.bytes) |bytes| {
+                return @ptrToInt(bytes);
+            } else {
+                return 0;
+            }
+        }
+
+        pub fn incref(self: *const RocStr) void {
+            rocIncref(self.refcountedData());
+        }
+
+        pub fn decref(self: *const RocStr) void {
+            rocDecref(self.refcountedData(), @alignOf(usize));
+        }
+    };
+
+    pub fn RocList(comptime T: type) type {
+        return extern struct {
+            elements: ?[*]T,
+            length: usize,
+            capacity: usize,
+
+            const Self = @This();
+
+            pub fn len(self: Self) usize {
+                // The highest bit is set if this list is a slice of another one.
+                return self.length & (std.math.maxInt(usize) >> 1);
+            }
+
+            pub fn asSlice(self: Self) []const T {
+                if (self.elements) |elements| {
+                    return elements[0..self.len()];
+                } else {
+                    return &[_]T{};
+                }
+            }
+
+            fn refcountedData(self: Self) usize {
+                if (@bitCast(isize, self.length | self.capacity) < 0) {
+                    // This is a slice of another list, so its capacity holds that list's data pointer.
+                    return self.capacity << 1;
+                } else if (self.elements) |elements| {
+                    return @ptrToInt(elements);
+                } else {
+                    return 0;
+                }
+            }
+
+            pub fn incref(self: Self) void {
+                rocIncref(self.refcountedData());
+            }
+
+            // This does not decrement the reference counts of the list's elements.
+            pub fn decref(self: Self) void {
+                rocDecref(self.refcountedData(), std.math.max(@alignOf(T), @alignOf(usize)));
+            }
+        };
+    }
+
+    // A fixed-point decimal: a 128-bit integer which is 10^18 times the number it represents.
+    pub const RocDec = extern struct {
+        num: i128 align(\(align128)),
+    };
+
+
+    """
+
+typeName = \types, id ->
+    when Types.shape types id is
+        Unit -> "void"
+        Unsized -> "RocList(u8)"
+        EmptyTagUnion -> "void"
+        RocStr -> "RocStr"
+        Bool -> "bool"
+        Num U8 -> "u8"
+        Num U16 -> "u16"
+        Num U32 -> "u32"
+        Num U64 -> "u64"
+        Num U128 -> "u128"
+        Num I8 -> "i8"
+        Num I16 -> "i16"
+        Num I32 -> "i32"
+        Num I64 -> "i64"
+        Num I128 -> "i128"
+        Num F32 -> "f32"
+        Num F64 -> "f64"
+        Num Dec -> "RocDec"
+        RocList elem ->
+            elemType = if isZeroSized types elem then "u8" else typeName types elem
+
+            "RocList(\(elemType))"
+
+        RocBox elem ->
+            if isZeroSized types elem then
+                "*anyopaque"
+            else
+                elemType = typeName types elem
+
+                "*\(elemType)"
+
+        RocDict _ _ ->
+            idStr = Num.toStr id
+
+            "RocDict_\(idStr)"
+
+        RocSet _ ->
+            idStr = Num.toStr id
+
+            "RocSet_\(idStr)"
+
+        RocResult _ _ ->
+            # Zero-sized types can't go in extern unions, so each kind of Result gets its own type.
+            idStr = Num.toStr id
+
+            "RocResult_\(idStr)"
+
+        RecursivePointer content ->
+            typeName types content
+
+        Struct { name } -> escapeKW name
+        TagUnionPayload { name } -> escapeKW name
+        TagUnion (NonRecursive { name }) -> escapeKW name
+        TagUnion (Recursive { name }) -> escapeKW name
+        TagUnion (Enumeration { name }) -> escapeKW name
+        TagUnion (NullableWrapped { name }) -> escapeKW name
+        TagUnion (NullableUnwrapped { name }) -> escapeKW name
+        TagUnion (NonNullableUnwrapped { name }) -> escapeKW name
+        TagUnion (SingleTagStruct { name }) -> escapeKW name
+        Function { functionName } -> escapeKW functionName
+
+## The type of a field, which must spell out the alignment Roc expects
+## for 128-bit integers, since Zig's can differ from it on some targets.
+fieldType = \types, id ->
+    type = typeName types id
+
+    when Types.shape types id is
+        Num I128 | Num U128 ->
+            alignStr = Num.toStr (Types.alignment types id)
+
+            "\(type) align(\(alignStr))"
+
+        _ ->
+            type
+
+tagsWithPayloads = \types, tags ->
+    List.keepOks tags \{ name: tagName, payload } ->
+        when nonZeroPayload types payload is
+            Some payloadId -> Ok { tagName, payloadId }
+            None -> Err NoPayload
+
+nonZeroPayload = \types, payload ->
+    when payload is
+        Some payloadId ->
+            if isZeroSized types payloadId then
+                None
+            else
+                Some payloadId
+
+        None ->
+            None
+
+isZeroSized : Types, TypeId -> Bool
+isZeroSized = \types, id ->
+    when Types.shape types id is
+        # Unit is recorded with a size of 1, but it never takes up any space.
+        Unit | EmptyTagUnion -> Bool.true
+        _ -> Types.size types id == 0
+
+discriminantType = \size ->
+    when size is
+        2 -> "u16"
+        4 -> "u32"
+        8 -> "u64"
+        _ -> "u8"
+
+alignmentOf128BitNums = \types ->
+    Types.walkShapes types 16 \answer, shape, id ->
+        when shape is
+            Num I128 | Num U128 | Num Dec -> Types.alignment types id
+            _ -> answer
+
+pointerTagMask = \arch ->
+    when arch is
+        Aarch64 | X86x64 -> "7"
+        Aarch32 | Wasm32 | X86x32 -> "3"
+
+getSizeRoundedToAlignment = \types, id ->
+    alignment = Types.alignment types id
+
+    Types.size types id
+    |> roundUpToAlignment alignment
+
+roundUpToAlignment = \width, alignment ->
+    when alignment is
+        0 -> width
+        1 -> width
+        _ ->
+            if width % alignment > 0 then
+                width + alignment - (width % alignment)
+            else
+                width
+
+max = \a, b -> if a >= b then a else b
+
+archName = \arch ->
+    when arch is
+        Aarch32 ->
+            "arm"
+
+        Aarch64 ->
+            "aarch64"
+
+        Wasm32 ->
+            "wasm32"
+
+        X86x32 ->
+            "x86"
+
+        X86x64 ->
+            "x86_64"
+
+zigArchName = \arch ->
+    when arch is
+        Aarch32 ->
+            "arm"
+
+        Aarch64 ->
+            "aarch64"
+
+        Wasm32 ->
+            "wasm32"
+
+        X86x32 ->
+            "i386"
+
+        X86x64 ->
+            "x86_64"
+
+fileHeader =
+    """
+    // ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+
+    """
+
+indent = "    "
+docDiscriminant = "// Returns which tag this tag union holds. Note that this never includes a payload!"
+docRefcounting = "// Decrementing does not decrement the payload's reference counts; if this is unique, do that first!"
+
+reservedKeywords = Set.fromList [
+    "addrspace",
+    "align",
+    "allowzero",
+    "and",
+    "anyframe",
+    "anyopaque",
+    "anytype",
+    "asm",
+    "async",
+    "await",
+    "bool",
+    "break",
+    "callconv",
+    "catch",
+    "comptime",
+    "const",
+    "continue",
+    "defer",
+    "else",
+    "enum",
+    "errdefer",
+    "error",
+    "export",
+    "extern",
+    "false",
+    "fn",
+    "for",
+    "if",
+    "inline",
+    "linksection",
+    "noalias",
+    "noinline",
+    "nosuspend",
+    "null",
+    "opaque",
+    "or",
+    "orelse",
+    "packed",
+    "pub",
+    "resume",
+    "return",
+    "struct",
+    "suspend",
+    "switch",
+    "test",
+    "threadlocal",
+    "true",
+    "try",
+    "type",
+    "undefined",
+    "union",
+    "unreachable",
+    "usingnamespace",
+    "var",
+    "void",
+    "volatile",
+    "while",
+]
+
+escapeKW = \input ->
+    # Zig lets any name be used as an identifier, as long as it's written like @"this".
+    if Set.contains reservedKeywords input then
+        "@\"\(input)\""
+    else
+        input
@@ -16,6 +16,7 @@ use roc_mono::ir::{generate_glue_procs, GlueProc, OptLevel};
 use roc_mono::layout::{GlobalLayoutInterner, LayoutCache, LayoutInterner};
 use roc_packaging::cache::{self, RocCacheDir};
 use roc_reporting::report::{RenderTarget, DEFAULT_PALETTE};
+use roc_reporting::warnings::WarningConfig;
 use roc_target::{Architecture, TargetInfo};
 use roc_types::subs::{Subs, Variable};
 use std::fs::File;
@@ -79,6 +80,7 @@ pub fn generate(input_path: &Path, output_path: &Path, spec_path: &Path) -> io::
                 None,
                 RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
                 load_config,
+                &WarningConfig::default(),
             );
 
             match res_binary_path {
@@ -188,10 +190,17 @@ pub fn generate(input_path: &Path, output_path: &Path, spec_path: &Path) -> io::
 
                     Ok(0)
                 }
-                Err(BuildFileError::ErrorModule { module, total_time }) => {
-                    handle_error_module(module, total_time, spec_path.as_os_str(), true)
+                Err(BuildFileError::ErrorModule { module, total_time }) => handle_error_module(
+                    module,
+                    total_time,
+                    spec_path.as_os_str(),
+                    true,
+                    &WarningConfig::default(),
+                    RenderTarget::ColorTerminal,
+                ),
+                Err(BuildFileError::LoadingProblem(problem)) => {
+                    handle_loading_problem(problem, RenderTarget::ColorTerminal)
                 }
-                Err(BuildFileError::LoadingProblem(problem)) => handle_loading_problem(problem),
             }
         }
         Err(err) => match err.kind() {
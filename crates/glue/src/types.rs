@@ -55,6 +55,10 @@ pub struct Types {
     sizes: Vec<u32>,
     aligns: Vec<u32>,
 
+    /// The offset of each field within a struct-like type (in the same order as its fields),
+    /// according to roc_mono's layout of it. Empty for types without fields.
+    offsets: Vec<Vec<u32>>,
+
     entry_points: Vec<(String, TypeId)>,
 
     // Needed to check for duplicates
@@ -74,16 +78,19 @@ impl Types {
         let mut types = Vec::with_capacity(cap);
         let mut sizes = Vec::with_capacity(cap);
         let mut aligns = Vec::with_capacity(cap);
+        let mut offsets = Vec::with_capacity(cap);
 
         types.push(RocType::Unit);
         sizes.push(1);
         aligns.push(1);
+        offsets.push(Vec::new());
 
         Self {
             target: target_info,
             types,
             sizes,
             aligns,
+            offsets,
             types_by_name: FnvHashMap::with_capacity_and_hasher(10, Default::default()),
             entry_points: Vec::new(),
             deps: VecMap::with_capacity(cap),
@@ -548,6 +555,7 @@ impl Types {
 
         debug_assert_eq!(self.types.len(), self.sizes.len());
         debug_assert_eq!(self.types.len(), self.aligns.len());
+        debug_assert_eq!(self.types.len(), self.offsets.len());
 
         let id = TypeId(self.types.len());
 
@@ -559,10 +567,35 @@ impl Types {
         self.types.push(typ);
         self.sizes.push(size);
         self.aligns.push(align);
+        self.offsets.push(Vec::new());
 
         id
     }
 
+    /// Record the offsets of a struct-like type's fields, in the same order as its fields.
+    pub fn set_offsets(&mut self, id: TypeId, offsets: Vec<u32>) {
+        debug_assert!(self.offsets.get(id.0).is_some());
+
+        self.offsets[id.0] = offsets;
+    }
+
+    /// Record a struct's layout, for when the layout it was added with isn't its own.
+    /// (For example, a tag union payload gets added with the layout of the whole union.)
+    fn set_struct_layout(&mut self, id: TypeId, layout: StructLayout) {
+        debug_assert!(self.types.get(id.0).is_some());
+
+        self.sizes[id.0] = layout.size;
+        self.aligns[id.0] = layout.align;
+        self.offsets[id.0] = layout.offsets;
+    }
+
+    pub fn offsets(&self, id: TypeId) -> &[u32] {
+        match self.offsets.get(id.0) {
+            Some(offsets) => offsets,
+            None => unreachable!(),
+        }
+    }
+
     pub fn depends(&mut self, id: TypeId, depends_on: TypeId) {
         self.deps.get_or_insert(id, Vec::new).push(depends_on);
     }
@@ -668,6 +701,11 @@ impl From<&Types> for roc_type::Types {
             aligns: types.aligns.as_slice().into(),
             deps,
             entrypoints,
+            offsets: types
+                .offsets
+                .iter()
+                .map(|offsets| offsets.as_slice().into())
+                .collect(),
             sizes: types.sizes.as_slice().into(),
             types: types.types.iter().map(roc_type::RocType::from).collect(),
             typesByName: types_by_name,
@@ -1908,6 +1946,11 @@ where
         )
     });
 
+    let struct_layout = StructLayout::from_fields(
+        &env.layout_cache.interner,
+        sortables.iter().map(|(_, _, field_layout)| *field_layout),
+    );
+
     // This layout should have an entry in glue_procs_by_layout iff it
     // contains closures, but we'll double-check that with a debug_assert.
     let layout = env.layout_cache.interner.get(in_layout);
@@ -1946,12 +1989,50 @@ where
         }
     };
 
-    types.add_named(
+    let id = types.add_named(
         &env.layout_cache.interner,
         name.clone(),
         to_type(name, struct_fields),
         in_layout,
-    )
+    );
+
+    types.set_struct_layout(id, struct_layout);
+
+    id
+}
+
+struct StructLayout {
+    size: u32,
+    align: u32,
+    offsets: Vec<u32>,
+}
+
+impl StructLayout {
+    /// Roc lays out struct fields one after another, in the order they were sorted into,
+    /// so each field's offset is the total size of the fields before it.
+    fn from_fields<'a>(
+        interner: &TLLayoutInterner<'a>,
+        field_layouts: impl Iterator<Item = InLayout<'a>>,
+    ) -> Self {
+        let mut size = 0;
+        let mut align = 0;
+        let offsets = field_layouts
+            .map(|field_layout| {
+                let offset = size;
+
+                size += interner.stack_size(field_layout);
+                align = align.max(interner.alignment_bytes(field_layout));
+
+                offset
+            })
+            .collect();
+
+        Self {
+            size: round_up_to_alignment(size, align),
+            align,
+            offsets,
+        }
+    }
 }
 
 trait UnionTag: Label + std::fmt::Debug {
@@ -2209,9 +2290,38 @@ fn add_tag_union<'a>(
         layout,
     );
 
+    // A single-tag union is laid out just like a struct of its payloads.
+    let opt_payload_ids: Option<Vec<TypeId>> = match &tag_union_type {
+        RocTagUnion::SingleTagStruct {
+            payload: RocSingleTagPayload::HasNoClosure { payload_fields },
+            ..
+        } => Some(payload_fields.clone()),
+        RocTagUnion::SingleTagStruct {
+            payload: RocSingleTagPayload::HasClosure { payload_getters },
+            ..
+        } => Some(payload_getters.iter().map(|(id, _)| *id).collect()),
+        _ => None,
+    };
+
     let typ = RocType::TagUnion(tag_union_type);
     let type_id = types.add_named(&env.layout_cache.interner, name, typ, layout);
 
+    if let Some(payload_ids) = opt_payload_ids {
+        let mut offset = 0;
+        let offsets = payload_ids
+            .into_iter()
+            .map(|payload_id| {
+                let field_offset = offset;
+
+                offset += types.size_rounded_to_alignment(payload_id);
+
+                field_offset
+            })
+            .collect();
+
+        types.set_offsets(type_id, offsets);
+    }
+
     if let Some(rec_var) = rec_root {
         env.known_recursive_types.insert(rec_var, type_id);
     }
@@ -0,0 +1,13 @@
+app "app"
+    packages { pf: "platform.roc" }
+    imports []
+    provides [main] to pf
+
+main = \n ->
+    shape = { name: "triangle", sides: 3, id: Num.toI32 n, labels: ["a", "b"] }
+
+    {
+        shape,
+        expr: Add (Num n) (Neg (Num 1)),
+        scale: \x -> Add (Num (x * n)) (Num (Num.toI64 shape.sides)),
+    }
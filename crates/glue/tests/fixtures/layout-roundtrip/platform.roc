@@ -0,0 +1,15 @@
+platform "test-platform"
+    requires {} { main : _ }
+    exposes []
+    packages {}
+    imports []
+    provides [mainForHost]
+
+Shape : { name : Str, sides : U8, id : I32, labels : List Str }
+
+Expr : [Num I64, Add Expr Expr, Neg Expr]
+
+Roundtrip : { shape : Shape, expr : Expr, scale : I64 -> Expr }
+
+mainForHost : I64 -> Roundtrip
+mainForHost = \n -> main n
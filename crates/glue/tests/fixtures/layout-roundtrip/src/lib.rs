@@ -0,0 +1,95 @@
+mod test_glue;
+
+use indoc::indoc;
+use roc_std::RocList;
+use test_glue::{Expr, Shape};
+
+#[no_mangle]
+pub extern "C" fn rust_main() -> i32 {
+    let roundtrip = test_glue::mainForHost(42);
+
+    // The values below were built by the LLVM backend. If the glue's idea of any of these
+    // layouts had drifted from the backend's, these comparisons would read the wrong bytes.
+    let expected_shape = Shape {
+        labels: RocList::from_slice(&["a".into(), "b".into()]),
+        name: "triangle".into(),
+        id: 42,
+        sides: 3,
+    };
+
+    assert_eq!(roundtrip.shape, expected_shape);
+    assert_eq!(
+        roundtrip.expr,
+        Expr::Add(Expr::Num(42), Expr::Neg(Expr::Num(1)))
+    );
+
+    print!(
+        indoc!(
+            r#"
+                shape was: {:?}
+                expr was: {:?}
+            "#
+        ),
+        roundtrip.shape, roundtrip.expr,
+    ); // Debug
+
+    // The closure captures both the argument and the shape, so calling it back from the
+    // host exercises the lambda set layout too.
+    let scaled = roundtrip.scale.force_thunk(2);
+
+    assert_eq!(scaled, Expr::Add(Expr::Num(84), Expr::Num(3)));
+
+    println!("scale 2 was: {:?}", scaled);
+
+    // Exit code
+    0
+}
+
+// Externs required by roc_std and by the Roc app
+
+use core::ffi::c_void;
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
+#[no_mangle]
+pub unsafe extern "C" fn roc_alloc(size: usize, _alignment: u32) -> *mut c_void {
+    return libc::malloc(size);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn roc_realloc(
+    c_ptr: *mut c_void,
+    new_size: usize,
+    _old_size: usize,
+    _alignment: u32,
+) -> *mut c_void {
+    return libc::realloc(c_ptr, new_size);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn roc_dealloc(c_ptr: *mut c_void, _alignment: u32) {
+    return libc::free(c_ptr);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn roc_panic(c_ptr: *mut c_void, tag_id: u32) {
+    match tag_id {
+        0 => {
+            let slice = CStr::from_ptr(c_ptr as *const c_char);
+            let string = slice.to_str().unwrap();
+            eprintln!("Roc hit a panic: {}", string);
+            std::process::exit(1);
+        }
+        _ => todo!(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn roc_memcpy(dst: *mut c_void, src: *mut c_void, n: usize) -> *mut c_void {
+    libc::memcpy(dst, src, n)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn roc_memset(dst: *mut c_void, c: i32, n: usize) -> *mut c_void {
+    libc::memset(dst, c, n)
+}
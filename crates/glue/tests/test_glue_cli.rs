@@ -136,6 +136,177 @@ mod glue_cli_run {
         return_function:"return-function" => indoc!(r#"
             Answer was: 43 41
         "#),
+        layout_roundtrip:"layout-roundtrip" => indoc!(r#"
+            shape was: Shape { labels: ["a", "b"], name: "triangle", id: 42, sides: 3 }
+            expr was: Expr::Add(Expr::Num(42), Expr::Neg(Expr::Num(1)))
+            scale 2 was: Expr::Add(Expr::Num(84), Expr::Num(3))
+        "#),
+    }
+
+    /// These fixtures cover every kind of shape the C and Zig glue specs generate code for.
+    const NON_RUST_GLUE_FIXTURES: &[&str] = &[
+        "arguments",
+        "basic-record",
+        "enumeration",
+        "layout-roundtrip",
+        "nested-record",
+        "nonnullable-unwrapped",
+        "nullable-unwrapped",
+        "nullable-wrapped",
+        "return-function",
+        "rocresult",
+        "single-tag-union",
+        "union-with-padding",
+    ];
+
+    #[test]
+    fn c_glue_compiles() {
+        for fixture in NON_RUST_GLUE_FIXTURES {
+            let dir = fixtures_dir(fixture);
+            let glue_dir = dir.join("c_glue");
+
+            run_glue_spec(
+                "CGlue.roc",
+                &glue_dir,
+                &dir.join("platform.roc"),
+                std::iter::empty(),
+            );
+
+            // The generated header asserts that every type has the size and alignment Roc
+            // expects, so compiling it checks those against this machine's C compiler.
+            let check_file = glue_dir.join("check.c");
+
+            fs::write(&check_file, "#include \"roc_app.h\"\n").unwrap();
+
+            let out = std::process::Command::new("cc")
+                .args(["-std=c11", "-Wall", "-Werror", "-fsyntax-only"])
+                .arg(&check_file)
+                .output()
+                .expect("failed to run cc");
+
+            assert!(
+                out.status.success(),
+                "The C glue for {} did not compile:\n\n{}",
+                fixture,
+                String::from_utf8_lossy(&out.stderr)
+            );
+        }
+    }
+
+    /// The file each architecture's Zig glue goes in, and a Zig target to compile it for.
+    /// Roc computes the same layouts for every operating system, so these are freestanding.
+    const ZIG_GLUE_TARGETS: &[(&str, &str)] = &[
+        ("x86_64", "x86_64-freestanding"),
+        ("x86", "i386-freestanding"),
+        ("aarch64", "aarch64-freestanding"),
+        ("arm", "arm-freestanding"),
+        ("wasm32", "wasm32-freestanding"),
+    ];
+
+    #[test]
+    fn zig_glue_compiles() {
+        for fixture in NON_RUST_GLUE_FIXTURES {
+            let dir = fixtures_dir(fixture);
+            let glue_dir = dir.join("zig_glue");
+
+            run_glue_spec(
+                "ZigGlue.roc",
+                &glue_dir,
+                &dir.join("platform.roc"),
+                std::iter::empty(),
+            );
+
+            let main_zig = fs::read_to_string(glue_dir.join("main.zig")).unwrap();
+
+            assert!(main_zig.contains("@import(\"x86_64.zig\")"));
+
+            // Cross-compiling each file runs its size, alignment and offset assertions
+            // against what Zig computes for that architecture.
+            for (arch, target) in ZIG_GLUE_TARGETS {
+                let out = compile_zig_glue(&glue_dir, arch, target);
+
+                assert!(
+                    out.status.success(),
+                    "The {} Zig glue for {} did not compile:\n\n{}",
+                    arch,
+                    fixture,
+                    String::from_utf8_lossy(&out.stderr)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn zig_glue_layout_mismatch_fails_to_compile() {
+        let dir = fixtures_dir("basic-record");
+        // Not the fixture's own zig_glue directory, which the other tests are using.
+        let out_dir = tempfile::tempdir().unwrap();
+        let glue_dir = out_dir.path().join("zig_glue");
+
+        run_glue_spec(
+            "ZigGlue.roc",
+            &glue_dir,
+            &dir.join("platform.roc"),
+            std::iter::empty(),
+        );
+
+        // Pretend Roc expects a different size, to show the assertions are really checked.
+        let path = glue_dir.join("x86_64.zig");
+        let src = fs::read_to_string(&path).unwrap();
+        let check = src.find("if (@sizeOf(").unwrap();
+        let size_start = check + src[check..].find(") != ").unwrap() + ") != ".len();
+        let size_end = size_start + src[size_start..].find(')').unwrap();
+        let wrong_size = src[size_start..size_end].parse::<u32>().unwrap() + 1;
+
+        fs::write(
+            &path,
+            format!("{}{}{}", &src[..size_start], wrong_size, &src[size_end..]),
+        )
+        .unwrap();
+
+        let out = compile_zig_glue(&glue_dir, "x86_64", "x86_64-freestanding");
+        let stderr = String::from_utf8_lossy(&out.stderr);
+
+        assert!(!out.status.success());
+        assert!(stderr.contains("Roc expects"), "{}", stderr);
+    }
+
+    /// Type checks one architecture's Zig glue, along with main.zig if that architecture is
+    /// this machine's. Zig only analyzes declarations that are used, so this refers to all
+    /// of them.
+    fn compile_zig_glue(glue_dir: &Path, arch: &str, target: &str) -> std::process::Output {
+        let check_file = glue_dir.join(format!("check_{}.zig", arch));
+        let main_check = if arch == std::env::consts::ARCH {
+            "_ = @import(\"main.zig\").REFCOUNT_ONE;\n    "
+        } else {
+            ""
+        };
+
+        fs::write(
+            &check_file,
+            format!(
+                indoc!(
+                    r#"
+                    const glue = @import("{}.zig");
+
+                    comptime {{
+                        {}inline for (@typeInfo(glue).Struct.decls) |decl| {{
+                            if (decl.is_pub) _ = @field(glue, decl.name);
+                        }}
+                    }}
+                    "#
+                ),
+                arch, main_check
+            ),
+        )
+        .unwrap();
+
+        roc_command_utils::zig()
+            .args(["build-obj", "-fno-emit-bin", "-target", target])
+            .arg(&check_file)
+            .current_dir(glue_dir)
+            .output()
+            .expect("failed to run zig")
     }
 
     fn check_for_tests(all_fixtures: &mut roc_collections::VecSet<String>) {
@@ -187,26 +358,32 @@ mod glue_cli_run {
             .run()
             .unwrap();
 
+        run_glue_spec("RustGlue.roc", &glue_dir, &platform_module_path, args)
+    }
+
+    /// Run the given glue spec from src/, writing its output into glue_dir.
+    fn run_glue_spec<'a, I: IntoIterator<Item = &'a str>>(
+        spec_filename: &str,
+        glue_dir: &Path,
+        platform_module_path: &Path,
+        args: I,
+    ) -> Out {
         // Delete the glue file to make sure we're actually regenerating it!
         if glue_dir.exists() {
-            fs::remove_dir_all(&glue_dir)
-                .expect("Unable to remove test_glue dir in order to regenerate it in the test");
+            fs::remove_dir_all(glue_dir)
+                .expect("Unable to remove glue dir in order to regenerate it in the test");
         }
 
-        let rust_glue_spec = fixture_templates_dir
-            .parent()
-            .unwrap()
-            .parent()
-            .unwrap()
+        let glue_spec = Path::new(env!("CARGO_MANIFEST_DIR"))
             .join("src")
-            .join("RustGlue.roc");
+            .join(spec_filename);
 
-        // Generate a fresh test_glue for this platform
+        // Generate fresh glue for this platform
         let glue_out = run_glue(
             // converting these all to String avoids lifetime issues
             std::iter::once("glue".to_string()).chain(
                 args.into_iter().map(|arg| arg.to_string()).chain([
-                    rust_glue_spec.to_str().unwrap().to_string(),
+                    glue_spec.to_str().unwrap().to_string(),
                     glue_dir.to_str().unwrap().to_string(),
                     platform_module_path.to_str().unwrap().to_string(),
                 ]),
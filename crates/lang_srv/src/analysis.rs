@@ -0,0 +1,426 @@
+//! Type checking a document, and answering questions about the result.
+use crate::convert::{path_to_uri, Lines};
+use bumpalo::Bump;
+use roc_can::expr::Declarations;
+use roc_can::pattern::Pattern;
+use roc_can::traverse::{find_closest_symbol_at, find_closest_type_at, walk_pattern, Visitor};
+use roc_load::{LoadedModule, LoadingProblem};
+use roc_module::symbol::Symbol;
+use roc_packaging::cache::{self, RocCacheDir};
+use roc_parse::ast::{self, Defs, TypeDef, ValueDef};
+use roc_parse::module::{self, module_defs};
+use roc_parse::parser::Parser;
+use roc_parse::state::State;
+use roc_problem::Severity;
+use roc_region::all::{LineInfo, Loc, Region};
+use roc_reporting::report::{
+    can_problem, type_problem, CiWrite, RenderTarget, Report, RocDocAllocator, DEFAULT_PALETTE,
+};
+use roc_target::TargetInfo;
+use roc_types::pretty_print::{name_and_print_var, DebugPrint};
+use roc_types::subs::Variable;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use target_lexicon::Triple;
+
+/// The result of type checking one version of a document.
+pub struct Analysis {
+    path: PathBuf,
+    source: String,
+    diagnostics: Vec<Value>,
+    /// Only present if the document (and everything it imports) could be loaded at all.
+    loaded: Option<LoadedModule>,
+}
+
+impl Analysis {
+    pub fn new(path: &Path, source: &str) -> Self {
+        let arena = Bump::new();
+        let src_dir = path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .to_path_buf();
+        let cache_dir = cache::roc_cache_dir();
+
+        let result = roc_load::load_and_typecheck_str(
+            &arena,
+            path.to_path_buf(),
+            arena.alloc_str(source),
+            src_dir,
+            TargetInfo::from(&Triple::host()),
+            RenderTarget::Generic,
+            RocCacheDir::Persistent(cache_dir.as_path()),
+            DEFAULT_PALETTE,
+        );
+
+        let lines = Lines::new(source);
+
+        match result {
+            Ok(mut loaded) => {
+                let diagnostics = problem_diagnostics(path, &lines, &mut loaded);
+
+                Self {
+                    path: path.to_path_buf(),
+                    source: source.to_string(),
+                    diagnostics,
+                    loaded: Some(loaded),
+                }
+            }
+            Err(problem) => {
+                // Reports about loading problems (e.g. syntax errors) are already rendered,
+                // so there is no region to attach them to but the start of the document.
+                let message = match problem {
+                    LoadingProblem::FormattedReport(report) => report,
+                    other => format!("{:?}", other),
+                };
+
+                let diagnostic = json!({
+                    "range": lines.to_lsp_range(Region::zero()),
+                    "severity": 1,
+                    "source": "roc",
+                    "message": message,
+                });
+
+                Self {
+                    path: path.to_path_buf(),
+                    source: source.to_string(),
+                    diagnostics: vec![diagnostic],
+                    loaded: None,
+                }
+            }
+        }
+    }
+
+    pub fn diagnostics(&self) -> &[Value] {
+        &self.diagnostics
+    }
+
+    /// The type of the smallest expression or pattern at the given LSP position.
+    pub fn hover(&mut self, position: &Value) -> Option<Value> {
+        let lines = Lines::new(&self.source);
+        let pos = lines.from_lsp_position(position)?;
+        let loaded = self.loaded.as_mut()?;
+        let home = loaded.module_id;
+        let decls = loaded.declarations_by_id.get(&home)?;
+
+        let (region, var) = find_closest_type_at(pos, decls)?;
+        let type_str = name_and_print_var(
+            var,
+            loaded.solved.inner_mut(),
+            home,
+            &loaded.interns,
+            DebugPrint::NOTHING,
+        );
+
+        Some(json!({
+            "contents": {
+                "kind": "markdown",
+                "value": format!("```roc\n{}\n```", type_str),
+            },
+            "range": lines.to_lsp_range(region),
+        }))
+    }
+
+    /// The location where the symbol at the given LSP position was defined.
+    pub fn definition(&self, position: &Value) -> Option<Value> {
+        let lines = Lines::new(&self.source);
+        let pos = lines.from_lsp_position(position)?;
+        let loaded = self.loaded.as_ref()?;
+        let home = loaded.module_id;
+        let decls = loaded.declarations_by_id.get(&home)?;
+
+        let symbol = find_closest_symbol_at(pos, decls)?.value;
+        let module_id = symbol.module_id();
+
+        if module_id == home {
+            let region = find_introduction(symbol, decls)?;
+
+            Some(json!({
+                "uri": path_to_uri(&self.path),
+                "range": lines.to_lsp_range(region),
+            }))
+        } else if module_id.is_builtin() {
+            // Builtins are compiled into the compiler, so they have no file to jump to.
+            None
+        } else {
+            let (path, src) = loaded.sources.get(&module_id)?;
+            let region = find_top_level_def(src, symbol.as_str(&loaded.interns))?;
+
+            Some(json!({
+                "uri": path_to_uri(path),
+                "range": Lines::new(src).to_lsp_range(region),
+            }))
+        }
+    }
+}
+
+fn problem_diagnostics(path: &Path, lines: &Lines, loaded: &mut LoadedModule) -> Vec<Value> {
+    let home = loaded.module_id;
+    let can_problems = loaded.can_problems.remove(&home).unwrap_or_default();
+    let type_problems = loaded.type_problems.remove(&home).unwrap_or_default();
+
+    let src = match loaded.sources.get(&home) {
+        Some((_, src)) => src,
+        None => return Vec::new(),
+    };
+    let src_lines: Vec<&str> = src.split('\n').collect();
+    let line_info = LineInfo::new(src);
+    let alloc = RocDocAllocator::new(&src_lines, home, &loaded.interns);
+
+    let mut diagnostics = Vec::with_capacity(can_problems.len() + type_problems.len());
+
+    for problem in can_problems {
+        let region = problem.region();
+        let report = can_problem(&alloc, &line_info, path.to_path_buf(), problem);
+
+        diagnostics.push(to_diagnostic(lines, region, report));
+    }
+
+    for problem in type_problems {
+        let region = problem.region();
+
+        if let Some(report) = type_problem(&alloc, &line_info, path.to_path_buf(), problem) {
+            diagnostics.push(to_diagnostic(lines, region, report));
+        }
+    }
+
+    diagnostics
+}
+
+fn to_diagnostic(lines: &Lines, region: Option<Region>, report: Report) -> Value {
+    let severity = match report.severity {
+        Severity::RuntimeError | Severity::Fatal => 1,
+        Severity::Warning => 2,
+    };
+
+    let mut body = String::new();
+    report
+        .doc
+        .1
+        .render_raw(70, &mut CiWrite::new(&mut body))
+        .expect("<buffer is not a utf-8 encoded string>");
+
+    let mut diagnostic = json!({
+        "range": lines.to_lsp_range(region.unwrap_or_else(Region::zero)),
+        "severity": severity,
+        "source": "roc",
+        "message": format!("{}\n\n{}", report.title, body.trim()),
+    });
+
+    if let Some(code) = report.code {
+        diagnostic["code"] = json!(code.to_string());
+    }
+
+    diagnostic
+}
+
+/// Finds the region of the pattern that introduces `symbol`: a top-level or nested def,
+/// a function argument, or a pattern in a `when` branch.
+fn find_introduction(symbol: Symbol, decls: &Declarations) -> Option<Region> {
+    let mut visitor = Finder {
+        symbol,
+        found: None,
+    };
+    visitor.visit_decls(decls);
+    return visitor.found;
+
+    struct Finder {
+        symbol: Symbol,
+        found: Option<Region>,
+    }
+
+    impl Visitor for Finder {
+        fn should_visit(&mut self, _region: Region) -> bool {
+            self.found.is_none()
+        }
+
+        fn visit_pattern(&mut self, pattern: &Pattern, region: Region, _opt_var: Option<Variable>) {
+            match pattern {
+                Pattern::Identifier(symbol)
+                | Pattern::Shadowed(_, _, symbol)
+                | Pattern::AbilityMemberSpecialization { ident: symbol, .. }
+                    if *symbol == self.symbol =>
+                {
+                    self.found = Some(region);
+                }
+                _ => walk_pattern(self, pattern),
+            }
+        }
+    }
+}
+
+/// Finds the region of the top-level def (or type definition) named `name` by parsing `src`.
+fn find_top_level_def(src: &str, name: &str) -> Option<Region> {
+    let arena = Bump::new();
+    let (_, state) = module::parse_header(&arena, State::new(src.as_bytes())).ok()?;
+    let (_, defs, _) = module_defs().parse(&arena, state, 0).ok()?;
+
+    find_in_defs(&defs, name)
+}
+
+fn find_in_defs(defs: &Defs, name: &str) -> Option<Region> {
+    defs.defs().find_map(|def| match def {
+        Ok(TypeDef::Alias { header, .. })
+        | Ok(TypeDef::Opaque { header, .. })
+        | Ok(TypeDef::Ability { header, .. }) => {
+            (header.name.value == name).then_some(header.name.region)
+        }
+        Err(ValueDef::Annotation(loc_pattern, _)) => identifier_region(loc_pattern, name),
+        Err(ValueDef::Body(loc_pattern, _)) => identifier_region(loc_pattern, name),
+        Err(ValueDef::AnnotatedBody { ann_pattern, .. }) => identifier_region(ann_pattern, name),
+        Err(_) => None,
+    })
+}
+
+fn identifier_region(loc_pattern: &Loc<ast::Pattern>, name: &str) -> Option<Region> {
+    match loc_pattern.value {
+        ast::Pattern::Identifier(ident) if ident == name => Some(loc_pattern.region),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test_analysis {
+    use super::Analysis;
+    use crate::convert::path_to_uri;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    fn analyze(src: &str) -> (PathBuf, Analysis) {
+        let path = std::env::temp_dir().join("roc_lang_srv").join("Test.roc");
+        let analysis = Analysis::new(&path, src);
+
+        (path, analysis)
+    }
+
+    const GREETING: &str = indoc!(
+        r#"
+        interface Test exposes [main] imports []
+
+        greeting = "Hello"
+
+        main = Str.concat greeting "!"
+        "#
+    );
+
+    #[test]
+    fn no_diagnostics_for_a_module_that_checks() {
+        let (_, analysis) = analyze(GREETING);
+
+        assert!(analysis.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn type_errors_are_diagnostics() {
+        let (_, analysis) = analyze(indoc!(
+            r#"
+            interface Test exposes [main] imports []
+
+            main = Str.concat 1 "!"
+            "#
+        ));
+        let diagnostics = analysis.diagnostics();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0]["severity"], json!(1));
+        assert_eq!(diagnostics[0]["range"]["start"]["line"], json!(2));
+        assert!(diagnostics[0]["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("TYPE MISMATCH"));
+        assert!(diagnostics[0]["code"].as_str().unwrap().starts_with("R03"));
+    }
+
+    #[test]
+    fn warnings_are_diagnostics() {
+        let (_, analysis) = analyze(indoc!(
+            r#"
+            interface Test exposes [main] imports []
+
+            main = \unused -> 1
+            "#
+        ));
+        let diagnostics = analysis.diagnostics();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0]["severity"], json!(2));
+        assert_eq!(
+            diagnostics[0]["range"],
+            json!({
+                "start": { "line": 2, "character": 8 },
+                "end": { "line": 2, "character": 14 },
+            })
+        );
+    }
+
+    #[test]
+    fn syntax_errors_are_reported_at_the_start() {
+        let (_, analysis) = analyze("interface Test exposes [main] imports []\n\nmain = )\n");
+        let diagnostics = analysis.diagnostics();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0]["severity"], json!(1));
+        assert_eq!(
+            diagnostics[0]["range"]["start"],
+            json!({ "line": 0, "character": 0 })
+        );
+    }
+
+    #[test]
+    fn hover_shows_the_type() {
+        let (_, mut analysis) = analyze(GREETING);
+
+        // On `greeting` in `main`
+        let hover = analysis
+            .hover(&json!({ "line": 4, "character": 20 }))
+            .unwrap();
+
+        assert_eq!(hover["contents"]["value"], json!("```roc\nStr\n```"));
+        assert_eq!(
+            hover["range"],
+            json!({
+                "start": { "line": 4, "character": 18 },
+                "end": { "line": 4, "character": 26 },
+            })
+        );
+    }
+
+    #[test]
+    fn no_hover_without_a_loaded_module() {
+        let (_, mut analysis) = analyze("interface Test exposes [main] imports []\n\nmain = )\n");
+
+        assert_eq!(analysis.hover(&json!({ "line": 2, "character": 0 })), None);
+    }
+
+    #[test]
+    fn definition_in_the_same_module() {
+        let (path, analysis) = analyze(GREETING);
+
+        let definition = analysis
+            .definition(&json!({ "line": 4, "character": 20 }))
+            .unwrap();
+
+        assert_eq!(
+            definition,
+            json!({
+                "uri": path_to_uri(&path),
+                "range": {
+                    "start": { "line": 2, "character": 0 },
+                    "end": { "line": 2, "character": 8 },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn no_definition_for_builtins() {
+        let (_, analysis) = analyze(GREETING);
+
+        // On `Str.concat`
+        assert_eq!(
+            analysis.definition(&json!({ "line": 4, "character": 9 })),
+            None
+        );
+    }
+}
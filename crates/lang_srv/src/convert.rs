@@ -0,0 +1,190 @@
+//! Conversions between Roc's source locations and the Language Server Protocol's.
+//!
+//! Roc regions are byte offsets into the source, while LSP positions are a line number and a
+//! column counted in UTF-16 code units.
+use roc_region::all::{Position, Region};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+/// Line starts of a document, for converting between byte offsets and LSP positions.
+pub struct Lines<'a> {
+    src: &'a str,
+    line_offsets: Vec<usize>,
+}
+
+impl<'a> Lines<'a> {
+    pub fn new(src: &'a str) -> Self {
+        let mut line_offsets = vec![0];
+        line_offsets.extend(src.match_indices('\n').map(|(offset, _)| offset + 1));
+
+        Self { src, line_offsets }
+    }
+
+    fn line_text(&self, line: usize) -> &'a str {
+        let start = self.line_offsets[line];
+        let end = self
+            .line_offsets
+            .get(line + 1)
+            .copied()
+            .unwrap_or(self.src.len());
+
+        &self.src[start..end]
+    }
+
+    pub fn to_lsp_position(&self, pos: Position) -> Value {
+        let offset = pos.byte_offset().min(self.src.len());
+        let line = match self.line_offsets.binary_search(&offset) {
+            Ok(line) => line,
+            Err(line) => line - 1,
+        };
+        let line_start = self.line_offsets[line];
+        let character: usize = self.src[line_start..offset]
+            .chars()
+            .map(char::len_utf16)
+            .sum();
+
+        json!({ "line": line, "character": character })
+    }
+
+    pub fn to_lsp_range(&self, region: Region) -> Value {
+        json!({
+            "start": self.to_lsp_position(region.start()),
+            "end": self.to_lsp_position(region.end()),
+        })
+    }
+
+    /// Converts an LSP position to a byte offset, clamping it to the document.
+    pub fn from_lsp_position(&self, position: &Value) -> Option<Position> {
+        let line = position.get("line")?.as_u64()? as usize;
+        let character = position.get("character")?.as_u64()? as usize;
+
+        if line >= self.line_offsets.len() {
+            return Some(Position::new(self.src.len() as u32));
+        }
+
+        let mut utf16_column = 0;
+        let mut byte_column = 0;
+
+        for ch in self.line_text(line).chars() {
+            if utf16_column >= character || ch == '\n' {
+                break;
+            }
+
+            utf16_column += ch.len_utf16();
+            byte_column += ch.len_utf8();
+        }
+
+        Some(Position::new(
+            (self.line_offsets[line] + byte_column) as u32,
+        ))
+    }
+
+    /// The range covering the whole document.
+    pub fn full_range(&self) -> Value {
+        self.to_lsp_range(Region::new(
+            Position::zero(),
+            Position::new(self.src.len() as u32),
+        ))
+    }
+}
+
+/// Converts a `file://` URI to a path. Other schemes are not supported.
+pub fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'%' if index + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[index + 1..index + 3]).ok()?;
+                decoded.push(u8::from_str_radix(hex, 16).ok()?);
+                index += 3;
+            }
+            byte => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+
+    let path = String::from_utf8(decoded).ok()?;
+
+    // On Windows, URIs look like file:///C:/foo, so the leading slash needs to go.
+    if cfg!(windows) {
+        Some(PathBuf::from(path.trim_start_matches('/')))
+    } else {
+        Some(PathBuf::from(path))
+    }
+}
+
+/// Converts a path to a `file://` URI, percent-encoding anything that isn't unreserved.
+pub fn path_to_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut uri = String::from("file://");
+
+    if !path.starts_with('/') {
+        uri.push('/');
+    }
+
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+
+    uri
+}
+
+#[cfg(test)]
+mod test_convert {
+    use super::{path_to_uri, uri_to_path, Lines};
+    use roc_region::all::Position;
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    #[test]
+    fn positions_round_trip_through_utf16() {
+        // "é" is 2 bytes in utf-8 but 1 code unit in utf-16; "🎉" is 4 and 2.
+        let src = "x = 1\ny = \"é🎉\" z\n";
+        let lines = Lines::new(src);
+
+        let z_offset = src.find('z').unwrap() as u32;
+        let lsp_position = lines.to_lsp_position(Position::new(z_offset));
+        assert_eq!(lsp_position, json!({ "line": 1, "character": 10 }));
+
+        assert_eq!(
+            lines.from_lsp_position(&lsp_position),
+            Some(Position::new(z_offset))
+        );
+    }
+
+    #[test]
+    fn positions_past_the_end_are_clamped() {
+        let src = "x = 1\n";
+        let lines = Lines::new(src);
+
+        assert_eq!(
+            lines.from_lsp_position(&json!({ "line": 0, "character": 99 })),
+            Some(Position::new(5))
+        );
+        assert_eq!(
+            lines.from_lsp_position(&json!({ "line": 9, "character": 0 })),
+            Some(Position::new(6))
+        );
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn uris_round_trip() {
+        let path = PathBuf::from("/home/roc user/main.roc");
+        let uri = path_to_uri(&path);
+
+        assert_eq!(uri, "file:///home/roc%20user/main.roc");
+        assert_eq!(uri_to_path(&uri), Some(path));
+    }
+}
@@ -0,0 +1,61 @@
+//! Formatting whole documents with `roc_fmt`.
+use bumpalo::Bump;
+use roc_fmt::def::fmt_defs;
+use roc_fmt::module::fmt_module;
+use roc_fmt::Buf;
+use roc_parse::module::{self, module_defs};
+use roc_parse::parser::Parser;
+use roc_parse::state::State;
+
+/// Formats the given source, or returns [None] if it does not parse.
+pub fn format_src(src: &str) -> Option<String> {
+    let arena = Bump::new();
+
+    let (module, state) = module::parse_header(&arena, State::new(src.as_bytes())).ok()?;
+    let (_, defs, _) = module_defs().parse(&arena, state, 0).ok()?;
+
+    let mut buf = Buf::new_in(&arena);
+
+    fmt_module(&mut buf, &module);
+    fmt_defs(&mut buf, &defs, 0);
+    buf.fmt_end_of_file();
+
+    Some(buf.as_str().to_string())
+}
+
+#[cfg(test)]
+mod test_format {
+    use super::format_src;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn formats_a_module() {
+        let src = indoc!(
+            r#"
+            interface Foo exposes [x] imports []
+
+            x =    1
+            "#
+        );
+
+        assert_eq!(
+            format_src(src).unwrap(),
+            indoc!(
+                r#"
+                interface Foo exposes [x] imports []
+
+                x = 1
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn syntax_errors_are_not_formatted() {
+        assert_eq!(
+            format_src("interface Foo exposes [x] imports []\n\nx = )"),
+            None
+        );
+    }
+}
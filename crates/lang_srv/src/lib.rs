@@ -0,0 +1,21 @@
+//! A language server for Roc, started with `roc lsp`.
+//!
+//! It speaks the Language Server Protocol over stdin and stdout, and supports diagnostics,
+//! hover (showing the inferred type), go to definition, and formatting. Documents are synced in
+//! full, and every change to a document type checks it again.
+mod analysis;
+mod convert;
+mod format;
+mod rpc;
+mod server;
+
+use std::io;
+
+/// Serve LSP requests on stdin and stdout until the client asks us to exit.
+/// Returns the process exit code.
+pub fn run() -> io::Result<i32> {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+
+    server::Server::new(stdout.lock()).run(&mut stdin.lock())
+}
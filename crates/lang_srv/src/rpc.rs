@@ -0,0 +1,210 @@
+//! The base protocol of the Language Server Protocol: JSON-RPC messages, each preceded by a
+//! `Content-Length` header.
+use serde_json::{json, Value};
+use std::io::{self, BufRead, Write};
+
+/// A message sent to us by the client.
+#[derive(Debug)]
+pub enum Incoming {
+    Request {
+        id: Value,
+        method: String,
+        params: Value,
+    },
+    Notification {
+        method: String,
+        params: Value,
+    },
+    /// A response to a request we sent. We never send requests, so these are ignored.
+    Response,
+    /// A message whose body was not valid JSON. The client should be told, but the stream is
+    /// still in sync because the body had the length its header promised.
+    Malformed(String),
+}
+
+impl Incoming {
+    fn from_json(mut message: Value) -> Self {
+        let params = message
+            .get_mut("params")
+            .map(Value::take)
+            .unwrap_or(Value::Null);
+
+        match (message.get("id").cloned(), message.get("method")) {
+            (Some(id), Some(Value::String(method))) => Incoming::Request {
+                id,
+                method: method.clone(),
+                params,
+            },
+            (None, Some(Value::String(method))) => Incoming::Notification {
+                method: method.clone(),
+                params,
+            },
+            _ => Incoming::Response,
+        }
+    }
+}
+
+/// Standard JSON-RPC and LSP error codes.
+#[derive(Debug, Clone, Copy)]
+pub enum ErrorCode {
+    ParseError = -32700,
+    InvalidParams = -32602,
+    MethodNotFound = -32601,
+    InvalidRequest = -32600,
+}
+
+/// Read the next message, or [None] if the client closed the stream.
+pub fn read_message(reader: &mut impl BufRead) -> io::Result<Option<Incoming>> {
+    let mut content_length = None;
+
+    loop {
+        let mut line = String::new();
+
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let line = line.trim_end();
+
+        if line.is_empty() {
+            break;
+        }
+
+        // The only other header is Content-Type, which is always utf-8 JSON in practice.
+        if let Some(value) = line.strip_prefix("Content-Length:") {
+            content_length = value.trim().parse::<usize>().ok();
+        }
+    }
+
+    let content_length = content_length.ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "missing Content-Length header")
+    })?;
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    match serde_json::from_slice(&body) {
+        Ok(message) => Ok(Some(Incoming::from_json(message))),
+        Err(err) => Ok(Some(Incoming::Malformed(err.to_string()))),
+    }
+}
+
+fn write_message(writer: &mut impl Write, message: Value) -> io::Result<()> {
+    let body = message.to_string();
+
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    writer.flush()
+}
+
+pub fn write_response(writer: &mut impl Write, id: Value, result: Value) -> io::Result<()> {
+    write_message(
+        writer,
+        json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "result": result,
+        }),
+    )
+}
+
+pub fn write_error(
+    writer: &mut impl Write,
+    id: Value,
+    code: ErrorCode,
+    message: &str,
+) -> io::Result<()> {
+    write_message(
+        writer,
+        json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": {
+                "code": code as i32,
+                "message": message,
+            },
+        }),
+    )
+}
+
+pub fn write_notification(writer: &mut impl Write, method: &str, params: Value) -> io::Result<()> {
+    write_message(
+        writer,
+        json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+        }),
+    )
+}
+
+#[cfg(test)]
+mod test_rpc {
+    use super::{read_message, write_response, Incoming};
+    use serde_json::json;
+
+    #[test]
+    fn read_request_and_notification() {
+        let input = concat!(
+            "Content-Length: 51\r\n\r\n",
+            r#"{"jsonrpc":"2.0","id":1,"method":"shutdown","x":[]}"#,
+            "Content-Length: 50\r\n",
+            "Content-Type: application/vscode-jsonrpc; charset=utf-8\r\n\r\n",
+            r#"{"jsonrpc":"2.0","method":"exit","params":{"a":1}}"#,
+        );
+        let mut reader = input.as_bytes();
+
+        match read_message(&mut reader).unwrap() {
+            Some(Incoming::Request { id, method, params }) => {
+                assert_eq!(id, json!(1));
+                assert_eq!(method, "shutdown");
+                assert_eq!(params, json!(null));
+            }
+            other => panic!("expected a request, got {:?}", other),
+        }
+
+        match read_message(&mut reader).unwrap() {
+            Some(Incoming::Notification { method, params }) => {
+                assert_eq!(method, "exit");
+                assert_eq!(params, json!({ "a": 1 }));
+            }
+            other => panic!("expected a notification, got {:?}", other),
+        }
+
+        assert!(read_message(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn malformed_json_does_not_end_the_stream() {
+        let input = concat!(
+            "Content-Length: 9\r\n\r\n",
+            "{\"id\": 1,",
+            "Content-Length: 33\r\n\r\n",
+            r#"{"jsonrpc":"2.0","method":"exit"}"#,
+        );
+        let mut reader = input.as_bytes();
+
+        assert!(matches!(
+            read_message(&mut reader).unwrap(),
+            Some(Incoming::Malformed(_))
+        ));
+        assert!(matches!(
+            read_message(&mut reader).unwrap(),
+            Some(Incoming::Notification { .. })
+        ));
+    }
+
+    #[test]
+    fn write_then_read_response() {
+        let mut buf = Vec::new();
+        write_response(&mut buf, json!(7), json!({ "ok": true })).unwrap();
+
+        let text = String::from_utf8(buf.clone()).unwrap();
+        let (header, body) = text.split_once("\r\n\r\n").unwrap();
+        assert_eq!(header, format!("Content-Length: {}", body.len()));
+
+        assert!(matches!(
+            read_message(&mut buf.as_slice()).unwrap(),
+            Some(Incoming::Response)
+        ));
+    }
+}
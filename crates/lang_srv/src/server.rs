@@ -0,0 +1,326 @@
+//! Dispatching LSP requests and notifications, and keeping track of open documents.
+use crate::analysis::Analysis;
+use crate::convert::{uri_to_path, Lines};
+use crate::format::format_src;
+use crate::rpc::{self, ErrorCode, Incoming};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+struct Document {
+    text: String,
+    analysis: Analysis,
+}
+
+pub struct Server<W: Write> {
+    writer: W,
+    documents: HashMap<String, Document>,
+    shutdown_requested: bool,
+}
+
+impl<W: Write> Server<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            documents: HashMap::new(),
+            shutdown_requested: false,
+        }
+    }
+
+    /// Serve messages until the client sends `exit` or closes the stream, returning the exit code.
+    pub fn run(&mut self, reader: &mut impl BufRead) -> io::Result<i32> {
+        while let Some(message) = rpc::read_message(reader)? {
+            match message {
+                Incoming::Request { id, method, params } => {
+                    self.handle_request(id, &method, params)?;
+                }
+                Incoming::Notification { method, params } => {
+                    if method == "exit" {
+                        return Ok(if self.shutdown_requested { 0 } else { 1 });
+                    }
+
+                    self.handle_notification(&method, params)?;
+                }
+                Incoming::Response => {}
+                Incoming::Malformed(message) => {
+                    // We can't know the request's id, so JSON-RPC says to reply with a null one.
+                    rpc::write_error(
+                        &mut self.writer,
+                        Value::Null,
+                        ErrorCode::ParseError,
+                        &message,
+                    )?;
+                }
+            }
+        }
+
+        // The client went away without asking us to exit.
+        Ok(1)
+    }
+
+    fn handle_request(&mut self, id: Value, method: &str, params: Value) -> io::Result<()> {
+        if self.shutdown_requested {
+            return rpc::write_error(
+                &mut self.writer,
+                id,
+                ErrorCode::InvalidRequest,
+                "the server is shutting down",
+            );
+        }
+
+        let result = match method {
+            "initialize" => json!({
+                "capabilities": {
+                    // Full document sync: every change sends the whole text.
+                    "textDocumentSync": 1,
+                    "hoverProvider": true,
+                    "definitionProvider": true,
+                    "documentFormattingProvider": true,
+                },
+                "serverInfo": {
+                    "name": "roc_lang_srv",
+                },
+            }),
+            "shutdown" => {
+                self.shutdown_requested = true;
+
+                Value::Null
+            }
+            "textDocument/hover" | "textDocument/definition" | "textDocument/formatting" => {
+                let uri = match params["textDocument"]["uri"].as_str() {
+                    Some(uri) => uri,
+                    None => {
+                        return rpc::write_error(
+                            &mut self.writer,
+                            id,
+                            ErrorCode::InvalidParams,
+                            "missing textDocument.uri",
+                        );
+                    }
+                };
+
+                match self.documents.get_mut(uri) {
+                    None => Value::Null,
+                    Some(document) => match method {
+                        "textDocument/hover" => document
+                            .analysis
+                            .hover(&params["position"])
+                            .unwrap_or(Value::Null),
+                        "textDocument/definition" => document
+                            .analysis
+                            .definition(&params["position"])
+                            .unwrap_or(Value::Null),
+                        _ => match format_src(&document.text) {
+                            Some(formatted) if formatted != document.text => json!([{
+                                "range": Lines::new(&document.text).full_range(),
+                                "newText": formatted,
+                            }]),
+                            _ => json!([]),
+                        },
+                    },
+                }
+            }
+            _ => {
+                return rpc::write_error(
+                    &mut self.writer,
+                    id,
+                    ErrorCode::MethodNotFound,
+                    &format!("unsupported method {}", method),
+                );
+            }
+        };
+
+        rpc::write_response(&mut self.writer, id, result)
+    }
+
+    fn handle_notification(&mut self, method: &str, params: Value) -> io::Result<()> {
+        let uri = params["textDocument"]["uri"].as_str().map(str::to_string);
+
+        match (method, uri) {
+            ("textDocument/didOpen", Some(uri)) => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+
+                self.update(uri, text.to_string())
+            }
+            ("textDocument/didChange", Some(uri)) => {
+                // With full sync, the last change holds the entire new text.
+                let text = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str());
+
+                match text {
+                    Some(text) => self.update(uri, text.to_string()),
+                    None => Ok(()),
+                }
+            }
+            ("textDocument/didSave", Some(uri)) => {
+                // Other modules may have changed on disk, so check this one again.
+                match self.documents.get(&uri) {
+                    Some(document) => {
+                        let text = document.text.clone();
+
+                        self.update(uri, text)
+                    }
+                    None => Ok(()),
+                }
+            }
+            ("textDocument/didClose", Some(uri)) => {
+                self.documents.remove(&uri);
+
+                self.publish_diagnostics(uri, Vec::new())
+            }
+            // Everything else (e.g. `initialized` or `$/cancelRequest`) needs no reply.
+            _ => Ok(()),
+        }
+    }
+
+    fn update(&mut self, uri: String, text: String) -> io::Result<()> {
+        let path = match uri_to_path(&uri) {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+
+        let analysis = Analysis::new(&path, &text);
+        let diagnostics = analysis.diagnostics().to_vec();
+
+        self.documents
+            .insert(uri.clone(), Document { text, analysis });
+
+        self.publish_diagnostics(uri, diagnostics)
+    }
+
+    fn publish_diagnostics(&mut self, uri: String, diagnostics: Vec<Value>) -> io::Result<()> {
+        rpc::write_notification(
+            &mut self.writer,
+            "textDocument/publishDiagnostics",
+            json!({
+                "uri": uri,
+                "diagnostics": diagnostics,
+            }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test_server {
+    use super::Server;
+    use crate::convert::path_to_uri;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use serde_json::{json, Value};
+
+    fn frame(message: Value) -> String {
+        let body = message.to_string();
+
+        format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+    }
+
+    /// Splits everything the server wrote back into its JSON messages.
+    fn messages(mut output: &str) -> Vec<Value> {
+        let mut messages = Vec::new();
+
+        while let Some((header, rest)) = output.split_once("\r\n\r\n") {
+            let length: usize = header
+                .strip_prefix("Content-Length: ")
+                .unwrap()
+                .parse()
+                .unwrap();
+
+            messages.push(serde_json::from_str(&rest[..length]).unwrap());
+            output = &rest[length..];
+        }
+
+        messages
+    }
+
+    #[test]
+    fn open_a_document_then_hover() {
+        let path = std::env::temp_dir().join("roc_lang_srv").join("Main.roc");
+        let uri = path_to_uri(&path);
+        let text = indoc!(
+            r#"
+            interface Main exposes [main] imports []
+
+            greeting = "Hello"
+
+            main = Str.concat greeting 1
+            "#
+        );
+
+        let mut input = String::new();
+        input.push_str(&frame(json!({
+            "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {},
+        })));
+        input.push_str(&frame(json!({
+            "jsonrpc": "2.0", "method": "initialized", "params": {},
+        })));
+        input.push_str(&frame(json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": {
+                "textDocument": { "uri": uri, "languageId": "roc", "version": 1, "text": text },
+            },
+        })));
+        input.push_str(&frame(json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "textDocument/hover",
+            "params": {
+                "textDocument": { "uri": uri },
+                "position": { "line": 2, "character": 3 },
+            },
+        })));
+        input.push_str("Content-Length: 5\r\n\r\n{\"id\"");
+        input.push_str(&frame(json!({
+            "jsonrpc": "2.0", "id": 3, "method": "shutdown",
+        })));
+        input.push_str(&frame(json!({ "jsonrpc": "2.0", "method": "exit" })));
+
+        let mut output = Vec::new();
+        let exit_code = Server::new(&mut output).run(&mut input.as_bytes()).unwrap();
+        assert_eq!(exit_code, 0);
+
+        let output = String::from_utf8(output).unwrap();
+        let messages = messages(&output);
+        assert_eq!(messages.len(), 5);
+
+        assert_eq!(messages[0]["id"], json!(1));
+        assert_eq!(
+            messages[0]["result"]["capabilities"]["hoverProvider"],
+            json!(true)
+        );
+
+        assert_eq!(
+            messages[1]["method"],
+            json!("textDocument/publishDiagnostics")
+        );
+        assert_eq!(messages[1]["params"]["uri"], json!(uri));
+        let diagnostics = messages[1]["params"]["diagnostics"].as_array().unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0]["range"]["start"]["line"], json!(4));
+
+        assert_eq!(messages[2]["id"], json!(2));
+        assert_eq!(
+            messages[2]["result"]["contents"]["value"],
+            json!("```roc\nStr\n```")
+        );
+
+        assert_eq!(messages[3]["id"], Value::Null);
+        assert_eq!(messages[3]["error"]["code"], json!(-32700));
+
+        assert_eq!(messages[4]["id"], json!(3));
+        assert_eq!(messages[4]["result"], Value::Null);
+    }
+
+    #[test]
+    fn exit_without_shutdown_is_an_error() {
+        let input = frame(json!({ "jsonrpc": "2.0", "method": "exit" }));
+        let mut output = Vec::new();
+
+        let exit_code = Server::new(&mut output).run(&mut input.as_bytes()).unwrap();
+
+        assert_eq!(exit_code, 1);
+        assert!(output.is_empty());
+    }
+}
@@ -1,96 +1,391 @@
-use object::write;
-use object::{Architecture, BinaryFormat, Endianness, SymbolFlags, SymbolKind, SymbolScope};
-use roc_error_macros::internal_error;
-use std::path::Path;
-use std::process::Command;
+use object::endian::{BigEndian, U16, U32, U64};
+use object::macho;
+use object::pod::bytes_of;
+use object::LittleEndian as LE;
+use std::mem;
 use target_lexicon::Triple;
 
-// TODO: Eventually do this from scratch and in memory instead of with ld.
+/// The install name of the stub dylib. Hosts record this name in an `LC_LOAD_DYLIB` command,
+/// which the surgical linker uses to find the symbols that the host imports from the app.
+pub const APP_DYLIB: &str = "libapp.dylib";
+
+// The Apple Silicon page size. It is also a valid (if generous) alignment on x86_64.
+const PAGE_SIZE: u64 = 0x4000;
+
+/// Creates a dylib that exports the given symbols, so a host can be linked against it.
+///
+/// The dylib is built from scratch and in memory (rather than with `ld`), so that it can also be
+/// generated when cross-compiling for macOS. Its functions are never actually called: surgical
+/// linking replaces every reference to them with a reference into the app.
 pub fn create_dylib_macho(
     custom_names: &[String],
     triple: &Triple,
 ) -> object::read::Result<Vec<u8>> {
-    let dummy_obj_file = tempfile::Builder::new()
-        .prefix("roc_lib")
-        .suffix(".o")
-        .tempfile()
-        .unwrap_or_else(|e| internal_error!("{}", e));
-    let tmp = tempfile::tempdir().unwrap_or_else(|e| internal_error!("{}", e));
-    let dummy_lib_file = tmp.path().to_path_buf().with_file_name("libapp.so");
-
-    let obj_target = BinaryFormat::MachO;
-    let obj_arch = match triple.architecture {
-        target_lexicon::Architecture::X86_64 => Architecture::X86_64,
-        target_lexicon::Architecture::Aarch64(_) => Architecture::Aarch64,
-        _ => {
-            // We should have verified this via supported() before calling this function
-            unreachable!()
-        }
-    };
-    let mut out_object = write::Object::new(obj_target, obj_arch, Endianness::Little);
-
-    let text_section = out_object.section_id(write::StandardSection::Text);
-
-    for name in custom_names {
-        out_object.add_symbol(write::Symbol {
-            name: name.as_bytes().to_vec(),
-            value: 0,
-            size: 0,
-            kind: SymbolKind::Text,
-            scope: SymbolScope::Dynamic,
-            weak: false,
-            section: write::SymbolSection::Section(text_section),
-            flags: SymbolFlags::None,
-        });
+    let (cputype, cpusubtype, minos, ret_instruction): (u32, u32, u32, &[u8]) =
+        match triple.architecture {
+            target_lexicon::Architecture::X86_64 => (
+                macho::CPU_TYPE_X86_64,
+                macho::CPU_SUBTYPE_X86_64_ALL,
+                0x000a_0d00, // 10.13
+                &[0xc3],
+            ),
+            target_lexicon::Architecture::Aarch64(_) => (
+                macho::CPU_TYPE_ARM64,
+                macho::CPU_SUBTYPE_ARM64_ALL,
+                0x000b_0000, // 11.0
+                &[0xc0, 0x03, 0x5f, 0xd6],
+            ),
+            _ => {
+                // We should have verified this via supported() before calling this function
+                unreachable!()
+            }
+        };
+
+    // C symbols get an underscore prefix on macOS
+    let symbol_names: Vec<Vec<u8>> = custom_names
+        .iter()
+        .map(|name| [b"_", name.as_bytes()].concat())
+        .collect();
+
+    let header_size = mem::size_of::<macho::MachHeader64<LE>>();
+    let segment_size = mem::size_of::<macho::SegmentCommand64<LE>>();
+    let section_size = mem::size_of::<macho::Section64<LE>>();
+    let id_dylib_size = align(
+        mem::size_of::<macho::DylibCommand<LE>>() + APP_DYLIB.len() + 1,
+        8,
+    );
+
+    let sizeofcmds = (segment_size + section_size)
+        + segment_size
+        + id_dylib_size
+        + mem::size_of::<macho::DyldInfoCommand<LE>>()
+        + mem::size_of::<macho::SymtabCommand<LE>>()
+        + mem::size_of::<macho::DysymtabCommand<LE>>()
+        + mem::size_of::<macho::BuildVersionCommand<LE>>();
+
+    // All the stub functions share a single `ret` instruction right after the load commands.
+    let text_offset = align(header_size + sizeofcmds, 16) as u64;
+
+    // Lay out __LINKEDIT: the export trie, then the symbol table, then the string table.
+    let mut trie = TrieNode::default();
+    for name in symbol_names.iter() {
+        trie.insert(name, text_offset);
+    }
+    let export_trie = trie.encode();
+
+    let mut linkedit = export_trie;
+    linkedit.resize(align(linkedit.len(), 8), 0);
+
+    let export_off = PAGE_SIZE;
+    let export_size = linkedit.len() as u64;
+
+    let symoff = PAGE_SIZE + linkedit.len() as u64;
+    let mut strtab = vec![0u8];
+    for name in symbol_names.iter() {
+        let nlist = macho::Nlist64 {
+            n_strx: U32::new(LE, strtab.len() as u32),
+            n_type: macho::N_SECT | macho::N_EXT,
+            n_sect: 1,
+            n_desc: U16::new(LE, 0),
+            n_value: U64::new(LE, text_offset),
+        };
+        linkedit.extend_from_slice(bytes_of(&nlist));
+
+        strtab.extend_from_slice(name);
+        strtab.push(0);
     }
+    strtab.resize(align(strtab.len(), 8), 0);
+
+    let stroff = PAGE_SIZE + linkedit.len() as u64;
+    linkedit.extend_from_slice(&strtab);
+
+    let mut out = Vec::with_capacity(PAGE_SIZE as usize + linkedit.len());
 
-    std::fs::write(
-        dummy_obj_file.path(),
-        out_object.write().expect("failed to build output object"),
-    )
-    .expect("failed to write object to file");
-
-    // This path only exists on macOS Big Sur, and it causes ld errors
-    // on Catalina if it's specified with -L, so we replace it with a
-    // redundant -lSystem if the directory isn't there.
-    let big_sur_path = "/Library/Developer/CommandLineTools/SDKs/MacOSX.sdk/usr/lib";
-    let big_sur_fix = if Path::new(big_sur_path).exists() {
-        "-L/Library/Developer/CommandLineTools/SDKs/MacOSX.sdk/usr/lib"
-    } else {
-        "-lSystem" // We say -lSystem twice in the case of non-Big-Sur OSes, but it's fine.
+    let header = macho::MachHeader64 {
+        magic: U32::new(BigEndian, macho::MH_CIGAM_64),
+        cputype: U32::new(LE, cputype),
+        cpusubtype: U32::new(LE, cpusubtype),
+        filetype: U32::new(LE, macho::MH_DYLIB),
+        ncmds: U32::new(LE, 7),
+        sizeofcmds: U32::new(LE, sizeofcmds as u32),
+        flags: U32::new(
+            LE,
+            macho::MH_NOUNDEFS | macho::MH_DYLDLINK | macho::MH_TWOLEVEL,
+        ),
+        reserved: U32::new(LE, 0),
     };
+    out.extend_from_slice(bytes_of(&header));
 
-    let ld_flag_soname = "-install_name";
-    let ld_prefix_args = [big_sur_fix, "-lSystem", "-dylib"];
-
-    let output = Command::new("ld")
-        .args(ld_prefix_args)
-        .args([
-            ld_flag_soname,
-            dummy_lib_file.file_name().unwrap().to_str().unwrap(),
-            dummy_obj_file.path().to_str().unwrap(),
-            "-o",
-            dummy_lib_file.to_str().unwrap(),
-        ])
-        .output()
-        .unwrap();
-
-    // Extend the lifetime of the tempfile so it doesn't get dropped
-    // (and thus deleted) before the linker process is done using it!
-    let _ = dummy_obj_file;
-
-    if !output.status.success() {
-        match std::str::from_utf8(&output.stderr) {
-            Ok(stderr) => panic!(
-                "Failed to link dummy shared library - stderr of the `ld` command was:\n{}",
-                stderr
-            ),
-            Err(utf8_err) => panic!(
-                "Failed to link dummy shared library  - stderr of the `ld` command was invalid utf8 ({:?})",
-                utf8_err
-            ),
+    let text_segment = macho::SegmentCommand64 {
+        cmd: U32::new(LE, macho::LC_SEGMENT_64),
+        cmdsize: U32::new(LE, (segment_size + section_size) as u32),
+        segname: name16(b"__TEXT"),
+        vmaddr: U64::new(LE, 0),
+        vmsize: U64::new(LE, PAGE_SIZE),
+        fileoff: U64::new(LE, 0),
+        filesize: U64::new(LE, PAGE_SIZE),
+        maxprot: U32::new(LE, macho::VM_PROT_READ | macho::VM_PROT_EXECUTE),
+        initprot: U32::new(LE, macho::VM_PROT_READ | macho::VM_PROT_EXECUTE),
+        nsects: U32::new(LE, 1),
+        flags: U32::new(LE, 0),
+    };
+    out.extend_from_slice(bytes_of(&text_segment));
+
+    let text_section = macho::Section64 {
+        sectname: name16(b"__text"),
+        segname: name16(b"__TEXT"),
+        addr: U64::new(LE, text_offset),
+        size: U64::new(LE, ret_instruction.len() as u64),
+        offset: U32::new(LE, text_offset as u32),
+        align: U32::new(LE, 2),
+        reloff: U32::new(LE, 0),
+        nreloc: U32::new(LE, 0),
+        flags: U32::new(
+            LE,
+            macho::S_REGULAR | macho::S_ATTR_PURE_INSTRUCTIONS | macho::S_ATTR_SOME_INSTRUCTIONS,
+        ),
+        reserved1: U32::new(LE, 0),
+        reserved2: U32::new(LE, 0),
+        reserved3: U32::new(LE, 0),
+    };
+    out.extend_from_slice(bytes_of(&text_section));
+
+    let linkedit_segment = macho::SegmentCommand64 {
+        cmd: U32::new(LE, macho::LC_SEGMENT_64),
+        cmdsize: U32::new(LE, segment_size as u32),
+        segname: name16(b"__LINKEDIT"),
+        vmaddr: U64::new(LE, PAGE_SIZE),
+        vmsize: U64::new(LE, align(linkedit.len(), PAGE_SIZE as usize) as u64),
+        fileoff: U64::new(LE, PAGE_SIZE),
+        filesize: U64::new(LE, linkedit.len() as u64),
+        maxprot: U32::new(LE, macho::VM_PROT_READ),
+        initprot: U32::new(LE, macho::VM_PROT_READ),
+        nsects: U32::new(LE, 0),
+        flags: U32::new(LE, 0),
+    };
+    out.extend_from_slice(bytes_of(&linkedit_segment));
+
+    let id_dylib = macho::DylibCommand {
+        cmd: U32::new(LE, macho::LC_ID_DYLIB),
+        cmdsize: U32::new(LE, id_dylib_size as u32),
+        dylib: macho::Dylib {
+            name: macho::LcStr {
+                offset: U32::new(LE, mem::size_of::<macho::DylibCommand<LE>>() as u32),
+            },
+            timestamp: U32::new(LE, 1),
+            current_version: U32::new(LE, 0x0001_0000),
+            compatibility_version: U32::new(LE, 0x0001_0000),
+        },
+    };
+    let id_dylib_start = out.len();
+    out.extend_from_slice(bytes_of(&id_dylib));
+    out.extend_from_slice(APP_DYLIB.as_bytes());
+    out.resize(id_dylib_start + id_dylib_size, 0);
+
+    let dyld_info = macho::DyldInfoCommand {
+        cmd: U32::new(LE, macho::LC_DYLD_INFO_ONLY),
+        cmdsize: U32::new(LE, mem::size_of::<macho::DyldInfoCommand<LE>>() as u32),
+        rebase_off: U32::new(LE, 0),
+        rebase_size: U32::new(LE, 0),
+        bind_off: U32::new(LE, 0),
+        bind_size: U32::new(LE, 0),
+        weak_bind_off: U32::new(LE, 0),
+        weak_bind_size: U32::new(LE, 0),
+        lazy_bind_off: U32::new(LE, 0),
+        lazy_bind_size: U32::new(LE, 0),
+        export_off: U32::new(LE, export_off as u32),
+        export_size: U32::new(LE, export_size as u32),
+    };
+    out.extend_from_slice(bytes_of(&dyld_info));
+
+    let symtab = macho::SymtabCommand {
+        cmd: U32::new(LE, macho::LC_SYMTAB),
+        cmdsize: U32::new(LE, mem::size_of::<macho::SymtabCommand<LE>>() as u32),
+        symoff: U32::new(LE, symoff as u32),
+        nsyms: U32::new(LE, symbol_names.len() as u32),
+        stroff: U32::new(LE, stroff as u32),
+        strsize: U32::new(LE, strtab.len() as u32),
+    };
+    out.extend_from_slice(bytes_of(&symtab));
+
+    let zero = U32::new(LE, 0);
+    let dysymtab = macho::DysymtabCommand {
+        cmd: U32::new(LE, macho::LC_DYSYMTAB),
+        cmdsize: U32::new(LE, mem::size_of::<macho::DysymtabCommand<LE>>() as u32),
+        ilocalsym: zero,
+        nlocalsym: zero,
+        iextdefsym: zero,
+        nextdefsym: U32::new(LE, symbol_names.len() as u32),
+        iundefsym: U32::new(LE, symbol_names.len() as u32),
+        nundefsym: zero,
+        tocoff: zero,
+        ntoc: zero,
+        modtaboff: zero,
+        nmodtab: zero,
+        extrefsymoff: zero,
+        nextrefsyms: zero,
+        indirectsymoff: zero,
+        nindirectsyms: zero,
+        extreloff: zero,
+        nextrel: zero,
+        locreloff: zero,
+        nlocrel: zero,
+    };
+    out.extend_from_slice(bytes_of(&dysymtab));
+
+    let build_version = macho::BuildVersionCommand {
+        cmd: U32::new(LE, macho::LC_BUILD_VERSION),
+        cmdsize: U32::new(LE, mem::size_of::<macho::BuildVersionCommand<LE>>() as u32),
+        platform: U32::new(LE, macho::PLATFORM_MACOS),
+        minos: U32::new(LE, minos),
+        sdk: U32::new(LE, minos),
+        ntools: U32::new(LE, 0),
+    };
+    out.extend_from_slice(bytes_of(&build_version));
+
+    debug_assert_eq!(out.len(), header_size + sizeofcmds);
+
+    out.resize(text_offset as usize, 0);
+    out.extend_from_slice(ret_instruction);
+
+    out.resize(PAGE_SIZE as usize, 0);
+    out.extend_from_slice(&linkedit);
+
+    Ok(out)
+}
+
+fn align(value: usize, alignment: usize) -> usize {
+    (value + alignment - 1) / alignment * alignment
+}
+
+fn name16(name: &[u8]) -> [u8; 16] {
+    let mut bytes = [0; 16];
+    bytes[..name.len()].copy_from_slice(name);
+    bytes
+}
+
+fn uleb128(out: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+
+        if value == 0 {
+            out.push(byte);
+            break;
+        } else {
+            out.push(byte | 0x80);
+        }
+    }
+}
+
+/// A node of the export trie that dyld (and the static linker) use to look up exported symbols.
+/// Edges are labeled with the part of the symbol name they match, and a node is terminal if the
+/// path from the root to it spells out an exported symbol.
+#[derive(Default)]
+struct TrieNode {
+    address: Option<u64>,
+    children: Vec<(Vec<u8>, TrieNode)>,
+}
+
+impl TrieNode {
+    fn insert(&mut self, name: &[u8], address: u64) {
+        if name.is_empty() {
+            self.address = Some(address);
+            return;
+        }
+
+        for (label, child) in self.children.iter_mut() {
+            let common = label.iter().zip(name).take_while(|(a, b)| a == b).count();
+
+            if common == 0 {
+                continue;
+            }
+
+            if common < label.len() {
+                // Split the edge, so that the shared prefix gets a node of its own
+                let rest = label.split_off(common);
+                let old_child = mem::take(child);
+                child.children.push((rest, old_child));
+            }
+
+            child.insert(&name[common..], address);
+            return;
         }
+
+        let leaf = TrieNode {
+            address: Some(address),
+            children: Vec::new(),
+        };
+        self.children.push((name.to_vec(), leaf));
     }
 
-    Ok(std::fs::read(dummy_lib_file).expect("Failed to load dummy library"))
+    /// Flattens the trie in pre-order, returning each node with the indices of its children
+    fn flatten<'a>(&'a self, nodes: &mut Vec<(&'a TrieNode, Vec<usize>)>) -> usize {
+        let index = nodes.len();
+        nodes.push((self, Vec::new()));
+
+        for (_, child) in self.children.iter() {
+            let child_index = child.flatten(nodes);
+            nodes[index].1.push(child_index);
+        }
+
+        index
+    }
+
+    fn encode_node(&self, children: &[usize], offsets: &[u64], out: &mut Vec<u8>) {
+        match self.address {
+            Some(address) => {
+                let mut info = Vec::new();
+                uleb128(&mut info, macho::EXPORT_SYMBOL_FLAGS_KIND_REGULAR as u64);
+                uleb128(&mut info, address);
+
+                uleb128(out, info.len() as u64);
+                out.extend_from_slice(&info);
+            }
+            None => out.push(0),
+        }
+
+        out.push(self.children.len() as u8);
+        for ((label, _), child_index) in self.children.iter().zip(children) {
+            out.extend_from_slice(label);
+            out.push(0);
+            uleb128(out, offsets[*child_index]);
+        }
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut nodes = Vec::new();
+        self.flatten(&mut nodes);
+
+        // The size of a node depends on the (uleb128-encoded) offsets of its children, so
+        // iterate until the offsets no longer change
+        let mut offsets = vec![0; nodes.len()];
+        loop {
+            let mut changed = false;
+            let mut offset = 0;
+            let mut buffer = Vec::new();
+
+            for (i, (node, children)) in nodes.iter().enumerate() {
+                if offsets[i] != offset {
+                    offsets[i] = offset;
+                    changed = true;
+                }
+
+                buffer.clear();
+                node.encode_node(children, &offsets, &mut buffer);
+                offset += buffer.len() as u64;
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        let mut out = Vec::new();
+        for (node, children) in nodes.iter() {
+            node.encode_node(children, &offsets, &mut out);
+        }
+
+        out
+    }
 }
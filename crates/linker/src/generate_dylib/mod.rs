@@ -10,6 +10,7 @@ pub(crate) use pe::synthetic_dll;
 #[cfg(test)]
 pub(crate) use elf64::create_dylib_elf64;
 
+pub(crate) use macho::APP_DYLIB;
 pub(crate) use pe::APP_DLL;
 
 pub fn generate(target: &Triple, custom_names: &[String]) -> object::read::Result<Vec<u8>> {
@@ -35,10 +36,16 @@ mod tests {
 
         let exports = object.exports().unwrap();
         for custom in custom_names {
+            let name = match target.binary_format {
+                // C symbols get an underscore prefix on macOS
+                target_lexicon::BinaryFormat::Macho => format!("_{custom}"),
+                _ => custom,
+            };
+
             assert!(
-                exports.iter().any(|e| e.name() == custom.as_bytes()),
+                exports.iter().any(|e| e.name() == name.as_bytes()),
                 "missing {}",
-                &custom
+                &name
             );
         }
     }
@@ -55,6 +62,32 @@ mod tests {
         check_exports(&target);
     }
 
+    #[test]
+    fn check_exports_macho_x86_64() {
+        let target = target_lexicon::Triple {
+            architecture: target_lexicon::Architecture::X86_64,
+            operating_system: target_lexicon::OperatingSystem::Darwin,
+            binary_format: target_lexicon::BinaryFormat::Macho,
+            ..target_lexicon::Triple::host()
+        };
+
+        check_exports(&target);
+    }
+
+    #[test]
+    fn check_exports_macho_aarch64() {
+        let target = target_lexicon::Triple {
+            architecture: target_lexicon::Architecture::Aarch64(
+                target_lexicon::Aarch64Architecture::Aarch64,
+            ),
+            operating_system: target_lexicon::OperatingSystem::Darwin,
+            binary_format: target_lexicon::BinaryFormat::Macho,
+            ..target_lexicon::Triple::host()
+        };
+
+        check_exports(&target);
+    }
+
     #[test]
     fn check_exports_coff() {
         // NOTE: this does not work
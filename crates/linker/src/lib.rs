@@ -40,12 +40,13 @@ pub fn supported(link_type: LinkType, target: &Triple) -> bool {
                 ..
             } => true,
 
-            // macho support is incomplete
             Triple {
+                architecture:
+                    target_lexicon::Architecture::X86_64 | target_lexicon::Architecture::Aarch64(_),
                 operating_system: target_lexicon::OperatingSystem::Darwin,
                 binary_format: target_lexicon::BinaryFormat::Macho,
                 ..
-            } => false,
+            } => true,
 
             Triple {
                 architecture: target_lexicon::Architecture::X86_64,
@@ -135,10 +136,12 @@ pub fn generate_stub_lib(
     };
 
     if let EntryPoint::Executable { platform_path, .. } = &loaded.entry_point {
-        let stub_lib = if let target_lexicon::OperatingSystem::Windows = triple.operating_system {
-            platform_path.with_file_name("libapp.obj")
-        } else {
-            platform_path.with_file_name("libapp.so")
+        let stub_lib = match triple.operating_system {
+            target_lexicon::OperatingSystem::Windows => platform_path.with_file_name("libapp.obj"),
+            target_lexicon::OperatingSystem::Darwin => {
+                platform_path.with_file_name(generate_dylib::APP_DYLIB)
+            }
+            _ => platform_path.with_file_name("libapp.so"),
         };
 
         let stub_dll_symbols = exposed_symbols.stub_dll_symbols();
@@ -154,10 +157,12 @@ pub fn generate_stub_lib_from_loaded(
     platform_main_roc: &Path,
     stub_dll_symbols: &[String],
 ) -> PathBuf {
-    let stub_lib_path = if let target_lexicon::OperatingSystem::Windows = target.operating_system {
-        platform_main_roc.with_file_name("libapp.dll")
-    } else {
-        platform_main_roc.with_file_name("libapp.so")
+    let stub_lib_path = match target.operating_system {
+        target_lexicon::OperatingSystem::Windows => platform_main_roc.with_file_name("libapp.dll"),
+        target_lexicon::OperatingSystem::Darwin => {
+            platform_main_roc.with_file_name(generate_dylib::APP_DYLIB)
+        }
+        _ => platform_main_roc.with_file_name("libapp.so"),
     };
 
     generate_dynamic_lib(target, stub_dll_symbols, &stub_lib_path);
@@ -345,12 +350,22 @@ fn object_matches_target<'a>(target: &Triple, object: &object::File<'a, &'a [u8]
             let target_format = match target.operating_system {
                 TLO::Linux => object::BinaryFormat::Elf,
                 TLO::Windows => object::BinaryFormat::Pe,
+                TLO::Darwin => object::BinaryFormat::MachO,
                 _ => todo!("surgical linker does not support target {:?}", target),
             };
 
             object.format() == target_format
         }
-        TLA::Aarch64(_) => object.architecture() == object::Architecture::Aarch64,
+        TLA::Aarch64(_) => {
+            if object.architecture() != object::Architecture::Aarch64 {
+                return false;
+            }
+
+            match target.operating_system {
+                TLO::Darwin => object.format() == object::BinaryFormat::MachO,
+                _ => true,
+            }
+        }
         _ => todo!("surgical linker does not support target {:?}", target),
     }
 }
@@ -376,7 +391,11 @@ fn stub_lib_is_up_to_date(target: &Triple, stub_lib_path: &Path, custom_names: &
     // we need all symbols to be there and in the correct order
     let dynamic_symbols: Vec<_> = object.exports().unwrap();
 
-    let it1 = dynamic_symbols.iter().map(|e| e.name());
+    let it1 = dynamic_symbols.iter().map(|e| match object.format() {
+        // C symbols get an underscore prefix on macOS
+        object::BinaryFormat::MachO => e.name().strip_prefix(b"_").unwrap_or(e.name()),
+        _ => e.name(),
+    });
     let it2 = custom_names.iter().map(|s| s.as_bytes());
 
     it1.eq(it2)
@@ -4,13 +4,14 @@ use memmap2::MmapMut;
 use object::macho;
 use object::{
     CompressedFileRange, CompressionFormat, LittleEndian as LE, Object, ObjectSection,
-    ObjectSymbol, RelocationKind, RelocationTarget, Section, SectionIndex, SectionKind, Symbol,
-    SymbolIndex, SymbolSection,
+    ObjectSymbol, Relocation, RelocationEncoding, RelocationKind, RelocationTarget, Section,
+    SectionIndex, SectionKind, Symbol, SymbolIndex, SymbolSection,
 };
 use roc_collections::all::MutMap;
-use roc_error_macros::internal_error;
+use roc_error_macros::{internal_error, user_error};
 use serde::{Deserialize, Serialize};
 use std::{
+    borrow::Cow,
     ffi::CStr,
     io::{BufReader, BufWriter},
     mem,
@@ -20,23 +21,33 @@ use std::{
 use target_lexicon::Triple;
 
 use crate::{
-    align_by_constraint, align_to_offset_by_constraint, load_struct_inplace,
-    load_struct_inplace_mut, load_structs_inplace, load_structs_inplace_mut, open_mmap,
-    open_mmap_mut,
+    align_by_constraint, load_struct_inplace, load_struct_inplace_mut, load_structs_inplace,
+    open_mmap, open_mmap_mut,
 };
 
 const MIN_SECTION_ALIGNMENT: usize = 0x40;
 
-// TODO: Analyze if this offset is always correct.
-const PLT_ADDRESS_OFFSET: u64 = 0x10;
-const STUB_ADDRESS_OFFSET: u64 = 0x06;
+const TEXT_SEGMENT: [u8; 16] = *b"__TEXT\0\0\0\0\0\0\0\0\0\0";
+const LINKEDIT_SEGMENT: [u8; 16] = *b"__LINKEDIT\0\0\0\0\0\0";
+const APP_DATA_SEGMENT: [u8; 16] = *b"__ROC_DATA\0\0\0\0\0\0";
+const APP_TEXT_SEGMENT: [u8; 16] = *b"__ROC_TEXT\0\0\0\0\0\0";
 
-// struct MachoDynamicDeps {
-//     got_app_syms: Vec<(String, usize)>,
-//     got_sections: Vec<(usize, usize)>,
-//     dynamic_lib_count: usize,
-//     shared_lib_index: usize,
-// }
+/// The app's read-only data and code each get a segment with a single section.
+const APP_SEGMENT_COUNT: usize = 2;
+const APP_LOAD_COMMANDS_SIZE: usize = APP_SEGMENT_COUNT
+    * (mem::size_of::<macho::SegmentCommand64<LE>>() + mem::size_of::<macho::Section64<LE>>());
+
+const AARCH64_BRANCH_MASK: u32 = 0xfc00_0000;
+const AARCH64_B: u32 = 0x1400_0000;
+const AARCH64_BL: u32 = 0x9400_0000;
+const AARCH64_NOP: u32 = 0xd503_201f;
+
+// Pointer formats of chained fixups, from <mach-o/fixup-chains.h>.
+const DYLD_CHAINED_PTR_64: u16 = 2;
+const DYLD_CHAINED_PTR_64_OFFSET: u16 = 6;
+
+const CHAINED_PTR_64_BIND: u64 = 1 << 63;
+const CHAINED_PTR_64_NEXT_MASK: u64 = 0xfff << 51;
 
 #[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
 enum VirtualOffset {
@@ -51,10 +62,13 @@ struct SurgeryEntry {
     size: u8,
 }
 
-// TODO: Reanalyze each piece of data in this struct.
-// I think a number of them can be combined to reduce string duplication.
-// Also I think a few of them aren't need.
-// For example, I think preprocessing can deal with all shifting and remove the need for added_byte_count.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
+struct GotEntry {
+    file_offset: u64,
+    // The chained fixup pointer format of the segment the entry is in.
+    pointer_format: u16,
+}
+
 // TODO: we probably should be storing numbers in an endian neutral way.
 #[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
 struct Metadata {
@@ -62,18 +76,23 @@ struct Metadata {
     // offset followed by address.
     plt_addresses: MutMap<String, (u64, u64)>,
     surgeries: MutMap<String, Vec<SurgeryEntry>>,
+    // GOT entries that the host loads the address of an app function from.
+    got_entries: MutMap<String, Vec<GotEntry>>,
+    // index into the host's symbol table.
     dynamic_symbol_indices: MutMap<String, u64>,
-    _static_symbol_indices: MutMap<String, u64>,
     roc_symbol_vaddresses: MutMap<String, u64>,
+    cpu_type: u32,
+    stub_size: u64,
     exec_len: u64,
     load_align_constraint: u64,
-    added_byte_count: u64,
-    last_vaddr: u64,
-    _dynamic_section_offset: u64,
-    _dynamic_symbol_table_section_offset: u64,
-    _symbol_table_section_offset: u64,
-    _symbol_table_size: u64,
+    // The (still empty) load commands for the app's segments start here.
     macho_cmd_loc: u64,
+    // The number of the first app section, as used by symbol table entries.
+    first_app_section: u8,
+    linkedit_file_offset: u64,
+    linkedit_vaddr: u64,
+    // The address of __TEXT, which DYLD_CHAINED_PTR_64_OFFSET targets are relative to.
+    image_base: u64,
 }
 
 impl Metadata {
@@ -153,6 +172,12 @@ fn collect_roc_definitions<'a>(object: &object::File<'a, &'a [u8]>) -> MutMap<St
     vaddresses
 }
 
+/// The name that `collect_roc_definitions` uses for a symbol.
+/// C symbols get an underscore prefix on macOS.
+fn unprefixed_name(name: &str) -> &str {
+    name.strip_prefix('_').unwrap_or(name)
+}
+
 struct Surgeries<'a> {
     surgeries: MutMap<String, Vec<SurgeryEntry>>,
     app_func_addresses: MutMap<u64, &'a str>,
@@ -181,6 +206,7 @@ impl<'a> Surgeries<'a> {
         &mut self,
         object_bytes: &[u8],
         object: &object::File<'a, &'a [u8]>,
+        cpu_type: u32,
         verbose: bool,
     ) {
         let text_sections: Vec<Section> = object
@@ -204,11 +230,19 @@ impl<'a> Surgeries<'a> {
         }
 
         for text_section in text_sections {
-            self.append_text_section(object_bytes, &text_section, verbose)
+            match cpu_type {
+                macho::CPU_TYPE_X86_64 => {
+                    self.append_text_section(object_bytes, &text_section, verbose)
+                }
+                macho::CPU_TYPE_ARM64 => {
+                    self.append_text_section_aarch64(object_bytes, &text_section, verbose)
+                }
+                _ => internal_error!("Unsupported Mach-O cpu type: {:#x}", cpu_type),
+            }
         }
     }
 
-    fn append_text_section(&mut self, object_bytes: &[u8], sec: &Section, verbose: bool) {
+    fn text_section_data<'b>(sec: &Section<'b, '_>) -> (u64, bool, Cow<'b, [u8]>) {
         let (file_offset, compressed) = match sec.compressed_file_range() {
             Ok(CompressedFileRange {
                 format: CompressionFormat::None,
@@ -231,6 +265,13 @@ impl<'a> Surgeries<'a> {
                 internal_error!("Failed to load text section, {:+x?}: {}", sec, err);
             }
         };
+
+        (file_offset, compressed, data)
+    }
+
+    fn append_text_section(&mut self, object_bytes: &[u8], sec: &Section, verbose: bool) {
+        let (file_offset, compressed, data) = Self::text_section_data(sec);
+
         let mut decoder = Decoder::with_ip(64, &data, sec.address(), DecoderOptions::NONE);
         let mut inst = Instruction::default();
 
@@ -322,6 +363,132 @@ impl<'a> Surgeries<'a> {
             }
         }
     }
+
+    /// On aarch64 every instruction is 4 bytes, so rather than disassembling, we look for the
+    /// `b` and `bl` instructions that branch to the stub of an app function.
+    fn append_text_section_aarch64(&mut self, object_bytes: &[u8], sec: &Section, verbose: bool) {
+        let (file_offset, compressed, data) = Self::text_section_data(sec);
+
+        for (i, bytes) in data.chunks_exact(4).enumerate() {
+            let inst = u32::from_le_bytes(bytes.try_into().unwrap());
+
+            let opcode = inst & AARCH64_BRANCH_MASK;
+            if opcode != AARCH64_B && opcode != AARCH64_BL {
+                continue;
+            }
+
+            let ip = sec.address() + 4 * i as u64;
+            let target = ip.wrapping_add(aarch64_branch_offset(inst) as u64);
+
+            if let Some(func_name) = self.app_func_addresses.get(&target) {
+                if compressed {
+                    internal_error!(
+                        "Surgical linking does not work with compressed text sections: {:+x?}",
+                        sec
+                    );
+                }
+
+                let offset = file_offset + 4 * i as u64;
+                if verbose {
+                    println!(
+                        "Found branch from {:+x} to {:+x}({})",
+                        ip, target, func_name
+                    );
+                    println!(
+                        "\tNeed to surgically replace 4 bytes at file offset {:+x}",
+                        offset,
+                    );
+                    println!(
+                        "\tIts current value is {:+x?}",
+                        &object_bytes[offset as usize..offset as usize + 4]
+                    )
+                }
+
+                self.surgeries
+                    .get_mut(*func_name)
+                    .unwrap()
+                    .push(SurgeryEntry {
+                        file_offset: offset,
+                        // branches on aarch64 are relative to the branch instruction itself
+                        virtual_offset: VirtualOffset::Relative(ip),
+                        size: 4,
+                    });
+            }
+        }
+    }
+}
+
+/// The (sign-extended) byte offset of an aarch64 `b` or `bl` instruction.
+fn aarch64_branch_offset(inst: u32) -> i64 {
+    (((inst << 6) as i32) >> 6) as i64 * 4
+}
+
+/// Replaces the target of an aarch64 `b` or `bl` instruction.
+fn aarch64_set_branch_offset(inst: u32, offset: i64) -> u32 {
+    if offset % 4 != 0 || !(-(1 << 27)..(1 << 27)).contains(&offset) {
+        internal_error!(
+            "Branch offset {:+x} is out of range for an aarch64 branch",
+            offset
+        );
+    }
+
+    (inst & AARCH64_BRANCH_MASK) | ((offset >> 2) as u32 & !AARCH64_BRANCH_MASK)
+}
+
+/// Returns the file offset and `cmd` of every load command.
+fn load_commands(data: &[u8]) -> Vec<(usize, u32)> {
+    let header = load_struct_inplace::<macho::MachHeader64<LE>>(data, 0);
+    let mut offset = mem::size_of_val(header);
+
+    (0..header.ncmds.get(LE))
+        .map(|_| {
+            let info = load_struct_inplace::<macho::LoadCommand<LE>>(data, offset);
+            let command = (offset, info.cmd.get(LE));
+            offset += info.cmdsize.get(LE) as usize;
+            command
+        })
+        .collect()
+}
+
+fn segment_sections(data: &[u8], segment_offset: usize) -> &[macho::Section64<LE>] {
+    let segment = load_struct_inplace::<macho::SegmentCommand64<LE>>(data, segment_offset);
+
+    load_structs_inplace::<macho::Section64<LE>>(
+        data,
+        segment_offset + mem::size_of_val(segment),
+        segment.nsects.get(LE) as usize,
+    )
+}
+
+/// Reads the path out of a `LC_LOAD_DYLIB` command.
+fn dylib_path(exec_data: &[u8], offset: usize) -> &Path {
+    let info = load_struct_inplace::<macho::DylibCommand<LE>>(exec_data, offset);
+    let name_offset = info.dylib.name.offset.get(LE) as usize;
+    let str_start_index = offset + name_offset;
+    let str_end_index = offset + info.cmdsize.get(LE) as usize;
+    let str_bytes = &exec_data[str_start_index..str_end_index];
+
+    if str_bytes[str_bytes.len() - 1] == 0 {
+        // If it's nul-terminated, it's a C String.
+        // Use the unchecked version because these are
+        // padded with 0s at the end, so since we don't
+        // know the exact length, using the checked version
+        // of this can fail due to the interior nul bytes.
+        //
+        // Also, we have to use from_ptr instead of
+        // from_bytes_with_nul_unchecked because currently
+        // std::ffi::CStr is actually not a char* under
+        // the hood (!) but rather an array, so to strip
+        // the trailing null bytes we have to use from_ptr.
+        let c_str = unsafe { CStr::from_ptr(str_bytes.as_ptr() as *const std::ffi::c_char) };
+
+        Path::new(c_str.to_str().unwrap())
+    } else {
+        // It wasn't nul-terminated, so treat all the bytes
+        // as the string
+
+        Path::new(std::str::from_utf8(str_bytes).unwrap())
+    }
 }
 
 /// Constructs a `Metadata` from a host executable binary, and writes it to disk
@@ -344,8 +511,30 @@ pub(crate) fn preprocess_macho(
         }
     };
 
+    let exec_header = load_struct_inplace::<macho::MachHeader64<LE>>(exec_data, 0);
+    if exec_header.magic.get(object::BigEndian) != macho::MH_CIGAM_64 {
+        // Every 64-bit Mac is little-endian.
+        internal_error!("Roc only supports little-endian 64-bit Mach-O hosts!");
+    }
+
+    let cpu_type = exec_header.cputype.get(LE);
+    let load_align_constraint = match (target.architecture, cpu_type) {
+        (target_lexicon::Architecture::X86_64, macho::CPU_TYPE_X86_64) => 0x1000,
+        // Apple Silicon uses 16k pages.
+        (target_lexicon::Architecture::Aarch64(_), macho::CPU_TYPE_ARM64) => 0x4000,
+        _ => {
+            internal_error!(
+                "The host executable (cpu type {:#x}) was not built for {}",
+                cpu_type,
+                target
+            );
+        }
+    };
+
     let mut md = Metadata {
         roc_symbol_vaddresses: collect_roc_definitions(&exec_obj),
+        cpu_type,
+        load_align_constraint,
         ..Default::default()
     };
 
@@ -358,166 +547,157 @@ pub(crate) fn preprocess_macho(
 
     let exec_parsing_duration = exec_parsing_start.elapsed();
 
-    // PLT stands for Procedure Linkage Table which is, put simply, used to call external
-    // procedures/functions whose address isn't known in the time of linking, and is left
-    // to be resolved by the dynamic linker at run time.
+    // Stubs are the Mach-O equivalent of the PLT (Procedure Linkage Table), which is, put simply,
+    // used to call external procedures/functions whose address isn't known in the time of linking,
+    // and is left to be resolved by the dynamic linker at run time.
     let symbol_and_plt_processing_start = Instant::now();
-    let plt_section_name = "__stubs";
-
-    let (plt_address, plt_offset) = match exec_obj.section_by_name(plt_section_name) {
-        Some(section) => {
-            let file_offset = match section.compressed_file_range() {
-                Ok(
-                    range @ CompressedFileRange {
-                        format: CompressionFormat::None,
-                        ..
-                    },
-                ) => range.offset,
-                _ => {
-                    internal_error!("Surgical linking does not work with compressed plt section");
+
+    let mut stubs = None;
+    let mut got_sections = Vec::new();
+    let mut indirect_symbols: &[u8] = &[];
+    let mut chained_fixups = None;
+    let mut macho_load_so_offset = None;
+    let shared_lib_filename = shared_lib.file_name();
+    let mut segment_index = 0;
+
+    for (offset, cmd) in load_commands(exec_data) {
+        match cmd {
+            macho::LC_SEGMENT_64 => {
+                let segment = load_struct_inplace::<macho::SegmentCommand64<LE>>(exec_data, offset);
+                if segment.segname == TEXT_SEGMENT {
+                    md.image_base = segment.vmaddr.get(LE);
                 }
-            };
-            (section.address(), file_offset)
+
+                for section in segment_sections(exec_data, offset) {
+                    match section.flags.get(LE) & macho::SECTION_TYPE {
+                        macho::S_SYMBOL_STUBS => stubs = Some(*section),
+                        macho::S_NON_LAZY_SYMBOL_POINTERS => {
+                            got_sections.push((segment_index, *section))
+                        }
+                        _ => {}
+                    }
+                }
+                segment_index += 1;
+            }
+            macho::LC_DYLD_CHAINED_FIXUPS => {
+                let info = load_struct_inplace::<macho::LinkeditDataCommand<LE>>(exec_data, offset);
+                let start = info.dataoff.get(LE) as usize;
+                let len = info.datasize.get(LE) as usize;
+
+                chained_fixups = Some(&exec_data[start..start + len]);
+            }
+            macho::LC_DYSYMTAB => {
+                let info = load_struct_inplace::<macho::DysymtabCommand<LE>>(exec_data, offset);
+                let start = info.indirectsymoff.get(LE) as usize;
+                let len = info.nindirectsyms.get(LE) as usize * mem::size_of::<u32>();
+
+                indirect_symbols = &exec_data[start..start + len];
+            }
+            macho::LC_LOAD_DYLIB
+                if dylib_path(exec_data, offset).file_name() == shared_lib_filename =>
+            {
+                macho_load_so_offset = Some(offset);
+            }
+            _ => {}
         }
+    }
+
+    let stubs = match stubs {
+        Some(section) => section,
         None => {
-            internal_error!("Failed to find PLT section. Probably an malformed executable.");
+            internal_error!("Failed to find the stubs section. Probably an malformed executable.");
         }
     };
+
+    let plt_address = stubs.addr.get(LE);
+    let plt_offset = stubs.offset.get(LE) as u64;
+    md.stub_size = stubs.reserved2.get(LE) as u64;
     if verbose {
         println!("PLT Address: {:+x}", plt_address);
         println!("PLT File Offset: {:+x}", plt_offset);
+        println!("PLT Entry Size: {:+x}", md.stub_size);
     }
 
     let app_syms: Vec<_> = exec_obj.symbols().filter(is_roc_undefined).collect();
 
     let mut app_func_addresses: MutMap<u64, &str> = MutMap::default();
-    let mut macho_load_so_offset = None;
-
-    {
-        use macho::{DyldInfoCommand, DylibCommand, Section64, SegmentCommand64};
-
-        let exec_header = load_struct_inplace::<macho::MachHeader64<LE>>(exec_data, 0);
-        let num_load_cmds = exec_header.ncmds.get(LE);
-
-        let mut offset = mem::size_of_val(exec_header);
-
-        let mut stubs_symbol_index = None;
-        let mut stubs_symbol_count = None;
 
-        'cmds: for _ in 0..num_load_cmds {
-            let info = load_struct_inplace::<macho::LoadCommand<LE>>(exec_data, offset);
-            let cmd = info.cmd.get(LE);
-            let cmdsize = info.cmdsize.get(LE);
-
-            if cmd == macho::LC_SEGMENT_64 {
-                let info = load_struct_inplace::<SegmentCommand64<LE>>(exec_data, offset);
-
-                if &info.segname[0..6] == b"__TEXT" {
-                    let sections = info.nsects.get(LE);
-
-                    let sections_info = load_structs_inplace::<Section64<LE>>(
-                        exec_data,
-                        offset + mem::size_of_val(info),
-                        sections as usize,
-                    );
-
-                    for section_info in sections_info {
-                        if &section_info.sectname[0..7] == b"__stubs" {
-                            stubs_symbol_index = Some(section_info.reserved1.get(LE));
-                            stubs_symbol_count =
-                                Some(section_info.size.get(LE) / STUB_ADDRESS_OFFSET);
-
-                            break 'cmds;
-                        }
-                    }
-                }
-            }
+    // Each stub has an entry in the indirect symbol table, which is an index into the symbol table.
+    let first_stub_symbol = stubs.reserved1.get(LE) as usize;
+    let stub_count = stubs.size.get(LE) / md.stub_size;
+    for i in 0..stub_count {
+        let entry = (first_stub_symbol + i as usize) * mem::size_of::<u32>();
+        let symbol_index = match indirect_symbols.get(entry..entry + 4) {
+            Some(bytes) => u32::from_le_bytes(bytes.try_into().unwrap()) as usize,
+            None => internal_error!("The indirect symbol table is missing an entry for stub {i}"),
+        };
 
-            offset += cmdsize as usize;
+        if let Some(sym) = app_syms.iter().find(|sym| sym.index().0 == symbol_index) {
+            let func_address = plt_address + i * md.stub_size;
+            let func_offset = plt_offset + i * md.stub_size;
+            app_func_addresses.insert(func_address, sym.name().unwrap());
+            md.plt_addresses
+                .insert(sym.name().unwrap().to_string(), (func_offset, func_address));
         }
+    }
 
-        let stubs_symbol_index = stubs_symbol_index.unwrap_or_else(|| {
-            panic!("Could not find stubs symbol index.");
-        });
-        let stubs_symbol_count = stubs_symbol_count.unwrap_or_else(|| {
-            panic!("Could not find stubs symbol count.");
-        });
-
-        // Reset offset before looping through load commands again
-        offset = mem::size_of_val(exec_header);
-
-        let shared_lib_filename = shared_lib.file_name();
-
-        for _ in 0..num_load_cmds {
-            let info = load_struct_inplace::<macho::LoadCommand<LE>>(exec_data, offset);
-            let cmd = info.cmd.get(LE);
-            let cmdsize = info.cmdsize.get(LE);
-
-            if cmd == macho::LC_DYLD_INFO_ONLY {
-                let info = load_struct_inplace::<DyldInfoCommand<LE>>(exec_data, offset);
+    // A host that takes the address of an app function loads it from a GOT entry, which dyld
+    // would bind to the stub dylib. Surgery points those entries at the app function instead.
+    for (segment_index, got) in got_sections {
+        let first_got_symbol = got.reserved1.get(LE) as usize;
+        let entry_count = got.size.get(LE) / mem::size_of::<u64>() as u64;
+        for i in 0..entry_count {
+            let entry = (first_got_symbol + i as usize) * mem::size_of::<u32>();
+            let symbol_index = match indirect_symbols.get(entry..entry + 4) {
+                Some(bytes) => u32::from_le_bytes(bytes.try_into().unwrap()) as usize,
+                None => {
+                    internal_error!(
+                        "The indirect symbol table is missing an entry for GOT entry {i}"
+                    )
+                }
+            };
 
-                let lazy_bind_offset = info.lazy_bind_off.get(LE) as usize;
+            let name = match app_syms.iter().find(|sym| sym.index().0 == symbol_index) {
+                Some(sym) => sym.name().unwrap(),
+                None => continue,
+            };
 
-                let lazy_bind_symbols = mach_object::LazyBind::parse(
-                    &exec_data[lazy_bind_offset..],
-                    mem::size_of::<usize>(),
+            let pointer_format = match chained_fixups {
+                Some(fixups) => chained_pointer_format(fixups, segment_index),
+                None => user_error!(
+                    "The host takes the address of `{}`, which the surgical linker can only support in hosts that use chained fixups. Please build the host for macOS 12 or later, or only call `{}` directly.",
+                    name,
+                    name
+                ),
+            };
+            if !matches!(
+                pointer_format,
+                DYLD_CHAINED_PTR_64 | DYLD_CHAINED_PTR_64_OFFSET
+            ) {
+                user_error!(
+                    "The host takes the address of `{}` through a chained fixup of format {}, which the surgical linker does not support.",
+                    name,
+                    pointer_format
                 );
+            }
 
-                // Find all the lazily-bound roc symbols
-                // (e.g. "_roc__mainForHost_1_exposed")
-                // For Macho, we may need to deal with some GOT stuff here as well.
-                for (i, symbol) in lazy_bind_symbols
-                    .skip(stubs_symbol_index as usize)
-                    .take(stubs_symbol_count as usize)
-                    .enumerate()
-                {
-                    if let Some(sym) = app_syms
-                        .iter()
-                        .find(|app_sym| app_sym.name() == Ok(&symbol.name))
-                    {
-                        let func_address = (i as u64 + 1) * STUB_ADDRESS_OFFSET + plt_address;
-                        let func_offset = (i as u64 + 1) * STUB_ADDRESS_OFFSET + plt_offset;
-                        app_func_addresses.insert(func_address, sym.name().unwrap());
-                        md.plt_addresses
-                            .insert(sym.name().unwrap().to_string(), (func_offset, func_address));
-                    }
-                }
-            } else if cmd == macho::LC_LOAD_DYLIB {
-                let info = load_struct_inplace::<DylibCommand<LE>>(exec_data, offset);
-                let name_offset = info.dylib.name.offset.get(LE) as usize;
-                let str_start_index = offset + name_offset;
-                let str_end_index = offset + cmdsize as usize;
-                let str_bytes = &exec_data[str_start_index..str_end_index];
-                let path = {
-                    if str_bytes[str_bytes.len() - 1] == 0 {
-                        // If it's nul-terminated, it's a C String.
-                        // Use the unchecked version because these are
-                        // padded with 0s at the end, so since we don't
-                        // know the exact length, using the checked version
-                        // of this can fail due to the interior nul bytes.
-                        //
-                        // Also, we have to use from_ptr instead of
-                        // from_bytes_with_nul_unchecked because currently
-                        // std::ffi::CStr is actually not a char* under
-                        // the hood (!) but rather an array, so to strip
-                        // the trailing null bytes we have to use from_ptr.
-                        let c_str = unsafe { CStr::from_ptr(str_bytes.as_ptr() as *const i8) };
-
-                        Path::new(c_str.to_str().unwrap())
-                    } else {
-                        // It wasn't nul-terminated, so treat all the bytes
-                        // as the string
-
-                        Path::new(std::str::from_utf8(str_bytes).unwrap())
-                    }
-                };
-
-                if path.file_name() == shared_lib_filename {
-                    macho_load_so_offset = Some(offset);
-                }
+            let file_offset = got.offset.get(LE) as u64 + i * mem::size_of::<u64>() as u64;
+            let value = u64::from_le_bytes(
+                exec_data[file_offset as usize..file_offset as usize + 8]
+                    .try_into()
+                    .unwrap(),
+            );
+            if value & CHAINED_PTR_64_BIND == 0 {
+                internal_error!("The GOT entry for `{}` is not bound by dyld", name);
             }
 
-            offset += cmdsize as usize;
+            md.got_entries
+                .entry(name.to_string())
+                .or_default()
+                .push(GotEntry {
+                    file_offset,
+                    pointer_format,
+                });
         }
     }
 
@@ -542,65 +722,28 @@ pub(crate) fn preprocess_macho(
     let text_disassembly_start = Instant::now();
 
     let mut surgeries = Surgeries::new(&app_syms, app_func_addresses);
-    surgeries.append_text_sections(exec_data, &exec_obj, verbose);
+    surgeries.append_text_sections(exec_data, &exec_obj, md.cpu_type, verbose);
     md.surgeries = surgeries.surgeries;
 
     let text_disassembly_duration = text_disassembly_start.elapsed();
 
-    let scanning_dynamic_deps_duration;
-    let platform_gen_start;
+    let platform_gen_start = Instant::now();
 
-    let out_mmap = {
-        match target
-            .endianness()
-            .unwrap_or(target_lexicon::Endianness::Little)
-        {
-            target_lexicon::Endianness::Little => {
-                let scanning_dynamic_deps_start = Instant::now();
-
-                // let ElfDynamicDeps {
-                //     got_app_syms,
-                //     got_sections,
-                //     dynamic_lib_count,
-                //     shared_lib_index,
-                // } = scan_elf_dynamic_deps(
-                //     &exec_obj, &mut md, &app_syms, shared_lib, exec_data, verbose,
-                // );
-
-                scanning_dynamic_deps_duration = scanning_dynamic_deps_start.elapsed();
-
-                platform_gen_start = Instant::now();
-
-                // TODO little endian
-                let macho_load_so_offset = match macho_load_so_offset {
-                    Some(offset) => offset,
-                    None => {
-                        internal_error!("Host does not link library `{}`!", shared_lib.display());
-                    }
-                };
-
-                // TODO this is correct on modern Macs (they align to the page size)
-                // but maybe someone can override the alignment somehow? Maybe in the
-                // future this could change? Is there some way to make this more future-proof?
-                md.load_align_constraint = 4096;
-
-                gen_macho_le(
-                    exec_data,
-                    &mut md,
-                    preprocessed_path,
-                    macho_load_so_offset,
-                    target,
-                    verbose,
-                )
-            }
-            target_lexicon::Endianness::Big => {
-                // TODO Is big-endian macOS even a thing that exists anymore?
-                // Just ancient PowerPC machines maybe?
-                todo!("Roc does not yet support big-endian macOS hosts!");
-            }
+    let macho_load_so_offset = match macho_load_so_offset {
+        Some(offset) => offset,
+        None => {
+            internal_error!("Host does not link library `{}`!", shared_lib.display());
         }
     };
 
+    let out_mmap = gen_macho_le(
+        exec_data,
+        &mut md,
+        preprocessed_path,
+        macho_load_so_offset,
+        verbose,
+    );
+
     let platform_gen_duration = platform_gen_start.elapsed();
 
     if verbose {
@@ -631,7 +774,6 @@ pub(crate) fn preprocess_macho(
             symbol_and_plt_processing_duration,
         );
         report_timing("Text Disassembly", text_disassembly_duration);
-        report_timing("Scanning Dynamic Deps", scanning_dynamic_deps_duration);
         report_timing("Generate Modified Platform", platform_gen_duration);
         report_timing("Saving Metadata", saving_metadata_duration);
         report_timing("Flushing Data to Disk", flushing_data_duration);
@@ -641,7 +783,6 @@ pub(crate) fn preprocess_macho(
                 - exec_parsing_duration
                 - symbol_and_plt_processing_duration
                 - text_disassembly_duration
-                - scanning_dynamic_deps_duration
                 - platform_gen_duration
                 - saving_metadata_duration
                 - flushing_data_duration,
@@ -650,13 +791,21 @@ pub(crate) fn preprocess_macho(
     }
 }
 
+/// Generates the preprocessed host. Compared to the original host, it
+///
+/// - has (empty) load commands for the app's segments, right before the `__LINKEDIT` segment;
+/// - weakly links the stub dylib, so dyld does not complain that it is missing at run time;
+/// - no longer has a code signature, which would not match after surgery anyway.
+///
+/// The host's code and data do not move, so none of their addresses change. At surgery time,
+/// the app is put where `__LINKEDIT` used to be, and `__LINKEDIT` moves to the end of the file.
+/// Keeping `__LINKEDIT` as the last segment is something that Apple tooling expects.
 fn gen_macho_le(
     exec_data: &[u8],
     md: &mut Metadata,
     out_filename: &Path,
     macho_load_so_offset: usize,
-    _target: &Triple,
-    _verbose: bool,
+    verbose: bool,
 ) -> MmapMut {
     // Just adding some extra context/useful info here.
     // I was talking to Jakub from the Zig team about macho linking and here are some useful comments:
@@ -669,469 +818,359 @@ fn gen_macho_le(
     // https://github.com/kubkon/zignature
     // https://github.com/kubkon/zig-deploy
 
-    use macho::{Section64, SegmentCommand64};
+    use macho::{LinkeditDataCommand, LoadCommand, SegmentCommand64};
 
     let exec_header = load_struct_inplace::<macho::MachHeader64<LE>>(exec_data, 0);
-    let num_load_cmds = exec_header.ncmds.get(LE);
-    let size_of_cmds = exec_header.sizeofcmds.get(LE) as usize;
+    let header_size = mem::size_of_val(exec_header);
 
-    // Add a new text segment and data segment
-    let segment_cmd_size = mem::size_of::<SegmentCommand64<LE>>();
-    let section_size = mem::size_of::<Section64<LE>>();
+    let mut cmds: Vec<u8> =
+        Vec::with_capacity(exec_header.sizeofcmds.get(LE) as usize + APP_LOAD_COMMANDS_SIZE);
+    let mut num_load_cmds = 0;
 
-    // We need the full command size, including the dynamic-length string at the end.
-    // To get that, we need to load the command.
-    let info = load_struct_inplace::<macho::LoadCommand<LE>>(exec_data, macho_load_so_offset);
-    let total_cmd_size = info.cmdsize.get(LE) as usize;
-
-    // ======================== Important TODO ==========================
-    // TODO: we accidentally instroduced a big change here.
-    // We use a mix of added_bytes and md.added_byte_count.
-    // Theses should proabably be the same variable.
-    // Also, I just realized that I have been shifting a lot of virtual offsets.
-    // This should not be necessary. If we add a fully 4k of buffer to the file, all of the virtual offsets will still stay aligned.
-    // So a lot of the following work can probably be commented out if we fix that.
-    // Of coruse, it will cost about 4k bytes to a final executable.
-    // This is what the elf version currently does.
-    // Theoretically it is not needed (if we update all virtual addresses in the binary),
-    // but I definitely ran into problems with elf when not adding this extra buffering.
-
-    // Copy header and shift everything to enable more program sections.
-    let added_byte_count = ((2 * segment_cmd_size) + (2 * section_size) - total_cmd_size) as u64;
-    md.added_byte_count = added_byte_count
-        // add some alignment padding
-        + (MIN_SECTION_ALIGNMENT as u64 - added_byte_count % MIN_SECTION_ALIGNMENT as u64);
-
-    md.exec_len = exec_data.len() as u64 + md.added_byte_count;
+    let mut segment_count = 0;
+    let mut section_count = 0;
+    let mut linkedit = None;
+    let mut code_signature = None;
+    let mut chained_fixups_cmd_loc = None;
 
-    let mut out_mmap = open_mmap_mut(out_filename, md.exec_len as usize);
-    let end_of_cmds = size_of_cmds + mem::size_of_val(exec_header);
+    // The load commands can grow into the padding before the first section.
+    let mut first_section_offset = exec_data.len();
 
-    // "Delete" the dylib load command - by copying all the bytes before it
-    // and all the bytes after it, while skipping over its bytes.
-    // It has a dynamic-length string at the end that we also need to delete,
-    // in addition to the header.
-    out_mmap[..macho_load_so_offset].copy_from_slice(&exec_data[..macho_load_so_offset]);
+    for (offset, cmd) in load_commands(exec_data) {
+        let cmd_size = load_struct_inplace::<LoadCommand<LE>>(exec_data, offset)
+            .cmdsize
+            .get(LE) as usize;
+        let cmd_bytes = &exec_data[offset..offset + cmd_size];
+        let cmd_loc = header_size + cmds.len();
 
-    out_mmap[macho_load_so_offset..end_of_cmds - total_cmd_size]
-        .copy_from_slice(&exec_data[macho_load_so_offset + total_cmd_size..end_of_cmds]);
+        match cmd {
+            macho::LC_SEGMENT_64 => {
+                let segment = load_struct_inplace::<SegmentCommand64<LE>>(exec_data, offset);
+
+                for section in segment_sections(exec_data, offset) {
+                    let section_offset = section.offset.get(LE) as usize;
+                    if section_offset != 0 {
+                        first_section_offset = first_section_offset.min(section_offset);
+                    }
+                }
 
-    // Copy the rest of the file, leaving a gap for the surgical linking to add our 2 commands
-    // (which happens after preprocessing), and some zero padding at the end for alignemnt.
-    // (It seems to cause bugs if that padding isn't there!)
-    let rest_of_data = &exec_data[end_of_cmds..];
-    let start_index = end_of_cmds + md.added_byte_count as usize;
+                if segment.segname == LINKEDIT_SEGMENT {
+                    md.macho_cmd_loc = cmd_loc as u64;
+                    md.first_app_section = section_count as u8 + 1;
+                    md.linkedit_file_offset = segment.fileoff.get(LE);
+                    md.linkedit_vaddr = segment.vmaddr.get(LE);
+
+                    // Leave room for the app's segments, which surgery will fill in
+                    cmds.resize(cmds.len() + APP_LOAD_COMMANDS_SIZE, 0);
+                    num_load_cmds += APP_SEGMENT_COUNT as u32;
+
+                    linkedit = Some((
+                        header_size + cmds.len(),
+                        segment_count + APP_SEGMENT_COUNT,
+                        segment.filesize.get(LE) as usize,
+                    ));
+                }
 
-    out_mmap[start_index..start_index + rest_of_data.len()].copy_from_slice(rest_of_data);
+                segment_count += 1;
+                section_count += segment.nsects.get(LE) as usize;
+            }
+            macho::LC_CODE_SIGNATURE => {
+                let info = load_struct_inplace::<LinkeditDataCommand<LE>>(exec_data, offset);
+                code_signature = Some(info.dataoff.get(LE) as usize);
 
-    let out_header = load_struct_inplace_mut::<macho::MachHeader64<LE>>(&mut out_mmap, 0);
+                // Drop the command; the signature no longer matches once the app is added.
+                continue;
+            }
+            macho::LC_DYLD_CHAINED_FIXUPS => {
+                chained_fixups_cmd_loc = Some(cmd_loc);
+            }
+            _ => {}
+        }
+
+        cmds.extend_from_slice(cmd_bytes);
+        num_load_cmds += 1;
+
+        if offset == macho_load_so_offset {
+            // Weakly linking the dylib means that dyld will not fail when it is missing at run time.
+            // We cannot remove the command altogether, because load commands for dylibs are
+            // referred to by their index (e.g. by binding info).
+            let cmd = load_struct_inplace_mut::<LoadCommand<LE>>(&mut cmds, cmd_loc - header_size);
+            cmd.cmd.set(LE, macho::LC_LOAD_WEAK_DYLIB);
+        }
+    }
+
+    let (linkedit_cmd_loc, linkedit_segment_index, linkedit_size) = match linkedit {
+        Some(linkedit) => linkedit,
+        None => internal_error!(
+            "Failed to find the __LINKEDIT segment. Probably an malformed executable."
+        ),
+    };
+
+    let end_of_cmds = header_size + cmds.len();
+    if end_of_cmds > first_section_offset {
+        user_error!(
+            "The host executable does not have enough room after its load commands for the surgical linker (it needs {} bytes, but only has {}). Please link the host with `-headerpad 0x1000` or `-headerpad_max_install_names`.",
+            end_of_cmds - header_size,
+            first_section_offset - header_size,
+        );
+    }
+
+    // The code signature is always at the end of __LINKEDIT, so we can drop it from the file.
+    let linkedit_end = md.linkedit_file_offset as usize + linkedit_size;
+    let linkedit_end = match code_signature {
+        Some(offset) if offset < linkedit_end => offset,
+        _ => linkedit_end,
+    };
+
+    // The chained fixups list every segment, so they need updating too.
+    // The updated version goes at the end of __LINKEDIT, because it is larger than the old one.
+    let chained_fixups = chained_fixups_cmd_loc.map(|cmd_loc| {
+        let info =
+            load_struct_inplace_mut::<LinkeditDataCommand<LE>>(&mut cmds, cmd_loc - header_size);
+        let start = info.dataoff.get(LE) as usize;
+        let fixups = add_segments_to_chained_fixups(
+            &exec_data[start..start + info.datasize.get(LE) as usize],
+            linkedit_segment_index - APP_SEGMENT_COUNT,
+            APP_SEGMENT_COUNT,
+        );
+
+        info.dataoff
+            .set(LE, align_by_constraint(linkedit_end, 8) as u32);
+        info.datasize.set(LE, fixups.len() as u32);
+
+        fixups
+    });
+
+    md.exec_len = match &chained_fixups {
+        Some(fixups) => (align_by_constraint(linkedit_end, 8) + fixups.len()) as u64,
+        None => linkedit_end as u64,
+    };
 
-    // TODO: this needs to change to adding the 2 new commands when we are ready.
-    // -1 because we're deleting 1 load command and then NOT adding 2 new ones.
     {
-        let added_bytes = -(total_cmd_size as isize); // TODO: Change when add the new sections.
-        out_header.ncmds.set(LE, num_load_cmds - 1);
-        out_header
-            .sizeofcmds
-            .set(LE, (size_of_cmds as isize + added_bytes) as u32);
+        let linkedit = load_struct_inplace_mut::<SegmentCommand64<LE>>(
+            &mut cmds,
+            linkedit_cmd_loc - header_size,
+        );
+        let linkedit_size = md.exec_len - md.linkedit_file_offset;
+        linkedit.filesize.set(LE, linkedit_size);
+        linkedit.vmsize.set(
+            LE,
+            align_by_constraint(linkedit_size as usize, md.load_align_constraint as usize) as u64,
+        );
     }
 
-    // Go through every command and shift it by added_bytes if it's absolute, unless it's inside the command header
-    let mut offset = mem::size_of_val(exec_header);
-
-    // TODO: Add this back in the future when needed.
-    // let cpu_type = match target.architecture {
-    //     target_lexicon::Architecture::X86_64 => macho::CPU_TYPE_X86_64,
-    //     target_lexicon::Architecture::Aarch64(_) => macho::CPU_TYPE_ARM64,
-    //     _ => {
-    //         // We should have verified this via supported() before calling this function
-    //         unreachable!()
-    //     }
-    // };
-
-    // minus one because we "deleted" a load command
-    for _ in 0..(num_load_cmds - 1) {
-        let info = load_struct_inplace::<macho::LoadCommand<LE>>(&out_mmap, offset);
-        let cmd_size = info.cmdsize.get(LE) as usize;
-
-        match info.cmd.get(LE) {
-            macho::LC_SEGMENT_64 => {
-                let cmd =
-                    load_struct_inplace_mut::<macho::SegmentCommand64<LE>>(&mut out_mmap, offset);
+    if verbose {
+        println!();
+        println!(
+            "Preprocessed host has {} load commands ({} bytes)",
+            num_load_cmds,
+            cmds.len()
+        );
+        println!(
+            "__LINKEDIT is at {:+x} (virt: {:+x})",
+            md.linkedit_file_offset, md.linkedit_vaddr
+        );
+    }
 
-                // Ignore page zero, it never moves.
-                if cmd.segname == "__PAGEZERO\0\0\0\0\0\0".as_bytes() || cmd.vmaddr.get(LE) == 0 {
-                    offset += cmd_size;
-                    continue;
-                }
+    let mut out_mmap = open_mmap_mut(out_filename, md.exec_len as usize);
 
-                let old_file_offest = cmd.fileoff.get(LE);
-                // The segment with file offset zero also includes the header.
-                // As such, its file offset does not change.
-                // Instead, its file size should be increased.
-                if old_file_offest > 0 {
-                    cmd.fileoff.set(LE, old_file_offest + md.added_byte_count);
-                    cmd.vmaddr.set(LE, cmd.vmaddr.get(LE) + md.added_byte_count);
-                } else {
-                    cmd.filesize
-                        .set(LE, cmd.filesize.get(LE) + md.added_byte_count);
-                    cmd.vmsize.set(LE, cmd.vmsize.get(LE) + md.added_byte_count);
-                }
+    out_mmap[..linkedit_end].copy_from_slice(&exec_data[..linkedit_end]);
+    out_mmap[header_size..end_of_cmds].copy_from_slice(&cmds);
 
-                // let num_sections = cmd.nsects.get(LE);
-                // let sections = load_structs_inplace_mut::<macho::Section64<LE >>(
-                //     &mut out_mmap,
-                //     offset + mem::size_of::<macho::SegmentCommand64<LE >>(),
-                //     num_sections as usize,
-                // );
-                // struct Relocation {
-                //     offset: u32,
-                //     num_relocations: u32,
-                // }
-
-                // let mut relocation_offsets = Vec::with_capacity(sections.len());
-
-                // for section in sections {
-                //     section.addr.set(
-                //         LE ,
-                //         section.addr.get(LE) + md.added_byte_count as u64,
-                //     );
-
-                //     // If offset is zero, don't update it.
-                //     // Zero is used for things like BSS that don't exist in the file.
-                //     let old_offset = section.offset.get(LE);
-                //     if old_offset > 0 {
-                //         section
-                //             .offset
-                //             .set(LE , old_offset + md.added_byte_count as u32);
-                //     }
-
-                //     // dbg!(&section.reloff.get(LE));
-                //     // dbg!(section.reloff.get(LE) as i32);
-                //     // dbg!(&section);
-                //     // dbg!(&md.added_byte_count);
-                //     // dbg!(String::from_utf8_lossy(&section.sectname));
-                //     if section.nreloc.get(LE) > 0 {
-                //         section.reloff.set(
-                //             LE ,
-                //             section.reloff.get(LE) + md.added_byte_count as u32,
-                //         );
-                //     }
-
-                //     relocation_offsets.push(Relocation {
-                //         offset: section.reloff.get(LE),
-                //         num_relocations: section.nreloc.get(LE),
-                //     });
-                // }
-
-                // TODO FIXME this is necessary for ARM, but seems to be broken. Skipping for now since we're just targeting x86
-                // for Relocation {
-                //     offset,
-                //     num_relocations,
-                // } in relocation_offsets
-                // {
-                //     let relos = load_structs_inplace_mut::<macho::Relocation<LE >>(
-                //         &mut out_mmap,
-                //         offset as usize,
-                //         num_relocations as usize,
-                //     );
-
-                //     // TODO this has never been tested, because scattered relocations only come up on ARM!
-                //     for relo in relos.iter_mut() {
-                //         if relo.r_scattered(LE , cpu_type) {
-                //             let mut scattered_info = relo.scattered_info(LE);
-
-                //             if !scattered_info.r_pcrel {
-                //                 scattered_info.r_value += md.added_byte_count as u32;
-
-                //                 let new_info = scattered_info.relocation(LE );
-
-                //                 relo.r_word0 = new_info.r_word0;
-                //                 relo.r_word1 = new_info.r_word1;
-                //             }
-                //         }
-                //     }
-                // }
-
-                // TODO this seems to be wrong and unnecessary, and should probably be deleted.
-                // offset += num_sections as usize * mem::size_of::<macho::Section64<LE >>();
-            }
-            macho::LC_SYMTAB => {
-                let cmd =
-                    load_struct_inplace_mut::<macho::SymtabCommand<LE>>(&mut out_mmap, offset);
+    if let (Some(fixups), Some(cmd_loc)) = (chained_fixups, chained_fixups_cmd_loc) {
+        let info = load_struct_inplace::<LinkeditDataCommand<LE>>(&out_mmap, cmd_loc);
+        let start = info.dataoff.get(LE) as usize;
+        out_mmap[start..start + fixups.len()].copy_from_slice(&fixups);
+    }
 
-                let sym_offset = cmd.symoff.get(LE);
-                let num_syms = cmd.nsyms.get(LE);
+    let out_header = load_struct_inplace_mut::<macho::MachHeader64<LE>>(&mut out_mmap, 0);
+    out_header.ncmds.set(LE, num_load_cmds);
+    out_header.sizeofcmds.set(LE, cmds.len() as u32);
 
-                if num_syms > 0 {
-                    cmd.symoff.set(LE, sym_offset + md.added_byte_count as u32);
-                }
+    out_mmap
+}
 
-                if cmd.strsize.get(LE) > 0 {
-                    cmd.stroff
-                        .set(LE, cmd.stroff.get(LE) + md.added_byte_count as u32);
+/// The pointer format of the chained fixups in the given segment.
+fn chained_pointer_format(fixups: &[u8], segment_index: usize) -> u16 {
+    let read_u32 =
+        |offset: usize| u32::from_le_bytes(fixups[offset..offset + 4].try_into().unwrap());
+
+    // struct dyld_chained_fixups_header
+    let starts_offset = read_u32(4) as usize;
+
+    // struct dyld_chained_starts_in_image
+    let seg_info_offset = match read_u32(starts_offset + 4 + 4 * segment_index) {
+        0 => internal_error!(
+            "Segment {} of the host has no chained fixups",
+            segment_index
+        ),
+        offset => starts_offset + offset as usize,
+    };
+
+    // struct dyld_chained_starts_in_segment
+    let pointer_format = seg_info_offset + 6;
+    u16::from_le_bytes(
+        fixups[pointer_format..pointer_format + 2]
+            .try_into()
+            .unwrap(),
+    )
+}
+
+/// Adds (empty) entries for new segments to the `dyld_chained_starts_in_image` table of the
+/// chained fixups. dyld expects that table to have an entry for every segment.
+fn add_segments_to_chained_fixups(fixups: &[u8], index: usize, count: usize) -> Vec<u8> {
+    let read_u32 = |bytes: &[u8], offset: usize| {
+        u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+    };
+
+    // struct dyld_chained_fixups_header
+    let starts_offset = read_u32(fixups, 4) as usize;
+    let imports_offset = read_u32(fixups, 8) as usize;
+    let symbols_offset = read_u32(fixups, 12) as usize;
+
+    // struct dyld_chained_starts_in_image
+    let seg_count = read_u32(fixups, starts_offset) as usize;
+    let seg_info_offsets_end = starts_offset + 4 + 4 * seg_count;
+
+    if imports_offset < seg_info_offsets_end || symbols_offset < seg_info_offsets_end {
+        internal_error!("Unexpected layout of the chained fixups in the host executable");
+    }
+
+    let added = 4 * count as u32;
+    let mut out = Vec::with_capacity(fixups.len() + added as usize);
+
+    out.extend_from_slice(&fixups[..starts_offset]);
+    out[8..12].copy_from_slice(&(imports_offset as u32 + added).to_le_bytes());
+    out[12..16].copy_from_slice(&(symbols_offset as u32 + added).to_le_bytes());
+
+    out.extend_from_slice(&((seg_count + count) as u32).to_le_bytes());
+    for i in 0..seg_count {
+        if i == index {
+            out.resize(out.len() + added as usize, 0);
+        }
+
+        // These offsets are relative to the start of dyld_chained_starts_in_image,
+        // and zero means that the segment has no fixups.
+        let seg_info_offset = match read_u32(fixups, starts_offset + 4 + 4 * i) {
+            0 => 0,
+            offset => offset + added,
+        };
+        out.extend_from_slice(&seg_info_offset.to_le_bytes());
+    }
+
+    out.extend_from_slice(&fixups[seg_info_offsets_end..]);
+
+    out
+}
+
+/// Moves all the file offsets that point into `__LINKEDIT`, as well as the segment itself.
+fn shift_linkedit(exec_mmap: &mut [u8], file_shift: u64, virtual_shift: u64) {
+    use macho::{
+        DyldInfoCommand, DysymtabCommand, LinkeditDataCommand, SegmentCommand64, SymtabCommand,
+        TwolevelHintsCommand,
+    };
+
+    let shift = |offset: u32| offset + file_shift as u32;
+
+    for (offset, cmd) in load_commands(exec_mmap) {
+        match cmd {
+            macho::LC_SEGMENT_64 => {
+                let cmd = load_struct_inplace_mut::<SegmentCommand64<LE>>(exec_mmap, offset);
+
+                if cmd.segname == LINKEDIT_SEGMENT {
+                    cmd.fileoff.set(LE, cmd.fileoff.get(LE) + file_shift);
+                    cmd.vmaddr.set(LE, cmd.vmaddr.get(LE) + virtual_shift);
                 }
+            }
+            macho::LC_SYMTAB => {
+                let cmd = load_struct_inplace_mut::<SymtabCommand<LE>>(exec_mmap, offset);
 
-                let table = load_structs_inplace_mut::<macho::Nlist64<LE>>(
-                    &mut out_mmap,
-                    sym_offset as usize + md.added_byte_count as usize,
-                    num_syms as usize,
-                );
+                if cmd.nsyms.get(LE) > 0 {
+                    cmd.symoff.set(LE, shift(cmd.symoff.get(LE)));
+                }
 
-                for entry in table {
-                    let entry_type = entry.n_type & macho::N_TYPE;
-                    if entry_type == macho::N_ABS || entry_type == macho::N_SECT {
-                        entry
-                            .n_value
-                            .set(LE, entry.n_value.get(LE) + md.added_byte_count);
-                    }
+                if cmd.strsize.get(LE) > 0 {
+                    cmd.stroff.set(LE, shift(cmd.stroff.get(LE)));
                 }
             }
             macho::LC_DYSYMTAB => {
-                let cmd =
-                    load_struct_inplace_mut::<macho::DysymtabCommand<LE>>(&mut out_mmap, offset);
+                let cmd = load_struct_inplace_mut::<DysymtabCommand<LE>>(exec_mmap, offset);
 
                 if cmd.ntoc.get(LE) > 0 {
-                    cmd.tocoff
-                        .set(LE, cmd.tocoff.get(LE) + md.added_byte_count as u32);
+                    cmd.tocoff.set(LE, shift(cmd.tocoff.get(LE)));
                 }
 
                 if cmd.nmodtab.get(LE) > 0 {
-                    cmd.modtaboff
-                        .set(LE, cmd.modtaboff.get(LE) + md.added_byte_count as u32);
+                    cmd.modtaboff.set(LE, shift(cmd.modtaboff.get(LE)));
                 }
 
                 if cmd.nextrefsyms.get(LE) > 0 {
-                    cmd.extrefsymoff
-                        .set(LE, cmd.extrefsymoff.get(LE) + md.added_byte_count as u32);
+                    cmd.extrefsymoff.set(LE, shift(cmd.extrefsymoff.get(LE)));
                 }
 
                 if cmd.nindirectsyms.get(LE) > 0 {
                     cmd.indirectsymoff
-                        .set(LE, cmd.indirectsymoff.get(LE) + md.added_byte_count as u32);
+                        .set(LE, shift(cmd.indirectsymoff.get(LE)));
                 }
 
                 if cmd.nextrel.get(LE) > 0 {
-                    cmd.extreloff
-                        .set(LE, cmd.extreloff.get(LE) + md.added_byte_count as u32);
+                    cmd.extreloff.set(LE, shift(cmd.extreloff.get(LE)));
                 }
 
                 if cmd.nlocrel.get(LE) > 0 {
-                    cmd.locreloff
-                        .set(LE, cmd.locreloff.get(LE) + md.added_byte_count as u32);
+                    cmd.locreloff.set(LE, shift(cmd.locreloff.get(LE)));
                 }
-
-                // TODO maybe we need to update something else too - relocations maybe?
-                // I think this also has symbols that need to get moved around.
-                // Look at otool -I at least for the indirect symbols.
             }
-            macho::LC_TWOLEVEL_HINTS => {
-                let cmd = load_struct_inplace_mut::<macho::TwolevelHintsCommand<LE>>(
-                    &mut out_mmap,
-                    offset,
-                );
+            macho::LC_DYLD_INFO | macho::LC_DYLD_INFO_ONLY => {
+                let cmd = load_struct_inplace_mut::<DyldInfoCommand<LE>>(exec_mmap, offset);
 
-                if cmd.nhints.get(LE) > 0 {
-                    cmd.offset
-                        .set(LE, cmd.offset.get(LE) + md.added_byte_count as u32);
+                if cmd.rebase_size.get(LE) > 0 {
+                    cmd.rebase_off.set(LE, shift(cmd.rebase_off.get(LE)));
                 }
-            }
-            macho::LC_FUNCTION_STARTS => {
-                let cmd = load_struct_inplace_mut::<macho::LinkeditDataCommand<LE>>(
-                    &mut out_mmap,
-                    offset,
-                );
 
-                if cmd.datasize.get(LE) > 0 {
-                    cmd.dataoff
-                        .set(LE, cmd.dataoff.get(LE) + md.added_byte_count as u32);
-                    // TODO: This lists the start of every function. Which, of course, have moved.
-                    // That being said, to my understanding this section is optional and may just be debug information.
-                    // As such, updating it should not be required.
-                    // It will be more work to update due to being in "DWARF-style ULEB128" values.
+                if cmd.bind_size.get(LE) > 0 {
+                    cmd.bind_off.set(LE, shift(cmd.bind_off.get(LE)));
+                }
+
+                if cmd.weak_bind_size.get(LE) > 0 {
+                    cmd.weak_bind_off.set(LE, shift(cmd.weak_bind_off.get(LE)));
+                }
+
+                if cmd.lazy_bind_size.get(LE) > 0 {
+                    cmd.lazy_bind_off.set(LE, shift(cmd.lazy_bind_off.get(LE)));
+                }
+
+                if cmd.export_size.get(LE) > 0 {
+                    cmd.export_off.set(LE, shift(cmd.export_off.get(LE)));
                 }
             }
-            macho::LC_DATA_IN_CODE => {
-                let (offset, size) = {
-                    let cmd = load_struct_inplace_mut::<macho::LinkeditDataCommand<LE>>(
-                        &mut out_mmap,
-                        offset,
-                    );
+            macho::LC_TWOLEVEL_HINTS => {
+                let cmd = load_struct_inplace_mut::<TwolevelHintsCommand<LE>>(exec_mmap, offset);
 
-                    if cmd.datasize.get(LE) > 0 {
-                        cmd.dataoff
-                            .set(LE, cmd.dataoff.get(LE) + md.added_byte_count as u32);
-                    }
-                    (cmd.dataoff.get(LE), cmd.datasize.get(LE))
-                };
-
-                // Update every data in code entry.
-                if size > 0 {
-                    let entry_size = mem::size_of::<macho::DataInCodeEntry<LE>>();
-                    let entries = load_structs_inplace_mut::<macho::DataInCodeEntry<LE>>(
-                        &mut out_mmap,
-                        offset as usize,
-                        size as usize / entry_size,
-                    );
-                    for entry in entries.iter_mut() {
-                        entry
-                            .offset
-                            .set(LE, entry.offset.get(LE) + md.added_byte_count as u32)
-                    }
+                if cmd.nhints.get(LE) > 0 {
+                    cmd.offset.set(LE, shift(cmd.offset.get(LE)));
                 }
             }
-            macho::LC_CODE_SIGNATURE
+            macho::LC_FUNCTION_STARTS
+            | macho::LC_DATA_IN_CODE
+            | macho::LC_CODE_SIGNATURE
             | macho::LC_SEGMENT_SPLIT_INFO
             | macho::LC_DYLIB_CODE_SIGN_DRS
             | macho::LC_LINKER_OPTIMIZATION_HINT
             | macho::LC_DYLD_EXPORTS_TRIE
             | macho::LC_DYLD_CHAINED_FIXUPS => {
-                let cmd = load_struct_inplace_mut::<macho::LinkeditDataCommand<LE>>(
-                    &mut out_mmap,
-                    offset,
-                );
+                let cmd = load_struct_inplace_mut::<LinkeditDataCommand<LE>>(exec_mmap, offset);
 
                 if cmd.datasize.get(LE) > 0 {
-                    cmd.dataoff
-                        .set(LE, cmd.dataoff.get(LE) + md.added_byte_count as u32);
+                    cmd.dataoff.set(LE, shift(cmd.dataoff.get(LE)));
                 }
             }
-            macho::LC_ENCRYPTION_INFO_64 => {
-                let cmd = load_struct_inplace_mut::<macho::EncryptionInfoCommand64<LE>>(
-                    &mut out_mmap,
-                    offset,
-                );
-
-                if cmd.cryptsize.get(LE) > 0 {
-                    cmd.cryptoff
-                        .set(LE, cmd.cryptoff.get(LE) + md.added_byte_count as u32);
-                }
+            _ => {
+                // Other commands don't refer to __LINKEDIT, so no change is needed for these.
             }
-            macho::LC_DYLD_INFO | macho::LC_DYLD_INFO_ONLY => {
-                let cmd =
-                    load_struct_inplace_mut::<macho::DyldInfoCommand<LE>>(&mut out_mmap, offset);
-
-                if cmd.rebase_size.get(LE) > 0 {
-                    cmd.rebase_off
-                        .set(LE, cmd.rebase_off.get(LE) + md.added_byte_count as u32);
-                }
-
-                if cmd.bind_size.get(LE) > 0 {
-                    cmd.bind_off
-                        .set(LE, cmd.bind_off.get(LE) + md.added_byte_count as u32);
-                }
-
-                if cmd.weak_bind_size.get(LE) > 0 {
-                    cmd.weak_bind_off
-                        .set(LE, cmd.weak_bind_off.get(LE) + md.added_byte_count as u32);
-                }
-
-                if cmd.lazy_bind_size.get(LE) > 0 {
-                    cmd.lazy_bind_off
-                        .set(LE, cmd.lazy_bind_off.get(LE) + md.added_byte_count as u32);
-                }
-
-                // TODO: Parse and update the related tables here.
-                // It is possible we may just need to delete things that point to stuff that will be in the roc app.
-                // We also may just be able to ignore it (lazy bindings should never run).
-                // This definitely has a list of virtual address that need to be updated.
-                // Some of them definitely will point to the roc app and should probably be removed.
-                // Also `xcrun dyldinfo` is useful for debugging this.
-            }
-            macho::LC_SYMSEG => {
-                let cmd =
-                    load_struct_inplace_mut::<macho::SymsegCommand<LE>>(&mut out_mmap, offset);
-
-                if cmd.size.get(LE) > 0 {
-                    cmd.offset
-                        .set(LE, cmd.offset.get(LE) + md.added_byte_count as u32);
-                }
-            }
-            macho::LC_MAIN => {
-                let cmd =
-                    load_struct_inplace_mut::<macho::EntryPointCommand<LE>>(&mut out_mmap, offset);
-
-                cmd.entryoff
-                    .set(LE, cmd.entryoff.get(LE) + md.added_byte_count);
-            }
-            macho::LC_NOTE => {
-                let cmd = load_struct_inplace_mut::<macho::NoteCommand<LE>>(&mut out_mmap, offset);
-
-                if cmd.size.get(LE) > 0 {
-                    cmd.offset.set(LE, cmd.offset.get(LE) + md.added_byte_count);
-                }
-            }
-            macho::LC_ID_DYLIB
-            | macho::LC_LOAD_WEAK_DYLIB
-            | macho::LC_LOAD_DYLIB
-            | macho::LC_REEXPORT_DYLIB
-            | macho::LC_SOURCE_VERSION
-            | macho::LC_IDENT
-            | macho::LC_LINKER_OPTION
-            | macho::LC_BUILD_VERSION
-            | macho::LC_VERSION_MIN_MACOSX
-            | macho::LC_VERSION_MIN_IPHONEOS
-            | macho::LC_VERSION_MIN_WATCHOS
-            | macho::LC_VERSION_MIN_TVOS
-            | macho::LC_UUID
-            | macho::LC_RPATH
-            | macho::LC_ID_DYLINKER
-            | macho::LC_LOAD_DYLINKER
-            | macho::LC_DYLD_ENVIRONMENT
-            | macho::LC_ROUTINES_64
-            | macho::LC_THREAD
-            | macho::LC_UNIXTHREAD
-            | macho::LC_PREBOUND_DYLIB
-            | macho::LC_SUB_FRAMEWORK
-            | macho::LC_SUB_CLIENT
-            | macho::LC_SUB_UMBRELLA
-            | macho::LC_SUB_LIBRARY => {
-                // These don't involve file offsets, so no change is needed for these.
-                // Just continue the loop!
-            }
-            macho::LC_PREBIND_CKSUM => {
-                // We don't *think* we need to change this, but
-                // maybe what we're doing will break checksums?
-            }
-            macho::LC_SEGMENT | macho::LC_ROUTINES | macho::LC_ENCRYPTION_INFO => {
-                // These are 32-bit and unsuppoted
-                unreachable!();
-            }
-            macho::LC_FVMFILE | macho::LC_IDFVMLIB | macho::LC_LOADFVMLIB => {
-                // These are obsolete and unsupported
-                unreachable!()
-            }
-            cmd => {
-                eprintln!(
-                    "- - - Unrecognized Mach-O command during linker preprocessing: 0x{:x?}",
-                    cmd
-                );
-                // panic!(
-                //     "Unrecognized Mach-O command during linker preprocessing: 0x{:x?}",
-                //     cmd
-                // );
-            }
-        }
-
-        offset += dbg!(cmd_size);
-    }
-
-    // cmd_loc should be where the last offset ended
-    md.macho_cmd_loc = offset as u64;
-
-    out_mmap
-}
-
-// fn scan_macho_dynamic_deps(
-//     _exec_obj: &object::File,
-//     _md: &mut Metadata,
-//     _app_syms: &[Symbol],
-//     _shared_lib: &str,
-//     _exec_data: &[u8],
-//     _verbose: bool,
-// ) {
-//     // TODO
-// }
+        }
+    }
+}
 
 pub(crate) fn surgery_macho(
     roc_app_bytes: &[u8],
@@ -1147,30 +1186,32 @@ pub(crate) fn surgery_macho(
         }
     };
 
+    if app_sections(&app_obj)
+        .iter()
+        .flat_map(|sec| sec.relocations())
+        .any(|(_, reloc)| reloc.kind() == RelocationKind::Absolute)
+    {
+        eprintln!("The surgical linker currently has issue #3609 and would fail linking your app.");
+        eprintln!("Please use `--linker=legacy` to avoid the issue for now.");
+        std::process::exit(1);
+    }
+
     let total_start = Instant::now();
 
     let loading_metadata_start = total_start;
     let md = Metadata::read_from_file(metadata_path);
     let loading_metadata_duration = loading_metadata_start.elapsed();
 
+    let layout = AppLayout::new(&md, &app_obj, verbose);
+
     let load_and_mmap_start = Instant::now();
-    let max_out_len = md.exec_len + roc_app_bytes.len() as u64 + md.load_align_constraint;
-    let mut exec_mmap = open_mmap_mut(executable_path, max_out_len as usize);
+    let out_len = layout.linkedit_file_offset + (md.exec_len - md.linkedit_file_offset) as usize;
+    let mut exec_mmap = open_mmap_mut(executable_path, out_len);
     let load_and_mmap_duration = load_and_mmap_start.elapsed();
 
     let out_gen_start = Instant::now();
-    let mut offset = 0;
 
-    surgery_macho_help(
-        metadata_path,
-        executable_path,
-        verbose,
-        time,
-        &md,
-        &mut exec_mmap,
-        &mut offset,
-        app_obj,
-    );
+    surgery_macho_help(verbose, &md, &layout, &mut exec_mmap, app_obj);
 
     let out_gen_duration = out_gen_start.elapsed();
     let flushing_data_start = Instant::now();
@@ -1184,6 +1225,24 @@ pub(crate) fn surgery_macho(
 
     let flushing_data_duration = flushing_data_start.elapsed();
 
+    // Apple Silicon does not run executables without a code signature, and preprocessing
+    // dropped the host's signature. An ad-hoc signature is all that is required.
+    #[cfg(target_os = "macos")]
+    if md.cpu_type == macho::CPU_TYPE_ARM64 {
+        let output = std::process::Command::new("codesign")
+            .args(["--force", "--sign", "-"])
+            .arg(executable_path)
+            .output()
+            .unwrap_or_else(|e| internal_error!("Failed to run codesign: {}", e));
+
+        if !output.status.success() {
+            internal_error!(
+                "Failed to sign the executable - stderr of the `codesign` command was:\n{}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+    }
+
     // Make sure the final executable has permision to execute.
     #[cfg(target_family = "unix")]
     {
@@ -1216,130 +1275,278 @@ pub(crate) fn surgery_macho(
     }
 }
 
-#[allow(clippy::too_many_arguments)]
+/// The app sections that end up in the executable.
+/// Note, it is important the bss sections come after the rodata sections.
+fn app_sections<'a, 'b>(app_obj: &'b object::File<'a>) -> Vec<Section<'a, 'b>> {
+    // TODO: In the future Roc may use a data section to store memoized toplevel thunks
+    // in development builds for caching the results of top-level constants
+    let rodata_sections = app_obj
+        .sections()
+        .filter(|sec| sec.kind() == SectionKind::ReadOnlyData);
+
+    // bss section is like rodata section, but it has zero file size and non-zero virtual size.
+    let bss_sections = app_obj
+        .sections()
+        .filter(|sec| sec.kind() == SectionKind::UninitializedData);
+
+    let text_sections = app_obj
+        .sections()
+        .filter(|sec| sec.kind() == SectionKind::Text);
+
+    rodata_sections
+        .chain(bss_sections)
+        .chain(text_sections)
+        .collect()
+}
+
+#[derive(Debug, Default)]
+struct AppSegment {
+    file_offset: usize,
+    file_size: usize,
+    vaddr: usize,
+    vm_size: usize,
+}
+
+/// Where the app's sections go in the final executable: into two new segments that take the
+/// place of `__LINKEDIT`, which moves to the end.
+struct AppLayout {
+    section_offset_map: MutMap<SectionIndex, (usize, usize)>,
+    data_segment: AppSegment,
+    text_segment: AppSegment,
+    linkedit_file_offset: usize,
+    linkedit_vaddr: usize,
+}
+
+impl AppLayout {
+    fn new(md: &Metadata, app_obj: &object::File, verbose: bool) -> Self {
+        let page_size = md.load_align_constraint as usize;
+
+        let mut offset = md.linkedit_file_offset as usize;
+        let mut virt_offset = md.linkedit_vaddr as usize;
+
+        let mut section_offset_map = MutMap::default();
+        let mut data_segment = AppSegment {
+            file_offset: offset,
+            vaddr: virt_offset,
+            ..Default::default()
+        };
+        let mut text_segment = AppSegment::default();
+
+        let sections = app_sections(app_obj);
+        if !sections.iter().any(|sec| sec.kind() == SectionKind::Text) {
+            internal_error!("No text sections found. This application has no code.");
+        }
+
+        for sec in sections.iter() {
+            if sec.kind() == SectionKind::Text && text_segment.file_offset == 0 {
+                // Segments must start on a page boundary.
+                // Also, the data segment is never empty, because dyld does not like that.
+                data_segment.file_size = offset - data_segment.file_offset;
+                data_segment.vm_size =
+                    align_by_constraint((virt_offset - data_segment.vaddr).max(1), page_size);
+
+                offset = align_by_constraint(offset, page_size);
+                virt_offset = data_segment.vaddr + data_segment.vm_size;
+
+                text_segment.file_offset = offset;
+                text_segment.vaddr = virt_offset;
+            }
+
+            let alignment = MIN_SECTION_ALIGNMENT.max(sec.align() as usize);
+            offset = align_by_constraint(offset, alignment);
+            virt_offset = align_by_constraint(virt_offset, alignment);
+            if verbose {
+                println!(
+                    "Section, {}, is being put at offset: {:+x}(virt: {:+x})",
+                    sec.name().unwrap(),
+                    offset,
+                    virt_offset
+                )
+            }
+            section_offset_map.insert(sec.index(), (offset, virt_offset));
+
+            let section_size = match sec.file_range() {
+                Some((_, size)) => size,
+                None => 0,
+            };
+            if sec.kind() == SectionKind::UninitializedData {
+                // bss sections only modify the virtual size.
+                virt_offset += sec.size() as usize;
+            } else if section_size != sec.size() {
+                internal_error!( "We do not deal with non bss sections that have different on disk and in memory sizes");
+            } else {
+                offset += section_size as usize;
+                virt_offset += sec.size() as usize;
+            }
+        }
+
+        text_segment.file_size = offset - text_segment.file_offset;
+        text_segment.vm_size = align_by_constraint(virt_offset - text_segment.vaddr, page_size);
+
+        let linkedit_file_offset = align_by_constraint(offset, page_size);
+        let linkedit_vaddr = text_segment.vaddr + text_segment.vm_size;
+
+        if verbose {
+            println!("App data segment: {:+x?}", data_segment);
+            println!("App text segment: {:+x?}", text_segment);
+            println!(
+                "__LINKEDIT is moved to {:+x} (virt: {:+x})",
+                linkedit_file_offset, linkedit_vaddr
+            );
+        }
+
+        Self {
+            section_offset_map,
+            data_segment,
+            text_segment,
+            linkedit_file_offset,
+            linkedit_vaddr,
+        }
+    }
+}
+
+fn write_app_segment(
+    exec_mmap: &mut [u8],
+    cmd_offset: usize,
+    segment: &AppSegment,
+    segname: [u8; 16],
+    sectname: [u8; 16],
+    prot: u32,
+    section_flags: u32,
+) -> usize {
+    use macho::{Section64, SegmentCommand64};
+
+    let segment_cmd_size = mem::size_of::<SegmentCommand64<LE>>();
+    let section_size = mem::size_of::<Section64<LE>>();
+
+    {
+        let cmd = load_struct_inplace_mut::<SegmentCommand64<LE>>(exec_mmap, cmd_offset);
+
+        cmd.cmd.set(LE, macho::LC_SEGMENT_64);
+        cmd.cmdsize
+            .set(LE, (segment_cmd_size + section_size) as u32);
+        cmd.segname = segname;
+        cmd.vmaddr.set(LE, segment.vaddr as u64);
+        cmd.vmsize.set(LE, segment.vm_size as u64);
+        cmd.fileoff.set(LE, segment.file_offset as u64);
+        cmd.filesize.set(LE, segment.file_size as u64);
+        cmd.maxprot.set(LE, prot);
+        cmd.initprot.set(LE, prot);
+        cmd.nsects.set(LE, 1);
+        cmd.flags.set(LE, 0);
+    }
+
+    {
+        let section =
+            load_struct_inplace_mut::<Section64<LE>>(exec_mmap, cmd_offset + segment_cmd_size);
+
+        section.sectname = sectname;
+        section.segname = segname;
+        section.addr.set(LE, segment.vaddr as u64);
+        section.size.set(LE, segment.file_size as u64);
+        section.offset.set(
+            LE,
+            if segment.file_size > 0 {
+                segment.file_offset as u32
+            } else {
+                0
+            },
+        );
+        section
+            .align
+            .set(LE, MIN_SECTION_ALIGNMENT.trailing_zeros());
+        section.reloff.set(LE, 0);
+        section.nreloc.set(LE, 0);
+        section.flags.set(LE, section_flags);
+        section.reserved1.set(LE, 0);
+        section.reserved2.set(LE, 0);
+        section.reserved3.set(LE, 0);
+    }
+
+    cmd_offset + segment_cmd_size + section_size
+}
+
 fn surgery_macho_help(
-    _metadata_filename: &Path,
-    _out_filename: &Path,
     verbose: bool,
-    _time: bool,
     md: &Metadata,
+    layout: &AppLayout,
     exec_mmap: &mut MmapMut,
-    offset_ref: &mut usize, // TODO return this instead of taking a mutable reference to it
     app_obj: object::File,
 ) {
-    let mut offset = align_by_constraint(md.exec_len as usize, MIN_SECTION_ALIGNMENT);
-    // let new_rodata_section_offset = offset;
-
-    // Align physical and virtual address of new segment.
-    let mut virt_offset = align_to_offset_by_constraint(
-        md.last_vaddr as usize,
-        offset,
-        md.load_align_constraint as usize,
+    // Move __LINKEDIT out of the way, to the end of the file.
+    let linkedit_file_offset = md.linkedit_file_offset as usize;
+    let linkedit_size = md.exec_len as usize - linkedit_file_offset;
+    exec_mmap.copy_within(
+        linkedit_file_offset..linkedit_file_offset + linkedit_size,
+        layout.linkedit_file_offset,
     );
-    let new_rodata_section_vaddr = virt_offset;
-    if verbose {
-        println!();
-        println!(
-            "New Virtual Rodata Section Address: {:+x?}",
-            new_rodata_section_vaddr
+    exec_mmap[linkedit_file_offset..layout.linkedit_file_offset].fill(0);
+
+    // Fill in the load commands that preprocessing made room for.
+    // These need to be valid before anything walks the load commands.
+    {
+        let cmd_offset = write_app_segment(
+            exec_mmap,
+            md.macho_cmd_loc as usize,
+            &layout.data_segment,
+            APP_DATA_SEGMENT,
+            *b"__const\0\0\0\0\0\0\0\0\0",
+            macho::VM_PROT_READ | macho::VM_PROT_WRITE,
+            macho::S_REGULAR,
+        );
+
+        write_app_segment(
+            exec_mmap,
+            cmd_offset,
+            &layout.text_segment,
+            APP_TEXT_SEGMENT,
+            *b"__text\0\0\0\0\0\0\0\0\0\0",
+            macho::VM_PROT_READ | macho::VM_PROT_EXECUTE,
+            macho::S_REGULAR | macho::S_ATTR_PURE_INSTRUCTIONS | macho::S_ATTR_SOME_INSTRUCTIONS,
         );
     }
 
+    shift_linkedit(
+        exec_mmap,
+        (layout.linkedit_file_offset - linkedit_file_offset) as u64,
+        (layout.linkedit_vaddr - md.linkedit_vaddr as usize) as u64,
+    );
+
     // First decide on sections locations and then recode every exact symbol locations.
 
     // Copy sections and resolve their symbols/relocations.
     let symbols = app_obj.symbols().collect::<Vec<Symbol>>();
-    let mut section_offset_map: MutMap<SectionIndex, (usize, usize)> = MutMap::default();
     let mut symbol_vaddr_map: MutMap<SymbolIndex, usize> = MutMap::default();
     let mut app_func_vaddr_map: MutMap<String, usize> = MutMap::default();
-    let mut app_func_size_map: MutMap<String, u64> = MutMap::default();
-
-    // TODO: In the future Roc may use a data section to store memoized toplevel thunks
-    // in development builds for caching the results of top-level constants
-
-    let rodata_sections: Vec<Section> = app_obj
-        .sections()
-        .filter(|sec| sec.kind() == SectionKind::ReadOnlyData)
-        .collect();
 
-    // bss section is like rodata section, but it has zero file size and non-zero virtual size.
-    let bss_sections: Vec<Section> = app_obj
-        .sections()
-        .filter(|sec| sec.kind() == SectionKind::UninitializedData)
-        .collect();
-
-    let text_sections: Vec<Section> = app_obj
-        .sections()
-        .filter(|sec| sec.kind() == SectionKind::Text)
-        .collect();
-    if text_sections.is_empty() {
-        internal_error!("No text sections found. This application has no code.");
-    }
+    let sections = app_sections(&app_obj);
 
     // Calculate addresses and load symbols.
-    // Note, it is important the bss sections come after the rodata sections.
-    for sec in rodata_sections
-        .iter()
-        .chain(bss_sections.iter())
-        .chain(text_sections.iter())
-    {
-        offset = align_by_constraint(offset, MIN_SECTION_ALIGNMENT);
-        virt_offset =
-            align_to_offset_by_constraint(virt_offset, offset, md.load_align_constraint as usize);
-        if verbose {
-            println!(
-                "Section, {}, is being put at offset: {:+x}(virt: {:+x})",
-                sec.name().unwrap(),
-                offset,
-                virt_offset
-            )
-        }
-        section_offset_map.insert(sec.index(), (offset, virt_offset));
+    for sec in sections.iter() {
+        let (_, virt_offset) = layout.section_offset_map[&sec.index()];
         for sym in symbols.iter() {
             if sym.section() == SymbolSection::Section(sec.index()) {
                 let name = sym.name().unwrap_or_default().to_string();
-                if !md.roc_symbol_vaddresses.contains_key(&name) {
-                    symbol_vaddr_map.insert(sym.index(), virt_offset + sym.address() as usize);
+                let address = virt_offset + (sym.address() - sec.address()) as usize;
+                if !md
+                    .roc_symbol_vaddresses
+                    .contains_key(unprefixed_name(&name))
+                {
+                    symbol_vaddr_map.insert(sym.index(), address);
                 }
                 if md.app_functions.contains(&name) {
-                    app_func_vaddr_map.insert(name.clone(), virt_offset + sym.address() as usize);
-                    app_func_size_map.insert(name, sym.size());
+                    app_func_vaddr_map.insert(name, address);
                 }
             }
         }
-        let section_size = match sec.file_range() {
-            Some((_, size)) => size,
-            None => 0,
-        };
-        if sec.name().unwrap_or_default().starts_with("__BSS") {
-            // bss sections only modify the virtual size.
-            virt_offset += sec.size() as usize;
-        } else if section_size != sec.size() {
-            internal_error!( "We do not deal with non bss sections that have different on disk and in memory sizes");
-        } else {
-            offset += section_size as usize;
-            virt_offset += sec.size() as usize;
-        }
     }
     if verbose {
         println!("Data Relocation Offsets: {:+x?}", symbol_vaddr_map);
         println!("Found App Function Symbols: {:+x?}", app_func_vaddr_map);
     }
 
-    // let (new_text_section_offset, new_text_section_vaddr) = text_sections
-    //     .iter()
-    //     .map(|sec| section_offset_map.get(&sec.index()).unwrap())
-    //     .min()
-    //     .unwrap();
-    // let (new_text_section_offset, new_text_section_vaddr) =
-    //     (*new_text_section_offset, *new_text_section_vaddr);
-
     // Move data and deal with relocations.
-    for sec in rodata_sections
-        .iter()
-        .chain(bss_sections.iter())
-        .chain(text_sections.iter())
-    {
+    for sec in sections.iter() {
         let data = match sec.data() {
             Ok(data) => data,
             Err(err) => {
@@ -1350,9 +1557,7 @@ fn surgery_macho_help(
                 );
             }
         };
-        let (section_offset, section_virtual_offset) =
-            section_offset_map.get(&sec.index()).unwrap();
-        let (section_offset, section_virtual_offset) = (*section_offset, *section_virtual_offset);
+        let (section_offset, section_virtual_offset) = layout.section_offset_map[&sec.index()];
         exec_mmap[section_offset..section_offset + data.len()].copy_from_slice(data);
         // Deal with definitions and relocations for this section.
         if verbose {
@@ -1362,75 +1567,65 @@ fn surgery_macho_help(
                 sec, section_offset, section_virtual_offset
             );
         }
+
+        // An ARM64_RELOC_ADDEND provides the addend for the relocation that follows it.
+        let mut addend = 0;
+
         for rel in sec.relocations() {
             if verbose {
                 println!("\tFound Relocation: {:+x?}", rel);
             }
-            match rel.1.target() {
+
+            if let (
+                RelocationKind::MachO {
+                    value: macho::ARM64_RELOC_ADDEND,
+                    ..
+                },
+                RelocationTarget::Section(SectionIndex(value)),
+            ) = (rel.1.kind(), rel.1.target())
+            {
+                // The addend is stored as a 24-bit signed integer.
+                addend = ((value as i32) << 8 >> 8) as i64;
+                continue;
+            }
+
+            let virt_base = section_virtual_offset + rel.0 as usize;
+            let base = section_offset + rel.0 as usize;
+
+            let target_offset = match rel.1.target() {
                 RelocationTarget::Symbol(index) => {
-                    let target_offset = if let Some(target_offset) = symbol_vaddr_map.get(&index) {
+                    let name = app_obj
+                        .symbol_by_index(index)
+                        .and_then(|sym| sym.name())
+                        .unwrap_or_default();
+
+                    if let Some(target_offset) = symbol_vaddr_map.get(&index) {
                         if verbose {
                             println!(
                                 "\t\tRelocation targets symbol in app at: {:+x}",
                                 target_offset
                             );
                         }
-                        Some(*target_offset as i64)
-                    } else {
-                        app_obj
-                            .symbol_by_index(index)
-                            .and_then(|sym| sym.name())
-                            .ok()
-                            .and_then(|name| {
-                                md.roc_symbol_vaddresses.get(name).map(|address| {
-                                    let vaddr = (*address + md.added_byte_count) as i64;
-                                    if verbose {
-                                        println!(
-                                            "\t\tRelocation targets symbol in host: {} @ {:+x}",
-                                            name, vaddr
-                                        );
-                                    }
-                                    vaddr
-                                })
-                            })
-                    };
-
-                    if let Some(target_offset) = target_offset {
-                        let virt_base = section_virtual_offset + rel.0 as usize;
-                        let base = section_offset + rel.0 as usize;
-                        let target: i64 = match rel.1.kind() {
-                            RelocationKind::Relative | RelocationKind::PltRelative => {
-                                target_offset - virt_base as i64 + rel.1.addend()
-                            }
-                            x => {
-                                internal_error!("Relocation Kind not yet support: {:?}", x);
-                            }
-                        };
+                        *target_offset as i64
+                    } else if let Some(address) =
+                        md.roc_symbol_vaddresses.get(unprefixed_name(name))
+                    {
+                        let vaddr = *address as i64;
                         if verbose {
                             println!(
-                                "\t\tRelocation base location: {:+x} (virt: {:+x})",
-                                base, virt_base
+                                "\t\tRelocation targets symbol in host: {} @ {:+x}",
+                                name, vaddr
                             );
-                            println!("\t\tFinal relocation target offset: {:+x}", target);
                         }
-                        match rel.1.size() {
-                            32 => {
-                                let data = (target as i32).to_le_bytes();
-                                exec_mmap[base..base + 4].copy_from_slice(&data);
-                            }
-                            64 => {
-                                let data = target.to_le_bytes();
-                                exec_mmap[base..base + 8].copy_from_slice(&data);
-                            }
-                            x => {
-                                internal_error!("Relocation size not yet supported: {}", x);
-                            }
-                        }
-                    } else if matches!(app_obj.symbol_by_index(index), Ok(sym) if ["__divti3", "__udivti3", "___divti3", "___udivti3"].contains(&sym.name().unwrap_or_default()))
-                    {
-                        // Explicitly ignore some symbols that are currently always linked.
-                        continue;
+                        vaddr
                     } else {
+                        // Explicitly ignore some symbols that are currently always linked.
+                        const ALWAYS_LINKED: &[&str] = &["___divti3", "___udivti3"];
+
+                        if ALWAYS_LINKED.contains(&name) {
+                            continue;
+                        }
+
                         internal_error!(
                             "Undefined Symbol in relocation, {:+x?}: {:+x?}",
                             rel,
@@ -1438,113 +1633,73 @@ fn surgery_macho_help(
                         );
                     }
                 }
+                RelocationTarget::Section(index) if md.cpu_type == macho::CPU_TYPE_X86_64 => {
+                    // The implicit addend is the address of the target within the object file.
+                    // Convert it to an address in the executable.
+                    let target_section = app_obj
+                        .section_by_index(index)
+                        .unwrap_or_else(|e| internal_error!("{}", e));
+                    let (_, target_virtual_offset) = match layout.section_offset_map.get(&index) {
+                        Some(offsets) => *offsets,
+                        None => internal_error!(
+                            "Relocation targets section that is not part of the app: {:+x?}",
+                            target_section
+                        ),
+                    };
 
+                    let implicit_addend =
+                        i32::from_le_bytes(exec_mmap[base..base + 4].try_into().unwrap()) as i64;
+                    let old_target = implicit_addend
+                        + (sec.address() + rel.0) as i64
+                        + 4
+                        + x86_pcrel_extra(rel.1.kind());
+                    let new_target =
+                        old_target - target_section.address() as i64 + target_virtual_offset as i64;
+
+                    // The extra bytes after the displacement are accounted for by the implicit
+                    // addend for relocations that target symbols, so do the same here.
+                    exec_mmap[base..base + 4].copy_from_slice(&0i32.to_le_bytes());
+                    new_target - x86_pcrel_extra(rel.1.kind())
+                }
                 _ => {
                     internal_error!("Relocation target not yet support: {:+x?}", rel);
                 }
+            };
+
+            let target_offset = target_offset + mem::take(&mut addend);
+
+            if verbose {
+                println!(
+                    "\t\tRelocation base location: {:+x} (virt: {:+x})",
+                    base, virt_base
+                );
+            }
+
+            match md.cpu_type {
+                macho::CPU_TYPE_X86_64 => {
+                    apply_x86_64_relocation(exec_mmap, base, &rel.1, virt_base, target_offset)
+                }
+                macho::CPU_TYPE_ARM64 => apply_aarch64_relocation(
+                    &mut exec_mmap[base..base + 4],
+                    rel.1.kind(),
+                    virt_base,
+                    target_offset,
+                ),
+                _ => internal_error!("Unsupported Mach-O cpu type: {:#x}", md.cpu_type),
             }
         }
     }
 
-    // Flush app only data to speed up write to disk.
-    exec_mmap
-        .flush_async_range(md.exec_len as usize, offset - md.exec_len as usize)
-        .unwrap_or_else(|e| internal_error!("{}", e));
-
     // TODO: look into merging symbol tables, debug info, and eh frames to enable better debugger experience.
 
-    // let mut cmd_offset = md.macho_cmd_loc as usize;
-
-    // // Load this section (we made room for it earlier) and then mutate all its data to make it the desired command
-    // {
-    //     let cmd =
-    //         load_struct_inplace_mut::<macho::SegmentCommand64<LE >>(exec_mmap, cmd_offset);
-    //     let size_of_section = mem::size_of::<macho::Section64<LE >>() as u32;
-    //     let size_of_cmd = mem::size_of_val(cmd);
-
-    //     cmd_offset += size_of_cmd;
-
-    //     cmd.cmd.set(LE , macho::LC_SEGMENT_64);
-    //     cmd.cmdsize
-    //         .set(LE , size_of_section + size_of_cmd as u32);
-    //     cmd.segname = *b"__DATA_CONST\0\0\0\0";
-    //     cmd.vmaddr
-    //         .set(LE , new_rodata_section_vaddr as u64);
-    //     cmd.vmsize.set(
-    //         LE ,
-    //         (new_text_section_vaddr - new_rodata_section_vaddr) as u64,
-    //     );
-    //     cmd.fileoff
-    //         .set(LE , new_rodata_section_offset as u64);
-    //     cmd.filesize.set(
-    //         LE ,
-    //         (new_text_section_offset - new_rodata_section_offset) as u64,
-    //     );
-    //     cmd.nsects.set(LE , 1);
-    //     cmd.maxprot.set(LE , 0x00000003);
-    //     cmd.initprot.set(LE , 0x00000003);
-
-    //     // TODO set protection
-    // }
-
-    // {
-    //     let cmd = load_struct_inplace_mut::<macho::Section64<LE >>(exec_mmap, cmd_offset);
-    //     let size_of_cmd = mem::size_of_val(cmd);
-
-    //     cmd_offset += size_of_cmd;
-
-    //     cmd.sectname = *b"__const\0\0\0\0\0\0\0\0\0";
-    //     cmd.segname = *b"__DATA_CONST\0\0\0\0";
-    //     cmd.addr.set(LE , new_rodata_section_vaddr as u64);
-    //     cmd.size.set(
-    //         LE ,
-    //         (new_text_section_offset - new_rodata_section_offset) as u64,
-    //     );
-    //     cmd.offset.set(LE , 0); // TODO is this offset since the start of the file, or segment offset?
-    //     cmd.align.set(LE , 12); // TODO should this be 4096?
-    //     cmd.reloff.set(LE , 264); // TODO this should NOT be hardcoded! Should get it from somewhere.
-    // }
-
-    // {
-    //     let cmd =
-    //         load_struct_inplace_mut::<macho::SegmentCommand64<LE >>(exec_mmap, cmd_offset);
-    //     let size_of_section = mem::size_of::<macho::Section64<LE >>() as u32;
-    //     let size_of_cmd = mem::size_of_val(cmd);
-
-    //     cmd_offset += size_of_cmd;
-
-    //     cmd.cmd.set(LE , macho::LC_SEGMENT_64);
-    //     cmd.cmdsize
-    //         .set(LE , size_of_section + size_of_cmd as u32);
-    //     cmd.segname = *b"__TEXT\0\0\0\0\0\0\0\0\0\0";
-    //     cmd.vmaddr.set(LE , new_text_section_vaddr as u64);
-    //     cmd.vmsize
-    //         .set(LE , (offset - new_text_section_offset) as u64);
-    //     cmd.fileoff
-    //         .set(LE , new_text_section_offset as u64);
-    //     cmd.filesize
-    //         .set(LE , (offset - new_text_section_offset) as u64);
-    //     cmd.nsects.set(LE , 1);
-    //     cmd.maxprot.set(LE , 0x00000005); // this is what a zig-generated host had
-    //     cmd.initprot.set(LE , 0x00000005); // this is what a zig-generated host had
-    // }
-
-    // {
-    //     let cmd = load_struct_inplace_mut::<macho::Section64<LE >>(exec_mmap, cmd_offset);
-
-    //     cmd.segname = *b"__TEXT\0\0\0\0\0\0\0\0\0\0";
-    //     cmd.sectname = *b"__text\0\0\0\0\0\0\0\0\0\0";
-    //     cmd.addr.set(LE , new_text_section_vaddr as u64);
-    //     cmd.size
-    //         .set(LE , (offset - new_text_section_offset) as u64);
-    //     cmd.offset.set(LE , 0); // TODO is this offset since the start of the file, or segment offset?
-    //     cmd.align.set(LE , 12); // TODO this is 4096 (2^12) - which load_align_constraint does, above - but should it?
-    //     cmd.flags.set(LE , 0x80000400); // TODO this is what a zig-generated host had
-    //     cmd.reloff.set(LE , 264); // TODO this should NOT be hardcoded! Should get it from somewhere.
-    // }
-
-    // Update calls from platform and dynamic symbols.
-    // let dynsym_offset = md.dynamic_symbol_table_section_offset + md.added_byte_count;
+    // Update calls from platform and the symbol table.
+    let symtab = load_commands(exec_mmap)
+        .into_iter()
+        .find(|(_, cmd)| *cmd == macho::LC_SYMTAB)
+        .map(|(offset, _)| {
+            let cmd = load_struct_inplace::<macho::SymtabCommand<LE>>(exec_mmap, offset);
+            cmd.symoff.get(LE) as usize
+        });
 
     for func_name in md.app_functions.iter() {
         let func_virt_offset = match app_func_vaddr_map.get(func_name) {
@@ -1565,74 +1720,959 @@ fn surgery_macho_help(
                 println!("\tPerforming surgery: {:+x?}", s);
             }
             let surgery_virt_offset = match s.virtual_offset {
-                VirtualOffset::Relative(vs) => (vs + md.added_byte_count) as i64,
+                VirtualOffset::Relative(vs) => vs as i64,
                 VirtualOffset::Absolute => 0,
             };
-            match s.size {
-                4 => {
+            let file_offset = s.file_offset as usize;
+            match (md.cpu_type, s.size) {
+                (macho::CPU_TYPE_ARM64, 4) => {
+                    let inst = u32::from_le_bytes(
+                        exec_mmap[file_offset..file_offset + 4].try_into().unwrap(),
+                    );
+                    let target = func_virt_offset as i64 - surgery_virt_offset;
+                    if verbose {
+                        println!("\tTarget Jump: {:+x}", target);
+                    }
+                    let data = aarch64_set_branch_offset(inst, target).to_le_bytes();
+                    exec_mmap[file_offset..file_offset + 4].copy_from_slice(&data);
+                }
+                (_, 4) => {
                     let target = (func_virt_offset as i64 - surgery_virt_offset) as i32;
                     if verbose {
                         println!("\tTarget Jump: {:+x}", target);
                     }
                     let data = target.to_le_bytes();
-                    exec_mmap[(s.file_offset + md.added_byte_count) as usize
-                        ..(s.file_offset + md.added_byte_count) as usize + 4]
-                        .copy_from_slice(&data);
+                    exec_mmap[file_offset..file_offset + 4].copy_from_slice(&data);
                 }
-                8 => {
+                (_, 8) => {
                     let target = func_virt_offset as i64 - surgery_virt_offset;
                     if verbose {
                         println!("\tTarget Jump: {:+x}", target);
                     }
                     let data = target.to_le_bytes();
-                    exec_mmap[(s.file_offset + md.added_byte_count) as usize
-                        ..(s.file_offset + md.added_byte_count) as usize + 8]
-                        .copy_from_slice(&data);
+                    exec_mmap[file_offset..file_offset + 8].copy_from_slice(&data);
                 }
-                x => {
+                (_, x) => {
                     internal_error!("Surgery size not yet supported: {}", x);
                 }
             }
         }
 
-        // Replace plt call code with just a jump.
-        // This is a backup incase we missed a call to the plt.
+        // Replace the stub code with just a jump.
+        // This is a backup incase we missed a call to the stub.
         if let Some((plt_off, plt_vaddr)) = md.plt_addresses.get(func_name) {
-            let plt_off = (*plt_off + md.added_byte_count) as usize;
-            let plt_vaddr = *plt_vaddr + md.added_byte_count;
-            let jmp_inst_len = 5;
-            let target =
-                (func_virt_offset as i64 - (plt_vaddr as i64 + jmp_inst_len as i64)) as i32;
+            let plt_off = *plt_off as usize;
+            let plt_vaddr = *plt_vaddr;
             if verbose {
                 println!("\tPLT: {:+x}, {:+x}", plt_off, plt_vaddr);
-                println!("\tTarget Jump: {:+x}", target);
             }
-            let data = target.to_le_bytes();
-            exec_mmap[plt_off] = 0xE9;
-            exec_mmap[plt_off + 1..plt_off + jmp_inst_len].copy_from_slice(&data);
-            for i in jmp_inst_len..PLT_ADDRESS_OFFSET as usize {
-                exec_mmap[plt_off + i] = 0x90;
+
+            let stub = &mut exec_mmap[plt_off..plt_off + md.stub_size as usize];
+            match md.cpu_type {
+                macho::CPU_TYPE_ARM64 => {
+                    let target = func_virt_offset as i64 - plt_vaddr as i64;
+                    if verbose {
+                        println!("\tTarget Jump: {:+x}", target);
+                    }
+                    let data = aarch64_set_branch_offset(AARCH64_B, target).to_le_bytes();
+                    stub[..4].copy_from_slice(&data);
+                    for inst in stub[4..].chunks_exact_mut(4) {
+                        inst.copy_from_slice(&AARCH64_NOP.to_le_bytes());
+                    }
+                }
+                _ => {
+                    let jmp_inst_len = 5;
+                    let target =
+                        (func_virt_offset as i64 - (plt_vaddr as i64 + jmp_inst_len as i64)) as i32;
+                    if verbose {
+                        println!("\tTarget Jump: {:+x}", target);
+                    }
+                    stub[0] = 0xE9;
+                    stub[1..jmp_inst_len].copy_from_slice(&target.to_le_bytes());
+                    stub[jmp_inst_len..].fill(0x90);
+                }
+            }
+        }
+
+        // GOT entries for the function become rebases to it. They keep their place in the chain of
+        // fixups, so dyld still visits the entries after them.
+        for entry in md.got_entries.get(func_name).unwrap_or(&vec![]) {
+            let file_offset = entry.file_offset as usize;
+            let old =
+                u64::from_le_bytes(exec_mmap[file_offset..file_offset + 8].try_into().unwrap());
+            let target = match entry.pointer_format {
+                DYLD_CHAINED_PTR_64_OFFSET => func_virt_offset - md.image_base,
+                _ => func_virt_offset,
+            };
+            if verbose {
+                println!("\tGOT: {:+x}, {:+x}", file_offset, target);
             }
+
+            let rebase = (old & CHAINED_PTR_64_NEXT_MASK) | target;
+            exec_mmap[file_offset..file_offset + 8].copy_from_slice(&rebase.to_le_bytes());
+        }
+
+        // The app function is now defined in the executable itself.
+        if let (Some(symoff), Some(i)) = (symtab, md.dynamic_symbol_indices.get(func_name)) {
+            let sym = load_struct_inplace_mut::<macho::Nlist64<LE>>(
+                exec_mmap,
+                symoff + *i as usize * mem::size_of::<macho::Nlist64<LE>>(),
+            );
+            sym.n_type = macho::N_SECT | macho::N_EXT;
+            // The text section comes after the data section.
+            sym.n_sect = md.first_app_section + 1;
+            sym.n_desc.set(LE, 0);
+            sym.n_value.set(LE, func_virt_offset);
         }
+    }
+}
+
+/// How many bytes of the instruction follow the 4 bytes of an x86_64 rip-relative relocation.
+fn x86_pcrel_extra(kind: RelocationKind) -> i64 {
+    match kind {
+        RelocationKind::MachO {
+            value: macho::X86_64_RELOC_SIGNED_1,
+            ..
+        } => 1,
+        RelocationKind::MachO {
+            value: macho::X86_64_RELOC_SIGNED_2,
+            ..
+        } => 2,
+        RelocationKind::MachO {
+            value: macho::X86_64_RELOC_SIGNED_4,
+            ..
+        } => 4,
+        _ => 0,
+    }
+}
 
-        // Commented out because it doesn't apply to mach-o
-        // if let Some(i) = md.dynamic_symbol_indices.get(func_name) {
-        //     let sym = load_struct_inplace_mut::<elf::Sym64<LE >>(
-        //         exec_mmap,
-        //         dynsym_offset as usize + *i as usize * mem::size_of::<elf::Sym64<LE >>(),
-        //     );
-        //     sym.st_value.set(LE , func_virt_offset as u64);
-        //     sym.st_size.set(
-        //         LE ,
-        //         match app_func_size_map.get(func_name) {
-        //             Some(size) => *size,
-        //             None => {
-        //                 internal_error!("Size missing for: {}", func_name);
-        //             }
-        //         },
-        //     );
-        // }
+fn apply_x86_64_relocation(
+    exec_mmap: &mut [u8],
+    base: usize,
+    rel: &Relocation,
+    virt_base: usize,
+    target_offset: i64,
+) {
+    match (rel.kind(), rel.encoding()) {
+        (RelocationKind::Relative, _)
+        | (
+            RelocationKind::MachO {
+                value:
+                    macho::X86_64_RELOC_SIGNED_1
+                    | macho::X86_64_RELOC_SIGNED_2
+                    | macho::X86_64_RELOC_SIGNED_4,
+                relative: true,
+            },
+            _,
+        ) => {}
+        (RelocationKind::GotRelative, RelocationEncoding::X86RipRelativeMovq)
+            if base >= 2 && exec_mmap[base - 2] == 0x8b =>
+        {
+            // The app has no GOT, so turn the `mov` of the GOT entry into a `lea` of the target.
+            exec_mmap[base - 2] = 0x8d;
+        }
+        _ => {
+            internal_error!("Relocation Kind not yet support: {:?}", rel);
+        }
     }
 
-    *offset_ref = offset;
+    // Mach-O relocations on x86_64 store the addend in the instruction itself.
+    let implicit_addend = i32::from_le_bytes(exec_mmap[base..base + 4].try_into().unwrap());
+    let target = target_offset + implicit_addend as i64 - (virt_base as i64 + 4);
+
+    exec_mmap[base..base + 4].copy_from_slice(&(target as i32).to_le_bytes());
+}
+
+fn apply_aarch64_relocation(
+    inst_bytes: &mut [u8],
+    kind: RelocationKind,
+    virt_base: usize,
+    target_offset: i64,
+) {
+    let inst = u32::from_le_bytes(inst_bytes[..4].try_into().unwrap());
+    let page = |address: i64| address & !0xfff;
+
+    let new_inst = match kind {
+        RelocationKind::MachO {
+            value: macho::ARM64_RELOC_BRANCH26,
+            ..
+        } => aarch64_set_branch_offset(inst, target_offset - virt_base as i64),
+        RelocationKind::MachO {
+            value: macho::ARM64_RELOC_PAGE21 | macho::ARM64_RELOC_GOT_LOAD_PAGE21,
+            ..
+        } => {
+            // adrp: the target's page, relative to this instruction's page
+            let pages = (page(target_offset) - page(virt_base as i64)) >> 12;
+            if !(-(1 << 20)..(1 << 20)).contains(&pages) {
+                internal_error!("Page offset {:+x} is out of range for adrp", pages);
+            }
+
+            let immlo = (pages as u32 & 0x3) << 29;
+            let immhi = ((pages as u32 >> 2) & 0x7ffff) << 5;
+            (inst & 0x9f00_001f) | immlo | immhi
+        }
+        RelocationKind::MachO {
+            value: macho::ARM64_RELOC_PAGEOFF12,
+            ..
+        } => {
+            let page_offset = (target_offset & 0xfff) as u32;
+
+            // Loads and stores scale their immediate by the size of the access.
+            let scale = if inst & 0x3b00_0000 == 0x3900_0000 {
+                let size = inst >> 30;
+                let is_128_bit_vector = size == 0 && inst & 0x0480_0000 == 0x0480_0000;
+                if is_128_bit_vector {
+                    4
+                } else {
+                    size
+                }
+            } else {
+                0
+            };
+
+            if page_offset & ((1 << scale) - 1) != 0 {
+                internal_error!("Misaligned page offset {:+x} for {:#x}", page_offset, inst);
+            }
+
+            (inst & !(0xfff << 10)) | ((page_offset >> scale) << 10)
+        }
+        RelocationKind::MachO {
+            value: macho::ARM64_RELOC_GOT_LOAD_PAGEOFF12,
+            ..
+        } => {
+            // The app has no GOT; turn the `ldr xT, [xN, #got_entry]` into `add xT, xN, #target`.
+            if inst & 0xffc0_0000 != 0xf940_0000 {
+                internal_error!("Unexpected instruction for a GOT load: {:#x}", inst);
+            }
+
+            let page_offset = (target_offset & 0xfff) as u32;
+            0x9100_0000 | (page_offset << 10) | (inst & 0x3ff)
+        }
+        _ => {
+            internal_error!("Relocation Kind not yet support: {:?}", kind);
+        }
+    };
+
+    inst_bytes[..4].copy_from_slice(&new_inst.to_le_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use object::write;
+    use object::{Architecture, BinaryFormat, Endianness, ObjectSegment};
+    use std::str::FromStr;
+
+    const APP_FUNCTION: &str = "_roc__mainForHost_1_exposed";
+
+    /// A minimal host executable, laid out the way ld64 links this C program for macOS 12:
+    ///
+    /// ```c
+    /// #include <stdio.h>
+    ///
+    /// extern void roc__mainForHost_1_exposed(void);
+    ///
+    /// void roc_alloc(void) {}
+    ///
+    /// int main(void) {
+    ///     roc__mainForHost_1_exposed();
+    ///     puts("");
+    ///     return 0;
+    /// }
+    /// ```
+    ///
+    /// `main` calls the app and `puts` through their stubs, which jump through the GOT. The GOT
+    /// entries are chained fixups that bind to libSystem and the app's stub dylib. The code
+    /// signature is a stand-in, since surgery removes it anyway.
+    fn dynhost(cpu_type: u32) -> Vec<u8> {
+        use macho::*;
+        use object::pod::bytes_of;
+        use object::{BigEndian, U16, U32, U64};
+
+        let u32le = |value: u32| U32::new(LE, value);
+        let u64le = |value: u64| U64::new(LE, value);
+        let name16 = |name: &str| {
+            let mut out = [0; 16];
+            out[..name.len()].copy_from_slice(name.as_bytes());
+            out
+        };
+        let segment = |name: &str, vmaddr: u64, size: u64, fileoff: u64, prot: u32, nsects| {
+            SegmentCommand64 {
+                cmd: u32le(LC_SEGMENT_64),
+                cmdsize: u32le(72 + 80 * nsects),
+                segname: name16(name),
+                vmaddr: u64le(vmaddr),
+                vmsize: u64le(size),
+                fileoff: u64le(fileoff),
+                filesize: u64le(if fileoff == 0 && vmaddr == 0 { 0 } else { size }),
+                maxprot: u32le(prot),
+                initprot: u32le(prot),
+                nsects: u32le(nsects),
+                flags: u32le(0),
+            }
+        };
+        let section =
+            |name: &str, seg: &str, addr: u64, size: usize, flags: u32, reserved: (u32, u32)| {
+                Section64 {
+                    sectname: name16(name),
+                    segname: name16(seg),
+                    addr: u64le(addr),
+                    size: u64le(size as u64),
+                    offset: u32le((addr - BASE) as u32),
+                    align: u32le(2),
+                    reloff: u32le(0),
+                    nreloc: u32le(0),
+                    flags: u32le(flags),
+                    reserved1: u32le(reserved.0),
+                    reserved2: u32le(reserved.1),
+                    reserved3: u32le(0),
+                }
+            };
+        let dylib = |path: &str| {
+            let size = (mem::size_of::<DylibCommand<LE>>() + path.len() + 1 + 7) & !7;
+            let cmd = DylibCommand {
+                cmd: u32le(LC_LOAD_DYLIB),
+                cmdsize: u32le(size as u32),
+                dylib: Dylib {
+                    name: LcStr {
+                        offset: u32le(mem::size_of::<DylibCommand<LE>>() as u32),
+                    },
+                    timestamp: u32le(2),
+                    current_version: u32le(0x10000),
+                    compatibility_version: u32le(0x10000),
+                },
+            };
+            let mut bytes = bytes_of(&cmd).to_vec();
+            bytes.extend_from_slice(path.as_bytes());
+            bytes.resize(size, 0);
+            bytes
+        };
+
+        const BASE: u64 = 0x1_0000_0000;
+        let arm = cpu_type == CPU_TYPE_ARM64;
+        let page: u64 = if arm { 0x4000 } else { 0x1000 };
+
+        // __TEXT holds `main`, then `roc_alloc`, then the stubs for `puts` and the app.
+        let text_addr = BASE + page / 2;
+        let roc_alloc_addr = text_addr + 0x10;
+        let stubs_addr = text_addr + 0x20;
+        let stub_size: u64 = if arm { 12 } else { 6 };
+        let got_addr = BASE + page;
+        let linkedit_addr = BASE + 2 * page;
+
+        let mut text = Vec::new();
+        if arm {
+            let bl = |from: u64, to: u64| {
+                AARCH64_BL | (((to as i64 - from as i64) >> 2) as u32 & !AARCH64_BRANCH_MASK)
+            };
+            for inst in [
+                bl(text_addr, stubs_addr + stub_size),
+                bl(text_addr + 4, stubs_addr),
+                0x5280_0000, // mov w0, #0
+                0xd65f_03c0, // ret
+                0xd65f_03c0, // roc_alloc: ret
+            ] {
+                text.extend_from_slice(&u32::to_le_bytes(inst));
+            }
+        } else {
+            for (from, to) in [
+                (text_addr, stubs_addr + stub_size),
+                (text_addr + 5, stubs_addr),
+            ] {
+                text.push(0xe8); // call
+                text.extend_from_slice(&((to as i64 - (from as i64 + 5)) as i32).to_le_bytes());
+            }
+            text.extend_from_slice(&[0x31, 0xc0, 0xc3]); // xor eax, eax; ret
+            text.resize(0x10, 0xcc);
+            text.push(0xc3); // roc_alloc: ret
+        }
+
+        let mut stubs = Vec::new();
+        for i in 0..2 {
+            let from = stubs_addr + i * stub_size;
+            let got_entry = got_addr + 8 * i;
+            if arm {
+                let pages = ((got_entry & !0xfff) - (from & !0xfff)) as u32 >> 12;
+                for inst in [
+                    0x9000_0010 | (pages & 3) << 29 | (pages >> 2) << 5, // adrp x16, got_entry@PAGE
+                    0xf940_0210 | ((got_entry & 0xfff) as u32 >> 3) << 10, // ldr x16, [x16, got_entry@PAGEOFF]
+                    0xd61f_0200,                                           // br x16
+                ] {
+                    stubs.extend_from_slice(&u32::to_le_bytes(inst));
+                }
+            } else {
+                stubs.extend_from_slice(&[0xff, 0x25]); // jmp [rip + got_entry]
+                stubs.extend_from_slice(&((got_entry - (from + 6)) as u32).to_le_bytes());
+            }
+        }
+
+        // Binds to imports 0 (`puts`) and 1 (the app), chained 8 bytes apart.
+        let mut got = Vec::new();
+        got.extend_from_slice(&(CHAINED_PTR_64_BIND | 2 << 51).to_le_bytes());
+        got.extend_from_slice(&(CHAINED_PTR_64_BIND | 1).to_le_bytes());
+
+        // __LINKEDIT starts with the chained fixups.
+        let pointer_format = if arm {
+            DYLD_CHAINED_PTR_64
+        } else {
+            DYLD_CHAINED_PTR_64_OFFSET
+        };
+        let starts_offset = 32u32;
+        let seg_info_offset = 24u32;
+        let imports_offset = starts_offset + seg_info_offset + 24;
+        let symbols_offset = imports_offset + 8;
+        let mut linkedit = Vec::new();
+        // dyld_chained_fixups_header
+        for value in [0, starts_offset, imports_offset, symbols_offset, 2, 1, 0] {
+            linkedit.extend_from_slice(&u32::to_le_bytes(value));
+        }
+        linkedit.resize(starts_offset as usize, 0);
+        // dyld_chained_starts_in_image, with fixups in __DATA_CONST only
+        for value in [4, 0, 0, seg_info_offset, 0] {
+            linkedit.extend_from_slice(&u32::to_le_bytes(value));
+        }
+        linkedit.resize((starts_offset + seg_info_offset) as usize, 0);
+        // dyld_chained_starts_in_segment
+        linkedit.extend_from_slice(&24u32.to_le_bytes());
+        linkedit.extend_from_slice(&(page as u16).to_le_bytes());
+        linkedit.extend_from_slice(&pointer_format.to_le_bytes());
+        linkedit.extend_from_slice(&page.to_le_bytes());
+        linkedit.extend_from_slice(&[0; 4]); // max_valid_pointer
+        linkedit.extend_from_slice(&1u16.to_le_bytes()); // page_count
+        linkedit.extend_from_slice(&0u16.to_le_bytes()); // page_start
+                                                         // dyld_chained_import: lib_ordinal, then the name offset
+        linkedit.extend_from_slice(&(1u32 | 1 << 9).to_le_bytes());
+        linkedit.extend_from_slice(&(2u32 | 7 << 9).to_le_bytes());
+        linkedit.extend_from_slice(b"\0_puts\0_roc__mainForHost_1_exposed\0");
+        linkedit.resize((linkedit.len() + 7) & !7, 0);
+        let fixups_size = linkedit.len();
+
+        let strtab = b" \0_main\0_roc_alloc\0_puts\0_roc__mainForHost_1_exposed\0";
+        let symbols = [
+            ("_main", N_SECT | N_EXT, 1, 0, text_addr),
+            ("_roc_alloc", N_SECT | N_EXT, 1, 0, roc_alloc_addr),
+            ("_puts", N_UNDF | N_EXT, 0, 1 << 8, 0),
+            (APP_FUNCTION, N_UNDF | N_EXT, 0, 2 << 8, 0),
+        ];
+        let symoff = linkedit.len();
+        for (name, n_type, n_sect, n_desc, n_value) in symbols {
+            let n_strx = strtab
+                .windows(name.len() + 1)
+                .position(|window| window == [name.as_bytes(), b"\0"].concat())
+                .unwrap();
+            linkedit.extend_from_slice(bytes_of(&Nlist64 {
+                n_strx: u32le(n_strx as u32),
+                n_type,
+                n_sect,
+                n_desc: U16::new(LE, n_desc),
+                n_value: u64le(n_value),
+            }));
+        }
+        // The stubs, then the GOT, refer to `puts` and the app.
+        let indirectsymoff = linkedit.len();
+        for index in [2u32, 3, 2, 3] {
+            linkedit.extend_from_slice(&index.to_le_bytes());
+        }
+        let stroff = linkedit.len();
+        linkedit.extend_from_slice(strtab);
+        linkedit.resize((linkedit.len() + 15) & !15, 0);
+        let signature_offset = linkedit.len();
+        linkedit.extend_from_slice(&0xfade_0cc0u32.to_be_bytes());
+        linkedit.resize(signature_offset + 0x30, 0);
+
+        let linkedit_offset = |offset: usize| u32le((linkedit_addr - BASE) as u32 + offset as u32);
+        let zero = u32le(0);
+        let mut cmds = Vec::new();
+        let mut text_segment = bytes_of(&segment("__TEXT", BASE, page, 0, 5, 2)).to_vec();
+        text_segment.extend_from_slice(bytes_of(&section(
+            "__text",
+            "__TEXT",
+            text_addr,
+            text.len(),
+            S_REGULAR | S_ATTR_PURE_INSTRUCTIONS | S_ATTR_SOME_INSTRUCTIONS,
+            (0, 0),
+        )));
+        text_segment.extend_from_slice(bytes_of(&section(
+            "__stubs",
+            "__TEXT",
+            stubs_addr,
+            stubs.len(),
+            S_SYMBOL_STUBS | S_ATTR_PURE_INSTRUCTIONS | S_ATTR_SOME_INSTRUCTIONS,
+            (0, stub_size as u32),
+        )));
+        let mut data_segment =
+            bytes_of(&segment("__DATA_CONST", got_addr, page, page, 3, 1)).to_vec();
+        data_segment.extend_from_slice(bytes_of(&section(
+            "__got",
+            "__DATA_CONST",
+            got_addr,
+            got.len(),
+            S_NON_LAZY_SYMBOL_POINTERS,
+            (2, 0),
+        )));
+        let load_commands: Vec<Vec<u8>> = vec![
+            bytes_of(&segment("__PAGEZERO", 0, BASE, 0, 0, 0)).to_vec(),
+            text_segment,
+            data_segment,
+            bytes_of(&SegmentCommand64 {
+                filesize: u64le(linkedit.len() as u64),
+                ..segment("__LINKEDIT", linkedit_addr, page, 2 * page, 1, 0)
+            })
+            .to_vec(),
+            bytes_of(&LinkeditDataCommand {
+                cmd: u32le(LC_DYLD_CHAINED_FIXUPS),
+                cmdsize: u32le(16),
+                dataoff: linkedit_offset(0),
+                datasize: u32le(fixups_size as u32),
+            })
+            .to_vec(),
+            bytes_of(&SymtabCommand {
+                cmd: u32le(LC_SYMTAB),
+                cmdsize: u32le(24),
+                symoff: linkedit_offset(symoff),
+                nsyms: u32le(symbols.len() as u32),
+                stroff: linkedit_offset(stroff),
+                strsize: u32le(strtab.len() as u32),
+            })
+            .to_vec(),
+            bytes_of(&DysymtabCommand {
+                cmd: u32le(LC_DYSYMTAB),
+                cmdsize: u32le(80),
+                ilocalsym: zero,
+                nlocalsym: zero,
+                iextdefsym: zero,
+                nextdefsym: u32le(2),
+                iundefsym: u32le(2),
+                nundefsym: u32le(2),
+                tocoff: zero,
+                ntoc: zero,
+                modtaboff: zero,
+                nmodtab: zero,
+                extrefsymoff: zero,
+                nextrefsyms: zero,
+                indirectsymoff: linkedit_offset(indirectsymoff),
+                nindirectsyms: u32le(4),
+                extreloff: zero,
+                nextrel: zero,
+                locreloff: zero,
+                nlocrel: zero,
+            })
+            .to_vec(),
+            dylib("/usr/lib/libSystem.B.dylib"),
+            dylib(crate::generate_dylib::APP_DYLIB),
+            bytes_of(&EntryPointCommand {
+                cmd: u32le(LC_MAIN),
+                cmdsize: u32le(24),
+                entryoff: u64le(text_addr - BASE),
+                stacksize: u64le(0),
+            })
+            .to_vec(),
+            bytes_of(&LinkeditDataCommand {
+                cmd: u32le(LC_CODE_SIGNATURE),
+                cmdsize: u32le(16),
+                dataoff: linkedit_offset(signature_offset),
+                datasize: u32le(0x30),
+            })
+            .to_vec(),
+        ];
+        for cmd in load_commands.iter() {
+            cmds.extend_from_slice(cmd);
+        }
+
+        let header = MachHeader64 {
+            magic: U32::new(BigEndian, MH_CIGAM_64),
+            cputype: u32le(cpu_type),
+            cpusubtype: u32le(if arm { 0 } else { 3 }),
+            filetype: u32le(MH_EXECUTE),
+            ncmds: u32le(load_commands.len() as u32),
+            sizeofcmds: u32le(cmds.len() as u32),
+            flags: u32le(MH_NOUNDEFS | MH_DYLDLINK | MH_TWOLEVEL | MH_PIE),
+            reserved: zero,
+        };
+
+        let mut host = vec![0; 2 * page as usize];
+        let mut write = |address: u64, bytes: &[u8]| {
+            let offset = (address - BASE) as usize;
+            host[offset..offset + bytes.len()].copy_from_slice(bytes);
+        };
+        write(BASE, bytes_of(&header));
+        write(BASE + mem::size_of::<MachHeader64<LE>>() as u64, &cmds);
+        write(text_addr, &text);
+        write(stubs_addr, &stubs);
+        write(got_addr, &got);
+        host.extend_from_slice(&linkedit);
+
+        host
+    }
+
+    /// An app whose main function calls `roc_alloc` and loads the address of "foo".
+    fn app_object(architecture: Architecture) -> Vec<u8> {
+        let mut obj = write::Object::new(BinaryFormat::MachO, architecture, Endianness::Little);
+
+        let rodata = obj.section_id(write::StandardSection::ReadOnlyData);
+        obj.append_section_data(rodata, b"foo\0", 1);
+        let foo = obj.add_symbol(write::Symbol {
+            name: b"foo".to_vec(),
+            value: 0,
+            size: 4,
+            kind: object::SymbolKind::Data,
+            scope: object::SymbolScope::Compilation,
+            weak: false,
+            section: write::SymbolSection::Section(rodata),
+            flags: object::SymbolFlags::None,
+        });
+
+        let roc_alloc = obj.add_symbol(write::Symbol {
+            name: b"roc_alloc".to_vec(),
+            value: 0,
+            size: 0,
+            kind: object::SymbolKind::Text,
+            scope: object::SymbolScope::Linkage,
+            weak: false,
+            section: write::SymbolSection::Undefined,
+            flags: object::SymbolFlags::None,
+        });
+
+        let text = obj.section_id(write::StandardSection::Text);
+        let (code, relocations): (&[u8], Vec<_>) = match architecture {
+            Architecture::X86_64 => (
+                &[
+                    0xe8, 0, 0, 0, 0, // call roc_alloc
+                    0x48, 0x8d, 0x05, 0, 0, 0, 0,    // lea rax, [rip + foo]
+                    0xc3, // ret
+                ],
+                vec![
+                    (
+                        1,
+                        roc_alloc,
+                        RelocationKind::Relative,
+                        RelocationEncoding::X86Branch,
+                    ),
+                    (
+                        8,
+                        obj.section_symbol(rodata),
+                        RelocationKind::Relative,
+                        RelocationEncoding::X86RipRelative,
+                    ),
+                ],
+            ),
+            Architecture::Aarch64 => {
+                let macho_kind = |value, relative| RelocationKind::MachO { value, relative };
+
+                (
+                    &[
+                        0x00, 0x00, 0x00, 0x94, // bl roc_alloc
+                        0x00, 0x00, 0x00, 0x90, // adrp x0, foo@PAGE
+                        0x00, 0x00, 0x00, 0x91, // add x0, x0, foo@PAGEOFF
+                        0xc0, 0x03, 0x5f, 0xd6, // ret
+                    ],
+                    vec![
+                        (
+                            0,
+                            roc_alloc,
+                            macho_kind(macho::ARM64_RELOC_BRANCH26, true),
+                            RelocationEncoding::Generic,
+                        ),
+                        (
+                            4,
+                            foo,
+                            macho_kind(macho::ARM64_RELOC_PAGE21, true),
+                            RelocationEncoding::Generic,
+                        ),
+                        (
+                            8,
+                            foo,
+                            macho_kind(macho::ARM64_RELOC_PAGEOFF12, false),
+                            RelocationEncoding::Generic,
+                        ),
+                    ],
+                )
+            }
+            _ => unreachable!(),
+        };
+
+        let offset = obj.append_section_data(text, code, 16);
+        obj.add_symbol(write::Symbol {
+            name: APP_FUNCTION.trim_start_matches('_').as_bytes().to_vec(),
+            value: offset,
+            size: code.len() as u64,
+            kind: object::SymbolKind::Text,
+            scope: object::SymbolScope::Linkage,
+            weak: false,
+            section: write::SymbolSection::Section(text),
+            flags: object::SymbolFlags::None,
+        });
+
+        for (offset, symbol, kind, encoding) in relocations {
+            obj.add_relocation(
+                text,
+                write::Relocation {
+                    offset,
+                    size: 32,
+                    kind,
+                    encoding,
+                    symbol,
+                    addend: if architecture == Architecture::X86_64 {
+                        -4
+                    } else {
+                        0
+                    },
+                },
+            )
+            .unwrap();
+        }
+
+        let mut bytes = obj.write().unwrap();
+
+        if architecture == Architecture::X86_64 {
+            // Relocations that target a section store the displacement to the target as it is in
+            // the object file, which `object` does not write for us.
+            let file = object::File::parse(bytes.as_slice()).unwrap();
+            let text = file.section_by_name("__text").unwrap();
+            let rodata = file.section_by_name("__const").unwrap();
+            let file_offset = text.file_range().unwrap().0 as usize + 8;
+            let displacement = rodata.address() as i64 - (text.address() as i64 + 8 + 4);
+
+            bytes[file_offset..file_offset + 4]
+                .copy_from_slice(&(displacement as i32).to_le_bytes());
+        }
+
+        bytes
+    }
+
+    fn link(host: &[u8], triple: &str) -> Vec<u8> {
+        let target = Triple::from_str(triple).unwrap();
+        let architecture = match target.architecture {
+            target_lexicon::Architecture::X86_64 => Architecture::X86_64,
+            _ => Architecture::Aarch64,
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+
+        std::fs::write(dir.join("dynhost"), host).unwrap();
+
+        preprocess_macho(
+            &target,
+            &dir.join("dynhost"),
+            &dir.join("metadata"),
+            &dir.join("preprocessedhost"),
+            Path::new(crate::generate_dylib::APP_DYLIB),
+            false,
+            false,
+        );
+
+        std::fs::copy(dir.join("preprocessedhost"), dir.join("app")).unwrap();
+
+        surgery_macho(
+            &app_object(architecture),
+            &dir.join("metadata"),
+            &dir.join("app"),
+            false,
+            false,
+        );
+
+        std::fs::read(dir.join("app")).unwrap()
+    }
+
+    fn read_u32(bytes: &[u8], offset: u64) -> u32 {
+        let offset = offset as usize;
+        u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+    }
+
+    /// The target of the `call`, `jmp` or `b(l)` instruction at the given address.
+    fn branch_target(exe: &object::File, bytes: &[u8], cpu_type: u32, address: u64) -> u64 {
+        let section = exe
+            .sections()
+            .find(|sec| (sec.address()..sec.address() + sec.size()).contains(&address))
+            .unwrap();
+        let offset = section.file_range().unwrap().0 + address - section.address();
+
+        match cpu_type {
+            macho::CPU_TYPE_X86_64 => {
+                assert!(matches!(bytes[offset as usize], 0xe8 | 0xe9));
+                let displacement = read_u32(bytes, offset + 1) as i32 as i64;
+                (address as i64 + 5 + displacement) as u64
+            }
+            _ => {
+                let inst = read_u32(bytes, offset);
+                address.wrapping_add(aarch64_branch_offset(inst) as u64)
+            }
+        }
+    }
+
+    fn check_surgery(host: &[u8], triple: &str) {
+        let output = link(host, triple);
+
+        let host_obj = object::File::parse(host).unwrap();
+        let exe = object::File::parse(output.as_slice()).unwrap();
+        let cpu_type = load_struct_inplace::<macho::MachHeader64<LE>>(&output, 0)
+            .cputype
+            .get(LE);
+
+        // The app's segments come right before __LINKEDIT, which still ends the file.
+        let segments: Vec<_> = exe
+            .segments()
+            .map(|seg| seg.name().unwrap().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            segments,
+            [
+                "__PAGEZERO",
+                "__TEXT",
+                "__DATA_CONST",
+                "__ROC_DATA",
+                "__ROC_TEXT",
+                "__LINKEDIT"
+            ]
+        );
+        let (linkedit_offset, linkedit_size) = exe.segments().last().unwrap().file_range();
+        assert_eq!(linkedit_offset + linkedit_size, output.len() as u64);
+
+        // The app function is now defined in the app's text segment.
+        let app_function = exe
+            .symbols()
+            .find(|sym| sym.name() == Ok(APP_FUNCTION))
+            .unwrap();
+        let app_text = exe.section_by_name("__text").unwrap();
+        assert!(app_function.is_definition());
+        let app_text = exe
+            .sections()
+            .filter(|sec| sec.name() == Ok("__text"))
+            .last()
+            .unwrap_or(app_text);
+        assert_eq!(app_text.segment_name(), Ok(Some("__ROC_TEXT")));
+        assert_eq!(app_function.address(), app_text.address());
+
+        // The host calls the app directly, and the app's stub jumps there too.
+        let main = host_obj.entry();
+        let stubs = exe.section_by_name("__stubs").unwrap();
+        let stub_size = stubs.size() / 2;
+        let main = host_obj
+            .symbols()
+            .find(|sym| sym.name() == Ok("_main"))
+            .map_or(main, |sym| sym.address());
+        assert_eq!(
+            branch_target(&exe, &output, cpu_type, main),
+            app_function.address()
+        );
+        assert_eq!(
+            branch_target(&exe, &output, cpu_type, stubs.address() + stub_size),
+            app_function.address()
+        );
+
+        // Other stubs are untouched.
+        let host_stubs = host_obj.section_by_name("__stubs").unwrap();
+        assert_eq!(
+            stubs.data().unwrap()[..stub_size as usize],
+            host_stubs.data().unwrap()[..stub_size as usize]
+        );
+
+        // The host's GOT entry for the app is now a rebase to it, and the one for `puts` is
+        // untouched. Both are still in the same chain.
+        let got = exe.section_by_name("__got").unwrap();
+        let host_got = host_obj.section_by_name("__got").unwrap().data().unwrap();
+        assert_eq!(got.data().unwrap()[..8], host_got[..8]);
+        let app_entry = u64::from_le_bytes(got.data().unwrap()[8..16].try_into().unwrap());
+        let image_base = match cpu_type {
+            macho::CPU_TYPE_X86_64 => 0x1_0000_0000,
+            _ => 0,
+        };
+        assert_eq!(app_entry, app_function.address() - image_base);
+
+        // The app's relocations point at the host's roc_alloc and the app's data.
+        let roc_alloc = exe
+            .symbols()
+            .find(|sym| sym.name() == Ok("_roc_alloc"))
+            .unwrap()
+            .address();
+        assert_eq!(
+            branch_target(&exe, &output, cpu_type, app_function.address()),
+            roc_alloc
+        );
+
+        let app_data = exe
+            .segments()
+            .find(|seg| seg.name() == Ok(Some("__ROC_DATA")))
+            .unwrap();
+        assert_eq!(&app_data.data().unwrap()[..4], b"foo\0");
+
+        let text_offset = app_text.file_range().unwrap().0;
+        let foo = match cpu_type {
+            macho::CPU_TYPE_X86_64 => {
+                let displacement = read_u32(&output, text_offset + 8) as i32 as i64;
+                (app_function.address() as i64 + 12 + displacement) as u64
+            }
+            _ => {
+                let adrp = read_u32(&output, text_offset + 4);
+                let add = read_u32(&output, text_offset + 8);
+                let pages = ((adrp >> 29) & 0x3) | (((adrp >> 5) & 0x7ffff) << 2);
+                let pages = ((pages << 11) as i32 >> 11) as i64;
+                let page = (app_function.address() + 4) & !0xfff;
+                (page as i64 + (pages << 12)) as u64 + ((add >> 10) & 0xfff) as u64
+            }
+        };
+        assert_eq!(foo, app_data.address());
+
+        // The stub dylib is now weakly linked, and the invalid code signature is gone.
+        let commands: Vec<_> = load_commands(&output)
+            .into_iter()
+            .map(|(_, cmd)| cmd)
+            .collect();
+        assert!(!commands.contains(&macho::LC_CODE_SIGNATURE));
+        let weak_dylibs: Vec<_> = load_commands(&output)
+            .into_iter()
+            .filter(|(_, cmd)| *cmd == macho::LC_LOAD_WEAK_DYLIB)
+            .map(|(offset, _)| dylib_path(&output, offset).to_path_buf())
+            .collect();
+        assert_eq!(weak_dylibs, [Path::new(crate::generate_dylib::APP_DYLIB)]);
+    }
+
+    #[test]
+    fn surgery_macho_x86_64() {
+        check_surgery(&dynhost(macho::CPU_TYPE_X86_64), "x86_64-apple-darwin");
+    }
+
+    #[test]
+    fn surgery_macho_aarch64() {
+        check_surgery(&dynhost(macho::CPU_TYPE_ARM64), "aarch64-apple-darwin");
+    }
+
+    #[test]
+    fn chained_fixups_cover_app_segments() {
+        let output = link(&dynhost(macho::CPU_TYPE_ARM64), "aarch64-apple-darwin");
+
+        let (offset, _) = load_commands(&output)
+            .into_iter()
+            .find(|(_, cmd)| *cmd == macho::LC_DYLD_CHAINED_FIXUPS)
+            .unwrap();
+        let cmd = load_struct_inplace::<macho::LinkeditDataCommand<LE>>(&output, offset);
+        let fixups = cmd.dataoff.get(LE) as u64;
+
+        // dyld_chained_starts_in_image has an entry for every segment
+        let starts = fixups + read_u32(&output, fixups + 4) as u64;
+        let seg_count = read_u32(&output, starts);
+        assert_eq!(seg_count, 6);
+
+        let seg_info_offsets: Vec<_> = (0..seg_count as u64)
+            .map(|i| read_u32(&output, starts + 4 + 4 * i))
+            .collect();
+        assert_eq!(seg_info_offsets[..2], [0, 0]);
+        assert_ne!(seg_info_offsets[2], 0);
+        assert_eq!(seg_info_offsets[3..], [0, 0, 0]);
+
+        // __DATA_CONST still points at its dyld_chained_starts_in_segment
+        let segment_offset = starts + seg_info_offsets[2] as u64;
+        let page_size = u16::from_le_bytes(
+            output[segment_offset as usize + 4..segment_offset as usize + 6]
+                .try_into()
+                .unwrap(),
+        );
+        assert_eq!(page_size, 0x4000);
+
+        // and the imports moved along with the new entries.
+        let symbols = fixups + read_u32(&output, fixups + 12) as u64;
+        assert_eq!(&output[symbols as usize..][..7], b"\0_puts\0");
+    }
 }
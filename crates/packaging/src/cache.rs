@@ -1,11 +1,12 @@
 #[cfg(not(target_family = "wasm"))]
 use {
-    crate::https::{self, PackageMetadata, Problem},
+    crate::https::{Destination, PackageMetadata, Problem},
+    crate::{mirror, signature},
     roc_error_macros::internal_error,
     std::fs,
 };
 #[cfg(not(target_family = "wasm"))]
-const MAX_DOWNLOAD_BYTES: u64 = 32 * 1_000_000_000; // GB
+pub(crate) const MAX_DOWNLOAD_BYTES: u64 = 32 * 1_000_000_000; // GB
 
 use std::path::{Path, PathBuf};
 
@@ -59,8 +60,10 @@ fn nixos_error_if_dynamic(url: &str, dest_dir: &Path) {
 /// into that dir. If the cache dir on the filesystem, then look into it to see if we already
 /// have an entry for the given URL. If we do, return its info. If we don't already have it, then:
 ///
-/// - Download and decompress the compressed tarball from the given URL
+/// - Download and decompress the compressed tarball from the given URL, or from a mirror
+///   configured using ROC_PACKAGE_MIRRORS or roc-mirrors.txt
 /// - Verify its bytes against the hash in the URL
+/// - Verify its signature, if any trusted keys were set using ROC_TRUSTED_PACKAGE_KEYS
 /// - Extract the tarball's contents into the appropriate cache directory
 ///
 /// Returns the path to the installed package (which will be in the cache dir somewhere), as well
@@ -101,52 +104,52 @@ pub fn install_package<'a>(
                 );
                 let tempdir = tempfile::tempdir().map_err(Problem::IoErr)?;
                 let tempdir_path = tempdir.path();
-                let downloaded_hash =
-                    https::download_and_hash(url, tempdir_path, MAX_DOWNLOAD_BYTES)?;
-
-                // Download the tarball into memory and verify it.
-                // The tarball name is the hash of its contents.
-                if downloaded_hash == content_hash {
-                    // Now that we've verified the hash, rename the tempdir to the real dir.
-
-                    // Create the destination dir's parent dir, since it may not exist yet.
-                    fs::create_dir_all(parent_dir).map_err(Problem::IoErr)?;
-
-                    // This rename should be super cheap if it succeeds - just an inode change.
-                    if fs::rename(tempdir_path, &dest_dir).is_err() {
-                        // If the rename failed, try a recursive copy -
-                        // it could have failed due to std::io::ErrorKind::CrossesDevices
-                        // (e.g. if the source an destination directories are on different disks)
-                        // which as of this implementation is nightly-only
-                        // https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.CrossesDevices                       match io_err.kind() {
-                        // but if that's what happened, this should work!
-
-                        // fs_extra::dir::copy needs the destination directory to exist already.
-                        fs::create_dir(&dest_dir).map_err(Problem::IoErr)?;
-                        fs_extra::dir::copy(
-                            tempdir_path,
-                            &dest_dir,
-                            &fs_extra::dir::CopyOptions {
-                                content_only: true,
-                                ..Default::default()
-                            },
-                        )
-                        .map_err(Problem::FsExtraErr)?;
-                    }
-
-                    #[cfg(target_os = "linux")]
-                    {
-                        nixos_error_if_dynamic(url, &dest_dir);
-                    }
-
-                    // The package's files are now in the cache. We're done!
-                    Ok((dest_dir, root_module_filename))
-                } else {
-                    Err(Problem::InvalidContentHash {
-                        expected: content_hash.to_string(),
-                        actual: downloaded_hash,
-                    })
+
+                // This verifies the downloaded bytes against content_hash,
+                // whether they came from a mirror or from the URL itself.
+                mirror::fetch(
+                    url,
+                    content_hash,
+                    Destination::Unpack(tempdir_path),
+                    &mirror::mirrors()?,
+                    MAX_DOWNLOAD_BYTES,
+                    &signature::trusted_keys(),
+                )?;
+
+                // Now that we've verified the hash, rename the tempdir to the real dir.
+
+                // Create the destination dir's parent dir, since it may not exist yet.
+                fs::create_dir_all(parent_dir).map_err(Problem::IoErr)?;
+
+                // This rename should be super cheap if it succeeds - just an inode change.
+                if fs::rename(tempdir_path, &dest_dir).is_err() {
+                    // If the rename failed, try a recursive copy -
+                    // it could have failed due to std::io::ErrorKind::CrossesDevices
+                    // (e.g. if the source an destination directories are on different disks)
+                    // which as of this implementation is nightly-only
+                    // https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.CrossesDevices                       match io_err.kind() {
+                    // but if that's what happened, this should work!
+
+                    // fs_extra::dir::copy needs the destination directory to exist already.
+                    fs::create_dir(&dest_dir).map_err(Problem::IoErr)?;
+                    fs_extra::dir::copy(
+                        tempdir_path,
+                        &dest_dir,
+                        &fs_extra::dir::CopyOptions {
+                            content_only: true,
+                            ..Default::default()
+                        },
+                    )
+                    .map_err(Problem::FsExtraErr)?;
                 }
+
+                #[cfg(target_os = "linux")]
+                {
+                    nixos_error_if_dynamic(url, &dest_dir);
+                }
+
+                // The package's files are now in the cache. We're done!
+                Ok((dest_dir, root_module_filename))
             }
         }
         RocCacheDir::Disallowed => {
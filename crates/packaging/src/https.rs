@@ -1,8 +1,10 @@
 use std::{
+    fs,
     io::{self, Read},
-    path::Path,
+    path::{Path, PathBuf},
 };
 
+use crate::signature::{self, SIGNATURE_EXT};
 use crate::tarball::Compression;
 
 // gzip should be the most widely supported, and brotli offers the highest compression.
@@ -116,19 +118,68 @@ pub enum Problem {
     InvalidUrl(UrlProblem),
     /// The Content-Length header of the response exceeded max_download_bytes
     DownloadTooBig(u64),
+    /// There were trusted keys, but the package's signature could not be downloaded
+    MissingSignature(String),
+    InvalidSignature(signature::Problem),
+    /// An entry in ROC_PACKAGE_MIRRORS or a roc-mirrors.txt file was malformed
+    InvalidMirror(String),
 }
 
+/// Where to put a tarball once it has been decompressed.
+#[derive(Debug, Clone, Copy)]
+pub enum Destination<'a> {
+    /// Unpack the tarball's contents into this directory
+    Unpack(&'a Path),
+    /// Write the uncompressed .tar file itself to this path
+    Tarball(&'a Path),
+}
+
+/// A tarball whose hash has been computed, and whose signature (if any) has been verified.
+#[derive(Debug)]
+pub struct Verified {
+    /// The base64url-encoded BLAKE3 hash of the uncompressed tarball
+    pub content_hash: String,
+    /// Only present if there were trusted keys to check it against.
+    pub signature: Option<String>,
+}
+
+/// Download the tarball at the given URL, decompress it into the given destination,
+/// and return its hash.
+///
+/// If there are any trusted keys, this also downloads the package's detached signature
+/// (at the URL with SIGNATURE_EXT appended) and fails unless one of those keys signed
+/// the hash of what was downloaded.
 pub fn download_and_hash(
     url: &str,
-    dest_dir: &Path,
+    dest: Destination<'_>,
     max_download_bytes: u64,
-) -> Result<String, Problem> {
+    trusted_keys: &[String],
+) -> Result<Verified, Problem> {
     // TODO apparently it really improves performance to construct a Client once and then reuse it,
     // instead of making a new Client for every request.
     // Per https://github.com/seanmonstar/reqwest/issues/1454#issuecomment-1026076701
-    let resp = reqwest::blocking::Client::new()
+    let client = reqwest::blocking::Client::new();
+
+    // Get the signature first, so we don't download a big tarball only to find it's not signed.
+    let opt_signature = if trusted_keys.is_empty() {
+        None
+    } else {
+        let sig_url = format!("{}{}", url.split('#').next().unwrap_or(url), SIGNATURE_EXT);
+        let resp = client
+            .get(&sig_url)
+            .send()
+            .and_then(|resp| resp.error_for_status())
+            .map_err(|_| Problem::MissingSignature(sig_url))?;
+
+        Some(resp.text().map_err(Problem::HttpErr)?)
+    };
+
+    // Mirrors may well serve a 404 for packages they don't have, and we don't want to
+    // go on to try to unpack the HTML of an error page.
+    let resp = client
         .get(url)
         .send()
+        .and_then(|resp| resp.error_for_status())
         .map_err(Problem::HttpErr)?;
 
     // Some servers don't return Content-Length - e.g. Netlify seems to only sometimes return it.
@@ -153,7 +204,55 @@ pub fn download_and_hash(
 
     // Use .take to prevent a malicious server from sending back bytes
     // until system resources are exhausted!
-    decompress_into(dest_dir, encoding, resp.take(max_download_bytes))
+    let content_hash = decompress_into(dest, encoding, resp.take(max_download_bytes))?;
+
+    verify_signature(content_hash, opt_signature, trusted_keys)
+}
+
+/// Like `download_and_hash`, but for a tarball that's already on disk, e.g. in a mirror
+/// directory. Its signature (if there are any trusted keys) must be next to it, in a file
+/// with SIGNATURE_EXT appended to the tarball's filename.
+pub fn read_and_hash(
+    path: &Path,
+    dest: Destination<'_>,
+    trusted_keys: &[String],
+) -> Result<Verified, Problem> {
+    let opt_signature = if trusted_keys.is_empty() {
+        None
+    } else {
+        let mut sig_path = path.as_os_str().to_os_string();
+
+        sig_path.push(SIGNATURE_EXT);
+
+        let sig_path = PathBuf::from(sig_path);
+
+        Some(
+            fs::read_to_string(&sig_path)
+                .map_err(|_| Problem::MissingSignature(sig_path.display().to_string()))?,
+        )
+    };
+
+    let encoding = Encoding::new("", &path.to_string_lossy())?;
+    let file = fs::File::open(path).map_err(Problem::IoErr)?;
+    let content_hash = decompress_into(dest, encoding, file)?;
+
+    verify_signature(content_hash, opt_signature, trusted_keys)
+}
+
+fn verify_signature(
+    content_hash: String,
+    opt_signature: Option<String>,
+    trusted_keys: &[String],
+) -> Result<Verified, Problem> {
+    if let Some(signature) = &opt_signature {
+        signature::verify(&content_hash, signature, trusted_keys)
+            .map_err(Problem::InvalidSignature)?;
+    }
+
+    Ok(Verified {
+        content_hash,
+        signature: opt_signature,
+    })
 }
 
 /// The content encodings we support
@@ -214,12 +313,21 @@ fn encoding_from_tar_br() {
     assert_eq!(Encoding::Brotli, actual);
 }
 
-fn hash_and_unpack(dest_dir: &Path, reader: impl Read) -> Result<String, Problem> {
+fn hash_and_unpack(dest: Destination<'_>, reader: impl Read) -> Result<String, Problem> {
     let mut hash_reader = HashReader::new(reader);
 
-    tar::Archive::new(&mut hash_reader)
-        .unpack(dest_dir)
-        .map_err(Problem::IoErr)?;
+    match dest {
+        Destination::Unpack(dest_dir) => {
+            tar::Archive::new(&mut hash_reader)
+                .unpack(dest_dir)
+                .map_err(Problem::IoErr)?;
+        }
+        Destination::Tarball(dest_file) => {
+            let mut file = fs::File::create(dest_file).map_err(Problem::IoErr)?;
+
+            io::copy(&mut hash_reader, &mut file).map_err(Problem::IoErr)?;
+        }
+    }
 
     let mut buf = Vec::with_capacity(1024);
 
@@ -231,25 +339,24 @@ fn hash_and_unpack(dest_dir: &Path, reader: impl Read) -> Result<String, Problem
 }
 
 /// Read from the given reader, decompress the bytes using the given Content-Encoding string,
-/// write them to the given writer, and return the base64url-encoded BLAKE3 hash of what was written.
-/// This both writes and hashes incrementally as it reads, so the only extra work that's done
-/// at the end is base64url-encoding the final hash.
+/// write them to the given destination, and return the base64url-encoded BLAKE3 hash of what
+/// was written. This both writes and hashes incrementally as it reads, so the only extra work
+/// that's done at the end is base64url-encoding the final hash.
 fn decompress_into(
-    dest_dir: &Path,
+    dest: Destination<'_>,
     encoding: Encoding,
     reader: impl Read,
 ) -> Result<String, Problem> {
     match encoding {
-        Encoding::Brotli => hash_and_unpack(
-            dest_dir,
-            brotli::Decompressor::new(reader, BROTLI_BUFFER_BYTES),
-        ),
+        Encoding::Brotli => {
+            hash_and_unpack(dest, brotli::Decompressor::new(reader, BROTLI_BUFFER_BYTES))
+        }
         Encoding::Gzip => {
             // Note: GzDecoder::new immediately parses the gzip header (so, calls read())
-            hash_and_unpack(dest_dir, flate2::read::GzDecoder::new(reader))
+            hash_and_unpack(dest, flate2::read::GzDecoder::new(reader))
         }
-        Encoding::Deflate => hash_and_unpack(dest_dir, flate2::read::DeflateDecoder::new(reader)),
-        Encoding::Uncompressed => hash_and_unpack(dest_dir, reader),
+        Encoding::Deflate => hash_and_unpack(dest, flate2::read::DeflateDecoder::new(reader)),
+        Encoding::Uncompressed => hash_and_unpack(dest, reader),
     }
 }
 
@@ -1,4 +1,11 @@
 pub mod cache;
 #[cfg(not(target_family = "wasm"))]
 pub mod https;
+#[cfg(not(target_family = "wasm"))]
+pub mod manifest;
+#[cfg(not(target_family = "wasm"))]
+pub mod mirror;
+pub mod signature;
 pub mod tarball;
+#[cfg(not(target_family = "wasm"))]
+pub mod vendor;
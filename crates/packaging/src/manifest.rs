@@ -0,0 +1,213 @@
+use crate::https::{PackageMetadata, UrlProblem};
+use crate::tarball::read_header;
+use bumpalo::Bump;
+use roc_parse::ast::{ExtractSpaces, Header};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+
+/// The name of the metadata file that `roc package` puts in the root of the tarball.
+pub const MANIFEST_FILENAME: &str = "roc-package.json";
+
+/// What a published package contains and depends on, so that it can be audited
+/// without having to unpack and read all of its .roc files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// The name from the `package` header, e.g. "json"
+    pub name: String,
+    pub version: String,
+    /// An SPDX license identifier, e.g. "UPL-1.0"
+    pub license: Option<String>,
+    /// The module names from the `package` header's `exposes` list
+    pub exposes: Vec<String>,
+    /// The packages from the `package` header's `packages` record, sorted by shorthand
+    pub dependencies: Vec<Dependency>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Dependency {
+    pub shorthand: String,
+    pub url: String,
+    /// The BLAKE3 hash of the dependency's tarball, as given in its URL
+    pub content_hash: String,
+}
+
+#[derive(Debug)]
+pub enum Problem {
+    IoErr(io::Error),
+    /// The root module had some other header than `package`
+    NotAPackage,
+    /// A dependency was a local path; published packages can only depend on URLs.
+    LocalDependency {
+        shorthand: String,
+        path: String,
+    },
+    InvalidDependencyUrl {
+        shorthand: String,
+        problem: UrlProblem,
+    },
+    InvalidJson(serde_json::Error),
+}
+
+impl Manifest {
+    /// Read the name, exposed modules and dependencies from the `package` header of the given
+    /// .roc file. The version and license are not part of the header, so they're passed in.
+    pub fn from_package_header(
+        path_to_main: &Path,
+        version: String,
+        license: Option<String>,
+    ) -> Result<Self, Problem> {
+        let arena = Bump::new();
+        let mut buf = Vec::new();
+
+        let header = match read_header(&arena, &mut buf, path_to_main)
+            .map_err(Problem::IoErr)?
+            .header
+        {
+            Header::Package(header) => header,
+            _ => return Err(Problem::NotAPackage),
+        };
+
+        let exposes = header
+            .exposes
+            .item
+            .iter()
+            .map(|loc_name| {
+                let module_name: &str = loc_name.value.extract_spaces().item.into();
+
+                module_name.to_string()
+            })
+            .collect();
+
+        let mut dependencies = Vec::with_capacity(header.packages.item.len());
+
+        for loc_entry in header.packages.item.iter() {
+            let entry = loc_entry.value.extract_spaces().item;
+            let shorthand = entry.shorthand.to_string();
+            let url = entry.package_name.value.as_str();
+
+            match PackageMetadata::try_from(url) {
+                Ok(metadata) => dependencies.push(Dependency {
+                    shorthand,
+                    url: url.to_string(),
+                    content_hash: metadata.content_hash.to_string(),
+                }),
+                Err(UrlProblem::MissingHttps) => {
+                    return Err(Problem::LocalDependency {
+                        shorthand,
+                        path: url.to_string(),
+                    });
+                }
+                Err(problem) => {
+                    return Err(Problem::InvalidDependencyUrl { shorthand, problem });
+                }
+            }
+        }
+
+        // Sort so that the manifest doesn't change when the header is merely reformatted.
+        dependencies.sort_by(|a, b| a.shorthand.cmp(&b.shorthand));
+
+        Ok(Manifest {
+            name: header.name.value.as_str().to_string(),
+            version,
+            license,
+            exposes,
+            dependencies,
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        // Serializing these types can't fail; they're just strings and lists.
+        let mut json = serde_json::to_string_pretty(self).unwrap();
+
+        json.push('\n');
+
+        json
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Problem> {
+        serde_json::from_str(json).map_err(Problem::InvalidJson)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+
+    fn manifest_for(header: &str) -> Result<Manifest, Problem> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("main.roc");
+
+        fs::write(&path, header).unwrap();
+
+        Manifest::from_package_header(&path, "1.2.3".to_string(), Some("UPL-1.0".to_string()))
+    }
+
+    #[test]
+    fn reads_package_header() {
+        let manifest = manifest_for(
+            r#"package "json"
+    exposes [Json, Json.Decode]
+    packages {
+        parser: "https://example.com/parser/Jx8WRt-u3Ymq1Xv4SjWvA0LpyH9wTQXKfLbTIA4uD4Q.tar.br",
+        core: "https://example.com/roc-core/k2RmaC0LU4A-VyQJDKFoRLstDT6iEnTVjmZRSPDbUMk.tar.gz",
+    }
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            manifest,
+            Manifest {
+                name: "json".to_string(),
+                version: "1.2.3".to_string(),
+                license: Some("UPL-1.0".to_string()),
+                exposes: vec!["Json".to_string(), "Json.Decode".to_string()],
+                dependencies: vec![
+                    Dependency {
+                        shorthand: "core".to_string(),
+                        url: "https://example.com/roc-core/k2RmaC0LU4A-VyQJDKFoRLstDT6iEnTVjmZRSPDbUMk.tar.gz".to_string(),
+                        content_hash: "k2RmaC0LU4A-VyQJDKFoRLstDT6iEnTVjmZRSPDbUMk".to_string(),
+                    },
+                    Dependency {
+                        shorthand: "parser".to_string(),
+                        url: "https://example.com/parser/Jx8WRt-u3Ymq1Xv4SjWvA0LpyH9wTQXKfLbTIA4uD4Q.tar.br".to_string(),
+                        content_hash: "Jx8WRt-u3Ymq1Xv4SjWvA0LpyH9wTQXKfLbTIA4uD4Q".to_string(),
+                    },
+                ],
+            }
+        );
+
+        assert_eq!(Manifest::from_json(&manifest.to_json()).unwrap(), manifest);
+    }
+
+    #[test]
+    fn rejects_local_dependencies() {
+        let problem = manifest_for(
+            r#"package "json"
+    exposes [Json]
+    packages { parser: "../parser/main.roc" }
+"#,
+        )
+        .unwrap_err();
+
+        assert!(matches!(
+            problem,
+            Problem::LocalDependency { shorthand, .. } if shorthand == "parser"
+        ));
+    }
+
+    #[test]
+    fn rejects_other_headers() {
+        let problem = manifest_for(
+            r#"interface Json
+    exposes []
+    imports []
+"#,
+        )
+        .unwrap_err();
+
+        assert!(matches!(problem, Problem::NotAPackage));
+    }
+}
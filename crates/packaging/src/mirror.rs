@@ -0,0 +1,290 @@
+//! Mirrors let packages be fetched from somewhere other than the URL in the module header,
+//! e.g. a local directory on a build machine with no internet access, or an internal server.
+//!
+//! Each mirror maps a URL prefix to either a directory or a base URL:
+//!
+//! ```text
+//! https://github.com/roc-lang/ = https://artifacts.example.com/roc-lang/
+//! * = /srv/roc-packages
+//! ```
+//!
+//! A directory mirror holds tarballs named after their content hashes (as written by
+//! `roc vendor`), so only the hash in the URL matters when looking something up there.
+//! A URL mirror gets whatever followed the matched prefix appended to its base URL.
+//! Either way, what's fetched from a mirror must still match the hash in the original URL.
+use crate::https::{self, Destination, Problem, Verified};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Comma-separated mirrors, e.g. `ROC_PACKAGE_MIRRORS="* = /srv/roc-packages"`
+pub const MIRRORS_ENV_VAR: &str = "ROC_PACKAGE_MIRRORS";
+
+/// One mirror per line, with `#` for comments. Read from the current directory.
+pub const MIRRORS_FILENAME: &str = "roc-mirrors.txt";
+
+/// Matches every package URL
+const ANY_URL: &str = "*";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MirrorTarget {
+    /// A directory of `<content hash>.tar` (or .tar.gz or .tar.br) files
+    Dir(PathBuf),
+    /// A base URL, which can be http:// as well as https:// since hashes get verified anyway
+    Url(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mirror {
+    /// Either ANY_URL, or a prefix that package URLs must start with
+    pub prefix: String,
+    pub target: MirrorTarget,
+}
+
+impl Mirror {
+    /// Parse an entry of the form `PREFIX = TARGET`. A relative directory
+    /// target is resolved relative to base_dir.
+    pub fn parse(entry: &str, base_dir: &Path) -> Result<Self, Problem> {
+        let (prefix, target) = match entry.split_once('=') {
+            Some((prefix, target)) if !prefix.trim().is_empty() && !target.trim().is_empty() => {
+                (prefix.trim(), target.trim())
+            }
+            _ => return Err(Problem::InvalidMirror(entry.to_string())),
+        };
+
+        let target = if target.starts_with("https://") || target.starts_with("http://") {
+            MirrorTarget::Url(target.to_string())
+        } else {
+            MirrorTarget::Dir(base_dir.join(target))
+        };
+
+        Ok(Mirror {
+            prefix: prefix.to_string(),
+            target,
+        })
+    }
+
+    /// If this mirror applies to the given package URL, return what's left of the URL
+    /// once this mirror's prefix has been removed.
+    fn strip_prefix<'a>(&self, url: &'a str) -> Option<&'a str> {
+        if self.prefix == ANY_URL {
+            Some(url.strip_prefix("https://").unwrap_or(url))
+        } else {
+            url.strip_prefix(self.prefix.as_str())
+        }
+    }
+}
+
+/// Parse the mirrors in the given text, one entry per line or comma-separated.
+pub fn parse_mirrors(text: &str, base_dir: &Path) -> Result<Vec<Mirror>, Problem> {
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .flat_map(|line| line.split(','))
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| Mirror::parse(entry, base_dir))
+        .collect()
+}
+
+/// The mirrors from MIRRORS_ENV_VAR, followed by the ones in the current directory's
+/// MIRRORS_FILENAME (if it exists), in the order they should be tried.
+///
+/// This looks up environment variables and reads files, so it should ideally be called once
+/// and then cached!
+pub fn mirrors() -> Result<Vec<Mirror>, Problem> {
+    let cwd = std::env::current_dir().map_err(Problem::IoErr)?;
+    let mut answer = match std::env::var(MIRRORS_ENV_VAR) {
+        Ok(entries) => parse_mirrors(&entries, &cwd)?,
+        Err(_) => Vec::new(),
+    };
+
+    match fs::read_to_string(cwd.join(MIRRORS_FILENAME)) {
+        Ok(text) => answer.extend(parse_mirrors(&text, &cwd)?),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(Problem::IoErr(err)),
+    }
+
+    Ok(answer)
+}
+
+/// Fetch the package at the given URL into the given destination, trying each applicable
+/// mirror in turn before falling back on the URL itself. Whatever gets fetched must have the
+/// given content hash.
+///
+/// A mirror which doesn't have the package (or which is unreachable, or has the wrong bytes)
+/// just gets skipped, with a warning. If the URL itself fails, that's the error returned.
+pub fn fetch(
+    url: &str,
+    content_hash: &str,
+    dest: Destination<'_>,
+    mirrors: &[Mirror],
+    max_download_bytes: u64,
+    trusted_keys: &[String],
+) -> Result<Verified, Problem> {
+    // The fragment (if any) names the root module, and doesn't affect what gets downloaded.
+    let without_fragment = url.split('#').next().unwrap_or(url);
+
+    for mirror in mirrors {
+        let rest = match mirror.strip_prefix(without_fragment) {
+            Some(rest) => rest,
+            None => continue,
+        };
+
+        let result = match &mirror.target {
+            MirrorTarget::Dir(dir) => match find_tarball(dir, content_hash) {
+                Some(path) => {
+                    reset(dest).map_err(Problem::IoErr)?;
+                    https::read_and_hash(&path, dest, trusted_keys)
+                }
+                None => continue,
+            },
+            MirrorTarget::Url(base_url) => {
+                let mirror_url = format!("{}/{}", base_url.trim_end_matches('/'), rest);
+
+                reset(dest).map_err(Problem::IoErr)?;
+                https::download_and_hash(&mirror_url, dest, max_download_bytes, trusted_keys)
+            }
+        };
+
+        match result {
+            Ok(verified) if verified.content_hash == content_hash => return Ok(verified),
+            Ok(verified) => {
+                eprintln!(
+                    "Warning: skipping the mirror {} = {:?} for {url}, because what it had hashed to {} instead.",
+                    mirror.prefix, mirror.target, verified.content_hash
+                );
+            }
+            Err(problem) => {
+                eprintln!(
+                    "Warning: skipping the mirror {} = {:?} for {url}, because of this problem: {:?}",
+                    mirror.prefix, mirror.target, problem
+                );
+            }
+        }
+    }
+
+    reset(dest).map_err(Problem::IoErr)?;
+
+    let verified = https::download_and_hash(url, dest, max_download_bytes, trusted_keys)?;
+
+    if verified.content_hash == content_hash {
+        Ok(verified)
+    } else {
+        Err(Problem::InvalidContentHash {
+            expected: content_hash.to_string(),
+            actual: verified.content_hash,
+        })
+    }
+}
+
+/// Tarballs are named after their content hashes, so any compression will do.
+fn find_tarball(dir: &Path, content_hash: &str) -> Option<PathBuf> {
+    [".tar", ".tar.br", ".tar.gz"]
+        .iter()
+        .map(|ext| dir.join(format!("{content_hash}{ext}")))
+        .find(|path| path.is_file())
+}
+
+/// Clear out anything a previous (failed) attempt may have left in the destination.
+fn reset(dest: Destination<'_>) -> std::io::Result<()> {
+    match dest {
+        Destination::Unpack(dir) => {
+            if dir.exists() {
+                fs::remove_dir_all(dir)?;
+            }
+
+            fs::create_dir_all(dir)
+        }
+        // This gets truncated when it's written anyway.
+        Destination::Tarball(_) => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn tarball(contents: &[u8]) -> (Vec<u8>, String) {
+        let mut builder = tar::Builder::new(Vec::new());
+        let mut header = tar::Header::new_gnu();
+
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        builder
+            .append_data(&mut header, "main.roc", contents)
+            .unwrap();
+
+        let bytes = builder.into_inner().unwrap();
+        let hash = base64_url::encode(blake3::hash(&bytes).as_bytes());
+
+        (bytes, hash)
+    }
+
+    #[test]
+    fn parses_mirrors() {
+        let base_dir = Path::new("/home/roc/project");
+        let mirrors = parse_mirrors(
+            "# Our build machines are offline\n\
+             https://github.com/roc-lang/ = https://artifacts.example.com/roc-lang/\n\
+             * = vendor, * = /srv/roc-packages\n",
+            base_dir,
+        )
+        .unwrap();
+
+        assert_eq!(
+            mirrors,
+            vec![
+                Mirror {
+                    prefix: "https://github.com/roc-lang/".to_string(),
+                    target: MirrorTarget::Url(
+                        "https://artifacts.example.com/roc-lang/".to_string()
+                    ),
+                },
+                Mirror {
+                    prefix: "*".to_string(),
+                    target: MirrorTarget::Dir(base_dir.join("vendor")),
+                },
+                Mirror {
+                    prefix: "*".to_string(),
+                    target: MirrorTarget::Dir(PathBuf::from("/srv/roc-packages")),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_entries_without_targets() {
+        assert!(matches!(
+            parse_mirrors("https://example.com/", Path::new(".")),
+            Err(Problem::InvalidMirror(_))
+        ));
+    }
+
+    #[test]
+    fn fetches_from_dir_mirror() {
+        let mirror_dir = tempfile::tempdir().unwrap();
+        let dest_dir = tempfile::tempdir().unwrap();
+        let (bytes, hash) = tarball(b"package \"json\" exposes [] packages {}\n");
+
+        fs::write(mirror_dir.path().join(format!("{hash}.tar")), bytes).unwrap();
+
+        // The URL says .tar.br, but the mirror only has the .tar - which is fine,
+        // since it has the same hash.
+        let url = format!("https://example.com/{hash}.tar.br#main.roc");
+        let mirrors = parse_mirrors(
+            &format!("* = {}", mirror_dir.path().display()),
+            Path::new("."),
+        )
+        .unwrap();
+        let verified = fetch(
+            &url,
+            &hash,
+            Destination::Unpack(dest_dir.path()),
+            &mirrors,
+            1_000_000,
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(verified.content_hash, hash);
+        assert!(dest_dir.path().join("main.roc").exists());
+    }
+}
@@ -0,0 +1,222 @@
+//! Detached signatures for package tarballs.
+//!
+//! A signature covers the BLAKE3 content hash of the (uncompressed) tarball, which is the same
+//! hash that ends up in the package's URL and that `https::download_and_hash` computes while
+//! downloading. That means one signature is valid for the .tar, .tar.gz and .tar.br versions
+//! of the same package.
+//!
+//! Keys and signatures are Ed25519, and are stored base64url-encoded, like content hashes.
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signature, Signer, Verifier};
+use std::io::{self, Write};
+use std::path::Path;
+
+/// A package's signature is published next to its tarball, at the tarball's URL plus this
+/// extension, e.g. `https://example.com/jDRlAFAA3738vu3-vMpLUoyxtA86Z7CaZneoOKrihbE.tar.br.sig`
+pub const SIGNATURE_EXT: &str = ".sig";
+
+/// Comma-separated public keys. If this is set, only packages signed by one of these keys
+/// will be downloaded.
+pub const TRUSTED_KEYS_ENV_VAR: &str = "ROC_TRUSTED_PACKAGE_KEYS";
+
+#[derive(Debug)]
+pub enum Problem {
+    IoErr(io::Error),
+    InvalidKey(String),
+    InvalidSignature(String),
+    /// The signature is well-formed, but was not made by any of the trusted keys
+    /// for this content hash.
+    UntrustedSignature {
+        content_hash: String,
+    },
+}
+
+pub struct SigningKey(Keypair);
+
+impl SigningKey {
+    pub fn generate() -> Self {
+        let secret = SecretKey::from_bytes(&rand::random::<[u8; 32]>())
+            .expect("Any 32 bytes should be a valid Ed25519 secret key");
+
+        Self::from_secret(secret)
+    }
+
+    fn from_secret(secret: SecretKey) -> Self {
+        let public = PublicKey::from(&secret);
+
+        SigningKey(Keypair { secret, public })
+    }
+
+    /// Read a key file written by `SigningKey::write`.
+    pub fn read(path: &Path) -> Result<Self, Problem> {
+        let contents = std::fs::read_to_string(path).map_err(Problem::IoErr)?;
+        let bytes = base64_url::decode(contents.trim())
+            .map_err(|_| Problem::InvalidKey(contents.trim().to_string()))?;
+        let secret = SecretKey::from_bytes(&bytes)
+            .map_err(|_| Problem::InvalidKey(contents.trim().to_string()))?;
+
+        Ok(Self::from_secret(secret))
+    }
+
+    /// Write the secret key to a new file, which should then be kept private!
+    /// On unix, only the owner can read and write the file.
+    ///
+    /// Fails with `io::ErrorKind::AlreadyExists` instead of overwriting an existing file,
+    /// since that might be another key.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let mut contents = base64_url::encode(self.0.secret.as_bytes());
+
+        contents.push('\n');
+
+        let mut options = std::fs::OpenOptions::new();
+
+        options.write(true).create_new(true);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+
+            options.mode(0o600);
+        }
+
+        let mut file = options.open(path)?;
+
+        file.write_all(contents.as_bytes())
+    }
+
+    /// The base64url-encoded public key, which people can use to verify signatures.
+    pub fn public_key(&self) -> String {
+        base64_url::encode(self.0.public.as_bytes())
+    }
+
+    /// Returns the base64url-encoded signature of the given content hash.
+    pub fn sign(&self, content_hash: &str) -> String {
+        base64_url::encode(&self.0.sign(content_hash.as_bytes()).to_bytes())
+    }
+}
+
+/// Succeeds if the given signature of the content hash was made by one of the trusted keys.
+pub fn verify(content_hash: &str, signature: &str, trusted_keys: &[String]) -> Result<(), Problem> {
+    let signature = base64_url::decode(signature.trim())
+        .ok()
+        .and_then(|bytes| Signature::try_from(bytes.as_slice()).ok())
+        .ok_or_else(|| Problem::InvalidSignature(signature.trim().to_string()))?;
+
+    for key in trusted_keys {
+        let public_key = base64_url::decode(key)
+            .ok()
+            .and_then(|bytes| PublicKey::from_bytes(&bytes).ok())
+            .ok_or_else(|| Problem::InvalidKey(key.to_string()))?;
+
+        if public_key
+            .verify(content_hash.as_bytes(), &signature)
+            .is_ok()
+        {
+            return Ok(());
+        }
+    }
+
+    Err(Problem::UntrustedSignature {
+        content_hash: content_hash.to_string(),
+    })
+}
+
+/// This looks up environment variables, so it should ideally be called once and then cached!
+pub fn trusted_keys() -> Vec<String> {
+    match std::env::var(TRUSTED_KEYS_ENV_VAR) {
+        Ok(keys) => keys
+            .split(',')
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(str::to_string)
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CONTENT_HASH: &str = "jDRlAFAA3738vu3-vMpLUoyxtA86Z7CaZneoOKrihbE";
+
+    #[test]
+    fn sign_and_verify() {
+        let key = SigningKey::generate();
+        let signature = key.sign(CONTENT_HASH);
+
+        assert!(verify(CONTENT_HASH, &signature, &[key.public_key()]).is_ok());
+    }
+
+    #[test]
+    fn wrong_content_hash() {
+        let key = SigningKey::generate();
+        let signature = key.sign(CONTENT_HASH);
+
+        assert!(matches!(
+            verify(
+                "k2RmaC0LU4A-VyQJDKFoRLstDT6iEnTVjmZRSPDbUMk",
+                &signature,
+                &[key.public_key()]
+            ),
+            Err(Problem::UntrustedSignature { .. })
+        ));
+    }
+
+    #[test]
+    fn untrusted_key() {
+        let key = SigningKey::generate();
+        let other_key = SigningKey::generate();
+        let signature = key.sign(CONTENT_HASH);
+
+        assert!(matches!(
+            verify(CONTENT_HASH, &signature, &[other_key.public_key()]),
+            Err(Problem::UntrustedSignature { .. })
+        ));
+    }
+
+    #[test]
+    fn key_file_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("roc-package.key");
+        let key = SigningKey::generate();
+
+        key.write(&path).unwrap();
+
+        let read_key = SigningKey::read(&path).unwrap();
+
+        assert_eq!(read_key.public_key(), key.public_key());
+        assert_eq!(read_key.sign(CONTENT_HASH), key.sign(CONTENT_HASH));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn key_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("roc-package.key");
+
+        SigningKey::generate().write(&path).unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn key_file_not_overwritten() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("roc-package.key");
+        let key = SigningKey::generate();
+
+        key.write(&path).unwrap();
+
+        let err = SigningKey::generate().write(&path).unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(
+            SigningKey::read(&path).unwrap().public_key(),
+            key.public_key()
+        );
+    }
+}
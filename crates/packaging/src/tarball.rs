@@ -1,3 +1,5 @@
+#[cfg(not(target_family = "wasm"))]
+use crate::manifest::{Manifest, MANIFEST_FILENAME};
 use brotli::enc::BrotliEncoderParams;
 use bumpalo::Bump;
 use flate2::write::GzEncoder;
@@ -64,8 +66,35 @@ impl<'a> TryFrom<&'a str> for Compression {
 pub fn build(path_to_main: &Path, compression: Compression) -> io::Result<String> {
     let mut archive_bytes = Vec::new();
 
-    write_archive(path_to_main, &mut archive_bytes)?;
+    write_archive(path_to_main, &[], &mut archive_bytes)?;
 
+    write_compressed(path_to_main, compression, archive_bytes)
+}
+
+/// Like `build`, but the archive also gets the given manifest in its root directory.
+#[cfg(not(target_family = "wasm"))]
+pub fn build_package(
+    path_to_main: &Path,
+    compression: Compression,
+    manifest: &Manifest,
+) -> io::Result<String> {
+    let mut archive_bytes = Vec::new();
+    let manifest_json = manifest.to_json();
+
+    write_archive(
+        path_to_main,
+        &[(MANIFEST_FILENAME, manifest_json.as_bytes())],
+        &mut archive_bytes,
+    )?;
+
+    write_compressed(path_to_main, compression, archive_bytes)
+}
+
+fn write_compressed(
+    path_to_main: &Path,
+    compression: Compression,
+    archive_bytes: Vec<u8>,
+) -> io::Result<String> {
     // Now that we have our compressed archive, get its BLAKE3 hash
     // and base64url encode it. Use base64url encoding because:
     // - It's more concise than hex encoding, so the URL can be shorter
@@ -111,7 +140,14 @@ pub fn build(path_to_main: &Path, compression: Compression) -> io::Result<String
 }
 
 /// Write an uncompressed tar archive to the given writer.
-fn write_archive<W: Write>(path: &Path, writer: W) -> io::Result<()> {
+///
+/// The archive only depends on the contents of the files, not on their timestamps, owners or
+/// the order in which the file system lists them, so building it twice gives the same hash.
+fn write_archive<W: Write>(
+    path: &Path,
+    extra_files: &[(&str, &[u8])],
+    writer: W,
+) -> io::Result<()> {
     let root_dir = if let Some(parent) = path.parent() {
         parent
     } else {
@@ -122,6 +158,16 @@ fn write_archive<W: Write>(path: &Path, writer: W) -> io::Result<()> {
         std::process::exit(1);
     };
     let mut builder = tar::Builder::new(writer);
+    builder.mode(tar::HeaderMode::Deterministic);
+
+    for (name, contents) in extra_files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+
+        builder.append_data(&mut header, name, *contents)?;
+    }
+
     let arena = Bump::new();
     let mut buf = Vec::new();
 
@@ -146,9 +192,13 @@ fn write_archive<W: Write>(path: &Path, writer: W) -> io::Result<()> {
         Header::Platform(PlatformHeader { imports: _, .. }) => {
             // Add all the prebuilt host files to the archive.
             // These should all be in the same directory as the platform module.
-            for entry in std::fs::read_dir(root_dir)? {
-                let path = entry?.path();
+            let mut paths = std::fs::read_dir(root_dir)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<io::Result<Vec<_>>>()?;
 
+            paths.sort();
+
+            for path in paths {
                 if [
                     // surgical linker format
                     Some("rh"),
@@ -223,12 +273,16 @@ fn add_dot_roc_files<W: Write>(
     root_dir: &Path,
     builder: &mut tar::Builder<W>,
 ) -> Result<(), io::Error> {
-    for entry in WalkDir::new(root_dir).into_iter().filter_entry(|entry| {
-        let path = entry.path();
+    for entry in WalkDir::new(root_dir)
+        .sort_by(|a, b| a.file_name().cmp(b.file_name()))
+        .into_iter()
+        .filter_entry(|entry| {
+            let path = entry.path();
 
-        // Ignore everything except directories and .roc files
-        path.is_dir() || path.extension().and_then(OsStr::to_str) == Some("roc")
-    }) {
+            // Ignore everything except directories and .roc files
+            path.is_dir() || path.extension().and_then(OsStr::to_str) == Some("roc")
+        })
+    {
         let entry = entry?;
         let path = entry.path();
 
@@ -250,7 +304,7 @@ fn add_dot_roc_files<W: Write>(
     Ok(())
 }
 
-fn read_header<'a>(
+pub(crate) fn read_header<'a>(
     arena: &'a Bump,
     buf: &'a mut Vec<u8>,
     path: &'a Path,
@@ -268,9 +322,16 @@ fn read_header<'a>(
     // (We can't use that for the parser state and still return Module<'a> unfortunately.)
     let arena_buf = bumpalo::collections::Vec::from_iter_in(buf.iter().copied(), arena);
     let parse_state = State::new(arena_buf.into_bump_slice());
-    let (module, _) = parse_header(arena, parse_state).unwrap_or_else(|_err| {
-        todo!(); // TODO report a nice error and exit 1 - or maybe just return Err, for better testability?
-    });
+    let (module, _) = parse_header(arena, parse_state).map_err(|err| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "{} does not have a valid module header: {:?}",
+                path.display(),
+                err
+            ),
+        )
+    })?;
 
     Ok(module)
 }
@@ -0,0 +1,219 @@
+//! Copy every package a module depends on - including the dependencies of those packages -
+//! into one directory, which can then be used as a mirror (see the `mirror` module) so
+//! that builds don't need internet access.
+use crate::cache::MAX_DOWNLOAD_BYTES;
+use crate::https::{Destination, PackageMetadata, Problem};
+use crate::mirror::{self, Mirror};
+use crate::signature::SIGNATURE_EXT;
+use crate::tarball::read_header;
+use bumpalo::Bump;
+use roc_parse::ast::{ExtractSpaces, Header};
+use roc_parse::header::To;
+use std::collections::{HashSet, VecDeque};
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Vendored {
+    pub url: String,
+    /// False if the vendor dir already had this package, so nothing was fetched.
+    pub fetched: bool,
+}
+
+/// Fetch the packages that root_module depends on, and all of their dependencies in turn,
+/// into vendor_dir as uncompressed `<content hash>.tar` files (plus signatures, if there are
+/// trusted keys). Packages get fetched via the given mirrors, just like when building.
+///
+/// Returns the URLs of all the packages, in the order they were discovered.
+pub fn vendor(
+    root_module: &Path,
+    vendor_dir: &Path,
+    mirrors: &[Mirror],
+    trusted_keys: &[String],
+) -> Result<Vec<Vendored>, Problem> {
+    fs::create_dir_all(vendor_dir).map_err(Problem::IoErr)?;
+
+    let mut queue: VecDeque<String> = package_urls(root_module)?.into();
+    let mut seen = HashSet::new();
+    let mut answer = Vec::new();
+
+    while let Some(url) = queue.pop_front() {
+        let PackageMetadata {
+            content_hash,
+            root_module_filename,
+            ..
+        } = PackageMetadata::try_from(url.as_str()).map_err(Problem::InvalidUrl)?;
+
+        if !seen.insert(content_hash.to_string()) {
+            continue;
+        }
+
+        let tarball_path = vendor_dir.join(format!("{content_hash}.tar"));
+        let fetched = !tarball_path.exists();
+
+        if fetched {
+            // Fetch into a temporary file, so that an interrupted or invalid download
+            // never looks like a vendored package.
+            let partial_path = vendor_dir.join(format!("{content_hash}.tar.partial"));
+            let verified = match mirror::fetch(
+                &url,
+                content_hash,
+                Destination::Tarball(&partial_path),
+                mirrors,
+                MAX_DOWNLOAD_BYTES,
+                trusted_keys,
+            ) {
+                Ok(verified) => verified,
+                Err(problem) => {
+                    let _ = fs::remove_file(&partial_path);
+
+                    return Err(problem);
+                }
+            };
+
+            if let Some(signature) = verified.signature {
+                fs::write(
+                    vendor_dir.join(format!("{content_hash}.tar{SIGNATURE_EXT}")),
+                    signature,
+                )
+                .map_err(Problem::IoErr)?;
+            }
+
+            fs::rename(&partial_path, &tarball_path).map_err(Problem::IoErr)?;
+        }
+
+        // Look in the package's root module for the packages it depends on.
+        let tempdir = tempfile::tempdir().map_err(Problem::IoErr)?;
+        let file = fs::File::open(&tarball_path).map_err(Problem::IoErr)?;
+
+        tar::Archive::new(file)
+            .unpack(tempdir.path())
+            .map_err(Problem::IoErr)?;
+
+        queue.extend(package_urls(
+            &tempdir
+                .path()
+                .join(root_module_filename.unwrap_or("main.roc")),
+        )?);
+
+        answer.push(Vendored { url, fetched });
+    }
+
+    Ok(answer)
+}
+
+/// The URLs of the packages in the given module's header. Local paths are skipped,
+/// since there's nothing to vendor for those.
+fn package_urls(module_path: &Path) -> Result<Vec<String>, Problem> {
+    let arena = Bump::new();
+    let mut buf = Vec::new();
+    let module = read_header(&arena, &mut buf, module_path).map_err(Problem::IoErr)?;
+    let (entries, opt_platform) = match module.header {
+        // An app can name its platform's URL directly, instead of via a shorthand.
+        Header::App(header) => (
+            header
+                .packages
+                .map(|packages| packages.item.items)
+                .unwrap_or_default(),
+            match header.provides.to.value {
+                To::NewPackage(package_name) => Some(package_name.as_str()),
+                To::ExistingPackage(_) => None,
+            },
+        ),
+        Header::Package(header) => (header.packages.item.items, None),
+        Header::Platform(header) => (header.packages.item.items, None),
+        Header::Interface(_) | Header::Hosted(_) => (Default::default(), None),
+    };
+
+    let mut urls: Vec<String> = entries
+        .iter()
+        .map(|loc_entry| {
+            let entry = loc_entry.value.extract_spaces().item;
+
+            entry.package_name.value.as_str().to_string()
+        })
+        .chain(opt_platform.map(str::to_string))
+        .collect();
+
+    urls.retain(|url| url.starts_with("https://"));
+
+    Ok(urls)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Write a tarball containing a main.roc with the given contents into dir,
+    /// and return its URL.
+    fn publish(dir: &Path, main_roc: &str) -> String {
+        let mut builder = tar::Builder::new(Vec::new());
+        let mut header = tar::Header::new_gnu();
+
+        header.set_size(main_roc.len() as u64);
+        header.set_mode(0o644);
+        builder
+            .append_data(&mut header, "main.roc", main_roc.as_bytes())
+            .unwrap();
+
+        let bytes = builder.into_inner().unwrap();
+        let hash = base64_url::encode(blake3::hash(&bytes).as_bytes());
+
+        fs::write(dir.join(format!("{hash}.tar")), bytes).unwrap();
+
+        format!("https://example.com/packages/{hash}.tar.br")
+    }
+
+    #[test]
+    fn vendors_transitive_packages() {
+        let mirror_dir = tempfile::tempdir().unwrap();
+        let vendor_dir = tempfile::tempdir().unwrap();
+        let app_dir = tempfile::tempdir().unwrap();
+
+        let parser_url = publish(
+            mirror_dir.path(),
+            "package \"parser\"\n    exposes [Parser]\n    packages {}\n",
+        );
+        let json_url = publish(
+            mirror_dir.path(),
+            &format!(
+                "package \"json\"\n    exposes [Json]\n    packages {{ parser: \"{parser_url}\" }}\n"
+            ),
+        );
+        let app_path = app_dir.path().join("main.roc");
+
+        fs::write(
+            &app_path,
+            format!(
+                "app \"example\"\n    packages {{ pf: \"../platform/main.roc\", json: \"{json_url}\" }}\n    imports []\n    provides [main] to pf\n"
+            ),
+        )
+        .unwrap();
+
+        let mirrors = mirror::parse_mirrors(
+            &format!("* = {}", mirror_dir.path().display()),
+            Path::new("."),
+        )
+        .unwrap();
+        let vendored = vendor(&app_path, vendor_dir.path(), &mirrors, &[]).unwrap();
+
+        assert_eq!(
+            vendored,
+            vec![
+                Vendored {
+                    url: json_url.clone(),
+                    fetched: true,
+                },
+                Vendored {
+                    url: parser_url.clone(),
+                    fetched: true,
+                },
+            ]
+        );
+
+        // Vendoring again shouldn't need to fetch anything.
+        let vendored = vendor(&app_path, vendor_dir.path(), &[], &[]).unwrap();
+
+        assert!(vendored.iter().all(|vendored| !vendored.fetched));
+    }
+}
@@ -1,44 +1,64 @@
 use bumpalo::Bump;
 use inkwell::context::Context;
 use libloading::Library;
-use roc_build::link::llvm_module_to_dylib;
+use roc_build::link::{llvm_module_to_dylib, LinkType, LinkingStrategy};
+use roc_build::program::{
+    build_str, report_problems_typechecked, standard_load_config, BuildFileError, BuildOrdering,
+    BuiltFile, CodeGenBackend, CodeGenOptions,
+};
 use roc_collections::all::MutSet;
 use roc_gen_llvm::llvm::build::LlvmBackendMode;
 use roc_gen_llvm::llvm::externs::add_default_roc_externs;
 use roc_gen_llvm::{run_jit_function, run_jit_function_dynamic_type};
-use roc_load::{EntryPoint, MonomorphizedModule};
+use roc_load::{EntryPoint, LoadingProblem, MonomorphizedModule, Threading};
 use roc_mono::ir::OptLevel;
 use roc_mono::layout::STLayoutInterner;
+use roc_packaging::cache::{self, RocCacheDir};
 use roc_parse::ast::Expr;
 use roc_repl_eval::eval::jit_to_ast;
-use roc_repl_eval::gen::{compile_to_mono, format_answer, Problems, ReplOutput};
+use roc_repl_eval::gen::{compile_to_mono, format_answer, Problems, ReplImports, ReplOutput};
 use roc_repl_eval::{ReplApp, ReplAppMemory};
-use roc_reporting::report::DEFAULT_PALETTE;
+use roc_reporting::report::{RenderTarget, DEFAULT_PALETTE};
+use roc_reporting::warnings::WarningConfig;
 use roc_std::RocStr;
 use roc_target::TargetInfo;
 use roc_types::pretty_print::{name_and_print_var, DebugPrint};
 use roc_types::subs::Subs;
+use std::process::Command;
+use std::time::{Duration, Instant};
 use target_lexicon::Triple;
 
+/// How long it took to compile and then evaluate a REPL input, for `:time`
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EvalTimings {
+    pub compile: Duration,
+    pub eval: Duration,
+}
+
 pub fn gen_and_eval_llvm<'a, I: Iterator<Item = &'a str>>(
+    imports: &ReplImports,
     defs: I,
     src: &str,
     target: Triple,
     opt_level: OptLevel,
-) -> (Option<ReplOutput>, Problems) {
+) -> (Option<ReplOutput>, Problems, EvalTimings) {
     let arena = Bump::new();
     let target_info = TargetInfo::from(&target);
+    let compile_start = Instant::now();
+    let mut timings = EvalTimings::default();
 
     let mut loaded;
     let problems;
 
-    match compile_to_mono(&arena, defs, src, target_info, DEFAULT_PALETTE) {
+    match compile_to_mono(&arena, imports, defs, src, target_info, DEFAULT_PALETTE) {
         (Some(mono), probs) => {
             loaded = mono;
             problems = probs;
         }
         (None, probs) => {
-            return (None, probs);
+            timings.compile = compile_start.elapsed();
+
+            return (None, probs, timings);
         }
     };
 
@@ -67,7 +87,9 @@ pub fn gen_and_eval_llvm<'a, I: Iterator<Item = &'a str>>(
             let empty_vec: Vec<String> = Vec::new(); // rustc can't infer the type of this Vec.
             debug_assert_ne!(problems.errors, empty_vec, "Got no errors but also no valid layout for the generated main function in the repl!");
 
-            return (None, problems);
+            timings.compile = compile_start.elapsed();
+
+            return (None, problems, timings);
         }
     };
 
@@ -78,6 +100,9 @@ pub fn gen_and_eval_llvm<'a, I: Iterator<Item = &'a str>>(
 
     let mut app = CliApp { lib };
 
+    timings.compile = compile_start.elapsed();
+
+    let eval_start = Instant::now();
     let expr = jit_to_ast(
         &arena,
         &mut app,
@@ -91,15 +116,98 @@ pub fn gen_and_eval_llvm<'a, I: Iterator<Item = &'a str>>(
     );
     let expr_str = format_answer(&arena, expr).to_string();
 
+    timings.eval = eval_start.elapsed();
+
     (
         Some(ReplOutput {
             expr: expr_str,
             expr_type: expr_type_str,
         }),
         problems,
+        timings,
     )
 }
 
+/// Build an app whose `main` is this expr, on the platform of the app the REPL was started in,
+/// and run it. Whatever it prints goes straight to the terminal. Returns its exit code.
+///
+/// Building the platform's host is only needed the first time; after that, pass `host_built`.
+pub fn run_on_platform<'a, I: Iterator<Item = &'a str>>(
+    imports: &ReplImports,
+    defs: I,
+    src: &str,
+    target: Triple,
+    host_built: bool,
+) -> Result<i32, String> {
+    let app_dir = imports.app_dir().ok_or_else(|| {
+        "The REPL was not started in an app, so there is no platform to run this on".to_string()
+    })?;
+
+    // The executable is only good for this one input, so it goes in a directory of its own
+    // rather than cluttering up the app's.
+    let output_dir = tempfile::tempdir()
+        .map_err(|err| format!("I could not create a temporary directory: {}", err))?;
+
+    let arena = Bump::new();
+    let app_src = imports
+        .platform_app_src("repl", defs, src)
+        .ok_or_else(|| "I don't know how to run this on the app's platform".to_string())?;
+
+    let code_gen_options = CodeGenOptions {
+        backend: CodeGenBackend::Llvm(LlvmBackendMode::BinaryDev),
+        opt_level: OptLevel::Development,
+        emit_debug_info: false,
+    };
+    let load_config = standard_load_config(
+        &target,
+        BuildOrdering::BuildIfChecks,
+        Threading::AllAvailable,
+    );
+
+    // The app's source never gets written to disk, but it pretends to be next to the app the REPL
+    // was started in, so that relative paths in its header still resolve.
+    let built = build_str(
+        &arena,
+        &target,
+        app_dir.join("Repl.roc"),
+        arena.alloc_str(&app_src),
+        output_dir.path(),
+        code_gen_options,
+        LinkType::Executable,
+        // The surgical linker needs a preprocessed host, which not every platform ships with.
+        LinkingStrategy::Legacy,
+        host_built,
+        RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
+        load_config,
+        &WarningConfig::default(),
+    );
+
+    match built {
+        Ok(BuiltFile { binary_path, .. }) => match Command::new(&binary_path).status() {
+            Ok(status) => Ok(status.code().unwrap_or(1)),
+            Err(err) => Err(format!(
+                "I could not run {}: {}",
+                binary_path.display(),
+                err
+            )),
+        },
+        Err(BuildFileError::ErrorModule { mut module, .. }) => {
+            // This prints the problems, so all that's left to say is that we gave up.
+            report_problems_typechecked(
+                &mut module,
+                RenderTarget::ColorTerminal,
+                &WarningConfig::default(),
+            );
+
+            Err("I could not build this to run it on the app's platform.".to_string())
+        }
+        Err(BuildFileError::LoadingProblem(LoadingProblem::FormattedReport(report))) => Err(report),
+        Err(BuildFileError::LoadingProblem(problem)) => {
+            Err(format!("I could not build this to run it: {:?}", problem))
+        }
+    }
+}
+
 struct CliApp {
     lib: Library,
 }
@@ -222,6 +330,7 @@ fn mono_module_to_dylib<'a>(
         mode: LlvmBackendMode::GenTest, // so roc_panic is generated
         // important! we don't want any procedures to get the C calling convention
         exposed_to_host: MutSet::default(),
+        debug_sources: Default::default(),
     };
 
     // Add roc_alloc, roc_realloc, and roc_dealloc, since the repl has no
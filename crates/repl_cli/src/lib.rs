@@ -3,9 +3,10 @@ mod cli_gen;
 mod colors;
 pub mod repl_state;
 
-use colors::{BLUE, END_COL, PINK};
+use colors::{BLUE, END_COL, GREEN, PINK};
 use const_format::concatcp;
 use repl_state::ReplState;
+use std::path::Path;
 
 use crate::repl_state::PROMPT;
 
@@ -25,17 +26,36 @@ pub const WELCOME_MESSAGE: &str = concatcp!(
 // TODO add link to repl tutorial(does not yet exist).
 pub const SHORT_INSTRUCTIONS: &str = "Enter an expression, or :help, or :q to quit.\n\n";
 
-pub fn main() -> i32 {
+/// Run the REPL. If given the path to an app, the REPL starts out with the app's imports and
+/// packages in scope.
+pub fn main(opt_app: Option<&Path>) -> i32 {
     use rustyline::error::ReadlineError;
     use rustyline::Editor;
 
+    let repl_helper = match opt_app {
+        Some(app) => match ReplState::with_app(app) {
+            Ok(repl_state) => repl_state,
+            Err(message) => {
+                eprintln!("\n{}\n", message);
+
+                return 1;
+            }
+        },
+        None => ReplState::new(),
+    };
+
     // To debug rustyline:
     // <UNCOMMENT> env_logger::init();
     // <RUN WITH:> RUST_LOG=rustyline=debug cargo run repl 2> debug.log
-    print!("{}{}", WELCOME_MESSAGE, SHORT_INSTRUCTIONS);
+    print!("{}", WELCOME_MESSAGE);
+
+    if let Some(app) = opt_app {
+        println!("In the scope of {}{}{}\n", GREEN, app.display(), END_COL);
+    }
+
+    print!("{}", SHORT_INSTRUCTIONS);
 
     let mut editor = Editor::<ReplState>::new();
-    let repl_helper = ReplState::new();
     editor.set_helper(Some(repl_helper));
 
     loop {
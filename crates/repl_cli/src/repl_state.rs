@@ -1,4 +1,4 @@
-use crate::cli_gen::gen_and_eval_llvm;
+use crate::cli_gen::{gen_and_eval_llvm, run_on_platform, EvalTimings};
 use crate::colors::{BLUE, END_COL, GREEN, PINK};
 use bumpalo::Bump;
 use const_format::concatcp;
@@ -12,11 +12,16 @@ use roc_parse::parser::{EWhen, Either};
 use roc_parse::state::State;
 use roc_parse::{join_alias_to_body, join_ann_to_body};
 use roc_region::all::Loc;
-use roc_repl_eval::gen::{Problems, ReplOutput};
+use roc_repl_eval::gen::{
+    compile_to_types, def_type, expr_type, Problems, ReplImports, ReplOutput,
+};
+use roc_reporting::report::DEFAULT_PALETTE;
+use roc_target::TargetInfo;
 use rustyline::highlight::{Highlighter, PromptInfo};
 use rustyline::validate::{self, ValidationContext, ValidationResult, Validator};
 use rustyline_derive::{Completer, Helper, Hinter};
 use std::borrow::Cow;
+use std::path::Path;
 use target_lexicon::Triple;
 
 pub const PROMPT: &str = concatcp!(BLUE, "»", END_COL, " ");
@@ -26,6 +31,10 @@ pub const CONT_PROMPT: &str = concatcp!(BLUE, "…", END_COL, " ");
 /// e.g. if the prefix is "val" then the first expr you enter will be named "val1"
 pub const AUTO_VAR_PREFIX: &str = "val";
 
+/// What goes between a redefined def's name and a number to give the old def a new name,
+/// e.g. "x" becomes "xShadowed1" after `x` is redefined
+const SHADOWED_INFIX: &str = "Shadowed";
+
 // TODO add link to repl tutorial(does not yet exist).
 pub const TIPS: &str = concatcp!(
     "\nEnter an expression to evaluate, or a definition (like ",
@@ -59,21 +68,55 @@ pub const TIPS: &str = concatcp!(
     BLUE,
     "  - ",
     END_COL,
-    ":help"
+    ":help\n\nCommands:\n\n",
+    BLUE,
+    "  :type <expr>",
+    END_COL,
+    "       show an expression's type without evaluating it\n",
+    BLUE,
+    "  :load <file.roc>",
+    END_COL,
+    "   bring what an interface module exposes into scope\n",
+    BLUE,
+    "  :defs",
+    END_COL,
+    "              list the current definitions and their types\n",
+    BLUE,
+    "  :reset",
+    END_COL,
+    "             forget all definitions\n",
+    BLUE,
+    "  :time",
+    END_COL,
+    "              turn timing of compilation and evaluation on or off"
 );
 
 #[derive(Debug, Clone, PartialEq)]
 struct PastDef {
     ident: String,
     src: String,
+    /// Every identifier that appears in the src. This can include things the def doesn't actually
+    /// refer to (like record field names), but never leaves out anything it does refer to.
+    mentions: MutSet<String>,
+    /// Whether this def has since been redefined. It gets a new name so that the defs which were
+    /// defined in terms of it keep using it, but it can no longer be referred to directly.
+    shadowed: bool,
 }
 
 #[derive(Completer, Helper, Hinter)]
 pub struct ReplState {
     validator: InputValidator,
+    /// Past defs are kept as source, not as solved types or compiled procs: `roc_load` can only
+    /// compile a module from scratch. So every input is parsed, type checked and compiled again
+    /// together with the past defs it can refer to (see [ReplState::relevant_defs]). An input
+    /// that uses many past defs still pays for compiling all of them.
     past_defs: Vec<PastDef>,
     past_def_idents: MutSet<String>,
     last_auto_ident: u64,
+    imports: ReplImports,
+    show_timings: bool,
+    /// Whether the host of the app's platform has been built yet, for running tasks
+    host_built: bool,
 }
 
 impl Default for ReplState {
@@ -89,9 +132,37 @@ impl ReplState {
             past_defs: Default::default(),
             past_def_idents: Default::default(),
             last_auto_ident: 0,
+            imports: Default::default(),
+            show_timings: false,
+            host_built: false,
         }
     }
 
+    /// Start in the scope of the app at this path, with its imports and packages available.
+    pub fn with_app(path: &Path) -> Result<Self, String> {
+        let imports = ReplImports::from_app(path)?;
+
+        // Make sure the app's header works before every input starts copying it.
+        let arena = Bump::new();
+        let (_, problems) = compile_to_types(
+            &arena,
+            &imports,
+            std::iter::empty(),
+            "{}",
+            TargetInfo::from(&Triple::host()),
+            DEFAULT_PALETTE,
+        );
+
+        if !problems.errors.is_empty() {
+            return Err(problems.errors.join("\n"));
+        }
+
+        Ok(Self {
+            imports,
+            ..Self::new()
+        })
+    }
+
     pub fn step(&mut self, line: &str, dimensions: Option<(usize, usize)>) -> Result<String, i32> {
         let arena = Bump::new();
 
@@ -124,13 +195,32 @@ impl ReplState {
                 // TODO add link to repl tutorial(does not yet exist).
                 Ok(TIPS.to_string())
             }
+            ParseOutcome::TypeOf(expr) => Ok(self.type_of(expr)),
+            ParseOutcome::Load(path) => Ok(self.load(path)),
+            ParseOutcome::Reset => {
+                self.past_defs.clear();
+                self.past_def_idents.clear();
+                self.last_auto_ident = 0;
+
+                Ok("Forgot all definitions.".to_string())
+            }
+            ParseOutcome::Defs => Ok(self.defs()),
+            ParseOutcome::Time => {
+                self.show_timings = !self.show_timings;
+
+                if self.show_timings {
+                    Ok("Timing is on.".to_string())
+                } else {
+                    Ok("Timing is off.".to_string())
+                }
+            }
             ParseOutcome::Exit => Err(0),
         }
     }
 
     pub fn eval_and_format(&mut self, src: &str, dimensions: Option<(usize, usize)>) -> String {
         let arena = Bump::new();
-        let pending_past_def;
+        let pending_past_def: Option<(String, String)>;
         let mut opt_var_name;
         let src = match parse_src(&arena, src) {
             ParseOutcome::Expr(_) | ParseOutcome::Incomplete | ParseOutcome::SyntaxErr => {
@@ -238,27 +328,48 @@ impl ReplState {
                 // can be evaluated as expressions.
                 return String::new();
             }
-            ParseOutcome::Empty | ParseOutcome::Help | ParseOutcome::Exit => unreachable!(),
+            ParseOutcome::Empty
+            | ParseOutcome::Help
+            | ParseOutcome::TypeOf(_)
+            | ParseOutcome::Load(_)
+            | ParseOutcome::Reset
+            | ParseOutcome::Defs
+            | ParseOutcome::Time
+            | ParseOutcome::Exit => unreachable!(),
         };
 
         // Record e.g. "val1" as a past def, unless our input was exactly the name of
         // an existing identifer (e.g. I just typed "val1" into the prompt - there's no
         // need to reassign "val1" to "val2" just because I wanted to see what its value was!)
-        let (output, problems) =
+        if pending_past_def.is_none() {
+            if let Some(output) = self.try_run_on_platform(src) {
+                return output;
+            }
+        }
+
+        // Set aside the def this one replaces (if any) first, so that this doesn't refer to it.
+        if let Some((ident, _)) = &pending_past_def {
+            self.shadow_past_def(ident);
+        }
+
+        let relevant_defs = self.relevant_defs(src);
+        let (output, problems, timings) =
             match opt_var_name.or_else(|| self.past_def_idents.get(src.trim()).cloned()) {
                 Some(existing_ident) => {
                     opt_var_name = Some(existing_ident);
 
                     gen_and_eval_llvm(
-                        self.past_defs.iter().map(|def| def.src.as_str()),
+                        &self.imports,
+                        relevant_defs.into_iter(),
                         src,
                         Triple::host(),
                         OptLevel::Normal,
                     )
                 }
                 None => {
-                    let (output, problems) = gen_and_eval_llvm(
-                        self.past_defs.iter().map(|def| def.src.as_str()),
+                    let (output, problems, timings) = gen_and_eval_llvm(
+                        &self.imports,
+                        relevant_defs.into_iter(),
                         src,
                         Triple::host(),
                         OptLevel::Normal,
@@ -276,7 +387,7 @@ impl ReplState {
                         opt_var_name = None;
                     }
 
-                    (output, problems)
+                    (output, problems, timings)
                 }
             };
 
@@ -284,7 +395,158 @@ impl ReplState {
             self.add_past_def(ident, src);
         }
 
-        format_output(output, problems, opt_var_name, dimensions)
+        let mut buf = format_output(output, problems, opt_var_name, dimensions);
+
+        if self.show_timings {
+            buf.push_str(&format_timings(timings));
+        }
+
+        buf
+    }
+
+    /// If the REPL was started in an app, and this expr is something the app's platform can run
+    /// (like a `Task`), run it on the platform rather than evaluating it here.
+    fn try_run_on_platform(&mut self, expr: &str) -> Option<String> {
+        self.imports.app_dir()?;
+
+        let arena = Bump::new();
+        let (opt_loaded, problems) = compile_to_types(
+            &arena,
+            &self.imports,
+            self.relevant_defs(expr).into_iter(),
+            expr,
+            TargetInfo::from(&Triple::host()),
+            DEFAULT_PALETTE,
+        );
+
+        // If it doesn't compile, evaluating it as usual will report why.
+        if !problems.errors.is_empty() {
+            return None;
+        }
+
+        let expr_type = expr_type(&mut opt_loaded?)?;
+
+        if !self.imports.runs_on_platform(&expr_type) {
+            return None;
+        }
+
+        let result = run_on_platform(
+            &self.imports,
+            self.relevant_defs(expr).into_iter(),
+            expr,
+            Triple::host(),
+            self.host_built,
+        );
+
+        let output = match result {
+            Ok(exit_code) => {
+                self.host_built = true;
+
+                if exit_code == 0 {
+                    String::new()
+                } else {
+                    format!("\n{PINK}The task exited with code {exit_code}{END_COL}\n")
+                }
+            }
+            Err(message) => format!("\n{}\n", message),
+        };
+
+        Some(output)
+    }
+
+    /// `:type <expr>` - typecheck the expr, in the scope of the past defs, but don't evaluate it.
+    fn type_of(&self, expr: &str) -> String {
+        if expr.is_empty() {
+            return format!("\nUsage: {BLUE}:type <expr>{END_COL}\n");
+        }
+
+        let arena = Bump::new();
+        let (opt_loaded, problems) = compile_to_types(
+            &arena,
+            &self.imports,
+            self.relevant_defs(expr).into_iter(),
+            expr,
+            TargetInfo::from(&Triple::host()),
+            DEFAULT_PALETTE,
+        );
+        let output = opt_loaded.and_then(|mut loaded| {
+            Some(ReplOutput {
+                expr: expr.trim().to_string(),
+                expr_type: expr_type(&mut loaded)?,
+            })
+        });
+
+        format_output(output, problems, None, None)
+    }
+
+    /// `:load <file.roc>` - bring what an interface module exposes into scope for future inputs.
+    fn load(&mut self, path: &str) -> String {
+        if path.is_empty() {
+            return format!("\nUsage: {BLUE}:load <file.roc>{END_COL}\n");
+        }
+
+        let mut imports = self.imports.clone();
+        let module_name = match imports.add(Path::new(path.trim_matches('"'))) {
+            Ok(module_name) => module_name,
+            Err(message) => return format!("\n{}\n", message),
+        };
+
+        // Make sure the module compiles before we start importing it into every future input.
+        let arena = Bump::new();
+        let (_, problems) = compile_to_types(
+            &arena,
+            &imports,
+            self.past_defs.iter().map(|def| def.src.as_str()),
+            "{}",
+            TargetInfo::from(&Triple::host()),
+            DEFAULT_PALETTE,
+        );
+
+        if !problems.errors.is_empty() {
+            return format_output(None, problems, None, None);
+        }
+
+        self.imports = imports;
+
+        format!("\nLoaded {GREEN}{module_name}{END_COL}\n")
+    }
+
+    /// `:defs` - list the current definitions, with the types of the values.
+    fn defs(&self) -> String {
+        if self.past_defs.is_empty() {
+            return "\nThere are no definitions yet.\n".to_string();
+        }
+
+        let arena = Bump::new();
+        let (opt_loaded, problems) = compile_to_types(
+            &arena,
+            &self.imports,
+            self.past_defs.iter().map(|def| def.src.as_str()),
+            "{}",
+            TargetInfo::from(&Triple::host()),
+            DEFAULT_PALETTE,
+        );
+        let mut loaded = match opt_loaded {
+            Some(loaded) => loaded,
+            None => return format_output(None, problems, None, None),
+        };
+        let mut buf = String::new();
+
+        for def in self.past_defs.iter().filter(|def| !def.shadowed) {
+            let line = match def_type(&mut loaded, &def.ident) {
+                Some(def_type) => format!("{}{PINK} : {END_COL}{}", def.ident, def_type),
+                // Type definitions and standalone annotations have no value to show the type of,
+                // so show how they were defined instead.
+                None => def.src.trim().to_string(),
+            };
+
+            buf.push('\n');
+            buf.push_str(&line);
+        }
+
+        buf.push('\n');
+
+        buf
     }
 
     fn next_auto_ident(&mut self) -> u64 {
@@ -293,14 +555,238 @@ impl ReplState {
     }
 
     fn add_past_def(&mut self, ident: String, src: String) {
+        self.shadow_past_def(&ident);
+
         let existing_idents = &mut self.past_def_idents;
 
         existing_idents.insert(ident.clone());
 
-        self.past_defs.push(PastDef { ident, src });
+        let mentions = mentioned_idents(&src);
+
+        self.past_defs.push(PastDef {
+            ident,
+            src,
+            mentions,
+            shadowed: false,
+        });
+    }
+
+    /// Make way for a new def with this name. Past defs that were defined in terms of the old one
+    /// keep their values: after `x = 5`, `y = x + 1` and `x = "five"`, `y` is still 6. To make
+    /// that work, the old def (and every mention of it) gets renamed to something nobody else
+    /// can refer to. If nothing mentions the old def, it's simply dropped.
+    fn shadow_past_def(&mut self, ident: &str) {
+        let index = match self
+            .past_defs
+            .iter()
+            .position(|def| !def.shadowed && def.ident == ident)
+        {
+            Some(index) => index,
+            None => return,
+        };
+        let has_dependents = self
+            .past_defs
+            .iter()
+            .any(|def| def.ident != ident && def.mentions.contains(ident));
+
+        if !has_dependents {
+            self.past_defs.remove(index);
+
+            return;
+        }
+
+        let mut suffix = 1;
+        let new_ident = loop {
+            let candidate = format!("{ident}{SHADOWED_INFIX}{suffix}");
+
+            if !self
+                .past_defs
+                .iter()
+                .any(|def| def.mentions.contains(&candidate))
+            {
+                break candidate;
+            }
+
+            suffix += 1;
+        };
+
+        for def in self.past_defs.iter_mut() {
+            if def.mentions.contains(ident) {
+                def.src = rename_ident(&def.src, ident, &new_ident);
+                def.mentions = mentioned_idents(&def.src);
+            }
+        }
+
+        let def = &mut self.past_defs[index];
+
+        def.ident = new_ident;
+        def.shadowed = true;
+    }
+
+    /// The past defs which the given source could refer to, either directly or through other past
+    /// defs, in the order they were defined. Only these get compiled along with the source, so an
+    /// input takes about as long to compile after 40 defs as it did after 1 (unless it really
+    /// does use all 40). This prunes what gets recompiled; nothing from compiling earlier inputs
+    /// is reused.
+    fn relevant_defs(&self, src: &str) -> Vec<&str> {
+        let mut mentioned = mentioned_idents(src);
+        let mut relevant = vec![false; self.past_defs.len()];
+        let mut changed = true;
+
+        // A type def can mention a value def that was defined after it (e.g. an ability
+        // implementation) so keep going until nothing new turns out to be relevant.
+        while changed {
+            changed = false;
+
+            for (index, def) in self.past_defs.iter().enumerate() {
+                // Type defs can be used without mentioning their names (e.g. by calling one
+                // of an ability's members) so they're always relevant.
+                let is_type_def = def.ident.starts_with(char::is_uppercase);
+
+                if !relevant[index] && (is_type_def || mentioned.contains(&def.ident)) {
+                    relevant[index] = true;
+                    changed = true;
+
+                    mentioned.extend(def.mentions.iter().cloned());
+                }
+            }
+        }
+
+        self.past_defs
+            .iter()
+            .zip(relevant)
+            .filter(|(_, relevant)| *relevant)
+            .map(|(def, _)| def.src.as_str())
+            .collect()
     }
 }
 
+/// Every identifier-like word in the source, e.g. `x`, `List`, `map` and `y` in `List.map x y`.
+fn mentioned_idents(src: &str) -> MutSet<String> {
+    src.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|word| word.starts_with(char::is_alphabetic))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Rename every reference to the identifier `from` in the source, leaving alone anything that just
+/// happens to have the same name: record field names, field accesses like `.x`, module names,
+/// string contents and comments. Fields which are given by punning (`{ x }`) are expanded so that
+/// they keep their name (`{ x: renamed }`).
+fn rename_ident(src: &str, from: &str, to: &str) -> String {
+    #[derive(PartialEq)]
+    enum Nesting {
+        /// `(` or `[`
+        Group,
+        /// `{`, which can contain record fields
+        Braces,
+        /// The `\(` of string interpolation
+        Interpolation,
+        /// The contents of a string; `true` if it's a `"""` string
+        Str(bool),
+    }
+
+    let is_ident_char = |c: char| c.is_alphanumeric() || c == '_';
+    let next_non_space = |rest: &str| rest.trim_start().chars().next();
+    let prev_non_space = |before: &str| before.trim_end().chars().last();
+
+    let mut answer = String::with_capacity(src.len());
+    let mut stack: Vec<Nesting> = Vec::new();
+    let mut rest = src;
+
+    while let Some(c) = rest.chars().next() {
+        let (token, after) = if let Some(Nesting::Str(triple)) = stack.last() {
+            let triple = *triple;
+
+            if rest.starts_with("\\(") {
+                stack.push(Nesting::Interpolation);
+
+                rest.split_at(2)
+            } else if c == '\\' {
+                // An escape sequence, e.g. `\"`
+                let len = rest[1..].chars().next().map_or(0, char::len_utf8);
+
+                rest.split_at(1 + len)
+            } else if triple && rest.starts_with("\"\"\"") {
+                stack.pop();
+
+                rest.split_at(3)
+            } else {
+                if !triple && c == '"' {
+                    stack.pop();
+                }
+
+                rest.split_at(c.len_utf8())
+            }
+        } else if rest.starts_with("\"\"\"") {
+            stack.push(Nesting::Str(true));
+
+            rest.split_at(3)
+        } else if c == '"' {
+            stack.push(Nesting::Str(false));
+
+            rest.split_at(1)
+        } else if c == '#' {
+            rest.split_at(rest.find('\n').unwrap_or(rest.len()))
+        } else if c == '\'' {
+            // A character literal, e.g. 'x' or '\''
+            let len = match rest[1..].strip_prefix('\\') {
+                Some(escaped) => {
+                    let escaped_len = escaped.chars().next().map_or(0, char::len_utf8);
+
+                    escaped[escaped_len..]
+                        .find('\'')
+                        .map(|index| index + escaped_len + 3)
+                }
+                None => rest[1..].find('\'').map(|index| index + 2),
+            };
+
+            rest.split_at(len.unwrap_or(rest.len()))
+        } else if is_ident_char(c) {
+            let len = rest.find(|c| !is_ident_char(c)).unwrap_or(rest.len());
+            let (word, after) = rest.split_at(len);
+            let before = &src[..src.len() - rest.len()];
+            let in_braces = stack.last() == Some(&Nesting::Braces);
+            let is_field_access = before.ends_with('.');
+            let is_module_name = word.starts_with(char::is_uppercase) && after.starts_with('.');
+            let is_field_name = in_braces && matches!(next_non_space(after), Some(':' | '?'));
+            let is_pun = in_braces
+                && matches!(prev_non_space(before), Some('{' | ','))
+                && matches!(next_non_space(after), Some(',' | '}'));
+
+            if word != from || is_field_access || is_module_name || is_field_name {
+                answer.push_str(word);
+            } else if is_pun {
+                answer.push_str(word);
+                answer.push_str(": ");
+                answer.push_str(to);
+            } else {
+                answer.push_str(to);
+            }
+
+            rest = after;
+
+            continue;
+        } else {
+            match c {
+                '(' | '[' => stack.push(Nesting::Group),
+                '{' => stack.push(Nesting::Braces),
+                ')' | ']' | '}' => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+
+            rest.split_at(c.len_utf8())
+        };
+
+        answer.push_str(token);
+        rest = after;
+    }
+
+    answer
+}
+
 #[derive(Debug, PartialEq)]
 enum ParseOutcome<'a> {
     ValueDef(ValueDef<'a>),
@@ -310,13 +796,43 @@ enum ParseOutcome<'a> {
     SyntaxErr,
     Empty,
     Help,
+    TypeOf(&'a str),
+    Load(&'a str),
+    Reset,
+    Defs,
+    Time,
     Exit,
 }
 
+/// If the line is the given command (e.g. `:type`), the argument which follows it
+fn command_arg<'a>(line: &'a str, command: &str) -> Option<&'a str> {
+    let (first, rest) = match line.find(char::is_whitespace) {
+        Some(index) => line.split_at(index),
+        None => (line, ""),
+    };
+
+    if first.eq_ignore_ascii_case(command) {
+        Some(rest.trim())
+    } else {
+        None
+    }
+}
+
 fn parse_src<'a>(arena: &'a Bump, line: &'a str) -> ParseOutcome<'a> {
-    match line.trim().to_lowercase().as_str() {
+    let trimmed = line.trim();
+
+    if let Some(expr) = command_arg(trimmed, ":type") {
+        return ParseOutcome::TypeOf(expr);
+    } else if let Some(path) = command_arg(trimmed, ":load") {
+        return ParseOutcome::Load(path);
+    }
+
+    match trimmed.to_lowercase().as_str() {
         "" => ParseOutcome::Empty,
         ":help" => ParseOutcome::Help,
+        ":reset" => ParseOutcome::Reset,
+        ":defs" => ParseOutcome::Defs,
+        ":time" => ParseOutcome::Time,
         ":exit" | ":quit" | ":q" => ParseOutcome::Exit,
         _ => {
             let src_bytes = line.as_bytes();
@@ -511,6 +1027,11 @@ pub fn is_incomplete(input: &str) -> bool {
         }
         ParseOutcome::Empty
         | ParseOutcome::Help
+        | ParseOutcome::TypeOf(_)
+        | ParseOutcome::Load(_)
+        | ParseOutcome::Reset
+        | ParseOutcome::Defs
+        | ParseOutcome::Time
         | ParseOutcome::Exit
         | ParseOutcome::ValueDef(_)
         | ParseOutcome::TypeDef(_)
@@ -632,3 +1153,10 @@ fn format_output(
 
     buf
 }
+
+fn format_timings(timings: EvalTimings) -> String {
+    format!(
+        "{GREEN}# compiled in {:.1?}, evaluated in {:.1?}{END_COL}\n",
+        timings.compile, timings.eval
+    )
+}
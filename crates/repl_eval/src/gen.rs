@@ -1,15 +1,24 @@
 use bumpalo::Bump;
-use roc_load::{ExecutionMode, LoadConfig, LoadMonomorphizedError, Threading};
+use roc_collections::MutMap;
+use roc_load::{ExecutionMode, LoadConfig, LoadMonomorphizedError, LoadedModule, Threading};
+use roc_module::symbol::{Interns, ModuleId};
 use roc_packaging::cache::{self, RocCacheDir};
+use roc_parse::ast::{ExtractSpaces, Header, TypeAnnotation};
+use roc_parse::header::{PackageEntry, To};
+use roc_parse::module::parse_header;
+use roc_parse::state::State;
 use roc_problem::Severity;
 use roc_reporting::report::Palette;
-use std::path::PathBuf;
+use roc_solve_problem::TypeError;
+use roc_types::pretty_print::{name_and_print_var, DebugPrint};
+use std::fs;
+use std::path::{Path, PathBuf};
 
 use roc_fmt::annotation::Formattable;
 use roc_fmt::annotation::{Newlines, Parens};
 use roc_load::{LoadingProblem, MonomorphizedModule};
 use roc_parse::ast::Expr;
-use roc_region::all::LineInfo;
+use roc_region::all::{LineInfo, Region};
 use roc_reporting::report::{can_problem, type_problem, RocDocAllocator};
 use roc_target::TargetInfo;
 
@@ -44,21 +53,370 @@ impl Problems {
     }
 }
 
+/// What's in scope in the REPL besides builtins: the app it was started in (if any), and the
+/// interface modules whose exposed defs have been brought into scope with `:load`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplImports {
+    /// The directory the modules are in. The REPL's own module pretends to live here too, so
+    /// that it can import them.
+    src_dir: Option<PathBuf>,
+    app: Option<AppScope>,
+    /// Each module's name, along with the names it exposes
+    modules: Vec<(String, Vec<String>)>,
+}
+
+/// The parts of an app's header that the REPL copies into its own module, so that inputs can
+/// use everything the app can.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AppScope {
+    /// e.g. `pf: "cli-platform/main.roc"`
+    packages: Vec<String>,
+    /// Each import's module name, along with the import as written, e.g.
+    /// `("pf.Task", "pf.Task.{ await }")`
+    imports: Vec<(String, String)>,
+    /// The app's platform, if it's one we know how to run inputs on
+    platform: Option<AppPlatform>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AppPlatform {
+    /// e.g. "pf"
+    shorthand: String,
+    /// The name of the type the platform requires `main` to have, e.g. "Task"
+    main_type: String,
+}
+
+/// The REPL's own module is named as if it were this file in the app's directory, so that
+/// relative package paths in the app's header still resolve.
+const REPL_FILENAME: &str = "Repl.roc";
+
+impl ReplImports {
+    /// Start out in the scope of the app at this path: with its packages, and whatever it imports.
+    pub fn from_app(path: &Path) -> Result<Self, String> {
+        let src = fs::read(path)
+            .map_err(|err| format!("I could not read {}: {}", path.display(), err))?;
+        let arena = Bump::new();
+
+        let header = match parse_header(&arena, State::new(&src)) {
+            Ok((module, _)) => module.header,
+            Err(_) => {
+                return Err(format!(
+                    "I could not parse the header of {}",
+                    path.display()
+                ))
+            }
+        };
+
+        let header = match header {
+            Header::App(header) => header,
+            Header::Interface(_) => {
+                return Err(format!(
+                    "{} is an interface module, not an app. To use it in the REPL, run `roc repl` and then `:load {}`",
+                    path.display(),
+                    path.display()
+                ))
+            }
+            Header::Package(_) | Header::Platform(_) | Header::Hosted(_) => {
+                return Err(format!(
+                    "{} is not an app, so the REPL can't start in its scope",
+                    path.display()
+                ))
+            }
+        };
+
+        let app_dir = path
+            .canonicalize()
+            .map_err(|err| format!("I could not read {}: {}", path.display(), err))?
+            .parent()
+            .unwrap()
+            .to_path_buf();
+
+        // Copy entries the way they're written, rather than reformatting them
+        let written = |region: Region| {
+            String::from_utf8_lossy(
+                &src[region.start().offset as usize..region.end().offset as usize],
+            )
+            .into_owned()
+        };
+
+        let package_entries: Vec<PackageEntry> = header
+            .packages
+            .iter()
+            .flat_map(|packages| packages.item.items.iter())
+            .map(|entry| entry.extract_spaces().item)
+            .collect();
+        let packages = header
+            .packages
+            .iter()
+            .flat_map(|packages| packages.item.items.iter())
+            .map(|entry| written(entry.region))
+            .collect();
+        let imports = header
+            .imports
+            .iter()
+            .flat_map(|imports| imports.item.items.iter())
+            .map(|entry| {
+                let import = written(entry.region);
+                let module_name = match import.split_once(".{") {
+                    Some((module_name, _)) => module_name.trim().to_string(),
+                    None => import.trim().to_string(),
+                };
+
+                (module_name, import)
+            })
+            .collect();
+
+        let platform = match header.provides.to.value {
+            To::ExistingPackage(shorthand) => package_entries
+                .iter()
+                .find(|entry| entry.shorthand == shorthand)
+                .and_then(|entry| {
+                    let main_type =
+                        platform_main_type(&app_dir, entry.package_name.value.to_str())?;
+
+                    Some(AppPlatform {
+                        shorthand: shorthand.to_string(),
+                        main_type,
+                    })
+                }),
+            To::NewPackage(_) => None,
+        };
+
+        Ok(Self {
+            src_dir: Some(app_dir),
+            app: Some(AppScope {
+                packages,
+                imports,
+                platform,
+            }),
+            modules: Vec::new(),
+        })
+    }
+
+    /// Bring everything the interface module at this path exposes into scope, and return the
+    /// module's name.
+    pub fn add(&mut self, path: &Path) -> Result<String, String> {
+        let src = fs::read(path)
+            .map_err(|err| format!("I could not read {}: {}", path.display(), err))?;
+        let arena = Bump::new();
+
+        let header = match parse_header(&arena, State::new(&src)) {
+            Ok((module, _)) => module.header,
+            Err(_) => {
+                return Err(format!(
+                    "I could not parse the header of {}",
+                    path.display()
+                ))
+            }
+        };
+
+        let (name, exposes) = match header {
+            Header::Interface(header) => (
+                header.name.value.as_str().to_string(),
+                header
+                    .exposes
+                    .item
+                    .items
+                    .iter()
+                    .map(|exposed| exposed.extract_spaces().item.as_str().to_string())
+                    .collect(),
+            ),
+            Header::App(_) | Header::Package(_) | Header::Platform(_) | Header::Hosted(_) => {
+                return Err(format!(
+                    "{} is not an interface module, so there is nothing to load from it",
+                    path.display()
+                ))
+            }
+        };
+
+        let file_stem = path.file_stem().map(|stem| stem.to_string_lossy());
+
+        if file_stem.as_deref() != Some(name.as_str()) {
+            return Err(format!(
+                "The module {} needs to be in a file named {}.roc to be imported",
+                name, name
+            ));
+        }
+
+        let dir = path
+            .canonicalize()
+            .map_err(|err| format!("I could not read {}: {}", path.display(), err))?
+            .parent()
+            .unwrap()
+            .to_path_buf();
+
+        match &self.src_dir {
+            Some(src_dir) if src_dir != &dir => {
+                return Err(format!(
+                    "All loaded modules must be in the same directory, and {} is not in {}",
+                    path.display(),
+                    src_dir.display()
+                ));
+            }
+            _ => self.src_dir = Some(dir),
+        }
+
+        // Loading a module again (e.g. after editing it) replaces what it exposes
+        self.modules.retain(|(existing, _)| existing != &name);
+        self.modules.push((name.clone(), exposes));
+
+        Ok(name)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.app.is_none() && self.modules.is_empty()
+    }
+
+    /// The directory of the app the REPL was started in, if any
+    pub fn app_dir(&self) -> Option<&Path> {
+        match self.app {
+            Some(_) => self.src_dir.as_deref(),
+            None => None,
+        }
+    }
+
+    /// Whether an expr of this type (as printed by `expr_type`) is something the app's platform
+    /// can run, e.g. a `Task`. If so, `platform_app_src` makes an app that runs it.
+    pub fn runs_on_platform(&self, expr_type: &str) -> bool {
+        match self.app.as_ref().and_then(|app| app.platform.as_ref()) {
+            Some(platform) => {
+                // e.g. "Task.Task {} *"
+                let type_name = expr_type
+                    .split_whitespace()
+                    .next()
+                    .and_then(|name| name.rsplit('.').next());
+
+                type_name == Some(platform.main_type.as_str())
+            }
+            None => false,
+        }
+    }
+
+    /// The source of an app, on the same platform as the one the REPL was started in, whose
+    /// `main` is the given expr. It should be put in `app_dir` so that everything resolves.
+    /// The app's name is what its executable will be called.
+    pub fn platform_app_src<'i, I: Iterator<Item = &'i str>>(
+        &self,
+        app_name: &str,
+        defs: I,
+        expr: &str,
+    ) -> Option<String> {
+        let platform = self.app.as_ref()?.platform.as_ref()?;
+        let arena = Bump::new();
+        let header = self.header(&arena, app_name, "main", &platform.shorthand);
+        let (_, src) = module_with_expr(&arena, header, defs, "main", expr);
+
+        Some(src.to_string())
+    }
+
+    fn src_dir(&self) -> PathBuf {
+        match &self.src_dir {
+            Some(src_dir) => src_dir.clone(),
+            None => PathBuf::from("fake/test/path"),
+        }
+    }
+
+    fn filename(&self) -> PathBuf {
+        match &self.app {
+            Some(_) => self.src_dir().join(REPL_FILENAME),
+            None => PathBuf::from(""),
+        }
+    }
+
+    /// e.g. `app "app" imports [Foo.{ a, b }] provides [replOutput] to "./platform"`
+    fn header<'a>(&self, arena: &'a Bump, app_name: &str, provides: &str, to: &str) -> &'a str {
+        let mut buffer = bumpalo::collections::string::String::from_str_in("app \"", arena);
+
+        buffer.push_str(app_name);
+        buffer.push('"');
+
+        let mut imports: Vec<String> = Vec::new();
+
+        if let Some(app) = &self.app {
+            if !app.packages.is_empty() {
+                buffer.push_str(" packages { ");
+                buffer.push_str(&app.packages.join(", "));
+                buffer.push_str(" }");
+            }
+
+            // A module that's been `:load`ed exposes everything, so it replaces the app's import
+            imports.extend(
+                app.imports
+                    .iter()
+                    .filter(|(module_name, _)| {
+                        !self.modules.iter().any(|(loaded, _)| loaded == module_name)
+                    })
+                    .map(|(_, import)| import.clone()),
+            );
+        }
+
+        imports.extend(
+            self.modules.iter().map(|(module_name, exposes)| {
+                format!("{}.{{ {} }}", module_name, exposes.join(", "))
+            }),
+        );
+
+        if !imports.is_empty() {
+            buffer.push_str(" imports [");
+            buffer.push_str(&imports.join(", "));
+            buffer.push(']');
+        }
+
+        buffer.push_str(" provides [");
+        buffer.push_str(provides);
+        buffer.push_str("] to ");
+        buffer.push_str(to);
+
+        buffer.into_bump_str()
+    }
+}
+
+/// The name of the type a platform requires an app's `main` to have, e.g. "Task" for
+/// `requires {} { main : Task {} [] }`, or None if we can't tell.
+fn platform_main_type(app_dir: &Path, package_name: &str) -> Option<String> {
+    let root_module = if package_name.starts_with("https://") {
+        // Loading the app will have installed the package already, so this won't download it
+        // unless the app failed to load anyway.
+        let (package_dir, opt_root_module) = cache::install_package(
+            RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
+            package_name,
+        )
+        .ok()?;
+
+        package_dir.join(opt_root_module.unwrap_or("main.roc"))
+    } else {
+        app_dir.join(package_name)
+    };
+
+    let src = fs::read(root_module).ok()?;
+    let arena = Bump::new();
+    let header = match parse_header(&arena, State::new(&src)).ok()?.0.header {
+        Header::Platform(header) => header,
+        _ => return None,
+    };
+    let signature = header.requires.item.signature.extract_spaces().item;
+
+    match signature.ann.value.extract_spaces().item {
+        TypeAnnotation::Apply(_, name, _) => Some(name.to_string()),
+        _ => None,
+    }
+}
+
 pub fn compile_to_mono<'a, 'i, I: Iterator<Item = &'i str>>(
     arena: &'a Bump,
+    imports: &ReplImports,
     defs: I,
     expr: &str,
     target_info: TargetInfo,
     palette: Palette,
 ) -> (Option<MonomorphizedModule<'a>>, Problems) {
-    let filename = PathBuf::from("");
-    let src_dir = PathBuf::from("fake/test/path");
-    let (bytes_before_expr, module_src) = promote_expr_to_module(arena, defs, expr);
+    let filename = imports.filename();
+    let (bytes_before_expr, module_src) = promote_expr_to_module(arena, imports, defs, expr);
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
         filename,
         module_src,
-        src_dir,
+        imports.src_dir(),
         RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
         LoadConfig {
             target_info,
@@ -92,6 +450,68 @@ pub fn compile_to_mono<'a, 'i, I: Iterator<Item = &'i str>>(
     };
 
     let MonomorphizedModule {
+        module_id,
+        interns,
+        sources,
+        can_problems,
+        type_problems,
+        ..
+    } = &mut loaded;
+
+    let problems = report_problems(
+        *module_id,
+        sources,
+        can_problems,
+        type_problems,
+        interns,
+        bytes_before_expr,
+        palette,
+    );
+
+    (Some(loaded), problems)
+}
+
+/// Like `compile_to_mono`, but stops after type checking; this is what `:type` and `:defs` use,
+/// since they don't need to evaluate anything.
+pub fn compile_to_types<'a, 'i, I: Iterator<Item = &'i str>>(
+    arena: &'a Bump,
+    imports: &ReplImports,
+    defs: I,
+    expr: &str,
+    target_info: TargetInfo,
+    palette: Palette,
+) -> (Option<LoadedModule>, Problems) {
+    let filename = imports.filename();
+    let (bytes_before_expr, module_src) = promote_expr_to_module(arena, imports, defs, expr);
+    let loaded = roc_load::load_and_typecheck_str(
+        arena,
+        filename,
+        module_src,
+        imports.src_dir(),
+        target_info,
+        roc_reporting::report::RenderTarget::ColorTerminal,
+        RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
+        palette,
+    );
+
+    let mut loaded = match loaded {
+        Ok(v) => v,
+        Err(LoadingProblem::FormattedReport(report)) => {
+            return (
+                None,
+                Problems {
+                    errors: vec![report],
+                    warnings: Vec::new(),
+                },
+            );
+        }
+        Err(e) => {
+            todo!("error while loading module: {:?}", e)
+        }
+    };
+
+    let LoadedModule {
+        module_id,
         interns,
         sources,
         can_problems,
@@ -99,6 +519,62 @@ pub fn compile_to_mono<'a, 'i, I: Iterator<Item = &'i str>>(
         ..
     } = &mut loaded;
 
+    let problems = report_problems(
+        *module_id,
+        sources,
+        can_problems,
+        type_problems,
+        interns,
+        bytes_before_expr,
+        palette,
+    );
+
+    (Some(loaded), problems)
+}
+
+/// The type of the expr in a module from `compile_to_types`, e.g. "Num *"
+pub fn expr_type(loaded: &mut LoadedModule) -> Option<String> {
+    let var = *loaded.exposed_to_host.values().next()?;
+
+    Some(name_and_print_var(
+        var,
+        loaded.solved.inner_mut(),
+        loaded.module_id,
+        &loaded.interns,
+        DebugPrint::NOTHING,
+    ))
+}
+
+/// The type of a top-level def in a module from `compile_to_types`. If the def was shadowed,
+/// this is the type of the most recent one.
+pub fn def_type(loaded: &mut LoadedModule, ident: &str) -> Option<String> {
+    let declarations = loaded.declarations_by_id.get(&loaded.module_id)?;
+    let var = declarations
+        .symbols
+        .iter()
+        .zip(declarations.variables.iter())
+        .filter(|(symbol, _)| symbol.value.as_str(&loaded.interns) == ident)
+        .max_by_key(|(symbol, _)| symbol.region.start())
+        .map(|(_, var)| *var)?;
+
+    Some(name_and_print_var(
+        var,
+        loaded.solved.inner_mut(),
+        loaded.module_id,
+        &loaded.interns,
+        DebugPrint::NOTHING,
+    ))
+}
+
+fn report_problems(
+    repl_module_id: ModuleId,
+    sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+    can_problems: &mut MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
+    type_problems: &mut MutMap<ModuleId, Vec<TypeError>>,
+    interns: &Interns,
+    bytes_before_expr: usize,
+    palette: Palette,
+) -> Problems {
     let mut problems = Problems::default();
 
     let errors = &mut problems.errors;
@@ -114,8 +590,17 @@ pub fn compile_to_mono<'a, 'i, I: Iterator<Item = &'i str>>(
             continue;
         }
 
-        let line_info = LineInfo::new(module_src);
+        // Modules brought in with `:load` are reported on like any other module, except that
+        // we leave their warnings for `roc check` rather than repeating them on every input.
+        let is_repl_module = *home == repl_module_id;
+        let line_info = LineInfo::new(src);
         let src_lines: Vec<&str> = src.split('\n').collect();
+        // The REPL's module doesn't really live anywhere, so don't show a path for it.
+        let module_path = if is_repl_module {
+            PathBuf::new()
+        } else {
+            module_path.clone()
+        };
 
         // Report parsing and canonicalization problems
         let alloc = RocDocAllocator::new(&src_lines, *home, interns);
@@ -125,7 +610,9 @@ pub fn compile_to_mono<'a, 'i, I: Iterator<Item = &'i str>>(
             // because they must be part of the defs (excluding the most renently added def,
             // if that's the one being evaluated) and therefore not things we should show.
             // This filters out things like shadowing warnings and unused def warnings.
-            if problem.region().unwrap_or_default().end().offset as usize >= bytes_before_expr {
+            if !is_repl_module
+                || problem.region().unwrap_or_default().end().offset as usize >= bytes_before_expr
+            {
                 let report = can_problem(&alloc, &line_info, module_path.clone(), problem);
                 let severity = report.severity;
                 let mut buf = String::new();
@@ -134,7 +621,9 @@ pub fn compile_to_mono<'a, 'i, I: Iterator<Item = &'i str>>(
 
                 match severity {
                     Severity::Warning => {
-                        warnings.push(buf);
+                        if is_repl_module {
+                            warnings.push(buf);
+                        }
                     }
                     Severity::Fatal | Severity::RuntimeError => {
                         errors.push(buf);
@@ -144,6 +633,14 @@ pub fn compile_to_mono<'a, 'i, I: Iterator<Item = &'i str>>(
         }
 
         for problem in type_probs {
+            // When the REPL is started in an app, its platform gets loaded, and the platform looks
+            // up the `main` it requires in the REPL's module. The REPL's module doesn't provide
+            // one, which is fine, since we never run the platform's entry point.
+            if matches!(problem, TypeError::UnexposedLookup(symbol) if symbol.module_id() == repl_module_id)
+            {
+                continue;
+            }
+
             if let Some(report) = type_problem(&alloc, &line_info, module_path.clone(), problem) {
                 let severity = report.severity;
                 let mut buf = String::new();
@@ -152,7 +649,9 @@ pub fn compile_to_mono<'a, 'i, I: Iterator<Item = &'i str>>(
 
                 match severity {
                     Severity::Warning => {
-                        warnings.push(buf);
+                        if is_repl_module {
+                            warnings.push(buf);
+                        }
                     }
                     Severity::Fatal | Severity::RuntimeError => {
                         errors.push(buf);
@@ -162,19 +661,36 @@ pub fn compile_to_mono<'a, 'i, I: Iterator<Item = &'i str>>(
         }
     }
 
-    (Some(loaded), problems)
+    problems
 }
 
 fn promote_expr_to_module<'a, 'i, I: Iterator<Item = &'i str>>(
     arena: &'a Bump,
+    imports: &ReplImports,
     defs: I,
     expr: &str,
 ) -> (usize, &'a str) {
-    const REPL_MODULE_HEADER: &str = "app \"app\" provides [replOutput] to \"./platform\"\n\n";
-    const REPL_MODULE_MAIN_DEF: &str = "replOutput =\n";
+    const REPL_MODULE_MAIN_DEF: &str = "replOutput";
+
+    let header = imports.header(arena, "app", REPL_MODULE_MAIN_DEF, "\"./platform\"");
+
+    module_with_expr(arena, header, defs, REPL_MODULE_MAIN_DEF, expr)
+}
+
+/// A module with the given header and defs, where the expr is the body of `main_def`.
+/// Also returns where the expr begins.
+fn module_with_expr<'a, 'i, I: Iterator<Item = &'i str>>(
+    arena: &'a Bump,
+    header: &str,
+    defs: I,
+    main_def: &str,
+    expr: &str,
+) -> (usize, &'a str) {
     const INDENT: &str = "    ";
 
-    let mut buffer = bumpalo::collections::string::String::from_str_in(REPL_MODULE_HEADER, arena);
+    let mut buffer = bumpalo::collections::string::String::from_str_in(header, arena);
+
+    buffer.push_str("\n\n");
 
     for line in defs {
         // don't indent the defs
@@ -182,7 +698,8 @@ fn promote_expr_to_module<'a, 'i, I: Iterator<Item = &'i str>>(
         buffer.push_str("\n\n");
     }
 
-    buffer.push_str(REPL_MODULE_MAIN_DEF);
+    buffer.push_str(main_def);
+    buffer.push_str(" =\n");
 
     let bytes_before_expr = buffer.len();
 
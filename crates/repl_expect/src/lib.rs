@@ -1,26 +1,19 @@
 //! Supports evaluating `expect` and printing contextual information when they fail.
-#[cfg(not(windows))]
-use {
-    roc_module::symbol::Interns,
-    roc_mono::{
-        ir::ProcLayout,
-        layout::{GlobalLayoutInterner, LayoutCache, Niche},
-    },
-    roc_parse::ast::Expr,
-    roc_repl_eval::{eval::jit_to_ast, ReplAppMemory},
-    roc_target::TargetInfo,
-    roc_types::subs::{Subs, Variable},
+use roc_module::symbol::Interns;
+use roc_mono::{
+    ir::ProcLayout,
+    layout::{GlobalLayoutInterner, LayoutCache, Niche},
 };
+use roc_parse::ast::Expr;
+use roc_repl_eval::{eval::jit_to_ast, ReplAppMemory};
+use roc_target::TargetInfo;
+use roc_types::subs::{Subs, Variable};
 
-#[cfg(not(windows))]
 mod app;
-#[cfg(not(windows))]
 pub mod run;
 
-#[cfg(not(windows))]
 use app::{ExpectMemory, ExpectReplApp};
 
-#[cfg(not(windows))]
 #[allow(clippy::too_many_arguments)]
 pub fn get_values<'a>(
     target_info: TargetInfo,
@@ -88,7 +81,6 @@ pub fn get_values<'a>(
     (app.offset, result, result_vars)
 }
 
-#[cfg(not(windows))]
 #[cfg(test)]
 mod test {
     use indoc::indoc;
@@ -99,7 +91,7 @@ mod test {
     use roc_reporting::report::{RenderTarget, DEFAULT_PALETTE};
     use target_lexicon::Triple;
 
-    use crate::run::expect_mono_module_to_dylib;
+    use crate::run::{expect_mono_module_to_dylib, ExpectRunner};
 
     use super::*;
 
@@ -170,7 +162,7 @@ mod test {
         unsafe { set_shared_buffer((shared_buffer.as_mut_ptr(), BUFFER_SIZE), &mut result) };
 
         let mut writer = Vec::with_capacity(1024);
-        let (_failed, _passed) = crate::run::run_expects_with_memory(
+        let _results = crate::run::run_expects_with_memory(
             &mut writer,
             RenderTarget::ColorTerminal,
             arena,
@@ -180,6 +172,8 @@ mod test {
             &mut expectations,
             expects,
             &mut memory,
+            // the buffer is not shared memory, so expect-fx can't run in a child process
+            ExpectRunner::InProcess,
         )
         .unwrap();
 
@@ -245,6 +239,43 @@ mod test {
         );
     }
 
+    #[test]
+    fn fx_in_process_pass() {
+        run_expect_test(
+            r#"
+            app "test" provides [main] to "./platform"
+
+            main = 0
+
+            expect-fx 1 == 1
+            "#,
+            "",
+        );
+    }
+
+    #[test]
+    fn fx_in_process_fail() {
+        run_expect_test(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                expect-fx 1 == 2
+                "#
+            ),
+            indoc!(
+                r#"
+                This expectation failed:
+
+                5│  expect-fx 1 == 2
+                    ^^^^^^^^^^^^^^^^
+                "#
+            ),
+        );
+    }
+
     #[test]
     fn lookup_integer() {
         run_expect_test(
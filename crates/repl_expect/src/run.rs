@@ -1,9 +1,11 @@
+#[cfg(unix)]
+use std::os::unix::process::parent_id;
 use std::{
-    os::unix::process::parent_id,
     sync::{
         atomic::{AtomicBool, AtomicU32},
         Arc,
     },
+    time::{Duration, Instant},
 };
 
 use bumpalo::collections::Vec as BumpVec;
@@ -30,10 +32,39 @@ use roc_target::TargetInfo;
 use roc_types::subs::Subs;
 use target_lexicon::Triple;
 
+/// How top-level `expect-fx`s are run. Pure expects always run in the current process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpectRunner {
+    /// Run each `expect-fx` in a forked child process, which shares its expect buffer with
+    /// the parent through shared memory. A child that crashes cannot take the test run down.
+    #[cfg(unix)]
+    Fork,
+    /// Run each `expect-fx` in the current process, like a pure expect. This works on every
+    /// platform, but a crash (other than a roc panic) ends the whole test run.
+    InProcess,
+}
+
+impl Default for ExpectRunner {
+    fn default() -> Self {
+        #[cfg(unix)]
+        {
+            ExpectRunner::Fork
+        }
+
+        #[cfg(not(unix))]
+        {
+            ExpectRunner::InProcess
+        }
+    }
+}
+
 pub struct ExpectMemory<'a> {
     ptr: *mut u8,
     length: usize,
+    #[cfg_attr(not(unix), allow(dead_code))]
     shm_name: Option<std::ffi::CString>,
+    /// Owns the buffer if it lives on the heap rather than in shared memory
+    _heap: Option<Box<[u8]>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 
@@ -46,20 +77,55 @@ impl<'a> ExpectMemory<'a> {
             ptr: slice.as_mut_ptr(),
             length: slice.len(),
             shm_name: None,
+            _heap: None,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// A buffer that is only visible to the current process.
+    pub fn allocate() -> Self {
+        let mut heap = vec![0xAA; Self::SHM_SIZE].into_boxed_slice();
+        let ptr = heap.as_mut_ptr();
+
+        // puts in the initial header
+        let _ = ExpectSequence::new(ptr);
+
+        Self {
+            ptr,
+            length: Self::SHM_SIZE,
+            shm_name: None,
+            _heap: Some(heap),
             _marker: std::marker::PhantomData,
         }
     }
 
+    /// Creates the buffer for the given runner: shared memory if the expects run in
+    /// a child process, and plain memory otherwise.
+    pub fn for_runner(runner: ExpectRunner) -> Self {
+        match runner {
+            #[cfg(unix)]
+            ExpectRunner::Fork => {
+                let shm_name = format!("/roc_expect_buffer_{}", std::process::id());
+
+                Self::create_or_reuse_mmap(&shm_name)
+            }
+            ExpectRunner::InProcess => Self::allocate(),
+        }
+    }
+
+    #[cfg(unix)]
     pub fn create_or_reuse_mmap(shm_name: &str) -> Self {
         let cstring = std::ffi::CString::new(shm_name).unwrap();
         Self::mmap_help(cstring, libc::O_RDWR | libc::O_CREAT)
     }
 
+    #[cfg(unix)]
     fn reuse_mmap(&mut self) -> Option<Self> {
         let shm_name = self.shm_name.as_ref()?.clone();
         Some(Self::mmap_help(shm_name, libc::O_RDWR))
     }
 
+    #[cfg(unix)]
     fn mmap_help(cstring: std::ffi::CString, shm_flags: i32) -> Self {
         let ptr = unsafe {
             let shared_fd = libc::shm_open(cstring.as_ptr().cast(), shm_flags, 0o666);
@@ -104,6 +170,7 @@ impl<'a> ExpectMemory<'a> {
             ptr: ptr.cast(),
             length: Self::SHM_SIZE,
             shm_name: Some(cstring),
+            _heap: None,
             _marker: std::marker::PhantomData,
         }
     }
@@ -135,11 +202,11 @@ pub fn run_inline_expects<'a, W: std::io::Write>(
     lib: &libloading::Library,
     expectations: &mut VecMap<ModuleId, Expectations>,
     expects: ExpectFunctions<'_>,
+    runner: ExpectRunner,
 ) -> std::io::Result<(usize, usize)> {
-    let shm_name = format!("/roc_expect_buffer_{}", std::process::id());
-    let mut memory = ExpectMemory::create_or_reuse_mmap(&shm_name);
+    let mut memory = ExpectMemory::for_runner(runner);
 
-    run_expects_with_memory(
+    let results = run_expects_with_memory(
         writer,
         render_target,
         arena,
@@ -149,11 +216,16 @@ pub fn run_inline_expects<'a, W: std::io::Write>(
         expectations,
         expects,
         &mut memory,
-    )
+        runner,
+    )?;
+
+    Ok(count_failed_and_passed(&results))
 }
 
+/// Runs the given top-level expects, writing a report for each failure to `writer`.
+/// Returns a result for every expect, in the order they were run.
 #[allow(clippy::too_many_arguments)]
-pub fn run_toplevel_expects<'a, W: std::io::Write>(
+pub fn run_toplevel_expects<'a, 'e, W: std::io::Write>(
     writer: &mut W,
     render_target: RenderTarget,
     arena: &'a Bump,
@@ -161,10 +233,10 @@ pub fn run_toplevel_expects<'a, W: std::io::Write>(
     layout_interner: &GlobalLayoutInterner<'a>,
     lib: &libloading::Library,
     expectations: &mut VecMap<ModuleId, Expectations>,
-    expects: ExpectFunctions<'_>,
-) -> std::io::Result<(usize, usize)> {
-    let shm_name = format!("/roc_expect_buffer_{}", std::process::id());
-    let mut memory = ExpectMemory::create_or_reuse_mmap(&shm_name);
+    expects: ExpectFunctions<'e>,
+    runner: ExpectRunner,
+) -> std::io::Result<Vec<ExpectResult<'e>>> {
+    let mut memory = ExpectMemory::for_runner(runner);
 
     run_expects_with_memory(
         writer,
@@ -176,11 +248,29 @@ pub fn run_toplevel_expects<'a, W: std::io::Write>(
         expectations,
         expects,
         &mut memory,
+        runner,
     )
 }
 
+/// The outcome of running a single top-level expect
+#[derive(Debug, Clone)]
+pub struct ExpectResult<'a> {
+    pub expect: ToplevelExpect<'a>,
+    pub passed: bool,
+    pub duration: Duration,
+    /// The failure report, rendered for the requested render target. Empty if the expect passed.
+    pub failure: String,
+}
+
+/// Returns `(failed, passed)`
+pub fn count_failed_and_passed(results: &[ExpectResult]) -> (usize, usize) {
+    let passed = results.iter().filter(|result| result.passed).count();
+
+    (results.len() - passed, passed)
+}
+
 #[allow(clippy::too_many_arguments)]
-pub(crate) fn run_expects_with_memory<'a, W: std::io::Write>(
+pub(crate) fn run_expects_with_memory<'a, 'e, W: std::io::Write>(
     writer: &mut W,
     render_target: RenderTarget,
     arena: &'a Bump,
@@ -188,36 +278,56 @@ pub(crate) fn run_expects_with_memory<'a, W: std::io::Write>(
     layout_interner: &GlobalLayoutInterner<'a>,
     lib: &libloading::Library,
     expectations: &mut VecMap<ModuleId, Expectations>,
-    expects: ExpectFunctions<'_>,
+    expects: ExpectFunctions<'e>,
     memory: &mut ExpectMemory,
-) -> std::io::Result<(usize, usize)> {
-    let mut failed = 0;
-    let mut passed = 0;
+    runner: ExpectRunner,
+) -> std::io::Result<Vec<ExpectResult<'e>>> {
+    let mut results = Vec::with_capacity(expects.fx.len() + expects.pure.len());
+
+    // A forked child points the buffer at its own mapping of the shared memory, so this only
+    // matters for expects that run in this process.
+    memory.set_shared_buffer(lib);
 
+    // Each failure is rendered into its own buffer first, so that it can be reported per expect.
     for expect in expects.fx {
-        let result = run_expect_fx(
-            writer,
-            render_target,
-            arena,
-            interns,
-            layout_interner,
-            lib,
-            expectations,
-            memory,
-            expect,
-        )?;
+        let mut buffer = Vec::new();
+        let start = Instant::now();
+
+        let passed = match runner {
+            #[cfg(unix)]
+            ExpectRunner::Fork => run_expect_fx(
+                &mut buffer,
+                render_target,
+                arena,
+                interns,
+                layout_interner,
+                lib,
+                expectations,
+                memory,
+                expect,
+            )?,
+            ExpectRunner::InProcess => run_expect_pure(
+                &mut buffer,
+                render_target,
+                arena,
+                interns,
+                layout_interner,
+                lib,
+                expectations,
+                memory,
+                expect,
+            )?,
+        };
 
-        match result {
-            true => passed += 1,
-            false => failed += 1,
-        }
+        results.push(expect_result(writer, expect, passed, start, buffer)?);
     }
 
-    memory.set_shared_buffer(lib);
-
     for expect in expects.pure {
-        let result = run_expect_pure(
-            writer,
+        let mut buffer = Vec::new();
+        let start = Instant::now();
+
+        let passed = run_expect_pure(
+            &mut buffer,
             render_target,
             arena,
             interns,
@@ -228,13 +338,29 @@ pub(crate) fn run_expects_with_memory<'a, W: std::io::Write>(
             expect,
         )?;
 
-        match result {
-            true => passed += 1,
-            false => failed += 1,
-        }
+        results.push(expect_result(writer, expect, passed, start, buffer)?);
     }
 
-    Ok((failed, passed))
+    Ok(results)
+}
+
+fn expect_result<'e, W: std::io::Write>(
+    writer: &mut W,
+    expect: ToplevelExpect<'e>,
+    passed: bool,
+    start: Instant,
+    buffer: Vec<u8>,
+) -> std::io::Result<ExpectResult<'e>> {
+    let duration = start.elapsed();
+
+    writer.write_all(&buffer)?;
+
+    Ok(ExpectResult {
+        expect,
+        passed,
+        duration,
+        failure: String::from_utf8_lossy(&buffer).into_owned(),
+    })
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -295,6 +421,7 @@ fn run_expect_pure<'a, W: std::io::Write>(
     }
 }
 
+#[cfg(unix)]
 #[allow(clippy::too_many_arguments)]
 fn run_expect_fx<'a, W: std::io::Write>(
     writer: &mut W,
@@ -747,6 +874,7 @@ pub fn expect_mono_module_to_dylib<'a>(
         mode,
         // important! we don't want any procedures to get the C calling convention
         exposed_to_host: MutSet::default(),
+        debug_sources: Default::default(),
     };
 
     // Add roc_alloc, roc_realloc, and roc_dealloc, since the repl has no
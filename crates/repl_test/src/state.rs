@@ -1,5 +1,7 @@
 use indoc::indoc;
 use roc_repl_cli::repl_state::{is_incomplete, ReplState, TIPS};
+use std::fs;
+use std::path::Path;
 
 // These are tests of the REPL state machine. They work without actually
 // running the CLI, and without using rustyline, and instead verify
@@ -30,6 +32,45 @@ fn persisted_defs() {
     complete("val1 + x + y", &mut state, Ok(("15 : Num *", "val2")));
 }
 
+#[test]
+fn redefined_defs() {
+    let mut state = ReplState::new();
+
+    complete("x = 5", &mut state, Ok(("5 : Num *", "x")));
+    complete("y = x + 1", &mut state, Ok(("6 : Num *", "y")));
+    complete("x = \"five\"", &mut state, Ok(("\"five\" : Str", "x")));
+    complete("x", &mut state, Ok(("\"five\" : Str", "x")));
+    complete(
+        "Str.concat x \"!\"",
+        &mut state,
+        Ok(("\"five!\" : Str", "val1")),
+    );
+
+    // Defs from before the redefinition keep using the old value
+    complete("y", &mut state, Ok(("6 : Num *", "y")));
+    complete("y * 2", &mut state, Ok(("12 : Num *", "val2")));
+    output(
+        ":defs",
+        &mut state,
+        "y : Num *\nx : Str\nval1 : Str\nval2 : Num *",
+    );
+}
+
+#[test]
+fn redefined_record_field() {
+    let mut state = ReplState::new();
+
+    complete("x = 5", &mut state, Ok(("5 : Num *", "x")));
+    complete(
+        "r = { x, double: x * 2 }",
+        &mut state,
+        Ok(("{ double: 10, x: 5 } : { double : Num *, x : Num * }", "r")),
+    );
+    complete("x = \"five\"", &mut state, Ok(("\"five\" : Str", "x")));
+    complete("r.x", &mut state, Ok(("5 : Num *", "val1")));
+    complete("r.double", &mut state, Ok(("10 : Num *", "val2")));
+}
+
 #[test]
 fn annotated_body() {
     let mut input = "t : [A, B, C]".to_string();
@@ -66,7 +107,7 @@ fn exhaustiveness_problem() {
 
         const EXPECTED_ERROR: &str = indoc!(
             r#"
-            ── UNSAFE PATTERN ──────────────────────────────────────────────────────────────
+            ── UNSAFE PATTERN [R0305] ──────────────────────────────────────────────────────
 
             This when does not cover all the possibilities:
 
@@ -101,6 +142,116 @@ fn standalone_annotation() {
     assert_eq!(state.step(&input, None), Ok(String::new()));
 }
 
+#[test]
+fn type_of_expr() {
+    let mut state = ReplState::new();
+
+    complete("x = 5", &mut state, Ok(("5 : Num *", "x")));
+    output(":type x + 1", &mut state, "x + 1 : Num *");
+    output(
+        ":TYPE Str.concat",
+        &mut state,
+        "Str.concat : Str, Str -> Str",
+    );
+
+    // :type doesn't evaluate anything, so it doesn't get a name either
+    complete("x", &mut state, Ok(("5 : Num *", "x")));
+    complete("1", &mut state, Ok(("1 : Num *", "val1")));
+}
+
+#[test]
+fn list_defs() {
+    let mut state = ReplState::new();
+
+    output(":defs", &mut state, "There are no definitions yet.");
+
+    complete("x = 5", &mut state, Ok(("5 : Num *", "x")));
+    complete("\"hi\"", &mut state, Ok(("\"hi\" : Str", "val1")));
+    output(":defs", &mut state, "x : Num *\nval1 : Str");
+}
+
+#[test]
+fn reset_defs() {
+    let mut state = ReplState::new();
+
+    complete("x = 5", &mut state, Ok(("5 : Num *", "x")));
+    complete("x + 1", &mut state, Ok(("6 : Num *", "val1")));
+    output(":reset", &mut state, "Forgot all definitions.");
+    output(":defs", &mut state, "There are no definitions yet.");
+    complete("2", &mut state, Ok(("2 : Num *", "val1")));
+}
+
+#[test]
+fn toggle_timing() {
+    let mut state = ReplState::new();
+
+    output(":time", &mut state, "Timing is on.");
+    output(":time", &mut state, "Timing is off.");
+}
+
+#[test]
+fn in_app_scope() {
+    let app = Path::new("../../examples/cli/countdown.roc");
+    let mut state = ReplState::with_app(app).unwrap_or_else(|message| panic!("{}", message));
+
+    // The app imports Stdout, and Stdout.line is a Task, which is what its platform runs.
+    output(
+        ":type Stdout.line \"hi\"",
+        &mut state,
+        "Stdout.line \"hi\" : Task.Task {} *",
+    );
+    complete(
+        "Str.concat \"a\" \"b\"",
+        &mut state,
+        Ok(("\"ab\" : Str", "val1")),
+    );
+}
+
+#[test]
+fn run_task_on_platform() {
+    let app = Path::new("../../examples/cli/file.roc");
+    let app_dir_entries = || {
+        let mut entries: Vec<_> = fs::read_dir(app.parent().unwrap())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+
+        entries.sort();
+
+        entries
+    };
+    let entries_before = app_dir_entries();
+    let out_dir = tempfile::tempdir().unwrap();
+    let out_file = out_dir.path().join("out.txt");
+    let mut state = ReplState::with_app(app).unwrap_or_else(|message| panic!("{}", message));
+
+    // A Task is run by the app's platform rather than evaluated, so it has no output of its own.
+    output(
+        &format!(
+            "File.writeUtf8 (Path.fromStr \"{}\") \"hi\" |> Task.onFail \\_ -> Process.exit 2",
+            out_file.display()
+        ),
+        &mut state,
+        "",
+    );
+    assert_eq!(fs::read_to_string(&out_file).unwrap(), "hi");
+
+    output("Process.exit 3", &mut state, "The task exited with code 3");
+
+    // Building and running those didn't leave anything behind next to the app.
+    assert_eq!(app_dir_entries(), entries_before);
+}
+
+#[test]
+fn app_scope_needs_an_app() {
+    let interface = Path::new("../../examples/cli/cli-platform/Stdout.roc");
+
+    match ReplState::with_app(interface) {
+        Ok(_) => panic!("started the REPL in the scope of an interface module"),
+        Err(message) => assert!(message.contains("is an interface module, not an app")),
+    }
+}
+
 /// validate and step the given input, then check the Result vs the output
 /// with ANSI escape codes stripped.
 fn complete(input: &str, state: &mut ReplState, expected_step_result: Result<(&str, &str), i32>) {
@@ -150,3 +301,15 @@ fn error(input: &str, state: &mut ReplState, expected_step_result: String) {
 
     assert_eq!(Ok(expected_step_result), escaped);
 }
+
+/// validate and step the given input, then check the given string vs the output
+/// with ANSI escape codes stripped.
+fn output(input: &str, state: &mut ReplState, expected_output: &str) {
+    assert!(!is_incomplete(input));
+
+    let escaped = state.step(input, None).map(|string| {
+        std::string::String::from_utf8(strip_ansi_escapes::strip(string.trim()).unwrap()).unwrap()
+    });
+
+    assert_eq!(Ok(expected_output.to_string()), escaped);
+}
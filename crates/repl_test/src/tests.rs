@@ -636,7 +636,7 @@ fn too_few_args() {
         "Num.add 2",
         indoc!(
             r#"
-                ── TOO FEW ARGS ────────────────────────────────────────────────────────────────
+                ── TOO FEW ARGS [R0301] ────────────────────────────────────────────────────────
 
                 The add function expects 2 arguments, but it got only 1:
 
@@ -657,7 +657,7 @@ fn type_problem() {
         "1 + \"\"",
         indoc!(
             r#"
-                ── TYPE MISMATCH ───────────────────────────────────────────────────────────────
+                ── TYPE MISMATCH [R0301] ───────────────────────────────────────────────────────
 
                 This 2nd argument to add has an unexpected type:
 
@@ -943,7 +943,7 @@ fn parse_problem() {
         "add m n = m + n",
         indoc!(
             r#"
-                ── ARGUMENTS BEFORE EQUALS ─────────────────────────────────────────────────────
+                ── ARGUMENTS BEFORE EQUALS [R0108] ─────────────────────────────────────────────
 
                 I am partway through parsing a definition, but I got stuck here:
 
@@ -977,7 +977,7 @@ fn issue_2343_complete_mono_with_shadowed_vars() {
         ),
         indoc!(
             r#"
-                ── DUPLICATE NAME ──────────────────────────────────────────────────────────────
+                ── DUPLICATE NAME [R0210] ──────────────────────────────────────────────────────
 
                 The b name is first defined here:
 
@@ -2,8 +2,12 @@ use std::path::PathBuf;
 
 use roc_collections::MutMap;
 use roc_module::symbol::{Interns, ModuleId};
-use roc_region::all::LineInfo;
+use roc_region::all::{LineColumnRegion, LineInfo};
 use roc_solve_problem::TypeError;
+use serde_json::{json, Value};
+
+use crate::report::{Palette, RenderTarget, Report, RocDocAllocator};
+use crate::warnings::{SourceAllows, WarningConfig};
 
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub struct Problems {
@@ -51,14 +55,78 @@ impl Problems {
     }
 }
 
+/// A SARIF 2.1.0 log of reports rendered with [RenderTarget::Sarif]
+pub fn sarif_log<I>(results: I) -> String
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let results: Vec<Value> = results
+        .into_iter()
+        .map(|result| serde_json::from_str(result.as_ref()).expect("a rendered SARIF result"))
+        .collect();
+
+    sarif_log_of_values(results)
+}
+
+/// A SARIF 2.1.0 log of these results, which are SARIF result objects
+pub fn sarif_log_of_values(results: Vec<Value>) -> String {
+    let log = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "roc",
+                    "informationUri": "https://www.roc-lang.org",
+                },
+            },
+            "results": results,
+        }],
+    });
+
+    log.to_string()
+}
+
+/// Print a report which stopped compilation, e.g. a parse error, rendered with `render`
+pub fn print_fatal_report(report: &str, render: RenderTarget) {
+    match render {
+        RenderTarget::ColorTerminal | RenderTarget::Generic => print!("{}", report),
+        RenderTarget::Json => println!("{}", report),
+        RenderTarget::Sarif => println!("{}", sarif_log([report])),
+    }
+}
+
+fn render_report<'b>(
+    report: Report<'b>,
+    region: Option<LineColumnRegion>,
+    render: RenderTarget,
+    alloc: &'b RocDocAllocator<'b>,
+    palette: &'b Palette,
+) -> String {
+    let mut buf = String::new();
+
+    match render {
+        RenderTarget::Json => report.render_json(&mut buf, alloc, region),
+        RenderTarget::Sarif => report.render_sarif(&mut buf, alloc, region),
+        RenderTarget::ColorTerminal | RenderTarget::Generic => {
+            report.render(render, &mut buf, alloc, palette)
+        }
+    }
+
+    buf
+}
+
 pub fn report_problems(
     total_problems: usize,
     sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
     interns: &Interns,
     can_problems: &mut MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
     type_problems: &mut MutMap<ModuleId, Vec<TypeError>>,
+    render: RenderTarget,
+    warning_config: &WarningConfig,
 ) -> Problems {
-    use crate::report::{can_problem, type_problem, Report, RocDocAllocator, DEFAULT_PALETTE};
+    use crate::report::{can_problem, type_problem, DEFAULT_PALETTE};
     use roc_problem::Severity::*;
     let palette = DEFAULT_PALETTE;
 
@@ -74,6 +142,7 @@ pub fn report_problems(
         src_lines.extend(src.split('\n'));
 
         let lines = LineInfo::new(&src_lines.join("\n"));
+        let allows = SourceAllows::new(src);
 
         // Report parsing and canonicalization problems
         let alloc = RocDocAllocator::new(&src_lines, *home, interns);
@@ -81,11 +150,15 @@ pub fn report_problems(
         let problems = can_problems.remove(home).unwrap_or_default();
 
         for problem in problems.into_iter() {
+            let region = problem.region().map(|region| lines.convert_region(region));
             let report = can_problem(&alloc, &lines, module_path.clone(), problem);
+            let line = region.map(|region| region.start.line);
+            let report = match warning_config.configure(report, line, &allows) {
+                Some(report) => report,
+                None => continue,
+            };
             let severity = report.severity;
-            let mut buf = String::new();
-
-            report.render_color_terminal(&mut buf, &alloc, &palette);
+            let buf = render_report(report, region, render, &alloc, &palette);
 
             match severity {
                 Warning => {
@@ -104,11 +177,15 @@ pub fn report_problems(
         let problems = type_problems.remove(home).unwrap_or_default();
 
         for problem in problems {
-            if let Some(report) = type_problem(&alloc, &lines, module_path.clone(), problem) {
-                let severity = report.severity;
-                let mut buf = String::new();
+            let region = problem.region().map(|region| lines.convert_region(region));
 
-                report.render_color_terminal(&mut buf, &alloc, &palette);
+            let line = region.map(|region| region.start.line);
+            let report = type_problem(&alloc, &lines, module_path.clone(), problem)
+                .and_then(|report| warning_config.configure(report, line, &allows));
+
+            if let Some(report) = report {
+                let severity = report.severity;
+                let buf = render_report(report, region, render, &alloc, &palette);
 
                 match severity {
                     Warning => {
@@ -128,8 +205,20 @@ pub fn report_problems(
 
     let problems_reported;
 
-    // Only print warnings if there are no errors
-    if errors.is_empty() {
+    // Other programs get every problem, so they can e.g. annotate each line of a pull request
+    if render == RenderTarget::Sarif {
+        // no horizontal rule after these, because only a person would want one
+        problems_reported = 0;
+
+        println!("{}", sarif_log(errors.iter().chain(warnings.iter())));
+    } else if render == RenderTarget::Json {
+        problems_reported = 0;
+
+        for problem in errors.iter().chain(warnings.iter()) {
+            println!("{}", problem);
+        }
+    } else if errors.is_empty() {
+        // Only print warnings if there are no errors
         problems_reported = warnings.len();
 
         for warning in warnings.iter() {
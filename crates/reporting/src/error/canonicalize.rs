@@ -12,7 +12,9 @@ use roc_types::types::AliasKind;
 use std::path::PathBuf;
 
 use crate::error::r#type::suggest;
-use crate::report::{to_file_problem_report, Annotation, Report, RocDocAllocator, RocDocBuilder};
+use crate::report::{
+    to_file_problem_report, Annotation, ErrorCode, Report, RocDocAllocator, RocDocBuilder,
+};
 use ven_pretty::DocAllocator;
 
 const SYNTAX_PROBLEM: &str = "SYNTAX PROBLEM";
@@ -69,6 +71,7 @@ pub fn can_problem<'b>(
     let doc;
     let title;
     let severity = problem.severity();
+    let code = ErrorCode::of_can_problem(&problem);
 
     match problem {
         Problem::UnusedDef(symbol, region) => {
@@ -1105,6 +1108,7 @@ pub fn can_problem<'b>(
         filename,
         doc,
         severity,
+        code: Some(code),
     }
 }
 
@@ -1138,6 +1142,7 @@ fn to_invalid_optional_value_report<'b>(
         filename,
         doc,
         severity: Severity::RuntimeError,
+        code: Some(ErrorCode::BadOptionalValue),
     }
 }
 
@@ -0,0 +1,1582 @@
+//! Stable codes for every kind of problem we report, and the longer explanations that
+//! `roc explain` prints for them.
+//!
+//! Codes are grouped by the compiler phase that finds the problem: `R01xx` for parsing,
+//! `R02xx` for canonicalization and `R03xx` for type checking. Once a code has been handed
+//! out it must never be reused for a different problem, because people search for them
+//! and tools may match on them. Retired codes should be left as gaps in the numbering.
+use indoc::indoc;
+use roc_parse::parser::{
+    EAbility, EClosure, EExpect, EExpr, EIf, EInParens, EList, EString, EType, ETypeApply,
+    ETypeInParens, ETypeRecord, ETypeTagUnion, EWhen, SyntaxError,
+};
+use roc_problem::can::{MalformedPatternProblem, Problem, RuntimeError};
+use roc_solve_problem::TypeError;
+use std::fmt;
+
+macro_rules! error_codes {
+    ($($variant:ident = $number:literal, $name:literal, $explanation:expr;)*) => {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum ErrorCode {
+            $($variant,)*
+        }
+
+        impl ErrorCode {
+            pub const ALL: &'static [ErrorCode] = &[$(ErrorCode::$variant,)*];
+
+            pub fn number(self) -> u16 {
+                match self {
+                    $(ErrorCode::$variant => $number,)*
+                }
+            }
+
+            /// A short kebab-case name for the code, e.g. `unused-import`
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(ErrorCode::$variant => $name,)*
+                }
+            }
+
+            /// The longer explanation, with examples, that `roc explain` prints
+            pub fn explanation(self) -> &'static str {
+                match self {
+                    $(ErrorCode::$variant => $explanation,)*
+                }
+            }
+        }
+    };
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "R{:04}", self.number())
+    }
+}
+
+impl ErrorCode {
+    /// Look up a code by the form shown in reports (`R0202`), by its bare number (`202`),
+    /// or by its name (`unused-import`). Case is ignored.
+    pub fn lookup(code: &str) -> Option<ErrorCode> {
+        let code = code.trim().to_ascii_lowercase();
+        let digits = code.strip_prefix('r').unwrap_or(&code);
+
+        match digits.parse::<u16>() {
+            Ok(number) => Self::ALL.iter().copied().find(|c| c.number() == number),
+            Err(_) => Self::ALL.iter().copied().find(|c| c.name() == code),
+        }
+    }
+
+    /// Whether problems with this code are reported as warnings, which are the only problems that
+    /// can be allowed or denied. A few codes cover both a warning and an error: for example, a
+    /// duplicate field name is only a warning in a record value, not in a record type.
+    pub fn is_warning(self) -> bool {
+        use ErrorCode::*;
+
+        matches!(
+            self,
+            UnusedDef
+                | UnusedImport
+                | UnusedArgument
+                | UnusedBranchDef
+                | DefsOnlyUsedInRecursion
+                | UnusedAliasParameter
+                | DuplicateFieldName
+                | NotAnAbility
+                | DuplicateBoundAbility
+                | DuplicateImplementation
+                | UnnecessaryImplementations
+                | NoIdentifiersIntroduced
+                | OverloadedSpecialization
+                | UnnecessaryWildcard
+                | RedundantPattern
+                | UnmatchablePattern
+        )
+    }
+
+    pub fn of_syntax_error(error: &SyntaxError) -> ErrorCode {
+        use ErrorCode::*;
+
+        match error {
+            SyntaxError::Unexpected(_) => UnexpectedToken,
+            SyntaxError::OutdentedTooFar => OutdentedTooFar,
+            SyntaxError::TooManyLines => TooManyLines,
+            SyntaxError::Eof(_) => UnexpectedEndOfFile,
+            SyntaxError::InvalidPattern => InvalidPattern,
+            SyntaxError::BadUtf8 => BadUtf8,
+            SyntaxError::ReservedKeyword(_) => ReservedKeyword,
+            SyntaxError::ArgumentsBeforeEquals(_) => ArgumentsBeforeEquals,
+            SyntaxError::NotYetImplemented(_) | SyntaxError::Todo => NotYetImplemented,
+            SyntaxError::Type(error) => Self::of_type_syntax_error(error),
+            SyntaxError::Pattern(_) => PatternSyntax,
+            SyntaxError::Expr(error, _) => Self::of_expr_error(error),
+            SyntaxError::Header(_) => HeaderSyntax,
+            SyntaxError::Space(_) => WhitespaceSyntax,
+            SyntaxError::NotEndOfFile(_) => NotEndOfFile,
+        }
+    }
+
+    /// Expression errors nest: a problem inside the condition of an `if` is reported for the
+    /// innermost expression, so we follow the same path the report does.
+    fn of_expr_error(error: &EExpr) -> ErrorCode {
+        use ErrorCode::*;
+
+        match error {
+            EExpr::DefMissingFinalExpr2(nested, _) => Self::of_expr_error(nested),
+            EExpr::If(error, _) => match error {
+                EIf::Condition(nested, _)
+                | EIf::ThenBranch(nested, _)
+                | EIf::ElseBranch(nested, _) => Self::of_expr_error(nested),
+                EIf::Space(..) => WhitespaceSyntax,
+                _ => IfSyntax,
+            },
+            EExpr::When(error, _) => match error {
+                EWhen::Condition(nested, _) | EWhen::Branch(nested, _) => {
+                    Self::of_expr_error(nested)
+                }
+                EWhen::IfGuard(nested, _) => match nested {
+                    // An `->` right after `if` is a missing guard, which the `when` report covers
+                    EExpr::Start(_) | EExpr::IndentStart(_) => WhenSyntax,
+                    _ => Self::of_expr_error(nested),
+                },
+                EWhen::Pattern(..) => PatternSyntax,
+                EWhen::Space(..) => WhitespaceSyntax,
+                _ => WhenSyntax,
+            },
+            EExpr::Expect(error, _) | EExpr::Dbg(error, _) => match error {
+                EExpect::Condition(nested, _) | EExpect::Continuation(nested, _) => {
+                    Self::of_expr_error(nested)
+                }
+                EExpect::Space(..) => WhitespaceSyntax,
+                _ => ExpressionSyntax,
+            },
+            EExpr::Closure(error, _) => match error {
+                EClosure::Body(nested, _) => Self::of_expr_error(nested),
+                EClosure::Pattern(..) => PatternSyntax,
+                EClosure::Space(..) => WhitespaceSyntax,
+                _ => FunctionSyntax,
+            },
+            EExpr::List(error, _) => match error {
+                EList::Expr(nested, _) => Self::of_expr_error(nested),
+                EList::Space(..) => WhitespaceSyntax,
+                _ => ListSyntax,
+            },
+            EExpr::Str(error, _) => match error {
+                EString::Format(nested, _) => Self::of_expr_error(nested),
+                EString::Space(..) => WhitespaceSyntax,
+                _ => StringSyntax,
+            },
+            EExpr::InParens(error, _) => match error {
+                EInParens::Expr(nested, _) => Self::of_expr_error(nested),
+                EInParens::Space(..) => WhitespaceSyntax,
+                _ => ParenthesesSyntax,
+            },
+            EExpr::Ability(error, _) => match error {
+                EAbility::Type(error, _) => Self::of_type_syntax_error(error),
+                EAbility::Space(..) => WhitespaceSyntax,
+                _ => AbilityDefinitionSyntax,
+            },
+            EExpr::Type(error, _) => Self::of_type_syntax_error(error),
+            EExpr::Pattern(..) => PatternSyntax,
+            EExpr::Space(..) => WhitespaceSyntax,
+            EExpr::ElmStyleFunction(..) | EExpr::Colon(_) => ArgumentsBeforeEquals,
+            EExpr::BadOperator(..) => UnknownOperator,
+            EExpr::QualifiedTag(_) => QualifiedTag,
+            EExpr::Start(_) | EExpr::IndentStart(_) | EExpr::DefMissingFinalExpr(_) => {
+                MissingExpression
+            }
+            EExpr::BackpassArrow(_) => BadBackpassingArrow,
+            EExpr::Record(..) => RecordSyntax,
+            EExpr::Number(..) => InvalidNumberLiteral,
+            EExpr::IndentEnd(_) => IndentEndsAfterExpression,
+            _ => ExpressionSyntax,
+        }
+    }
+
+    /// Type errors only get their own code for whitespace; everything else in an annotation is
+    /// a [TypeAnnotationSyntax] problem.
+    fn of_type_syntax_error(error: &EType) -> ErrorCode {
+        use ErrorCode::*;
+
+        match error {
+            EType::TRecord(ETypeRecord::Type(nested, _), _)
+            | EType::TTagUnion(ETypeTagUnion::Type(nested, _), _)
+            | EType::TInParens(ETypeInParens::Type(nested, _), _) => {
+                Self::of_type_syntax_error(nested)
+            }
+            EType::Space(..)
+            | EType::TRecord(ETypeRecord::Space(..), _)
+            | EType::TTagUnion(ETypeTagUnion::Space(..), _)
+            | EType::TInParens(ETypeInParens::Space(..), _)
+            | EType::TApply(ETypeApply::Space(..), _) => WhitespaceSyntax,
+            _ => TypeAnnotationSyntax,
+        }
+    }
+
+    pub fn of_can_problem(problem: &Problem) -> ErrorCode {
+        use ErrorCode::*;
+
+        match problem {
+            Problem::UnusedDef(..) => UnusedDef,
+            Problem::UnusedImport(..) | Problem::UnusedModuleImport(..) => UnusedImport,
+            Problem::ExposedButNotDefined(_) => ExposedButNotDefined,
+            Problem::UnknownGeneratesWith(_) => UnknownGeneratesWith,
+            Problem::UnusedArgument(..) => UnusedArgument,
+            Problem::UnusedBranchDef(..) => UnusedBranchDef,
+            Problem::DefsOnlyUsedInRecursion(..) => DefsOnlyUsedInRecursion,
+            Problem::PrecedenceProblem(_) => PrecedenceConflict,
+            Problem::UnsupportedPattern(..) => UnsupportedPattern,
+            Problem::Shadowing { .. } => DuplicateName,
+            Problem::CyclicAlias(..) => CyclicAlias,
+            Problem::BadRecursion(_) => CircularDefinition,
+            Problem::PhantomTypeArgument { .. } => UnusedAliasParameter,
+            Problem::UnboundTypeVariable { .. } => UnboundTypeVariable,
+            Problem::DuplicateRecordFieldValue { .. }
+            | Problem::DuplicateRecordFieldType { .. } => DuplicateFieldName,
+            Problem::InvalidOptionalValue { .. } => BadOptionalValue,
+            Problem::DuplicateTag { .. } => DuplicateTagName,
+            Problem::RuntimeError(error) => Self::of_runtime_error(error),
+            Problem::SignatureDefMismatch { .. } => SignatureDefMismatch,
+            Problem::InvalidAliasRigid { .. } => InvalidAliasRigid,
+            Problem::InvalidInterpolation(_) => InvalidInterpolation,
+            Problem::InvalidHexadecimal(_) | Problem::InvalidUnicodeCodePt(_) => InvalidUnicode,
+            Problem::NestedDatatype { .. } => NestedDatatype,
+            Problem::InvalidExtensionType { .. } => InvalidExtensionType,
+            Problem::AbilityHasTypeVariables { .. } => AbilityHasTypeVariables,
+            Problem::HasClauseIsNotAbility { .. } | Problem::NotAnAbility(_) => NotAnAbility,
+            Problem::IllegalHasClause { .. } => IllegalHasClause,
+            Problem::DuplicateHasAbility { .. } => DuplicateBoundAbility,
+            Problem::AbilityMemberMissingHasClause { .. } => AbilityMemberMissingHasClause,
+            Problem::AbilityMemberMultipleBoundVars { .. } => AbilityMemberBindsMultipleVariables,
+            Problem::AbilityNotOnToplevel { .. } | Problem::NestedSpecialization(..) => {
+                AbilityNotOnToplevel
+            }
+            Problem::AbilityUsedAsType(..) => AbilityUsedAsType,
+            Problem::IllegalDerivedAbility(_) => IllegalDerive,
+            Problem::ImplementationNotFound { .. } => ImplementationNotFound,
+            Problem::NotAnAbilityMember { .. } => NotAnAbilityMember,
+            Problem::OptionalAbilityImpl { .. } => OptionalAbilityImplementation,
+            Problem::QualifiedAbilityImpl { .. } => QualifiedAbilityImplementation,
+            Problem::AbilityImplNotIdent { .. } => AbilityImplementationNotIdentifier,
+            Problem::DuplicateImpl { .. } => DuplicateImplementation,
+            Problem::ImplementsNonRequired { .. } => UnnecessaryImplementations,
+            Problem::DoesNotImplementAbility { .. } => IncompleteAbilityImplementation,
+            Problem::NotBoundInAllPatterns { .. } => NotBoundInAllPatterns,
+            Problem::NoIdentifiersIntroduced(_) => NoIdentifiersIntroduced,
+            Problem::OverloadedSpecialization { .. } => OverloadedSpecialization,
+            Problem::UnnecessaryOutputWildcard { .. } => UnnecessaryWildcard,
+            Problem::MultipleListRestPattern { .. } => MultipleListRestPatterns,
+            Problem::BadTypeArguments { .. } => BadTypeArguments,
+            Problem::UnappliedCrash { .. } => UnappliedCrash,
+            Problem::OverAppliedCrash { .. } => OverappliedCrash,
+            Problem::FileProblem { .. } => FileProblem,
+        }
+    }
+
+    pub fn of_runtime_error(error: &RuntimeError) -> ErrorCode {
+        use ErrorCode::*;
+
+        match error {
+            RuntimeError::Shadowing { .. } => DuplicateName,
+            RuntimeError::InvalidOptionalValue { .. } => BadOptionalValue,
+            RuntimeError::UnsupportedPattern(_) => UnsupportedPattern,
+            RuntimeError::MalformedPattern(problem, _) => match problem {
+                MalformedPatternProblem::MalformedInt
+                | MalformedPatternProblem::MalformedBase(_) => InvalidInt,
+                MalformedPatternProblem::MalformedFloat => InvalidFloat,
+                MalformedPatternProblem::BadIdent(_) => MalformedIdentifier,
+                MalformedPatternProblem::EmptySingleQuote
+                | MalformedPatternProblem::MultipleCharsInSingleQuote => InvalidCharacterLiteral,
+                MalformedPatternProblem::DuplicateListRestPattern => MultipleListRestPatterns,
+                MalformedPatternProblem::Unknown | MalformedPatternProblem::QualifiedIdentifier => {
+                    MalformedPattern
+                }
+            },
+            RuntimeError::LookupNotInScope(..) => UnrecognizedName,
+            RuntimeError::OpaqueNotDefined { .. } => OpaqueNotDefined,
+            RuntimeError::OpaqueOutsideScope { .. } => OpaqueDeclaredOutsideScope,
+            RuntimeError::OpaqueNotApplied(_) => OpaqueNotApplied,
+            RuntimeError::OpaqueAppliedToMultipleArgs(_) => OpaqueOverApplied,
+            RuntimeError::ValueNotExposed { .. } => ValueNotExposed,
+            RuntimeError::ModuleNotImported { .. } => ModuleNotImported,
+            RuntimeError::InvalidPrecedence(..) => PrecedenceConflict,
+            RuntimeError::MalformedIdentifier(..) | RuntimeError::MalformedTypeName(..) => {
+                MalformedIdentifier
+            }
+            RuntimeError::MalformedClosure(_) => MalformedClosure,
+            RuntimeError::InvalidRecordUpdate { .. } => InvalidRecordUpdate,
+            RuntimeError::InvalidFloat(..) => InvalidFloat,
+            RuntimeError::InvalidInt(..) => InvalidInt,
+            RuntimeError::CircularDef(_) => CircularDefinition,
+            RuntimeError::InvalidInterpolation(_) => InvalidInterpolation,
+            RuntimeError::InvalidHexadecimal(_) | RuntimeError::InvalidUnicodeCodePt(_) => {
+                InvalidUnicode
+            }
+            RuntimeError::ExposedButNotDefined(_) => ExposedButNotDefined,
+            RuntimeError::EmptySingleQuote(_) | RuntimeError::MultipleCharsInSingleQuote(_) => {
+                InvalidCharacterLiteral
+            }
+            RuntimeError::DegenerateBranch(_) => DegenerateBranch,
+            RuntimeError::UnresolvedTypeVar
+            | RuntimeError::ErroneousType
+            | RuntimeError::NonExhaustivePattern
+            | RuntimeError::NoImplementationNamed { .. }
+            | RuntimeError::NoImplementation
+            | RuntimeError::VoidValue => CompilerProblem,
+        }
+    }
+
+    pub fn of_type_error(error: &TypeError) -> ErrorCode {
+        use ErrorCode::*;
+
+        match error {
+            TypeError::BadExpr(..) => TypeMismatch,
+            TypeError::BadPattern(..) => PatternTypeMismatch,
+            TypeError::CircularType(..) => CircularType,
+            TypeError::CircularDef(_) => CircularDefinition,
+            TypeError::UnexposedLookup(_) => UnrecognizedName,
+            TypeError::UnfulfilledAbility(_)
+            | TypeError::BadExprMissingAbility(..)
+            | TypeError::BadPatternMissingAbility(..) => UnfulfilledAbility,
+            TypeError::Exhaustive(roc_exhaustive::Error::Incomplete(..)) => NonExhaustivePattern,
+            TypeError::Exhaustive(roc_exhaustive::Error::Redundant { .. }) => RedundantPattern,
+            TypeError::Exhaustive(roc_exhaustive::Error::Unmatchable { .. }) => UnmatchablePattern,
+            TypeError::StructuralSpecialization { .. } => StructuralSpecialization,
+            TypeError::WrongSpecialization { .. } => WrongSpecialization,
+            TypeError::IngestedFileBadUtf8(..) => IngestedFileBadUtf8,
+            TypeError::IngestedFileUnsupportedType(..) => IngestedFileUnsupportedType,
+        }
+    }
+}
+
+error_codes! {
+    // Parsing
+
+    UnexpectedToken = 101, "unexpected-token", indoc!(
+        r#"
+        The parser ran into something it did not expect at this point in the file.
+
+        This is most often a stray character, or a symbol that has no meaning in Roc:
+
+            total = 1 + 2 ;
+
+        Remove the unexpected character, or check whether an operator or closing bracket
+        is missing just before it.
+        "#
+    );
+    OutdentedTooFar = 102, "outdented-too-far", indoc!(
+        r#"
+        A line is indented less than the expression it continues.
+
+        Roc uses indentation to decide where an expression ends. Every line that continues
+        a definition must be indented further than the start of that definition:
+
+            greeting =
+            "Hello"
+
+        Indent the continuation:
+
+            greeting =
+                "Hello"
+        "#
+    );
+    TooManyLines = 103, "too-many-lines", indoc!(
+        r#"
+        The file has more lines than the compiler can track positions for.
+
+        Split the module into several smaller modules.
+        "#
+    );
+    UnexpectedEndOfFile = 104, "unexpected-end-of-file", indoc!(
+        r#"
+        The file ended in the middle of an expression or definition.
+
+        This usually means a definition has no body, or a bracket or string was never
+        closed:
+
+            main =
+
+        Finish the expression, or remove the incomplete definition.
+        "#
+    );
+    InvalidPattern = 105, "invalid-pattern", indoc!(
+        r#"
+        Something appears where a pattern was expected, but it is not a valid pattern.
+
+        Patterns are things like names, tags, literals, records and lists:
+
+            when color is
+                Red -> 1
+                x + 1 -> 2
+
+        An arithmetic expression like `x + 1` can't be matched against. Use a name and
+        compute with it in the branch instead.
+        "#
+    );
+    BadUtf8 = 106, "bad-utf8", indoc!(
+        r#"
+        The source file is not valid UTF-8.
+
+        Roc source files must be encoded as UTF-8. Re-save the file with UTF-8 encoding.
+        "#
+    );
+    ReservedKeyword = 107, "reserved-keyword", indoc!(
+        r#"
+        A reserved keyword is used where a name was expected.
+
+        Words like `if`, `then`, `else`, `when`, `is`, `as`, `expect`, `dbg` and `crash`
+        have special meaning and can't be used as names:
+
+            when = 5
+
+        Pick a different name, like `whenever` or `moment`.
+        "#
+    );
+    ArgumentsBeforeEquals = 108, "arguments-before-equals", indoc!(
+        r#"
+        A definition has arguments written before the `=`.
+
+        Roc functions are values, so their arguments go in a lambda on the right of the `=`:
+
+            increment n = n + 1
+
+        Write it like this instead:
+
+            increment = \n -> n + 1
+        "#
+    );
+    NotYetImplemented = 109, "not-yet-implemented", indoc!(
+        r#"
+        The parser recognized this syntax, but the compiler does not support it yet.
+
+        Rewrite the code without it, and consider letting us know on Zulip that you ran
+        into this.
+        "#
+    );
+    TypeAnnotationSyntax = 110, "type-annotation-syntax", indoc!(
+        r#"
+        A type annotation could not be parsed.
+
+        This is often an unclosed record or tag union type, or a missing arrow:
+
+            greet : Str Str
+
+        Function types separate their arguments from their result with `->`:
+
+            greet : Str -> Str
+        "#
+    );
+    PatternSyntax = 111, "pattern-syntax", indoc!(
+        r#"
+        A pattern could not be parsed.
+
+        This is often an unclosed record or list pattern:
+
+            { x, y -> x + y
+
+        Close the pattern before continuing:
+
+            \{ x, y } -> x + y
+        "#
+    );
+    ExpressionSyntax = 112, "expression-syntax", indoc!(
+        r#"
+        An expression could not be parsed.
+
+        The report names the construct that was being parsed, such as a record, list,
+        `if` or `when`, and points at where it went wrong:
+
+            point = { x: 1, y: 2
+
+        Most often something is missing, like the closing `}` here:
+
+            point = { x: 1, y: 2 }
+        "#
+    );
+    HeaderSyntax = 113, "header-syntax", indoc!(
+        r#"
+        The module header at the top of the file could not be parsed.
+
+        Each kind of module has its own header. An interface looks like this:
+
+            interface Parser
+                exposes [parse]
+                imports []
+
+        and an application looks like this:
+
+            app "hello"
+                packages { pf: "platform/main.roc" }
+                imports [pf.Stdout]
+                provides [main] to pf
+
+        Check that every section is present, in this order, and that lists use `[` and `]`.
+        "#
+    );
+    WhitespaceSyntax = 114, "whitespace-syntax", indoc!(
+        r#"
+        The file contains whitespace or control characters that Roc does not allow.
+
+        Roc code must be indented with spaces. Tab characters, carriage returns that are
+        not followed by a newline, and other ASCII control characters are not allowed.
+
+        Configure your editor to insert spaces, or run `roc format`.
+        "#
+    );
+    NotEndOfFile = 115, "not-end-of-file", indoc!(
+        r#"
+        The parser finished reading the module's definitions, but there is more in the file.
+
+        This is usually a definition that is indented when it should start at the
+        beginning of a line, or an expression at the top level that is not part of a
+        definition:
+
+            main = "Hello"
+              more = 1
+
+        Start top-level definitions in the first column.
+        "#
+    );
+    UnknownOperator = 116, "unknown-operator", indoc!(
+        r#"
+        An operator was used that Roc does not have, or one that is not allowed here.
+
+        Some operators from other languages have different names in Roc:
+
+            greeting = "Hello, " ++ name
+
+        Use a function instead:
+
+            greeting = Str.concat "Hello, " name
+
+        The `->` arrow is only used in function definitions and `when` branches, and `:`
+        only in type annotations.
+        "#
+    );
+    QualifiedTag = 117, "qualified-tag", indoc!(
+        r#"
+        A tag was written with a module name in front of it.
+
+        Tags are not defined in modules, so they can't be qualified:
+
+            color = Color.Red
+
+        Write the tag on its own:
+
+            color = Red
+        "#
+    );
+    MissingExpression = 118, "missing-expression", indoc!(
+        r#"
+        An expression was expected, but there was none.
+
+        This happens when a definition, branch or argument is left empty, and when
+        nested definitions are not followed by a final expression:
+
+            area =
+                width = 4
+                height = 2
+
+        Finish with the expression that the definitions are for:
+
+            area =
+                width = 4
+                height = 2
+
+                width * height
+        "#
+    );
+    BadBackpassingArrow = 119, "bad-backpassing-arrow", indoc!(
+        r#"
+        A backpassing arrow `<-` was found where it isn't allowed.
+
+        A backpassing arrow must have patterns on its left and a function call on its
+        right, and it must be followed by the rest of the body on the next line:
+
+            name <- Task.await Stdin.line
+            Stdout.line "Hi, \(name)!"
+        "#
+    );
+    RecordSyntax = 120, "record-syntax", indoc!(
+        r#"
+        A record could not be parsed.
+
+        Records are written with `{` and `}`, with a `:` between each field name and its
+        value, and commas between fields:
+
+            point = { x: 1, y: 2 }
+
+        Field names start with a lowercase letter.
+        "#
+    );
+    InvalidNumberLiteral = 121, "invalid-number-literal", indoc!(
+        r#"
+        A number literal could not be parsed.
+
+        Numbers may use `_` as a separator and may end in a type suffix, but nothing else
+        can come straight after the digits:
+
+            size = 1_000u32
+
+        Hex, octal and binary numbers start with `0x`, `0o` and `0b`.
+        "#
+    );
+    AbilityDefinitionSyntax = 122, "ability-definition-syntax", indoc!(
+        r#"
+        An ability definition could not be parsed.
+
+        An ability is named, followed by `has`, and then lists the functions its members
+        must implement, each with a type annotation:
+
+            Hash has
+                hash : a -> U64 | a has Hash
+        "#
+    );
+    IndentEndsAfterExpression = 123, "indent-ends-after-expression", indoc!(
+        r#"
+        An expression was followed by something that is indented less than it, but is not
+        the start of a new definition.
+
+        Check that every line of a multi-line expression is indented at least as far as
+        the line it started on.
+        "#
+    );
+    IfSyntax = 124, "if-syntax", indoc!(
+        r#"
+        An `if` expression could not be parsed.
+
+        Every `if` needs a `then` branch and an `else` branch:
+
+            sign = if n < 0 then "negative" else "positive"
+        "#
+    );
+    WhenSyntax = 125, "when-syntax", indoc!(
+        r#"
+        A `when` expression could not be parsed.
+
+        A `when` is followed by the value to match and `is`, and then by branches, each
+        with a pattern, an optional `if` guard, an `->` arrow and a body:
+
+            when color is
+                Red -> "red"
+                Other name if name != "" -> name
+                _ -> "unknown"
+
+        All the branch patterns must start in the same column.
+        "#
+    );
+    FunctionSyntax = 126, "function-syntax", indoc!(
+        r#"
+        A function could not be parsed.
+
+        Functions start with `\`, then the arguments separated by commas, then `->` and
+        the body:
+
+            add = \x, y -> x + y
+        "#
+    );
+    ListSyntax = 127, "list-syntax", indoc!(
+        r#"
+        A list could not be parsed.
+
+        Lists are written with `[` and `]`, with commas between the elements:
+
+            numbers = [1, 2, 3]
+
+        Most often the closing `]` or a comma is missing.
+        "#
+    );
+    StringSyntax = 128, "string-syntax", indoc!(
+        r#"
+        A string or character literal could not be parsed.
+
+        Strings need a closing `"`, and only a few escapes are allowed after `\`: `\n`,
+        `\r`, `\t`, `\"`, `\'`, `\\`, `\u(...)` for a code point, and `\(...)` for
+        interpolation:
+
+            greeting = "Hello, \(name)!\n"
+
+        Multi-line strings are written between `"""`. Single quotes are only for a single
+        character, like `'a'`.
+        "#
+    );
+    ParenthesesSyntax = 129, "parentheses-syntax", indoc!(
+        r#"
+        Parentheses could not be parsed.
+
+        Parentheses must contain exactly one expression and be closed with `)`. Empty
+        parentheses `()` are not a value in Roc; use an empty record `{}` instead.
+        "#
+    );
+
+    // Canonicalization
+
+    UnusedDef = 201, "unused-def", indoc!(
+        r#"
+        A definition is never used.
+
+            main =
+                unused = 1
+                "Hello"
+
+        Remove the definition, or use it. Unused definitions are a warning, so the program
+        still runs.
+        "#
+    );
+    UnusedImport = 202, "unused-import", indoc!(
+        r#"
+        A module or a value is imported but never used.
+
+            interface Greet
+                exposes [greet]
+                imports [Dict]
+
+            greet = \name -> "Hello, \(name)!"
+
+        Remove `Dict` from the `imports` list. Unused imports are a warning, so the program
+        still runs.
+        "#
+    );
+    ExposedButNotDefined = 203, "exposed-but-not-defined", indoc!(
+        r#"
+        The module header exposes a name that the module never defines.
+
+            interface Greet
+                exposes [greet, farewell]
+                imports []
+
+            greet = \name -> "Hello, \(name)!"
+
+        Either define `farewell`, or remove it from the `exposes` list.
+        "#
+    );
+    UnknownGeneratesWith = 204, "unknown-generates-with", indoc!(
+        r#"
+        A hosted module's `generates ... with` clause names a function that does not exist.
+
+        Only the functions listed in the `with` clause of a `generates` header can be
+        generated. Check the spelling against the functions the platform provides.
+        "#
+    );
+    UnusedArgument = 205, "unused-argument", indoc!(
+        r#"
+        A function argument is never used in the function body.
+
+            add = \x, y -> x + 1
+
+        If the argument is needed to match a required signature, prefix it with an
+        underscore to say that it is unused on purpose:
+
+            add = \x, _y -> x + 1
+        "#
+    );
+    UnusedBranchDef = 206, "unused-branch-def", indoc!(
+        r#"
+        A `when` branch binds a name that the branch never uses.
+
+            when result is
+                Ok value -> "ok"
+                Err _ -> "error"
+
+        Replace the name with `_`, or prefix it with an underscore:
+
+            when result is
+                Ok _value -> "ok"
+                Err _ -> "error"
+        "#
+    );
+    DefsOnlyUsedInRecursion = 207, "defs-only-used-in-recursion", indoc!(
+        r#"
+        One or more definitions only use each other, and nothing else uses them.
+
+            countdown = \n -> if n == 0 then 0 else countdown (n - 1)
+
+        If nothing outside `countdown` calls it, it can be removed.
+        "#
+    );
+    PrecedenceConflict = 208, "precedence-conflict", indoc!(
+        r#"
+        Two operators that don't associate are used together without parentheses.
+
+        Comparison operators like `==` and `<` can't be chained:
+
+            1 == 1 == 1
+
+        Add parentheses to say which comparison happens first:
+
+            (1 == 1) == Bool.true
+        "#
+    );
+    UnsupportedPattern = 209, "unsupported-pattern", indoc!(
+        r#"
+        A pattern is used in a place where it is not allowed.
+
+        Definitions and function arguments must match every possible value, so they can't
+        use patterns that might fail, like number literals or single tags of a larger union:
+
+            5 = 2 + 3
+
+        Use a `when` expression to match on values that might not fit the pattern.
+        "#
+    );
+    DuplicateName = 210, "duplicate-name", indoc!(
+        r#"
+        A name is defined again while an earlier definition with the same name is in scope.
+
+        Roc does not allow shadowing:
+
+            x = 1
+            f = \x -> x + 1
+
+        Give the inner definition a different name:
+
+            x = 1
+            f = \n -> n + 1
+        "#
+    );
+    CyclicAlias = 211, "cyclic-alias", indoc!(
+        r#"
+        A type alias refers to itself, directly or through other aliases.
+
+            Tree : [Leaf, Node Tree Tree]
+
+        Aliases are only names for types, so they can't be recursive. Use an opaque type
+        to define a recursive type:
+
+            Tree := [Leaf, Node Tree Tree]
+        "#
+    );
+    CircularDefinition = 212, "circular-definition", indoc!(
+        r#"
+        A value is defined in terms of itself, so it could never be computed.
+
+            x = y + 1
+            y = x + 1
+
+        Recursion is only allowed through functions. Break the cycle, or turn the values
+        into functions.
+        "#
+    );
+    UnusedAliasParameter = 213, "unused-alias-parameter", indoc!(
+        r#"
+        A type alias has a type parameter that its definition never uses.
+
+            Pair a b : { first : a, second : a }
+
+        Use the parameter, or remove it:
+
+            Pair a : { first : a, second : a }
+        "#
+    );
+    UnboundTypeVariable = 214, "unbound-type-variable", indoc!(
+        r#"
+        A type alias or opaque type uses a type variable that is not one of its parameters.
+
+            Result : [Ok a, Err e]
+
+        Add the variables as parameters:
+
+            Result a e : [Ok a, Err e]
+        "#
+    );
+    DuplicateFieldName = 215, "duplicate-field-name", indoc!(
+        r#"
+        A record or record type mentions the same field more than once.
+
+            user = { name: "Ana", age: 31, name: "Bo" }
+
+        Remove one of the fields. In a record value, the last one is the one that is used.
+        "#
+    );
+    BadOptionalValue = 216, "bad-optional-value", indoc!(
+        r#"
+        An optional field default (`field ? default`) is used outside a record destructure.
+
+            config = { verbose ? Bool.false }
+
+        Optional fields can only have defaults when destructuring a record argument:
+
+            run = \{ verbose ? Bool.false } -> verbose
+        "#
+    );
+    DuplicateTagName = 217, "duplicate-tag-name", indoc!(
+        r#"
+        A tag union type mentions the same tag more than once.
+
+            Color : [Red, Green, Red]
+
+        Remove the duplicate tag.
+        "#
+    );
+    SignatureDefMismatch = 218, "signature-def-mismatch", indoc!(
+        r#"
+        A type annotation is followed by a definition with a different name.
+
+            double : I64 -> I64
+            triple = \n -> n * 3
+
+        The annotation and the definition must be next to each other and use the same name.
+        "#
+    );
+    InvalidAliasRigid = 219, "invalid-alias-rigid", indoc!(
+        r#"
+        A type alias has a parameter that is not a type variable.
+
+            Wrapper Str : { value : Str }
+
+        Alias parameters must be lowercase type variables:
+
+            Wrapper a : { value : a }
+        "#
+    );
+    InvalidInterpolation = 220, "invalid-interpolation", indoc!(
+        r#"
+        A string interpolation contains something other than a name.
+
+            "Total: \(1 + 2)"
+
+        Define the value first and interpolate its name:
+
+            total = Num.toStr (1 + 2)
+            "Total: \(total)"
+        "#
+    );
+    InvalidUnicode = 221, "invalid-unicode", indoc!(
+        r#"
+        A `\u(...)` escape in a string is not a valid Unicode code point.
+
+            "\u(zzzz)"
+
+        The parentheses must contain between 1 and 8 hexadecimal digits that name a
+        valid code point:
+
+            "\u(00E9)"
+        "#
+    );
+    NestedDatatype = 222, "nested-datatype", indoc!(
+        r#"
+        A recursive type refers to itself with different type arguments.
+
+            Nested a := [Chain a (Nested (List a)), Term]
+
+        Roc does not support these "nested datatypes". Recursive references must pass the
+        same type arguments as the definition:
+
+            Nested a := [Chain a (Nested a), Term]
+        "#
+    );
+    InvalidExtensionType = 223, "invalid-extension-type", indoc!(
+        r#"
+        A record or tag union type is extended with something that is not a record or tag
+        union.
+
+            Point : { x : I64 }Str
+
+        The extension must be a type variable, or an alias of a record or tag union of the
+        same kind:
+
+            Point a : { x : I64 }a
+        "#
+    );
+    AbilityHasTypeVariables = 224, "ability-has-type-variables", indoc!(
+        r#"
+        An ability is declared with type variables.
+
+            Hash a has hash : a -> U64 | a has Hash
+
+        Abilities don't take type variables. Each member's type mentions the implementing
+        type with a `has` clause instead:
+
+            Hash has hash : a -> U64 | a has Hash
+        "#
+    );
+    NotAnAbility = 225, "not-an-ability", indoc!(
+        r#"
+        Something that is not an ability is used where an ability was expected.
+
+            isEqual : a, a -> Bool | a has Str
+
+        `has` clauses and `has [...]` implementation lists must name abilities, like
+        `Eq` or `Hash`:
+
+            isEqual : a, a -> Bool | a has Eq
+        "#
+    );
+    IllegalHasClause = 226, "illegal-has-clause", indoc!(
+        r#"
+        A `has` clause is used somewhere it is not allowed.
+
+            Wrapper a : { value : a } | a has Eq
+
+        `has` clauses can only be used in the type annotation of a value or an ability
+        member.
+        "#
+    );
+    DuplicateBoundAbility = 227, "duplicate-bound-ability", indoc!(
+        r#"
+        A `has` clause lists the same ability more than once for a type variable.
+
+            same : a, a -> Bool | a has Eq & Eq
+
+        Remove the duplicate:
+
+            same : a, a -> Bool | a has Eq
+        "#
+    );
+    AbilityMemberMissingHasClause = 228, "ability-member-missing-has-clause", indoc!(
+        r#"
+        An ability member's type does not say which type variable implements the ability.
+
+            Hash has hash : a -> U64
+
+        Add a `has` clause binding the implementing type to the ability:
+
+            Hash has hash : a -> U64 | a has Hash
+        "#
+    );
+    AbilityMemberBindsMultipleVariables = 229, "ability-member-binds-multiple-variables", indoc!(
+        r#"
+        An ability member binds more than one type variable to the ability being defined.
+
+            Eq has isEq : a, b -> Bool | a has Eq, b has Eq
+
+        Each member must be about exactly one implementing type:
+
+            Eq has isEq : a, a -> Bool | a has Eq
+        "#
+    );
+    AbilityNotOnToplevel = 230, "ability-not-on-toplevel", indoc!(
+        r#"
+        An ability, or an implementation of an ability member, is defined inside another
+        definition.
+
+            main =
+                Hash has hash : a -> U64 | a has Hash
+                "hello"
+
+        Abilities and their implementations must be defined at the top level of a module.
+        "#
+    );
+    AbilityUsedAsType = 231, "ability-used-as-type", indoc!(
+        r#"
+        An ability is used as if it were a type.
+
+            hashAll : List Hash -> U64
+
+        Abilities describe what a type can do, so use a type variable bound to the ability:
+
+            hashAll : List a -> U64 | a has Hash
+        "#
+    );
+    IllegalDerive = 232, "illegal-derive", indoc!(
+        r#"
+        An opaque type asks to derive an ability that can't be derived.
+
+        Only the builtin abilities `Eq`, `Hash`, `Encoding` and `Decoding` can
+        be derived. Other abilities need an explicit implementation:
+
+            Id := U64 has [Eq { isEq: idIsEq }]
+        "#
+    );
+    ImplementationNotFound = 233, "implementation-not-found", indoc!(
+        r#"
+        An ability implementation list refers to a function that is not defined.
+
+            Id := U64 has [Eq { isEq: idIsEq }]
+
+        Define `idIsEq` in the same module as `Id`:
+
+            idIsEq = \@Id a, @Id b -> a == b
+        "#
+    );
+    NotAnAbilityMember = 234, "not-an-ability-member", indoc!(
+        r#"
+        An implementation is given for something that is not a member of the ability.
+
+            Id := U64 has [Eq { isEqual: idIsEq }]
+
+        Use the member's exact name, which for `Eq` is `isEq`:
+
+            Id := U64 has [Eq { isEq: idIsEq }]
+        "#
+    );
+    OptionalAbilityImplementation = 235, "optional-ability-implementation", indoc!(
+        r#"
+        An ability implementation uses optional field syntax.
+
+            Id := U64 has [Eq { isEq ? idIsEq }]
+
+        Ability members must be implemented, so use `:`:
+
+            Id := U64 has [Eq { isEq: idIsEq }]
+        "#
+    );
+    QualifiedAbilityImplementation = 236, "qualified-ability-implementation", indoc!(
+        r#"
+        An ability implementation refers to a function in another module.
+
+            Id := U64 has [Eq { isEq: Other.isEq }]
+
+        Implementations must be defined in the same module as the opaque type.
+        "#
+    );
+    AbilityImplementationNotIdentifier = 237, "ability-implementation-not-identifier", indoc!(
+        r#"
+        An ability implementation is given as an expression instead of a name.
+
+            Id := U64 has [Eq { isEq: \@Id a, @Id b -> a == b }]
+
+        Define the implementation separately and refer to it by name:
+
+            Id := U64 has [Eq { isEq: idIsEq }]
+
+            idIsEq = \@Id a, @Id b -> a == b
+        "#
+    );
+    DuplicateImplementation = 238, "duplicate-implementation", indoc!(
+        r#"
+        The same ability member is implemented more than once for a type.
+
+            Id := U64 has [Eq { isEq: idIsEq, isEq: idIsEq2 }]
+
+        Remove one of the implementations.
+        "#
+    );
+    UnnecessaryImplementations = 239, "unnecessary-implementations", indoc!(
+        r#"
+        An implementation list is given for an ability that is derived, or that has no
+        members to implement.
+
+            Id := U64 has [Encoding { toEncoder: idToEncoder }]
+
+        If the ability can be derived, list it without implementations:
+
+            Id := U64 has [Encoding]
+        "#
+    );
+    IncompleteAbilityImplementation = 240, "incomplete-ability-implementation", indoc!(
+        r#"
+        A type claims an ability but does not implement all of its members.
+
+            Hash has
+                hash : a -> U64 | a has Hash
+                hashAll : List a -> U64 | a has Hash
+
+            Id := U64 has [Hash { hash: idHash }]
+
+        Implement every member of the ability:
+
+            Id := U64 has [Hash { hash: idHash, hashAll: idHashAll }]
+        "#
+    );
+    NotBoundInAllPatterns = 241, "not-bound-in-all-patterns", indoc!(
+        r#"
+        A `when` branch with several patterns binds a name in some patterns but not all.
+
+            when shape is
+                Circle radius | Square side -> radius
+
+        Every alternative must bind the same names, so the branch body can use them no
+        matter which pattern matched:
+
+            when shape is
+                Circle size | Square size -> size
+        "#
+    );
+    NoIdentifiersIntroduced = 242, "no-identifiers-introduced", indoc!(
+        r#"
+        A definition's pattern introduces no names, so the definition can never be used.
+
+            _ = compute 5
+
+        If the value is needed, give it a name. If it is only computed for its effects,
+        consider whether those effects should be a task instead.
+        "#
+    );
+    OverloadedSpecialization = 243, "overloaded-specialization", indoc!(
+        r#"
+        An ability member is implemented more than once for the same type.
+
+        Each type may only implement each ability member once. Remove one of the
+        implementations.
+        "#
+    );
+    UnnecessaryWildcard = 244, "unnecessary-wildcard", indoc!(
+        r#"
+        A type annotation uses a wildcard `*` in a position where it has no effect.
+
+            colors : List [Red, Green]*
+
+        Tag unions and records in output positions don't need to be open. Remove the `*`:
+
+            colors : List [Red, Green]
+        "#
+    );
+    MultipleListRestPatterns = 245, "multiple-list-rest-patterns", indoc!(
+        r#"
+        A list pattern contains more than one `..` rest pattern.
+
+            when list is
+                [first, .., middle, ..] -> middle
+
+        A list pattern can have at most one rest pattern:
+
+            when list is
+                [first, .., last] -> last
+        "#
+    );
+    BadTypeArguments = 246, "bad-type-arguments", indoc!(
+        r#"
+        A type is applied to the wrong number of type arguments.
+
+            names : List Str Str
+
+        `List` takes exactly one type argument:
+
+            names : List Str
+        "#
+    );
+    UnappliedCrash = 247, "unapplied-crash", indoc!(
+        r#"
+        `crash` is used without a message.
+
+            main = crash
+
+        `crash` must be given exactly one string describing why the program stopped:
+
+            main = crash "This should never happen!"
+        "#
+    );
+    OverappliedCrash = 248, "overapplied-crash", indoc!(
+        r#"
+        `crash` is given more than one argument.
+
+            main = crash "Unreachable" 42
+
+        `crash` must be given exactly one message:
+
+            main = crash "Unreachable: 42"
+        "#
+    );
+    FileProblem = 249, "file-problem", indoc!(
+        r#"
+        A file referenced by the module could not be read.
+
+        Check that the path is correct, that the file exists, and that it can be read by
+        the user running `roc`.
+        "#
+    );
+    UnrecognizedName = 250, "unrecognized-name", indoc!(
+        r#"
+        A name is used that is not defined or imported anywhere in scope.
+
+            main = Str.concat greeting "!"
+
+        Define the name, fix its spelling, or import it from the module that exposes it.
+        The report lists similarly named values that are in scope.
+        "#
+    );
+    OpaqueNotDefined = 251, "opaque-not-defined", indoc!(
+        r#"
+        An opaque type is used with `@` but no opaque type by that name is defined.
+
+            age = @Age 23
+
+        Define the opaque type in this module:
+
+            Age := U32
+        "#
+    );
+    OpaqueDeclaredOutsideScope = 252, "opaque-declared-outside-scope", indoc!(
+        r#"
+        An opaque type is wrapped or unwrapped with `@` outside the module that defines it.
+
+        Only the defining module can see inside an opaque type. Other modules must use the
+        functions that module exposes:
+
+            age = Age.fromYears 23
+        "#
+    );
+    OpaqueNotApplied = 253, "opaque-not-applied", indoc!(
+        r#"
+        An opaque type is used with `@` but is not given a value to wrap.
+
+            age = @Age
+
+        Wrap a value:
+
+            age = @Age 23
+        "#
+    );
+    OpaqueOverApplied = 254, "opaque-over-applied", indoc!(
+        r#"
+        An opaque type is used with `@` and given more than one value.
+
+            point = @Point 1 2
+
+        An opaque type wraps exactly one value. Wrap a record or tuple instead:
+
+            point = @Point { x: 1, y: 2 }
+        "#
+    );
+    ValueNotExposed = 255, "value-not-exposed", indoc!(
+        r#"
+        A module is asked for a value that it does not expose.
+
+            List.isempty []
+
+        Check the spelling against the values the module exposes. The report lists the
+        most similar ones:
+
+            List.isEmpty []
+        "#
+    );
+    ModuleNotImported = 256, "module-not-imported", indoc!(
+        r#"
+        A qualified name refers to a module that is not imported.
+
+            main = Parser.parse "1 + 2"
+
+        Add the module to the `imports` list in the module header.
+        "#
+    );
+    MalformedPattern = 257, "malformed-pattern", indoc!(
+        r#"
+        A pattern is syntactically valid but can't be used as a pattern.
+
+        Qualified names like `Str.x` can't be bound in a pattern:
+
+            \Str.x -> x
+
+        Use a plain lowercase name instead.
+        "#
+    );
+    MalformedIdentifier = 258, "malformed-identifier", indoc!(
+        r#"
+        A name is not well formed.
+
+        Value names start with a lowercase letter and type names with an uppercase letter.
+        Neither may contain underscores in the middle or end in a `.`:
+
+            my_value = 1
+
+        Use camelCase:
+
+            myValue = 1
+        "#
+    );
+    MalformedClosure = 259, "malformed-closure", indoc!(
+        r#"
+        A function (`\args -> body`) is not well formed.
+
+            add = \ -> 1
+
+        Functions need at least one argument pattern before the `->`:
+
+            add = \x -> x + 1
+        "#
+    );
+    InvalidFloat = 260, "invalid-float", indoc!(
+        r#"
+        A fractional number literal is not valid.
+
+        It might contain characters that are not digits, be too large to represent, or have
+        an integer type suffix:
+
+            x = 1.5i64
+
+        Remove the invalid characters, or use a fractional suffix like `f64` or `dec`.
+        "#
+    );
+    InvalidInt = 261, "invalid-int", indoc!(
+        r#"
+        An integer literal is not valid.
+
+        It might contain digits that don't belong to its base, be too large or too small for
+        its type suffix, or have a fractional type suffix:
+
+            x = 300u8
+
+        `U8` can hold numbers from 0 to 255. Use a wider type:
+
+            x = 300u16
+        "#
+    );
+    InvalidRecordUpdate = 262, "invalid-record-update", indoc!(
+        r#"
+        A record update uses something other than a plain name as the record to update.
+
+            { getUser {} & name: "Ana" }
+
+        Name the record first:
+
+            user = getUser {}
+            { user & name: "Ana" }
+        "#
+    );
+    InvalidCharacterLiteral = 263, "invalid-character-literal", indoc!(
+        r#"
+        A single-quoted character literal is empty or contains more than one character.
+
+            letter = 'ab'
+
+        Single quotes hold exactly one character. Use double quotes for strings:
+
+            letter = 'a'
+            word = "ab"
+        "#
+    );
+    DegenerateBranch = 264, "degenerate-branch", indoc!(
+        r#"
+        A `when` branch has a pattern that does not bind the names the branch body uses.
+
+        Every name the branch uses must be bound by each of its patterns. See also
+        `roc explain not-bound-in-all-patterns`.
+        "#
+    );
+    CompilerProblem = 299, "compiler-problem", indoc!(
+        r#"
+        The compiler ran into an internal problem.
+
+        This is a bug in the compiler, not in your code. Please report it at
+        https://github.com/roc-lang/roc/issues, ideally with the code that triggered it.
+        "#
+    );
+
+    // Type checking
+
+    TypeMismatch = 301, "type-mismatch", indoc!(
+        r#"
+        An expression has a different type than its context needs.
+
+            greeting : Str
+            greeting = 42
+
+        The report shows the type that was found and the type that was expected. Change
+        the expression or the annotation so they agree:
+
+            greeting : Str
+            greeting = "42"
+        "#
+    );
+    PatternTypeMismatch = 302, "pattern-type-mismatch", indoc!(
+        r#"
+        A pattern can never match the type of the value it is matched against.
+
+            when "hello" is
+                Ok s -> s
+
+        `"hello"` is a `Str`, so it can't match a tag. Match against string patterns, or
+        fix the value being matched.
+        "#
+    );
+    CircularType = 303, "circular-type", indoc!(
+        r#"
+        A value would need an infinitely large type.
+
+            f = \x -> f [x]
+
+        `f` would have to accept `x`, a list of `x`, a list of lists of `x`, and so on. Use
+        a recursive tag union or opaque type to describe the data explicitly.
+        "#
+    );
+    UnfulfilledAbility = 304, "unfulfilled-ability", indoc!(
+        r#"
+        A value is used somewhere that needs an ability its type does not have.
+
+            Id := U64
+
+            same = \a, b -> a == b
+
+            same (@Id 1) (@Id 2)
+
+        `==` needs `Eq`, which `Id` does not have. Add it to the opaque type:
+
+            Id := U64 has [Eq]
+
+        Functions can't have `Eq` or `Hash`, so they can't be compared or used as `Dict`
+        keys.
+        "#
+    );
+    NonExhaustivePattern = 305, "non-exhaustive-pattern", indoc!(
+        r#"
+        A pattern does not cover every possible value.
+
+            when color is
+                Red -> 1
+                Green -> 2
+
+        If `color` can also be `Blue`, this program would crash on it. Add the missing
+        cases, or a catch-all branch:
+
+            when color is
+                Red -> 1
+                Green -> 2
+                _ -> 3
+        "#
+    );
+    RedundantPattern = 306, "redundant-pattern", indoc!(
+        r#"
+        A `when` branch can never match, because the branches before it already cover
+        everything it matches.
+
+            when color is
+                _ -> 1
+                Red -> 2
+
+        Remove the redundant branch, or move it before the one that covers it.
+        "#
+    );
+    UnmatchablePattern = 307, "unmatchable-pattern", indoc!(
+        r#"
+        A `when` branch can never match, because no value of the matched type fits it.
+
+            result : Result Str []
+            when result is
+                Ok s -> s
+                Err _ -> "never"
+
+        The error type `[]` has no values, so the `Err` branch can be removed.
+        "#
+    );
+    StructuralSpecialization = 308, "structural-specialization", indoc!(
+        r#"
+        An ability member is implemented for a structural type, like a record or tag union.
+
+        Abilities can only be implemented for opaque types. Wrap the type in an opaque type
+        and implement the ability for that:
+
+            Point := { x : I64, y : I64 } has [Eq { isEq: pointIsEq }]
+        "#
+    );
+    WrongSpecialization = 309, "wrong-specialization", indoc!(
+        r#"
+        An ability member implementation does not have the type the ability requires.
+
+            Hash has hash : a -> U64 | a has Hash
+
+            Id := U64 has [Hash { hash: idHash }]
+
+            idHash = \@Id n -> Num.toStr n
+
+        `hash` must return a `U64` for `Id`, but `idHash` returns a `Str`.
+        "#
+    );
+    IngestedFileBadUtf8 = 310, "ingested-file-bad-utf8", indoc!(
+        r#"
+        A file imported as a `Str` is not valid UTF-8.
+
+        Import it as `List U8` instead, and decode it yourself:
+
+            imports ["data.bin" as data : List U8]
+        "#
+    );
+    IngestedFileUnsupportedType = 311, "ingested-file-unsupported-type", indoc!(
+        r#"
+        A file is imported with a type that can't hold file contents.
+
+            imports ["config.json" as config : I64]
+
+        Ingested files can only be imported as `Str` or `List U8`.
+        "#
+    );
+}
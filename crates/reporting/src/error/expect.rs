@@ -156,6 +156,7 @@ impl<'a> Renderer<'a> {
             doc,
             filename: self.filename.clone(),
             severity: Severity::RuntimeError,
+            code: None,
         };
 
         let mut buf = String::new();
@@ -227,6 +228,7 @@ impl<'a> Renderer<'a> {
             doc,
             filename: self.filename.clone(),
             severity: Severity::RuntimeError,
+            code: None,
         };
 
         let mut buf = String::new();
@@ -3,7 +3,7 @@ use roc_problem::Severity;
 use roc_region::all::{LineColumn, LineColumnRegion, LineInfo, Position, Region};
 use std::path::PathBuf;
 
-use crate::report::{Report, RocDocAllocator, RocDocBuilder};
+use crate::report::{ErrorCode, Report, RocDocAllocator, RocDocBuilder};
 use ven_pretty::DocAllocator;
 
 pub fn parse_problem<'a>(
@@ -13,7 +13,12 @@ pub fn parse_problem<'a>(
     _starting_line: u32,
     parse_problem: FileError<SyntaxError<'a>>,
 ) -> Report<'a> {
-    to_syntax_report(alloc, lines, filename, &parse_problem.problem.problem)
+    let problem = &parse_problem.problem.problem;
+
+    Report {
+        code: Some(ErrorCode::of_syntax_error(problem)),
+        ..to_syntax_report(alloc, lines, filename, problem)
+    }
 }
 
 fn note_for_record_type_indent<'a>(alloc: &'a RocDocAllocator<'a>) -> RocDocBuilder<'a> {
@@ -65,6 +70,7 @@ fn to_syntax_report<'a>(
         doc,
         title: "PARSE PROBLEM".to_string(),
         severity: Severity::RuntimeError,
+        code: None,
     };
 
     match parse_problem {
@@ -79,6 +85,7 @@ fn to_syntax_report<'a>(
                 doc,
                 title: "PARSE PROBLEM".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         Unexpected(region) => {
@@ -111,6 +118,7 @@ fn to_syntax_report<'a>(
                 doc,
                 title: "NOT END OF FILE".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         SyntaxError::Eof(region) => {
@@ -124,6 +132,7 @@ fn to_syntax_report<'a>(
                 doc,
                 title: "PARSE PROBLEM".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         SyntaxError::OutdentedTooFar => {
@@ -134,6 +143,7 @@ fn to_syntax_report<'a>(
                 doc,
                 title: "PARSE PROBLEM".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         Type(typ) => to_type_report(alloc, lines, filename, typ, Position::default()),
@@ -215,6 +225,7 @@ fn to_expr_report<'a>(
                 doc,
                 title: "ARGUMENTS BEFORE EQUALS".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -320,6 +331,7 @@ fn to_expr_report<'a>(
                 doc,
                 title: "UNKNOWN OPERATOR".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -346,6 +358,7 @@ fn to_expr_report<'a>(
                 doc,
                 title: "WEIRD IDENTIFIER".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -428,6 +441,7 @@ fn to_expr_report<'a>(
                 doc,
                 title: title.to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -456,6 +470,7 @@ fn to_expr_report<'a>(
                 doc,
                 title: "MISSING FINAL EXPRESSION".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -487,6 +502,7 @@ fn to_expr_report<'a>(
                 doc,
                 title: "SYNTAX PROBLEM".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -510,6 +526,7 @@ fn to_expr_report<'a>(
                 doc,
                 title: "ARGUMENTS BEFORE EQUALS".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -528,6 +545,7 @@ fn to_expr_report<'a>(
                 doc,
                 title: "BAD BACKPASSING ARROW".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -546,6 +564,7 @@ fn to_expr_report<'a>(
                 doc,
                 title: "RECORD PARSE PROBLEM".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -612,6 +631,7 @@ fn to_expr_report<'a>(
                 doc,
                 title: "INDENT ENDS AFTER EXPRESSION".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         EExpr::Expect(e_expect, _position) => {
@@ -657,6 +677,7 @@ fn to_lambda_report<'a>(
                     doc,
                     title: "WEIRD ARROW".to_string(),
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
             _ => {
@@ -679,6 +700,7 @@ fn to_lambda_report<'a>(
                     doc,
                     title: "MISSING ARROW".to_string(),
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
         },
@@ -704,6 +726,7 @@ fn to_lambda_report<'a>(
                     doc,
                     title: "WEIRD ARROW".to_string(),
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
             _ => {
@@ -726,6 +749,7 @@ fn to_lambda_report<'a>(
                     doc,
                     title: "MISSING ARROW".to_string(),
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
         },
@@ -750,6 +774,7 @@ fn to_lambda_report<'a>(
                     doc,
                     title: "UNFINISHED ARGUMENT LIST".to_string(),
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
             _ => {
@@ -771,6 +796,7 @@ fn to_lambda_report<'a>(
                     doc,
                     title: "MISSING ARROW".to_string(),
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
         },
@@ -852,6 +878,7 @@ fn to_unfinished_lambda_report<'a>(
         doc,
         title: "UNFINISHED FUNCTION".to_string(),
         severity: Severity::RuntimeError,
+        code: None,
     }
 }
 
@@ -918,6 +945,7 @@ fn to_str_report<'a>(
                 doc,
                 title: "WEIRD ESCAPE".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         EString::CodePtOpen(pos) | EString::CodePtEnd(pos) => {
@@ -944,6 +972,7 @@ fn to_str_report<'a>(
                 doc,
                 title: "WEIRD CODE POINT".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         EString::FormatEnd(pos) => {
@@ -965,6 +994,7 @@ fn to_str_report<'a>(
                 doc,
                 title: "ENDLESS FORMAT".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         EString::EndlessSingleQuote(pos) => {
@@ -988,6 +1018,7 @@ fn to_str_report<'a>(
                 doc,
                 title: "ENDLESS SCALAR".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         EString::InvalidSingleQuote(e, pos) => {
@@ -1054,6 +1085,7 @@ fn to_str_report<'a>(
                 doc,
                 title: "INVALID SCALAR".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         EString::EndlessSingleLine(pos) => {
@@ -1077,6 +1109,7 @@ fn to_str_report<'a>(
                 doc,
                 title: "ENDLESS STRING".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         EString::ExpectedDoubleQuoteGotSingleQuote(pos) => {
@@ -1101,6 +1134,7 @@ fn to_str_report<'a>(
                 doc,
                 title: "EXPECTED STRING".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         EString::EndlessMultiLine(pos) => {
@@ -1124,6 +1158,7 @@ fn to_str_report<'a>(
                 doc,
                 title: "ENDLESS STRING".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         EString::MultilineInsufficientIndent(pos) => {
@@ -1145,6 +1180,7 @@ fn to_str_report<'a>(
                 doc,
                 title: "INSUFFICIENT INDENT IN MULTI-LINE STRING".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
     }
@@ -1187,6 +1223,7 @@ fn to_expr_in_parens_report<'a>(
                 doc,
                 title: "EMPTY PARENTHESES".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         EInParens::End(pos) => {
@@ -1211,6 +1248,7 @@ fn to_expr_in_parens_report<'a>(
                 doc,
                 title: "UNFINISHED PARENTHESES".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         EInParens::Open(pos) => {
@@ -1236,6 +1274,7 @@ fn to_expr_in_parens_report<'a>(
                 doc,
                 title: "UNFINISHED PARENTHESES".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
     }
@@ -1286,6 +1325,7 @@ fn to_list_report<'a>(
                         doc,
                         title: "UNFINISHED LIST".to_string(),
                         severity: Severity::RuntimeError,
+                        code: None,
                     }
                 }
                 _ => {
@@ -1319,6 +1359,7 @@ fn to_list_report<'a>(
                         doc,
                         title: "UNFINISHED LIST".to_string(),
                         severity: Severity::RuntimeError,
+                        code: None,
                     }
                 }
             }
@@ -1466,6 +1507,7 @@ fn to_unfinished_if_report<'a>(
         doc,
         title: "UNFINISHED IF".to_string(),
         severity: Severity::RuntimeError,
+        code: None,
     }
 }
 
@@ -1499,6 +1541,7 @@ fn to_when_report<'a>(
                         doc,
                         title: "IF GUARD NO CONDITION".to_string(),
                         severity: Severity::RuntimeError,
+                        code: None,
                     }
                 }
                 _ => to_expr_report(
@@ -1531,6 +1574,7 @@ fn to_when_report<'a>(
                 doc,
                 title: "MISSING ARROW".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -1686,6 +1730,7 @@ fn to_unfinished_when_report<'a>(
                 doc,
                 title: "UNFINISHED WHEN".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
     }
@@ -1726,6 +1771,7 @@ fn to_unexpected_arrow_report<'a>(
         doc,
         title: "UNEXPECTED ARROW".to_string(),
         severity: Severity::RuntimeError,
+        code: None,
     }
 }
 
@@ -1802,6 +1848,7 @@ fn to_pattern_report<'a>(
                 doc,
                 title: "UNFINISHED PATTERN".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         EPattern::Record(record, pos) => to_precord_report(alloc, lines, filename, record, *pos),
@@ -1846,6 +1893,7 @@ fn to_precord_report<'a>(
                     doc,
                     title: "UNFINISHED RECORD PATTERN".to_string(),
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
             _ => {
@@ -1863,6 +1911,7 @@ fn to_precord_report<'a>(
                     doc,
                     title: "UNFINISHED RECORD PATTERN".to_string(),
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
         },
@@ -1888,6 +1937,7 @@ fn to_precord_report<'a>(
                         doc,
                         title: "UNFINISHED RECORD PATTERN".to_string(),
                         severity: Severity::RuntimeError,
+                        code: None,
                     }
                 }
                 _ => {
@@ -1908,6 +1958,7 @@ fn to_precord_report<'a>(
                         doc,
                         title: "UNFINISHED RECORD PATTERN".to_string(),
                         severity: Severity::RuntimeError,
+                        code: None,
                     }
                 }
             }
@@ -1933,6 +1984,7 @@ fn to_precord_report<'a>(
                     doc,
                     title: "UNFINISHED RECORD PATTERN".to_string(),
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
             Next::Other(Some(',')) => todo!(),
@@ -1958,6 +2010,7 @@ fn to_precord_report<'a>(
                     doc,
                     title: "PROBLEM IN RECORD PATTERN".to_string(),
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
         },
@@ -2011,6 +2064,7 @@ fn to_plist_report<'a>(
                 doc,
                 title: "UNFINISHED LIST PATTERN".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -2033,6 +2087,7 @@ fn to_plist_report<'a>(
                 doc,
                 title: "UNFINISHED LIST PATTERN".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -2055,6 +2110,7 @@ fn to_plist_report<'a>(
                 doc,
                 title: "INCORRECT REST PATTERN".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -2098,6 +2154,7 @@ fn to_pattern_in_parens_report<'a>(
                 doc,
                 title: "UNFINISHED PARENTHESES".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -2119,6 +2176,7 @@ fn to_pattern_in_parens_report<'a>(
                 doc,
                 title: "EMPTY PARENTHESES".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -2143,6 +2201,7 @@ fn to_pattern_in_parens_report<'a>(
                 doc,
                 title: "UNFINISHED PARENTHESES".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -2171,6 +2230,7 @@ fn to_malformed_number_literal_report<'a>(
         doc,
         title: "INVALID NUMBER LITERAL".to_string(),
         severity: Severity::RuntimeError,
+        code: None,
     }
 }
 
@@ -2211,6 +2271,7 @@ fn to_type_report<'a>(
                         doc,
                         title: "DOUBLE COMMA".to_string(),
                         severity: Severity::RuntimeError,
+                        code: None,
                     }
                 }
                 _ => todo!(),
@@ -2238,6 +2299,7 @@ fn to_type_report<'a>(
                 doc,
                 title: "UNFINISHED TYPE".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -2256,6 +2318,7 @@ fn to_type_report<'a>(
                 doc,
                 title: "UNFINISHED TYPE".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -2274,6 +2337,7 @@ fn to_type_report<'a>(
                 doc,
                 title: "UNFINISHED TYPE".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -2292,6 +2356,7 @@ fn to_type_report<'a>(
                 doc,
                 title: "UNFINISHED INLINE ALIAS".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -2309,6 +2374,7 @@ fn to_type_report<'a>(
                 doc,
                 title: "BAD TYPE VARIABLE".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -2346,6 +2412,7 @@ fn to_trecord_report<'a>(
                     doc,
                     title: "UNFINISHED RECORD TYPE".to_string(),
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
             _ => {
@@ -2367,6 +2434,7 @@ fn to_trecord_report<'a>(
                     doc,
                     title: "UNFINISHED RECORD TYPE".to_string(),
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
         },
@@ -2392,6 +2460,7 @@ fn to_trecord_report<'a>(
                         doc,
                         title: "UNFINISHED RECORD TYPE".to_string(),
                         severity: Severity::RuntimeError,
+                        code: None,
                     }
                 }
                 _ => {
@@ -2412,6 +2481,7 @@ fn to_trecord_report<'a>(
                         doc,
                         title: "UNFINISHED RECORD TYPE".to_string(),
                         severity: Severity::RuntimeError,
+                        code: None,
                     }
                 }
             }
@@ -2437,6 +2507,7 @@ fn to_trecord_report<'a>(
                     doc,
                     title: "UNFINISHED RECORD TYPE".to_string(),
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
             Next::Other(Some(',')) => todo!(),
@@ -2462,6 +2533,7 @@ fn to_trecord_report<'a>(
                     doc,
                     title: "PROBLEM IN RECORD TYPE".to_string(),
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
         },
@@ -2495,6 +2567,7 @@ fn to_trecord_report<'a>(
                 doc,
                 title: "UNFINISHED RECORD TYPE".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -2519,6 +2592,7 @@ fn to_trecord_report<'a>(
                         doc,
                         title: "NEED MORE INDENTATION".to_string(),
                         severity: Severity::RuntimeError,
+                        code: None,
                     }
                 }
                 None => {
@@ -2544,6 +2618,7 @@ fn to_trecord_report<'a>(
                         doc,
                         title: "UNFINISHED RECORD TYPE".to_string(),
                         severity: Severity::RuntimeError,
+                        code: None,
                     }
                 }
             }
@@ -2591,6 +2666,7 @@ fn to_ttag_union_report<'a>(
                     doc,
                     title: "UNFINISHED TAG UNION TYPE".to_string(),
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
             Next::Other(Some(c)) if c.is_alphabetic() => {
@@ -2613,6 +2689,7 @@ fn to_ttag_union_report<'a>(
                     doc,
                     title: "WEIRD TAG NAME".to_string(),
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
             _ => {
@@ -2634,6 +2711,7 @@ fn to_ttag_union_report<'a>(
                     doc,
                     title: "UNFINISHED TAG UNION TYPE".to_string(),
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
         },
@@ -2660,6 +2738,7 @@ fn to_ttag_union_report<'a>(
                         doc,
                         title: "WEIRD TAG NAME".to_string(),
                         severity: Severity::RuntimeError,
+                        code: None,
                     }
                 }
                 _ => {
@@ -2680,6 +2759,7 @@ fn to_ttag_union_report<'a>(
                         doc,
                         title: "UNFINISHED TAG UNION TYPE".to_string(),
                         severity: Severity::RuntimeError,
+                        code: None,
                     }
                 }
             }
@@ -2723,6 +2803,7 @@ fn to_tinparens_report<'a>(
                         doc,
                         title: "UNFINISHED PARENTHESES".to_string(),
                         severity: Severity::RuntimeError,
+                        code: None,
                     }
                 }
                 Next::Other(Some(c)) if c.is_alphabetic() => {
@@ -2745,6 +2826,7 @@ fn to_tinparens_report<'a>(
                         doc,
                         title: "WEIRD TAG NAME".to_string(),
                         severity: Severity::RuntimeError,
+                        code: None,
                     }
                 }
                 _ => {
@@ -2768,6 +2850,7 @@ fn to_tinparens_report<'a>(
                         doc,
                         title: "UNFINISHED PARENTHESES".to_string(),
                         severity: Severity::RuntimeError,
+                        code: None,
                     }
                 }
             }
@@ -2791,6 +2874,7 @@ fn to_tinparens_report<'a>(
                 doc,
                 title: "EMPTY PARENTHESES".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -2817,6 +2901,7 @@ fn to_tinparens_report<'a>(
                         doc,
                         title: "WEIRD TAG NAME".to_string(),
                         severity: Severity::RuntimeError,
+                        code: None,
                     }
                 }
                 _ => {
@@ -2837,6 +2922,7 @@ fn to_tinparens_report<'a>(
                         doc,
                         title: "UNFINISHED PARENTHESES".to_string(),
                         severity: Severity::RuntimeError,
+                        code: None,
                     }
                 }
             }
@@ -2865,6 +2951,7 @@ fn to_tinparens_report<'a>(
                 doc,
                 title: "UNFINISHED PARENTHESES".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -2889,6 +2976,7 @@ fn to_tinparens_report<'a>(
                         doc,
                         title: "NEED MORE INDENTATION".to_string(),
                         severity: Severity::RuntimeError,
+                        code: None,
                     }
                 }
                 None => {
@@ -2914,6 +3002,7 @@ fn to_tinparens_report<'a>(
                         doc,
                         title: "UNFINISHED PARENTHESES".to_string(),
                         severity: Severity::RuntimeError,
+                        code: None,
                     }
                 }
             }
@@ -2947,6 +3036,7 @@ fn to_tapply_report<'a>(
                 doc,
                 title: "DOUBLE DOT".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         ETypeApply::TrailingDot(pos) => {
@@ -2969,6 +3059,7 @@ fn to_tapply_report<'a>(
                 doc,
                 title: "TRAILING DOT".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         ETypeApply::StartIsNumber(pos) => {
@@ -2991,6 +3082,7 @@ fn to_tapply_report<'a>(
                 doc,
                 title: "WEIRD QUALIFIED NAME".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         ETypeApply::StartNotUppercase(pos) => {
@@ -3013,6 +3105,7 @@ fn to_tapply_report<'a>(
                 doc,
                 title: "WEIRD QUALIFIED NAME".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3031,6 +3124,7 @@ fn to_tapply_report<'a>(
                 doc,
                 title: "END OF FILE".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3071,6 +3165,7 @@ fn to_talias_report<'a>(
                 doc,
                 title: "NOT AN INLINE ALIAS".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         ETypeInlineAlias::Qualified(pos) => {
@@ -3087,6 +3182,7 @@ fn to_talias_report<'a>(
                 doc,
                 title: "QUALIFIED ALIAS NAME".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         ETypeInlineAlias::ArgumentNotLowercase(pos) => {
@@ -3103,6 +3199,7 @@ fn to_talias_report<'a>(
                 doc,
                 title: "TYPE ARGUMENT NOT LOWERCASE".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
     }
@@ -3149,6 +3246,7 @@ fn to_header_report<'a>(
                 doc,
                 title: "INCOMPLETE HEADER".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3177,6 +3275,7 @@ fn to_header_report<'a>(
                 doc,
                 title: "MISSING HEADER".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3201,6 +3300,7 @@ fn to_header_report<'a>(
                 doc,
                 title: "WEIRD MODULE NAME".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3228,6 +3328,7 @@ fn to_header_report<'a>(
                 doc,
                 title: "WEIRD MODULE NAME".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3252,6 +3353,7 @@ fn to_header_report<'a>(
                 doc,
                 title: "WEIRD APP NAME".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3274,6 +3376,7 @@ fn to_header_report<'a>(
                 doc,
                 title: "INVALID PACKAGE NAME".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3297,6 +3400,7 @@ fn to_header_report<'a>(
                 doc,
                 title: "INVALID PLATFORM NAME".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3320,6 +3424,7 @@ fn to_header_report<'a>(
                 doc,
                 title: "WEIRD GENERATED TYPE NAME".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         EHeader::GeneratesWith(generates_with, pos) => {
@@ -3360,6 +3465,7 @@ fn to_generates_with_report<'a>(
                 doc,
                 title: "WEIRD GENERATES".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3385,6 +3491,7 @@ fn to_generates_with_report<'a>(
                 doc,
                 title: "WEIRD GENERATES".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3426,6 +3533,7 @@ fn to_provides_report<'a>(
                 doc,
                 title: "WEIRD PROVIDES".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3451,6 +3559,7 @@ fn to_provides_report<'a>(
                 doc,
                 title: "WEIRD PROVIDES".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3491,6 +3600,7 @@ fn to_exposes_report<'a>(
                 doc,
                 title: "WEIRD EXPOSES".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3516,6 +3626,7 @@ fn to_exposes_report<'a>(
                 doc,
                 title: "WEIRD EXPOSES".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3555,6 +3666,7 @@ fn to_imports_report<'a>(
                 doc,
                 title: "WEIRD IMPORTS".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3580,6 +3692,7 @@ fn to_imports_report<'a>(
                 doc,
                 title: "WEIRD IMPORTS".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3606,6 +3719,7 @@ fn to_imports_report<'a>(
                 doc,
                 title: "WEIRD MODULE NAME".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3625,6 +3739,7 @@ fn to_imports_report<'a>(
                 doc,
                 title: "WEIRD IMPORTS".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3664,6 +3779,7 @@ fn to_requires_report<'a>(
                 doc,
                 title: "MISSING REQUIRES".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3691,6 +3807,7 @@ fn to_requires_report<'a>(
                 doc,
                 title: "MISSING REQUIRES".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3720,6 +3837,7 @@ fn to_requires_report<'a>(
                 doc,
                 title: "BAD REQUIRES RIGIDS".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3749,6 +3867,7 @@ fn to_requires_report<'a>(
                 doc,
                 title: "BAD REQUIRES".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3786,6 +3905,7 @@ fn to_packages_report<'a>(
                 doc,
                 title: "MISSING PACKAGES".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3819,6 +3939,7 @@ fn to_space_report<'a>(
                 doc,
                 title: "TAB CHARACTER".to_string(),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -3900,6 +4021,7 @@ fn to_unfinished_ability_report<'a>(
         doc,
         title: "UNFINISHED ABILITY".to_string(),
         severity: Severity::RuntimeError,
+        code: None,
     }
 }
 
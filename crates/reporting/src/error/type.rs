@@ -1,7 +1,7 @@
 #![allow(clippy::too_many_arguments)]
 
 use crate::error::canonicalize::{to_circular_def_doc, CIRCULAR_DEF};
-use crate::report::{Annotation, Report, RocDocAllocator, RocDocBuilder};
+use crate::report::{Annotation, ErrorCode, Report, RocDocAllocator, RocDocBuilder};
 use itertools::EitherOrBoth;
 use itertools::Itertools;
 use roc_can::expected::{Expected, PExpected};
@@ -40,6 +40,20 @@ pub fn type_problem<'b>(
     lines: &LineInfo,
     filename: PathBuf,
     problem: TypeError,
+) -> Option<Report<'b>> {
+    let code = ErrorCode::of_type_error(&problem);
+
+    type_problem_help(alloc, lines, filename, problem).map(|report| Report {
+        code: Some(code),
+        ..report
+    })
+}
+
+fn type_problem_help<'b>(
+    alloc: &'b RocDocAllocator<'b>,
+    lines: &LineInfo,
+    filename: PathBuf,
+    problem: TypeError,
 ) -> Option<Report<'b>> {
     use TypeError::*;
 
@@ -52,6 +66,7 @@ pub fn type_problem<'b>(
                 filename,
                 doc,
                 severity,
+                code: None,
             })
         };
 
@@ -113,6 +128,7 @@ pub fn type_problem<'b>(
                 filename,
                 doc: alloc.stack(stack),
                 severity,
+                code: None,
             };
             Some(report)
         }
@@ -135,6 +151,7 @@ pub fn type_problem<'b>(
                 filename,
                 doc: alloc.stack(stack),
                 severity,
+                code: None,
             };
             Some(report)
         }
@@ -148,6 +165,7 @@ pub fn type_problem<'b>(
                 filename,
                 doc,
                 severity,
+                code: None,
             })
         }
         StructuralSpecialization {
@@ -178,6 +196,7 @@ pub fn type_problem<'b>(
                 filename,
                 doc: alloc.stack(stack),
                 severity,
+                code: None,
             })
         }
         WrongSpecialization {
@@ -207,6 +226,7 @@ pub fn type_problem<'b>(
                 filename,
                 doc: alloc.stack(stack),
                 severity,
+                code: None,
             })
         }
         IngestedFileBadUtf8(file_path, utf8_err) => {
@@ -223,6 +243,7 @@ pub fn type_problem<'b>(
                 filename,
                 doc: alloc.stack(stack),
                 severity,
+                code: None,
             })
         }
         IngestedFileUnsupportedType(file_path, typ) => {
@@ -246,6 +267,7 @@ pub fn type_problem<'b>(
                 filename,
                 doc: alloc.stack(stack),
                 severity,
+                code: None,
             })
         }
     }
@@ -562,6 +584,7 @@ fn report_mismatch<'b>(
         filename,
         doc: alloc.stack(lines),
         severity,
+        code: None,
     }
 }
 
@@ -605,6 +628,7 @@ fn report_bad_type<'b>(
         filename,
         doc: alloc.stack(lines),
         severity,
+        code: None,
     }
 }
 
@@ -692,6 +716,7 @@ fn to_expr_report<'b>(
                     comparison,
                 ]),
                 severity,
+                code: None,
             }
         }
         Expected::FromAnnotation(name, _arity, annotation_source, expected_type) => {
@@ -815,6 +840,7 @@ fn to_expr_report<'b>(
                     comparison,
                 ]),
                 severity,
+                code: None,
             }
         }
         Expected::ForReason(reason, expected_type, region) => match reason {
@@ -1183,6 +1209,7 @@ fn to_expr_report<'b>(
                         title: "TOO MANY ARGS".to_string(),
                         doc,
                         severity,
+                        code: None,
                     }
                 }
                 DescribedFunction::Arguments(n) => {
@@ -1218,6 +1245,7 @@ fn to_expr_report<'b>(
                             title: "TOO MANY ARGS".to_string(),
                             doc: alloc.stack(lines),
                             severity,
+                            code: None,
                         }
                     } else {
                         let lines = vec![
@@ -1245,6 +1273,7 @@ fn to_expr_report<'b>(
                             title: "TOO FEW ARGS".to_string(),
                             doc: alloc.stack(lines),
                             severity,
+                            code: None,
                         }
                     }
                 }
@@ -1434,6 +1463,7 @@ fn to_expr_report<'b>(
                     filename,
                     doc: alloc.stack(lines),
                     severity,
+                    code: None,
                 }
             }
 
@@ -1471,6 +1501,7 @@ fn to_expr_report<'b>(
                     title: "TYPE MISMATCH".to_string(),
                     doc,
                     severity,
+                    code: None,
                 }
             }
 
@@ -1507,6 +1538,7 @@ fn to_expr_report<'b>(
                     title: "TYPE MISMATCH".to_string(),
                     doc: alloc.stack(lines),
                     severity,
+                    code: None,
                 }
             }
 
@@ -1929,6 +1961,7 @@ fn to_pattern_report<'b>(
                 title: "TYPE MISMATCH".to_string(),
                 doc,
                 severity,
+                code: None,
             }
         }
 
@@ -1972,6 +2005,7 @@ fn to_pattern_report<'b>(
                     title: "TYPE MISMATCH".to_string(),
                     doc,
                     severity,
+                    code: None,
                 }
             }
             PReason::WhenMatch { index, sub_pattern } => {
@@ -2052,6 +2086,7 @@ fn to_pattern_report<'b>(
                     title: "TYPE MISMATCH".to_string(),
                     doc,
                     severity,
+                    code: None,
                 }
             }
             PReason::ListElem => {
@@ -2079,6 +2114,7 @@ fn to_pattern_report<'b>(
                     title: "TYPE MISMATCH".to_string(),
                     doc,
                     severity,
+                    code: None,
                 }
             }
             PReason::TagArg { .. } | PReason::PatternGuard => {
@@ -2180,6 +2216,7 @@ fn to_circular_report<'b>(
             ])
         },
         severity,
+        code: None,
     }
 }
 
@@ -4846,6 +4883,7 @@ fn report_record_field_typo<'b>(
         title: "TYPE MISMATCH".to_string(),
         doc,
         severity,
+        code: None,
     }
 }
 
@@ -4883,6 +4921,7 @@ fn exhaustive_problem<'a>(
                     title: "UNSAFE PATTERN".to_string(),
                     doc,
                     severity,
+                    code: None,
                 }
             }
             BadDestruct => {
@@ -4907,6 +4946,7 @@ fn exhaustive_problem<'a>(
                     title: "UNSAFE PATTERN".to_string(),
                     doc,
                     severity,
+                    code: None,
                 }
             }
             BadCase => {
@@ -4931,6 +4971,7 @@ fn exhaustive_problem<'a>(
                     title: "UNSAFE PATTERN".to_string(),
                     doc,
                     severity,
+                    code: None,
                 }
             }
         },
@@ -4960,6 +5001,7 @@ fn exhaustive_problem<'a>(
                 title: "REDUNDANT PATTERN".to_string(),
                 doc,
                 severity,
+                code: None,
             }
         }
         Unmatchable {
@@ -4988,6 +5030,7 @@ fn exhaustive_problem<'a>(
                 title: "UNMATCHABLE PATTERN".to_string(),
                 doc,
                 severity,
+                code: None,
             }
         }
     }
@@ -3,11 +3,14 @@ use roc_module::ident::{Lowercase, ModuleName, TagName, Uppercase};
 use roc_module::symbol::{Interns, ModuleId, ModuleIds, PQModuleName, PackageQualified, Symbol};
 use roc_problem::Severity;
 use roc_region::all::LineColumnRegion;
+use serde_json::json;
+use std::cell::Cell;
 use std::path::{Path, PathBuf};
 use std::{fmt, io};
 use ven_pretty::{BoxAllocator, DocAllocator, DocBuilder, Render, RenderAnnotated};
 
 pub use crate::error::canonicalize::can_problem;
+pub use crate::error::code::ErrorCode;
 pub use crate::error::parse::parse_problem;
 pub use crate::error::r#type::type_problem;
 
@@ -100,10 +103,31 @@ pub fn pretty_header_with_path(title: &str, path: &Path) -> String {
     header
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum RenderTarget {
     ColorTerminal,
     Generic,
+    /// One JSON object per report, for editors and other tools to read
+    Json,
+    /// One SARIF result object per report, see [crate::cli::sarif_log]
+    Sarif,
+}
+
+impl RenderTarget {
+    /// Whether reports are rendered for other programs to read, rather than for people.
+    pub fn is_machine_readable(&self) -> bool {
+        matches!(self, RenderTarget::Json | RenderTarget::Sarif)
+    }
+}
+
+/// A report's location, severity and text without its header and colors, plus the hints and
+/// tips in that text, for rendering it as JSON or SARIF
+struct MachineReadableParts {
+    region: Option<LineColumnRegion>,
+    /// `warning` or `error`, which JSON and SARIF both use
+    severity: &'static str,
+    message: String,
+    hints: Vec<String>,
 }
 
 /// A textual report.
@@ -112,24 +136,28 @@ pub struct Report<'b> {
     pub filename: PathBuf,
     pub doc: RocDocBuilder<'b>,
     pub severity: Severity,
+    /// The stable code for this kind of problem, if it has one; see `roc explain`
+    pub code: Option<ErrorCode>,
 }
 
 impl<'b> Report<'b> {
     pub fn render(
         self,
         target: RenderTarget,
-        buf: &'b mut String,
+        buf: &mut String,
         alloc: &'b RocDocAllocator<'b>,
         palette: &'b Palette,
     ) {
         match target {
             RenderTarget::Generic => self.render_ci(buf, alloc),
             RenderTarget::ColorTerminal => self.render_color_terminal(buf, alloc, palette),
+            RenderTarget::Json => self.render_json(buf, alloc, None),
+            RenderTarget::Sarif => self.render_sarif(buf, alloc, None),
         }
     }
 
     /// Render to CI console output, where no colors are available.
-    pub fn render_ci(self, buf: &'b mut String, alloc: &'b RocDocAllocator<'b>) {
+    pub fn render_ci(self, buf: &mut String, alloc: &'b RocDocAllocator<'b>) {
         let err_msg = "<buffer is not a utf-8 encoded string>";
 
         self.pretty(alloc)
@@ -154,14 +182,123 @@ impl<'b> Report<'b> {
             .expect(err_msg);
     }
 
+    /// Render as a single line of JSON. Without a `region`, the report points at the first
+    /// snippet of code it shows.
+    pub fn render_json(
+        self,
+        buf: &mut String,
+        alloc: &'b RocDocAllocator<'b>,
+        region: Option<LineColumnRegion>,
+    ) {
+        let parts = self.machine_readable_parts(alloc, region);
+
+        let json = json!({
+            "severity": parts.severity,
+            "code": self.code.map(|code| code.to_string()),
+            "title": self.title,
+            "file": self.filename.to_string_lossy(),
+            "region": parts.region.map(|region| {
+                json!({
+                    "start": { "line": region.start.line + 1, "column": region.start.column + 1 },
+                    "end": { "line": region.end.line + 1, "column": region.end.column + 1 },
+                })
+            }),
+            "message": parts.message,
+            "hints": parts.hints,
+        });
+
+        buf.push_str(&json.to_string());
+    }
+
+    /// Render as a single line of JSON, in the shape of a SARIF 2.1.0 result.
+    /// Without a `region`, the report points at the first snippet of code it shows.
+    pub fn render_sarif(
+        self,
+        buf: &mut String,
+        alloc: &'b RocDocAllocator<'b>,
+        region: Option<LineColumnRegion>,
+    ) {
+        let parts = self.machine_readable_parts(alloc, region);
+
+        let mut physical_location = json!({
+            "artifactLocation": { "uri": self.filename.to_string_lossy().replace('\\', "/") },
+        });
+
+        if let Some(region) = parts.region {
+            physical_location["region"] = json!({
+                "startLine": region.start.line + 1,
+                "startColumn": region.start.column + 1,
+                "endLine": region.end.line + 1,
+                "endColumn": region.end.column + 1,
+            });
+        }
+
+        let rule_id = match self.code {
+            Some(code) => code.to_string(),
+            None => self.title.clone(),
+        };
+
+        let json = json!({
+            "ruleId": rule_id,
+            "level": parts.severity,
+            "message": { "text": parts.message },
+            "locations": [{ "physicalLocation": physical_location }],
+            "properties": { "hints": parts.hints },
+        });
+
+        buf.push_str(&json.to_string());
+    }
+
+    /// What both the JSON and the SARIF renderings are made of
+    fn machine_readable_parts(
+        &self,
+        alloc: &RocDocAllocator<'b>,
+        region: Option<LineColumnRegion>,
+    ) -> MachineReadableParts {
+        // Take the shown region even if it's not needed, so it can't leak into the next report
+        let shown_region = alloc.take_shown_region();
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::RuntimeError | Severity::Fatal => "error",
+        };
+        let mut message = String::new();
+
+        self.doc
+            .1
+            .render_raw(70, &mut CiWrite::new(&mut message))
+            .expect("<buffer is not a utf-8 encoded string>");
+
+        let hints = message
+            .split("\n\n")
+            .map(str::trim)
+            .filter(|paragraph| paragraph.starts_with("Hint:") || paragraph.starts_with("Tip:"))
+            .map(|paragraph| {
+                let lines: Vec<&str> = paragraph.lines().map(str::trim).collect();
+
+                lines.join(" ")
+            })
+            .collect();
+
+        MachineReadableParts {
+            region: region.or(shown_region),
+            severity,
+            message: message.trim().to_string(),
+            hints,
+        }
+    }
+
     pub fn pretty(self, alloc: &'b RocDocAllocator<'b>) -> RocDocBuilder<'b> {
         if self.title.is_empty() {
             self.doc
         } else {
+            let title = match self.code {
+                Some(code) => format!("{} [{}]", self.title, code),
+                None => self.title,
+            };
             let header = if self.filename == PathBuf::from("") {
-                crate::report::pretty_header(&self.title)
+                crate::report::pretty_header(&title)
             } else {
-                crate::report::pretty_header_with_path(&self.title, &self.filename)
+                crate::report::pretty_header_with_path(&title, &self.filename)
             };
 
             alloc.stack([alloc.text(header).annotate(Annotation::Header), self.doc])
@@ -290,6 +427,9 @@ pub struct RocDocAllocator<'a> {
     pub src_lines: &'a [&'a str],
     pub home: ModuleId,
     pub interns: &'a Interns,
+    /// The first snippet of code shown since a report was last rendered as JSON or SARIF.
+    /// Only `show_region` and `take_shown_region` should touch this.
+    shown_region: Cell<Option<LineColumnRegion>>,
 }
 
 pub type RocDocBuilder<'b> = DocBuilder<'b, RocDocAllocator<'b>, Annotation>;
@@ -326,9 +466,21 @@ impl<'a> RocDocAllocator<'a> {
             home,
             src_lines,
             interns,
+            shown_region: Cell::new(None),
+        }
+    }
+
+    fn show_region(&self, region: LineColumnRegion) {
+        if self.shown_region.get().is_none() {
+            self.shown_region.set(Some(region));
         }
     }
 
+    /// The first snippet of code shown since this was last called, which starts over
+    fn take_shown_region(&self) -> Option<LineColumnRegion> {
+        self.shown_region.take()
+    }
+
     /// vertical concatenation. Adds a newline between elements
     pub fn vcat<A, I>(&'a self, docs: I) -> DocBuilder<'a, Self, A>
     where
@@ -533,6 +685,8 @@ impl<'a> RocDocAllocator<'a> {
         debug_assert!(region.contains(&sub_region1));
         debug_assert!(region.contains(&sub_region2));
 
+        self.show_region(sub_region1);
+
         // if true, the final line of the snippet will be some ^^^ that point to the region where
         // the problem is. Otherwise, the snippet will have a > on the lines that are in the region
         // where the problem is.
@@ -647,6 +801,8 @@ impl<'a> RocDocAllocator<'a> {
     ) -> DocBuilder<'a, Self, Annotation> {
         // debug_assert!(region.contains(&sub_region));
 
+        self.show_region(sub_region);
+
         // If the outer region takes more than 1 full screen (~60 lines), only show the inner region
         if region.end().line.saturating_sub(region.start().line) > 60 {
             // If the inner region contains the outer region (or if they are the same),
@@ -1123,6 +1279,7 @@ pub fn to_file_problem_report<'b>(
                 doc,
                 title: "FILE NOT FOUND".to_string(),
                 severity: Severity::Fatal,
+                code: None,
             }
         }
         io::ErrorKind::PermissionDenied => {
@@ -1141,6 +1298,7 @@ pub fn to_file_problem_report<'b>(
                 doc,
                 title: "FILE PERMISSION DENIED".to_string(),
                 severity: Severity::Fatal,
+                code: None,
             }
         }
         _ => {
@@ -1158,6 +1316,7 @@ pub fn to_file_problem_report<'b>(
                 doc,
                 title: "FILE PROBLEM".to_string(),
                 severity: Severity::Fatal,
+                code: None,
             }
         }
     }
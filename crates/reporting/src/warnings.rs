@@ -0,0 +1,198 @@
+//! Per-project and per-line control over which warnings are reported, and which
+//! are promoted to errors so that e.g. CI fails on them.
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use roc_collections::MutMap;
+use roc_problem::Severity;
+
+use crate::report::{ErrorCode, Report};
+
+/// Read from the directory of the main .roc file. One `<level> <warning>` per line, e.g.
+/// `deny unused-import` or `allow warnings`, with `#` for comments. Later lines win.
+pub const WARNINGS_FILENAME: &str = "roc-warnings.txt";
+
+/// `# roc:allow unused-def, unused-argument` silences those warnings on the line it's on, or,
+/// if the comment is on a line by itself, on the next line that isn't blank or a comment.
+pub const ALLOW_COMMENT: &str = "# roc:allow";
+
+/// Refers to every warning in a directive, as in `--deny warnings`
+const ALL_WARNINGS: &str = "warnings";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WarningLevel {
+    /// Don't report it at all
+    Allow,
+    Warn,
+    /// Report it as an error
+    Deny,
+}
+
+impl WarningLevel {
+    pub fn parse(level: &str) -> Option<Self> {
+        match level {
+            "allow" => Some(WarningLevel::Allow),
+            "warn" => Some(WarningLevel::Warn),
+            "deny" => Some(WarningLevel::Deny),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct WarningConfig {
+    /// The level of every warning that doesn't have one of its own
+    all: Option<WarningLevel>,
+    levels: MutMap<ErrorCode, WarningLevel>,
+}
+
+impl WarningConfig {
+    /// `warning` is a code (`R0202`), a code's name (`unused-import`), or `warnings` for all of them.
+    /// Codes of errors are rejected, since errors can't be allowed or denied.
+    pub fn set(&mut self, level: WarningLevel, warning: &str) -> Result<(), String> {
+        if warning == ALL_WARNINGS {
+            self.all = Some(level);
+
+            return Ok(());
+        }
+
+        match ErrorCode::lookup(warning) {
+            Some(code) if code.is_warning() => {
+                self.levels.insert(code, level);
+
+                Ok(())
+            }
+            Some(code) => Err(format!(
+                "`{}` ({}) is an error, not a warning, so it can't be allowed or denied.",
+                warning, code
+            )),
+            None => Err(format!(
+                "`{}` is not a warning. You can run `roc explain` to list them all.",
+                warning
+            )),
+        }
+    }
+
+    /// Apply the directives in `dir`'s WARNINGS_FILENAME, if there is one.
+    pub fn read_project_file(&mut self, dir: &Path) -> Result<(), String> {
+        let path = dir.join(WARNINGS_FILENAME);
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(format!("I could not read {}: {}", path.display(), err)),
+        };
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut words = line.split_whitespace();
+            let directive = match (words.next(), words.next(), words.next()) {
+                (Some(level), Some(warning), None) => {
+                    WarningLevel::parse(level).map(|level| (level, warning))
+                }
+                _ => None,
+            };
+
+            let result = match directive {
+                Some((level, warning)) => self.set(level, warning),
+                None => Err(format!(
+                    "I expected `allow`, `warn`, or `deny` followed by a warning, but found `{}`.",
+                    line
+                )),
+            };
+
+            result.map_err(|msg| format!("{}:{}: {}", path.display(), index + 1, msg))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn level(&self, code: Option<ErrorCode>) -> WarningLevel {
+        code.and_then(|code| self.levels.get(&code).copied())
+            .or(self.all)
+            .unwrap_or(WarningLevel::Warn)
+    }
+
+    /// `None` if the report is a warning that's allowed here, otherwise the report to show,
+    /// turned into an error if it's a denied warning. Errors are never affected.
+    /// `line` is the 0-based line the problem starts on, if it has a region.
+    pub fn configure<'b>(
+        &self,
+        mut report: Report<'b>,
+        line: Option<u32>,
+        allows: &SourceAllows,
+    ) -> Option<Report<'b>> {
+        if report.severity != Severity::Warning {
+            return Some(report);
+        }
+
+        if let (Some(line), Some(code)) = (line, report.code) {
+            if allows.allows(line, code) {
+                return None;
+            }
+        }
+
+        match self.level(report.code) {
+            WarningLevel::Allow => None,
+            WarningLevel::Warn => Some(report),
+            WarningLevel::Deny => {
+                report.severity = Severity::RuntimeError;
+
+                Some(report)
+            }
+        }
+    }
+}
+
+/// The warnings that ALLOW_COMMENT comments silence in one module, by 0-based line.
+#[derive(Clone, Debug, Default)]
+pub struct SourceAllows {
+    by_line: MutMap<u32, Vec<ErrorCode>>,
+}
+
+impl SourceAllows {
+    pub fn new(src: &str) -> Self {
+        let lines: Vec<&str> = src.lines().collect();
+        let mut by_line: MutMap<u32, Vec<ErrorCode>> = MutMap::default();
+
+        for (index, line) in lines.iter().enumerate() {
+            let start = match line.find(ALLOW_COMMENT) {
+                Some(start) => start,
+                None => continue,
+            };
+
+            let codes = line[start + ALLOW_COMMENT.len()..]
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter_map(ErrorCode::lookup);
+
+            let target = if line[..start].trim().is_empty() {
+                // A comment on its own line applies to the code after it
+                (index + 1..lines.len()).find(|&i| {
+                    let line = lines[i].trim_start();
+
+                    !line.is_empty() && !line.starts_with('#')
+                })
+            } else {
+                Some(index)
+            };
+
+            if let Some(target) = target {
+                by_line.entry(target as u32).or_default().extend(codes);
+            }
+        }
+
+        SourceAllows { by_line }
+    }
+
+    pub fn allows(&self, line: u32, code: ErrorCode) -> bool {
+        match self.by_line.get(&line) {
+            Some(codes) => codes.contains(&code),
+            None => false,
+        }
+    }
+}
@@ -13,6 +13,7 @@ mod test_reporting {
     use indoc::indoc;
     use roc_can::abilities::AbilitiesStore;
     use roc_can::expr::PendingDerives;
+    use roc_collections::all::MutSet;
     use roc_load::{self, ExecutionMode, LoadConfig, LoadedModule, LoadingProblem, Threading};
     use roc_module::symbol::{Interns, ModuleId};
     use roc_packaging::cache::RocCacheDir;
@@ -22,10 +23,11 @@ mod test_reporting {
     use roc_problem::Severity;
     use roc_region::all::LineInfo;
     use roc_reporting::report::{
-        can_problem, parse_problem, type_problem, RenderTarget, Report, ANSI_STYLE_CODES,
-        DEFAULT_PALETTE,
+        can_problem, parse_problem, type_problem, ErrorCode, RenderTarget, Report,
+        ANSI_STYLE_CODES, DEFAULT_PALETTE,
     };
     use roc_reporting::report::{RocDocAllocator, RocDocBuilder};
+    use roc_reporting::warnings::{SourceAllows, WarningConfig, WarningLevel};
     use roc_solve_problem::TypeError;
     use roc_types::subs::Subs;
     use std::path::PathBuf;
@@ -43,6 +45,7 @@ mod test_reporting {
             doc,
             filename: filename_from_string(r"/code/proj/Main.roc"),
             severity: Severity::RuntimeError,
+            code: None,
         }
     }
 
@@ -478,7 +481,7 @@ mod test_reporting {
         "#
         ),
         @r###"
-    ── NOT EXPOSED ─────────────────────────────────────────── /code/proj/Main.roc ─
+    ── NOT EXPOSED [R0255] ─────────────────────────────────── /code/proj/Main.roc ─
 
     The List module does not expose `isempty`:
 
@@ -505,7 +508,7 @@ mod test_reporting {
         "#
         ),
         @r###"
-    ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED DEFINITION [R0201] ───────────────────────────── /code/proj/Main.roc ─
 
     `y` is not used anywhere in your code.
 
@@ -530,7 +533,7 @@ mod test_reporting {
        "#
         ),
         @r###"
-    ── DUPLICATE NAME ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE NAME [R0210] ──────────────────────────────── /code/proj/Main.roc ─
 
     The `i` name is first defined here:
 
@@ -562,7 +565,7 @@ mod test_reporting {
        "#
         ),
         @r###"
-    ── DUPLICATE NAME ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE NAME [R0210] ──────────────────────────────── /code/proj/Main.roc ─
 
     The `Booly` name is first defined here:
 
@@ -594,7 +597,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0208] ──────────────────────────────── /code/proj/Main.roc ─
 
     Using != and == together requires parentheses, to clarify how they
     should be grouped.
@@ -623,7 +626,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-        ── UNRECOGNIZED NAME ───────────────────────────────────── /code/proj/Main.roc ─
+        ── UNRECOGNIZED NAME [R0250] ───────────────────────────── /code/proj/Main.roc ─
 
         Nothing is named `bar` in this scope.
 
@@ -647,7 +650,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNRECOGNIZED NAME ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNRECOGNIZED NAME [R0250] ───────────────────────────── /code/proj/Main.roc ─
 
     Nothing is named `true` in this scope.
 
@@ -679,7 +682,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0208] ──────────────────────────────── /code/proj/Main.roc ─
 
     Using more than one == like this requires parentheses, to clarify how
     things should be grouped.
@@ -705,7 +708,7 @@ mod test_reporting {
          "#
         ),
         @r###"
-    ── UNUSED ARGUMENT ─────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED ARGUMENT [R0205] ─────────────────────────────── /code/proj/Main.roc ─
 
     `box` doesn't use `htmlChildren`.
 
@@ -718,7 +721,7 @@ mod test_reporting {
     at the start of a variable name is a way of saying that the variable
     is not used.
 
-    ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED DEFINITION [R0201] ───────────────────────────── /code/proj/Main.roc ─
 
     `y` is not used anywhere in your code.
 
@@ -802,7 +805,7 @@ mod test_reporting {
             ),
             indoc!(
                 r#"
-                <cyan>── UNRECOGNIZED NAME ───────────────────────────────────── /code/proj/Main.roc ─<reset>
+                <cyan>── UNRECOGNIZED NAME [R0250] ───────────────────────────── /code/proj/Main.roc ─<reset>
 
                 Nothing is named `theAdmin` in this scope.
 
@@ -820,6 +823,234 @@ mod test_reporting {
         );
     }
 
+    #[test]
+    fn json_report_region_does_not_leak_into_the_next() {
+        use ven_pretty::DocAllocator;
+
+        let src: &str = indoc!(
+            r#"
+                isDisabled = \user -> user.isAdmin
+
+                theAdmin
+                    |> isDisabled
+            "#
+        );
+
+        let arena = Bump::new();
+        let (_type_problems, can_problems, home, interns) =
+            infer_expr_help(&arena, src).expect("parse error");
+
+        let src_lines: Vec<&str> = src.split('\n').collect();
+        let lines = LineInfo::new(src);
+        let filename = filename_from_string(r"/code/proj/Main.roc");
+        let alloc = RocDocAllocator::new(&src_lines, home, &interns);
+
+        // This report shows a snippet of code, but is given its region explicitly
+        let problem = can_problems
+            .into_iter()
+            .find(|problem| problem.region().is_some())
+            .expect("a problem with a region");
+        let region = problem.region().map(|region| lines.convert_region(region));
+        let mut buf = String::new();
+
+        can_problem(&alloc, &lines, filename.clone(), problem)
+            .render_json(&mut buf, &alloc, region);
+
+        // so this one, which shows no code, must not pick up the snippet shown by the last one
+        let report = Report {
+            title: "NO CODE".to_string(),
+            filename,
+            doc: alloc.text("This report doesn't show any code."),
+            severity: Severity::Warning,
+            code: None,
+        };
+        let mut buf = String::new();
+
+        report.render_json(&mut buf, &alloc, None);
+
+        let json: serde_json::Value = serde_json::from_str(&buf).unwrap();
+
+        assert_eq!(json["region"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn report_as_json_and_sarif() {
+        let src: &str = indoc!(
+            r#"
+                isDisabled = \user -> user.isAdmin
+
+                theAdmin
+                    |> isDisabled
+            "#
+        );
+
+        let arena = Bump::new();
+        let (_type_problems, can_problems, home, interns) =
+            infer_expr_help(&arena, src).expect("parse error");
+
+        let src_lines: Vec<&str> = src.split('\n').collect();
+        let lines = LineInfo::new(src);
+        let filename = filename_from_string(r"/code/proj/Main.roc");
+        let alloc = RocDocAllocator::new(&src_lines, home, &interns);
+
+        let mut json_reports = Vec::new();
+        let mut sarif_results = Vec::new();
+
+        for problem in can_problems {
+            let mut buf = String::new();
+            can_problem(&alloc, &lines, filename.clone(), problem.clone())
+                .render_json(&mut buf, &alloc, None);
+            json_reports.push(serde_json::from_str::<serde_json::Value>(&buf).unwrap());
+
+            let mut buf = String::new();
+            can_problem(&alloc, &lines, filename.clone(), problem)
+                .render_sarif(&mut buf, &alloc, None);
+            sarif_results.push(buf);
+        }
+
+        let report = json_reports
+            .iter()
+            .find(|report| report["title"] == "UNRECOGNIZED NAME")
+            .expect("a report about `theAdmin`");
+
+        assert_eq!(report["severity"], "error");
+        assert_eq!(report["code"], "R0250");
+        assert_eq!(report["file"], "/code/proj/Main.roc");
+        assert_eq!(
+            report["region"],
+            serde_json::json!({
+                "start": { "line": 3, "column": 1 },
+                "end": { "line": 3, "column": 9 },
+            })
+        );
+        assert!(report["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("Nothing is named `theAdmin` in this scope."));
+
+        let log: serde_json::Value =
+            serde_json::from_str(&roc_reporting::cli::sarif_log(&sarif_results)).unwrap();
+        let results = log["runs"][0]["results"].as_array().unwrap();
+        let result = results
+            .iter()
+            .find(|result| result["ruleId"] == "R0250")
+            .expect("a result about `theAdmin`");
+
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(result["level"], "error");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["region"],
+            serde_json::json!({
+                "startLine": 3,
+                "startColumn": 1,
+                "endLine": 3,
+                "endColumn": 9,
+            })
+        );
+    }
+
+    #[test]
+    fn error_codes_are_unique_and_explained() {
+        let mut numbers = MutSet::default();
+        let mut names = MutSet::default();
+
+        for &code in ErrorCode::ALL {
+            assert!(numbers.insert(code.number()), "{} is used twice", code);
+            assert!(names.insert(code.name()), "{} is used twice", code.name());
+            assert!(
+                !code.explanation().trim().is_empty(),
+                "{} has no explanation",
+                code
+            );
+
+            assert_eq!(ErrorCode::lookup(&code.to_string()), Some(code));
+            assert_eq!(
+                ErrorCode::lookup(&code.to_string().to_lowercase()),
+                Some(code)
+            );
+            assert_eq!(ErrorCode::lookup(code.name()), Some(code));
+        }
+
+        assert_eq!(ErrorCode::lookup("202"), Some(ErrorCode::UnusedImport));
+        assert_eq!(ErrorCode::lookup("R9999"), None);
+    }
+
+    #[test]
+    fn warning_config_allows_and_denies() {
+        let interns = Interns::default();
+        let alloc = RocDocAllocator::new(&[], test_home(), &interns);
+        let report = |severity, code| Report {
+            severity,
+            code: Some(code),
+            ..to_simple_report(alloc.module(ModuleId::NUM))
+        };
+        let configured = |config: &WarningConfig, allows: &SourceAllows, line, code| {
+            config
+                .configure(report(Severity::Warning, code), Some(line), allows)
+                .map(|report| report.severity)
+        };
+
+        let allows = SourceAllows::new(indoc!(
+            r#"
+            x = 1 # roc:allow unused-def
+            # roc:allow unused-argument, R0202
+            # another comment
+            f = \y -> 2
+            # roc:allow unused-def
+
+            g = 3
+            "#
+        ));
+
+        let mut config = WarningConfig::default();
+        config.set(WarningLevel::Deny, "warnings").unwrap();
+        config.set(WarningLevel::Allow, "R0205").unwrap();
+        config.set(WarningLevel::Warn, "unused-import").unwrap();
+
+        assert!(config.set(WarningLevel::Deny, "unused-everything").is_err());
+        assert!(config.set(WarningLevel::Allow, "type-mismatch").is_err());
+        assert!(config.set(WarningLevel::Allow, "R0112").is_err());
+
+        // Comments beat the config, but only on the line they apply to
+        assert_eq!(configured(&config, &allows, 0, ErrorCode::UnusedDef), None);
+        assert_eq!(
+            configured(&config, &allows, 3, ErrorCode::UnusedDef),
+            Some(Severity::RuntimeError)
+        );
+        assert_eq!(
+            configured(&config, &allows, 3, ErrorCode::UnusedImport),
+            None
+        );
+
+        // Specific warnings beat `warnings`
+        assert_eq!(
+            configured(&config, &allows, 1, ErrorCode::UnusedArgument),
+            None
+        );
+        assert_eq!(
+            configured(&config, &allows, 1, ErrorCode::UnusedImport),
+            Some(Severity::Warning)
+        );
+
+        // A comment on its own line skips blank lines too
+        assert_eq!(configured(&config, &allows, 6, ErrorCode::UnusedDef), None);
+        assert_eq!(
+            configured(&config, &allows, 5, ErrorCode::UnusedDef),
+            Some(Severity::RuntimeError)
+        );
+
+        // Errors can't be allowed
+        let error = config.configure(
+            report(Severity::RuntimeError, ErrorCode::UnusedDef),
+            Some(0),
+            &allows,
+        );
+        assert_eq!(
+            error.map(|report| report.severity),
+            Some(Severity::RuntimeError)
+        );
+    }
+
     test_report!(
         if_condition_not_bool,
         indoc!(
@@ -828,7 +1059,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This `if` condition needs to be a Bool:
 
@@ -854,7 +1085,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This `if` guard condition needs to be a Bool:
 
@@ -879,7 +1110,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This `if` has an `else` branch with a different type from its `then` branch:
 
@@ -906,7 +1137,7 @@ mod test_reporting {
              "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The 3rd branch of this `if` does not match all the previous branches:
 
@@ -936,7 +1167,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The 2nd branch of this `when` does not match all the previous branches:
 
@@ -974,7 +1205,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -1018,7 +1249,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This list contains elements with different types:
 
@@ -1045,7 +1276,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This list contains elements with different types:
 
@@ -1078,7 +1309,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     I cannot update the `.foo` field like this:
 
@@ -1108,7 +1339,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── CIRCULAR TYPE [R0303] ───────────────────────────────── /code/proj/Main.roc ─
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1133,7 +1364,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── CIRCULAR TYPE [R0303] ───────────────────────────────── /code/proj/Main.roc ─
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1159,7 +1390,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── CIRCULAR TYPE [R0303] ───────────────────────────────── /code/proj/Main.roc ─
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1172,7 +1403,7 @@ mod test_reporting {
 
         List ∞ -> *
 
-    ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── CIRCULAR TYPE [R0303] ───────────────────────────────── /code/proj/Main.roc ─
 
     I'm inferring a weird self-referential type for `g`:
 
@@ -1199,7 +1430,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression is used in an unexpected way:
 
@@ -1235,7 +1466,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression is used in an unexpected way:
 
@@ -1269,7 +1500,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── CIRCULAR TYPE [R0303] ───────────────────────────────── /code/proj/Main.roc ─
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1295,7 +1526,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── CIRCULAR TYPE [R0303] ───────────────────────────────── /code/proj/Main.roc ─
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1333,7 +1564,7 @@ mod test_reporting {
         // against that extra variable, rather than possibly having to translate a `Type`
         // again.
         @r###"
-    ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── CIRCULAR TYPE [R0303] ───────────────────────────────── /code/proj/Main.roc ─
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1346,7 +1577,7 @@ mod test_reporting {
 
         List ∞ -> List *
 
-    ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── CIRCULAR TYPE [R0303] ───────────────────────────────── /code/proj/Main.roc ─
 
     I'm inferring a weird self-referential type for `g`:
 
@@ -1373,7 +1604,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── CIRCULAR TYPE [R0303] ───────────────────────────────── /code/proj/Main.roc ─
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1386,7 +1617,7 @@ mod test_reporting {
 
         List ∞ -> List *
 
-    ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── CIRCULAR TYPE [R0303] ───────────────────────────────── /code/proj/Main.roc ─
 
     I'm inferring a weird self-referential type for `g`:
 
@@ -1414,7 +1645,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to `f` has an unexpected type:
 
@@ -1448,7 +1679,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to `f` has an unexpected type:
 
@@ -1485,7 +1716,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to `f` has an unexpected type:
 
@@ -1522,7 +1753,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the `then` branch of this `if` expression:
 
@@ -1556,7 +1787,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `x` definition:
 
@@ -1589,7 +1820,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `x` definition:
 
@@ -1621,7 +1852,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TOO MANY ARGS ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TOO MANY ARGS [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The `x` value is not a function, but it was given 1 argument:
 
@@ -1643,7 +1874,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TOO MANY ARGS ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TOO MANY ARGS [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The `f` function expects 1 argument, but it got 2 instead:
 
@@ -1665,7 +1896,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TOO FEW ARGS ────────────────────────────────────────── /code/proj/Main.roc ─
+    ── TOO FEW ARGS [R0301] ────────────────────────────────── /code/proj/Main.roc ─
 
     The `f` function expects 2 arguments, but it got only 1:
 
@@ -1686,7 +1917,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The branches of this `when` expression don't match the condition:
 
@@ -1715,7 +1946,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0302] ───────────────────────────────── /code/proj/Main.roc ─
 
     The 2nd pattern in this `when` does not match the previous ones:
 
@@ -1741,7 +1972,7 @@ mod test_reporting {
              "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The branches of this `when` expression don't match the condition:
 
@@ -1769,7 +2000,7 @@ mod test_reporting {
              "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The branches of this `when` expression don't match the condition:
 
@@ -1798,7 +2029,7 @@ mod test_reporting {
              "#
         ),
         @r###"
-    ── UNRECOGNIZED NAME ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNRECOGNIZED NAME [R0250] ───────────────────────────── /code/proj/Main.roc ─
 
     Nothing is named `foo` in this scope.
 
@@ -1854,7 +2085,7 @@ mod test_reporting {
         ),
         // Just putting this here. We should probably handle or-patterns better
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0302] ───────────────────────────────── /code/proj/Main.roc ─
 
     The 2nd pattern in this branch does not match the previous ones:
 
@@ -1882,7 +2113,7 @@ mod test_reporting {
         ),
         // Maybe this should specifically say the pattern doesn't work?
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression is used in an unexpected way:
 
@@ -1910,7 +2141,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of this definition:
 
@@ -1941,7 +2172,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0261] ──────────────────────────────── /code/proj/Main.roc ─
 
     This integer pattern is malformed:
 
@@ -1962,7 +2193,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0260] ──────────────────────────────── /code/proj/Main.roc ─
 
     This float pattern is malformed:
 
@@ -1983,7 +2214,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0261] ──────────────────────────────── /code/proj/Main.roc ─
 
     This hex integer pattern is malformed:
 
@@ -2004,7 +2235,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0261] ──────────────────────────────── /code/proj/Main.roc ─
 
     This octal integer pattern is malformed:
 
@@ -2025,7 +2256,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0261] ──────────────────────────────── /code/proj/Main.roc ─
 
     This binary integer pattern is malformed:
 
@@ -2047,7 +2278,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `x` definition:
 
@@ -2097,7 +2328,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the `else` branch of this `if` expression:
 
@@ -2131,7 +2362,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `f` definition:
 
@@ -2166,7 +2397,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `f` definition:
 
@@ -2202,7 +2433,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNRECOGNIZED NAME ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNRECOGNIZED NAME [R0250] ───────────────────────────── /code/proj/Main.roc ─
 
     Nothing is named `ok` in this scope.
 
@@ -2233,7 +2464,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED DEFINITION [R0201] ───────────────────────────── /code/proj/Main.roc ─
 
     `ok` is not used anywhere in your code.
 
@@ -2243,7 +2474,7 @@ mod test_reporting {
     If you didn't intend on using `ok` then remove it so future readers of
     your code don't wonder why it is there.
 
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `f` definition:
 
@@ -2275,7 +2506,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── CIRCULAR DEFINITION ─────────────────────────────────── /code/proj/Main.roc ─
+    ── CIRCULAR DEFINITION [R0212] ─────────────────────────── /code/proj/Main.roc ─
 
     `f` is defined directly in terms of itself:
 
@@ -2302,7 +2533,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── CIRCULAR DEFINITION ─────────────────────────────────── /code/proj/Main.roc ─
+    ── CIRCULAR DEFINITION [R0212] ─────────────────────────── /code/proj/Main.roc ─
 
     The `foo` definition is causing a very tricky infinite loop:
 
@@ -2330,7 +2561,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This `x` record doesn’t have a `foo` field:
 
@@ -2352,7 +2583,7 @@ mod test_reporting {
         ),
         // TODO also suggest fields with the correct type
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This `x` record doesn’t have a `foo` field:
 
@@ -2385,7 +2616,7 @@ mod test_reporting {
         ),
         // TODO also suggest fields with the correct type
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This `r` record doesn’t have a `foo` field:
 
@@ -2413,7 +2644,7 @@ mod test_reporting {
         ),
         // TODO also suggest fields with the correct type
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This `x` record doesn’t have a `foo` field:
 
@@ -2443,7 +2674,7 @@ mod test_reporting {
         ),
         // TODO also suggest fields with the correct type
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `add` has an unexpected type:
 
@@ -2468,7 +2699,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `add` has an unexpected type:
 
@@ -2496,7 +2727,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `add` has an unexpected type:
 
@@ -2524,7 +2755,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `f` definition:
 
@@ -2558,7 +2789,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `f` definition:
 
@@ -2601,7 +2832,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This pattern does not cover all the possibilities:
 
@@ -2632,7 +2863,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression is used in an unexpected way:
 
@@ -2663,7 +2894,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -2690,7 +2921,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -2718,7 +2949,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -2747,7 +2978,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -2776,7 +3007,7 @@ mod test_reporting {
         ),
         // Tip: Looks like a record field guard is not exhaustive. Learn more about record pattern matches at TODO.
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -2805,7 +3036,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -2831,7 +3062,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -2858,7 +3089,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── REDUNDANT PATTERN ───────────────────────────────────── /code/proj/Main.roc ─
+    ── REDUNDANT PATTERN [R0306] ───────────────────────────── /code/proj/Main.roc ─
 
     The 2nd pattern is redundant:
 
@@ -2886,7 +3117,7 @@ mod test_reporting {
         ),
         // de-aliases the alias to give a better error message
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to `f` has an unexpected type:
 
@@ -2924,7 +3155,7 @@ mod test_reporting {
         ),
         // should not report Bar as unused!
         @r###"
-    ── CYCLIC ALIAS ────────────────────────────────────────── /code/proj/Main.roc ─
+    ── CYCLIC ALIAS [R0211] ────────────────────────────────── /code/proj/Main.roc ─
 
     The `Foo` alias is self-recursive in an invalid way:
 
@@ -2950,7 +3181,7 @@ mod test_reporting {
         ),
         // should not report Bar as unused!
         @r###"
-    ── CYCLIC ALIAS ────────────────────────────────────────── /code/proj/Main.roc ─
+    ── CYCLIC ALIAS [R0211] ────────────────────────────────── /code/proj/Main.roc ─
 
     The `Foo` alias is self-recursive in an invalid way:
 
@@ -2970,7 +3201,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── DUPLICATE FIELD NAME ────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE FIELD NAME [R0215] ────────────────────────── /code/proj/Main.roc ─
 
     This record defines the `.x` field twice!
 
@@ -2994,7 +3225,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── DUPLICATE FIELD NAME ────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE FIELD NAME [R0215] ────────────────────────── /code/proj/Main.roc ─
 
     This record defines the `.x` field twice!
 
@@ -3022,7 +3253,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── DUPLICATE FIELD NAME ────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE FIELD NAME [R0215] ────────────────────────── /code/proj/Main.roc ─
 
     This record defines the `.x` field twice!
 
@@ -3057,7 +3288,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── DUPLICATE FIELD NAME ────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE FIELD NAME [R0215] ────────────────────────── /code/proj/Main.roc ─
 
     This record defines the `.x` field twice!
 
@@ -3090,7 +3321,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── DUPLICATE FIELD NAME ────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE FIELD NAME [R0215] ────────────────────────── /code/proj/Main.roc ─
 
     This record type defines the `.foo` field twice!
 
@@ -3118,7 +3349,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── DUPLICATE TAG NAME ──────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE TAG NAME [R0217] ──────────────────────────── /code/proj/Main.roc ─
 
     This tag union type defines the `Foo` tag twice!
 
@@ -3147,7 +3378,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── NAMING PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── NAMING PROBLEM [R0218] ──────────────────────────────── /code/proj/Main.roc ─
 
     This annotation does not match the definition immediately following
     it:
@@ -3183,7 +3414,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0219] ──────────────────────────────── /code/proj/Main.roc ─
 
     This definition of `MyAlias` has an unexpected pattern:
 
@@ -3192,7 +3423,7 @@ mod test_reporting {
 
     Only type variables like `a` or `value` can occur in this position.
 
-    ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED DEFINITION [R0201] ───────────────────────────── /code/proj/Main.roc ─
 
     `MyAlias` is not used anywhere in your code.
 
@@ -3215,7 +3446,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0219] ──────────────────────────────── /code/proj/Main.roc ─
 
     This definition of `Age` has an unexpected pattern:
 
@@ -3237,7 +3468,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TOO MANY TYPE ARGUMENTS ─────────────────────────────── /code/proj/Main.roc ─
+    ── TOO MANY TYPE ARGUMENTS [R0246] ─────────────────────── /code/proj/Main.roc ─
 
     The `Num` opaque expects 1 type argument, but it got 2 instead:
 
@@ -3259,7 +3490,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TOO MANY TYPE ARGUMENTS ─────────────────────────────── /code/proj/Main.roc ─
+    ── TOO MANY TYPE ARGUMENTS [R0246] ─────────────────────── /code/proj/Main.roc ─
 
     The `Num` opaque expects 1 type argument, but it got 2 instead:
 
@@ -3283,7 +3514,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TOO FEW TYPE ARGUMENTS ──────────────────────────────── /code/proj/Main.roc ─
+    ── TOO FEW TYPE ARGUMENTS [R0246] ──────────────────────── /code/proj/Main.roc ─
 
     The `Pair` alias expects 2 type arguments, but it got 1 instead:
 
@@ -3307,7 +3538,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TOO MANY TYPE ARGUMENTS ─────────────────────────────── /code/proj/Main.roc ─
+    ── TOO MANY TYPE ARGUMENTS [R0246] ─────────────────────── /code/proj/Main.roc ─
 
     The `Pair` alias expects 2 type arguments, but it got 3 instead:
 
@@ -3330,7 +3561,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNUSED TYPE ALIAS PARAMETER ─────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED TYPE ALIAS PARAMETER [R0213] ─────────────────── /code/proj/Main.roc ─
 
     The `a` type parameter is not used in the `Foo` alias definition:
 
@@ -3352,7 +3583,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── ARGUMENTS BEFORE EQUALS ────────────────── tmp/elm_function_syntax/Test.roc ─
+    ── ARGUMENTS BEFORE EQUALS [R0108] ────────── tmp/elm_function_syntax/Test.roc ─
 
     I am partway through parsing a definition, but I got stuck here:
 
@@ -3380,7 +3611,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `x` definition:
 
@@ -3429,7 +3660,7 @@ mod test_reporting {
         // TODO render tag unions across multiple lines
         // TODO do not show recursion var if the recursion var does not render on the surface of a type
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `x` definition:
 
@@ -3483,7 +3714,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0261] ──────────────────────────────── /code/proj/Main.roc ─
 
     This integer literal is too big:
 
@@ -3495,7 +3726,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0261] ──────────────────────────────── /code/proj/Main.roc ─
 
     This integer literal is too small:
 
@@ -3507,7 +3738,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0261] ──────────────────────────────── /code/proj/Main.roc ─
 
     This integer literal is too big:
 
@@ -3519,7 +3750,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0261] ──────────────────────────────── /code/proj/Main.roc ─
 
     This integer literal is too small:
 
@@ -3531,7 +3762,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `add` has an unexpected type:
 
@@ -3560,7 +3791,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0260] ──────────────────────────────── /code/proj/Main.roc ─
 
     This float literal is too big:
 
@@ -3572,7 +3803,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0260] ──────────────────────────────── /code/proj/Main.roc ─
 
     This float literal is too small:
 
@@ -3605,7 +3836,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0261] ──────────────────────────────── /code/proj/Main.roc ─
 
     This integer literal contains an invalid digit:
 
@@ -3617,7 +3848,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0261] ──────────────────────────────── /code/proj/Main.roc ─
 
     This hex integer literal contains an invalid digit:
 
@@ -3629,7 +3860,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0261] ──────────────────────────────── /code/proj/Main.roc ─
 
     This octal integer literal contains an invalid digit:
 
@@ -3641,7 +3872,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0261] ──────────────────────────────── /code/proj/Main.roc ─
 
     This binary integer literal contains an invalid digit:
 
@@ -3671,7 +3902,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0261] ──────────────────────────────── /code/proj/Main.roc ─
 
     This hex integer literal contains no digits:
 
@@ -3683,7 +3914,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0261] ──────────────────────────────── /code/proj/Main.roc ─
 
     This octal integer literal contains no digits:
 
@@ -3695,7 +3926,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0261] ──────────────────────────────── /code/proj/Main.roc ─
 
     This binary integer literal contains no digits:
 
@@ -3719,7 +3950,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0260] ──────────────────────────────── /code/proj/Main.roc ─
 
     This float literal contains an invalid digit:
 
@@ -3750,7 +3981,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0262] ──────────────────────────────── /code/proj/Main.roc ─
 
     This expression cannot be updated:
 
@@ -3759,7 +3990,7 @@ mod test_reporting {
 
     Only variables can be updated with record update syntax.
 
-    ── MODULE NOT IMPORTED ─────────────────────────────────── /code/proj/Main.roc ─
+    ── MODULE NOT IMPORTED [R0256] ─────────────────────────── /code/proj/Main.roc ─
 
     The `Test` module is not imported:
 
@@ -3774,7 +4005,7 @@ mod test_reporting {
         Dict
         Hash
 
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0262] ──────────────────────────────── /code/proj/Main.roc ─
 
     This expression cannot be updated:
 
@@ -3793,7 +4024,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── MODULE NOT IMPORTED ─────────────────────────────────── /code/proj/Main.roc ─
+    ── MODULE NOT IMPORTED [R0256] ─────────────────────────── /code/proj/Main.roc ─
 
     The `Foo` module is not imported:
 
@@ -3818,7 +4049,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `add` has an unexpected type:
 
@@ -3846,7 +4077,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0302] ───────────────────────────────── /code/proj/Main.roc ─
 
     The 1st argument to `f` is weird:
 
@@ -3875,7 +4106,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of this definition:
 
@@ -3906,7 +4137,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0302] ───────────────────────────────── /code/proj/Main.roc ─
 
     The 1st argument to `f` is weird:
 
@@ -3939,7 +4170,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The branches of this `when` expression don't match the condition:
 
@@ -3972,7 +4203,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression is used in an unexpected way:
 
@@ -4003,7 +4234,7 @@ mod test_reporting {
                 "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to this function has an unexpected type:
 
@@ -4037,7 +4268,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The branches of this `when` expression don't match the condition:
 
@@ -4071,7 +4302,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The branches of this `when` expression don't match the condition:
 
@@ -4099,7 +4330,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── BAD OPTIONAL VALUE ──────────────────────────────────── /code/proj/Main.roc ─
+    ── BAD OPTIONAL VALUE [R0216] ──────────────────────────── /code/proj/Main.roc ─
 
     This record uses an optional value for the `.y` field in an incorrect
     context!
@@ -4136,7 +4367,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── REDUNDANT PATTERN ───────────────────────────────────── /code/proj/Main.roc ─
+    ── REDUNDANT PATTERN [R0306] ───────────────────────────── /code/proj/Main.roc ─
 
     The 3rd pattern is redundant:
 
@@ -4181,7 +4412,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNUSED ARGUMENT ─────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED ARGUMENT [R0205] ─────────────────────────────── /code/proj/Main.roc ─
 
     `f` doesn't use `foo`.
 
@@ -4203,7 +4434,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0258] ──────────────────────────────── /code/proj/Main.roc ─
 
     I am trying to parse a qualified name here:
 
@@ -4224,7 +4455,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0258] ──────────────────────────────── /code/proj/Main.roc ─
 
     I am trying to parse a qualified name here:
 
@@ -4244,7 +4475,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0258] ──────────────────────────────── /code/proj/Main.roc ─
 
     I trying to parse a record field access here:
 
@@ -4266,7 +4497,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNKNOWN OPERATOR ──────────────── tmp/type_annotation_double_colon/Test.roc ─
+    ── UNKNOWN OPERATOR [R0116] ──────── tmp/type_annotation_double_colon/Test.roc ─
 
     This looks like an operator, but it's not one I recognize!
 
@@ -4298,7 +4529,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TOO MANY ARGS ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TOO MANY ARGS [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This value is not a function, but it was given 3 arguments:
 
@@ -4317,7 +4548,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED TAG UNION TYPE ───────────────────── tmp/tag_union_open/Test.roc ─
+    ── UNFINISHED TAG UNION TYPE [R0110] ───────────── tmp/tag_union_open/Test.roc ─
 
     I am partway through parsing a tag union type, but I got stuck here:
 
@@ -4339,7 +4570,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED TAG UNION TYPE ────────────────────── tmp/tag_union_end/Test.roc ─
+    ── UNFINISHED TAG UNION TYPE [R0110] ────────────── tmp/tag_union_end/Test.roc ─
 
     I am partway through parsing a tag union type, but I got stuck here:
 
@@ -4361,7 +4592,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── WEIRD TAG NAME ────────────────── tmp/tag_union_lowercase_tag_name/Test.roc ─
+    ── WEIRD TAG NAME [R0110] ────────── tmp/tag_union_lowercase_tag_name/Test.roc ─
 
     I am partway through parsing a tag union type, but I got stuck here:
 
@@ -4382,7 +4613,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── WEIRD TAG NAME ─────────── tmp/tag_union_second_lowercase_tag_name/Test.roc ─
+    ── WEIRD TAG NAME [R0110] ─── tmp/tag_union_second_lowercase_tag_name/Test.roc ─
 
     I am partway through parsing a tag union type, but I got stuck here:
 
@@ -4403,7 +4634,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED RECORD TYPE ────────────────────── tmp/record_type_open/Test.roc ─
+    ── UNFINISHED RECORD TYPE [R0110] ────────────── tmp/record_type_open/Test.roc ─
 
     I am partway through parsing a record type, but I got stuck here:
 
@@ -4426,7 +4657,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED RECORD TYPE ─────────────── tmp/record_type_open_indent/Test.roc ─
+    ── UNFINISHED RECORD TYPE [R0110] ─────── tmp/record_type_open_indent/Test.roc ─
 
     I am partway through parsing a record type, but I got stuck here:
 
@@ -4449,7 +4680,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED RECORD TYPE ─────────────────────── tmp/record_type_end/Test.roc ─
+    ── UNFINISHED RECORD TYPE [R0110] ─────────────── tmp/record_type_end/Test.roc ─
 
     I am partway through parsing a record type, but I got stuck here:
 
@@ -4471,7 +4702,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED RECORD TYPE ──────── tmp/record_type_keyword_field_name/Test.roc ─
+    ── UNFINISHED RECORD TYPE [R0110] ─ ...record_type_keyword_field_name/Test.roc ─
 
     I just started parsing a record type, but I got stuck on this field
     name:
@@ -4493,7 +4724,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED RECORD TYPE ───────────── tmp/record_type_missing_comma/Test.roc ─
+    ── UNFINISHED RECORD TYPE [R0110] ───── tmp/record_type_missing_comma/Test.roc ─
 
     I am partway through parsing a record type, but I got stuck here:
 
@@ -4510,7 +4741,7 @@ mod test_reporting {
         record_type_tab,
         "f : { foo \t }",
         @r###"
-    ── TAB CHARACTER ──────────────────────────────── tmp/record_type_tab/Test.roc ─
+    ── TAB CHARACTER [R0114] ──────────────────────── tmp/record_type_tab/Test.roc ─
 
     I encountered a tab character
 
@@ -4527,7 +4758,7 @@ mod test_reporting {
         |golden| pretty_assertions::assert_eq!(
             golden,
             &format!(
-                r###"── TAB CHARACTER ─────────────────────────────── tmp/comment_with_tab/Test.roc ─
+                r###"── TAB CHARACTER [R0114] ─────────────────────── tmp/comment_with_tab/Test.roc ─
 
 I encountered a tab character
 
@@ -4549,7 +4780,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── UNFINISHED PARENTHESES ────────────────── tmp/type_in_parens_start/Test.roc ─
+    ── UNFINISHED PARENTHESES [R0110] ────────── tmp/type_in_parens_start/Test.roc ─
 
     I am partway through parsing a type in parentheses, but I got stuck
     here:
@@ -4572,7 +4803,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── UNFINISHED PARENTHESES ──────────────────── tmp/type_in_parens_end/Test.roc ─
+    ── UNFINISHED PARENTHESES [R0110] ──────────── tmp/type_in_parens_end/Test.roc ─
 
     I am partway through parsing a type in parentheses, but I got stuck
     here:
@@ -4597,7 +4828,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0258] ──────────────────────────────── /code/proj/Main.roc ─
 
     I am confused by this type name:
 
@@ -4627,7 +4858,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0258] ──────────────────────────────── /code/proj/Main.roc ─
 
     I am confused by this type name:
 
@@ -4656,7 +4887,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── UNFINISHED TYPE ───────────────────────── tmp/type_apply_stray_dot/Test.roc ─
+    ── UNFINISHED TYPE [R0110] ───────────────── tmp/type_apply_stray_dot/Test.roc ─
 
     I just started parsing a type, but I got stuck here:
 
@@ -4677,7 +4908,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0258] ──────────────────────────────── /code/proj/Main.roc ─
 
     I am confused by this type name:
 
@@ -4708,7 +4939,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0258] ──────────────────────────────── /code/proj/Main.roc ─
 
     I am confused by this type name:
 
@@ -4728,7 +4959,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── MISSING FINAL EXPRESSION ──────── tmp/def_missing_final_expression/Test.roc ─
+    ── MISSING FINAL EXPRESSION [R0118] ─ ...def_missing_final_expression/Test.roc ─
 
     I am partway through parsing a definition, but I got stuck here:
 
@@ -4756,7 +4987,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── INDENT ENDS AFTER EXPRESSION ────── tmp/expression_indentation_end/Test.roc ─
+    ── INDENT ENDS AFTER EXPRESSION [R0123] ─ ...pression_indentation_end/Test.roc ─
 
     I am partway through parsing an expression, but I got stuck here:
 
@@ -4782,7 +5013,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── UNFINISHED INLINE ALIAS ──────────────────── tmp/type_inline_alias/Test.roc ─
+    ── UNFINISHED INLINE ALIAS [R0110] ──────────── tmp/type_inline_alias/Test.roc ─
 
     I just started parsing an inline type alias, but I got stuck here:
 
@@ -4804,7 +5035,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── DOUBLE COMMA ─────────────────────────────── tmp/type_double_comma/Test.roc ─
+    ── DOUBLE COMMA [R0110] ─────────────────────── tmp/type_double_comma/Test.roc ─
 
     I just started parsing a function argument type, but I encountered two
     commas in a row:
@@ -4827,7 +5058,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── UNFINISHED TYPE ─────────────────────── tmp/type_argument_no_arrow/Test.roc ─
+    ── UNFINISHED TYPE [R0110] ─────────────── tmp/type_argument_no_arrow/Test.roc ─
 
     I am partway through parsing a type, but I got stuck here:
 
@@ -4850,7 +5081,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── UNFINISHED TYPE ───────────── tmp/type_argument_arrow_then_nothing/Test.roc ─
+    ── UNFINISHED TYPE [R0110] ───── tmp/type_argument_arrow_then_nothing/Test.roc ─
 
     I just started parsing a type, but I got stuck here:
 
@@ -4875,7 +5106,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `myDict` definition:
 
@@ -4910,7 +5141,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `myDict` definition:
 
@@ -4942,7 +5173,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── IF GUARD NO CONDITION ───────────── tmp/if_guard_without_condition/Test.roc ─
+    ── IF GUARD NO CONDITION [R0125] ───── tmp/if_guard_without_condition/Test.roc ─
 
     I just started parsing an if guard, but there is no guard condition:
 
@@ -4967,7 +5198,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── UNFINISHED PATTERN ────────────────────────── tmp/empty_or_pattern/Test.roc ─
+    ── UNFINISHED PATTERN [R0111] ────────────────── tmp/empty_or_pattern/Test.roc ─
 
     I just started parsing a pattern, but I got stuck here:
 
@@ -4992,7 +5223,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── MISSING ARROW ────────────────────────── tmp/pattern_binds_keyword/Test.roc ─
+    ── MISSING ARROW [R0125] ────────────────── tmp/pattern_binds_keyword/Test.roc ─
 
     I am partway through parsing a `when` expression, but got stuck here:
 
@@ -5028,7 +5259,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── UNFINISHED WHEN ─────────────────────────── tmp/when_missing_arrow/Test.roc ─
+    ── UNFINISHED WHEN [R0125] ─────────────────── tmp/when_missing_arrow/Test.roc ─
 
     I was partway through parsing a `when` expression, but I got stuck here:
 
@@ -5061,7 +5292,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── UNFINISHED ARGUMENT LIST ───────────────── tmp/lambda_double_comma/Test.roc ─
+    ── UNFINISHED ARGUMENT LIST [R0126] ───────── tmp/lambda_double_comma/Test.roc ─
 
     I am partway through parsing a function argument list, but I got stuck
     at this comma:
@@ -5082,7 +5313,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── UNFINISHED ARGUMENT LIST ──────────────── tmp/lambda_leading_comma/Test.roc ─
+    ── UNFINISHED ARGUMENT LIST [R0126] ──────── tmp/lambda_leading_comma/Test.roc ─
 
     I am partway through parsing a function argument list, but I got stuck
     at this comma:
@@ -5129,7 +5360,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── NOT END OF FILE ──────────────────────── tmp/when_outdented_branch/Test.roc ─
+    ── NOT END OF FILE [R0115] ──────────────── tmp/when_outdented_branch/Test.roc ─
 
     I expected to reach the end of the file, but got stuck here:
 
@@ -5148,7 +5379,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── UNEXPECTED ARROW ─────────────── tmp/when_over_indented_underscore/Test.roc ─
+    ── UNEXPECTED ARROW [R0116] ─────── tmp/when_over_indented_underscore/Test.roc ─
 
     I am parsing a `when` expression right now, but this arrow is confusing
     me:
@@ -5185,7 +5416,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── UNEXPECTED ARROW ────────────────────── tmp/when_over_indented_int/Test.roc ─
+    ── UNEXPECTED ARROW [R0116] ────────────── tmp/when_over_indented_int/Test.roc ─
 
     I am parsing a `when` expression right now, but this arrow is confusing
     me:
@@ -5225,7 +5456,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── UNFINISHED IF ────────────────────────────── tmp/if_outdented_then/Test.roc ─
+    ── UNFINISHED IF [R0124] ────────────────────── tmp/if_outdented_then/Test.roc ─
 
     I was partway through parsing an `if` expression, but I got stuck here:
 
@@ -5245,7 +5476,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── UNFINISHED IF ──────────────────────────────── tmp/if_missing_else/Test.roc ─
+    ── UNFINISHED IF [R0124] ──────────────────────── tmp/if_missing_else/Test.roc ─
 
     I was partway through parsing an `if` expression, but I got stuck here:
 
@@ -5264,7 +5495,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── UNFINISHED LIST ──────────────────────────── tmp/list_double_comma/Test.roc ─
+    ── UNFINISHED LIST [R0127] ──────────────────── tmp/list_double_comma/Test.roc ─
 
     I am partway through started parsing a list, but I got stuck here:
 
@@ -5284,7 +5515,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── UNFINISHED LIST ───────────────────────────── tmp/list_without_end/Test.roc ─
+    ── UNFINISHED LIST [R0127] ───────────────────── tmp/list_without_end/Test.roc ─
 
     I am partway through started parsing a list, but I got stuck here:
 
@@ -5310,7 +5541,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0260] ──────────────────────────────── /code/proj/Main.roc ─
 
     This float literal contains an invalid digit:
 
@@ -5328,7 +5559,7 @@ Tab characters are not allowed."###,
         unicode_not_hex,
         r#""abc\u(zzzz)def""#,
         @r###"
-    ── WEIRD CODE POINT ───────────────────────────── tmp/unicode_not_hex/Test.roc ─
+    ── WEIRD CODE POINT [R0128] ───────────────────── tmp/unicode_not_hex/Test.roc ─
 
     I am partway through parsing a unicode code point, but I got stuck
     here:
@@ -5346,7 +5577,7 @@ Tab characters are not allowed."###,
         interpolate_not_identifier,
         r#""abc\(32)def""#,
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0220] ──────────────────────────────── /code/proj/Main.roc ─
 
     This string interpolation is invalid:
 
@@ -5364,7 +5595,7 @@ Tab characters are not allowed."###,
         unicode_too_large,
         r#""abc\u(110000)def""#,
         @r###"
-    ── INVALID UNICODE ─────────────────────────────────────── /code/proj/Main.roc ─
+    ── INVALID UNICODE [R0221] ─────────────────────────────── /code/proj/Main.roc ─
 
     This unicode code point is invalid:
 
@@ -5379,7 +5610,7 @@ Tab characters are not allowed."###,
         weird_escape,
         r#""abc\qdef""#,
         @r###"
-    ── WEIRD ESCAPE ──────────────────────────────────── tmp/weird_escape/Test.roc ─
+    ── WEIRD ESCAPE [R0128] ──────────────────────────── tmp/weird_escape/Test.roc ─
 
     I was partway through parsing a  string literal, but I got stuck here:
 
@@ -5403,7 +5634,7 @@ Tab characters are not allowed."###,
         single_quote_too_long,
         r#"'abcdef'"#,
         @r###"
-    ── INVALID SCALAR ───────────────────────── tmp/single_quote_too_long/Test.roc ─
+    ── INVALID SCALAR [R0128] ───────────────── tmp/single_quote_too_long/Test.roc ─
 
     I am part way through parsing this scalar literal (character literal),
     but it's too long to fit in a U32 so it's not a valid scalar.
@@ -5420,7 +5651,7 @@ Tab characters are not allowed."###,
         single_no_end,
         r#""there is no end"#,
         @r###"
-    ── ENDLESS STRING ───────────────────────────────── tmp/single_no_end/Test.roc ─
+    ── ENDLESS STRING [R0128] ───────────────────────── tmp/single_no_end/Test.roc ─
 
     I cannot find the end of this string:
 
@@ -5436,7 +5667,7 @@ Tab characters are not allowed."###,
         multi_no_end,
         r#""""there is no end"#,
         @r###"
-    ── ENDLESS STRING ────────────────────────────────── tmp/multi_no_end/Test.roc ─
+    ── ENDLESS STRING [R0128] ────────────────────────── tmp/multi_no_end/Test.roc ─
 
     I cannot find the end of this block string:
 
@@ -5452,7 +5683,7 @@ Tab characters are not allowed."###,
         multi_insufficient_indent,
         "    \"\"\"\n  testing\n    \"\"\"", // 4 space indent on the start, 2 space on the `testing` line
         @r###"
-    ── INSUFFICIENT INDENT IN MULTI-LINE STRING ─ ..._insufficient_indent/Test.roc ─
+    ── INSUFFICIENT INDENT IN MULTI-LINE STRING [R0128] ─ ...cient_indent/Test.roc ─
 
     This multiline string is not sufficiently indented:
 
@@ -5474,7 +5705,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── INDENT ENDS AFTER EXPRESSION ──── tmp/dbg_without_final_expression/Test.roc ─
+    ── INDENT ENDS AFTER EXPRESSION [R0123] ─ ...without_final_expression/Test.roc ─
 
     I am partway through parsing a dbg statement, but I got stuck here:
 
@@ -5496,7 +5727,7 @@ Tab characters are not allowed."###,
             "#
         ),
         @r###"
-    ── INDENT ENDS AFTER EXPRESSION ─ tmp/expect_without_final_expression/Test.roc ─
+    ── INDENT ENDS AFTER EXPRESSION [R0123] ─ ...without_final_expression/Test.roc ─
 
     I am partway through parsing an expect statement, but I got stuck
     here:
@@ -5522,7 +5753,7 @@ Tab characters are not allowed."###,
             "#,
             ),
             @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This `if` has an `else` branch with a different type from its `then` branch:
 
@@ -5548,7 +5779,7 @@ Tab characters are not allowed."###,
                 $name,
                 &format!(r#"if Bool.true then "abc" else 1 {} 2"#, $op),
                 |golden| assert_eq!(golden, format!(
-r#"── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+r#"── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
 This `if` has an `else` branch with a different type from its `then` branch:
 
@@ -5591,7 +5822,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This `foo` record doesn’t have a `if` field:
 
@@ -5610,7 +5841,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── NOT EXPOSED ─────────────────────────────────────────── /code/proj/Main.roc ─
+    ── NOT EXPOSED [R0255] ─────────────────────────────────── /code/proj/Main.roc ─
 
     The Num module does not expose `if`:
 
@@ -5634,7 +5865,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0258] ──────────────────────────────── /code/proj/Main.roc ─
 
     I trying to parse a record field access here:
 
@@ -5653,7 +5884,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0258] ──────────────────────────────── /code/proj/Main.roc ─
 
     I am very confused by this field access:
 
@@ -5672,7 +5903,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0258] ──────────────────────────────── /code/proj/Main.roc ─
 
     I am very confused by this field access
 
@@ -5693,7 +5924,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── NAMING PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── NAMING PROBLEM [R0258] ──────────────────────────────── /code/proj/Main.roc ─
 
     I am trying to parse an identifier here:
 
@@ -5727,7 +5958,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNKNOWN OPERATOR ───────────────────────────────────── tmp/case_of/Test.roc ─
+    ── UNKNOWN OPERATOR [R0116] ───────────────────────────── tmp/case_of/Test.roc ─
 
     This looks like an operator, but it's not one I recognize!
 
@@ -5760,7 +5991,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNRECOGNIZED NAME ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNRECOGNIZED NAME [R0250] ───────────────────────────── /code/proj/Main.roc ─
 
     Nothing is named `bar` in this scope.
 
@@ -5785,7 +6016,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNKNOWN OPERATOR ──────────────────────────── tmp/invalid_operator/Test.roc ─
+    ── UNKNOWN OPERATOR [R0116] ──────────────────── tmp/invalid_operator/Test.roc ─
 
     This looks like an operator, but it's not one I recognize!
 
@@ -5810,7 +6041,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNKNOWN OPERATOR ───────────────────────────────── tmp/double_plus/Test.roc ─
+    ── UNKNOWN OPERATOR [R0116] ───────────────────────── tmp/double_plus/Test.roc ─
 
     This looks like an operator, but it's not one I recognize!
 
@@ -5837,7 +6068,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNKNOWN OPERATOR ────────────────────────────── tmp/inline_hastype/Test.roc ─
+    ── UNKNOWN OPERATOR [R0116] ────────────────────── tmp/inline_hastype/Test.roc ─
 
     This looks like an operator, but it's not one I recognize!
 
@@ -5868,7 +6099,7 @@ All branches in an `if` must have the same type!
         |golden| pretty_assertions::assert_eq!(
             golden,
             &format!(
-                r###"── UNKNOWN OPERATOR ───────────────────────────── tmp/wild_case_arrow/Test.roc ─
+                r###"── UNKNOWN OPERATOR [R0116] ───────────────────── tmp/wild_case_arrow/Test.roc ─
 
 This looks like an operator, but it's not one I recognize!
 
@@ -5901,7 +6132,7 @@ In roc, functions are always written as a lambda, like{}
             ),
             indoc!(
                 r#"
-                ── WEIRD PROVIDES ──────────────────────────────────────── /code/proj/Main.roc ─
+                ── WEIRD PROVIDES [R0113] ──────────────────────────────── /code/proj/Main.roc ─
 
                 I am partway through parsing a provides list, but I got stuck here:
 
@@ -5938,7 +6169,7 @@ In roc, functions are always written as a lambda, like{}
             ),
             indoc!(
                 r#"
-                ── BAD REQUIRES ────────────────────────────────────────── /code/proj/Main.roc ─
+                ── BAD REQUIRES [R0113] ────────────────────────────────── /code/proj/Main.roc ─
 
                 I am partway through parsing a header, but I got stuck here:
 
@@ -5966,7 +6197,7 @@ In roc, functions are always written as a lambda, like{}
             ),
             indoc!(
                 r#"
-                ── WEIRD IMPORTS ───────────────────────────────────────── /code/proj/Main.roc ─
+                ── WEIRD IMPORTS [R0113] ───────────────────────────────── /code/proj/Main.roc ─
 
                 I am partway through parsing a header, but I got stuck here:
 
@@ -5993,7 +6224,7 @@ In roc, functions are always written as a lambda, like{}
             ),
             indoc!(
                 r#"
-                ── WEIRD EXPOSES ───────────────────────────────────────── /code/proj/Main.roc ─
+                ── WEIRD EXPOSES [R0113] ───────────────────────────────── /code/proj/Main.roc ─
 
                 I am partway through parsing an `exposes` list, but I got stuck here:
 
@@ -6021,7 +6252,7 @@ In roc, functions are always written as a lambda, like{}
             ),
             indoc!(
                 r#"
-                ── WEIRD MODULE NAME ───────────────────────────────────── /code/proj/Main.roc ─
+                ── WEIRD MODULE NAME [R0113] ───────────────────────────── /code/proj/Main.roc ─
 
                 I am partway through parsing a header, but got stuck here:
 
@@ -6047,7 +6278,7 @@ In roc, functions are always written as a lambda, like{}
             ),
             indoc!(
                 r#"
-                ── WEIRD APP NAME ──────────────────────────────────────── /code/proj/Main.roc ─
+                ── WEIRD APP NAME [R0113] ──────────────────────────────── /code/proj/Main.roc ─
 
                 I am partway through parsing a header, but got stuck here:
 
@@ -6071,7 +6302,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TOO MANY ARGS ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TOO MANY ARGS [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This value is not a function, but it was given 2 arguments:
 
@@ -6092,7 +6323,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TOO MANY ARGS ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TOO MANY ARGS [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This value is not a function, but it was given 2 arguments:
 
@@ -6114,7 +6345,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `x` definition:
 
@@ -6140,7 +6371,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── UNFINISHED PARENTHESES ──────────────── tmp/pattern_in_parens_open/Test.roc ─
+    ── UNFINISHED PARENTHESES [R0111] ──────── tmp/pattern_in_parens_open/Test.roc ─
 
     I am partway through parsing a pattern in parentheses, but I got stuck
     here:
@@ -6163,7 +6394,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── UNFINISHED PARENTHESES ─────────── tmp/pattern_in_parens_end_comma/Test.roc ─
+    ── UNFINISHED PARENTHESES [R0111] ─── tmp/pattern_in_parens_end_comma/Test.roc ─
 
     I am partway through parsing a pattern in parentheses, but I got stuck
     here:
@@ -6186,7 +6417,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── UNFINISHED PARENTHESES ───────────────── tmp/pattern_in_parens_end/Test.roc ─
+    ── UNFINISHED PARENTHESES [R0111] ───────── tmp/pattern_in_parens_end/Test.roc ─
 
     I am partway through parsing a pattern in parentheses, but I got stuck
     here:
@@ -6210,7 +6441,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── UNFINISHED FUNCTION ───── tmp/unfinished_closure_pattern_in_parens/Test.roc ─
+    ── UNFINISHED FUNCTION [R0126] ─ ...inished_closure_pattern_in_parens/Test.roc ─
 
     I was partway through parsing a  function, but I got stuck here:
 
@@ -6230,7 +6461,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── UNFINISHED PARENTHESES ───────── tmp/pattern_in_parens_indent_open/Test.roc ─
+    ── UNFINISHED PARENTHESES [R0111] ─ tmp/pattern_in_parens_indent_open/Test.roc ─
 
     I am partway through parsing a pattern in parentheses, but I got stuck
     here:
@@ -6255,7 +6486,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `map` has an unexpected type:
 
@@ -6283,7 +6514,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This `expect` condition needs to be a Bool:
 
@@ -6310,7 +6541,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `mul` has an unexpected type:
 
@@ -6325,7 +6556,7 @@ In roc, functions are always written as a lambda, like{}
 
         Num *
 
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `mult` definition:
 
@@ -6354,7 +6585,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `mul` has an unexpected type:
 
@@ -6369,7 +6600,7 @@ In roc, functions are always written as a lambda, like{}
 
         Num a
 
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `mult` definition:
 
@@ -6404,7 +6635,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── DUPLICATE NAME ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE NAME [R0210] ──────────────────────────────── /code/proj/Main.roc ─
 
     This alias has the same name as a builtin:
 
@@ -6414,7 +6645,7 @@ In roc, functions are always written as a lambda, like{}
     All builtin aliases are in scope by default, so I need this alias to
     have a different name!
 
-    ── TOO FEW TYPE ARGUMENTS ──────────────────────────────── /code/proj/Main.roc ─
+    ── TOO FEW TYPE ARGUMENTS [R0246] ──────────────────────── /code/proj/Main.roc ─
 
     The `Result` alias expects 2 type arguments, but it got 1 instead:
 
@@ -6442,7 +6673,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── DUPLICATE NAME ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE NAME [R0210] ──────────────────────────────── /code/proj/Main.roc ─
 
     This alias has the same name as a builtin:
 
@@ -6452,7 +6683,7 @@ In roc, functions are always written as a lambda, like{}
     All builtin aliases are in scope by default, so I need this alias to
     have a different name!
 
-    ── TOO MANY TYPE ARGUMENTS ─────────────────────────────── /code/proj/Main.roc ─
+    ── TOO MANY TYPE ARGUMENTS [R0246] ─────────────────────── /code/proj/Main.roc ─
 
     The `Result` alias expects 2 type arguments, but it got 3 instead:
 
@@ -6474,7 +6705,7 @@ In roc, functions are always written as a lambda, like{}
         ),
         // TODO: We should tell the user that we inferred `_` as `a`
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `f` definition:
 
@@ -6508,7 +6739,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `f` definition:
 
@@ -6540,7 +6771,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `f` definition:
 
@@ -6580,7 +6811,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `inner` definition:
 
@@ -6609,7 +6840,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── NOT AN INLINE ALIAS ────────── tmp/error_inline_alias_not_an_alias/Test.roc ─
+    ── NOT AN INLINE ALIAS [R0110] ── tmp/error_inline_alias_not_an_alias/Test.roc ─
 
     The inline type after this `as` is not a type alias:
 
@@ -6629,7 +6860,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── QUALIFIED ALIAS NAME ──────────── tmp/error_inline_alias_qualified/Test.roc ─
+    ── QUALIFIED ALIAS NAME [R0110] ──── tmp/error_inline_alias_qualified/Test.roc ─
 
     This type alias has a qualified name:
 
@@ -6649,7 +6880,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE ARGUMENT NOT LOWERCASE ─ ...r_inline_alias_argument_uppercase/Test.roc ─
+    ── TYPE ARGUMENT NOT LOWERCASE [R0110] ─ ..._alias_argument_uppercase/Test.roc ─
 
     This alias type argument is not lowercase:
 
@@ -6673,7 +6904,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to `isEmpty` has an unexpected type:
 
@@ -6709,7 +6940,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to `c` has an unexpected type:
 
@@ -6742,7 +6973,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── CYCLIC ALIAS ────────────────────────────────────────── /code/proj/Main.roc ─
+    ── CYCLIC ALIAS [R0211] ────────────────────────────────── /code/proj/Main.roc ─
 
     The `F` alias is self-recursive in an invalid way:
 
@@ -6765,7 +6996,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── CYCLIC ALIAS ────────────────────────────────────────── /code/proj/Main.roc ─
+    ── CYCLIC ALIAS [R0211] ────────────────────────────────── /code/proj/Main.roc ─
 
     The `F` alias is self-recursive in an invalid way:
 
@@ -6787,7 +7018,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── CYCLIC ALIAS ────────────────────────────────────────── /code/proj/Main.roc ─
+    ── CYCLIC ALIAS [R0211] ────────────────────────────────── /code/proj/Main.roc ─
 
     The `F` alias is self-recursive in an invalid way:
 
@@ -6812,7 +7043,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0302] ───────────────────────────────── /code/proj/Main.roc ─
 
     The 1st argument to `job` is weird:
 
@@ -6847,7 +7078,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `job` definition:
 
@@ -6878,7 +7109,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── NESTED DATATYPE ─────────────────────────────────────── /code/proj/Main.roc ─
+    ── NESTED DATATYPE [R0222] ─────────────────────────────── /code/proj/Main.roc ─
 
     `Nested` is a nested datatype. Here is one recursive usage of it:
 
@@ -6906,7 +7137,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── NESTED DATATYPE ─────────────────────────────────────── /code/proj/Main.roc ─
+    ── NESTED DATATYPE [R0222] ─────────────────────────────── /code/proj/Main.roc ─
 
     `Nested` is a nested datatype. Here is one recursive usage of it:
 
@@ -6954,7 +7185,7 @@ In roc, functions are always written as a lambda, like{}
 
                     let real = format!(indoc!(
                         r#"
-                        ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+                        ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
                         This 1st argument to `use` has an unexpected type:
 
@@ -7021,7 +7252,7 @@ In roc, functions are always written as a lambda, like{}
 
                     let real = format!(indoc!(
                         r#"
-                        ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+                        ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
                         The branches of this `when` expression don't match the condition:
 
@@ -7073,7 +7304,7 @@ In roc, functions are always written as a lambda, like{}
         ),
         // TODO: link to number suffixes
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0261] ──────────────────────────────── /code/proj/Main.roc ─
 
     This integer literal contains an invalid digit:
 
@@ -7096,7 +7327,7 @@ In roc, functions are always written as a lambda, like{}
         ),
         // TODO: link to number suffixes
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0261] ──────────────────────────────── /code/proj/Main.roc ─
 
     This integer literal contains an invalid digit:
 
@@ -7118,7 +7349,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── CONFLICTING NUMBER SUFFIX ───────────────────────────── /code/proj/Main.roc ─
+    ── CONFLICTING NUMBER SUFFIX [R0261] ───────────────────── /code/proj/Main.roc ─
 
     This number literal is an integer, but it has a float suffix:
 
@@ -7135,7 +7366,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── CONFLICTING NUMBER SUFFIX ───────────────────────────── /code/proj/Main.roc ─
+    ── CONFLICTING NUMBER SUFFIX [R0260] ───────────────────── /code/proj/Main.roc ─
 
     This number literal is a float, but it has an integer suffix:
 
@@ -7148,7 +7379,7 @@ In roc, functions are always written as a lambda, like{}
         u8_overflow,
         "256u8",
         @r###"
-    ── NUMBER OVERFLOWS SUFFIX ─────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER OVERFLOWS SUFFIX [R0261] ─────────────────────── /code/proj/Main.roc ─
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7164,7 +7395,7 @@ In roc, functions are always written as a lambda, like{}
         negative_u8,
         "-1u8",
         @r###"
-    ── NUMBER UNDERFLOWS SUFFIX ────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER UNDERFLOWS SUFFIX [R0261] ────────────────────── /code/proj/Main.roc ─
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7180,7 +7411,7 @@ In roc, functions are always written as a lambda, like{}
         u16_overflow,
         "65536u16",
         @r###"
-    ── NUMBER OVERFLOWS SUFFIX ─────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER OVERFLOWS SUFFIX [R0261] ─────────────────────── /code/proj/Main.roc ─
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7196,7 +7427,7 @@ In roc, functions are always written as a lambda, like{}
         negative_u16,
         "-1u16",
         @r###"
-    ── NUMBER UNDERFLOWS SUFFIX ────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER UNDERFLOWS SUFFIX [R0261] ────────────────────── /code/proj/Main.roc ─
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7212,7 +7443,7 @@ In roc, functions are always written as a lambda, like{}
         u32_overflow,
         "4_294_967_296u32",
         @r###"
-    ── NUMBER OVERFLOWS SUFFIX ─────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER OVERFLOWS SUFFIX [R0261] ─────────────────────── /code/proj/Main.roc ─
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7228,7 +7459,7 @@ In roc, functions are always written as a lambda, like{}
         negative_u32,
         "-1u32",
         @r###"
-    ── NUMBER UNDERFLOWS SUFFIX ────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER UNDERFLOWS SUFFIX [R0261] ────────────────────── /code/proj/Main.roc ─
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7244,7 +7475,7 @@ In roc, functions are always written as a lambda, like{}
         u64_overflow,
         "18_446_744_073_709_551_616u64",
         @r###"
-    ── NUMBER OVERFLOWS SUFFIX ─────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER OVERFLOWS SUFFIX [R0261] ─────────────────────── /code/proj/Main.roc ─
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7260,7 +7491,7 @@ In roc, functions are always written as a lambda, like{}
         negative_u64,
         "-1u64",
         @r###"
-    ── NUMBER UNDERFLOWS SUFFIX ────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER UNDERFLOWS SUFFIX [R0261] ────────────────────── /code/proj/Main.roc ─
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7276,7 +7507,7 @@ In roc, functions are always written as a lambda, like{}
         negative_u128,
         "-1u128",
         @r###"
-    ── NUMBER UNDERFLOWS SUFFIX ────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER UNDERFLOWS SUFFIX [R0261] ────────────────────── /code/proj/Main.roc ─
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7292,7 +7523,7 @@ In roc, functions are always written as a lambda, like{}
         i8_overflow,
         "128i8",
         @r###"
-    ── NUMBER OVERFLOWS SUFFIX ─────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER OVERFLOWS SUFFIX [R0261] ─────────────────────── /code/proj/Main.roc ─
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7308,7 +7539,7 @@ In roc, functions are always written as a lambda, like{}
         i8_underflow,
         "-129i8",
         @r###"
-    ── NUMBER UNDERFLOWS SUFFIX ────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER UNDERFLOWS SUFFIX [R0261] ────────────────────── /code/proj/Main.roc ─
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7324,7 +7555,7 @@ In roc, functions are always written as a lambda, like{}
         i16_overflow,
         "32768i16",
         @r###"
-    ── NUMBER OVERFLOWS SUFFIX ─────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER OVERFLOWS SUFFIX [R0261] ─────────────────────── /code/proj/Main.roc ─
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7340,7 +7571,7 @@ In roc, functions are always written as a lambda, like{}
         i16_underflow,
         "-32769i16",
         @r###"
-    ── NUMBER UNDERFLOWS SUFFIX ────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER UNDERFLOWS SUFFIX [R0261] ────────────────────── /code/proj/Main.roc ─
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7356,7 +7587,7 @@ In roc, functions are always written as a lambda, like{}
         i32_overflow,
         "2_147_483_648i32",
         @r###"
-    ── NUMBER OVERFLOWS SUFFIX ─────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER OVERFLOWS SUFFIX [R0261] ─────────────────────── /code/proj/Main.roc ─
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7372,7 +7603,7 @@ In roc, functions are always written as a lambda, like{}
         i32_underflow,
         "-2_147_483_649i32",
         @r###"
-    ── NUMBER UNDERFLOWS SUFFIX ────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER UNDERFLOWS SUFFIX [R0261] ────────────────────── /code/proj/Main.roc ─
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7388,7 +7619,7 @@ In roc, functions are always written as a lambda, like{}
         i64_overflow,
         "9_223_372_036_854_775_808i64",
         @r###"
-    ── NUMBER OVERFLOWS SUFFIX ─────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER OVERFLOWS SUFFIX [R0261] ─────────────────────── /code/proj/Main.roc ─
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7404,7 +7635,7 @@ In roc, functions are always written as a lambda, like{}
         i64_underflow,
         "-9_223_372_036_854_775_809i64",
         @r###"
-    ── NUMBER UNDERFLOWS SUFFIX ────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER UNDERFLOWS SUFFIX [R0261] ────────────────────── /code/proj/Main.roc ─
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7420,7 +7651,7 @@ In roc, functions are always written as a lambda, like{}
         i128_overflow,
         "170_141_183_460_469_231_731_687_303_715_884_105_728i128",
         @r###"
-    ── NUMBER OVERFLOWS SUFFIX ─────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER OVERFLOWS SUFFIX [R0261] ─────────────────────── /code/proj/Main.roc ─
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7442,7 +7673,7 @@ In roc, functions are always written as a lambda, like{}
         // TODO: this error message could be improved, e.g. something like "This argument can
         // be used as ... because of its literal value"
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `get` has an unexpected type:
 
@@ -7468,7 +7699,7 @@ In roc, functions are always written as a lambda, like{}
              "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `get` has an unexpected type:
 
@@ -7495,7 +7726,7 @@ In roc, functions are always written as a lambda, like{}
              "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `get` has an unexpected type:
 
@@ -7522,7 +7753,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The branches of this `when` expression don't match the condition:
 
@@ -7553,7 +7784,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── CYCLIC ALIAS ────────────────────────────────────────── /code/proj/Main.roc ─
+    ── CYCLIC ALIAS [R0211] ────────────────────────────────── /code/proj/Main.roc ─
 
     The `R` alias is self-recursive in an invalid way:
 
@@ -7576,7 +7807,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── CYCLIC ALIAS ────────────────────────────────────────── /code/proj/Main.roc ─
+    ── CYCLIC ALIAS [R0211] ────────────────────────────────── /code/proj/Main.roc ─
 
     The `R` alias is self-recursive in an invalid way:
 
@@ -7600,7 +7831,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── CYCLIC ALIAS ────────────────────────────────────────── /code/proj/Main.roc ─
+    ── CYCLIC ALIAS [R0211] ────────────────────────────────── /code/proj/Main.roc ─
 
     The `Foo` alias is recursive in an invalid way:
 
@@ -7635,7 +7866,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── DUPLICATE NAME ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE NAME [R0210] ──────────────────────────────── /code/proj/Main.roc ─
 
     This alias has the same name as a builtin:
 
@@ -7655,7 +7886,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── OPAQUE TYPE NOT DEFINED ─────────────────────────────── /code/proj/Main.roc ─
+    ── OPAQUE TYPE NOT DEFINED [R0251] ─────────────────────── /code/proj/Main.roc ─
 
     The opaque type Age referenced here is not defined:
 
@@ -7676,7 +7907,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── OPAQUE TYPE NOT DEFINED ─────────────────────────────── /code/proj/Main.roc ─
+    ── OPAQUE TYPE NOT DEFINED [R0251] ─────────────────────── /code/proj/Main.roc ─
 
     The opaque type Age referenced here is not defined:
 
@@ -7690,7 +7921,7 @@ In roc, functions are always written as a lambda, like{}
 
     Note: It looks like there are no opaque types declared in this scope yet!
 
-    ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED DEFINITION [R0201] ───────────────────────────── /code/proj/Main.roc ─
 
     `Age` is not used anywhere in your code.
 
@@ -7713,7 +7944,7 @@ In roc, functions are always written as a lambda, like{}
         // and checking it during can. The reason the error appears is because it is parsed as
         // Apply(Error(OtherModule), [@Age, 21])
         @r###"
-    ── OPAQUE TYPE NOT DEFINED ─────────────────────────────── /code/proj/Main.roc ─
+    ── OPAQUE TYPE NOT DEFINED [R0251] ─────────────────────── /code/proj/Main.roc ─
 
     The opaque type Age referenced here is not defined:
 
@@ -7722,7 +7953,7 @@ In roc, functions are always written as a lambda, like{}
 
     Note: It looks like there are no opaque types declared in this scope yet!
 
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [R0258] ──────────────────────────────── /code/proj/Main.roc ─
 
     I am trying to parse a qualified name here:
 
@@ -7749,7 +7980,7 @@ In roc, functions are always written as a lambda, like{}
         // `@Age` can be linked to the declaration of `Age` inside `age`, and a suggestion to
         // raise that declaration to the outer scope.
         @r###"
-    ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED DEFINITION [R0201] ───────────────────────────── /code/proj/Main.roc ─
 
     `Age` is not used anywhere in your code.
 
@@ -7759,7 +7990,7 @@ In roc, functions are always written as a lambda, like{}
     If you didn't intend on using `Age` then remove it so future readers of
     your code don't wonder why it is there.
 
-    ── OPAQUE TYPE NOT DEFINED ─────────────────────────────── /code/proj/Main.roc ─
+    ── OPAQUE TYPE NOT DEFINED [R0251] ─────────────────────── /code/proj/Main.roc ─
 
     The opaque type Age referenced here is not defined:
 
@@ -7780,7 +8011,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── MODULE NOT IMPORTED ─────────────────────────────────── /code/proj/Main.roc ─
+    ── MODULE NOT IMPORTED [R0256] ─────────────────────────── /code/proj/Main.roc ─
 
     The `Task` module is not imported:
 
@@ -7812,7 +8043,7 @@ In roc, functions are always written as a lambda, like{}
         // TODO(opaques): error could be improved by saying that the opaque definition demands
         // that the argument be a U8, and linking to the definitin!
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression is used in an unexpected way:
 
@@ -7841,7 +8072,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression is used in an unexpected way:
 
@@ -7871,7 +8102,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `v` definition:
 
@@ -7909,7 +8140,7 @@ In roc, functions are always written as a lambda, like{}
         // TODO(opaques): error could be improved by saying that the user-provided pattern
         // probably wants to change "Age" to "@Age"!
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0302] ───────────────────────────────── /code/proj/Main.roc ─
 
     The 1st argument to `f` is weird:
 
@@ -7944,7 +8175,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0302] ───────────────────────────────── /code/proj/Main.roc ─
 
     The 2nd pattern in this `when` does not match the previous ones:
 
@@ -7975,7 +8206,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The branches of this `when` expression don't match the condition:
 
@@ -8013,7 +8244,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -8046,7 +8277,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to `y` has an unexpected type:
 
@@ -8079,7 +8310,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -8103,7 +8334,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── INVALID_EXTENSION_TYPE ──────────────────────────────── /code/proj/Main.roc ─
+    ── INVALID_EXTENSION_TYPE [R0223] ──────────────────────── /code/proj/Main.roc ─
 
     This record extension type is invalid:
 
@@ -8124,7 +8355,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── INVALID_EXTENSION_TYPE ──────────────────────────────── /code/proj/Main.roc ─
+    ── INVALID_EXTENSION_TYPE [R0223] ──────────────────────── /code/proj/Main.roc ─
 
     This tag union extension type is invalid:
 
@@ -8151,7 +8382,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── UNRECOGNIZED NAME ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNRECOGNIZED NAME [R0250] ───────────────────────────── /code/proj/Main.roc ─
 
     Nothing is named `UnknownType` in this scope.
 
@@ -8165,7 +8396,7 @@ In roc, functions are always written as a lambda, like{}
         Unsigned32
         Unsigned16
 
-    ── UNRECOGNIZED NAME ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNRECOGNIZED NAME [R0250] ───────────────────────────── /code/proj/Main.roc ─
 
     Nothing is named `UnknownType` in this scope.
 
@@ -8192,7 +8423,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── UNFINISHED ABILITY ── tmp/ability_first_demand_not_indented_enough/Test.roc ─
+    ── UNFINISHED ABILITY [R0122] ─ ...y_first_demand_not_indented_enough/Test.roc ─
 
     I was partway through parsing an ability definition, but I got stuck
     here:
@@ -8217,7 +8448,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-        ── UNFINISHED ABILITY ─── tmp/ability_demands_not_indented_with_first/Test.roc ─
+        ── UNFINISHED ABILITY [R0122] ─ ...ty_demands_not_indented_with_first/Test.roc ─
 
         I was partway through parsing an ability definition, but I got stuck
         here:
@@ -8240,7 +8471,7 @@ In roc, functions are always written as a lambda, like{}
                 "#
         ),
         @r#"
-        ── UNFINISHED ABILITY ───────────── tmp/ability_demand_value_has_args/Test.roc ─
+        ── UNFINISHED ABILITY [R0122] ───── tmp/ability_demand_value_has_args/Test.roc ─
 
         I was partway through parsing an ability definition, but I got stuck
         here:
@@ -8264,7 +8495,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── UNFINISHED ABILITY ────────── tmp/ability_non_signature_expression/Test.roc ─
+    ── UNFINISHED ABILITY [R0122] ── tmp/ability_non_signature_expression/Test.roc ─
 
     I was partway through parsing an ability definition, but I got stuck
     here:
@@ -8287,7 +8518,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── UNBOUND TYPE VARIABLE ───────────────────────────────── /code/proj/Main.roc ─
+    ── UNBOUND TYPE VARIABLE [R0214] ───────────────────────── /code/proj/Main.roc ─
 
     The definition of `I` has an unbound type variable:
 
@@ -8309,7 +8540,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── UNBOUND TYPE VARIABLE ───────────────────────────────── /code/proj/Main.roc ─
+    ── UNBOUND TYPE VARIABLE [R0214] ───────────────────────── /code/proj/Main.roc ─
 
     The definition of `I` has an unbound type variable:
 
@@ -8331,7 +8562,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── UNBOUND TYPE VARIABLE ───────────────────────────────── /code/proj/Main.roc ─
+    ── UNBOUND TYPE VARIABLE [R0214] ───────────────────────── /code/proj/Main.roc ─
 
     The definition of `I` has 2 unbound type variables.
 
@@ -8355,7 +8586,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── UNBOUND TYPE VARIABLE ───────────────────────────────── /code/proj/Main.roc ─
+    ── UNBOUND TYPE VARIABLE [R0214] ───────────────────────── /code/proj/Main.roc ─
 
     The definition of `I` has an unbound type variable:
 
@@ -8377,7 +8608,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── UNBOUND TYPE VARIABLE ───────────────────────────────── /code/proj/Main.roc ─
+    ── UNBOUND TYPE VARIABLE [R0214] ───────────────────────── /code/proj/Main.roc ─
 
     The definition of `I` has an unbound type variable:
 
@@ -8400,7 +8631,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── ABILITY HAS TYPE VARIABLES ──────────────────────────── /code/proj/Main.roc ─
+    ── ABILITY HAS TYPE VARIABLES [R0224] ──────────────────── /code/proj/Main.roc ─
 
     The definition of the `MHash` ability includes type variables:
 
@@ -8410,7 +8641,7 @@ In roc, functions are always written as a lambda, like{}
     Abilities cannot depend on type variables, but their member values
     can!
 
-    ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED DEFINITION [R0201] ───────────────────────────── /code/proj/Main.roc ─
 
     `MHash` is not used anywhere in your code.
 
@@ -8432,7 +8663,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── HAS CLAUSE IS NOT AN ABILITY ────────────────────────── /code/proj/Main.roc ─
+    ── HAS CLAUSE IS NOT AN ABILITY [R0225] ────────────────── /code/proj/Main.roc ─
 
     The type referenced in this "has" clause is not an ability:
 
@@ -8451,7 +8682,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-        ── DUPLICATE NAME ──────────────────────────────────────── /code/proj/Main.roc ─
+        ── DUPLICATE NAME [R0210] ──────────────────────────────── /code/proj/Main.roc ─
 
         The `a` name is first defined here:
 
@@ -8480,7 +8711,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-        ── DUPLICATE NAME ──────────────────────────────────────── /code/proj/Main.roc ─
+        ── DUPLICATE NAME [R0210] ──────────────────────────────── /code/proj/Main.roc ─
 
         The `Ability` name is first defined here:
 
@@ -8507,7 +8738,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-        ── ABILITY MEMBER MISSING HAS CLAUSE ───────────────────── /code/proj/Main.roc ─
+        ── ABILITY MEMBER MISSING HAS CLAUSE [R0228] ───────────── /code/proj/Main.roc ─
 
         The definition of the ability member `ab` does not include a `has` clause
         binding a type variable to the ability `Ability`:
@@ -8522,7 +8753,7 @@ In roc, functions are always written as a lambda, like{}
 
         Otherwise, the function does not need to be part of the ability!
 
-        ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+        ── UNUSED DEFINITION [R0201] ───────────────────────────── /code/proj/Main.roc ─
 
         `Ability` is not used anywhere in your code.
 
@@ -8544,7 +8775,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-        ── ABILITY MEMBER BINDS MULTIPLE VARIABLES ─────────────── /code/proj/Main.roc ─
+        ── ABILITY MEMBER BINDS MULTIPLE VARIABLES [R0229] ─────── /code/proj/Main.roc ─
 
         The definition of the ability member `eq` includes multiple variables
         bound to the `MEq`` ability:`
@@ -8572,7 +8803,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── ILLEGAL HAS CLAUSE ──────────────────────────────────── /code/proj/Main.roc ─
+    ── ILLEGAL HAS CLAUSE [R0226] ──────────────────────────── /code/proj/Main.roc ─
 
     A `has` clause is not allowed here:
 
@@ -8581,7 +8812,7 @@ In roc, functions are always written as a lambda, like{}
 
     `has` clauses can only be specified on the top-level type annotations.
 
-    ── ABILITY MEMBER MISSING HAS CLAUSE ───────────────────── /code/proj/Main.roc ─
+    ── ABILITY MEMBER MISSING HAS CLAUSE [R0228] ───────────── /code/proj/Main.roc ─
 
     The definition of the ability member `hash` does not include a `has`
     clause binding a type variable to the ability `MHash`:
@@ -8612,7 +8843,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-        ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+        ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
         Something is off with this specialization of `hash`:
 
@@ -8645,7 +8876,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0240] ───────────── /code/proj/Main.roc ─
 
     This type does not fully implement the `MEq` ability:
 
@@ -8671,7 +8902,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED DEFINITION [R0201] ───────────────────────────── /code/proj/Main.roc ─
 
     `hash` is not used anywhere in your code.
 
@@ -8700,7 +8931,7 @@ In roc, functions are always written as a lambda, like{}
         ),
         // TODO: the error message here could be seriously improved!
         @r###"
-    ── OVERLOADED SPECIALIZATION ───────────────────────────── /code/proj/Main.roc ─
+    ── OVERLOADED SPECIALIZATION [R0243] ───────────────────── /code/proj/Main.roc ─
 
     This ability member specialization is already claimed to specialize
     another opaque type:
@@ -8713,7 +8944,7 @@ In roc, functions are always written as a lambda, like{}
     Ability specializations can only provide implementations for one
     opaque type, since all opaque types are different!
 
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This specialization of `hash` is overly general:
 
@@ -8753,7 +8984,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── OVERLOADED SPECIALIZATION ───────────────────────────── /code/proj/Main.roc ─
+    ── OVERLOADED SPECIALIZATION [R0243] ───────────────────── /code/proj/Main.roc ─
 
     This ability member specialization is already claimed to specialize
     another opaque type:
@@ -8784,7 +9015,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with this specialization of `eq`:
 
@@ -8819,7 +9050,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `hash` definition:
 
@@ -8860,7 +9091,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0304] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -8873,7 +9104,7 @@ In roc, functions are always written as a lambda, like{}
 
     Only builtin abilities can have generated implementations!
 
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0304] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -8898,7 +9129,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-        ── ABILITY NOT ON TOP-LEVEL ────────────────────────────── /code/proj/Main.roc ─
+        ── ABILITY NOT ON TOP-LEVEL [R0230] ────────────────────── /code/proj/Main.roc ─
 
         This ability definition is not on the top-level of a module:
 
@@ -8926,7 +9157,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `hashable` definition:
 
@@ -8972,7 +9203,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── ABILITY USED AS TYPE ────────────────────────────────── /code/proj/Main.roc ─
+    ── ABILITY USED AS TYPE [R0231] ────────────────────────── /code/proj/Main.roc ─
 
     You are attempting to use the ability `MHash` as a type directly:
 
@@ -8986,7 +9217,7 @@ In roc, functions are always written as a lambda, like{}
 
         a has MHash
 
-    ── ABILITY USED AS TYPE ────────────────────────────────── /code/proj/Main.roc ─
+    ── ABILITY USED AS TYPE [R0231] ────────────────────────── /code/proj/Main.roc ─
 
     You are attempting to use the ability `MHash` as a type directly:
 
@@ -9016,7 +9247,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The branches of this `when` expression don't match the condition:
 
@@ -9069,7 +9300,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-        ── WEIRD IMPORTS ────────────────────────── tmp/imports_missing_comma/Test.roc ─
+        ── WEIRD IMPORTS [R0113] ────────────────── tmp/imports_missing_comma/Test.roc ─
 
         I am partway through parsing a imports list, but I got stuck here:
 
@@ -9094,7 +9325,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-        ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+        ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
         This `when` does not cover all the possibilities:
 
@@ -9141,7 +9372,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-        ── SPECIALIZATION NOT ON TOP-LEVEL ─────────────────────── /code/proj/Main.roc ─
+        ── SPECIALIZATION NOT ON TOP-LEVEL [R0230] ─────────────── /code/proj/Main.roc ─
 
         This specialization of the `default` ability member is in a nested
         scope:
@@ -9166,7 +9397,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `isEq` has an unexpected type:
 
@@ -9202,7 +9433,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to `remove` has an unexpected type:
 
@@ -9223,7 +9454,7 @@ In roc, functions are always written as a lambda, like{}
     change the type annotation to be more specific? Maybe change the code
     to be more general?
 
-    ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── CIRCULAR TYPE [R0303] ───────────────────────────────── /code/proj/Main.roc ─
 
     I'm inferring a weird self-referential type for `new`:
 
@@ -9236,7 +9467,7 @@ In roc, functions are always written as a lambda, like{}
 
         { set : Set ∞ }
 
-    ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── CIRCULAR TYPE [R0303] ───────────────────────────────── /code/proj/Main.roc ─
 
     I'm inferring a weird self-referential type for `goal`:
 
@@ -9266,7 +9497,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-        ── CIRCULAR DEFINITION ─────────────────────────────────── /code/proj/Main.roc ─
+        ── CIRCULAR DEFINITION [R0212] ─────────────────────────── /code/proj/Main.roc ─
 
         The `t1` definition is causing a very tricky infinite loop:
 
@@ -9294,7 +9525,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0304] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -9322,7 +9553,7 @@ In roc, functions are always written as a lambda, like{}
         // TODO: this error message is quite unfortunate. We should remove the duplication, and
         // also support regions that point to things in other modules. See also https://github.com/roc-lang/roc/issues/3056.
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0304] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -9359,7 +9590,7 @@ In roc, functions are always written as a lambda, like{}
                 "#
         ),
         @r#"
-            ── CIRCULAR DEFINITION ─────────────────────────────────── /code/proj/Main.roc ─
+            ── CIRCULAR DEFINITION [R0212] ─────────────────────────── /code/proj/Main.roc ─
 
             The `t1` definition is causing a very tricky infinite loop:
 
@@ -9389,7 +9620,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── IMPLEMENTATION NOT FOUND ────────────────────────────── /code/proj/Main.roc ─
+    ── IMPLEMENTATION NOT FOUND [R0233] ────────────────────── /code/proj/Main.roc ─
 
     An implementation of `eq` could not be found in this scope:
 
@@ -9400,7 +9631,7 @@ In roc, functions are always written as a lambda, like{}
     another variable that implements this ability member, like
     { eq: myeq }
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0240] ───────────── /code/proj/Main.roc ─
 
     This type does not fully implement the `MEq` ability:
 
@@ -9427,7 +9658,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── UNRECOGNIZED NAME ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNRECOGNIZED NAME [R0250] ───────────────────────────── /code/proj/Main.roc ─
 
     Nothing is named `aMEq` in this scope.
 
@@ -9441,7 +9672,7 @@ In roc, functions are always written as a lambda, like{}
         myMEq
         eq
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0240] ───────────── /code/proj/Main.roc ─
 
     This type does not fully implement the `MEq` ability:
 
@@ -9468,7 +9699,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── OPTIONAL ABILITY IMPLEMENTATION ─────────────────────── /code/proj/Main.roc ─
+    ── OPTIONAL ABILITY IMPLEMENTATION [R0235] ─────────────── /code/proj/Main.roc ─
 
     Ability implementations cannot be optional:
 
@@ -9479,7 +9710,7 @@ In roc, functions are always written as a lambda, like{}
 
 
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0240] ───────────── /code/proj/Main.roc ─
 
     This type does not fully implement the `MEq` ability:
 
@@ -9506,7 +9737,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── OPTIONAL ABILITY IMPLEMENTATION ─────────────────────── /code/proj/Main.roc ─
+    ── OPTIONAL ABILITY IMPLEMENTATION [R0235] ─────────────── /code/proj/Main.roc ─
 
     Ability implementations cannot be optional:
 
@@ -9519,7 +9750,7 @@ In roc, functions are always written as a lambda, like{}
     record of implementations. For example,    has [Encoding] will attempt
     to derive `Encoding`
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0240] ───────────── /code/proj/Main.roc ─
 
     This type does not fully implement the `Encoding` ability:
 
@@ -9544,7 +9775,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── QUALIFIED ABILITY IMPLEMENTATION ────────────────────── /code/proj/Main.roc ─
+    ── QUALIFIED ABILITY IMPLEMENTATION [R0236] ────────────── /code/proj/Main.roc ─
 
     This ability implementation is qualified:
 
@@ -9554,7 +9785,7 @@ In roc, functions are always written as a lambda, like{}
     Custom implementations must be defined in the local scope, and
     unqualified.
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0240] ───────────── /code/proj/Main.roc ─
 
     This type does not fully implement the `MEq` ability:
 
@@ -9579,7 +9810,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── ABILITY IMPLEMENTATION NOT IDENTIFIER ───────────────── /code/proj/Main.roc ─
+    ── ABILITY IMPLEMENTATION NOT IDENTIFIER [R0237] ───────── /code/proj/Main.roc ─
 
     This ability implementation is not an identifier:
 
@@ -9591,7 +9822,7 @@ In roc, functions are always written as a lambda, like{}
 
     Tip: consider defining this expression as a variable.
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0240] ───────────── /code/proj/Main.roc ─
 
     This type does not fully implement the `MEq` ability:
 
@@ -9618,7 +9849,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── DUPLICATE IMPLEMENTATION ────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE IMPLEMENTATION [R0238] ────────────────────── /code/proj/Main.roc ─
 
     This ability member implementation is duplicate:
 
@@ -9646,7 +9877,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── NOT AN ABILITY ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── NOT AN ABILITY [R0225] ──────────────────────────────── /code/proj/Main.roc ─
 
     This identifier is not an ability in scope:
 
@@ -9669,7 +9900,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── ILLEGAL DERIVE ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── ILLEGAL DERIVE [R0232] ──────────────────────────────── /code/proj/Main.roc ─
 
     This ability cannot be derived:
 
@@ -9692,7 +9923,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0304] ───────────── /code/proj/Main.roc ─
 
     I can't derive an implementation of the `Encoding` ability for `A`:
 
@@ -9717,7 +9948,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0304] ───────────── /code/proj/Main.roc ─
 
     I can't derive an implementation of the `Encoding` ability for `A`:
 
@@ -9769,7 +10000,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── DUPLICATE NAME ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE NAME [R0210] ──────────────────────────────── /code/proj/Main.roc ─
 
     The `main` name is first defined here:
 
@@ -9784,7 +10015,7 @@ In roc, functions are always written as a lambda, like{}
     Since these variables have the same name, it's easy to use the wrong
     one by accident. Give one of them a new name.
 
-    ── UNNECESSARY DEFINITION ──────────────────────────────── /code/proj/Main.roc ─
+    ── UNNECESSARY DEFINITION [R0242] ──────────────────────── /code/proj/Main.roc ─
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -9818,7 +10049,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-        ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+        ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
         Something is off with the body of the `withOpen` definition:
 
@@ -9853,7 +10084,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression is used in an unexpected way:
 
@@ -9900,7 +10131,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to `foo` has an unexpected type:
 
@@ -9945,7 +10176,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `isEq` has an unexpected type:
 
@@ -9970,7 +10201,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `isEq` has an unexpected type:
 
@@ -10007,7 +10238,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the 2nd branch of this `when` expression:
 
@@ -10044,7 +10275,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `map` has an unexpected type:
 
@@ -10070,7 +10301,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-        ── NAME NOT BOUND IN ALL PATTERNS ──────────────────────── /code/proj/Main.roc ─
+        ── NAME NOT BOUND IN ALL PATTERNS [R0241] ──────────────── /code/proj/Main.roc ─
 
         `x` is not bound in all patterns of this `when` branch
 
@@ -10081,7 +10312,7 @@ In roc, functions are always written as a lambda, like{}
         of the branch. Otherwise, the program would crash when it tries to use
         an identifier that wasn't bound!
 
-        ── NAME NOT BOUND IN ALL PATTERNS ──────────────────────── /code/proj/Main.roc ─
+        ── NAME NOT BOUND IN ALL PATTERNS [R0241] ──────────────── /code/proj/Main.roc ─
 
         `y` is not bound in all patterns of this `when` branch
 
@@ -10092,7 +10323,7 @@ In roc, functions are always written as a lambda, like{}
         of the branch. Otherwise, the program would crash when it tries to use
         an identifier that wasn't bound!
 
-        ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+        ── UNUSED DEFINITION [R0206] ───────────────────────────── /code/proj/Main.roc ─
 
         `y` is not used in this `when` branch.
 
@@ -10114,7 +10345,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -10142,7 +10373,7 @@ In roc, functions are always written as a lambda, like{}
         |golden| pretty_assertions::assert_eq!(
             golden,
             &format!(
-                r###"── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+                r###"── SYNTAX PROBLEM [R0258] ──────────────────────────────── /code/proj/Main.roc ─
 
 Underscores are not allowed in identifier names:
 
@@ -10172,7 +10403,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
         @r###"
-    ── UNNECESSARY DEFINITION ──────────────────────────────── /code/proj/Main.roc ─
+    ── UNNECESSARY DEFINITION [R0242] ──────────────────────── /code/proj/Main.roc ─
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10184,7 +10415,7 @@ I recommend using camelCase. It's the standard style in Roc code!
     functional, assignments that don't introduce variables cannot affect a
     program's behavior!
 
-    ── UNNECESSARY DEFINITION ──────────────────────────────── /code/proj/Main.roc ─
+    ── UNNECESSARY DEFINITION [R0242] ──────────────────────── /code/proj/Main.roc ─
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10196,7 +10427,7 @@ I recommend using camelCase. It's the standard style in Roc code!
     functional, assignments that don't introduce variables cannot affect a
     program's behavior!
 
-    ── UNNECESSARY DEFINITION ──────────────────────────────── /code/proj/Main.roc ─
+    ── UNNECESSARY DEFINITION [R0242] ──────────────────────── /code/proj/Main.roc ─
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10208,7 +10439,7 @@ I recommend using camelCase. It's the standard style in Roc code!
     functional, assignments that don't introduce variables cannot affect a
     program's behavior!
 
-    ── UNNECESSARY DEFINITION ──────────────────────────────── /code/proj/Main.roc ─
+    ── UNNECESSARY DEFINITION [R0242] ──────────────────────── /code/proj/Main.roc ─
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10238,7 +10469,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
         @r###"
-    ── UNNECESSARY DEFINITION ──────────────────────────────── /code/proj/Main.roc ─
+    ── UNNECESSARY DEFINITION [R0242] ──────────────────────── /code/proj/Main.roc ─
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10250,7 +10481,7 @@ I recommend using camelCase. It's the standard style in Roc code!
     functional, assignments that don't introduce variables cannot affect a
     program's behavior!
 
-    ── UNNECESSARY DEFINITION ──────────────────────────────── /code/proj/Main.roc ─
+    ── UNNECESSARY DEFINITION [R0242] ──────────────────────── /code/proj/Main.roc ─
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10262,7 +10493,7 @@ I recommend using camelCase. It's the standard style in Roc code!
     functional, assignments that don't introduce variables cannot affect a
     program's behavior!
 
-    ── UNNECESSARY DEFINITION ──────────────────────────────── /code/proj/Main.roc ─
+    ── UNNECESSARY DEFINITION [R0242] ──────────────────────── /code/proj/Main.roc ─
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10274,7 +10505,7 @@ I recommend using camelCase. It's the standard style in Roc code!
     functional, assignments that don't introduce variables cannot affect a
     program's behavior!
 
-    ── UNNECESSARY DEFINITION ──────────────────────────────── /code/proj/Main.roc ─
+    ── UNNECESSARY DEFINITION [R0242] ──────────────────────── /code/proj/Main.roc ─
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10302,7 +10533,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
         @r###"
-    ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED DEFINITION [R0201] ───────────────────────────── /code/proj/Main.roc ─
 
     `hash` is not used anywhere in your code.
 
@@ -10329,7 +10560,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
         @r###"
-    ── WRONG SPECIALIZATION TYPE ───────────────────────────── /code/proj/Main.roc ─
+    ── WRONG SPECIALIZATION TYPE [R0309] ───────────────────── /code/proj/Main.roc ─
 
     This specialization of `hash` is not for the expected type:
 
@@ -10352,7 +10583,7 @@ I recommend using camelCase. It's the standard style in Roc code!
                 "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `x` definition:
 
@@ -10382,7 +10613,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
         @r###"
-    ── CYCLIC ALIAS ────────────────────────────────────────── /code/proj/Main.roc ─
+    ── CYCLIC ALIAS [R0211] ────────────────────────────────── /code/proj/Main.roc ─
 
     The `Recursive` opaque is self-recursive in an invalid way:
 
@@ -10404,7 +10635,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
         @r###"
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0304] ───────────── /code/proj/Main.roc ─
 
     I can't derive an implementation of the `Decoding` ability for `A`:
 
@@ -10429,7 +10660,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
         @r###"
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0304] ───────────── /code/proj/Main.roc ─
 
     I can't derive an implementation of the `Decoding` ability for `A`:
 
@@ -10483,7 +10714,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0304] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -10514,7 +10745,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0304] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -10544,7 +10775,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── UNUSED ARGUMENT ─────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED ARGUMENT [R0205] ─────────────────────────────── /code/proj/Main.roc ─
 
     This function doesn't use `x`.
 
@@ -10576,7 +10807,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The branches of this `when` expression don't match the condition:
 
@@ -10609,7 +10840,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `f` definition:
 
@@ -10646,7 +10877,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the `then` branch of this `if` expression:
 
@@ -10678,7 +10909,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED DEFINITION [R0206] ───────────────────────────── /code/proj/Main.roc ─
 
     `foo` is not used in this `when` branch.
 
@@ -10704,7 +10935,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0304] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -10733,7 +10964,7 @@ I recommend using camelCase. It's the standard style in Roc code!
              "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0304] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -10799,7 +11030,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -10827,7 +11058,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -10854,7 +11085,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── UNMATCHABLE PATTERN ─────────────────────────────────── /code/proj/Main.roc ─
+    ── UNMATCHABLE PATTERN [R0307] ─────────────────────────── /code/proj/Main.roc ─
 
     The 2nd pattern will never be matched:
 
@@ -10881,7 +11112,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── UNMATCHABLE PATTERN ─────────────────────────────────── /code/proj/Main.roc ─
+    ── UNMATCHABLE PATTERN [R0307] ─────────────────────────── /code/proj/Main.roc ─
 
     The 2nd pattern will never be matched:
 
@@ -10893,7 +11124,7 @@ I recommend using camelCase. It's the standard style in Roc code!
     It's impossible to create a value of this shape, so this pattern can
     be safely removed!
 
-    ── UNMATCHABLE PATTERN ─────────────────────────────────── /code/proj/Main.roc ─
+    ── UNMATCHABLE PATTERN [R0307] ─────────────────────────── /code/proj/Main.roc ─
 
     The 3rd pattern will never be matched:
 
@@ -10918,7 +11149,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── DUPLICATE NAME ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE NAME [R0210] ──────────────────────────────── /code/proj/Main.roc ─
 
     This opaque type has the same name as a builtin:
 
@@ -10940,7 +11171,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── UNUSED IMPORT ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED IMPORT [R0202] ───────────────────────────────── /code/proj/Main.roc ─
 
     `List.concat` is not used in this module.
 
@@ -10992,7 +11223,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── CIRCULAR DEFINITION ─────────────────────────────────── /code/proj/Main.roc ─
+    ── CIRCULAR DEFINITION [R0212] ─────────────────────────── /code/proj/Main.roc ─
 
     `main` is defined directly in terms of itself:
 
@@ -11014,7 +11245,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This `if` condition needs to be a Bool:
 
@@ -11040,7 +11271,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This `if` condition needs to be a Bool:
 
@@ -11068,7 +11299,7 @@ I recommend using camelCase. It's the standard style in Roc code!
              "#
         ),
         @r###"
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0304] ───────────── /code/proj/Main.roc ─
 
     I can't derive an implementation of the `Hash` ability for `A`:
 
@@ -11093,7 +11324,7 @@ I recommend using camelCase. It's the standard style in Roc code!
              "#
         ),
         @r###"
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0304] ───────────── /code/proj/Main.roc ─
 
     I can't derive an implementation of the `Hash` ability for `A`:
 
@@ -11175,7 +11406,7 @@ I recommend using camelCase. It's the standard style in Roc code!
              "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0304] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -11202,7 +11433,7 @@ I recommend using camelCase. It's the standard style in Roc code!
              "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0304] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -11248,7 +11479,7 @@ I recommend using camelCase. It's the standard style in Roc code!
              "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0304] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -11284,7 +11515,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `shiftRightZfBy` has an unexpected type:
 
@@ -11299,7 +11530,7 @@ I recommend using camelCase. It's the standard style in Roc code!
 
         U8
 
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `shiftRightBy` has an unexpected type:
 
@@ -11314,7 +11545,7 @@ I recommend using camelCase. It's the standard style in Roc code!
 
         U8
 
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `shiftLeftBy` has an unexpected type:
 
@@ -11342,7 +11573,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `contains` has an unexpected type:
 
@@ -11369,7 +11600,7 @@ I recommend using camelCase. It's the standard style in Roc code!
              "#
         ),
         @r###"
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0304] ───────────── /code/proj/Main.roc ─
 
     I can't derive an implementation of the `Eq` ability for `A`:
 
@@ -11394,7 +11625,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The branches of this `when` expression don't match the condition:
 
@@ -11424,7 +11655,7 @@ I recommend using camelCase. It's the standard style in Roc code!
              "#
         ),
         @r###"
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0304] ───────────── /code/proj/Main.roc ─
 
     I can't derive an implementation of the `Eq` ability for `A`:
 
@@ -11450,7 +11681,7 @@ I recommend using camelCase. It's the standard style in Roc code!
              "#
         ),
         @r###"
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0304] ───────────── /code/proj/Main.roc ─
 
     I can't derive an implementation of the `Eq` ability for `A`:
 
@@ -11478,7 +11709,7 @@ I recommend using camelCase. It's the standard style in Roc code!
              "#
         ),
         @r###"
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0304] ───────────── /code/proj/Main.roc ─
 
     I can't derive an implementation of the `Eq` ability for `A`:
 
@@ -11560,7 +11791,7 @@ I recommend using camelCase. It's the standard style in Roc code!
              "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0304] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -11587,7 +11818,7 @@ I recommend using camelCase. It's the standard style in Roc code!
              "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0304] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -11616,7 +11847,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0304] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -11639,7 +11870,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0304] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -11679,7 +11910,7 @@ I recommend using camelCase. It's the standard style in Roc code!
              "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0304] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -11717,7 +11948,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
         @r###"
-    ── NOT EXPOSED ─────────────────────────────────────────── /code/proj/Main.roc ─
+    ── NOT EXPOSED [R0255] ─────────────────────────────────── /code/proj/Main.roc ─
 
     The Bool module does not expose `structuralEq`:
 
@@ -11731,7 +11962,7 @@ I recommend using camelCase. It's the standard style in Roc code!
         Bool.false
         Bool.isEq
 
-    ── NOT EXPOSED ─────────────────────────────────────────── /code/proj/Main.roc ─
+    ── NOT EXPOSED [R0255] ─────────────────────────────────── /code/proj/Main.roc ─
 
     The Bool module does not expose `structuralNotEq`:
 
@@ -11759,7 +11990,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0304] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -11784,7 +12015,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0302] ───────────────────────────────── /code/proj/Main.roc ─
 
     The 1st argument to `foo` is weird:
 
@@ -11813,7 +12044,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0304] ───────────── /code/proj/Main.roc ─
 
     I can't derive an implementation of the `Hash` ability for `F`:
 
@@ -11824,7 +12055,7 @@ I recommend using camelCase. It's the standard style in Roc code!
 
     Tip: You can define a custom implementation of `Hash` for `F`.
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0304] ───────────── /code/proj/Main.roc ─
 
     I can't derive an implementation of the `Eq` ability for `F`:
 
@@ -11835,7 +12066,7 @@ I recommend using camelCase. It's the standard style in Roc code!
 
     Tip: You can define a custom implementation of `Eq` for `F`.
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0304] ───────────── /code/proj/Main.roc ─
 
     I can't derive an implementation of the `Encoding` ability for `F`:
 
@@ -11858,7 +12089,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── DUPLICATE BOUND ABILITY ─────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE BOUND ABILITY [R0227] ─────────────────────── /code/proj/Main.roc ─
 
     I already saw that this type variable is bound to the `Hash` ability
     once before:
@@ -11883,7 +12114,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to `g` has an unexpected type:
 
@@ -11920,7 +12151,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to `g` has an unexpected type:
 
@@ -11958,7 +12189,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to `g` has an unexpected type:
 
@@ -11991,7 +12222,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── UNFINISHED LIST PATTERN ────────── tmp/list_pattern_not_terminated/Test.roc ─
+    ── UNFINISHED LIST PATTERN [R0111] ── tmp/list_pattern_not_terminated/Test.roc ─
 
     I am partway through parsing a list pattern, but I got stuck here:
 
@@ -12012,7 +12243,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── INCORRECT REST PATTERN ─────── tmp/list_pattern_weird_rest_pattern/Test.roc ─
+    ── INCORRECT REST PATTERN [R0111] ─ ...ist_pattern_weird_rest_pattern/Test.roc ─
 
     It looks like you may trying to write a list rest pattern, but it's
     not the form I expect:
@@ -12034,7 +12265,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── UNNECESSARY WILDCARD ────────────────────────────────── /code/proj/Main.roc ─
+    ── UNNECESSARY WILDCARD [R0244] ────────────────────────── /code/proj/Main.roc ─
 
     This type annotation has a wildcard type variable (`*`) that isn't
     needed.
@@ -12061,7 +12292,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── MULTIPLE LIST REST PATTERNS ─────────────────────────── /code/proj/Main.roc ─
+    ── MULTIPLE LIST REST PATTERNS [R0245] ─────────────────── /code/proj/Main.roc ─
 
     This list pattern match has multiple rest patterns:
 
@@ -12071,7 +12302,7 @@ I recommend using camelCase. It's the standard style in Roc code!
     I only support compiling list patterns with one .. pattern! Can you
     remove this additional one?
 
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -12095,7 +12326,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── MULTIPLE LIST REST PATTERNS ─────────────────────────── /code/proj/Main.roc ─
+    ── MULTIPLE LIST REST PATTERNS [R0245] ─────────────────── /code/proj/Main.roc ─
 
     This list pattern match has multiple rest patterns:
 
@@ -12105,7 +12336,7 @@ I recommend using camelCase. It's the standard style in Roc code!
     I only support compiling list patterns with one .. pattern! Can you
     remove this additional one?
 
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -12129,7 +12360,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0302] ───────────────────────────────── /code/proj/Main.roc ─
 
     This list element doesn't match the types of other elements in the
     pattern:
@@ -12156,7 +12387,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The branches of this `when` expression don't match the condition:
 
@@ -12189,7 +12420,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -12229,7 +12460,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -12285,7 +12516,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -12312,7 +12543,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -12355,7 +12586,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -12382,7 +12613,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -12431,7 +12662,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -12462,7 +12693,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -12494,7 +12725,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -12543,7 +12774,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -12577,7 +12808,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── REDUNDANT PATTERN ───────────────────────────────────── /code/proj/Main.roc ─
+    ── REDUNDANT PATTERN [R0306] ───────────────────────────── /code/proj/Main.roc ─
 
     The 3rd pattern is redundant:
 
@@ -12605,7 +12836,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── REDUNDANT PATTERN ───────────────────────────────────── /code/proj/Main.roc ─
+    ── REDUNDANT PATTERN [R0306] ───────────────────────────── /code/proj/Main.roc ─
 
     The 3rd pattern is redundant:
 
@@ -12633,7 +12864,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── REDUNDANT PATTERN ───────────────────────────────────── /code/proj/Main.roc ─
+    ── REDUNDANT PATTERN [R0306] ───────────────────────────── /code/proj/Main.roc ─
 
     The 3rd pattern is redundant:
 
@@ -12661,7 +12892,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── REDUNDANT PATTERN ───────────────────────────────────── /code/proj/Main.roc ─
+    ── REDUNDANT PATTERN [R0306] ───────────────────────────── /code/proj/Main.roc ─
 
     The 2nd pattern is redundant:
 
@@ -12700,7 +12931,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0304] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -12730,7 +12961,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This value passed to `crash` is not a string:
 
@@ -12755,7 +12986,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── UNAPPLIED CRASH ─────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNAPPLIED CRASH [R0247] ─────────────────────────────── /code/proj/Main.roc ─
 
     This `crash` doesn't have a message given to it:
 
@@ -12776,7 +13007,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── OVERAPPLIED CRASH ───────────────────────────────────── /code/proj/Main.roc ─
+    ── OVERAPPLIED CRASH [R0248] ───────────────────────────── /code/proj/Main.roc ─
 
     This `crash` has too many values given to it:
 
@@ -12812,7 +13043,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0304] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -12854,7 +13085,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── DEFINITION ONLY USED IN RECURSION ───────────────────── /code/proj/Main.roc ─
+    ── DEFINITION ONLY USED IN RECURSION [R0207] ───────────── /code/proj/Main.roc ─
 
     This definition is only used in recursion with itself:
 
@@ -12887,7 +13118,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── DEFINITIONs ONLY USED IN RECURSION ──────────────────── /code/proj/Main.roc ─
+    ── DEFINITIONs ONLY USED IN RECURSION [R0207] ──────────── /code/proj/Main.roc ─
 
     These 2 definitions are only used in mutual recursion with themselves:
 
@@ -12923,7 +13154,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── DEFINITION ONLY USED IN RECURSION ───────────────────── /code/proj/Main.roc ─
+    ── DEFINITION ONLY USED IN RECURSION [R0207] ───────────── /code/proj/Main.roc ─
 
     This definition is only used in recursion with itself:
 
@@ -12959,7 +13190,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── DEFINITIONs ONLY USED IN RECURSION ──────────────────── /code/proj/Main.roc ─
+    ── DEFINITIONs ONLY USED IN RECURSION [R0207] ──────────── /code/proj/Main.roc ─
 
     These 2 definitions are only used in mutual recursion with themselves:
 
@@ -12998,7 +13229,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             {one, str}
         "#),
     @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `concat` has an unexpected type:
 
@@ -13029,7 +13260,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the `else` branch of this `if` expression:
 
@@ -13066,7 +13297,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the `else` branch of this `if` expression:
 
@@ -13102,7 +13333,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `main` definition:
 
@@ -13140,7 +13371,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
     @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `main` definition:
 
@@ -13191,7 +13422,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0304] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -13220,7 +13451,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0304] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -13270,7 +13501,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0304] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -13310,7 +13541,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [R0304] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -13350,7 +13581,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [R0305] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -13398,7 +13629,7 @@ I recommend using camelCase. It's the standard style in Roc code!
             "#
         ),
         @r###"
-    ── TOO MANY ARGS ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TOO MANY ARGS [R0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The `parser` value is an opaque type, so it cannot be called with an
     argument:
@@ -1,4 +1,5 @@
 use bumpalo::{collections::Vec, Bump};
+use std::collections::HashMap;
 use std::fmt::{self, Write};
 use std::iter::{self, once, Iterator};
 
@@ -10,7 +11,10 @@ use roc_wasm_module::{Value, ValueType};
 
 use crate::frame::Frame;
 use crate::value_store::ValueStore;
-use crate::{Error, ImportDispatcher};
+use crate::{Error, ImportDispatcher, Trap, TrapFrame, TrapKind};
+
+/// Calls can only be nested this deep before the program traps with a stack overflow
+pub const MAX_CALL_DEPTH: usize = 100_000;
 
 #[derive(Debug)]
 pub enum Action {
@@ -66,6 +70,8 @@ pub struct Instance<'a, I: ImportDispatcher> {
     import_arguments: Vec<'a, Value>,
     /// temporary storage for output using the --debug option
     debug_string: Option<String>,
+    /// Number of instructions the program may execute before trapping. `None` means no limit.
+    pub fuel: Option<u64>,
 }
 
 impl<'a, I: ImportDispatcher> Instance<'a, I> {
@@ -95,6 +101,7 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
             import_dispatcher,
             import_arguments: Vec::new_in(arena),
             debug_string: Some(String::new()),
+            fuel: None,
         }
     }
 
@@ -163,24 +170,26 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
             import_dispatcher,
             import_arguments: Vec::new_in(arena),
             debug_string,
+            fuel: None,
         })
     }
 
-    pub fn call_export<A>(&mut self, fn_name: &str, arg_values: A) -> Result<Option<Value>, String>
+    pub fn call_export<A>(&mut self, fn_name: &str, arg_values: A) -> Result<Option<Value>, Trap>
     where
         A: IntoIterator<Item = Value>,
     {
-        let (fn_index, param_type_iter, ret_type) =
-            self.call_export_help_before_arg_load(self.module, fn_name)?;
+        let (fn_index, param_type_iter, ret_type) = self
+            .call_export_help_before_arg_load(self.module, fn_name)
+            .map_err(Trap::invalid)?;
         let n_args = param_type_iter.len();
 
         for (i, (value, expected_type)) in arg_values.into_iter().zip(param_type_iter).enumerate() {
             let actual_type = ValueType::from(value);
             if actual_type != expected_type {
-                return Err(format!(
+                return Err(Trap::invalid(format!(
                     "Type mismatch on argument {} of {}. Expected {:?} but got {:?}",
                     i, fn_name, expected_type, value
-                ));
+                )));
             }
             self.value_store.push(value);
         }
@@ -193,7 +202,7 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
         module: &WasmModule<'a>,
         fn_name: &str,
         arg_strings: &'a [&'a [u8]],
-    ) -> Result<Option<Value>, String> {
+    ) -> Result<Option<Value>, Trap> {
         // We have two different mechanisms for handling CLI arguments!
         // 1. Basic numbers:
         //      e.g. `roc_wasm_interp fibonacci 12`
@@ -207,8 +216,9 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
 
         // Implement the "basic numbers" CLI
         // Check if the called Wasm function takes numeric arguments, and if so, try to parse them from the CLI.
-        let (fn_index, param_type_iter, ret_type) =
-            self.call_export_help_before_arg_load(module, fn_name)?;
+        let (fn_index, param_type_iter, ret_type) = self
+            .call_export_help_before_arg_load(module, fn_name)
+            .map_err(Trap::invalid)?;
         let n_args = param_type_iter.len();
         for (value_bytes, value_type) in arg_strings
             .iter()
@@ -217,11 +227,12 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
         {
             use ValueType::*;
             let value_str = String::from_utf8_lossy(value_bytes);
+            let invalid = |e: &dyn ToString| Trap::invalid(e.to_string());
             let value = match value_type {
-                I32 => Value::I32(value_str.parse::<i32>().map_err(|e| e.to_string())?),
-                I64 => Value::I64(value_str.parse::<i64>().map_err(|e| e.to_string())?),
-                F32 => Value::F32(value_str.parse::<f32>().map_err(|e| e.to_string())?),
-                F64 => Value::F64(value_str.parse::<f64>().map_err(|e| e.to_string())?),
+                I32 => Value::I32(value_str.parse::<i32>().map_err(|e| invalid(&e))?),
+                I64 => Value::I64(value_str.parse::<i64>().map_err(|e| invalid(&e))?),
+                F32 => Value::F32(value_str.parse::<f32>().map_err(|e| invalid(&e))?),
+                F64 => Value::F64(value_str.parse::<f64>().map_err(|e| invalid(&e))?),
             };
             self.value_store.push(value);
         }
@@ -299,7 +310,7 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
         fn_index: usize,
         n_args: usize,
         return_type: Option<ValueType>,
-    ) -> Result<Option<Value>, String> {
+    ) -> Result<Option<Value>, Trap> {
         self.previous_frames.clear();
         self.blocks.clear();
         self.blocks.push(Block {
@@ -328,10 +339,15 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
                     break;
                 }
                 Err(e) => {
-                    let file_offset = self.program_counter + module.code.section_offset as usize;
-                    let mut message = e.to_string_at(file_offset);
-                    self.debug_stack_trace(&mut message).unwrap();
-                    return Err(message);
+                    let kind = match e {
+                        Error::Trap(kind) => kind,
+                        _ => {
+                            let file_offset =
+                                self.program_counter + module.code.section_offset as usize;
+                            TrapKind::Invalid(e.to_string_at(file_offset))
+                        }
+                    };
+                    return Err(self.trap(kind));
                 }
             };
         }
@@ -391,17 +407,17 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
         }
     }
 
-    fn get_load_address(&mut self, module: &WasmModule<'a>) -> Result<u32, Error> {
+    fn get_load_address(&mut self, module: &WasmModule<'a>) -> Result<u64, Error> {
         // Alignment is not used in the execution steps from the spec! Maybe it's just an optimization hint?
         // https://webassembly.github.io/spec/core/exec/instructions.html#memory-instructions
         // Also note: in the text format we can specify the useless `align=` but not the useful `offset=`!
         let _alignment = self.fetch_immediate_u32(module);
         let offset = self.fetch_immediate_u32(module);
         let base_addr = self.value_store.pop_u32()?;
-        Ok(base_addr + offset)
+        Ok(base_addr as u64 + offset as u64)
     }
 
-    fn get_store_addr_value(&mut self, module: &WasmModule<'a>) -> Result<(usize, Value), Error> {
+    fn get_store_addr_value(&mut self, module: &WasmModule<'a>) -> Result<(u64, Value), Error> {
         // Alignment is not used in the execution steps from the spec! Maybe it's just an optimization hint?
         // https://webassembly.github.io/spec/core/exec/instructions.html#memory-instructions
         // Also note: in the text format we can specify the useless `align=` but not the useful `offset=`!
@@ -409,10 +425,20 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
         let offset = self.fetch_immediate_u32(module);
         let value = self.value_store.pop();
         let base_addr = self.value_store.pop_u32()?;
-        let addr = (base_addr + offset) as usize;
+        let addr = base_addr as u64 + offset as u64;
         Ok((addr, value))
     }
 
+    /// The range of memory accessed by a load or store, if it's in bounds
+    fn memory_range(&self, addr: u64, size: u32) -> Result<std::ops::Range<usize>, Error> {
+        let end = addr + size as u64;
+        if end <= self.memory.len() as u64 {
+            Ok(addr as usize..end as usize)
+        } else {
+            Err(Error::Trap(TrapKind::MemoryOutOfBounds { addr, size }))
+        }
+    }
+
     fn write_debug<T: fmt::Debug>(&mut self, value: T) {
         if let Some(debug_string) = self.debug_string.as_mut() {
             std::write!(debug_string, "{:?} ", value).unwrap();
@@ -499,11 +525,9 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
         };
 
         if let Some(expected) = expected_signature {
-            assert_eq!(
-                expected, signature_index,
-                "Indirect function call failed. Expected signature {} but found {}",
-                expected, signature_index,
-            );
+            if expected != signature_index {
+                return Err(Error::Trap(TrapKind::IndirectCallTypeMismatch));
+            }
         }
 
         let (arg_type_iter, ret_type) = module.types.look_up(signature_index);
@@ -538,6 +562,10 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
                 write!(debug_string, " {}.{}", import.module, import.name).unwrap();
             }
         } else {
+            if self.previous_frames.len() >= MAX_CALL_DEPTH {
+                return Err(Error::Trap(TrapKind::StackOverflow));
+            }
+
             let return_addr = self.program_counter;
             // set PC to start of function bytes
             let internal_fn_index = fn_index - self.import_count;
@@ -600,6 +628,13 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
     ) -> Result<Action, Error> {
         use OpCode::*;
 
+        if let Some(fuel) = self.fuel.as_mut() {
+            if *fuel == 0 {
+                return Err(Error::Trap(TrapKind::FuelExhausted));
+            }
+            *fuel -= 1;
+        }
+
         let file_offset = self.program_counter as u32 + module.code.section_offset;
         let op_code = OpCode::from(module.code.bytes[self.program_counter]);
         self.program_counter += 1;
@@ -614,7 +649,7 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
 
         match op_code {
             UNREACHABLE => {
-                return Err(Error::UnreachableOp);
+                return Err(Error::Trap(TrapKind::Unreachable));
             }
             NOP => {}
             BLOCK => {
@@ -741,12 +776,10 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
                 );
 
                 // Dereference the function pointer (look up the element index in the function table)
-                let fn_index = module.element.lookup(element_index).unwrap_or_else(|| {
-                    panic!(
-                        "Indirect function call failed. There is no function with element index {}",
-                        element_index
-                    )
-                });
+                let fn_index = module
+                    .element
+                    .lookup(element_index)
+                    .ok_or(Error::Trap(TrapKind::UndefinedElement))?;
 
                 self.do_call(Some(expected_signature), fn_index as usize, module)?;
             }
@@ -791,151 +824,160 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
                 self.globals[index as usize] = self.value_store.pop();
             }
             I32LOAD => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module)?;
                 let mut bytes = [0; 4];
-                bytes.copy_from_slice(&self.memory[addr..][..4]);
+                bytes.copy_from_slice(&self.memory[self.memory_range(addr, 4)?]);
                 let value = i32::from_le_bytes(bytes);
                 self.value_store.push(Value::I32(value));
             }
             I64LOAD => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module)?;
                 let mut bytes = [0; 8];
-                bytes.copy_from_slice(&self.memory[addr..][..8]);
+                bytes.copy_from_slice(&self.memory[self.memory_range(addr, 8)?]);
                 let value = i64::from_le_bytes(bytes);
                 self.value_store.push(Value::I64(value));
             }
             F32LOAD => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module)?;
                 let mut bytes = [0; 4];
-                bytes.copy_from_slice(&self.memory[addr..][..4]);
+                bytes.copy_from_slice(&self.memory[self.memory_range(addr, 4)?]);
                 let value = f32::from_le_bytes(bytes);
                 self.value_store.push(Value::F32(value));
             }
             F64LOAD => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module)?;
                 let mut bytes = [0; 8];
-                bytes.copy_from_slice(&self.memory[addr..][..8]);
+                bytes.copy_from_slice(&self.memory[self.memory_range(addr, 8)?]);
                 let value = f64::from_le_bytes(bytes);
                 self.value_store.push(Value::F64(value));
             }
             I32LOAD8S => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module)?;
                 let mut bytes = [0; 1];
-                bytes.copy_from_slice(&self.memory[addr..][..1]);
+                bytes.copy_from_slice(&self.memory[self.memory_range(addr, 1)?]);
                 let value = i8::from_le_bytes(bytes);
                 self.value_store.push(Value::I32(value as i32));
             }
             I32LOAD8U => {
-                let addr = self.get_load_address(module)? as usize;
-                let value = self.memory[addr];
+                let addr = self.get_load_address(module)?;
+                let value = self.memory[self.memory_range(addr, 1)?][0];
                 self.value_store.push(Value::I32(value as i32));
             }
             I32LOAD16S => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module)?;
                 let mut bytes = [0; 2];
-                bytes.copy_from_slice(&self.memory[addr..][..2]);
+                bytes.copy_from_slice(&self.memory[self.memory_range(addr, 2)?]);
                 let value = i16::from_le_bytes(bytes);
                 self.value_store.push(Value::I32(value as i32));
             }
             I32LOAD16U => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module)?;
                 let mut bytes = [0; 2];
-                bytes.copy_from_slice(&self.memory[addr..][..2]);
+                bytes.copy_from_slice(&self.memory[self.memory_range(addr, 2)?]);
                 let value = u16::from_le_bytes(bytes);
                 self.value_store.push(Value::I32(value as i32));
             }
             I64LOAD8S => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module)?;
                 let mut bytes = [0; 1];
-                bytes.copy_from_slice(&self.memory[addr..][..1]);
+                bytes.copy_from_slice(&self.memory[self.memory_range(addr, 1)?]);
                 let value = i8::from_le_bytes(bytes);
                 self.value_store.push(Value::I64(value as i64));
             }
             I64LOAD8U => {
-                let addr = self.get_load_address(module)? as usize;
-                let value = self.memory[addr];
+                let addr = self.get_load_address(module)?;
+                let value = self.memory[self.memory_range(addr, 1)?][0];
                 self.value_store.push(Value::I64(value as i64));
             }
             I64LOAD16S => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module)?;
                 let mut bytes = [0; 2];
-                bytes.copy_from_slice(&self.memory[addr..][..2]);
+                bytes.copy_from_slice(&self.memory[self.memory_range(addr, 2)?]);
                 let value = i16::from_le_bytes(bytes);
                 self.value_store.push(Value::I64(value as i64));
             }
             I64LOAD16U => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module)?;
                 let mut bytes = [0; 2];
-                bytes.copy_from_slice(&self.memory[addr..][..2]);
+                bytes.copy_from_slice(&self.memory[self.memory_range(addr, 2)?]);
                 let value = u16::from_le_bytes(bytes);
                 self.value_store.push(Value::I64(value as i64));
             }
             I64LOAD32S => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module)?;
                 let mut bytes = [0; 4];
-                bytes.copy_from_slice(&self.memory[addr..][..4]);
+                bytes.copy_from_slice(&self.memory[self.memory_range(addr, 4)?]);
                 let value = i32::from_le_bytes(bytes);
                 self.value_store.push(Value::I64(value as i64));
             }
             I64LOAD32U => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module)?;
                 let mut bytes = [0; 4];
-                bytes.copy_from_slice(&self.memory[addr..][..4]);
+                bytes.copy_from_slice(&self.memory[self.memory_range(addr, 4)?]);
                 let value = u32::from_le_bytes(bytes);
                 self.value_store.push(Value::I64(value as i64));
             }
             I32STORE => {
                 let (addr, value) = self.get_store_addr_value(module)?;
                 let unwrapped = value.expect_i32().map_err(Error::from)?;
-                let target = &mut self.memory[addr..][..4];
+                let range = self.memory_range(addr, 4)?;
+                let target = &mut self.memory[range];
                 target.copy_from_slice(&unwrapped.to_le_bytes());
             }
             I64STORE => {
                 let (addr, value) = self.get_store_addr_value(module)?;
                 let unwrapped = value.expect_i64().map_err(Error::from)?;
-                let target = &mut self.memory[addr..][..8];
+                let range = self.memory_range(addr, 8)?;
+                let target = &mut self.memory[range];
                 target.copy_from_slice(&unwrapped.to_le_bytes());
             }
             F32STORE => {
                 let (addr, value) = self.get_store_addr_value(module)?;
                 let unwrapped = value.expect_f32().map_err(Error::from)?;
-                let target = &mut self.memory[addr..][..4];
+                let range = self.memory_range(addr, 4)?;
+                let target = &mut self.memory[range];
                 target.copy_from_slice(&unwrapped.to_le_bytes());
             }
             F64STORE => {
                 let (addr, value) = self.get_store_addr_value(module)?;
                 let unwrapped = value.expect_f64().map_err(Error::from)?;
-                let target = &mut self.memory[addr..][..8];
+                let range = self.memory_range(addr, 8)?;
+                let target = &mut self.memory[range];
                 target.copy_from_slice(&unwrapped.to_le_bytes());
             }
             I32STORE8 => {
                 let (addr, value) = self.get_store_addr_value(module)?;
                 let unwrapped = value.expect_i32().map_err(Error::from)?;
-                let target = &mut self.memory[addr..][..1];
+                let range = self.memory_range(addr, 1)?;
+                let target = &mut self.memory[range];
                 target.copy_from_slice(&unwrapped.to_le_bytes()[..1]);
             }
             I32STORE16 => {
                 let (addr, value) = self.get_store_addr_value(module)?;
                 let unwrapped = value.expect_i32().map_err(Error::from)?;
-                let target = &mut self.memory[addr..][..2];
+                let range = self.memory_range(addr, 2)?;
+                let target = &mut self.memory[range];
                 target.copy_from_slice(&unwrapped.to_le_bytes()[..2]);
             }
             I64STORE8 => {
                 let (addr, value) = self.get_store_addr_value(module)?;
                 let unwrapped = value.expect_i64().map_err(Error::from)?;
-                let target = &mut self.memory[addr..][..1];
+                let range = self.memory_range(addr, 1)?;
+                let target = &mut self.memory[range];
                 target.copy_from_slice(&unwrapped.to_le_bytes()[..1]);
             }
             I64STORE16 => {
                 let (addr, value) = self.get_store_addr_value(module)?;
                 let unwrapped = value.expect_i64().map_err(Error::from)?;
-                let target = &mut self.memory[addr..][..2];
+                let range = self.memory_range(addr, 2)?;
+                let target = &mut self.memory[range];
                 target.copy_from_slice(&unwrapped.to_le_bytes()[..2]);
             }
             I64STORE32 => {
                 let (addr, value) = self.get_store_addr_value(module)?;
                 let unwrapped = value.expect_i64().map_err(Error::from)?;
-                let target = &mut self.memory[addr..][..4];
+                let range = self.memory_range(addr, 4)?;
+                let target = &mut self.memory[range];
                 target.copy_from_slice(&unwrapped.to_le_bytes()[..4]);
             }
             CURRENTMEMORY => {
@@ -1228,21 +1270,36 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
             I32DIVS => {
                 let arg2 = self.value_store.pop_i32()?;
                 let arg1 = self.value_store.pop_i32()?;
+                if arg2 == 0 {
+                    return Err(Error::Trap(TrapKind::DivisionByZero));
+                }
+                if arg1 == i32::MIN && arg2 == -1 {
+                    return Err(Error::Trap(TrapKind::IntegerOverflow));
+                }
                 self.value_store.push(Value::from(arg1.wrapping_div(arg2)));
             }
             I32DIVU => {
                 let arg2 = self.value_store.pop_u32()?;
                 let arg1 = self.value_store.pop_u32()?;
+                if arg2 == 0 {
+                    return Err(Error::Trap(TrapKind::DivisionByZero));
+                }
                 self.value_store.push(Value::from(arg1.wrapping_div(arg2)));
             }
             I32REMS => {
                 let arg2 = self.value_store.pop_i32()?;
                 let arg1 = self.value_store.pop_i32()?;
+                if arg2 == 0 {
+                    return Err(Error::Trap(TrapKind::DivisionByZero));
+                }
                 self.value_store.push(Value::from(arg1.wrapping_rem(arg2)));
             }
             I32REMU => {
                 let arg2 = self.value_store.pop_u32()?;
                 let arg1 = self.value_store.pop_u32()?;
+                if arg2 == 0 {
+                    return Err(Error::Trap(TrapKind::DivisionByZero));
+                }
                 self.value_store.push(Value::from(arg1.wrapping_rem(arg2)));
             }
             I32AND => {
@@ -1324,21 +1381,36 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
             I64DIVS => {
                 let arg2 = self.value_store.pop_i64()?;
                 let arg1 = self.value_store.pop_i64()?;
+                if arg2 == 0 {
+                    return Err(Error::Trap(TrapKind::DivisionByZero));
+                }
+                if arg1 == i64::MIN && arg2 == -1 {
+                    return Err(Error::Trap(TrapKind::IntegerOverflow));
+                }
                 self.value_store.push(Value::from(arg1.wrapping_div(arg2)));
             }
             I64DIVU => {
                 let arg2 = self.value_store.pop_u64()?;
                 let arg1 = self.value_store.pop_u64()?;
+                if arg2 == 0 {
+                    return Err(Error::Trap(TrapKind::DivisionByZero));
+                }
                 self.value_store.push(Value::from(arg1.wrapping_div(arg2)));
             }
             I64REMS => {
                 let arg2 = self.value_store.pop_i64()?;
                 let arg1 = self.value_store.pop_i64()?;
+                if arg2 == 0 {
+                    return Err(Error::Trap(TrapKind::DivisionByZero));
+                }
                 self.value_store.push(Value::from(arg1.wrapping_rem(arg2)));
             }
             I64REMU => {
                 let arg2 = self.value_store.pop_u64()?;
                 let arg1 = self.value_store.pop_u64()?;
+                if arg2 == 0 {
+                    return Err(Error::Trap(TrapKind::DivisionByZero));
+                }
                 self.value_store.push(Value::from(arg1.wrapping_rem(arg2)));
             }
             I64AND => {
@@ -1569,29 +1641,29 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
             }
             I32TRUNCSF32 => {
                 let arg = self.value_store.pop_f32()?;
-                if arg < i32::MIN as f32 || arg > i32::MAX as f32 {
-                    panic!("Cannot truncate {} from F32 to I32", arg);
+                if !is_truncatable(arg as f64, -2f64.powi(31), 2f64.powi(31)) {
+                    return Err(Error::Trap(TrapKind::InvalidConversion));
                 }
                 self.value_store.push(Value::I32(arg as i32));
             }
             I32TRUNCUF32 => {
                 let arg = self.value_store.pop_f32()?;
-                if arg < u32::MIN as f32 || arg > u32::MAX as f32 {
-                    panic!("Cannot truncate {} from F32 to unsigned I32", arg);
+                if !is_truncatable(arg as f64, 0.0, 2f64.powi(32)) {
+                    return Err(Error::Trap(TrapKind::InvalidConversion));
                 }
                 self.value_store.push(Value::from(arg as u32));
             }
             I32TRUNCSF64 => {
                 let arg = self.value_store.pop_f64()?;
-                if arg < i32::MIN as f64 || arg > i32::MAX as f64 {
-                    panic!("Cannot truncate {} from F64 to I32", arg);
+                if !is_truncatable(arg, -2f64.powi(31), 2f64.powi(31)) {
+                    return Err(Error::Trap(TrapKind::InvalidConversion));
                 }
                 self.value_store.push(Value::I32(arg as i32));
             }
             I32TRUNCUF64 => {
                 let arg = self.value_store.pop_f64()?;
-                if arg < u32::MIN as f64 || arg > u32::MAX as f64 {
-                    panic!("Cannot truncate {} from F64 to unsigned I32", arg);
+                if !is_truncatable(arg, 0.0, 2f64.powi(32)) {
+                    return Err(Error::Trap(TrapKind::InvalidConversion));
                 }
                 self.value_store.push(Value::from(arg as u32));
             }
@@ -1605,29 +1677,29 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
             }
             I64TRUNCSF32 => {
                 let arg = self.value_store.pop_f32()?;
-                if arg < i64::MIN as f32 || arg > i64::MAX as f32 {
-                    panic!("Cannot truncate {} from F32 to I64", arg);
+                if !is_truncatable(arg as f64, -2f64.powi(63), 2f64.powi(63)) {
+                    return Err(Error::Trap(TrapKind::InvalidConversion));
                 }
                 self.value_store.push(Value::I64(arg as i64));
             }
             I64TRUNCUF32 => {
                 let arg = self.value_store.pop_f32()?;
-                if arg < u64::MIN as f32 || arg > u64::MAX as f32 {
-                    panic!("Cannot truncate {} from F32 to unsigned I64", arg);
+                if !is_truncatable(arg as f64, 0.0, 2f64.powi(64)) {
+                    return Err(Error::Trap(TrapKind::InvalidConversion));
                 }
                 self.value_store.push(Value::from(arg as u64));
             }
             I64TRUNCSF64 => {
                 let arg = self.value_store.pop_f64()?;
-                if arg < i64::MIN as f64 || arg > i64::MAX as f64 {
-                    panic!("Cannot truncate {} from F64 to I64", arg);
+                if !is_truncatable(arg, -2f64.powi(63), 2f64.powi(63)) {
+                    return Err(Error::Trap(TrapKind::InvalidConversion));
                 }
                 self.value_store.push(Value::I64(arg as i64));
             }
             I64TRUNCUF64 => {
                 let arg = self.value_store.pop_f64()?;
-                if arg < u64::MIN as f64 || arg > u64::MAX as f64 {
-                    panic!("Cannot truncate {} from F64 to unsigned I64", arg);
+                if !is_truncatable(arg, 0.0, 2f64.powi(64)) {
+                    return Err(Error::Trap(TrapKind::InvalidConversion));
                 }
                 self.value_store.push(Value::from(arg as u64));
             }
@@ -1748,6 +1820,48 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
         eprintln!();
     }
 
+    /// Describe why the program stopped, with the call stack at the current instruction
+    fn trap(&self, kind: TrapKind) -> Trap {
+        let mut details = String::new();
+        self.debug_stack_trace(&mut details).unwrap();
+
+        let fn_names: HashMap<u32, &str> =
+            HashMap::from_iter(self.module.names.function_names.iter().copied());
+        let section_offset = self.module.code.section_offset as usize;
+        let frames = self.previous_frames.iter().chain(once(&self.current_frame));
+        let mut call_stack: std::vec::Vec<TrapFrame> = frames
+            .zip(self.execution_addrs())
+            .map(|(frame, addr)| TrapFrame {
+                fn_index: frame.fn_index,
+                fn_name: fn_names
+                    .get(&(frame.fn_index as u32))
+                    .map(|name| name.to_string()),
+                file_offset: section_offset + addr,
+            })
+            .collect();
+        call_stack.reverse();
+
+        Trap {
+            kind,
+            call_stack,
+            details,
+        }
+    }
+
+    /// Find the code address of each frame, outermost first.
+    /// For previous frames, this is the address of the CALL instruction.
+    /// For the current frame, it's the program counter value.
+    fn execution_addrs(&self) -> impl Iterator<Item = usize> + '_ {
+        // for each previous_frame, find return address of the *next* frame
+        let frames = self.previous_frames.iter().chain(once(&self.current_frame));
+        let next_frames = frames.skip(1);
+        let return_addrs = next_frames.map(|f| f.return_addr);
+        // roll back to the CALL instruction before that return address, it's more meaningful.
+        let call_addrs = return_addrs.map(|ra| self.debug_return_addr_to_call_addr(ra));
+        // For the current frame, show the program_counter
+        call_addrs.chain(once(self.program_counter))
+    }
+
     /// Dump a stack trace when an error occurs
     /// --------------
     /// func[123]
@@ -1762,18 +1876,7 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
 
         let frames = self.previous_frames.iter().chain(once(&self.current_frame));
         let next_frames = frames.clone().skip(1);
-
-        // Find the code address to display for each frame
-        // For previous frames, show the address of the CALL instruction
-        // For the current frame, show the program counter value
-        let mut execution_addrs = {
-            // for each previous_frame, find return address of the *next* frame
-            let return_addrs = next_frames.clone().map(|f| f.return_addr);
-            // roll back to the CALL instruction before that return address, it's more meaningful.
-            let call_addrs = return_addrs.map(|ra| self.debug_return_addr_to_call_addr(ra));
-            // For the current frame, show the program_counter
-            call_addrs.chain(once(self.program_counter))
-        };
+        let mut execution_addrs = self.execution_addrs();
 
         let mut frame_ends = next_frames.map(|f| f.locals_start);
 
@@ -1848,3 +1951,11 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
         call_addr
     }
 }
+
+/// Check whether a float truncates to an integer in the range `min..max_plus_one`.
+/// The bounds are powers of two, so they are exact as floats, unlike the integer's MAX.
+fn is_truncatable(x: f64, min: f64, max_plus_one: f64) -> bool {
+    // NaN is not truncatable, and all comparisons with it are false
+    let truncated = x.trunc();
+    truncated >= min && truncated < max_plus_one
+}
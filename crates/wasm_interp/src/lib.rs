@@ -5,11 +5,12 @@ mod value_store;
 pub mod wasi;
 
 // Main external interface
-pub use instance::Instance;
+pub use instance::{Instance, MAX_CALL_DEPTH};
 pub use wasi::{WasiDispatcher, WasiFile};
 
 pub use roc_wasm_module::Value;
 use roc_wasm_module::ValueType;
+use std::fmt;
 
 pub trait ImportDispatcher {
     /// Dispatch a call from WebAssembly to your own code, based on module and function name.
@@ -61,13 +62,121 @@ impl<'a> ImportDispatcher for DefaultImportDispatcher<'a> {
     }
 }
 
+/// Why the program stopped before returning from the function that was called
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrapKind {
+    /// The program executed an `unreachable` instruction. This is how Roc apps crash.
+    Unreachable,
+    /// A load or store of `size` bytes at `addr` was outside of the instance's memory
+    MemoryOutOfBounds { addr: u64, size: u32 },
+    /// Integer division or remainder by zero
+    DivisionByZero,
+    /// Signed integer division of the minimum value by -1
+    IntegerOverflow,
+    /// A float was NaN or out of range when truncating it to an integer
+    InvalidConversion,
+    /// `call_indirect` found a function with a different signature from the one it expected
+    IndirectCallTypeMismatch,
+    /// `call_indirect` used an element index that is not in the function table
+    UndefinedElement,
+    /// There were more than `MAX_CALL_DEPTH` nested calls
+    StackOverflow,
+    /// The instance executed as many instructions as its `fuel` allowed
+    FuelExhausted,
+    /// The module isn't valid WebAssembly, or the function was called in the wrong way
+    Invalid(String),
+}
+
+impl fmt::Display for TrapKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrapKind::Unreachable => write!(f, "WebAssembly `unreachable` instruction"),
+            TrapKind::MemoryOutOfBounds { addr, size } => write!(
+                f,
+                "out of bounds memory access of {} bytes at address {:#x}",
+                size, addr
+            ),
+            TrapKind::DivisionByZero => write!(f, "integer divide by zero"),
+            TrapKind::IntegerOverflow => write!(f, "integer overflow"),
+            TrapKind::InvalidConversion => write!(f, "invalid conversion to integer"),
+            TrapKind::IndirectCallTypeMismatch => write!(f, "indirect call type mismatch"),
+            TrapKind::UndefinedElement => write!(f, "undefined element in function table"),
+            TrapKind::StackOverflow => write!(f, "call stack exhausted"),
+            TrapKind::FuelExhausted => write!(f, "all fuel consumed"),
+            TrapKind::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+/// A function call that was in progress when the program trapped
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrapFrame {
+    pub fn_index: usize,
+    /// The function's name from the module's name section, if it has one
+    pub fn_name: Option<String>,
+    /// File offset of the instruction being executed. For callers, this is the call instruction.
+    pub file_offset: usize,
+}
+
+/// An error that stopped the program, with the WebAssembly call stack at the time
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trap {
+    pub kind: TrapKind,
+    /// Innermost call first. Empty if the program never started.
+    pub call_stack: std::vec::Vec<TrapFrame>,
+    /// Arguments, locals and stack values of each call, for debugging the interpreter
+    pub details: String,
+}
+
+impl Trap {
+    /// How many calls to show in `Display` output. A stack overflow has a *lot* of them!
+    const MAX_DISPLAYED_FRAMES: usize = 50;
+
+    pub(crate) fn invalid(message: String) -> Self {
+        Trap {
+            kind: TrapKind::Invalid(message),
+            call_stack: std::vec::Vec::new(),
+            details: String::new(),
+        }
+    }
+}
+
+impl fmt::Display for Trap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.kind)?;
+        for frame in self.call_stack.iter().take(Self::MAX_DISPLAYED_FRAMES) {
+            let name = frame.fn_name.as_deref().unwrap_or("<unnamed>");
+            writeln!(
+                f,
+                "    at func[{}] {} ({:#x})",
+                frame.fn_index, name, frame.file_offset
+            )?;
+        }
+        if self.call_stack.len() > Self::MAX_DISPLAYED_FRAMES {
+            writeln!(
+                f,
+                "    ... and {} more",
+                self.call_stack.len() - Self::MAX_DISPLAYED_FRAMES
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Trap> for String {
+    /// Includes the details, for test failures and other debugging
+    fn from(trap: Trap) -> Self {
+        format!("{}{}", trap, trap.details)
+    }
+}
+
 /// Errors that can happen while interpreting the program
 /// All of these cause a WebAssembly stack trace to be dumped
 #[derive(Debug, PartialEq)]
 pub(crate) enum Error {
     Type(ValueType, ValueType),
     StackEmpty,
-    UnreachableOp,
+    Trap(TrapKind),
 }
 
 impl Error {
@@ -85,11 +194,8 @@ impl Error {
                     file_offset
                 )
             }
-            Error::UnreachableOp => {
-                format!(
-                    "WebAssembly `unreachable` instruction at file offset {:#x}.\n",
-                    file_offset
-                )
+            Error::Trap(kind) => {
+                format!("{} at file offset {:#x}.\n", kind, file_offset)
             }
         }
     }
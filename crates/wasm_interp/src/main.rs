@@ -12,6 +12,8 @@ use roc_wasm_module::WasmModule;
 pub const FLAG_FUNCTION: &str = "function";
 pub const FLAG_DEBUG: &str = "debug";
 pub const FLAG_HEX: &str = "hex";
+pub const FLAG_DIR: &str = "dir";
+pub const FLAG_ENV: &str = "env";
 pub const WASM_FILE: &str = "WASM_FILE";
 pub const ARGS_FOR_APP: &str = "ARGS_FOR_APP";
 
@@ -38,6 +40,20 @@ fn main() -> io::Result<()> {
         .action(ArgAction::SetTrue)
         .required(false);
 
+    let flag_dir = Arg::new(FLAG_DIR)
+        .long(FLAG_DIR)
+        .help("Give the app access to a host directory, as HOST_DIR or GUEST_DIR::HOST_DIR\ne.g. `--dir .` or `--dir /data::./test-data`")
+        .action(ArgAction::Append)
+        .takes_value(true)
+        .required(false);
+
+    let flag_env = Arg::new(FLAG_ENV)
+        .long(FLAG_ENV)
+        .help("Set an environment variable for the app, as NAME=VALUE")
+        .action(ArgAction::Append)
+        .takes_value(true)
+        .required(false);
+
     let wasm_file_to_run = Arg::new(WASM_FILE)
         .help("The .wasm file to run")
         .required(true);
@@ -52,6 +68,8 @@ fn main() -> io::Result<()> {
         .arg(flag_function)
         .arg(flag_debug)
         .arg(flag_hex)
+        .arg(flag_dir)
+        .arg(flag_env)
         .arg(wasm_file_to_run)
         .trailing_var_arg(true)
         .arg(args_for_app);
@@ -64,6 +82,8 @@ fn main() -> io::Result<()> {
     let is_hex_format = matches.get_flag(FLAG_HEX);
     let start_arg_strings = matches.get_many::<String>(ARGS_FOR_APP).unwrap_or_default();
     let wasm_path = matches.get_one::<String>(WASM_FILE).unwrap();
+    let dirs = matches.get_many::<String>(FLAG_DIR).unwrap_or_default();
+    let env_vars = matches.get_many::<String>(FLAG_ENV).unwrap_or_default();
     // WASI expects the .wasm file to be argv[0]
     let wasi_argv_iter = once(wasm_path)
         .chain(start_arg_strings)
@@ -89,7 +109,28 @@ fn main() -> io::Result<()> {
 
     // Create an execution instance
 
-    let dispatcher = DefaultImportDispatcher::new(&wasi_argv);
+    let mut dispatcher = DefaultImportDispatcher::new(&wasi_argv);
+
+    for dir in dirs {
+        match dir.split_once("::") {
+            Some((guest_path, host_path)) => dispatcher.wasi.preopen_dir(guest_path, host_path),
+            None => dispatcher.wasi.preopen_dir(dir.as_str(), dir.as_str()),
+        }
+    }
+
+    for env_var in env_vars {
+        match env_var.split_once('=') {
+            Some((name, value)) => dispatcher.wasi.env.push((name.into(), value.into())),
+            None => {
+                eprintln!(
+                    "Environment variables must be given as NAME=VALUE, but I found `{}`",
+                    env_var
+                );
+                process::exit(1);
+            }
+        }
+    }
+
     let mut inst =
         Instance::for_module(&arena, &module, dispatcher, is_debug_mode).unwrap_or_else(|e| {
             eprintln!("{}", e);
@@ -111,8 +152,8 @@ fn main() -> io::Result<()> {
             }
         }
         Ok(None) => {}
-        Err(e) => {
-            eprintln!("{}", e);
+        Err(trap) => {
+            eprintln!("{}{}", trap, trap.details);
             process::exit(3);
         }
     }
@@ -7,8 +7,9 @@ mod test_f64;
 mod test_i32;
 mod test_i64;
 mod test_mem;
+mod test_wasi;
 
-use crate::{DefaultImportDispatcher, Instance};
+use crate::{DefaultImportDispatcher, Instance, Trap, TrapKind};
 use bumpalo::{collections::Vec, Bump};
 use roc_wasm_module::{
     opcodes::OpCode, Export, ExportType, SerialBuffer, Serialize, Signature, Value, ValueType,
@@ -51,6 +52,26 @@ pub fn const_value(buf: &mut Vec<'_, u8>, value: Value) {
 }
 
 pub fn test_op_example<A>(op: OpCode, args: A, expected: Value)
+where
+    A: IntoIterator<Item = Value>,
+{
+    let return_val = run_op_example(op, args, ValueType::from(expected))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(return_val, expected);
+}
+
+pub fn test_op_trap<A>(op: OpCode, args: A, ret_type: ValueType, expected: TrapKind)
+where
+    A: IntoIterator<Item = Value>,
+{
+    let trap = run_op_example(op, args, ret_type).unwrap_err();
+
+    assert_eq!(trap.kind, expected);
+}
+
+fn run_op_example<A>(op: OpCode, args: A, ret_type: ValueType) -> Result<Option<Value>, Trap>
 where
     A: IntoIterator<Item = Value>,
 {
@@ -74,7 +95,7 @@ where
         module.code.function_offsets.push(0);
         module.add_function_signature(Signature {
             param_types: Vec::new_in(&arena),
-            ret_type: Some(ValueType::from(expected)),
+            ret_type: Some(ret_type),
         });
         module.export.append(Export {
             name: "test",
@@ -95,9 +116,7 @@ where
     let mut inst =
         Instance::for_module(&arena, &module, DefaultImportDispatcher::default(), true).unwrap();
 
-    let return_val = inst.call_export("test", []).unwrap().unwrap();
-
-    assert_eq!(return_val, expected);
+    inst.call_export("test", [])
 }
 
 pub fn create_exported_function_no_locals<'a, F>(
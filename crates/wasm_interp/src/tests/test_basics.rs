@@ -5,7 +5,7 @@ use crate::tests::{
     const_value, create_exported_function_no_locals, create_exported_function_with_locals,
     default_state,
 };
-use crate::{DefaultImportDispatcher, ImportDispatcher, Instance};
+use crate::{DefaultImportDispatcher, ImportDispatcher, Instance, Trap, TrapKind, MAX_CALL_DEPTH};
 use bumpalo::{collections::Vec, Bump};
 use roc_wasm_module::sections::{Import, ImportDesc};
 use roc_wasm_module::{
@@ -702,29 +702,29 @@ fn test_call_return_with_args() {
 
 #[test]
 fn test_call_indirect_ok() {
-    let result = test_call_indirect_help(0, 0);
+    let result = test_call_indirect_help(0, 0).unwrap().unwrap();
     assert_eq!(result, Value::I32(111));
 }
 
 #[test]
-#[should_panic(expected = "Expected signature")]
 fn test_call_indirect_wrong_signature() {
-    test_call_indirect_help(0, 1);
+    let trap = test_call_indirect_help(0, 1).unwrap_err();
+    assert_eq!(trap.kind, TrapKind::IndirectCallTypeMismatch);
 }
 
 #[test]
-#[should_panic(expected = "element index")]
 fn test_call_indirect_index_out_of_bounds() {
-    test_call_indirect_help(0, 2);
+    let trap = test_call_indirect_help(0, 2).unwrap_err();
+    assert_eq!(trap.kind, TrapKind::UndefinedElement);
 }
 
 #[test]
 #[should_panic(expected = "Table index")]
 fn test_call_indirect_unsupported_table() {
-    test_call_indirect_help(1, 0);
+    test_call_indirect_help(1, 0).ok();
 }
 
-fn test_call_indirect_help(table_index: u32, elem_index: u32) -> Value {
+fn test_call_indirect_help(table_index: u32, elem_index: u32) -> Result<Option<Value>, Trap> {
     let arena = Bump::new();
     let mut module = WasmModule::new(&arena);
 
@@ -783,7 +783,97 @@ fn test_call_indirect_help(table_index: u32, elem_index: u32) -> Value {
         is_debug_mode,
     )
     .unwrap();
-    inst.call_export(start_fn_name, []).unwrap().unwrap()
+    inst.call_export(start_fn_name, [])
+}
+
+#[test]
+fn test_trap_call_stack() {
+    let arena = Bump::new();
+    let mut module = WasmModule::new(&arena);
+
+    let signature = || Signature {
+        param_types: bumpalo::vec![in &arena],
+        ret_type: None,
+    };
+
+    // function 0: call function 1
+    create_exported_function_no_locals(&mut module, "outer", signature(), |buf| {
+        buf.append_u8(OpCode::CALL as u8);
+        buf.encode_u32(1);
+        buf.append_u8(OpCode::END as u8);
+    });
+
+    // function 1: crash
+    create_exported_function_no_locals(&mut module, "inner", signature(), |buf| {
+        buf.append_u8(OpCode::UNREACHABLE as u8);
+        buf.append_u8(OpCode::END as u8);
+    });
+
+    module.names.append_function(0, "outer");
+    module.names.append_function(1, "inner");
+
+    let mut inst =
+        Instance::for_module(&arena, &module, DefaultImportDispatcher::default(), false).unwrap();
+    let trap = inst.call_export("outer", []).unwrap_err();
+
+    assert_eq!(trap.kind, TrapKind::Unreachable);
+    let names: std::vec::Vec<_> = trap
+        .call_stack
+        .iter()
+        .map(|frame| (frame.fn_index, frame.fn_name.as_deref()))
+        .collect();
+    assert_eq!(names, [(1, Some("inner")), (0, Some("outer"))]);
+    assert!(trap.to_string().contains("at func[1] inner"));
+}
+
+#[test]
+fn test_trap_stack_overflow() {
+    let arena = Bump::new();
+    let mut module = WasmModule::new(&arena);
+
+    let signature = Signature {
+        param_types: bumpalo::vec![in &arena],
+        ret_type: None,
+    };
+    create_exported_function_no_locals(&mut module, "recurse", signature, |buf| {
+        buf.append_u8(OpCode::CALL as u8);
+        buf.encode_u32(0);
+        buf.append_u8(OpCode::END as u8);
+    });
+
+    let mut inst =
+        Instance::for_module(&arena, &module, DefaultImportDispatcher::default(), false).unwrap();
+    let trap = inst.call_export("recurse", []).unwrap_err();
+
+    assert_eq!(trap.kind, TrapKind::StackOverflow);
+    assert_eq!(trap.call_stack.len(), MAX_CALL_DEPTH + 1);
+}
+
+#[test]
+fn test_trap_fuel_exhausted() {
+    let arena = Bump::new();
+    let mut module = WasmModule::new(&arena);
+
+    let signature = Signature {
+        param_types: bumpalo::vec![in &arena],
+        ret_type: None,
+    };
+    create_exported_function_no_locals(&mut module, "loop_forever", signature, |buf| {
+        buf.append_u8(OpCode::LOOP as u8);
+        buf.append_u8(ValueType::VOID);
+        buf.append_u8(OpCode::BR as u8);
+        buf.encode_u32(0);
+        buf.append_u8(OpCode::END as u8);
+        buf.append_u8(OpCode::END as u8);
+    });
+
+    let mut inst =
+        Instance::for_module(&arena, &module, DefaultImportDispatcher::default(), false).unwrap();
+    inst.fuel = Some(1000);
+    let trap = inst.call_export("loop_forever", []).unwrap_err();
+
+    assert_eq!(trap.kind, TrapKind::FuelExhausted);
+    assert_eq!(inst.fuel, Some(0));
 }
 
 // #[test]
@@ -1,7 +1,8 @@
 #![cfg(test)]
 
-use super::test_op_example;
-use roc_wasm_module::{opcodes::OpCode::*, Value};
+use super::{test_op_example, test_op_trap};
+use crate::TrapKind;
+use roc_wasm_module::{opcodes::OpCode::*, Value, ValueType};
 
 #[test]
 fn test_i32wrapi64() {
@@ -18,15 +19,41 @@ fn test_i32truncsf32() {
 }
 
 #[test]
-#[should_panic(expected = "Cannot truncate")]
 fn test_i32truncsf32_oob() {
-    test_op_example(
+    test_op_trap(
         I32TRUNCSF32,
         [Value::F32(i32::MAX as f32 * 2.0)],
+        ValueType::I32,
+        TrapKind::InvalidConversion,
+    );
+}
+
+#[test]
+fn test_i32truncsf32_boundary() {
+    // i32::MAX rounds up to 2^31 as an f32, so it's just out of range
+    test_op_trap(
+        I32TRUNCSF32,
+        [Value::F32(i32::MAX as f32)],
+        ValueType::I32,
+        TrapKind::InvalidConversion,
+    );
+    test_op_example(
+        I32TRUNCSF32,
+        [Value::F32(i32::MIN as f32)],
         Value::I32(i32::MIN),
     );
 }
 
+#[test]
+fn test_i32truncsf32_nan() {
+    test_op_trap(
+        I32TRUNCSF32,
+        [Value::F32(f32::NAN)],
+        ValueType::I32,
+        TrapKind::InvalidConversion,
+    );
+}
+
 #[test]
 fn test_i32truncuf32() {
     test_op_example(
@@ -37,12 +64,12 @@ fn test_i32truncuf32() {
 }
 
 #[test]
-#[should_panic(expected = "Cannot truncate")]
 fn test_i32truncuf32_oob() {
-    test_op_example(
+    test_op_trap(
         I32TRUNCUF32,
         [Value::F32(u32::MAX as f32 * 2.0)],
-        Value::I32(0),
+        ValueType::I32,
+        TrapKind::InvalidConversion,
     );
 }
 
@@ -52,12 +79,12 @@ fn test_i32truncsf64() {
 }
 
 #[test]
-#[should_panic(expected = "Cannot truncate")]
 fn test_i32truncsf64_oob() {
-    test_op_example(
+    test_op_trap(
         I32TRUNCSF64,
         [Value::F64(i32::MAX as f64 * 2.0)],
-        Value::I32(i32::MIN),
+        ValueType::I32,
+        TrapKind::InvalidConversion,
     );
 }
 
@@ -71,12 +98,12 @@ fn test_i32truncuf64() {
 }
 
 #[test]
-#[should_panic(expected = "Cannot truncate")]
 fn test_i32truncuf64_oob() {
-    test_op_example(
+    test_op_trap(
         I32TRUNCUF64,
         [Value::F64(u32::MAX as f64 * 2.0)],
-        Value::I32(0),
+        ValueType::I32,
+        TrapKind::InvalidConversion,
     );
 }
 
@@ -96,12 +123,12 @@ fn test_i64truncsf32() {
 }
 
 #[test]
-#[should_panic(expected = "Cannot truncate")]
 fn test_i64truncsf32_oob() {
-    test_op_example(
+    test_op_trap(
         I64TRUNCSF32,
         [Value::F32(i64::MAX as f32 * 2.0)],
-        Value::I64(i64::MIN),
+        ValueType::I64,
+        TrapKind::InvalidConversion,
     );
 }
 
@@ -115,12 +142,12 @@ fn test_i64truncuf32() {
 }
 
 #[test]
-#[should_panic(expected = "Cannot truncate")]
 fn test_i64truncuf32_oob() {
-    test_op_example(
+    test_op_trap(
         I64TRUNCUF32,
         [Value::F32(u64::MAX as f32 * 2.0)],
-        Value::I64(0),
+        ValueType::I64,
+        TrapKind::InvalidConversion,
     );
 }
 
@@ -130,12 +157,12 @@ fn test_i64truncsf64() {
 }
 
 #[test]
-#[should_panic(expected = "Cannot truncate")]
 fn test_i64truncsf64_oob() {
-    test_op_example(
+    test_op_trap(
         I64TRUNCSF64,
         [Value::F64(i64::MAX as f64 * 2.0)],
-        Value::I64(i64::MIN),
+        ValueType::I64,
+        TrapKind::InvalidConversion,
     );
 }
 
@@ -149,12 +176,22 @@ fn test_i64truncuf64() {
 }
 
 #[test]
-#[should_panic(expected = "Cannot truncate")]
+fn test_i64truncuf64_nan() {
+    test_op_trap(
+        I64TRUNCUF64,
+        [Value::F64(f64::NAN)],
+        ValueType::I64,
+        TrapKind::InvalidConversion,
+    );
+}
+
+#[test]
 fn test_i64truncuf64_oob() {
-    test_op_example(
-        I32TRUNCUF64,
+    test_op_trap(
+        I64TRUNCUF64,
         [Value::F64(u64::MAX as f64 * 2.0)],
-        Value::I32(0),
+        ValueType::I64,
+        TrapKind::InvalidConversion,
     );
 }
 
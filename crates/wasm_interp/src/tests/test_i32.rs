@@ -1,7 +1,8 @@
 #![cfg(test)]
 
-use super::test_op_example;
-use roc_wasm_module::{opcodes::OpCode, opcodes::OpCode::*, Value};
+use super::{test_op_example, test_op_trap};
+use crate::TrapKind;
+use roc_wasm_module::{opcodes::OpCode, opcodes::OpCode::*, Value, ValueType};
 
 fn test_i32_binop(op: OpCode, arg1: i32, arg2: i32, expected: i32) {
     test_op_example(
@@ -11,6 +12,15 @@ fn test_i32_binop(op: OpCode, arg1: i32, arg2: i32, expected: i32) {
     )
 }
 
+fn test_i32_binop_trap(op: OpCode, arg1: i32, arg2: i32, expected: TrapKind) {
+    test_op_trap(
+        op,
+        [Value::from(arg1), Value::from(arg2)],
+        ValueType::I32,
+        expected,
+    )
+}
+
 fn test_u32_binop(op: OpCode, arg1: u32, arg2: u32, expected: u32) {
     test_op_example(
         op,
@@ -190,13 +200,16 @@ fn test_i32divs() {
     let op = I32DIVS;
     test_i32_binop(op, -1, -1, 1);
     test_i32_binop(op, 6, 3, 2);
-    test_i32_binop(op, i32::MIN, -1, i32::MIN);
 }
 
 #[test]
-#[should_panic(expected = "divide by zero")]
+fn test_i32divs_overflow() {
+    test_i32_binop_trap(I32DIVS, i32::MIN, -1, TrapKind::IntegerOverflow);
+}
+
+#[test]
 fn test_i32divs_zero() {
-    test_i32_binop(I32DIVS, 1, 0, -1);
+    test_i32_binop_trap(I32DIVS, 1, 0, TrapKind::DivisionByZero);
 }
 
 #[test]
@@ -207,9 +220,8 @@ fn test_i32divu() {
 }
 
 #[test]
-#[should_panic(expected = "divide by zero")]
 fn test_i32divu_zero() {
-    test_i32_binop(I32DIVU, 1, 0, -1);
+    test_i32_binop_trap(I32DIVU, 1, 0, TrapKind::DivisionByZero);
 }
 
 #[test]
@@ -222,9 +234,8 @@ fn test_i32rems() {
 }
 
 #[test]
-#[should_panic(expected = "divisor of zero")]
 fn test_i32rems_zero() {
-    test_i32_binop(I32REMS, 1, 0, -1);
+    test_i32_binop_trap(I32REMS, 1, 0, TrapKind::DivisionByZero);
 }
 
 #[test]
@@ -234,9 +245,8 @@ fn test_i32remu() {
 }
 
 #[test]
-#[should_panic(expected = "divisor of zero")]
 fn test_i32remu_zero() {
-    test_i32_binop(I32REMU, 1, 0, -1);
+    test_i32_binop_trap(I32REMU, 1, 0, TrapKind::DivisionByZero);
 }
 
 #[test]
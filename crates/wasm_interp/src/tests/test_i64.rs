@@ -1,7 +1,8 @@
 #![cfg(test)]
 
-use super::test_op_example;
-use roc_wasm_module::{opcodes::OpCode, opcodes::OpCode::*, Value};
+use super::{test_op_example, test_op_trap};
+use crate::TrapKind;
+use roc_wasm_module::{opcodes::OpCode, opcodes::OpCode::*, Value, ValueType};
 
 fn test_i64_comparison(op: OpCode, arg1: i64, arg2: i64, expected: bool) {
     test_op_example(
@@ -27,6 +28,15 @@ fn test_i64_binop(op: OpCode, arg1: i64, arg2: i64, expected: i64) {
     )
 }
 
+fn test_i64_binop_trap(op: OpCode, arg1: i64, arg2: i64, expected: TrapKind) {
+    test_op_trap(
+        op,
+        [Value::from(arg1), Value::from(arg2)],
+        ValueType::I64,
+        expected,
+    )
+}
+
 fn test_u64_binop(op: OpCode, arg1: u64, arg2: u64, expected: u64) {
     test_op_example(
         op,
@@ -206,13 +216,16 @@ fn test_i64divs() {
     let op = I64DIVS;
     test_i64_binop(op, -1, -1, 1);
     test_i64_binop(op, 6, 3, 2);
-    test_i64_binop(op, i64::MIN, -1, i64::MIN);
 }
 
 #[test]
-#[should_panic(expected = "divide by zero")]
+fn test_i64divs_overflow() {
+    test_i64_binop_trap(I64DIVS, i64::MIN, -1, TrapKind::IntegerOverflow);
+}
+
+#[test]
 fn test_i64divs_zero() {
-    test_i64_binop(I64DIVS, 1, 0, -1);
+    test_i64_binop_trap(I64DIVS, 1, 0, TrapKind::DivisionByZero);
 }
 
 #[test]
@@ -223,9 +236,8 @@ fn test_i64divu() {
 }
 
 #[test]
-#[should_panic(expected = "divide by zero")]
 fn test_i64divu_zero() {
-    test_i64_binop(I64DIVU, 1, 0, -1);
+    test_i64_binop_trap(I64DIVU, 1, 0, TrapKind::DivisionByZero);
 }
 
 #[test]
@@ -238,9 +250,8 @@ fn test_i64rems() {
 }
 
 #[test]
-#[should_panic(expected = "divisor of zero")]
 fn test_i64rems_zero() {
-    test_i64_binop(I64REMS, 1, 0, -1);
+    test_i64_binop_trap(I64REMS, 1, 0, TrapKind::DivisionByZero);
 }
 
 #[test]
@@ -250,9 +261,8 @@ fn test_i64remu() {
 }
 
 #[test]
-#[should_panic(expected = "divisor of zero")]
 fn test_i64remu_zero() {
-    test_i64_binop(I64REMU, 1, 0, -1);
+    test_i64_binop_trap(I64REMU, 1, 0, TrapKind::DivisionByZero);
 }
 
 #[test]
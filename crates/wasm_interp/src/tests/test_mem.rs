@@ -1,5 +1,5 @@
 use super::create_exported_function_no_locals;
-use crate::{DefaultImportDispatcher, Instance};
+use crate::{DefaultImportDispatcher, Error, Instance, TrapKind};
 use bumpalo::{collections::Vec, Bump};
 use roc_wasm_module::{
     opcodes::OpCode,
@@ -49,6 +49,62 @@ fn test_growmemory() {
     assert_eq!(state.memory.len(), 5 * MemorySection::PAGE_SIZE as usize);
 }
 
+#[test]
+fn test_load_out_of_bounds() {
+    let arena = Bump::new();
+    let mut module = WasmModule::new(&arena);
+
+    let pages = 1;
+    let pc = 0;
+    let addr = MemorySection::PAGE_SIZE - 2;
+    module.memory = MemorySection::new(&arena, pages * MemorySection::PAGE_SIZE);
+    module.code.bytes.push(OpCode::I32CONST as u8);
+    module.code.bytes.encode_u32(addr);
+    module.code.bytes.push(OpCode::I32LOAD as u8);
+    module.code.bytes.encode_u32(0); // align
+    module.code.bytes.encode_u32(0); // offset
+
+    let mut state = Instance::new(&arena, pages, pc, [], DefaultImportDispatcher::default());
+    state.execute_next_instruction(&module).unwrap();
+    let result = state.execute_next_instruction(&module);
+    assert_eq!(
+        result.unwrap_err(),
+        Error::Trap(TrapKind::MemoryOutOfBounds {
+            addr: addr as u64,
+            size: 4
+        })
+    );
+}
+
+#[test]
+fn test_store_address_overflow() {
+    let arena = Bump::new();
+    let mut module = WasmModule::new(&arena);
+
+    let pages = 1;
+    let pc = 0;
+    module.memory = MemorySection::new(&arena, pages * MemorySection::PAGE_SIZE);
+    module.code.bytes.push(OpCode::I32CONST as u8);
+    module.code.bytes.encode_i32(-1);
+    module.code.bytes.push(OpCode::I32CONST as u8);
+    module.code.bytes.encode_i32(123);
+    module.code.bytes.push(OpCode::I32STORE8 as u8);
+    module.code.bytes.encode_u32(0); // align
+    module.code.bytes.encode_u32(8); // offset
+
+    let mut state = Instance::new(&arena, pages, pc, [], DefaultImportDispatcher::default());
+    state.execute_next_instruction(&module).unwrap();
+    state.execute_next_instruction(&module).unwrap();
+    let result = state.execute_next_instruction(&module);
+    assert_eq!(
+        result.unwrap_err(),
+        Error::Trap(TrapKind::MemoryOutOfBounds {
+            addr: u32::MAX as u64 + 8,
+            size: 1
+        })
+    );
+}
+
 fn test_load(load_op: OpCode, ty: ValueType, data: &[u8], addr: u32, offset: u32) -> Value {
     let arena = Bump::new();
     let mut module = WasmModule::new(&arena);
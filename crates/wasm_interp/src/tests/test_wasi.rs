@@ -0,0 +1,330 @@
+use crate::wasi::{Errno, WasiDispatcher, WasiFile};
+use roc_wasm_module::Value;
+use std::fs;
+
+const PATH_ADDR: usize = 0x100;
+const OUT_ADDR: usize = 0x200;
+const IOV_ADDR: usize = 0x300;
+const DATA_ADDR: usize = 0x400;
+
+fn call(wasi: &mut WasiDispatcher, memory: &mut [u8], name: &str, args: &[Value]) -> i32 {
+    wasi.dispatch(name, args, memory)
+        .unwrap()
+        .expect_i32()
+        .unwrap()
+}
+
+fn i32_args(args: &[usize]) -> Vec<Value> {
+    args.iter().map(|arg| Value::I32(*arg as i32)).collect()
+}
+
+fn read_u32(memory: &[u8], addr: usize) -> u32 {
+    u32::from_le_bytes(memory[addr..][..4].try_into().unwrap())
+}
+
+fn read_u64(memory: &[u8], addr: usize) -> u64 {
+    u64::from_le_bytes(memory[addr..][..8].try_into().unwrap())
+}
+
+fn write_path(memory: &mut [u8], path: &str) -> usize {
+    memory[PATH_ADDR..][..path.len()].copy_from_slice(path.as_bytes());
+    path.len()
+}
+
+/// Write a single iovec pointing at `len` bytes of DATA_ADDR
+fn write_iov(memory: &mut [u8], len: usize) {
+    memory[IOV_ADDR..][..4].copy_from_slice(&(DATA_ADDR as u32).to_le_bytes());
+    memory[IOV_ADDR + 4..][..4].copy_from_slice(&(len as u32).to_le_bytes());
+}
+
+fn path_open(
+    wasi: &mut WasiDispatcher,
+    memory: &mut [u8],
+    dir_fd: usize,
+    path: &str,
+    oflags: i32,
+    rights: i64,
+) -> Result<usize, i32> {
+    let path_len = write_path(memory, path);
+    let args = [
+        Value::I32(dir_fd as i32),
+        Value::I32(0),
+        Value::I32(PATH_ADDR as i32),
+        Value::I32(path_len as i32),
+        Value::I32(oflags),
+        Value::I64(rights),
+        Value::I64(0),
+        Value::I32(0),
+        Value::I32(OUT_ADDR as i32),
+    ];
+    match call(wasi, memory, "path_open", &args) {
+        0 => Ok(read_u32(memory, OUT_ADDR) as usize),
+        errno => Err(errno),
+    }
+}
+
+#[test]
+fn test_environ() {
+    let mut wasi = WasiDispatcher::default();
+    wasi.env = vec![("HOME".into(), "/home/roc".into()), ("X".into(), "".into())];
+    let mut memory = vec![0; 0x1000];
+
+    let errno = call(
+        &mut wasi,
+        &mut memory,
+        "environ_sizes_get",
+        &i32_args(&[0, 4]),
+    );
+    assert_eq!(errno, Errno::Success as i32);
+    assert_eq!(read_u32(&memory, 0), 2);
+    assert_eq!(read_u32(&memory, 4), 18);
+
+    let errno = call(
+        &mut wasi,
+        &mut memory,
+        "environ_get",
+        &i32_args(&[OUT_ADDR, DATA_ADDR]),
+    );
+    assert_eq!(errno, Errno::Success as i32);
+    assert_eq!(read_u32(&memory, OUT_ADDR) as usize, DATA_ADDR);
+    assert_eq!(read_u32(&memory, OUT_ADDR + 4) as usize, DATA_ADDR + 15);
+    assert_eq!(&memory[DATA_ADDR..][..18], b"HOME=/home/roc\0X=\0");
+}
+
+#[test]
+fn test_prestat() {
+    let mut wasi = WasiDispatcher::default();
+    wasi.preopen_dir("/data", "/tmp");
+    let mut memory = vec![0; 0x1000];
+
+    let errno = call(
+        &mut wasi,
+        &mut memory,
+        "fd_prestat_get",
+        &i32_args(&[3, OUT_ADDR]),
+    );
+    assert_eq!(errno, Errno::Success as i32);
+    assert_eq!(read_u32(&memory, OUT_ADDR + 4), 5);
+
+    let errno = call(
+        &mut wasi,
+        &mut memory,
+        "fd_prestat_dir_name",
+        &i32_args(&[3, PATH_ADDR, 5]),
+    );
+    assert_eq!(errno, Errno::Success as i32);
+    assert_eq!(&memory[PATH_ADDR..][..5], b"/data");
+
+    // WASI libc stops looking for preopens at the first Badf
+    let errno = call(
+        &mut wasi,
+        &mut memory,
+        "fd_prestat_get",
+        &i32_args(&[4, OUT_ADDR]),
+    );
+    assert_eq!(errno, Errno::Badf as i32);
+}
+
+#[test]
+fn test_write_seek_read_close() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut wasi = WasiDispatcher::default();
+    wasi.preopen_dir(".", dir.path());
+    let mut memory = vec![0; 0x1000];
+
+    let oflags_creat = 1;
+    let rights_read_write = (1 << 1) | (1 << 6);
+    let fd = path_open(
+        &mut wasi,
+        &mut memory,
+        3,
+        "hello.txt",
+        oflags_creat,
+        rights_read_write,
+    )
+    .unwrap();
+    assert_eq!(fd, 4);
+
+    memory[DATA_ADDR..][..5].copy_from_slice(b"hello");
+    write_iov(&mut memory, 5);
+    let errno = call(
+        &mut wasi,
+        &mut memory,
+        "fd_write",
+        &i32_args(&[fd, IOV_ADDR, 1, OUT_ADDR]),
+    );
+    assert_eq!(errno, Errno::Success as i32);
+    assert_eq!(read_u32(&memory, OUT_ADDR), 5);
+
+    let seek_args = [
+        Value::I32(fd as i32),
+        Value::I64(1),
+        Value::I32(0),
+        Value::I32(OUT_ADDR as i32),
+    ];
+    let errno = call(&mut wasi, &mut memory, "fd_seek", &seek_args);
+    assert_eq!(errno, Errno::Success as i32);
+    assert_eq!(read_u64(&memory, OUT_ADDR), 1);
+
+    memory[DATA_ADDR..][..5].fill(0);
+    write_iov(&mut memory, 5);
+    let errno = call(
+        &mut wasi,
+        &mut memory,
+        "fd_read",
+        &i32_args(&[fd, IOV_ADDR, 1, OUT_ADDR]),
+    );
+    assert_eq!(errno, Errno::Success as i32);
+    assert_eq!(read_u32(&memory, OUT_ADDR), 4);
+    assert_eq!(&memory[DATA_ADDR..][..4], b"ello");
+
+    let errno = call(
+        &mut wasi,
+        &mut memory,
+        "fd_tell",
+        &i32_args(&[fd, OUT_ADDR]),
+    );
+    assert_eq!(errno, Errno::Success as i32);
+    assert_eq!(read_u64(&memory, OUT_ADDR), 5);
+
+    assert_eq!(
+        call(&mut wasi, &mut memory, "fd_close", &i32_args(&[fd])),
+        0
+    );
+    assert_eq!(
+        call(&mut wasi, &mut memory, "fd_close", &i32_args(&[fd])),
+        Errno::Badf as i32
+    );
+    assert!(matches!(wasi.files[fd], WasiFile::Closed));
+
+    assert_eq!(fs::read(dir.path().join("hello.txt")).unwrap(), b"hello");
+}
+
+#[test]
+fn test_in_memory_reads_advance() {
+    let mut wasi = WasiDispatcher::default();
+    wasi.files[0] = WasiFile::ReadOnly(b"abc".to_vec());
+    let mut memory = vec![0; 0x1000];
+
+    write_iov(&mut memory, 2);
+    let args = i32_args(&[0, IOV_ADDR, 1, OUT_ADDR]);
+
+    assert_eq!(call(&mut wasi, &mut memory, "fd_read", &args), 0);
+    assert_eq!(
+        &memory[DATA_ADDR..][..read_u32(&memory, OUT_ADDR) as usize],
+        b"ab"
+    );
+
+    assert_eq!(call(&mut wasi, &mut memory, "fd_read", &args), 0);
+    assert_eq!(
+        &memory[DATA_ADDR..][..read_u32(&memory, OUT_ADDR) as usize],
+        b"c"
+    );
+
+    assert_eq!(call(&mut wasi, &mut memory, "fd_read", &args), 0);
+    assert_eq!(read_u32(&memory, OUT_ADDR), 0);
+}
+
+#[test]
+fn test_directories() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("b.txt"), "").unwrap();
+    let mut wasi = WasiDispatcher::default();
+    wasi.preopen_dir(".", dir.path());
+    let mut memory = vec![0; 0x1000];
+
+    let path_len = write_path(&mut memory, "a");
+    let args = i32_args(&[3, PATH_ADDR, path_len]);
+    assert_eq!(
+        call(&mut wasi, &mut memory, "path_create_directory", &args),
+        0
+    );
+    assert!(dir.path().join("a").is_dir());
+
+    let readdir_args = [
+        Value::I32(3),
+        Value::I32(DATA_ADDR as i32),
+        Value::I32(0x400),
+        Value::I64(0),
+        Value::I32(OUT_ADDR as i32),
+    ];
+    assert_eq!(call(&mut wasi, &mut memory, "fd_readdir", &readdir_args), 0);
+
+    let buf_used = read_u32(&memory, OUT_ADDR) as usize;
+    let mut names = Vec::new();
+    let mut offset = 0;
+    while offset < buf_used {
+        let dirent = DATA_ADDR + offset;
+        let name_len = read_u32(&memory, dirent + 16) as usize;
+        let name = &memory[dirent + 24..][..name_len];
+        names.push(String::from_utf8(name.to_vec()).unwrap());
+        offset += 24 + name_len;
+    }
+    assert_eq!(names, [".", "..", "a", "b.txt"]);
+
+    assert_eq!(
+        call(&mut wasi, &mut memory, "path_remove_directory", &args),
+        0
+    );
+    assert!(!dir.path().join("a").exists());
+
+    let path_len = write_path(&mut memory, "b.txt");
+    let args = i32_args(&[3, PATH_ADDR, path_len]);
+    assert_eq!(call(&mut wasi, &mut memory, "path_unlink_file", &args), 0);
+    assert!(!dir.path().join("b.txt").exists());
+}
+
+#[test]
+fn test_sandbox() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir(dir.path().join("inner")).unwrap();
+    let mut wasi = WasiDispatcher::default();
+    wasi.preopen_dir(".", dir.path().join("inner"));
+    let mut memory = vec![0; 0x1000];
+
+    let rights_read = 1 << 1;
+    for path in ["../secret.txt", "/etc/passwd", "x/../../secret.txt"] {
+        assert_eq!(
+            path_open(&mut wasi, &mut memory, 3, path, 0, rights_read),
+            Err(Errno::Notcapable as i32),
+            "{}",
+            path
+        );
+    }
+
+    // Going up is fine, as long as we stay inside the preopened directory
+    fs::create_dir(dir.path().join("inner/x")).unwrap();
+    fs::write(dir.path().join("inner/y.txt"), "y").unwrap();
+    assert!(path_open(&mut wasi, &mut memory, 3, "x/../y.txt", 0, rights_read).is_ok());
+
+    // Directories opened inside the sandbox are still limited to it
+    let x_fd = path_open(&mut wasi, &mut memory, 3, "x", 2, rights_read).unwrap();
+    assert!(path_open(&mut wasi, &mut memory, x_fd, "../y.txt", 0, rights_read).is_ok());
+    assert_eq!(
+        path_open(
+            &mut wasi,
+            &mut memory,
+            x_fd,
+            "../../secret.txt",
+            0,
+            rights_read
+        ),
+        Err(Errno::Notcapable as i32)
+    );
+
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(dir.path(), dir.path().join("inner/escape")).unwrap();
+        assert_eq!(
+            path_open(
+                &mut wasi,
+                &mut memory,
+                3,
+                "escape/secret.txt",
+                0,
+                rights_read
+            ),
+            Err(Errno::Notcapable as i32)
+        );
+    }
+}
@@ -1,14 +1,25 @@
 use rand::prelude::*;
 use roc_wasm_module::Value;
-use std::io::{self, Read, StderrLock, StdoutLock, Write};
+use std::collections::HashMap;
+use std::fs::{self, File, FileType, Metadata, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, StderrLock, StdoutLock, Write};
+use std::path::{Component, Path, PathBuf};
 use std::process::exit;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 pub const MODULE_NAME: &str = "wasi_snapshot_preview1";
 
 pub struct WasiDispatcher<'a> {
     pub args: &'a [&'a [u8]],
+    /// Environment variables visible to the app, as (name, value) pairs
+    pub env: Vec<(String, String)>,
     pub rng: ThreadRng,
+    /// The file descriptor table. Each file's index is its file descriptor.
     pub files: Vec<WasiFile>,
+    /// Read positions of in-memory files, by file descriptor. Host files track their own position.
+    cursors: HashMap<usize, u64>,
+    /// The zero point of the monotonic clock
+    start_time: Instant,
 }
 
 impl Default for WasiDispatcher<'_> {
@@ -22,14 +33,72 @@ pub enum WasiFile {
     WriteOnly(Vec<u8>),
     ReadWrite(Vec<u8>),
     HostSystemFile,
+    /// A host file, opened by the app with `path_open`
+    HostFile(File),
+    /// A host directory that the app has been given access to, and the path the app knows it by.
+    /// The app can only reach host files through preopened directories, and can't escape them.
+    PreopenedDir {
+        guest_path: String,
+        host_path: PathBuf,
+    },
+    /// A host directory opened by the app with `path_open`, somewhere inside `sandbox_root`
+    /// (the host path of the preopened directory it was reached through)
+    Dir {
+        host_path: PathBuf,
+        sandbox_root: PathBuf,
+    },
+    /// A file descriptor that has been closed. Its slot gets reused by the next `path_open`.
+    Closed,
 }
 
 enum WriteLock<'a> {
     StdOut(StdoutLock<'a>),
     Stderr(StderrLock<'a>),
     RegularFile(&'a mut Vec<u8>),
+    HostFile(&'a mut File),
 }
 
+// filetype
+const FILETYPE_UNKNOWN: u8 = 0;
+const FILETYPE_CHARACTER_DEVICE: u8 = 2;
+const FILETYPE_DIRECTORY: u8 = 3;
+const FILETYPE_REGULAR_FILE: u8 = 4;
+const FILETYPE_SYMBOLIC_LINK: u8 = 7;
+
+// oflags
+const OFLAGS_CREAT: u16 = 1 << 0;
+const OFLAGS_DIRECTORY: u16 = 1 << 1;
+const OFLAGS_EXCL: u16 = 1 << 2;
+const OFLAGS_TRUNC: u16 = 1 << 3;
+
+// fdflags
+const FDFLAGS_APPEND: u16 = 1 << 0;
+
+// rights
+const RIGHTS_FD_READ: u64 = 1 << 1;
+const RIGHTS_FD_WRITE: u64 = 1 << 6;
+/// Every right defined in preview1. Access control happens at the level of preopened directories.
+const RIGHTS_ALL: u64 = (1 << 29) - 1;
+
+// lookupflags
+const LOOKUPFLAGS_SYMLINK_FOLLOW: u32 = 1 << 0;
+
+// clockid
+const CLOCK_REALTIME: u32 = 0;
+const CLOCK_MONOTONIC: u32 = 1;
+const CLOCK_PROCESS_CPUTIME_ID: u32 = 2;
+const CLOCK_THREAD_CPUTIME_ID: u32 = 3;
+
+// poll_oneoff
+const EVENTTYPE_CLOCK: u8 = 0;
+const SUBCLOCKFLAGS_ABSTIME: u16 = 1 << 0;
+const SUBSCRIPTION_SIZE: usize = 48;
+const EVENT_SIZE: usize = 32;
+
+const FDSTAT_SIZE: usize = 24;
+const FILESTAT_SIZE: usize = 64;
+const DIRENT_SIZE: usize = 24;
+
 /// Implementation of WASI syscalls
 /// References for other engines:
 /// https://github.com/wasmerio/wasmer/blob/ef8d2f651ed29b4b06fdc2070eb8189922c54d82/lib/wasi/src/syscalls/mod.rs
@@ -38,15 +107,27 @@ impl<'a> WasiDispatcher<'a> {
     pub fn new(args: &'a [&'a [u8]]) -> Self {
         WasiDispatcher {
             args,
+            env: Vec::new(),
             rng: thread_rng(),
             files: vec![
                 WasiFile::HostSystemFile,
                 WasiFile::HostSystemFile,
                 WasiFile::HostSystemFile,
             ],
+            cursors: HashMap::new(),
+            start_time: Instant::now(),
         }
     }
 
+    /// Give the app access to a host directory, which it will see as `guest_path`.
+    /// WASI libc looks for preopened directories on startup, so do this before running the app.
+    pub fn preopen_dir(&mut self, guest_path: impl Into<String>, host_path: impl Into<PathBuf>) {
+        self.insert_file(WasiFile::PreopenedDir {
+            guest_path: guest_path.into(),
+            host_path: host_path.into(),
+        });
+    }
+
     pub fn dispatch(
         &mut self,
         function_name: &str,
@@ -88,15 +169,90 @@ impl<'a> WasiDispatcher<'a> {
 
                 success_code
             }
-            "environ_get" => todo!("WASI {}({:?})", function_name, arguments),
-            "environ_sizes_get" => todo!("WASI {}({:?})", function_name, arguments),
-            "clock_res_get" => success_code, // this dummy implementation seems to be good enough for some functions
-            "clock_time_get" => success_code,
-            "fd_advise" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_allocate" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_close" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_datasync" => todo!("WASI {}({:?})", function_name, arguments),
+            "environ_get" => {
+                // uint8_t ** environ
+                let mut ptr_ptr_environ = arguments[0].expect_i32().unwrap() as usize;
+                // uint8_t * environ_buf, where each variable is written as "NAME=value"
+                let mut ptr_environ_buf = arguments[1].expect_i32().unwrap() as usize;
+
+                for (name, value) in self.env.iter() {
+                    write_u32(memory, ptr_ptr_environ, ptr_environ_buf as u32);
+                    for bytes in [name.as_bytes(), "=".as_bytes(), value.as_bytes()] {
+                        memory[ptr_environ_buf..][..bytes.len()].copy_from_slice(bytes);
+                        ptr_environ_buf += bytes.len();
+                    }
+                    memory[ptr_environ_buf] = 0; // C string zero termination
+                    ptr_environ_buf += 1;
+                    ptr_ptr_environ += 4;
+                }
+
+                success_code
+            }
+            "environ_sizes_get" => {
+                // number of environment variables
+                let ptr_count = arguments[0].expect_i32().unwrap() as usize;
+                // size of environment variables buffer
+                let ptr_buf_size = arguments[1].expect_i32().unwrap() as usize;
+
+                write_u32(memory, ptr_count, self.env.len() as u32);
+
+                let buf_size: usize = self
+                    .env
+                    .iter()
+                    .map(|(name, value)| name.len() + value.len() + 2)
+                    .sum();
+                write_u32(memory, ptr_buf_size, buf_size as u32);
+
+                success_code
+            }
+            "clock_res_get" => {
+                let clock_id = arguments[0].expect_i32().unwrap() as u32;
+                let ptr_resolution = arguments[1].expect_i32().unwrap() as usize;
+
+                match self.clock_now(clock_id) {
+                    Ok(_) => {
+                        // Rust's clocks report nanoseconds, though the OS may be less precise
+                        write_u64(memory, ptr_resolution, 1);
+                        success_code
+                    }
+                    Err(errno) => error_code(errno),
+                }
+            }
+            "clock_time_get" => {
+                let clock_id = arguments[0].expect_i32().unwrap() as u32;
+                // arguments[1] is the maximum lag (precision), which we can ignore
+                let ptr_time = arguments[2].expect_i32().unwrap() as usize;
+
+                match self.clock_now(clock_id) {
+                    Ok(nanos) => {
+                        write_u64(memory, ptr_time, nanos);
+                        success_code
+                    }
+                    Err(errno) => error_code(errno),
+                }
+            }
+            "fd_advise" => {
+                // Advice is only a hint about access patterns, so we can ignore it
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                result_code(self.file(fd).map(|_| ()))
+            }
+            "fd_allocate" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let offset = arguments[1].expect_i64().unwrap() as u64;
+                let len = arguments[2].expect_i64().unwrap() as u64;
+                result_code(self.set_size(fd, offset + len, true))
+            }
+            "fd_close" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                result_code(self.close(fd))
+            }
+            "fd_datasync" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                result_code(self.sync(fd, true))
+            }
             "fd_fdstat_get" => {
+                use WasiFile::*;
+
                 // (i32, i32) -> i32
 
                 // file descriptor
@@ -104,9 +260,10 @@ impl<'a> WasiDispatcher<'a> {
                 // ptr to a wasi_fdstat_t
                 let stat_mut_ptr = arguments[1].expect_i32().unwrap() as usize;
 
-                match fd {
-                    1 => {
-                        // Tell WASI that stdout is a tty (no seek or tell)
+                let (filetype, rights_base, rights_inheriting) = match self.files.get(fd) {
+                    None | Some(Closed) => return error_code(Errno::Badf),
+                    Some(_) if fd <= 2 => {
+                        // Tell WASI that stdio is a tty (no seek or tell), even if it's in memory
                         // https://github.com/WebAssembly/wasi-libc/blob/659ff414560721b1660a19685110e484a081c3d4/libc-bottom-half/sources/isatty.c
                         // *Not* a tty if:
                         //     (statbuf.fs_filetype != __WASI_FILETYPE_CHARACTER_DEVICE ||
@@ -114,25 +271,71 @@ impl<'a> WasiDispatcher<'a> {
                         // So it's sufficient to set:
                         //     .fs_filetype = __WASI_FILETYPE_CHARACTER_DEVICE
                         //     .fs_rights_base = 0
+                        (FILETYPE_CHARACTER_DEVICE, 0, 0)
+                    }
+                    Some(HostSystemFile) => (FILETYPE_CHARACTER_DEVICE, 0, 0),
+                    Some(ReadOnly(_) | WriteOnly(_) | ReadWrite(_) | HostFile(_)) => {
+                        (FILETYPE_REGULAR_FILE, RIGHTS_ALL, 0)
+                    }
+                    Some(PreopenedDir { .. } | Dir { .. }) => {
+                        (FILETYPE_DIRECTORY, RIGHTS_ALL, RIGHTS_ALL)
+                    }
+                };
 
-                        const WASI_FILETYPE_CHARACTER_DEVICE: u8 = 2;
-                        memory[stat_mut_ptr] = WASI_FILETYPE_CHARACTER_DEVICE;
+                // fs_flags are always zero
+                memory[stat_mut_ptr..][..FDSTAT_SIZE].fill(0);
+                memory[stat_mut_ptr] = filetype;
+                write_u64(memory, stat_mut_ptr + 8, rights_base);
+                write_u64(memory, stat_mut_ptr + 16, rights_inheriting);
 
-                        for b in memory[stat_mut_ptr + 1..stat_mut_ptr + 24].iter_mut() {
-                            *b = 0;
-                        }
-                    }
-                    _ => todo!("WASI {}({:?})", function_name, arguments),
+                success_code
+            }
+            "fd_fdstat_set_flags" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let flags = arguments[1].expect_i32().unwrap();
+
+                // Switching to append or non-blocking mode after opening is not supported
+                match self.file(fd) {
+                    Ok(_) if flags == 0 => success_code,
+                    Ok(_) => error_code(Errno::Notsup),
+                    Err(errno) => error_code(errno),
                 }
+            }
+            "fd_fdstat_set_rights" => {
+                // We don't enforce rights, so there's nothing to restrict
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                result_code(self.file(fd).map(|_| ()))
+            }
+            "fd_filestat_get" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_filestat = arguments[1].expect_i32().unwrap() as usize;
+                result_code(self.fd_filestat(fd, memory, ptr_filestat))
+            }
+            "fd_filestat_set_size" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let size = arguments[1].expect_i64().unwrap() as u64;
+                result_code(self.set_size(fd, size, false))
+            }
+            "fd_filestat_set_times" | "path_filestat_set_times" => {
+                // The standard library has no portable way to set access and modification times
+                error_code(Errno::Nosys)
+            }
+            "fd_pread" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_iovs = arguments[1].expect_i32().unwrap() as usize;
+                let iovs_len = arguments[2].expect_i32().unwrap() as usize;
+                let offset = arguments[3].expect_i64().unwrap() as u64;
+                let ptr_nread = arguments[4].expect_i32().unwrap() as usize;
 
-                success_code
+                let iovs = read_iovs(memory, ptr_iovs, iovs_len);
+                match self.pread(fd, &iovs, offset, memory) {
+                    Ok(n_read) => {
+                        write_u32(memory, ptr_nread, n_read as u32);
+                        success_code
+                    }
+                    Err(errno) => error_code(errno),
+                }
             }
-            "fd_fdstat_set_flags" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_fdstat_set_rights" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_filestat_get" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_filestat_set_size" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_filestat_set_times" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_pread" => todo!("WASI {}({:?})", function_name, arguments),
             "fd_prestat_get" => {
                 // The preopened file descriptor to query
                 let fd = arguments[0].expect_i32().unwrap() as usize;
@@ -140,78 +343,124 @@ impl<'a> WasiDispatcher<'a> {
                 //  preopen type: 4 bytes, where 0=dir is the only one supported, it seems
                 //  preopen name length: 4 bytes
                 let ptr_buf = arguments[1].expect_i32().unwrap() as usize;
-                memory[ptr_buf..][..8].copy_from_slice(&0u64.to_le_bytes());
-                if fd < self.files.len() {
-                    success_code
-                } else {
-                    println!("WASI warning: file descriptor {} does not exist", fd);
-                    Some(Value::I32(Errno::Badf as i32))
+
+                // WASI libc asks about file descriptors 3, 4, 5... until it gets Badf
+                match self.files.get(fd) {
+                    Some(WasiFile::PreopenedDir { guest_path, .. }) => {
+                        write_u32(memory, ptr_buf, 0);
+                        write_u32(memory, ptr_buf + 4, guest_path.len() as u32);
+                        success_code
+                    }
+                    _ => error_code(Errno::Badf),
                 }
             }
             "fd_prestat_dir_name" => {
-                // We're not giving names to any of our files so just return success
-                success_code
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_path = arguments[1].expect_i32().unwrap() as usize;
+                let path_len = arguments[2].expect_i32().unwrap() as usize;
+
+                match self.files.get(fd) {
+                    Some(WasiFile::PreopenedDir { guest_path, .. }) => {
+                        let len = path_len.min(guest_path.len());
+                        memory[ptr_path..][..len].copy_from_slice(&guest_path.as_bytes()[..len]);
+                        success_code
+                    }
+                    _ => error_code(Errno::Badf),
+                }
             }
-            "fd_pwrite" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_read" => {
-                use WasiFile::*;
+            "fd_pwrite" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_iovs = arguments[1].expect_i32().unwrap() as usize;
+                let iovs_len = arguments[2].expect_i32().unwrap() as usize;
+                let offset = arguments[3].expect_i64().unwrap() as u64;
+                let ptr_nwritten = arguments[4].expect_i32().unwrap() as usize;
 
+                let iovs = read_iovs(memory, ptr_iovs, iovs_len);
+                match self.pwrite(fd, &iovs, offset, memory) {
+                    Ok(n_written) => {
+                        write_u32(memory, ptr_nwritten, n_written as u32);
+                        success_code
+                    }
+                    Err(errno) => error_code(errno),
+                }
+            }
+            "fd_read" => {
                 // file descriptor
                 let fd = arguments[0].expect_i32().unwrap() as usize;
                 // Array of IO vectors
                 let ptr_iovs = arguments[1].expect_i32().unwrap() as usize;
                 // Length of array
-                let iovs_len = arguments[2].expect_i32().unwrap();
+                let iovs_len = arguments[2].expect_i32().unwrap() as usize;
                 // Out param: number of bytes read
                 let ptr_nread = arguments[3].expect_i32().unwrap() as usize;
 
-                // https://man7.org/linux/man-pages/man2/readv.2.html
-                // struct iovec {
-                //     void  *iov_base;    /* Starting address */
-                //     size_t iov_len;     /* Number of bytes to transfer */
-                // };
-
-                let mut n_read: usize = 0;
-                match self.files.get(fd) {
-                    Some(ReadOnly(content) | ReadWrite(content)) => {
-                        for _ in 0..iovs_len {
-                            let iov_base = read_u32(memory, ptr_iovs) as usize;
-                            let iov_len = read_i32(memory, ptr_iovs + 4) as usize;
-                            let remaining = content.len() - n_read;
-                            let len = remaining.min(iov_len);
-                            if len == 0 {
-                                break;
-                            }
-                            memory[iov_base..][..len].copy_from_slice(&content[n_read..][..len]);
-                            n_read += len;
-                        }
+                let iovs = read_iovs(memory, ptr_iovs, iovs_len);
+                match self.read(fd, &iovs, memory) {
+                    Ok(n_read) => {
+                        write_u32(memory, ptr_nread, n_read as u32);
+                        success_code
                     }
-                    Some(HostSystemFile) if fd == 0 => {
-                        let mut stdin = io::stdin();
-                        for _ in 0..iovs_len {
-                            let iov_base = read_u32(memory, ptr_iovs) as usize;
-                            let iov_len = read_i32(memory, ptr_iovs + 4) as usize;
-                            match stdin.read(&mut memory[iov_base..][..iov_len]) {
-                                Ok(n) => {
-                                    n_read += n;
-                                }
-                                Err(_) => {
-                                    break;
-                                }
-                            }
-                        }
+                    Err(errno) => error_code(errno),
+                }
+            }
+            "fd_readdir" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_buf = arguments[1].expect_i32().unwrap() as usize;
+                let buf_len = arguments[2].expect_i32().unwrap() as usize;
+                // Where to continue from, as returned in the `d_next` field of a previous entry
+                let cookie = arguments[3].expect_i64().unwrap() as u64;
+                let ptr_bufused = arguments[4].expect_i32().unwrap() as usize;
+
+                match self.read_dir(fd, cookie, memory, ptr_buf, buf_len) {
+                    Ok(buf_used) => {
+                        write_u32(memory, ptr_bufused, buf_used as u32);
+                        success_code
                     }
-                    _ => return Some(Value::I32(Errno::Badf as i32)),
+                    Err(errno) => error_code(errno),
+                }
+            }
+            "fd_renumber" => {
+                let from = arguments[0].expect_i32().unwrap() as usize;
+                let to = arguments[1].expect_i32().unwrap() as usize;
+                result_code(self.renumber(from, to))
+            }
+            "fd_seek" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let offset = arguments[1].expect_i64().unwrap();
+                let whence = arguments[2].expect_i32().unwrap();
+                let ptr_newoffset = arguments[3].expect_i32().unwrap() as usize;
+
+                let seek_from = match whence {
+                    0 if offset >= 0 => SeekFrom::Start(offset as u64),
+                    1 => SeekFrom::Current(offset),
+                    2 => SeekFrom::End(offset),
+                    _ => return error_code(Errno::Inval),
                 };
 
-                memory[ptr_nread..][..4].copy_from_slice(&(n_read as u32).to_le_bytes());
-                success_code
+                match self.seek(fd, seek_from) {
+                    Ok(position) => {
+                        write_u64(memory, ptr_newoffset, position);
+                        success_code
+                    }
+                    Err(errno) => error_code(errno),
+                }
+            }
+            "fd_sync" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                result_code(self.sync(fd, false))
+            }
+            "fd_tell" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_offset = arguments[1].expect_i32().unwrap() as usize;
+
+                match self.seek(fd, SeekFrom::Current(0)) {
+                    Ok(position) => {
+                        write_u64(memory, ptr_offset, position);
+                        success_code
+                    }
+                    Err(errno) => error_code(errno),
+                }
             }
-            "fd_readdir" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_renumber" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_seek" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_sync" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_tell" => todo!("WASI {}({:?})", function_name, arguments),
             "fd_write" => {
                 use WasiFile::*;
 
@@ -235,6 +484,7 @@ impl<'a> WasiDispatcher<'a> {
                     Some(WriteOnly(content) | ReadWrite(content)) => {
                         WriteLock::RegularFile(content)
                     }
+                    Some(HostFile(file)) => WriteLock::HostFile(file),
                     _ => return Some(Value::I32(Errno::Badf as i32)),
                 };
 
@@ -263,6 +513,7 @@ impl<'a> WasiDispatcher<'a> {
                         WriteLock::StdOut(stdout) => stdout.write_all(bytes),
                         WriteLock::Stderr(stderr) => stderr.write_all(bytes),
                         WriteLock::RegularFile(content) => content.write_all(bytes),
+                        WriteLock::HostFile(file) => file.write_all(bytes),
                     };
                     if write_result.is_err() {
                         break;
@@ -284,23 +535,162 @@ impl<'a> WasiDispatcher<'a> {
                     Err(_) => Some(Value::I32(Errno::Io as i32)),
                 }
             }
-            "path_create_directory" => todo!("WASI {}({:?})", function_name, arguments),
-            "path_filestat_get" => todo!("WASI {}({:?})", function_name, arguments),
-            "path_filestat_set_times" => todo!("WASI {}({:?})", function_name, arguments),
-            "path_link" => todo!("WASI {}({:?})", function_name, arguments),
-            "path_open" => todo!("WASI {}({:?})", function_name, arguments),
-            "path_readlink" => todo!("WASI {}({:?})", function_name, arguments),
-            "path_remove_directory" => todo!("WASI {}({:?})", function_name, arguments),
-            "path_rename" => todo!("WASI {}({:?})", function_name, arguments),
-            "path_symlink" => todo!("WASI {}({:?})", function_name, arguments),
-            "path_unlink_file" => todo!("WASI {}({:?})", function_name, arguments),
-            "poll_oneoff" => todo!("WASI {}({:?})", function_name, arguments),
+            "path_create_directory" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_path = arguments[1].expect_i32().unwrap() as usize;
+                let path_len = arguments[2].expect_i32().unwrap() as usize;
+
+                let result = read_path(memory, ptr_path, path_len)
+                    .and_then(|path| self.resolve_path(fd, &path))
+                    .and_then(|(host_path, _)| fs::create_dir(host_path).map_err(errno_from_io));
+                result_code(result)
+            }
+            "path_filestat_get" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let lookup_flags = arguments[1].expect_i32().unwrap() as u32;
+                let ptr_path = arguments[2].expect_i32().unwrap() as usize;
+                let path_len = arguments[3].expect_i32().unwrap() as usize;
+                let ptr_filestat = arguments[4].expect_i32().unwrap() as usize;
+
+                let result = read_path(memory, ptr_path, path_len)
+                    .and_then(|path| self.resolve_path(fd, &path))
+                    .and_then(|(host_path, _)| {
+                        let metadata = if lookup_flags & LOOKUPFLAGS_SYMLINK_FOLLOW != 0 {
+                            fs::metadata(host_path)
+                        } else {
+                            fs::symlink_metadata(host_path)
+                        };
+                        metadata.map_err(errno_from_io)
+                    })
+                    .map(|metadata| write_host_filestat(memory, ptr_filestat, &metadata));
+                result_code(result)
+            }
+            "path_link" => {
+                let old_fd = arguments[0].expect_i32().unwrap() as usize;
+                // arguments[1] is the lookup flags for the old path
+                let ptr_old_path = arguments[2].expect_i32().unwrap() as usize;
+                let old_path_len = arguments[3].expect_i32().unwrap() as usize;
+                let new_fd = arguments[4].expect_i32().unwrap() as usize;
+                let ptr_new_path = arguments[5].expect_i32().unwrap() as usize;
+                let new_path_len = arguments[6].expect_i32().unwrap() as usize;
+
+                let result = self
+                    .resolve_path_pair(
+                        memory,
+                        (old_fd, ptr_old_path, old_path_len),
+                        (new_fd, ptr_new_path, new_path_len),
+                    )
+                    .and_then(|(old, new)| fs::hard_link(old, new).map_err(errno_from_io));
+                result_code(result)
+            }
+            "path_open" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                // arguments[1] is the lookup flags. We always follow symlinks, within the sandbox.
+                let ptr_path = arguments[2].expect_i32().unwrap() as usize;
+                let path_len = arguments[3].expect_i32().unwrap() as usize;
+                let oflags = arguments[4].expect_i32().unwrap() as u16;
+                let rights_base = arguments[5].expect_i64().unwrap() as u64;
+                // arguments[6] is the inheriting rights, which we don't enforce
+                let fdflags = arguments[7].expect_i32().unwrap() as u16;
+                let ptr_opened_fd = arguments[8].expect_i32().unwrap() as usize;
+
+                let result = read_path(memory, ptr_path, path_len)
+                    .and_then(|path| self.path_open(fd, &path, oflags, rights_base, fdflags));
+                match result {
+                    Ok(opened_fd) => {
+                        write_u32(memory, ptr_opened_fd, opened_fd as u32);
+                        success_code
+                    }
+                    Err(errno) => error_code(errno),
+                }
+            }
+            "path_readlink" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_path = arguments[1].expect_i32().unwrap() as usize;
+                let path_len = arguments[2].expect_i32().unwrap() as usize;
+                let ptr_buf = arguments[3].expect_i32().unwrap() as usize;
+                let buf_len = arguments[4].expect_i32().unwrap() as usize;
+                let ptr_bufused = arguments[5].expect_i32().unwrap() as usize;
+
+                let result = read_path(memory, ptr_path, path_len)
+                    .and_then(|path| self.resolve_path(fd, &path))
+                    .and_then(|(host_path, _)| fs::read_link(host_path).map_err(errno_from_io));
+                match result {
+                    Ok(target) => {
+                        // Like POSIX readlink, truncate if the buffer is too small
+                        let target = target.to_string_lossy();
+                        let len = buf_len.min(target.len());
+                        memory[ptr_buf..][..len].copy_from_slice(&target.as_bytes()[..len]);
+                        write_u32(memory, ptr_bufused, len as u32);
+                        success_code
+                    }
+                    Err(errno) => error_code(errno),
+                }
+            }
+            "path_remove_directory" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_path = arguments[1].expect_i32().unwrap() as usize;
+                let path_len = arguments[2].expect_i32().unwrap() as usize;
+
+                let result = read_path(memory, ptr_path, path_len)
+                    .and_then(|path| self.resolve_path(fd, &path))
+                    .and_then(|(host_path, _)| remove_dir(&host_path));
+                result_code(result)
+            }
+            "path_rename" => {
+                let old_fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_old_path = arguments[1].expect_i32().unwrap() as usize;
+                let old_path_len = arguments[2].expect_i32().unwrap() as usize;
+                let new_fd = arguments[3].expect_i32().unwrap() as usize;
+                let ptr_new_path = arguments[4].expect_i32().unwrap() as usize;
+                let new_path_len = arguments[5].expect_i32().unwrap() as usize;
+
+                let result = self
+                    .resolve_path_pair(
+                        memory,
+                        (old_fd, ptr_old_path, old_path_len),
+                        (new_fd, ptr_new_path, new_path_len),
+                    )
+                    .and_then(|(old, new)| fs::rename(old, new).map_err(errno_from_io));
+                result_code(result)
+            }
+            "path_symlink" => {
+                // A symlink could point outside of the sandbox, so we don't allow creating them
+                error_code(Errno::Notcapable)
+            }
+            "path_unlink_file" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_path = arguments[1].expect_i32().unwrap() as usize;
+                let path_len = arguments[2].expect_i32().unwrap() as usize;
+
+                let result = read_path(memory, ptr_path, path_len)
+                    .and_then(|path| self.resolve_path(fd, &path))
+                    .and_then(|(host_path, _)| remove_file(&host_path));
+                result_code(result)
+            }
+            "poll_oneoff" => {
+                let ptr_subscriptions = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_events = arguments[1].expect_i32().unwrap() as usize;
+                let n_subscriptions = arguments[2].expect_i32().unwrap() as usize;
+                let ptr_nevents = arguments[3].expect_i32().unwrap() as usize;
+
+                match self.poll_oneoff(memory, ptr_subscriptions, ptr_events, n_subscriptions) {
+                    Ok(n_events) => {
+                        write_u32(memory, ptr_nevents, n_events as u32);
+                        success_code
+                    }
+                    Err(errno) => error_code(errno),
+                }
+            }
             "proc_exit" => {
                 let exit_code = arguments[0].expect_i32().unwrap();
                 exit(exit_code);
             }
-            "proc_raise" => todo!("WASI {}({:?})", function_name, arguments),
-            "sched_yield" => todo!("WASI {}({:?})", function_name, arguments),
+            "proc_raise" => error_code(Errno::Nosys),
+            "sched_yield" => {
+                std::thread::yield_now();
+                success_code
+            }
             "random_get" => {
                 // A pointer to a buffer where the random bytes will be written
                 let ptr_buf = arguments[0].expect_i32().unwrap() as usize;
@@ -311,12 +701,655 @@ impl<'a> WasiDispatcher<'a> {
                 }
                 success_code
             }
-            "sock_recv" => todo!("WASI {}({:?})", function_name, arguments),
-            "sock_send" => todo!("WASI {}({:?})", function_name, arguments),
-            "sock_shutdown" => todo!("WASI {}({:?})", function_name, arguments),
+            "sock_accept" | "sock_recv" | "sock_send" | "sock_shutdown" => {
+                // There's no way to open a socket in preview1, so no file descriptor can be one
+                error_code(Errno::Notsock)
+            }
             _ => panic!("Unknown WASI function {}({:?})", function_name, arguments),
         }
     }
+
+    fn file(&self, fd: usize) -> Result<&WasiFile, Errno> {
+        match self.files.get(fd) {
+            None | Some(WasiFile::Closed) => Err(Errno::Badf),
+            Some(file) => Ok(file),
+        }
+    }
+
+    fn file_mut(&mut self, fd: usize) -> Result<&mut WasiFile, Errno> {
+        match self.files.get_mut(fd) {
+            None | Some(WasiFile::Closed) => Err(Errno::Badf),
+            Some(file) => Ok(file),
+        }
+    }
+
+    /// Put a file in the lowest free slot of the file descriptor table, like POSIX does
+    fn insert_file(&mut self, file: WasiFile) -> usize {
+        let free_slot = self
+            .files
+            .iter()
+            .skip(3)
+            .position(|f| matches!(f, WasiFile::Closed));
+
+        let fd = match free_slot {
+            Some(index) => index + 3,
+            None => {
+                self.files.push(WasiFile::Closed);
+                self.files.len() - 1
+            }
+        };
+
+        self.files[fd] = file;
+        self.cursors.remove(&fd);
+        fd
+    }
+
+    fn close(&mut self, fd: usize) -> Result<(), Errno> {
+        *self.file_mut(fd)? = WasiFile::Closed;
+        self.cursors.remove(&fd);
+        Ok(())
+    }
+
+    fn renumber(&mut self, from: usize, to: usize) -> Result<(), Errno> {
+        self.file(to)?;
+        let file = std::mem::replace(self.file_mut(from)?, WasiFile::Closed);
+        self.files[to] = file;
+
+        match self.cursors.remove(&from) {
+            Some(cursor) => self.cursors.insert(to, cursor),
+            None => self.cursors.remove(&to),
+        };
+        Ok(())
+    }
+
+    fn clock_now(&self, clock_id: u32) -> Result<u64, Errno> {
+        match clock_id {
+            CLOCK_REALTIME => Ok(nanos_since_epoch(Ok(SystemTime::now()))),
+            // We don't measure CPU time, so approximate it with the time since startup
+            CLOCK_MONOTONIC | CLOCK_PROCESS_CPUTIME_ID | CLOCK_THREAD_CPUTIME_ID => {
+                Ok(self.start_time.elapsed().as_nanos() as u64)
+            }
+            _ => Err(Errno::Inval),
+        }
+    }
+
+    fn read(
+        &mut self,
+        fd: usize,
+        iovs: &[(usize, usize)],
+        memory: &mut [u8],
+    ) -> Result<usize, Errno> {
+        use WasiFile::*;
+
+        let cursor = self.cursors.get(&fd).copied().unwrap_or(0);
+
+        let n_read = match self.file_mut(fd)? {
+            ReadOnly(content) | ReadWrite(content) => {
+                read_from_slice(content, cursor as usize, iovs, memory)
+            }
+            HostFile(file) => return read_from_host(file, iovs, memory),
+            HostSystemFile if fd == 0 => return read_from_host(&mut io::stdin(), iovs, memory),
+            _ => return Err(Errno::Badf),
+        };
+
+        self.cursors.insert(fd, cursor + n_read as u64);
+        Ok(n_read)
+    }
+
+    fn pread(
+        &mut self,
+        fd: usize,
+        iovs: &[(usize, usize)],
+        offset: u64,
+        memory: &mut [u8],
+    ) -> Result<usize, Errno> {
+        use WasiFile::*;
+
+        match self.file_mut(fd)? {
+            ReadOnly(content) | ReadWrite(content) => {
+                Ok(read_from_slice(content, offset as usize, iovs, memory))
+            }
+            HostFile(file) => at_offset(file, offset, |file| read_from_host(file, iovs, memory)),
+            HostSystemFile => Err(Errno::Spipe),
+            _ => Err(Errno::Badf),
+        }
+    }
+
+    fn pwrite(
+        &mut self,
+        fd: usize,
+        iovs: &[(usize, usize)],
+        offset: u64,
+        memory: &[u8],
+    ) -> Result<usize, Errno> {
+        use WasiFile::*;
+
+        match self.file_mut(fd)? {
+            WriteOnly(content) | ReadWrite(content) => {
+                let mut position = offset as usize;
+                for &(base, len) in iovs {
+                    if content.len() < position + len {
+                        content.resize(position + len, 0);
+                    }
+                    content[position..][..len].copy_from_slice(&memory[base..][..len]);
+                    position += len;
+                }
+                Ok(position - offset as usize)
+            }
+            HostFile(file) => at_offset(file, offset, |file| {
+                let mut n_written = 0;
+                for &(base, len) in iovs {
+                    file.write_all(&memory[base..][..len])
+                        .map_err(errno_from_io)?;
+                    n_written += len;
+                }
+                Ok(n_written)
+            }),
+            HostSystemFile => Err(Errno::Spipe),
+            _ => Err(Errno::Badf),
+        }
+    }
+
+    fn seek(&mut self, fd: usize, seek_from: SeekFrom) -> Result<u64, Errno> {
+        use WasiFile::*;
+
+        let cursor = self.cursors.get(&fd).copied().unwrap_or(0);
+
+        let new_cursor = match self.file_mut(fd)? {
+            HostFile(file) => return file.seek(seek_from).map_err(errno_from_io),
+            ReadOnly(content) | WriteOnly(content) | ReadWrite(content) => {
+                let (base, offset) = match seek_from {
+                    SeekFrom::Start(position) => (position, 0),
+                    SeekFrom::Current(offset) => (cursor, offset),
+                    SeekFrom::End(offset) => (content.len() as u64, offset),
+                };
+                (base as i64)
+                    .checked_add(offset)
+                    .filter(|position| *position >= 0)
+                    .ok_or(Errno::Inval)? as u64
+            }
+            HostSystemFile => return Err(Errno::Spipe),
+            _ => return Err(Errno::Badf),
+        };
+
+        self.cursors.insert(fd, new_cursor);
+        Ok(new_cursor)
+    }
+
+    fn sync(&mut self, fd: usize, data_only: bool) -> Result<(), Errno> {
+        match self.file_mut(fd)? {
+            WasiFile::HostFile(file) if data_only => file.sync_data().map_err(errno_from_io),
+            WasiFile::HostFile(file) => file.sync_all().map_err(errno_from_io),
+            // Everything else is either in memory or not buffered by us
+            _ => Ok(()),
+        }
+    }
+
+    /// Resize a file. If `only_grow` is set, a file that is already big enough is left alone.
+    fn set_size(&mut self, fd: usize, size: u64, only_grow: bool) -> Result<(), Errno> {
+        use WasiFile::*;
+
+        match self.file_mut(fd)? {
+            HostFile(file) => {
+                let current_size = file.metadata().map_err(errno_from_io)?.len();
+                if !only_grow || size > current_size {
+                    file.set_len(size).map_err(errno_from_io)?;
+                }
+                Ok(())
+            }
+            WriteOnly(content) | ReadWrite(content) => {
+                if !only_grow || size as usize > content.len() {
+                    content.resize(size as usize, 0);
+                }
+                Ok(())
+            }
+            ReadOnly(_) => Err(Errno::Badf),
+            _ => Err(Errno::Inval),
+        }
+    }
+
+    fn fd_filestat(&self, fd: usize, memory: &mut [u8], ptr_filestat: usize) -> Result<(), Errno> {
+        use WasiFile::*;
+
+        match self.file(fd)? {
+            ReadOnly(content) | WriteOnly(content) | ReadWrite(content) => {
+                write_filestat(
+                    memory,
+                    ptr_filestat,
+                    FILETYPE_REGULAR_FILE,
+                    content.len() as u64,
+                    None,
+                );
+            }
+            HostSystemFile => {
+                write_filestat(memory, ptr_filestat, FILETYPE_CHARACTER_DEVICE, 0, None);
+            }
+            HostFile(file) => {
+                let metadata = file.metadata().map_err(errno_from_io)?;
+                write_host_filestat(memory, ptr_filestat, &metadata);
+            }
+            PreopenedDir { host_path, .. } | Dir { host_path, .. } => {
+                let metadata = fs::metadata(host_path).map_err(errno_from_io)?;
+                write_host_filestat(memory, ptr_filestat, &metadata);
+            }
+            Closed => return Err(Errno::Badf),
+        }
+
+        Ok(())
+    }
+
+    /// Find the host path of `path`, relative to the directory `dir_fd`.
+    /// Also returns the sandbox root, the host path of the preopened directory that `dir_fd` was
+    /// reached through. Paths that would escape it are rejected.
+    fn resolve_path(&self, dir_fd: usize, path: &str) -> Result<(PathBuf, PathBuf), Errno> {
+        let (dir_path, sandbox_root) = match self.file(dir_fd)? {
+            WasiFile::PreopenedDir { host_path, .. } => (host_path, host_path),
+            WasiFile::Dir {
+                host_path,
+                sandbox_root,
+            } => (host_path, sandbox_root),
+            _ => return Err(Errno::Notdir),
+        };
+
+        // How many levels below the sandbox root we are, i.e. how far up `..` can go
+        let mut depth = dir_path
+            .strip_prefix(sandbox_root)
+            .map(|relative| relative.components().count())
+            .unwrap_or(0);
+        let mut resolved = dir_path.clone();
+
+        for component in Path::new(path).components() {
+            match component {
+                Component::Normal(name) => {
+                    resolved.push(name);
+                    depth += 1;
+                }
+                Component::CurDir => {}
+                Component::ParentDir if depth > 0 => {
+                    resolved.pop();
+                    depth -= 1;
+                }
+                Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                    return Err(Errno::Notcapable);
+                }
+            }
+        }
+
+        // The path can still escape through a symlink
+        if !is_inside(&resolved, sandbox_root) {
+            return Err(Errno::Notcapable);
+        }
+
+        Ok((resolved, sandbox_root.clone()))
+    }
+
+    /// Read and resolve the two paths of a `path_link` or `path_rename`
+    fn resolve_path_pair(
+        &self,
+        memory: &[u8],
+        (old_fd, ptr_old_path, old_path_len): (usize, usize, usize),
+        (new_fd, ptr_new_path, new_path_len): (usize, usize, usize),
+    ) -> Result<(PathBuf, PathBuf), Errno> {
+        let old_path = read_path(memory, ptr_old_path, old_path_len)?;
+        let new_path = read_path(memory, ptr_new_path, new_path_len)?;
+        let (old_host_path, _) = self.resolve_path(old_fd, &old_path)?;
+        let (new_host_path, _) = self.resolve_path(new_fd, &new_path)?;
+        Ok((old_host_path, new_host_path))
+    }
+
+    fn path_open(
+        &mut self,
+        dir_fd: usize,
+        path: &str,
+        oflags: u16,
+        rights: u64,
+        fdflags: u16,
+    ) -> Result<usize, Errno> {
+        let (host_path, sandbox_root) = self.resolve_path(dir_fd, path)?;
+
+        if oflags & OFLAGS_DIRECTORY != 0 || (oflags & OFLAGS_CREAT == 0 && host_path.is_dir()) {
+            return if host_path.is_dir() {
+                Ok(self.insert_file(WasiFile::Dir {
+                    host_path,
+                    sandbox_root,
+                }))
+            } else if host_path.exists() {
+                Err(Errno::Notdir)
+            } else {
+                Err(Errno::Noent)
+            };
+        }
+
+        let append = fdflags & FDFLAGS_APPEND != 0;
+        let write = append || rights & RIGHTS_FD_WRITE != 0;
+        let file = OpenOptions::new()
+            .read(!write || rights & RIGHTS_FD_READ != 0)
+            .write(write)
+            .append(append)
+            .create(oflags & OFLAGS_CREAT != 0)
+            .create_new(oflags & OFLAGS_CREAT != 0 && oflags & OFLAGS_EXCL != 0)
+            .truncate(oflags & OFLAGS_TRUNC != 0)
+            .open(&host_path)
+            .map_err(errno_from_io)?;
+
+        Ok(self.insert_file(WasiFile::HostFile(file)))
+    }
+
+    fn read_dir(
+        &self,
+        fd: usize,
+        cookie: u64,
+        memory: &mut [u8],
+        ptr_buf: usize,
+        buf_len: usize,
+    ) -> Result<usize, Errno> {
+        let host_path = match self.file(fd)? {
+            WasiFile::PreopenedDir { host_path, .. } | WasiFile::Dir { host_path, .. } => host_path,
+            _ => return Err(Errno::Notdir),
+        };
+
+        let mut children = fs::read_dir(host_path)
+            .map_err(errno_from_io)?
+            .map(|entry| {
+                let entry = entry.map_err(errno_from_io)?;
+                let filetype = entry.file_type().map_or(FILETYPE_UNKNOWN, filetype_of);
+                let name = entry.file_name().to_string_lossy().into_owned();
+                Ok((name.into_bytes(), filetype))
+            })
+            .collect::<Result<Vec<_>, Errno>>()?;
+
+        // Cookies are indices into this list, so the order must be the same on every call
+        children.sort();
+
+        let entries = [
+            (b".".to_vec(), FILETYPE_DIRECTORY),
+            (b"..".to_vec(), FILETYPE_DIRECTORY),
+        ]
+        .into_iter()
+        .chain(children);
+
+        let mut buf_used = 0;
+        for (index, (name, filetype)) in entries.enumerate().skip(cookie as usize) {
+            // struct dirent { u64 d_next; u64 d_ino; u32 d_namlen; u8 d_type; }
+            let mut dirent = [0; DIRENT_SIZE];
+            dirent[0..8].copy_from_slice(&(index as u64 + 1).to_le_bytes());
+            dirent[16..20].copy_from_slice(&(name.len() as u32).to_le_bytes());
+            dirent[20] = filetype;
+
+            // If the last entry doesn't fit, write as much of it as we can. WASI libc notices that
+            // the buffer is full, and tries again with a bigger buffer if it needs to.
+            for bytes in [&dirent[..], &name[..]] {
+                let len = bytes.len().min(buf_len - buf_used);
+                memory[ptr_buf + buf_used..][..len].copy_from_slice(&bytes[..len]);
+                buf_used += len;
+            }
+
+            if buf_used == buf_len {
+                break;
+            }
+        }
+
+        Ok(buf_used)
+    }
+
+    fn poll_oneoff(
+        &self,
+        memory: &mut [u8],
+        ptr_subscriptions: usize,
+        ptr_events: usize,
+        n_subscriptions: usize,
+    ) -> Result<usize, Errno> {
+        if n_subscriptions == 0 {
+            return Err(Errno::Inval);
+        }
+
+        // struct subscription { u64 userdata; u8 tag; union { clock, fd_read, fd_write } u; }
+        // For clocks, we need to know how long until they expire. Other subscriptions wait for a
+        // file descriptor to be ready, which in this implementation they always are.
+        let mut clock_timeouts = Vec::with_capacity(n_subscriptions);
+        for i in 0..n_subscriptions {
+            let ptr_subscription = ptr_subscriptions + i * SUBSCRIPTION_SIZE;
+            let timeout = if memory[ptr_subscription + 8] == EVENTTYPE_CLOCK {
+                let clock_id = read_u32(memory, ptr_subscription + 16);
+                let timeout = read_u64(memory, ptr_subscription + 24);
+                let flags = read_u16(memory, ptr_subscription + 40);
+                if flags & SUBCLOCKFLAGS_ABSTIME != 0 {
+                    Some(timeout.saturating_sub(self.clock_now(clock_id)?))
+                } else {
+                    Some(timeout)
+                }
+            } else {
+                None
+            };
+            clock_timeouts.push(timeout);
+        }
+
+        let wait_time = if clock_timeouts.iter().any(Option::is_none) {
+            0
+        } else {
+            let wait_time = clock_timeouts.iter().flatten().copied().min().unwrap_or(0);
+            std::thread::sleep(Duration::from_nanos(wait_time));
+            wait_time
+        };
+
+        // struct event { u64 userdata; u16 error; u8 type; fd_readwrite; }
+        let mut n_events = 0;
+        for (i, timeout) in clock_timeouts.into_iter().enumerate() {
+            if timeout.map_or(true, |timeout| timeout <= wait_time) {
+                let ptr_subscription = ptr_subscriptions + i * SUBSCRIPTION_SIZE;
+                let ptr_event = ptr_events + n_events * EVENT_SIZE;
+                let userdata = read_u64(memory, ptr_subscription);
+                let event_type = memory[ptr_subscription + 8];
+
+                memory[ptr_event..][..EVENT_SIZE].fill(0);
+                write_u64(memory, ptr_event, userdata);
+                memory[ptr_event + 10] = event_type;
+                n_events += 1;
+            }
+        }
+
+        Ok(n_events)
+    }
+}
+
+fn error_code(errno: Errno) -> Option<Value> {
+    Some(Value::I32(errno as i32))
+}
+
+fn result_code(result: Result<(), Errno>) -> Option<Value> {
+    error_code(result.err().unwrap_or(Errno::Success))
+}
+
+fn errno_from_io(error: io::Error) -> Errno {
+    use io::ErrorKind::*;
+
+    match error.kind() {
+        NotFound => Errno::Noent,
+        PermissionDenied => Errno::Access,
+        AlreadyExists => Errno::Exist,
+        InvalidInput => Errno::Inval,
+        WouldBlock => Errno::Again,
+        Interrupted => Errno::Intr,
+        Unsupported => Errno::Notsup,
+        _ => Errno::Io,
+    }
+}
+
+/// Read an array of `iovec` structs, as (address, length) pairs
+fn read_iovs(memory: &[u8], ptr_iovs: usize, iovs_len: usize) -> Vec<(usize, usize)> {
+    (0..iovs_len)
+        .map(|i| {
+            let ptr_iov = ptr_iovs + 8 * i;
+            let iov_base = read_u32(memory, ptr_iov) as usize;
+            let iov_len = read_u32(memory, ptr_iov + 4) as usize;
+            (iov_base, iov_len)
+        })
+        .collect()
+}
+
+fn read_from_slice(
+    content: &[u8],
+    offset: usize,
+    iovs: &[(usize, usize)],
+    memory: &mut [u8],
+) -> usize {
+    let start = offset.min(content.len());
+    let mut position = start;
+    for &(base, len) in iovs {
+        let n = len.min(content.len() - position);
+        memory[base..][..n].copy_from_slice(&content[position..][..n]);
+        position += n;
+        if n < len {
+            break;
+        }
+    }
+    position - start
+}
+
+fn read_from_host(
+    reader: &mut impl Read,
+    iovs: &[(usize, usize)],
+    memory: &mut [u8],
+) -> Result<usize, Errno> {
+    let mut n_read = 0;
+    for &(base, len) in iovs {
+        let n = reader
+            .read(&mut memory[base..][..len])
+            .map_err(errno_from_io)?;
+        n_read += n;
+        // Don't block waiting for more input than is available right now
+        if n < len {
+            break;
+        }
+    }
+    Ok(n_read)
+}
+
+/// Do positional I/O on a file, without moving its cursor
+fn at_offset<T>(
+    file: &mut File,
+    offset: u64,
+    f: impl FnOnce(&mut File) -> Result<T, Errno>,
+) -> Result<T, Errno> {
+    let saved_position = file.stream_position().map_err(errno_from_io)?;
+    file.seek(SeekFrom::Start(offset)).map_err(errno_from_io)?;
+    let result = f(file);
+    file.seek(SeekFrom::Start(saved_position))
+        .map_err(errno_from_io)?;
+    result
+}
+
+fn read_path(memory: &[u8], ptr_path: usize, path_len: usize) -> Result<String, Errno> {
+    let bytes = &memory[ptr_path..][..path_len];
+    String::from_utf8(bytes.to_vec()).map_err(|_| Errno::Ilseq)
+}
+
+/// Whether `path`, with any symlinks resolved, is inside `root`.
+/// Only the part of `path` that exists is checked, since the rest can't contain symlinks.
+fn is_inside(path: &Path, root: &Path) -> bool {
+    let root = match root.canonicalize() {
+        Ok(root) => root,
+        Err(_) => return false,
+    };
+
+    // A dangling symlink can't be canonicalized, but following it (e.g. to create a file) could
+    // still end up outside the root
+    let is_symlink = fs::symlink_metadata(path).map_or(false, |m| m.file_type().is_symlink());
+    if is_symlink && path.canonicalize().is_err() {
+        return false;
+    }
+
+    path.ancestors()
+        .find_map(|ancestor| ancestor.canonicalize().ok())
+        .map_or(false, |existing| existing.starts_with(root))
+}
+
+fn remove_file(host_path: &Path) -> Result<(), Errno> {
+    let metadata = fs::symlink_metadata(host_path).map_err(errno_from_io)?;
+    if metadata.is_dir() {
+        return Err(Errno::Isdir);
+    }
+    fs::remove_file(host_path).map_err(errno_from_io)
+}
+
+fn remove_dir(host_path: &Path) -> Result<(), Errno> {
+    let metadata = fs::symlink_metadata(host_path).map_err(errno_from_io)?;
+    if !metadata.is_dir() {
+        return Err(Errno::Notdir);
+    }
+    let mut entries = fs::read_dir(host_path).map_err(errno_from_io)?;
+    if entries.next().is_some() {
+        return Err(Errno::Notempty);
+    }
+    fs::remove_dir(host_path).map_err(errno_from_io)
+}
+
+fn filetype_of(file_type: FileType) -> u8 {
+    if file_type.is_dir() {
+        FILETYPE_DIRECTORY
+    } else if file_type.is_file() {
+        FILETYPE_REGULAR_FILE
+    } else if file_type.is_symlink() {
+        FILETYPE_SYMBOLIC_LINK
+    } else {
+        FILETYPE_UNKNOWN
+    }
+}
+
+fn nanos_since_epoch(time: io::Result<SystemTime>) -> u64 {
+    time.ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |duration| duration.as_nanos() as u64)
+}
+
+fn write_host_filestat(memory: &mut [u8], ptr_filestat: usize, metadata: &Metadata) {
+    let filetype = filetype_of(metadata.file_type());
+    write_filestat(
+        memory,
+        ptr_filestat,
+        filetype,
+        metadata.len(),
+        Some(metadata),
+    );
+}
+
+/// struct filestat { u64 dev; u64 ino; u8 filetype; u64 nlink; u64 size; u64 atim; u64 mtim; u64 ctim; }
+fn write_filestat(
+    memory: &mut [u8],
+    ptr_filestat: usize,
+    filetype: u8,
+    size: u64,
+    metadata: Option<&Metadata>,
+) {
+    // Device and inode numbers aren't portable, so leave them as zero
+    memory[ptr_filestat..][..FILESTAT_SIZE].fill(0);
+    memory[ptr_filestat + 16] = filetype;
+    write_u64(memory, ptr_filestat + 24, 1);
+    write_u64(memory, ptr_filestat + 32, size);
+
+    if let Some(metadata) = metadata {
+        write_u64(
+            memory,
+            ptr_filestat + 40,
+            nanos_since_epoch(metadata.accessed()),
+        );
+        write_u64(
+            memory,
+            ptr_filestat + 48,
+            nanos_since_epoch(metadata.modified()),
+        );
+        write_u64(
+            memory,
+            ptr_filestat + 56,
+            nanos_since_epoch(metadata.created()),
+        );
+    }
+}
+
+fn read_u16(memory: &[u8], addr: usize) -> u16 {
+    let mut bytes = [0; 2];
+    bytes.copy_from_slice(&memory[addr..][..2]);
+    u16::from_le_bytes(bytes)
 }
 
 fn read_u32(memory: &[u8], addr: usize) -> u32 {
@@ -331,6 +1364,12 @@ fn read_i32(memory: &[u8], addr: usize) -> i32 {
     i32::from_le_bytes(bytes)
 }
 
+fn read_u64(memory: &[u8], addr: usize) -> u64 {
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&memory[addr..][..8]);
+    u64::from_le_bytes(bytes)
+}
+
 fn write_u32(memory: &mut [u8], addr: usize, value: u32) {
     memory[addr..][..4].copy_from_slice(&value.to_le_bytes());
 }
@@ -339,6 +1378,10 @@ fn write_i32(memory: &mut [u8], addr: usize, value: i32) {
     memory[addr..][..4].copy_from_slice(&value.to_le_bytes());
 }
 
+fn write_u64(memory: &mut [u8], addr: usize, value: u64) {
+    memory[addr..][..8].copy_from_slice(&value.to_le_bytes());
+}
+
 /// Error codes returned by functions.
 /// Not all of these error codes are returned by the functions provided by this
 /// API; some are used in higher-level library layers, and others are provided